@@ -29,6 +29,16 @@ pub struct ValidatedClaims {
     pub mfa_verified: bool,
     /// When the token expires.
     pub expires_at: DateTime<Utc>,
+    /// Roles granted within the namespace.
+    pub roles: Vec<String>,
+}
+
+impl ValidatedClaims {
+    /// Returns true if the token grants the given role.
+    #[must_use]
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
 }
 
 /// Trait for validating JWTs.
@@ -60,6 +70,9 @@ struct RawClaims {
     /// Audience (can be string or array)
     #[serde(default)]
     aud: Audience,
+    /// Roles granted within the namespace
+    #[serde(default)]
+    roles: Vec<String>,
     /// Expiration timestamp
     exp: u64,
     /// Issued at timestamp (validated by jsonwebtoken)
@@ -170,6 +183,7 @@ impl JwtValidator for JwksValidator {
             session_id,
             mfa_verified: claims.mfa_verified,
             expires_at,
+            roles: claims.roles,
         })
     }
 }
@@ -177,7 +191,7 @@ impl JwtValidator for JwksValidator {
 /// A mock JWT validator for testing.
 ///
 /// This validator accepts any token in the format `test-token:<identity_uuid>:<namespace_uuid>`
-/// and extracts the IDs from it.
+/// and extracts the IDs from it. An optional trailing `:<role>,<role>` segment grants roles.
 #[cfg(any(test, feature = "test-utils"))]
 pub struct MockJwtValidator {
     /// Whether MFA is verified for all validated tokens.
@@ -197,13 +211,13 @@ impl Default for MockJwtValidator {
 #[async_trait]
 impl JwtValidator for MockJwtValidator {
     async fn validate(&self, token: &str) -> Result<ValidatedClaims> {
-        // Expected format: test-token:<identity_uuid>:<namespace_uuid>
+        // Expected format: test-token:<identity_uuid>:<namespace_uuid>[:<roles>]
         let rest = token.strip_prefix("test-token:").ok_or_else(|| {
             AuthError::InvalidToken("expected test-token:<identity>:<namespace>".to_string())
         })?;
 
         let parts: Vec<&str> = rest.split(':').collect();
-        if parts.len() != 2 {
+        if !(2..=3).contains(&parts.len()) {
             return Err(AuthError::InvalidToken(
                 "expected test-token:<identity>:<namespace>".to_string(),
             ));
@@ -214,6 +228,16 @@ impl JwtValidator for MockJwtValidator {
         let namespace_id =
             NamespaceId::from_str(parts[1]).map_err(|_| AuthError::InvalidNamespaceId)?;
         let session_id = SessionId::generate();
+        let roles = parts
+            .get(2)
+            .map(|roles| {
+                roles
+                    .split(',')
+                    .filter(|r| !r.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
 
         Ok(ValidatedClaims {
             identity_id,
@@ -221,6 +245,7 @@ impl JwtValidator for MockJwtValidator {
             session_id,
             mfa_verified: self.mfa_verified,
             expires_at: Utc::now() + chrono::Duration::hours(1),
+            roles,
         })
     }
 }
@@ -253,6 +278,22 @@ mod tests {
         assert!(claims.mfa_verified);
     }
 
+    #[tokio::test]
+    async fn mock_validator_with_roles() {
+        let validator = MockJwtValidator::default();
+        let identity_uuid = "550e8400-e29b-41d4-a716-446655440000";
+        let namespace_uuid = "6ba7b810-9dad-11d1-80b4-00c04fd430c8";
+
+        let token = format!("test-token:{identity_uuid}:{namespace_uuid}:namespace_admin,ops");
+        let claims = validator.validate(&token).await.unwrap();
+        assert!(claims.has_role("namespace_admin"));
+        assert!(claims.has_role("ops"));
+
+        let token = format!("test-token:{identity_uuid}:{namespace_uuid}");
+        let claims = validator.validate(&token).await.unwrap();
+        assert!(claims.roles.is_empty());
+    }
+
     #[tokio::test]
     async fn mock_validator_rejects_invalid() {
         let validator = MockJwtValidator::default();
@@ -0,0 +1,174 @@
+//! Role-based access to agents.
+//!
+//! Every request against an agent is checked against the role the caller
+//! holds on it:
+//!
+//! | Role       | Allows                                                   |
+//! |------------|----------------------------------------------------------|
+//! | `viewer`   | read status, schedules and sessions; watch sessions      |
+//! | `operator` | the above, plus open sessions and start/stop/hibernate   |
+//! | `owner`    | the above, plus delete the agent and manage its access   |
+//!
+//! The agent's creator and the admins of its namespace are implicit owners.
+//! Everyone else needs an explicit [`AgentGrant`]. Grants only apply where the
+//! agent is visible to the caller (see [`crate::namespace`]), so sharing never
+//! crosses tenant boundaries.
+
+use aura_swarm_store::{Agent, AgentGrant, AgentRole, Store};
+
+use crate::error::{ControlError, Result};
+use crate::namespace;
+use crate::types::Caller;
+
+/// The role a caller holds on an agent, if any.
+///
+/// `grant` is the caller's stored grant on the agent, if one exists.
+#[must_use]
+pub fn effective_role(
+    caller: &Caller,
+    agent: &Agent,
+    grant: Option<&AgentGrant>,
+) -> Option<AgentRole> {
+    if !namespace::in_scope(caller, agent) {
+        return None;
+    }
+
+    let administers =
+        caller.is_namespace_admin() && agent.namespace_id == Some(caller.namespace_id);
+    if agent.user_id == caller.user_id || administers {
+        return Some(AgentRole::Owner);
+    }
+
+    grant
+        .filter(|grant| grant.user_id == caller.user_id && grant.agent_id == agent.agent_id)
+        .map(|grant| grant.role)
+}
+
+/// Check that the caller holds at least `required` on an agent.
+///
+/// Returns the caller's effective role.
+///
+/// # Errors
+///
+/// Returns:
+/// - `AgentNotFound` if the agent belongs to another namespace
+/// - `NotOwner` if the caller has no access to the agent
+/// - `InsufficientRole` if the caller's role is below `required`
+pub fn authorize<S: Store>(
+    store: &S,
+    caller: &Caller,
+    agent: &Agent,
+    required: AgentRole,
+) -> Result<AgentRole> {
+    if !namespace::in_scope(caller, agent) {
+        return Err(ControlError::AgentNotFound(agent.agent_id));
+    }
+
+    // Owners never need a grant lookup
+    let mut role = effective_role(caller, agent, None);
+    if role.is_none() {
+        let grant = store.get_agent_grant(&agent.agent_id, &caller.user_id)?;
+        role = effective_role(caller, agent, grant.as_ref());
+    }
+
+    match role {
+        Some(role) if role >= required => Ok(role),
+        Some(_) => Err(ControlError::InsufficientRole {
+            user_id: caller.user_id,
+            agent_id: agent.agent_id,
+            required,
+        }),
+        None => Err(ControlError::NotOwner {
+            user_id: caller.user_id,
+            agent_id: agent.agent_id,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::{AgentId, NamespaceId, UserId};
+    use aura_swarm_store::{AgentSpec, AgentState, RestartPolicy, RestartState};
+    use chrono::Utc;
+
+    fn namespace(byte: u8) -> NamespaceId {
+        NamespaceId::from_uuid(uuid::Uuid::from_bytes([byte; 16]))
+    }
+
+    fn agent(owner: u8, namespace_id: Option<NamespaceId>) -> Agent {
+        Agent {
+            agent_id: AgentId::from_bytes([9u8; 32]),
+            user_id: UserId::from_bytes([owner; 32]),
+            namespace_id,
+            name: "agent".to_string(),
+            status: AgentState::Running,
+            spec: AgentSpec::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_heartbeat_at: None,
+            error_message: None,
+            cloned_from: None,
+            template: None,
+            labels: std::collections::BTreeMap::new(),
+            restart_policy: RestartPolicy::Never,
+            restarts: RestartState::default(),
+            hibernation: None,
+            provisioning_started_at: None,
+        }
+    }
+
+    fn grant(agent: &Agent, user: u8, role: AgentRole) -> AgentGrant {
+        AgentGrant {
+            agent_id: agent.agent_id,
+            user_id: UserId::from_bytes([user; 32]),
+            role,
+            granted_by: agent.user_id,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn owners_and_namespace_admins_are_implicit_owners() {
+        let agent = agent(1, Some(namespace(1)));
+        let owner = Caller::member(UserId::from_bytes([1u8; 32]), namespace(1));
+        let admin = Caller::namespace_admin(UserId::from_bytes([2u8; 32]), namespace(1));
+        let member = Caller::member(UserId::from_bytes([3u8; 32]), namespace(1));
+
+        assert_eq!(effective_role(&owner, &agent, None), Some(AgentRole::Owner));
+        assert_eq!(effective_role(&admin, &agent, None), Some(AgentRole::Owner));
+        assert_eq!(effective_role(&member, &agent, None), None);
+
+        // Unscoped agents belong to no tenant an admin could manage
+        let unscoped = self::agent(1, None);
+        assert_eq!(effective_role(&admin, &unscoped, None), None);
+    }
+
+    #[test]
+    fn grants_apply_only_to_their_holder_and_namespace() {
+        let agent = agent(1, Some(namespace(1)));
+        let viewer = grant(&agent, 3, AgentRole::Viewer);
+        let colleague = Caller::member(UserId::from_bytes([3u8; 32]), namespace(1));
+        let stranger = Caller::member(UserId::from_bytes([4u8; 32]), namespace(1));
+        let elsewhere = Caller::member(UserId::from_bytes([3u8; 32]), namespace(2));
+
+        assert_eq!(
+            effective_role(&colleague, &agent, Some(&viewer)),
+            Some(AgentRole::Viewer)
+        );
+        assert_eq!(effective_role(&stranger, &agent, Some(&viewer)), None);
+        assert_eq!(effective_role(&elsewhere, &agent, Some(&viewer)), None);
+
+        // Unscoped agents can't be shared
+        let unscoped = self::agent(1, None);
+        let viewer = grant(&unscoped, 3, AgentRole::Viewer);
+        assert_eq!(effective_role(&colleague, &unscoped, Some(&viewer)), None);
+    }
+
+    #[test]
+    fn roles_are_ordered() {
+        assert!(AgentRole::Viewer < AgentRole::Operator);
+        assert!(AgentRole::Operator < AgentRole::Owner);
+    }
+}
@@ -0,0 +1,186 @@
+//! Audit logging.
+//!
+//! Every mutating API call and authentication failure is recorded as an
+//! [`AuditEvent`] by the gateway. The control plane records the actions it
+//! takes on its own (scheduled runs, automatic restarts, expired sessions,
+//! drift corrections) with a [system](AuditActor::system) actor.
+//!
+//! Events are written to an append-only column family and pruned once they
+//! are older than the configured retention period.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use aura_swarm_core::{AgentId, AuditEventId, SessionId};
+use aura_swarm_store::{AuditActor, AuditEvent, AuditOutcome, AuditResource, Store};
+use chrono::Utc;
+
+/// Sink for audit events.
+///
+/// Logging never fails from the caller's point of view; implementations
+/// report their own errors.
+pub trait AuditLogger: Send + Sync {
+    /// Record an audit event.
+    fn log(&self, event: AuditEvent);
+}
+
+/// Audit logger that only emits `tracing` events on the `audit` target.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TracingAuditLogger;
+
+impl AuditLogger for TracingAuditLogger {
+    fn log(&self, event: AuditEvent) {
+        trace_event(&event);
+    }
+}
+
+/// Audit logger that persists events to the store and traces them.
+pub struct StoreAuditLogger<S: Store> {
+    store: Arc<S>,
+}
+
+impl<S: Store> StoreAuditLogger<S> {
+    /// Create a logger writing to `store`.
+    #[must_use]
+    pub const fn new(store: Arc<S>) -> Self {
+        Self { store }
+    }
+}
+
+impl<S: Store> AuditLogger for StoreAuditLogger<S> {
+    fn log(&self, event: AuditEvent) {
+        trace_event(&event);
+        if let Err(e) = self.store.append_audit_event(&event) {
+            tracing::error!(
+                event_id = %event.event_id,
+                event_type = %event.event_type,
+                error = %e,
+                "Failed to persist audit event"
+            );
+        }
+    }
+}
+
+fn trace_event(event: &AuditEvent) {
+    tracing::info!(
+        target: "audit",
+        event_id = %event.event_id,
+        event_type = %event.event_type,
+        actor.user_id = ?event.actor.user_id,
+        actor.ip_address = ?event.actor.ip_address,
+        namespace_id = ?event.namespace_id,
+        resource.type = ?event.resource.as_ref().map(|r| &r.resource_type),
+        resource.id = ?event.resource.as_ref().map(|r| &r.id),
+        action = %event.action,
+        outcome = ?event.outcome,
+        request_id = ?event.request_id,
+        "audit"
+    );
+}
+
+/// Build an event for an action the control plane took on its own.
+#[must_use]
+pub fn system_event(
+    event_type: &str,
+    action: &str,
+    resource: AuditResource,
+    outcome: AuditOutcome,
+) -> AuditEvent {
+    AuditEvent {
+        event_id: AuditEventId::generate(),
+        timestamp: Utc::now(),
+        event_type: event_type.to_string(),
+        actor: AuditActor::system(),
+        namespace_id: None,
+        resource: Some(resource),
+        action: action.to_string(),
+        outcome,
+        request_id: None,
+        details: BTreeMap::new(),
+    }
+}
+
+/// The audit resource for an agent.
+#[must_use]
+pub fn agent_resource(agent_id: &AgentId) -> AuditResource {
+    AuditResource {
+        resource_type: "agent".to_string(),
+        id: agent_id.to_string(),
+    }
+}
+
+/// The audit resource for a session.
+#[must_use]
+pub fn session_resource(session_id: &SessionId) -> AuditResource {
+    AuditResource {
+        resource_type: "session".to_string(),
+        id: session_id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::UserId;
+    use aura_swarm_store::{AuditQuery, RocksStore};
+    use tempfile::TempDir;
+
+    fn agent_id() -> AgentId {
+        AgentId::generate(&UserId::from_bytes([1u8; 32]), "agent")
+    }
+
+    #[test]
+    fn system_events_have_a_system_actor() {
+        let agent_id = agent_id();
+        let event = system_event(
+            "agent.auto_restart",
+            "restart",
+            agent_resource(&agent_id),
+            AuditOutcome::Success,
+        );
+
+        assert!(event.actor.is_system());
+        assert_eq!(event.namespace_id, None);
+        assert_eq!(event.request_id, None);
+        assert_eq!(event.event_type, "agent.auto_restart");
+        assert_eq!(event.action, "restart");
+        let resource = event.resource.unwrap();
+        assert_eq!(resource.resource_type, "agent");
+        assert_eq!(resource.id, agent_id.to_string());
+
+        let session_id = SessionId::generate();
+        let resource = session_resource(&session_id);
+        assert_eq!(resource.resource_type, "session");
+        assert_eq!(resource.id, session_id.to_string());
+    }
+
+    #[test]
+    fn store_logger_persists_events() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let logger = StoreAuditLogger::new(Arc::clone(&store));
+
+        let session_id = SessionId::generate();
+        let first = system_event(
+            "session.expired",
+            "close",
+            session_resource(&session_id),
+            AuditOutcome::Success,
+        );
+        let second = AuditEvent {
+            timestamp: first.timestamp + chrono::Duration::seconds(1),
+            ..system_event(
+                "agent.drift_corrected",
+                "reschedule",
+                agent_resource(&agent_id()),
+                AuditOutcome::Failure,
+            )
+        };
+        logger.log(first.clone());
+        logger.log(second.clone());
+
+        let events = store.list_audit_events(&AuditQuery::default()).unwrap();
+        let ids: Vec<_> = events.iter().map(|event| event.event_id).collect();
+        assert_eq!(ids, vec![second.event_id, first.event_id]);
+    }
+}
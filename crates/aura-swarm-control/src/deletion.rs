@@ -0,0 +1,152 @@
+//! Resumable agent deletion.
+//!
+//! Deleting an agent is tracked as an [`AgentDeletion`] job rather than done
+//! in a single call. The job runs its [`DeletionStep`]s in order and saves
+//! its progress after each one:
+//!
+//! 1. Purge the agent's sessions.
+//! 2. Terminate its pod.
+//! 3. Have the scheduler remove its state volume (or, on the shared volume,
+//!    run a cleanup job that removes its state directory) and evict its
+//!    cached endpoint.
+//! 4. Delete its Kubernetes Secret.
+//! 5. Delete its Kubernetes `NetworkPolicy`.
+//! 6. Drop the agent record, with its schedule, grants, and stored secrets.
+//!
+//! If a step fails, the job is marked failed with the step and the error,
+//! and the control plane's deletion worker resumes it from that step once
+//! the retry delay has passed. Deleting the agent again resumes it at once.
+//! Until the job completes, the agent record stays in place and the agent
+//! cannot be started.
+
+use aura_swarm_core::UserId;
+use aura_swarm_store::{Agent, AgentDeletion, DeletionStatus};
+use chrono::{DateTime, Duration, Utc};
+
+use crate::types::Caller;
+
+/// How long a running deletion is leased before the worker may resume it.
+///
+/// This is longer than the scheduler waits for the state cleanup job, so the
+/// worker only picks up a running deletion if the process running it died.
+#[must_use]
+pub fn lease() -> Duration {
+    Duration::minutes(30)
+}
+
+/// Start a deletion job for an agent.
+#[must_use]
+pub fn new_deletion(agent: &Agent, requested_by: UserId, now: DateTime<Utc>) -> AgentDeletion {
+    AgentDeletion {
+        agent_id: agent.agent_id,
+        user_id: agent.user_id,
+        namespace_id: agent.namespace_id,
+        requested_by,
+        status: DeletionStatus::InProgress,
+        completed_steps: Vec::new(),
+        failed_step: None,
+        error: None,
+        attempts: 0,
+        next_attempt_at: None,
+        created_at: now,
+        updated_at: now,
+    }
+}
+
+/// Whether a deletion has not finished yet.
+#[must_use]
+pub fn is_pending(deletion: &AgentDeletion) -> bool {
+    deletion.status != DeletionStatus::Completed
+}
+
+/// Whether the deletion worker should resume a deletion as of `now`.
+#[must_use]
+pub fn is_due(deletion: &AgentDeletion, now: DateTime<Utc>) -> bool {
+    is_pending(deletion) && deletion.next_attempt_at.is_none_or(|at| at <= now)
+}
+
+/// Whether the caller may see a deletion.
+///
+/// The agent's owner, the user who requested the deletion, and admins of the
+/// agent's namespace may see it.
+#[must_use]
+pub fn can_view(caller: &Caller, deletion: &AgentDeletion) -> bool {
+    let in_scope = deletion
+        .namespace_id
+        .is_none_or(|namespace_id| namespace_id == caller.namespace_id);
+    in_scope
+        && (caller.user_id == deletion.user_id
+            || caller.user_id == deletion.requested_by
+            || caller.is_namespace_admin())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::{AgentId, NamespaceId};
+    use aura_swarm_store::{AgentSpec, AgentState, DeletionStep, RestartPolicy, RestartState};
+    use std::collections::BTreeMap;
+
+    fn agent(namespace_id: NamespaceId) -> Agent {
+        Agent {
+            agent_id: AgentId::from_bytes([7u8; 32]),
+            user_id: UserId::from_bytes([1u8; 32]),
+            namespace_id: Some(namespace_id),
+            name: "doomed".to_string(),
+            status: AgentState::Stopped,
+            spec: AgentSpec::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_heartbeat_at: None,
+            error_message: None,
+            cloned_from: None,
+            template: None,
+            labels: BTreeMap::new(),
+            restart_policy: RestartPolicy::Never,
+            restarts: RestartState::default(),
+            hibernation: None,
+            provisioning_started_at: None,
+        }
+    }
+
+    #[test]
+    fn due_until_completed() {
+        let now = Utc::now();
+        let namespace_id = NamespaceId::from_uuid(uuid::Uuid::from_u128(1));
+        let mut deletion = new_deletion(&agent(namespace_id), UserId::from_bytes([1u8; 32]), now);
+        assert_eq!(deletion.next_step(), Some(DeletionStep::PurgeSessions));
+        assert!(is_due(&deletion, now));
+
+        deletion.next_attempt_at = Some(now + lease());
+        assert!(!is_due(&deletion, now));
+        assert!(is_due(&deletion, now + lease()));
+
+        deletion.status = DeletionStatus::Completed;
+        assert!(!is_pending(&deletion));
+        assert!(!is_due(&deletion, now + lease()));
+    }
+
+    #[test]
+    fn visible_to_owner_requester_and_namespace_admin() {
+        let namespace_id = NamespaceId::from_uuid(uuid::Uuid::from_u128(1));
+        let owner = UserId::from_bytes([1u8; 32]);
+        let requester = UserId::from_bytes([2u8; 32]);
+        let other = UserId::from_bytes([3u8; 32]);
+        let deletion = new_deletion(&agent(namespace_id), requester, Utc::now());
+
+        assert!(can_view(&Caller::member(owner, namespace_id), &deletion));
+        assert!(can_view(
+            &Caller::member(requester, namespace_id),
+            &deletion
+        ));
+        assert!(!can_view(&Caller::member(other, namespace_id), &deletion));
+        assert!(can_view(
+            &Caller::namespace_admin(other, namespace_id),
+            &deletion
+        ));
+        assert!(!can_view(
+            &Caller::namespace_admin(other, NamespaceId::from_uuid(uuid::Uuid::from_u128(2))),
+            &deletion
+        ));
+    }
+}
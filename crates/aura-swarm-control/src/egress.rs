@@ -0,0 +1,179 @@
+//! Validation for agent egress policies.
+//!
+//! An agent's [`EgressPolicy`] is turned into a Kubernetes `NetworkPolicy` by
+//! the scheduler when the agent is started. This module checks the policy when
+//! the spec is set, so a malformed rule is rejected up front instead of
+//! failing the agent's start.
+//!
+//! No rule may open a private, shared or link-local range (see
+//! [`EgressPolicy::PRIVATE_IPV4_RANGES`]): CIDRs overlapping one are
+//! rejected, and host names must be names rather than addresses. The
+//! scheduler leaves out host addresses that resolve into those ranges.
+
+use std::net::IpAddr;
+
+use aura_swarm_store::{EgressPolicy, EgressRule};
+
+use crate::error::{ControlError, Result};
+
+/// Maximum number of rules in an allowlist policy.
+pub const MAX_EGRESS_RULES: usize = 32;
+
+/// Maximum length of a DNS host name.
+const MAX_HOST_LEN: usize = 253;
+
+/// Validate an egress policy.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the policy has too many rules
+/// or any rule is invalid.
+pub fn validate(policy: &EgressPolicy) -> Result<()> {
+    let EgressPolicy::Allowlist { rules } = policy else {
+        return Ok(());
+    };
+    if rules.len() > MAX_EGRESS_RULES {
+        return Err(ControlError::InvalidRequest(format!(
+            "egress allowlist has {} rules, maximum is {MAX_EGRESS_RULES}",
+            rules.len()
+        )));
+    }
+    rules.iter().try_for_each(validate_rule)
+}
+
+fn validate_rule(rule: &EgressRule) -> Result<()> {
+    match (&rule.cidr, &rule.host) {
+        (Some(cidr), None) => validate_cidr(cidr)?,
+        (None, Some(host)) => validate_host(host)?,
+        _ => {
+            return Err(ControlError::InvalidRequest(
+                "each egress rule must set exactly one of cidr or host".to_string(),
+            ));
+        }
+    }
+    if rule.ports.contains(&0) {
+        return Err(ControlError::InvalidRequest(
+            "egress rule ports must be greater than zero".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn validate_cidr(cidr: &str) -> Result<()> {
+    let invalid = || ControlError::InvalidRequest(format!("invalid egress CIDR '{cidr}'"));
+    let (address, prefix) = cidr.split_once('/').ok_or_else(invalid)?;
+    let address: IpAddr = address.parse().map_err(|_| invalid())?;
+    let prefix: u8 = prefix.parse().map_err(|_| invalid())?;
+    let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+    if prefix > max_prefix {
+        return Err(invalid());
+    }
+    if EgressPolicy::overlaps_private_range(address, prefix) {
+        return Err(ControlError::InvalidRequest(format!(
+            "egress CIDR '{cidr}' overlaps a private address range"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_host(host: &str) -> Result<()> {
+    let valid = !host.is_empty()
+        && host.len() <= MAX_HOST_LEN
+        && host.parse::<IpAddr>().is_err()
+        && host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.');
+    if !valid {
+        return Err(ControlError::InvalidRequest(format!(
+            "invalid egress host '{host}'"
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cidr(cidr: &str) -> EgressRule {
+        EgressRule {
+            cidr: Some(cidr.to_string()),
+            host: None,
+            ports: vec![443],
+        }
+    }
+
+    fn host(host: &str) -> EgressRule {
+        EgressRule {
+            cidr: None,
+            host: Some(host.to_string()),
+            ports: vec![443],
+        }
+    }
+
+    fn allowlist(rules: Vec<EgressRule>) -> EgressPolicy {
+        EgressPolicy::Allowlist { rules }
+    }
+
+    #[test]
+    fn accepts_valid_policies() {
+        assert!(validate(&EgressPolicy::DenyAll).is_ok());
+        assert!(validate(&EgressPolicy::AllowInternet).is_ok());
+        assert!(validate(&allowlist(vec![
+            cidr("203.0.113.0/24"),
+            cidr("172.32.0.0/16"),
+            cidr("2001:db8::/32"),
+            host("api.github.com"),
+        ]))
+        .is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_rules() {
+        for rule in [
+            cidr("203.0.113.0"),
+            cidr("203.0.113.0/33"),
+            cidr("not-an-ip/8"),
+            // Private, shared and link-local ranges, or ranges containing them
+            cidr("0.0.0.0/0"),
+            cidr("10.0.0.0/8"),
+            cidr("10.1.2.3/32"),
+            cidr("100.64.1.0/24"),
+            cidr("169.254.169.254/32"),
+            cidr("172.20.0.0/16"),
+            cidr("192.0.0.0/2"),
+            cidr("::/0"),
+            cidr("fd00::/8"),
+            cidr("fe80::1/128"),
+            host("169.254.169.254"),
+            host(""),
+            host("evil.com/path"),
+            host("two words"),
+            EgressRule {
+                cidr: Some("10.0.0.0/8".to_string()),
+                host: Some("example.com".to_string()),
+                ports: Vec::new(),
+            },
+            EgressRule {
+                cidr: None,
+                host: None,
+                ports: vec![443],
+            },
+            EgressRule {
+                ports: vec![0],
+                ..host("example.com")
+            },
+        ] {
+            assert!(
+                matches!(
+                    validate(&allowlist(vec![rule.clone()])),
+                    Err(ControlError::InvalidRequest(_))
+                ),
+                "accepted {rule:?}"
+            );
+        }
+
+        let too_many = vec![host("example.com"); MAX_EGRESS_RULES + 1];
+        assert!(validate(&allowlist(too_many)).is_err());
+    }
+}
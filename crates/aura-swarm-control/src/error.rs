@@ -3,8 +3,8 @@
 //! This module defines all errors that can occur during agent lifecycle
 //! and session management operations.
 
-use aura_swarm_core::{AgentId, SessionId, UserId};
-use aura_swarm_store::AgentState;
+use aura_swarm_core::{AgentId, NamespaceId, RolloutId, SessionId, UserId, WebhookId};
+use aura_swarm_store::{AgentRole, AgentState};
 use thiserror::Error;
 
 /// A result type using `ControlError`.
@@ -30,6 +30,15 @@ pub enum ControlError {
         limit: u32,
     },
 
+    /// The namespace has reached its agent quota limit.
+    #[error("agent quota exceeded for namespace {namespace_id}: limit is {limit}")]
+    NamespaceQuotaExceeded {
+        /// The namespace that exceeded the quota.
+        namespace_id: NamespaceId,
+        /// The maximum number of agents allowed.
+        limit: u32,
+    },
+
     /// The user is not the owner of the requested resource.
     #[error("user {user_id} is not the owner of agent {agent_id}")]
     NotOwner {
@@ -39,6 +48,30 @@ pub enum ControlError {
         agent_id: AgentId,
     },
 
+    /// The user has access to the agent, but not enough for this operation.
+    #[error("user {user_id} needs the {required:?} role on agent {agent_id}")]
+    InsufficientRole {
+        /// The user making the request.
+        user_id: UserId,
+        /// The agent being accessed.
+        agent_id: AgentId,
+        /// The minimum role the operation requires.
+        required: AgentRole,
+    },
+
+    /// The user has no access grant on the agent.
+    #[error("no access grant for user {user_id} on agent {agent_id}")]
+    GrantNotFound {
+        /// The agent the grant would apply to.
+        agent_id: AgentId,
+        /// The user the grant would apply to.
+        user_id: UserId,
+    },
+
+    /// The operation requires the namespace admin role.
+    #[error("namespace admin role required for namespace {0}")]
+    NotNamespaceAdmin(NamespaceId),
+
     /// The requested state transition is not valid.
     #[error(
         "invalid state transition for agent {agent_id}: cannot transition from {from:?} to {to:?}"
@@ -60,6 +93,64 @@ pub enum ControlError {
     #[error("agent {0} already has an active session")]
     SessionAlreadyActive(AgentId),
 
+    /// Too many sessions are already active.
+    #[error("session limit exceeded: at most {limit} active sessions per {scope}")]
+    SessionLimitExceeded {
+        /// What the limit applies to (`agent`, `user` or `namespace`).
+        scope: &'static str,
+        /// The maximum number of active sessions allowed.
+        limit: u32,
+    },
+
+    /// The agent has no schedule.
+    #[error("no schedule for agent {0}")]
+    ScheduleNotFound(AgentId),
+
+    /// The requested webhook was not found (or belongs to another user).
+    #[error("webhook not found: {0}")]
+    WebhookNotFound(WebhookId),
+
+    /// The agent has no secret with this name.
+    #[error("secret {name} not found for agent {agent_id}")]
+    SecretNotFound {
+        /// The agent the secret would belong to.
+        agent_id: AgentId,
+        /// The secret's name.
+        name: String,
+    },
+
+    /// The requested rollout was not found.
+    #[error("rollout not found: {0}")]
+    RolloutNotFound(RolloutId),
+
+    /// Another rollout is still in progress.
+    #[error("rollout {0} is already in progress")]
+    RolloutInProgress(RolloutId),
+
+    /// The agent is being deleted and can no longer be started.
+    #[error("agent {0} is being deleted")]
+    AgentDeleting(AgentId),
+
+    /// The agent's state is being copied into a clone.
+    #[error("agent {0} state is being copied to a clone")]
+    StateCopyPending(AgentId),
+
+    /// No deletion has been requested for the agent.
+    #[error("no deletion found for agent {0}")]
+    DeletionNotFound(AgentId),
+
+    /// The requested template was not found.
+    #[error("template not found: {0}")]
+    TemplateNotFound(String),
+
+    /// A template with this name already exists in the requested scope.
+    #[error("template already exists: {0}")]
+    TemplateExists(String),
+
+    /// The request is malformed or violates configured limits.
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
     /// Storage layer error.
     #[error("storage error: {0}")]
     Store(#[from] aura_swarm_store::StoreError),
@@ -68,6 +159,14 @@ pub enum ControlError {
     #[error("authentication error: {0}")]
     Auth(#[from] aura_swarm_auth::AuthError),
 
+    /// The scheduler could not be reached or its circuit breaker is open.
+    #[error("scheduler unavailable: {0}")]
+    SchedulerUnavailable(String),
+
+    /// The agent's runtime could not be reached or rejected the request.
+    #[error("runtime error: {0}")]
+    Runtime(#[from] crate::runtime::RuntimeError),
+
     /// Internal error.
     #[error("internal error: {0}")]
     Internal(String),
@@ -78,13 +177,31 @@ impl ControlError {
     #[must_use]
     pub const fn http_status_code(&self) -> u16 {
         match self {
-            Self::AgentNotFound(_) | Self::SessionNotFound(_) => 404,
-            Self::QuotaExceeded { .. } => 429,
-            Self::NotOwner { .. } => 403,
+            Self::AgentNotFound(_)
+            | Self::SessionNotFound(_)
+            | Self::ScheduleNotFound(_)
+            | Self::WebhookNotFound(_)
+            | Self::TemplateNotFound(_)
+            | Self::GrantNotFound { .. }
+            | Self::SecretNotFound { .. }
+            | Self::RolloutNotFound(_)
+            | Self::DeletionNotFound(_) => 404,
+            Self::QuotaExceeded { .. }
+            | Self::NamespaceQuotaExceeded { .. }
+            | Self::SessionLimitExceeded { .. } => 429,
+            Self::NotOwner { .. } | Self::InsufficientRole { .. } | Self::NotNamespaceAdmin(_) => {
+                403
+            }
             Self::InvalidState { .. }
             | Self::AgentNotRunnable(_)
-            | Self::SessionAlreadyActive(_) => 409,
+            | Self::SessionAlreadyActive(_)
+            | Self::TemplateExists(_)
+            | Self::RolloutInProgress(_)
+            | Self::AgentDeleting(_)
+            | Self::StateCopyPending(_) => 409,
+            Self::InvalidRequest(_) => 400,
             Self::Store(_) | Self::Internal(_) => 500,
+            Self::SchedulerUnavailable(_) | Self::Runtime(_) => 503,
             Self::Auth(_) => 401,
         }
     }
@@ -92,7 +209,10 @@ impl ControlError {
     /// Returns true if this error might be resolved by retrying.
     #[must_use]
     pub const fn is_retriable(&self) -> bool {
-        matches!(self, Self::Store(_) | Self::Internal(_))
+        matches!(
+            self,
+            Self::Store(_) | Self::Internal(_) | Self::SchedulerUnavailable(_) | Self::Runtime(_)
+        )
     }
 }
 
@@ -118,10 +238,36 @@ mod tests {
             ControlError::QuotaExceeded { user_id, limit: 10 }.http_status_code(),
             429
         );
+        assert_eq!(
+            ControlError::SessionLimitExceeded {
+                scope: "agent",
+                limit: 5
+            }
+            .http_status_code(),
+            429
+        );
         assert_eq!(
             ControlError::NotOwner { user_id, agent_id }.http_status_code(),
             403
         );
+        assert_eq!(
+            ControlError::InsufficientRole {
+                user_id,
+                agent_id,
+                required: AgentRole::Operator
+            }
+            .http_status_code(),
+            403
+        );
+        assert_eq!(
+            ControlError::GrantNotFound { agent_id, user_id }.http_status_code(),
+            404
+        );
+        assert_eq!(
+            ControlError::NotNamespaceAdmin(NamespaceId::from_uuid(uuid::Uuid::nil()))
+                .http_status_code(),
+            403
+        );
         assert_eq!(
             ControlError::InvalidState {
                 agent_id,
@@ -131,5 +277,45 @@ mod tests {
             .http_status_code(),
             409
         );
+        assert_eq!(
+            ControlError::TemplateNotFound("small".to_string()).http_status_code(),
+            404
+        );
+        assert_eq!(
+            ControlError::SecretNotFound {
+                agent_id,
+                name: "TOKEN".to_string()
+            }
+            .http_status_code(),
+            404
+        );
+        let rollout_id = RolloutId::generate();
+        assert_eq!(
+            ControlError::RolloutNotFound(rollout_id).http_status_code(),
+            404
+        );
+        assert_eq!(
+            ControlError::RolloutInProgress(rollout_id).http_status_code(),
+            409
+        );
+        assert_eq!(
+            ControlError::AgentDeleting(agent_id).http_status_code(),
+            409
+        );
+        assert_eq!(
+            ControlError::StateCopyPending(agent_id).http_status_code(),
+            409
+        );
+        assert_eq!(
+            ControlError::DeletionNotFound(agent_id).http_status_code(),
+            404
+        );
+        assert_eq!(
+            ControlError::InvalidRequest("too big".to_string()).http_status_code(),
+            400
+        );
+        let runtime = ControlError::from(crate::runtime::RuntimeError::NoEndpoint);
+        assert_eq!(runtime.http_status_code(), 503);
+        assert!(runtime.is_retriable());
     }
 }
@@ -32,9 +32,9 @@
 //!
 //! ```no_run
 //! use std::sync::Arc;
-//! use aura_swarm_control::{ControlPlane, ControlPlaneService, CreateAgentRequest};
+//! use aura_swarm_control::{Caller, ControlPlane, ControlPlaneService, CreateAgentRequest};
 //! use aura_swarm_store::RocksStore;
-//! use aura_swarm_core::UserId;
+//! use aura_swarm_core::{NamespaceId, UserId};
 //!
 //! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 //! // Initialize store
@@ -43,10 +43,12 @@
 //! // Create control plane service
 //! let control = ControlPlaneService::with_defaults(store);
 //!
-//! // Create an agent
+//! // Create an agent on behalf of a user in their namespace
 //! let user_id = UserId::from_bytes([0u8; 32]);
+//! let namespace_id: NamespaceId = "6ba7b810-9dad-11d1-80b4-00c04fd430c8".parse()?;
+//! let caller = Caller::member(user_id, namespace_id);
 //! let request = CreateAgentRequest::new("my-agent");
-//! let agent = control.create_agent(&user_id, request).await?;
+//! let agent = control.create_agent(&caller, request).await?;
 //!
 //! println!("Created agent: {}", agent.agent_id);
 //! # Ok(())
@@ -72,18 +74,57 @@
 #![warn(clippy::all)]
 #![warn(clippy::pedantic)]
 
+pub mod access;
+pub mod audit;
+pub mod deletion;
+pub mod egress;
 pub mod error;
 pub mod lifecycle;
+pub mod logs;
+pub mod metering;
+pub mod namespace;
+pub mod placement;
+pub mod reconcile;
+pub mod rollout;
+pub mod runtime;
+pub mod schedule;
 pub mod scheduler_client;
+pub mod secrets;
 pub mod service;
 pub mod session;
 pub mod types;
+pub mod webhook;
 
+pub use audit::{AuditLogger, StoreAuditLogger, TracingAuditLogger};
 pub use error::{ControlError, Result};
-pub use scheduler_client::{HttpSchedulerClient, NoopSchedulerClient, PodStatusResponse, SchedulerClient};
+pub use logs::{LogEntry, LogEntryStream};
+pub use metering::{UsageGroupBy, UsageQuery, UsageSummary};
+pub use reconcile::{DriftAction, DriftCorrection, DriftReport};
+pub use runtime::{HibernateAck, RuntimeClient, RuntimeError};
+pub use scheduler_client::{
+    CircuitState, HttpSchedulerClient, LogLineStream, NoopSchedulerClient, PodMetricsResponse,
+    PodStatusResponse, PodSummary, SchedulerClient, SchedulerClientConfig,
+};
+pub use secrets::SecretCipher;
 pub use service::{ControlPlane, ControlPlaneService};
-pub use types::{AgentStatus, ControlConfig, CreateAgentRequest, LogOptions};
+pub use types::{
+    AgentSpecOverrides, AgentStatus, Caller, CloneAgentRequest, ControlConfig, CreateAgentRequest,
+    CreateRolloutRequest, CreateTemplateRequest, CreateWebhookRequest, CreatedWebhook, LogOptions,
+    NamespaceRole, NamespaceUsage, SetNamespaceQuotaRequest, SetScheduleRequest, SharedAgent,
+    MAX_AGENT_NAME_LEN,
+};
 
 // Re-export commonly used types from dependencies for convenience
-pub use aura_swarm_core::{AgentId, SessionId, UserId};
-pub use aura_swarm_store::{Agent, AgentSpec, AgentState, Session, SessionStatus};
+pub use aura_swarm_core::{
+    AgentId, AuditEventId, DeliveryId, NamespaceId, RolloutId, SessionId, UserId, WebhookId,
+};
+pub use aura_swarm_store::{
+    Agent, AgentDeletion, AgentGrant, AgentPlacement, AgentRole, AgentSchedule, AgentSecret,
+    AgentSpec, AgentState, AgentTemplate, AuditActor, AuditEvent, AuditOutcome, AuditQuery,
+    AuditResource, DeletionStatus, DeletionStep, DeliveryAttempt, DeliveryStatus, EgressPolicy,
+    EgressRule, HibernationCheckpoint, MissedRunPolicy, NamespaceQuota, PlacementToleration,
+    RestartPolicy, RestartState, Rollout, RolloutSelector, RolloutStatus, RolloutStrategy,
+    RolloutTarget, RolloutTargetState, ScheduleAction, ScheduleRule, ScheduleRun,
+    ScheduleRunOutcome, Session, SessionStatus, TaintEffect, TolerationOperator, TopologySpread,
+    UsageRecord, Webhook, WebhookDelivery, WebhookEvent,
+};
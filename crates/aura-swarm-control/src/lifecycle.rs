@@ -36,10 +36,13 @@
 //!                                         └──────────┘
 //! ```
 
+use std::time::Duration;
+
 use aura_swarm_core::AgentId;
 use aura_swarm_store::AgentState;
 
 use crate::error::{ControlError, Result};
+use crate::types::ControlConfig;
 
 /// Validates a state transition and returns the target state if valid.
 ///
@@ -114,6 +117,12 @@ pub const fn can_wake(state: AgentState) -> bool {
     matches!(state, AgentState::Hibernating | AgentState::Stopped)
 }
 
+/// Returns true if the agent's state directory is quiescent and can be cloned.
+#[must_use]
+pub const fn can_clone(state: AgentState) -> bool {
+    matches!(state, AgentState::Stopped | AgentState::Hibernating)
+}
+
 /// Returns true if the agent is in a terminal state (stopped or error).
 #[must_use]
 pub const fn is_terminal(state: AgentState) -> bool {
@@ -129,6 +138,19 @@ pub const fn is_active(state: AgentState) -> bool {
     )
 }
 
+/// Delay before automatic restart number `attempt` (1-based).
+///
+/// Doubles from `restart_backoff_base_seconds`, capped at
+/// `restart_backoff_max_seconds`.
+#[must_use]
+pub fn restart_delay(config: &ControlConfig, attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(31);
+    let delay = config
+        .restart_backoff_base_seconds
+        .saturating_mul(1u64 << exponent);
+    Duration::from_secs(delay.min(config.restart_backoff_max_seconds))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,6 +228,15 @@ mod tests {
         assert!(!can_wake(AgentState::Idle));
     }
 
+    #[test]
+    fn clone_eligibility() {
+        assert!(can_clone(AgentState::Stopped));
+        assert!(can_clone(AgentState::Hibernating));
+        assert!(!can_clone(AgentState::Running));
+        assert!(!can_clone(AgentState::Provisioning));
+        assert!(!can_clone(AgentState::Error));
+    }
+
     #[test]
     fn terminal_states() {
         assert!(is_terminal(AgentState::Stopped));
@@ -233,4 +264,19 @@ mod tests {
         assert!(transitions.contains(&AgentState::Error));
         assert!(!transitions.contains(&AgentState::Provisioning));
     }
+
+    #[test]
+    fn restart_backoff_doubles_up_to_cap() {
+        let config = ControlConfig {
+            restart_backoff_base_seconds: 10,
+            restart_backoff_max_seconds: 100,
+            ..ControlConfig::default()
+        };
+
+        assert_eq!(restart_delay(&config, 1), Duration::from_secs(10));
+        assert_eq!(restart_delay(&config, 2), Duration::from_secs(20));
+        assert_eq!(restart_delay(&config, 4), Duration::from_secs(80));
+        assert_eq!(restart_delay(&config, 5), Duration::from_secs(100));
+        assert_eq!(restart_delay(&config, u32::MAX), Duration::from_secs(100));
+    }
 }
@@ -0,0 +1,220 @@
+//! Agent log retrieval.
+//!
+//! The scheduler returns raw container log lines, each prefixed with the time
+//! Kubernetes recorded it. The runtime writes structured JSON lines in the
+//! `tracing-subscriber` format; this module parses those into [`LogEntry`]
+//! values and falls back to treating anything else as plain `info` text.
+
+use aura_swarm_core::AgentId;
+use chrono::{DateTime, Utc};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ControlError, Result};
+use crate::scheduler_client::SchedulerClient;
+use crate::types::LogOptions;
+
+/// Maximum number of lines a single log request may ask for.
+pub const MAX_LOG_LINES: u32 = 10_000;
+
+/// Level reported for lines that don't carry one.
+const DEFAULT_LEVEL: &str = "info";
+
+/// A single parsed log line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// When the line was written.
+    pub timestamp: DateTime<Utc>,
+    /// Log level, lowercased (e.g. `info`, `warn`).
+    pub level: String,
+    /// Log message.
+    pub message: String,
+}
+
+/// A stream of parsed log entries.
+pub type LogEntryStream = BoxStream<'static, Result<LogEntry>>;
+
+/// Optional `since` and `until` bounds of a log query.
+pub type TimeBounds = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// Stream an agent's logs through the scheduler.
+///
+/// An agent without a pod has no logs, so a missing pod yields an empty
+/// stream rather than an error.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the options are invalid, or an
+/// error if the scheduler cannot be reached.
+pub async fn stream<C: SchedulerClient + ?Sized>(
+    scheduler: &C,
+    agent_id: &AgentId,
+    options: &LogOptions,
+) -> Result<LogEntryStream> {
+    let (since, until) = validate(options)?;
+
+    let lines = match scheduler
+        .stream_pod_logs(agent_id, Some(options.lines), since, options.follow)
+        .await
+    {
+        Ok(lines) => lines,
+        Err(ControlError::AgentNotFound(_)) => return Ok(futures::stream::empty().boxed()),
+        Err(e) => return Err(e),
+    };
+
+    let entries = lines
+        .filter_map(|line| async move {
+            match line {
+                Ok(line) => parse_line(&line).map(Ok),
+                Err(e) => Some(Err(e)),
+            }
+        })
+        .take_while(move |entry| {
+            let before_until = match (entry, until) {
+                (Ok(entry), Some(until)) => entry.timestamp <= until,
+                _ => true,
+            };
+            futures::future::ready(before_until)
+        });
+
+    Ok(entries.boxed())
+}
+
+/// Validate log options, returning the parsed `since` and `until` times.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if `lines` is out of range or a
+/// time is not RFC 3339.
+pub fn validate(options: &LogOptions) -> Result<TimeBounds> {
+    if options.lines == 0 || options.lines > MAX_LOG_LINES {
+        return Err(ControlError::InvalidRequest(format!(
+            "lines must be between 1 and {MAX_LOG_LINES}"
+        )));
+    }
+
+    let parse = |name: &str, value: Option<&String>| {
+        value
+            .map(|value| {
+                parse_time(value).ok_or_else(|| {
+                    ControlError::InvalidRequest(format!("{name} must be an RFC 3339 time"))
+                })
+            })
+            .transpose()
+    };
+    let since = parse("since", options.since.as_ref())?;
+    let until = parse("until", options.until.as_ref())?;
+
+    if let (Some(since), Some(until)) = (since, until) {
+        if since > until {
+            return Err(ControlError::InvalidRequest(
+                "since must not be after until".to_string(),
+            ));
+        }
+    }
+    Ok((since, until))
+}
+
+/// Parse a timestamp-prefixed container log line.
+///
+/// Returns `None` for lines without a valid timestamp prefix.
+#[must_use]
+pub fn parse_line(line: &str) -> Option<LogEntry> {
+    let (prefix, content) = line.split_once(' ').unwrap_or((line, ""));
+    let written_at = parse_time(prefix)?;
+
+    let Ok(serde_json::Value::Object(fields)) = serde_json::from_str(content) else {
+        return Some(LogEntry {
+            timestamp: written_at,
+            level: DEFAULT_LEVEL.to_string(),
+            message: content.to_string(),
+        });
+    };
+
+    let text = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| fields.get(*key)?.as_str())
+            .map(str::to_string)
+    };
+
+    let message = fields
+        .get("fields")
+        .and_then(|f| f.get("message"))
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+        .or_else(|| text(&["message", "msg"]))
+        .unwrap_or_else(|| content.to_string());
+
+    Some(LogEntry {
+        timestamp: text(&["timestamp", "time", "ts"])
+            .and_then(|t| parse_time(&t))
+            .unwrap_or(written_at),
+        level: text(&["level", "severity"])
+            .map_or_else(|| DEFAULT_LEVEL.to_string(), |l| l.to_lowercase()),
+        message,
+    })
+}
+
+fn parse_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_runtime_json_lines() {
+        let entry = parse_line(
+            r#"2026-01-01T00:00:05.000000001Z {"timestamp":"2026-01-01T00:00:04.5Z","level":"WARN","fields":{"message":"tool call slow"},"target":"aura"}"#,
+        )
+        .unwrap();
+
+        assert_eq!(entry.level, "warn");
+        assert_eq!(entry.message, "tool call slow");
+        assert_eq!(
+            entry.timestamp,
+            parse_time("2026-01-01T00:00:04.5Z").unwrap()
+        );
+    }
+
+    #[test]
+    fn falls_back_to_plain_text() {
+        let entry = parse_line("2026-01-01T00:00:05Z starting runtime").unwrap();
+        assert_eq!(entry.level, "info");
+        assert_eq!(entry.message, "starting runtime");
+        assert_eq!(entry.timestamp, parse_time("2026-01-01T00:00:05Z").unwrap());
+
+        let entry = parse_line(r#"2026-01-01T00:00:05Z {"msg":"hello"}"#).unwrap();
+        assert_eq!(entry.level, "info");
+        assert_eq!(entry.message, "hello");
+
+        assert!(parse_line("no timestamp here").is_none());
+    }
+
+    #[test]
+    fn validates_options() {
+        assert!(validate(&LogOptions::default()).is_ok());
+        assert!(validate(&LogOptions::tail(0)).is_err());
+        assert!(validate(&LogOptions::tail(MAX_LOG_LINES + 1)).is_err());
+
+        let options = LogOptions {
+            since: Some("yesterday".to_string()),
+            ..LogOptions::default()
+        };
+        assert!(matches!(
+            validate(&options),
+            Err(ControlError::InvalidRequest(_))
+        ));
+
+        let options = LogOptions {
+            since: Some("2026-01-02T00:00:00Z".to_string()),
+            until: Some("2026-01-01T00:00:00Z".to_string()),
+            ..LogOptions::default()
+        };
+        assert!(validate(&options).is_err());
+    }
+}
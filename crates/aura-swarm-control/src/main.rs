@@ -5,7 +5,7 @@
 
 use std::sync::Arc;
 
-use aura_swarm_control::ControlPlaneService;
+use aura_swarm_control::{ControlConfig, ControlPlaneService};
 use aura_swarm_store::RocksStore;
 use axum::{
     extract::State,
@@ -79,7 +79,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!(data_dir = %data_dir, "Initialized RocksDB store");
 
     // Initialize control plane service
-    let control = Arc::new(ControlPlaneService::with_defaults(store));
+    let control = Arc::new(ControlPlaneService::new(store, ControlConfig::from_env()));
 
     // Create app state
     let state = AppState { control };
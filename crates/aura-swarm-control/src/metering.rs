@@ -0,0 +1,297 @@
+//! Usage metering.
+//!
+//! The control plane periodically samples every agent and charges the time
+//! since the previous sample to the agent's current state. Time is weighted by
+//! the CPU and memory the agent requested and rolled up into hourly
+//! [`UsageRecord`] buckets per namespace, owner and state.
+//!
+//! Stopped agents hold no resources and are not metered.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use aura_swarm_core::{NamespaceId, UserId};
+use aura_swarm_store::{Agent, AgentState, UsageRecord};
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use serde::Serialize;
+
+/// A dimension usage can be grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageGroupBy {
+    /// One row per hour.
+    Hour,
+    /// One row per namespace.
+    Namespace,
+    /// One row per agent owner.
+    User,
+    /// One row per agent state.
+    State,
+}
+
+impl UsageGroupBy {
+    /// The dimension's wire name.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Namespace => "namespace",
+            Self::User => "user",
+            Self::State => "state",
+        }
+    }
+}
+
+impl fmt::Display for UsageGroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for UsageGroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "hour" => Ok(Self::Hour),
+            "namespace" => Ok(Self::Namespace),
+            "user" => Ok(Self::User),
+            "state" => Ok(Self::State),
+            other => Err(format!("unknown usage dimension: {other}")),
+        }
+    }
+}
+
+/// Filter and grouping for a usage report.
+#[derive(Debug, Clone)]
+pub struct UsageQuery {
+    /// Start of the report; buckets for hours starting at or after this are included.
+    pub from: DateTime<Utc>,
+    /// End of the report; buckets for hours starting before this are included.
+    pub to: DateTime<Utc>,
+    /// Only usage in this namespace.
+    pub namespace_id: Option<NamespaceId>,
+    /// Only usage of agents owned by this user.
+    pub user_id: Option<UserId>,
+    /// Dimensions to group by; empty for a single total.
+    pub group_by: Vec<UsageGroupBy>,
+}
+
+/// One row of a usage report.
+///
+/// Dimension fields are only set when the report is grouped by them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UsageSummary {
+    /// Hour the row covers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hour: Option<DateTime<Utc>>,
+    /// Namespace the row covers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<NamespaceId>,
+    /// Agent owner the row covers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<UserId>,
+    /// Agent state the row covers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<AgentState>,
+    /// Agent-seconds.
+    pub agent_seconds: u64,
+    /// CPU millicore-seconds.
+    pub cpu_millicore_seconds: u64,
+    /// Memory megabyte-seconds.
+    pub memory_mb_seconds: u64,
+}
+
+/// The start of the hour containing `at`.
+#[must_use]
+pub fn hour_start(at: DateTime<Utc>) -> DateTime<Utc> {
+    at.duration_trunc(TimeDelta::hours(1)).unwrap_or(at)
+}
+
+/// Charge the interval `[from, to)` to every metered agent.
+///
+/// Intervals crossing an hour boundary are split between the hourly buckets.
+/// Agents created during the interval are only charged from their creation.
+#[must_use]
+pub fn meter(agents: &[Agent], from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<UsageRecord> {
+    let mut records = Vec::new();
+
+    for agent in agents {
+        if agent.status == AgentState::Stopped {
+            continue;
+        }
+
+        let mut start = from.max(agent.created_at);
+        while start < to {
+            let hour = hour_start(start);
+            let end = to.min(hour + TimeDelta::hours(1));
+            // Whole seconds between the truncated timestamps, so the pieces
+            // of a split interval add up to the interval's own length
+            let seconds = u64::try_from(end.timestamp() - start.timestamp()).unwrap_or(0);
+
+            if seconds > 0 {
+                records.push(UsageRecord {
+                    hour,
+                    namespace_id: agent.namespace_id,
+                    user_id: agent.user_id,
+                    state: agent.status,
+                    agent_seconds: seconds,
+                    cpu_millicore_seconds: seconds
+                        .saturating_mul(u64::from(agent.spec.cpu_millicores)),
+                    memory_mb_seconds: seconds.saturating_mul(u64::from(agent.spec.memory_mb)),
+                });
+            }
+            start = end;
+        }
+    }
+
+    records
+}
+
+/// Whether a usage record matches the query's namespace and user filters.
+#[must_use]
+pub fn matches(query: &UsageQuery, record: &UsageRecord) -> bool {
+    query
+        .namespace_id
+        .is_none_or(|id| record.namespace_id == Some(id))
+        && query.user_id.is_none_or(|id| record.user_id == id)
+}
+
+/// Sum usage records into one row per combination of the grouped dimensions.
+///
+/// Rows are ordered by hour, then namespace, user and state.
+#[must_use]
+pub fn summarize(records: &[UsageRecord], group_by: &[UsageGroupBy]) -> Vec<UsageSummary> {
+    type GroupKey = (Option<i64>, Option<[u8; 16]>, Option<[u8; 32]>, Option<u8>);
+
+    let grouped = |dimension: UsageGroupBy| group_by.contains(&dimension);
+    let mut rows: BTreeMap<GroupKey, UsageSummary> = BTreeMap::new();
+
+    for record in records {
+        let row = UsageSummary {
+            hour: grouped(UsageGroupBy::Hour).then_some(record.hour),
+            namespace_id: if grouped(UsageGroupBy::Namespace) {
+                record.namespace_id
+            } else {
+                None
+            },
+            user_id: grouped(UsageGroupBy::User).then_some(record.user_id),
+            state: grouped(UsageGroupBy::State).then_some(record.state),
+            agent_seconds: 0,
+            cpu_millicore_seconds: 0,
+            memory_mb_seconds: 0,
+        };
+        let key = (
+            row.hour.map(|hour| hour.timestamp()),
+            row.namespace_id.map(|id| *id.as_bytes()),
+            row.user_id.map(|id| *id.as_bytes()),
+            row.state.map(AgentState::as_u8),
+        );
+
+        let total = rows.entry(key).or_insert(row);
+        total.agent_seconds = total.agent_seconds.saturating_add(record.agent_seconds);
+        total.cpu_millicore_seconds = total
+            .cpu_millicore_seconds
+            .saturating_add(record.cpu_millicore_seconds);
+        total.memory_mb_seconds = total
+            .memory_mb_seconds
+            .saturating_add(record.memory_mb_seconds);
+    }
+
+    rows.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::AgentId;
+    use aura_swarm_store::{AgentSpec, RestartPolicy, RestartState};
+
+    fn at(seconds: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(seconds, 0).unwrap()
+    }
+
+    fn agent(byte: u8, status: AgentState) -> Agent {
+        Agent {
+            agent_id: AgentId::from_bytes([byte; 32]),
+            user_id: UserId::from_bytes([byte; 32]),
+            namespace_id: None,
+            name: format!("agent-{byte}"),
+            status,
+            spec: AgentSpec {
+                cpu_millicores: 500,
+                memory_mb: 256,
+                ..AgentSpec::default()
+            },
+            created_at: at(0),
+            updated_at: at(0),
+            last_heartbeat_at: None,
+            error_message: None,
+            cloned_from: None,
+            template: None,
+            labels: BTreeMap::new(),
+            restart_policy: RestartPolicy::Never,
+            restarts: RestartState::default(),
+            hibernation: None,
+            provisioning_started_at: None,
+        }
+    }
+
+    #[test]
+    fn meter_splits_intervals_at_hour_boundaries() {
+        let agents = [agent(1, AgentState::Running), agent(2, AgentState::Stopped)];
+
+        // 00:59:00 to 01:01:30
+        let records = meter(&agents, at(3540), at(3690));
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].hour, at(0));
+        assert_eq!(records[0].agent_seconds, 60);
+        assert_eq!(records[1].hour, at(3600));
+        assert_eq!(records[1].agent_seconds, 90);
+        assert_eq!(records[1].cpu_millicore_seconds, 90 * 500);
+        assert_eq!(records[1].memory_mb_seconds, 90 * 256);
+    }
+
+    #[test]
+    fn split_intervals_do_not_lose_fractional_seconds() {
+        let agents = [agent(1, AgentState::Running)];
+
+        // 00:55:00.5 to 01:05:00.5
+        let from = at(3300) + TimeDelta::milliseconds(500);
+        let records = meter(&agents, from, from + TimeDelta::minutes(10));
+        let total: u64 = records.iter().map(|record| record.agent_seconds).sum();
+        assert_eq!(total, 600);
+    }
+
+    #[test]
+    fn summarize_groups_by_requested_dimensions() {
+        let mut idle = agent(1, AgentState::Idle);
+        idle.created_at = at(3000);
+        let agents = [
+            agent(1, AgentState::Running),
+            idle,
+            agent(2, AgentState::Running),
+        ];
+        let records = meter(&agents, at(0), at(3600));
+
+        let total = summarize(&records, &[]);
+        assert_eq!(total.len(), 1);
+        assert_eq!(total[0].agent_seconds, 3600 + 600 + 3600);
+        assert!(total[0].state.is_none());
+
+        let by_state = summarize(&records, &[UsageGroupBy::State]);
+        assert_eq!(by_state.len(), 2);
+        assert_eq!(by_state[0].state, Some(AgentState::Running));
+        assert_eq!(by_state[0].agent_seconds, 7200);
+
+        let by_user_and_state = summarize(&records, &[UsageGroupBy::User, UsageGroupBy::State]);
+        assert_eq!(by_user_and_state.len(), 3);
+    }
+
+    #[test]
+    fn group_by_parses() {
+        assert_eq!("state".parse::<UsageGroupBy>(), Ok(UsageGroupBy::State));
+        assert!("agent".parse::<UsageGroupBy>().is_err());
+    }
+}
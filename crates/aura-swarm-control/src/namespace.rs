@@ -0,0 +1,190 @@
+//! Namespace (tenant) scoping and quotas.
+//!
+//! Every agent records the namespace it was created in, and sessions inherit
+//! their agent's namespace. A caller can only reach agents and sessions in
+//! the namespace their token was issued for:
+//!
+//! - Members may manage the agents they own or that were shared with them
+//!   (see [`crate::access`]).
+//! - Namespace admins may manage every agent in the namespace.
+//! - Resources in other namespaces are reported as not found, so tenants
+//!   cannot probe each other's IDs.
+//!
+//! Agents created before namespace scoping have no namespace; they stay
+//! reachable by their owner from any namespace and are invisible to everyone
+//! else, including admins and users they were shared with.
+//!
+//! Quotas are enforced per namespace in addition to the per-user limits.
+//! Overrides stored for a namespace take precedence over the defaults in
+//! [`ControlConfig`].
+
+use aura_swarm_core::NamespaceId;
+use aura_swarm_store::{Agent, SessionStatus, Store};
+
+use crate::error::{ControlError, Result};
+use crate::types::{Caller, ControlConfig, NamespaceUsage};
+
+/// Check that the caller administers their namespace.
+///
+/// # Errors
+///
+/// Returns `NotNamespaceAdmin` if the caller is a regular member.
+pub fn require_admin(caller: &Caller) -> Result<()> {
+    if caller.is_namespace_admin() {
+        Ok(())
+    } else {
+        Err(ControlError::NotNamespaceAdmin(caller.namespace_id))
+    }
+}
+
+/// Whether an agent is visible from the caller's namespace.
+///
+/// Agents without a namespace predate scoping and are visible only to their
+/// owner.
+#[must_use]
+pub fn in_scope(caller: &Caller, agent: &Agent) -> bool {
+    match agent.namespace_id {
+        Some(namespace_id) => namespace_id == caller.namespace_id,
+        None => agent.user_id == caller.user_id,
+    }
+}
+
+/// The caller to use when the control plane acts on an agent for its owner
+/// (e.g. scheduled wake/hibernate runs).
+pub(crate) fn owner_of(agent: &Agent) -> Caller {
+    // Unscoped agents accept their owner from any namespace, so any ID will do.
+    let namespace_id = agent
+        .namespace_id
+        .unwrap_or_else(|| NamespaceId::from_uuid(uuid::Uuid::nil()));
+    Caller::member(agent.user_id, namespace_id)
+}
+
+// =============================================================================
+// Quotas
+// =============================================================================
+
+/// Effective limits for a namespace.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NamespaceLimits {
+    pub(crate) max_agents: u32,
+    pub(crate) max_sessions: u32,
+}
+
+/// Resolve a namespace's limits, applying any stored overrides.
+pub(crate) fn limits<S: Store>(
+    store: &S,
+    config: &ControlConfig,
+    namespace_id: &NamespaceId,
+) -> Result<NamespaceLimits> {
+    let quota = store.get_namespace_quota(namespace_id)?;
+    let max_agents = quota.as_ref().and_then(|q| q.max_agents);
+    let max_sessions = quota.as_ref().and_then(|q| q.max_sessions);
+
+    Ok(NamespaceLimits {
+        max_agents: max_agents.unwrap_or(config.max_agents_per_namespace),
+        max_sessions: max_sessions.unwrap_or(config.max_sessions_per_namespace),
+    })
+}
+
+/// Ensure the namespace can hold another agent.
+pub(crate) fn check_agent_quota<S: Store>(
+    store: &S,
+    config: &ControlConfig,
+    namespace_id: &NamespaceId,
+) -> Result<()> {
+    let limit = limits(store, config, namespace_id)?.max_agents;
+    if store.count_agents_by_namespace(namespace_id)? >= limit {
+        return Err(ControlError::NamespaceQuotaExceeded {
+            namespace_id: *namespace_id,
+            limit,
+        });
+    }
+    Ok(())
+}
+
+/// Count active sessions across every agent in a namespace.
+pub(crate) fn count_active_sessions<S: Store>(
+    store: &S,
+    namespace_id: &NamespaceId,
+) -> Result<usize> {
+    let mut active = 0;
+    for agent in store.list_agents_by_namespace(namespace_id)? {
+        active += store
+            .list_sessions_by_agent(&agent.agent_id)?
+            .iter()
+            .filter(|s| s.status == SessionStatus::Active)
+            .count();
+    }
+    Ok(active)
+}
+
+/// Current usage and effective limits of a namespace.
+pub(crate) fn usage<S: Store>(
+    store: &S,
+    config: &ControlConfig,
+    namespace_id: &NamespaceId,
+) -> Result<NamespaceUsage> {
+    let limits = limits(store, config, namespace_id)?;
+    let active_sessions = count_active_sessions(store, namespace_id)?;
+
+    Ok(NamespaceUsage {
+        namespace_id: *namespace_id,
+        agents: store.count_agents_by_namespace(namespace_id)?,
+        max_agents: limits.max_agents,
+        active_sessions: u32::try_from(active_sessions).unwrap_or(u32::MAX),
+        max_sessions: limits.max_sessions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::{AgentId, UserId};
+    use aura_swarm_store::{AgentSpec, AgentState, RestartPolicy, RestartState};
+    use chrono::Utc;
+
+    fn namespace(byte: u8) -> NamespaceId {
+        NamespaceId::from_uuid(uuid::Uuid::from_bytes([byte; 16]))
+    }
+
+    fn agent(namespace_id: Option<NamespaceId>) -> Agent {
+        Agent {
+            agent_id: AgentId::from_bytes([9u8; 32]),
+            user_id: UserId::from_bytes([1u8; 32]),
+            namespace_id,
+            name: "agent".to_string(),
+            status: AgentState::Running,
+            spec: AgentSpec::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_heartbeat_at: None,
+            error_message: None,
+            cloned_from: None,
+            template: None,
+            labels: std::collections::BTreeMap::new(),
+            restart_policy: RestartPolicy::Never,
+            restarts: RestartState::default(),
+            hibernation: None,
+            provisioning_started_at: None,
+        }
+    }
+
+    #[test]
+    fn other_namespaces_are_out_of_scope() {
+        let caller = Caller::member(UserId::from_bytes([1u8; 32]), namespace(2));
+
+        assert!(!in_scope(&caller, &agent(Some(namespace(1)))));
+        assert!(in_scope(&caller, &agent(Some(namespace(2)))));
+        assert!(in_scope(&caller, &agent(None)));
+        assert!(in_scope(&owner_of(&agent(None)), &agent(None)));
+    }
+
+    #[test]
+    fn unscoped_agents_are_visible_only_to_their_owner() {
+        let stranger = Caller::member(UserId::from_bytes([2u8; 32]), namespace(2));
+        let admin = Caller::namespace_admin(UserId::from_bytes([3u8; 32]), namespace(2));
+
+        assert!(!in_scope(&stranger, &agent(None)));
+        assert!(!in_scope(&admin, &agent(None)));
+    }
+}
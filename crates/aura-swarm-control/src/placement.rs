@@ -0,0 +1,206 @@
+//! Validation for agent node placement.
+//!
+//! An agent's [`AgentPlacement`] is layered on top of the scheduler's
+//! defaults for its isolation level and copied into its pod spec. This module
+//! checks it when the spec is set, so Kubernetes never sees a malformed
+//! selector or toleration, agents cannot tolerate every taint, and they
+//! cannot claim system priority classes. Which selector keys, taints and
+//! priority classes an agent may use is up to the scheduler's allowlists.
+
+use aura_swarm_store::{AgentPlacement, PlacementToleration, TolerationOperator, TopologySpread};
+
+use crate::error::{ControlError, Result};
+
+/// Maximum number of tolerations or topology spread constraints.
+pub const MAX_PLACEMENT_RULES: usize = 16;
+
+/// Prefix of the priority classes Kubernetes reserves for itself.
+const SYSTEM_PRIORITY_PREFIX: &str = "system-";
+
+/// Validate an agent's placement.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if any part is invalid.
+pub fn validate(placement: &AgentPlacement) -> Result<()> {
+    for (key, value) in &placement.node_selector {
+        validate_label_key(key)?;
+        validate_label_value(value)?;
+    }
+
+    if placement.tolerations.len() > MAX_PLACEMENT_RULES
+        || placement.topology_spread.len() > MAX_PLACEMENT_RULES
+    {
+        return Err(ControlError::InvalidRequest(format!(
+            "placement allows at most {MAX_PLACEMENT_RULES} tolerations and topology spreads"
+        )));
+    }
+    placement
+        .tolerations
+        .iter()
+        .try_for_each(validate_toleration)?;
+    placement
+        .topology_spread
+        .iter()
+        .try_for_each(validate_topology_spread)?;
+
+    if let Some(class) = &placement.priority_class {
+        if !is_dns_subdomain(class) || class.starts_with(SYSTEM_PRIORITY_PREFIX) {
+            return Err(ControlError::InvalidRequest(format!(
+                "invalid priority class '{class}'"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_toleration(toleration: &PlacementToleration) -> Result<()> {
+    // A keyless toleration tolerates every taint
+    if toleration.key.is_empty() {
+        return Err(ControlError::InvalidRequest(
+            "a toleration needs a key".to_string(),
+        ));
+    }
+    if toleration.operator == TolerationOperator::Exists && toleration.value.is_some() {
+        return Err(ControlError::InvalidRequest(
+            "a toleration with operator 'exists' cannot have a value".to_string(),
+        ));
+    }
+    validate_label_key(&toleration.key)?;
+    if let Some(value) = &toleration.value {
+        validate_label_value(value)?;
+    }
+    Ok(())
+}
+
+fn validate_topology_spread(spread: &TopologySpread) -> Result<()> {
+    validate_label_key(&spread.topology_key)?;
+    if spread.max_skew == 0 {
+        return Err(ControlError::InvalidRequest(
+            "topology spread max_skew must be greater than zero".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check a label key: an optional DNS subdomain prefix and a name.
+fn validate_label_key(key: &str) -> Result<()> {
+    let (prefix, name) = match key.split_once('/') {
+        Some((prefix, name)) => (Some(prefix), name),
+        None => (None, key),
+    };
+    let valid = prefix.is_none_or(is_dns_subdomain) && !name.is_empty() && is_label_value(name);
+    if !valid {
+        return Err(ControlError::InvalidRequest(format!(
+            "invalid label key '{key}'"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_label_value(value: &str) -> Result<()> {
+    if !is_label_value(value) {
+        return Err(ControlError::InvalidRequest(format!(
+            "invalid label value '{value}'"
+        )));
+    }
+    Ok(())
+}
+
+/// Up to 63 alphanumerics, `-`, `_`, and `.`, starting and ending with an
+/// alphanumeric. Empty is allowed.
+fn is_label_value(value: &str) -> bool {
+    value.is_empty()
+        || (value.len() <= 63
+            && value.starts_with(|c: char| c.is_ascii_alphanumeric())
+            && value.ends_with(|c: char| c.is_ascii_alphanumeric())
+            && value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+}
+
+/// Up to 253 lowercase alphanumerics, `-`, and `.`, starting and ending with
+/// an alphanumeric.
+fn is_dns_subdomain(value: &str) -> bool {
+    !value.is_empty()
+        && value.len() <= 253
+        && value.starts_with(|c: char| c.is_ascii_alphanumeric())
+        && value.ends_with(|c: char| c.is_ascii_alphanumeric())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_store::TaintEffect;
+
+    fn placement() -> AgentPlacement {
+        let mut placement = AgentPlacement::default();
+        placement
+            .node_selector
+            .insert("node.kubernetes.io/pool".to_string(), "kata".to_string());
+        placement.tolerations.push(PlacementToleration {
+            key: "swarm.io/agents".to_string(),
+            operator: TolerationOperator::Exists,
+            value: None,
+            effect: Some(TaintEffect::NoSchedule),
+        });
+        placement.topology_spread.push(TopologySpread {
+            topology_key: "topology.kubernetes.io/zone".to_string(),
+            max_skew: 1,
+            required: false,
+        });
+        placement.priority_class = Some("agents-high".to_string());
+        placement
+    }
+
+    #[test]
+    fn accepts_valid_placement() {
+        assert!(validate(&AgentPlacement::default()).is_ok());
+        assert!(validate(&placement()).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_placement() {
+        let mut bad_selector = placement();
+        bad_selector
+            .node_selector
+            .insert("Bad Key/x".to_string(), "v".to_string());
+
+        let mut bad_toleration = placement();
+        bad_toleration.tolerations[0].value = Some("x".to_string());
+
+        let mut keyless_equal = placement();
+        keyless_equal.tolerations[0] = PlacementToleration {
+            key: String::new(),
+            operator: TolerationOperator::Equal,
+            value: Some("x".to_string()),
+            effect: None,
+        };
+
+        let mut keyless_exists = placement();
+        keyless_exists.tolerations[0].key = String::new();
+
+        let mut zero_skew = placement();
+        zero_skew.topology_spread[0].max_skew = 0;
+
+        let mut system_priority = placement();
+        system_priority.priority_class = Some("system-cluster-critical".to_string());
+
+        for placement in [
+            bad_selector,
+            bad_toleration,
+            keyless_equal,
+            keyless_exists,
+            zero_skew,
+            system_priority,
+        ] {
+            assert!(
+                matches!(validate(&placement), Err(ControlError::InvalidRequest(_))),
+                "accepted {placement:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! Drift detection between the store and the scheduler.
+//!
+//! The store records what each agent *should* be doing; the scheduler
+//! reports which pods actually exist. The two can disagree when pods are
+//! deleted out of band, a `schedule_agent` call fails after the agent was
+//! recorded, or an agent record is removed while its pod keeps running.
+//!
+//! [`plan_corrections`] compares the two views and decides what to fix. The
+//! control plane's drift reconciler executes the plan and reports every
+//! correction it made in a [`DriftReport`].
+//!
+//! | Store                               | Scheduler  | Correction               |
+//! |-------------------------------------|------------|--------------------------|
+//! | no agent record                     | pod        | terminate orphan pod     |
+//! | `Stopped` / `Hibernating`           | active pod | terminate unwanted pod   |
+//! | `Provisioning` / `Running` / `Idle` | no pod     | re-schedule (or `Error`) |
+//! | `Stopping`                          | no pod     | mark `Stopped`           |
+//!
+//! Agents updated within the grace period are left alone so the reconciler
+//! never races an in-flight lifecycle operation.
+
+use std::collections::{HashMap, HashSet};
+
+use aura_swarm_core::AgentId;
+use aura_swarm_store::{Agent, AgentState};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+
+use crate::scheduler_client::PodSummary;
+
+/// A correction applied by the drift reconciler.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftAction {
+    /// A pod with no matching agent record was terminated.
+    TerminateOrphanPod,
+    /// A pod for an agent that should not be running was terminated.
+    TerminateUnwantedPod,
+    /// An agent that should be running had no pod and was re-scheduled.
+    Reschedule,
+    /// A stopping agent whose pod is already gone was marked `Stopped`.
+    MarkStopped,
+}
+
+impl DriftAction {
+    /// The action's wire name.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::TerminateOrphanPod => "terminate_orphan_pod",
+            Self::TerminateUnwantedPod => "terminate_unwanted_pod",
+            Self::Reschedule => "reschedule",
+            Self::MarkStopped => "mark_stopped",
+        }
+    }
+}
+
+/// A single drift correction and its outcome.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DriftCorrection {
+    /// Agent the correction applies to.
+    pub agent_id: AgentId,
+    /// What the reconciler did.
+    pub action: DriftAction,
+    /// Agent state before the correction (`None` for orphan pods).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_state: Option<AgentState>,
+    /// Pod involved, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pod_name: Option<String>,
+    /// Agent state after the correction, if it changed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_state: Option<AgentState>,
+    /// Error if the correction could not be applied.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl DriftCorrection {
+    fn planned(agent_id: AgentId, action: DriftAction) -> Self {
+        Self {
+            agent_id,
+            action,
+            agent_state: None,
+            pod_name: None,
+            new_state: None,
+            error: None,
+        }
+    }
+}
+
+/// Result of one reconciliation pass.
+#[derive(Debug, Clone, Serialize)]
+pub struct DriftReport {
+    /// When the pass started.
+    pub started_at: DateTime<Utc>,
+    /// When the pass finished.
+    pub finished_at: DateTime<Utc>,
+    /// Number of agent records compared.
+    pub agents_checked: usize,
+    /// Number of pods compared.
+    pub pods_checked: usize,
+    /// Every correction made, in the order it was applied.
+    pub corrections: Vec<DriftCorrection>,
+}
+
+impl DriftReport {
+    /// Whether the store and scheduler agreed.
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.corrections.is_empty()
+    }
+
+    /// Number of corrections that failed to apply.
+    #[must_use]
+    pub fn failures(&self) -> usize {
+        self.corrections
+            .iter()
+            .filter(|c| c.error.is_some())
+            .count()
+    }
+}
+
+/// Decide which corrections are needed to bring the store and scheduler
+/// back into agreement.
+///
+/// Agents whose `updated_at` is within `grace` of `now` are skipped. Pods
+/// without an agent record are always reported.
+#[must_use]
+pub fn plan_corrections(
+    agents: &[Agent],
+    pods: &[PodSummary],
+    now: DateTime<Utc>,
+    grace: Duration,
+) -> Vec<DriftCorrection> {
+    let agents_by_id: HashMap<AgentId, &Agent> =
+        agents.iter().map(|agent| (agent.agent_id, agent)).collect();
+    let pod_owners: HashSet<AgentId> = pods.iter().map(|pod| pod.agent_id).collect();
+    let settled = |agent: &Agent| now - agent.updated_at >= grace;
+
+    let mut corrections = Vec::new();
+
+    for pod in pods {
+        let action = match agents_by_id.get(&pod.agent_id) {
+            None => DriftAction::TerminateOrphanPod,
+            Some(agent)
+                if matches!(agent.status, AgentState::Stopped | AgentState::Hibernating)
+                    && is_active_phase(&pod.status.phase)
+                    && settled(agent) =>
+            {
+                DriftAction::TerminateUnwantedPod
+            }
+            Some(_) => continue,
+        };
+
+        let mut correction = DriftCorrection::planned(pod.agent_id, action);
+        correction.agent_state = agents_by_id.get(&pod.agent_id).map(|a| a.status);
+        correction.pod_name = Some(pod.pod_name.clone());
+        corrections.push(correction);
+    }
+
+    for agent in agents {
+        if pod_owners.contains(&agent.agent_id) || !settled(agent) {
+            continue;
+        }
+
+        let action = match agent.status {
+            AgentState::Provisioning | AgentState::Running | AgentState::Idle => {
+                DriftAction::Reschedule
+            }
+            AgentState::Stopping => DriftAction::MarkStopped,
+            AgentState::Hibernating | AgentState::Stopped | AgentState::Error => continue,
+        };
+
+        let mut correction = DriftCorrection::planned(agent.agent_id, action);
+        correction.agent_state = Some(agent.status);
+        corrections.push(correction);
+    }
+
+    corrections
+}
+
+/// Whether a scheduler-reported phase means the pod is (or will be) running.
+///
+/// Phases are matched case-insensitively since the scheduler reports them in
+/// `snake_case` while Kubernetes uses `PascalCase`.
+fn is_active_phase(phase: &str) -> bool {
+    phase.eq_ignore_ascii_case("pending") || phase.eq_ignore_ascii_case("running")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler_client::PodStatusResponse;
+    use aura_swarm_core::UserId;
+    use aura_swarm_store::{AgentSpec, RestartPolicy, RestartState};
+
+    fn agent(byte: u8, status: AgentState, updated_at: DateTime<Utc>) -> Agent {
+        Agent {
+            agent_id: AgentId::from_bytes([byte; 32]),
+            user_id: UserId::from_bytes([1u8; 32]),
+            namespace_id: None,
+            name: format!("agent-{byte}"),
+            status,
+            spec: AgentSpec::default(),
+            created_at: updated_at,
+            updated_at,
+            last_heartbeat_at: None,
+            error_message: None,
+            cloned_from: None,
+            template: None,
+            labels: std::collections::BTreeMap::new(),
+            restart_policy: RestartPolicy::Never,
+            restarts: RestartState::default(),
+            hibernation: None,
+            provisioning_started_at: None,
+        }
+    }
+
+    fn pod(byte: u8, phase: &str) -> PodSummary {
+        PodSummary {
+            agent_id: AgentId::from_bytes([byte; 32]),
+            pod_name: format!("agent-{byte}"),
+            node_name: None,
+            status: PodStatusResponse {
+                phase: phase.to_string(),
+                ready: phase == "running",
+                restart_count: 0,
+                message: None,
+            },
+        }
+    }
+
+    #[test]
+    fn agreement_needs_no_corrections() {
+        let now = Utc::now();
+        let old = now - Duration::minutes(10);
+        let agents = vec![
+            agent(1, AgentState::Running, old),
+            agent(2, AgentState::Stopped, old),
+            agent(3, AgentState::Error, old),
+        ];
+        let pods = vec![pod(1, "running")];
+
+        assert!(plan_corrections(&agents, &pods, now, Duration::minutes(3)).is_empty());
+    }
+
+    #[test]
+    fn detects_each_kind_of_drift() {
+        let now = Utc::now();
+        let old = now - Duration::minutes(10);
+        let agents = vec![
+            agent(1, AgentState::Running, old),
+            agent(2, AgentState::Provisioning, old),
+            agent(3, AgentState::Stopping, old),
+            agent(4, AgentState::Hibernating, old),
+        ];
+        let pods = vec![pod(4, "running"), pod(9, "pending")];
+
+        let plan = plan_corrections(&agents, &pods, now, Duration::minutes(3));
+        let actions: Vec<_> = plan.iter().map(|c| (c.agent_id, c.action)).collect();
+
+        assert_eq!(
+            actions,
+            vec![
+                (
+                    AgentId::from_bytes([4; 32]),
+                    DriftAction::TerminateUnwantedPod
+                ),
+                (
+                    AgentId::from_bytes([9; 32]),
+                    DriftAction::TerminateOrphanPod
+                ),
+                (AgentId::from_bytes([1; 32]), DriftAction::Reschedule),
+                (AgentId::from_bytes([2; 32]), DriftAction::Reschedule),
+                (AgentId::from_bytes([3; 32]), DriftAction::MarkStopped),
+            ]
+        );
+        assert_eq!(plan[1].agent_state, None);
+        assert_eq!(plan[1].pod_name.as_deref(), Some("agent-9"));
+    }
+
+    #[test]
+    fn recently_updated_agents_are_left_alone() {
+        let now = Utc::now();
+        let recent = now - Duration::seconds(30);
+        let agents = vec![
+            agent(1, AgentState::Provisioning, recent),
+            agent(2, AgentState::Stopped, recent),
+        ];
+        let pods = vec![pod(2, "running")];
+
+        assert!(plan_corrections(&agents, &pods, now, Duration::minutes(3)).is_empty());
+    }
+}
@@ -0,0 +1,321 @@
+//! Fleet-wide runtime version rollouts.
+//!
+//! A rollout moves every agent matching a [`RolloutSelector`] to a new
+//! runtime version. The targeted agents are fixed when the rollout is
+//! created; the control plane's rollout controller then advances it on
+//! every tick:
+//!
+//! 1. Restarted agents still `updating` are checked with the scheduler's
+//!    pod status. A ready pod succeeds the target; a failed pod, an agent in
+//!    `Error`, or a pod that is not ready within the ready timeout fails it.
+//! 2. Once more than `max_failures` targets have failed, the rollout halts
+//!    (and, with `rollback_on_failure`, moves updated agents back).
+//! 3. While any target is still `updating`, nothing else happens.
+//! 4. Otherwise, after pausing `pause_seconds` since the previous batch, the
+//!    next `max_unavailable` pending agents are updated. Agents with a pod
+//!    are restarted on the new version; the rest pick it up on their next
+//!    start and succeed immediately.
+//!
+//! The rollout completes when no pending or updating targets remain.
+
+use aura_swarm_core::{IdentityId, RolloutId};
+use aura_swarm_store::{
+    Agent, Rollout, RolloutSelector, RolloutStatus, RolloutTarget, RolloutTargetState,
+};
+use chrono::{DateTime, Utc};
+
+use crate::error::{ControlError, Result};
+use crate::types::CreateRolloutRequest;
+
+/// Whether an agent is selected by a rollout for `target_version`.
+///
+/// Agents already on the target version are never selected.
+#[must_use]
+pub fn selects(selector: &RolloutSelector, target_version: &str, agent: &Agent) -> bool {
+    agent.spec.runtime_version != target_version
+        && selector
+            .runtime_version
+            .as_ref()
+            .is_none_or(|version| agent.spec.runtime_version == *version)
+        && selector
+            .labels
+            .iter()
+            .all(|(key, value)| agent.labels.get(key) == Some(value))
+}
+
+/// Maximum length of a Docker image tag.
+const MAX_VERSION_LEN: usize = 128;
+
+/// Check that a runtime version is a valid Docker image tag.
+///
+/// The scheduler uses the version as the tag of the runtime image, so it
+/// must match `[A-Za-z0-9_][A-Za-z0-9_.-]{0,127}`.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the version is not a valid tag.
+pub fn validate_version(version: &str) -> Result<()> {
+    let mut chars = version.chars();
+    let valid = version.len() <= MAX_VERSION_LEN
+        && chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'));
+    if !valid {
+        return Err(ControlError::InvalidRequest(format!(
+            "runtime version '{version}' is not a valid image tag"
+        )));
+    }
+    Ok(())
+}
+
+/// Build a new rollout targeting every selected agent, in creation order.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the request is invalid or no
+/// agent matches the selector.
+pub fn plan(
+    request: CreateRolloutRequest,
+    agents: &[Agent],
+    created_by: IdentityId,
+    now: DateTime<Utc>,
+) -> Result<Rollout> {
+    let target_version = request.target_version.trim().to_string();
+    if target_version.is_empty() {
+        return Err(ControlError::InvalidRequest(
+            "target_version must not be empty".to_string(),
+        ));
+    }
+    validate_version(&target_version)?;
+    if request.strategy.max_unavailable == 0 {
+        return Err(ControlError::InvalidRequest(
+            "max_unavailable must be at least 1".to_string(),
+        ));
+    }
+
+    let mut selected: Vec<&Agent> = agents
+        .iter()
+        .filter(|agent| selects(&request.selector, &target_version, agent))
+        .collect();
+    if selected.is_empty() {
+        return Err(ControlError::InvalidRequest(
+            "no agents match the rollout selector".to_string(),
+        ));
+    }
+    selected.sort_by_key(|agent| agent.created_at);
+
+    Ok(Rollout {
+        rollout_id: RolloutId::generate(),
+        target_version,
+        selector: request.selector,
+        strategy: request.strategy,
+        status: RolloutStatus::InProgress,
+        targets: selected
+            .into_iter()
+            .map(|agent| RolloutTarget {
+                agent_id: agent.agent_id,
+                previous_version: agent.spec.runtime_version.clone(),
+                state: RolloutTargetState::Pending,
+                restarted: false,
+                started_at: None,
+                error: None,
+            })
+            .collect(),
+        next_batch_at: None,
+        message: None,
+        created_by,
+        created_at: now,
+        updated_at: now,
+    })
+}
+
+/// Indexes of the targets to update in the next batch.
+#[must_use]
+pub fn next_batch(rollout: &Rollout) -> Vec<usize> {
+    let size = usize::try_from(rollout.strategy.max_unavailable).unwrap_or(usize::MAX);
+    rollout
+        .targets
+        .iter()
+        .enumerate()
+        .filter(|(_, target)| target.state == RolloutTargetState::Pending)
+        .map(|(index, _)| index)
+        .take(size)
+        .collect()
+}
+
+/// Whether more targets have failed than the rollout tolerates.
+#[must_use]
+pub fn exceeds_failure_budget(rollout: &Rollout) -> bool {
+    let failures = rollout.count(RolloutTargetState::Failed);
+    failures > usize::try_from(rollout.strategy.max_failures).unwrap_or(usize::MAX)
+}
+
+/// Whether a batch has already run, so the next one must wait for the pause.
+#[must_use]
+pub fn has_started(rollout: &Rollout) -> bool {
+    rollout
+        .targets
+        .iter()
+        .any(|target| target.state != RolloutTargetState::Pending)
+}
+
+/// Whether a target's runtime version was changed and may need reverting.
+#[must_use]
+pub const fn is_updated(state: RolloutTargetState) -> bool {
+    matches!(
+        state,
+        RolloutTargetState::Updating | RolloutTargetState::Succeeded | RolloutTargetState::Failed
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::{AgentId, UserId};
+    use aura_swarm_store::{AgentSpec, AgentState, RestartPolicy, RestartState, RolloutStrategy};
+    use std::collections::BTreeMap;
+
+    fn agent(byte: u8, version: &str, tier: &str) -> Agent {
+        Agent {
+            agent_id: AgentId::from_bytes([byte; 32]),
+            user_id: UserId::from_bytes([1u8; 32]),
+            namespace_id: None,
+            name: format!("agent-{byte}"),
+            status: AgentState::Running,
+            spec: AgentSpec {
+                runtime_version: version.to_string(),
+                ..AgentSpec::default()
+            },
+            created_at: DateTime::from_timestamp(i64::from(byte), 0).unwrap(),
+            updated_at: Utc::now(),
+            last_heartbeat_at: None,
+            error_message: None,
+            cloned_from: None,
+            template: None,
+            labels: BTreeMap::from([("tier".to_string(), tier.to_string())]),
+            restart_policy: RestartPolicy::Never,
+            restarts: RestartState::default(),
+            hibernation: None,
+            provisioning_started_at: None,
+        }
+    }
+
+    fn request(selector: RolloutSelector) -> CreateRolloutRequest {
+        CreateRolloutRequest {
+            target_version: "v2".to_string(),
+            selector,
+            strategy: RolloutStrategy {
+                max_unavailable: 2,
+                ..RolloutStrategy::default()
+            },
+        }
+    }
+
+    fn admin() -> IdentityId {
+        IdentityId::from_uuid(uuid::Uuid::nil())
+    }
+
+    #[test]
+    fn plan_selects_by_version_and_labels() {
+        let agents = [
+            agent(3, "v1", "canary"),
+            agent(1, "v1", "canary"),
+            agent(2, "v1", "prod"),
+            agent(4, "v0", "canary"),
+            agent(5, "v2", "canary"),
+        ];
+        let selector = RolloutSelector {
+            runtime_version: Some("v1".to_string()),
+            labels: BTreeMap::from([("tier".to_string(), "canary".to_string())]),
+        };
+
+        let rollout = plan(request(selector), &agents, admin(), Utc::now()).unwrap();
+        let targets: Vec<_> = rollout.targets.iter().map(|t| t.agent_id).collect();
+        assert_eq!(
+            targets,
+            vec![AgentId::from_bytes([1; 32]), AgentId::from_bytes([3; 32])]
+        );
+        assert_eq!(rollout.targets[0].previous_version, "v1");
+        assert_eq!(rollout.status, RolloutStatus::InProgress);
+
+        // Without a selector every agent not yet on v2 is targeted
+        let rollout = plan(
+            request(RolloutSelector::default()),
+            &agents,
+            admin(),
+            Utc::now(),
+        )
+        .unwrap();
+        assert_eq!(rollout.targets.len(), 4);
+    }
+
+    #[test]
+    fn plan_rejects_invalid_requests() {
+        let agents = [agent(1, "v1", "prod")];
+
+        let mut empty_version = request(RolloutSelector::default());
+        empty_version.target_version = " ".to_string();
+        assert!(plan(empty_version, &agents, admin(), Utc::now()).is_err());
+
+        let mut bad_tag = request(RolloutSelector::default());
+        bad_tag.target_version = "v2:evil".to_string();
+        assert!(plan(bad_tag, &agents, admin(), Utc::now()).is_err());
+
+        let mut no_batch = request(RolloutSelector::default());
+        no_batch.strategy.max_unavailable = 0;
+        assert!(plan(no_batch, &agents, admin(), Utc::now()).is_err());
+
+        let nothing = RolloutSelector {
+            runtime_version: Some("v9".to_string()),
+            ..RolloutSelector::default()
+        };
+        assert!(plan(request(nothing), &agents, admin(), Utc::now()).is_err());
+    }
+
+    #[test]
+    fn versions_must_be_image_tags() {
+        for version in ["latest", "v1.2.3", "1.0", "_canary-2", &"a".repeat(128)] {
+            assert!(validate_version(version).is_ok(), "{version}");
+        }
+        for version in [
+            "",
+            ".v1",
+            "-v1",
+            "v1:tag",
+            "v1@sha256",
+            "v 1",
+            "../x",
+            &"a".repeat(129),
+        ] {
+            assert!(validate_version(version).is_err(), "{version}");
+        }
+    }
+
+    #[test]
+    fn batches_and_failure_budget() {
+        let agents = [
+            agent(1, "v1", "prod"),
+            agent(2, "v1", "prod"),
+            agent(3, "v1", "prod"),
+        ];
+        let mut rollout = plan(
+            request(RolloutSelector::default()),
+            &agents,
+            admin(),
+            Utc::now(),
+        )
+        .unwrap();
+        assert!(!has_started(&rollout));
+        assert_eq!(next_batch(&rollout), vec![0, 1]);
+
+        rollout.targets[0].state = RolloutTargetState::Succeeded;
+        rollout.targets[1].state = RolloutTargetState::Failed;
+        assert!(has_started(&rollout));
+        assert_eq!(next_batch(&rollout), vec![2]);
+        assert!(exceeds_failure_budget(&rollout));
+
+        rollout.strategy.max_failures = 1;
+        assert!(!exceeds_failure_budget(&rollout));
+    }
+}
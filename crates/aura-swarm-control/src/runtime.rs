@@ -0,0 +1,205 @@
+//! Client for the agent runtime's control endpoints.
+//!
+//! Before an agent's pod is removed for hibernation, the control plane asks
+//! the runtime to flush its state (spec 06 §6.1):
+//!
+//! ```text
+//! POST /hibernate
+//! → {"status": "hibernating", "state_saved": true, "state_size_bytes": 1048576}
+//! ```
+//!
+//! `state_size_bytes` is optional. The runtime exits on its own once it has
+//! responded.
+
+use std::time::Duration;
+
+use serde::Deserialize;
+use thiserror::Error;
+
+/// The runtime's acknowledgement of a hibernate request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HibernateAck {
+    /// Size of the flushed state, if the runtime reported it.
+    pub state_size_bytes: Option<u64>,
+}
+
+/// Errors from calls to an agent runtime.
+#[derive(Debug, Error)]
+pub enum RuntimeError {
+    /// The agent's pod has no endpoint to reach the runtime on.
+    #[error("agent pod has no endpoint")]
+    NoEndpoint,
+
+    /// The runtime could not be reached or timed out.
+    #[error("runtime request failed: {0}")]
+    Request(#[source] reqwest::Error),
+
+    /// The runtime answered with a non-success status.
+    #[error("runtime returned {0}")]
+    Status(reqwest::StatusCode),
+
+    /// The runtime's response body could not be parsed.
+    #[error("invalid hibernate response: {0}")]
+    InvalidResponse(#[source] reqwest::Error),
+
+    /// The runtime answered but did not confirm that its state was saved.
+    #[error("runtime did not save its state (status: {status}, state_saved: {state_saved})")]
+    StateNotSaved {
+        /// The status the runtime reported.
+        status: String,
+        /// Whether the runtime reported its state as saved.
+        state_saved: bool,
+    },
+}
+
+#[derive(Debug, Deserialize)]
+struct HibernateResponse {
+    status: String,
+    #[serde(default)]
+    state_saved: bool,
+    #[serde(default)]
+    state_size_bytes: Option<u64>,
+}
+
+/// HTTP client for agent runtimes.
+#[derive(Debug, Clone)]
+pub struct RuntimeClient {
+    client: reqwest::Client,
+}
+
+impl RuntimeClient {
+    /// Create a client whose requests time out after `timeout`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    #[must_use]
+    pub fn new(timeout: Duration) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(timeout)
+            .connect_timeout(Duration::from_secs(5))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { client }
+    }
+
+    /// Ask the runtime at `endpoint` (`host:port`) to flush its state and
+    /// prepare to exit.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RuntimeError`] if the runtime could not be reached, timed
+    /// out, or did not confirm that its state was saved.
+    pub async fn hibernate(&self, endpoint: &str) -> Result<HibernateAck, RuntimeError> {
+        let url = format!("http://{endpoint}/hibernate");
+        let response = self
+            .client
+            .post(&url)
+            .send()
+            .await
+            .map_err(RuntimeError::Request)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(RuntimeError::Status(status));
+        }
+
+        let body: HibernateResponse = response
+            .json()
+            .await
+            .map_err(RuntimeError::InvalidResponse)?;
+
+        if body.status != "hibernating" || !body.state_saved {
+            return Err(RuntimeError::StateNotSaved {
+                status: body.status,
+                state_saved: body.state_saved,
+            });
+        }
+
+        Ok(HibernateAck {
+            state_size_bytes: body.state_size_bytes,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// Start a runtime that answers `POST /hibernate` with `response`.
+    async fn runtime(response: ResponseTemplate) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hibernate"))
+            .respond_with(response)
+            .expect(1)
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn client() -> RuntimeClient {
+        RuntimeClient::new(Duration::from_secs(5))
+    }
+
+    #[tokio::test]
+    async fn hibernate_returns_state_size() {
+        let server = runtime(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "hibernating",
+            "state_saved": true,
+            "state_size_bytes": 4096,
+        })))
+        .await;
+
+        let ack = client()
+            .hibernate(&server.address().to_string())
+            .await
+            .unwrap();
+        assert_eq!(ack.state_size_bytes, Some(4096));
+    }
+
+    #[tokio::test]
+    async fn hibernate_requires_saved_state() {
+        let server = runtime(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": "hibernating",
+            "state_saved": false,
+        })))
+        .await;
+
+        let result = client().hibernate(&server.address().to_string()).await;
+        assert!(matches!(
+            result,
+            Err(RuntimeError::StateNotSaved {
+                state_saved: false,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn hibernate_rejects_error_status() {
+        let server = runtime(ResponseTemplate::new(503)).await;
+
+        let result = client().hibernate(&server.address().to_string()).await;
+        assert!(matches!(
+            result,
+            Err(RuntimeError::Status(
+                reqwest::StatusCode::SERVICE_UNAVAILABLE
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn hibernate_times_out() {
+        let server = runtime(ResponseTemplate::new(200).set_delay(Duration::from_secs(2))).await;
+
+        let result = RuntimeClient::new(Duration::from_millis(100))
+            .hibernate(&server.address().to_string())
+            .await;
+        assert!(matches!(result, Err(RuntimeError::Request(_))));
+    }
+}
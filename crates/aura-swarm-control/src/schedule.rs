@@ -0,0 +1,377 @@
+//! Cron evaluation for agent wake/hibernate schedules.
+//!
+//! Schedules are stored as plain strings (cron expressions and an IANA time
+//! zone name). This module parses and validates them and computes which
+//! action is due within a time window.
+//!
+//! Cron expressions may use the common 5-field form (`min hour dom month dow`)
+//! or the 6/7-field form with a leading seconds field. Numeric days of the
+//! week follow the usual cron numbering (0-6 from Sunday, with 7 also
+//! meaning Sunday) in both forms. Schedules may fire at most once a minute,
+//! so the seconds field, when given, must be a single value.
+
+use std::str::FromStr;
+
+use aura_swarm_store::{AgentSchedule, ScheduleAction, ScheduleRule};
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use cron::Schedule;
+
+use crate::error::{ControlError, Result};
+
+/// Day names indexed by their usual cron number, where 0 is Sunday.
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Parse a cron expression, accepting the 5-field form.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the expression is invalid.
+pub fn parse_cron(expr: &str) -> Result<Schedule> {
+    let expr = expr.trim();
+    let invalid = |reason: &str| {
+        ControlError::InvalidRequest(format!("invalid cron expression '{expr}': {reason}"))
+    };
+
+    let mut fields: Vec<String> = expr.split_whitespace().map(str::to_string).collect();
+    // The cron crate requires a seconds field; fire on the minute by default.
+    if fields.len() == 5 {
+        fields.insert(0, "0".to_string());
+    }
+    // The cron crate numbers days of the week 1-7 from Sunday, so numeric
+    // days are passed on as names instead.
+    if let Some(dow) = fields.get_mut(5) {
+        *dow = weekday_names(dow).ok_or_else(|| invalid("invalid day of week"))?;
+    }
+
+    Schedule::from_str(&fields.join(" ")).map_err(|e| invalid(&e.to_string()))
+}
+
+/// Rewrite the numeric items of a day-of-week field as day names.
+///
+/// Items that are already names (or `*`/`?`) are kept as they are. Returns
+/// `None` if a numeric item is out of range or malformed.
+fn weekday_names(field: &str) -> Option<String> {
+    let mut items = Vec::new();
+    for item in field.split(',') {
+        if item == "*" || item == "?" || item.chars().any(|c| c.is_ascii_alphabetic()) {
+            items.push(item.to_string());
+            continue;
+        }
+
+        let (base, step) = match item.split_once('/') {
+            Some((base, step)) => (base, Some(step.parse::<usize>().ok()?)),
+            None => (item, None),
+        };
+        let (start, end) = match (base, base.split_once('-')) {
+            ("*", _) => (0, 6),
+            (_, Some((start, end))) => (start.parse().ok()?, end.parse().ok()?),
+            (_, None) => {
+                let day: usize = base.parse().ok()?;
+                (day, if step.is_some() { 7 } else { day })
+            }
+        };
+        if start > end || end > 7 || step == Some(0) {
+            return None;
+        }
+
+        let mut days: Vec<usize> = (start..=end)
+            .step_by(step.unwrap_or(1))
+            .map(|day| day % 7)
+            .collect();
+        days.sort_unstable();
+        days.dedup();
+        items.extend(days.into_iter().map(|day| WEEKDAYS[day].to_string()));
+    }
+    Some(items.join(","))
+}
+
+/// Parse an IANA time zone name.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the time zone is unknown.
+pub fn parse_timezone(name: &str) -> Result<Tz> {
+    name.parse::<Tz>()
+        .map_err(|_| ControlError::InvalidRequest(format!("unknown time zone '{name}'")))
+}
+
+/// Validate a time zone and set of rules.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if any part is invalid or a rule
+/// fires more than once a minute.
+pub fn validate(timezone: &str, rules: &[ScheduleRule]) -> Result<()> {
+    parse_timezone(timezone)?;
+    for rule in rules {
+        parse_cron(&rule.cron)?;
+
+        let fields: Vec<&str> = rule.cron.split_whitespace().collect();
+        if fields.len() > 5 && fields[0].parse::<u8>().is_err() {
+            return Err(ControlError::InvalidRequest(format!(
+                "invalid cron expression '{}': fires more than once a minute",
+                rule.cron.trim()
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Find the latest fire time in `(after, until]` across all rules.
+///
+/// When rules fire at the same instant, the one listed last wins. Each rule
+/// is walked backwards from `until`, so a long window costs no more than a
+/// short one.
+///
+/// # Errors
+///
+/// Returns an error if the stored schedule is invalid.
+pub fn latest_due(
+    schedule: &AgentSchedule,
+    after: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Result<Option<(ScheduleAction, DateTime<Utc>)>> {
+    let tz = parse_timezone(&schedule.timezone)?;
+    // Step back from just past `until` so a fire time at `until` is included.
+    let end = (until + chrono::Duration::seconds(1)).with_timezone(&tz);
+
+    let mut latest: Option<(ScheduleAction, DateTime<Utc>)> = None;
+    for rule in &schedule.rules {
+        let cron = parse_cron(&rule.cron)?;
+        let fired = cron
+            .after(&end)
+            .rev()
+            .map(|t| t.with_timezone(&Utc))
+            .take_while(|t| *t > after)
+            .find(|t| *t <= until);
+
+        if let Some(at) = fired {
+            if latest.is_none_or(|(_, current)| at >= current) {
+                latest = Some((rule.action, at));
+            }
+        }
+    }
+
+    Ok(latest)
+}
+
+/// Compute the next `count` planned runs after `now`, in chronological order.
+///
+/// # Errors
+///
+/// Returns an error if the stored schedule is invalid.
+pub fn next_runs(
+    schedule: &AgentSchedule,
+    now: DateTime<Utc>,
+    count: usize,
+) -> Result<Vec<(ScheduleAction, DateTime<Utc>)>> {
+    let tz = parse_timezone(&schedule.timezone)?;
+    let start = now.with_timezone(&tz);
+
+    let mut runs = Vec::new();
+    for rule in &schedule.rules {
+        let cron = parse_cron(&rule.cron)?;
+        runs.extend(
+            cron.after(&start)
+                .take(count)
+                .map(|t| (rule.action, t.with_timezone(&Utc))),
+        );
+    }
+
+    runs.sort_by_key(|(_, at)| *at);
+    runs.truncate(count);
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::{AgentId, UserId};
+    use aura_swarm_store::MissedRunPolicy;
+    use chrono::{Datelike, TimeZone, Weekday};
+
+    fn schedule(timezone: &str, rules: Vec<ScheduleRule>) -> AgentSchedule {
+        let user_id = UserId::from_bytes([1u8; 32]);
+        AgentSchedule {
+            agent_id: AgentId::generate(&user_id, "scheduled"),
+            user_id,
+            timezone: timezone.to_string(),
+            rules,
+            enabled: true,
+            missed_runs: MissedRunPolicy::RunLatest,
+            last_checked_at: Utc::now(),
+            last_run: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn working_hours() -> Vec<ScheduleRule> {
+        vec![
+            ScheduleRule {
+                action: ScheduleAction::Wake,
+                cron: "0 8 * * Mon-Fri".to_string(),
+            },
+            ScheduleRule {
+                action: ScheduleAction::Hibernate,
+                cron: "0 19 * * Mon-Fri".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn validate_rejects_bad_input() {
+        assert!(validate("UTC", &working_hours()).is_ok());
+        assert!(validate("Mars/Olympus", &working_hours()).is_err());
+
+        let bad = vec![ScheduleRule {
+            action: ScheduleAction::Wake,
+            cron: "every morning".to_string(),
+        }];
+        assert!(matches!(
+            validate("UTC", &bad),
+            Err(ControlError::InvalidRequest(_))
+        ));
+
+        // At most once a minute
+        for cron in ["30 0 8 * * Mon-Fri", "0 */5 * * * *"] {
+            let rules = vec![ScheduleRule {
+                action: ScheduleAction::Wake,
+                cron: cron.to_string(),
+            }];
+            assert!(validate("UTC", &rules).is_ok(), "{cron}");
+        }
+        for cron in ["* * * * * *", "*/10 * * * * *", "0,30 * * * * *"] {
+            let rules = vec![ScheduleRule {
+                action: ScheduleAction::Wake,
+                cron: cron.to_string(),
+            }];
+            assert!(
+                matches!(
+                    validate("UTC", &rules),
+                    Err(ControlError::InvalidRequest(_))
+                ),
+                "{cron}"
+            );
+        }
+    }
+
+    #[test]
+    fn numeric_weekdays_count_from_sunday() {
+        let monday = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+
+        // 1-5 is Monday to Friday
+        let weekdays = parse_cron("0 8 * * 1-5").unwrap();
+        let days: Vec<_> = weekdays
+            .after(&monday)
+            .take(5)
+            .map(|t| t.weekday())
+            .collect();
+        assert_eq!(
+            days,
+            vec![
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+                Weekday::Mon
+            ]
+        );
+
+        // 0 and 7 are both Sunday
+        for expr in ["0 8 * * 0", "0 8 * * 7"] {
+            let next = parse_cron(expr).unwrap().after(&monday).next().unwrap();
+            assert_eq!(next.weekday(), Weekday::Sun);
+        }
+
+        // Names keep working, and out-of-range days are rejected
+        let next = parse_cron("0 8 * * Sat")
+            .unwrap()
+            .after(&monday)
+            .next()
+            .unwrap();
+        assert_eq!(next.weekday(), Weekday::Sat);
+        assert!(parse_cron("0 8 * * 8").is_err());
+        assert!(parse_cron("0 8 * * 5-2").is_err());
+    }
+
+    #[test]
+    fn latest_due_respects_timezone() {
+        let schedule = schedule("Europe/Berlin", working_hours());
+
+        // Monday 2024-01-15: 08:00 Berlin is 07:00 UTC
+        let after = Utc.with_ymd_and_hms(2024, 1, 15, 6, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 1, 15, 7, 30, 0).unwrap();
+
+        let due = latest_due(&schedule, after, until).unwrap();
+        assert_eq!(
+            due,
+            Some((
+                ScheduleAction::Wake,
+                Utc.with_ymd_and_hms(2024, 1, 15, 7, 0, 0).unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn latest_due_picks_most_recent_missed_run() {
+        let schedule = schedule("UTC", working_hours());
+
+        // Downtime spanning Monday wake and hibernate
+        let after = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 1, 15, 22, 0, 0).unwrap();
+
+        let (action, at) = latest_due(&schedule, after, until).unwrap().unwrap();
+        assert_eq!(action, ScheduleAction::Hibernate);
+        assert_eq!(at, Utc.with_ymd_and_hms(2024, 1, 15, 19, 0, 0).unwrap());
+
+        // Nothing due over the weekend
+        let after = Utc.with_ymd_and_hms(2024, 1, 13, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 1, 14, 23, 0, 0).unwrap();
+        assert!(latest_due(&schedule, after, until).unwrap().is_none());
+    }
+
+    #[test]
+    fn latest_due_window_bounds() {
+        let schedule = schedule("UTC", working_hours());
+        let wake = Utc.with_ymd_and_hms(2024, 1, 15, 8, 0, 0).unwrap();
+
+        // `until` is inclusive and `after` is exclusive
+        let due = latest_due(&schedule, wake - chrono::Duration::hours(1), wake).unwrap();
+        assert_eq!(due, Some((ScheduleAction::Wake, wake)));
+        let due = latest_due(&schedule, wake, wake + chrono::Duration::hours(1)).unwrap();
+        assert!(due.is_none());
+
+        // A window of years only looks at the end of it
+        let after = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        let (action, at) = latest_due(&schedule, after, wake).unwrap().unwrap();
+        assert_eq!(action, ScheduleAction::Wake);
+        assert_eq!(at, wake);
+    }
+
+    #[test]
+    fn next_runs_are_ordered() {
+        let schedule = schedule("UTC", working_hours());
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+
+        let runs = next_runs(&schedule, now, 3).unwrap();
+        assert_eq!(
+            runs,
+            vec![
+                (
+                    ScheduleAction::Hibernate,
+                    Utc.with_ymd_and_hms(2024, 1, 15, 19, 0, 0).unwrap()
+                ),
+                (
+                    ScheduleAction::Wake,
+                    Utc.with_ymd_and_hms(2024, 1, 16, 8, 0, 0).unwrap()
+                ),
+                (
+                    ScheduleAction::Hibernate,
+                    Utc.with_ymd_and_hms(2024, 1, 16, 19, 0, 0).unwrap()
+                ),
+            ]
+        );
+    }
+}
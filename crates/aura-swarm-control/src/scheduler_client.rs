@@ -2,16 +2,39 @@
 //!
 //! This module provides the `SchedulerClient` for making HTTP requests to the
 //! scheduler service to manage agent pod lifecycles.
+//!
+//! # Resilience
+//!
+//! [`HttpSchedulerClient`] applies a per-call timeout to every request and
+//! retries idempotent calls (`get_pod_status`, `get_pod_endpoint`,
+//! `get_pod_metrics`, `stream_pod_logs`, `terminate_agent`, `list_pods` and
+//! the agent secret calls) on transport
+//! errors and 5xx responses with jittered exponential backoff. A circuit
+//! breaker opens after consecutive failures to reach the scheduler; while
+//! open, calls fail fast with `ControlError::SchedulerUnavailable` instead of
+//! waiting on timeouts. Error responses do not trip the breaker: the
+//! scheduler answers 503 when the Kubernetes API fails, which says nothing
+//! about whether the scheduler itself is reachable.
 
-use std::time::Duration;
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use async_trait::async_trait;
 use aura_swarm_core::AgentId;
 use aura_swarm_store::AgentSpec;
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 
 use crate::error::{ControlError, Result};
 
+/// A stream of raw log lines from an agent's pod, each prefixed with the
+/// RFC 3339 time it was written.
+pub type LogLineStream = BoxStream<'static, Result<String>>;
+
 /// Trait for scheduler communication.
 ///
 /// This trait abstracts the scheduler client interface, allowing for
@@ -52,6 +75,88 @@ pub trait SchedulerClient: Send + Sync {
     ///
     /// Returns an error if the HTTP request fails.
     async fn get_pod_endpoint(&self, agent_id: &AgentId) -> Result<Option<String>>;
+
+    /// Get the current resource usage of an agent's pod.
+    ///
+    /// Returns `None` if the cluster's metrics API has no sample for the pod.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the metrics API cannot
+    /// be queried.
+    async fn get_pod_metrics(&self, agent_id: &AgentId) -> Result<Option<PodMetricsResponse>>;
+
+    /// Read the logs of an agent's runtime container.
+    ///
+    /// Returns at most `tail` of the most recent lines written at or after
+    /// `since`. With `follow`, the stream stays open and yields new lines as
+    /// they are written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::AgentNotFound` if the agent has no pod, or an
+    /// error if the HTTP request fails. Errors while reading are returned by
+    /// the stream.
+    async fn stream_pod_logs(
+        &self,
+        agent_id: &AgentId,
+        tail: Option<u32>,
+        since: Option<DateTime<Utc>>,
+        follow: bool,
+    ) -> Result<LogLineStream>;
+
+    /// Copy one agent's persistent state directory into another agent's.
+    ///
+    /// Blocks until the scheduler reports that the copy has finished.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the copy job fails.
+    async fn copy_agent_state(&self, source: &AgentId, target: &AgentId) -> Result<()>;
+
+    /// Delete an agent's persistent state.
+    ///
+    /// Blocks until the scheduler reports that the cleanup has finished.
+    /// Deleting state that does not exist succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails or the cleanup job fails.
+    async fn delete_agent_state(&self, agent_id: &AgentId) -> Result<()>;
+
+    /// List every agent pod known to the scheduler.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails.
+    async fn list_pods(&self) -> Result<Vec<PodSummary>>;
+
+    /// Replace the Kubernetes Secret holding an agent's secrets.
+    ///
+    /// An empty map removes the Secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails.
+    async fn put_agent_secrets(
+        &self,
+        agent_id: &AgentId,
+        secrets: &BTreeMap<String, String>,
+    ) -> Result<()>;
+
+    /// Delete the Kubernetes Secret holding an agent's secrets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails.
+    async fn delete_agent_secrets(&self, agent_id: &AgentId) -> Result<()>;
+
+    /// Delete the Kubernetes `NetworkPolicy` restricting an agent's traffic.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP request fails.
+    async fn delete_agent_network_policy(&self, agent_id: &AgentId) -> Result<()>;
 }
 
 /// Response from the scheduler's pod status endpoint.
@@ -67,18 +172,192 @@ pub struct PodStatusResponse {
     pub message: Option<String>,
 }
 
+/// Response from the scheduler's pod metrics endpoint.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PodMetricsResponse {
+    /// CPU usage in millicores, summed across containers.
+    pub cpu_millicores: u64,
+    /// Memory working set in bytes, summed across containers.
+    pub memory_bytes: u64,
+    /// When the usage was sampled.
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// An agent pod as reported by the scheduler's pod listing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PodSummary {
+    /// Agent the pod belongs to.
+    pub agent_id: AgentId,
+    /// Kubernetes pod name.
+    pub pod_name: String,
+    /// Node the pod is scheduled on.
+    pub node_name: Option<String>,
+    /// Current pod status.
+    pub status: PodStatusResponse,
+}
+
+/// Timeout, retry, and circuit breaker settings for [`HttpSchedulerClient`].
+#[derive(Debug, Clone)]
+pub struct SchedulerClientConfig {
+    /// Timeout for establishing a connection.
+    pub connect_timeout: Duration,
+    /// Timeout for calls that change pods (`schedule_agent`, `terminate_agent`).
+    pub request_timeout: Duration,
+    /// Timeout for read-only calls (status, endpoint, pod listing).
+    pub query_timeout: Duration,
+    /// Timeout for state copies and deletions, which wait for a job to finish.
+    pub copy_state_timeout: Duration,
+    /// Longest a followed log stream stays open; clients reconnect after it.
+    pub log_follow_timeout: Duration,
+    /// Retries after the first attempt for idempotent calls.
+    pub max_retries: u32,
+    /// Backoff before the first retry; doubles per retry.
+    pub retry_base_delay: Duration,
+    /// Maximum backoff between retries.
+    pub retry_max_delay: Duration,
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a probe call is allowed.
+    pub open_duration: Duration,
+}
+
+impl Default for SchedulerClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(5),
+            request_timeout: Duration::from_secs(30),
+            query_timeout: Duration::from_secs(5),
+            copy_state_timeout: Duration::from_mins(15),
+            log_follow_timeout: Duration::from_hours(1),
+            max_retries: 3,
+            retry_base_delay: Duration::from_millis(100),
+            retry_max_delay: Duration::from_secs(2),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+/// State of the scheduler circuit breaker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Calls flow normally.
+    Closed,
+    /// Calls fail fast until the open period ends.
+    Open,
+    /// The open period has ended; the next call is a probe.
+    HalfOpen,
+}
+
+/// Circuit breaker guarding calls to the scheduler.
+///
+/// Only failures to reach the scheduler (transport errors and timeouts)
+/// count; any response, including a 5xx, means the scheduler is up.
+#[derive(Debug)]
+struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    probe_started: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn state(&self) -> CircuitState {
+        match self.lock().open_until {
+            None => CircuitState::Closed,
+            Some(until) if Instant::now() < until => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Check whether a call may proceed.
+    ///
+    /// Once the open period ends a single probe is let through; a probe that
+    /// never reports back is forgotten after another open period.
+    fn try_acquire(&self) -> Result<()> {
+        let now = Instant::now();
+        let mut state = self.lock();
+
+        let Some(until) = state.open_until else {
+            return Ok(());
+        };
+        if now < until {
+            return Err(ControlError::SchedulerUnavailable(format!(
+                "circuit open, retry in {}s",
+                (until - now).as_secs().max(1)
+            )));
+        }
+        if state
+            .probe_started
+            .is_some_and(|started| now.duration_since(started) < self.open_duration)
+        {
+            return Err(ControlError::SchedulerUnavailable(
+                "circuit half-open, probe in progress".to_string(),
+            ));
+        }
+
+        state.probe_started = Some(now);
+        Ok(())
+    }
+
+    fn record_success(&self) {
+        let mut state = self.lock();
+        if state.open_until.is_some() {
+            tracing::info!("Scheduler reachable again, closing circuit");
+        }
+        *state = BreakerState::default();
+    }
+
+    fn record_failure(&self) {
+        let mut state = self.lock();
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+
+        if state.probe_started.is_some() || state.consecutive_failures >= self.failure_threshold {
+            tracing::warn!(
+                consecutive_failures = state.consecutive_failures,
+                open_seconds = self.open_duration.as_secs(),
+                "Scheduler unreachable, opening circuit"
+            );
+            state.open_until = Some(Instant::now() + self.open_duration);
+            state.probe_started = None;
+        }
+    }
+}
+
 /// HTTP client for the scheduler service.
 ///
 /// This client makes HTTP requests to the scheduler service's REST API
-/// for managing agent pod lifecycles.
+/// for managing agent pod lifecycles. Clones share the same circuit breaker.
 #[derive(Debug, Clone)]
 pub struct HttpSchedulerClient {
     client: reqwest::Client,
     base_url: String,
+    config: SchedulerClientConfig,
+    breaker: Arc<CircuitBreaker>,
 }
 
 impl HttpSchedulerClient {
-    /// Create a new scheduler client.
+    /// Create a new scheduler client with default timeouts and retries.
     ///
     /// # Arguments
     ///
@@ -89,24 +368,44 @@ impl HttpSchedulerClient {
     /// Panics if the HTTP client cannot be created.
     #[must_use]
     pub fn new(base_url: impl Into<String>) -> Self {
+        Self::with_config(base_url, SchedulerClientConfig::default())
+    }
+
+    /// Create a new scheduler client with custom timeouts and retries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    #[must_use]
+    pub fn with_config(base_url: impl Into<String>, config: SchedulerClientConfig) -> Self {
         let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(30))
-            .connect_timeout(Duration::from_secs(5))
+            .connect_timeout(config.connect_timeout)
             .build()
             .expect("Failed to create HTTP client");
 
-        Self {
-            client,
-            base_url: base_url.into(),
-        }
+        Self::with_client_and_config(client, base_url, config)
     }
 
     /// Create a new scheduler client with a custom reqwest client.
     #[must_use]
     pub fn with_client(client: reqwest::Client, base_url: impl Into<String>) -> Self {
+        Self::with_client_and_config(client, base_url, SchedulerClientConfig::default())
+    }
+
+    fn with_client_and_config(
+        client: reqwest::Client,
+        base_url: impl Into<String>,
+        config: SchedulerClientConfig,
+    ) -> Self {
+        let breaker = Arc::new(CircuitBreaker::new(
+            config.failure_threshold,
+            config.open_duration,
+        ));
         Self {
             client,
             base_url: base_url.into(),
+            config,
+            breaker,
         }
     }
 
@@ -115,6 +414,133 @@ impl HttpSchedulerClient {
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
+
+    /// Get the current state of the circuit breaker.
+    #[must_use]
+    pub fn circuit_state(&self) -> CircuitState {
+        self.breaker.state()
+    }
+
+    /// Send a request through the circuit breaker, retrying if `idempotent`.
+    ///
+    /// Any HTTP response is returned to the caller once retries are exhausted,
+    /// so callers keep handling error statuses themselves.
+    async fn execute(
+        &self,
+        operation: &'static str,
+        timeout: Duration,
+        idempotent: bool,
+        build: impl Fn() -> reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response> {
+        let max_attempts = if idempotent {
+            self.config.max_retries.saturating_add(1)
+        } else {
+            1
+        };
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            self.breaker.try_acquire()?;
+
+            let outcome = build().timeout(timeout).send().await;
+            let retryable = if let Ok(response) = &outcome {
+                self.breaker.record_success();
+                response.status().is_server_error()
+            } else {
+                self.breaker.record_failure();
+                true
+            };
+
+            if !retryable || attempt >= max_attempts {
+                return outcome.map_err(|e| {
+                    ControlError::SchedulerUnavailable(format!("{operation} failed: {e}"))
+                });
+            }
+
+            let delay = retry_delay(&self.config, attempt);
+            tracing::debug!(
+                operation,
+                attempt,
+                delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                "Retrying scheduler request"
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Backoff before retry number `attempt` (1-based), with equal jitter.
+///
+/// The exponential delay is capped at `retry_max_delay`; half of it is fixed
+/// and the other half is random so that clients don't retry in lockstep.
+fn retry_delay(config: &SchedulerClientConfig, attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(20);
+    let capped = config
+        .retry_base_delay
+        .saturating_mul(1 << exponent)
+        .min(config.retry_max_delay);
+    let capped_ms = u64::try_from(capped.as_millis()).unwrap_or(u64::MAX);
+
+    let half = capped_ms / 2;
+    Duration::from_millis(rand::thread_rng().gen_range(half..=capped_ms))
+}
+
+/// Split a streamed response body into lines.
+///
+/// A final line without a trailing newline is still returned.
+fn response_lines(response: reqwest::Response) -> LogLineStream {
+    struct Lines {
+        response: Option<reqwest::Response>,
+        buffer: Vec<u8>,
+        ready: VecDeque<String>,
+    }
+
+    impl Lines {
+        fn take_line(&mut self, end: usize) {
+            let line: Vec<u8> = self.buffer.drain(..end).collect();
+            let line = line.strip_suffix(b"\n").unwrap_or(&line);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            self.ready
+                .push_back(String::from_utf8_lossy(line).into_owned());
+        }
+    }
+
+    let lines = Lines {
+        response: Some(response),
+        buffer: Vec::new(),
+        ready: VecDeque::new(),
+    };
+
+    futures::stream::unfold(lines, |mut lines| async move {
+        loop {
+            if let Some(line) = lines.ready.pop_front() {
+                return Some((Ok(line), lines));
+            }
+            let response = lines.response.as_mut()?;
+            match response.chunk().await {
+                Ok(Some(chunk)) => {
+                    lines.buffer.extend_from_slice(&chunk);
+                    while let Some(newline) = lines.buffer.iter().position(|&b| b == b'\n') {
+                        lines.take_line(newline + 1);
+                    }
+                }
+                Ok(None) => {
+                    lines.response = None;
+                    if !lines.buffer.is_empty() {
+                        lines.take_line(lines.buffer.len());
+                    }
+                }
+                Err(e) => {
+                    lines.response = None;
+                    let error =
+                        ControlError::SchedulerUnavailable(format!("log stream failed: {e}"));
+                    return Some((Err(error), lines));
+                }
+            }
+        }
+    })
+    .boxed()
 }
 
 /// Request body for scheduling an agent pod.
@@ -124,6 +550,18 @@ struct ScheduleRequest<'a> {
     spec: &'a AgentSpec,
 }
 
+/// Request body for copying agent state.
+#[derive(Debug, Serialize)]
+struct CopyStateRequest {
+    source_agent_id: String,
+}
+
+/// Request body for replacing an agent's secrets.
+#[derive(Debug, Serialize)]
+struct PutSecretsRequest<'a> {
+    secrets: &'a BTreeMap<String, String>,
+}
+
 /// Error response from the scheduler.
 #[derive(Debug, Deserialize)]
 struct ErrorResponse {
@@ -152,12 +590,10 @@ impl SchedulerClient for HttpSchedulerClient {
         };
 
         let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(|e| ControlError::Internal(format!("Scheduler request failed: {e}")))?;
+            .execute("schedule_agent", self.config.request_timeout, false, || {
+                self.client.post(&url).json(&request)
+            })
+            .await?;
 
         if response.status().is_success() {
             tracing::debug!(agent_id = %agent_id, "Scheduled agent via scheduler API");
@@ -187,11 +623,10 @@ impl SchedulerClient for HttpSchedulerClient {
         let url = format!("{}/v1/agents/{}", self.base_url, agent_id.to_hex());
 
         let response = self
-            .client
-            .delete(&url)
-            .send()
-            .await
-            .map_err(|e| ControlError::Internal(format!("Scheduler request failed: {e}")))?;
+            .execute("terminate_agent", self.config.request_timeout, true, || {
+                self.client.delete(&url)
+            })
+            .await?;
 
         if response.status().is_success() {
             tracing::debug!(agent_id = %agent_id, "Terminated agent via scheduler API");
@@ -221,11 +656,10 @@ impl SchedulerClient for HttpSchedulerClient {
         let url = format!("{}/v1/agents/{}/status", self.base_url, agent_id.to_hex());
 
         let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| ControlError::Internal(format!("Scheduler request failed: {e}")))?;
+            .execute("get_pod_status", self.config.query_timeout, true, || {
+                self.client.get(&url)
+            })
+            .await?;
 
         if response.status().is_success() {
             response
@@ -252,11 +686,10 @@ impl SchedulerClient for HttpSchedulerClient {
         let url = format!("{}/v1/agents/{}/endpoint", self.base_url, agent_id.to_hex());
 
         let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| ControlError::Internal(format!("Scheduler request failed: {e}")))?;
+            .execute("get_pod_endpoint", self.config.query_timeout, true, || {
+                self.client.get(&url)
+            })
+            .await?;
 
         if response.status().is_success() {
             #[derive(Deserialize)]
@@ -281,6 +714,280 @@ impl SchedulerClient for HttpSchedulerClient {
             )))
         }
     }
+
+    async fn get_pod_metrics(&self, agent_id: &AgentId) -> Result<Option<PodMetricsResponse>> {
+        let url = format!("{}/v1/agents/{}/metrics", self.base_url, agent_id.to_hex());
+
+        let response = self
+            .execute("get_pod_metrics", self.config.query_timeout, true, || {
+                self.client.get(&url)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            #[derive(Deserialize)]
+            struct MetricsResponse {
+                metrics: Option<PodMetricsResponse>,
+            }
+            let resp: MetricsResponse = response
+                .json()
+                .await
+                .map_err(|e| ControlError::Internal(format!("Failed to parse response: {e}")))?;
+            Ok(resp.metrics)
+        } else {
+            let status = response.status();
+            let error = response.json::<ErrorResponse>().await.map_or_else(
+                |_| format!("Scheduler returned status {status}"),
+                |e| e.error,
+            );
+
+            Err(ControlError::Internal(format!("Scheduler error: {error}")))
+        }
+    }
+
+    async fn stream_pod_logs(
+        &self,
+        agent_id: &AgentId,
+        tail: Option<u32>,
+        since: Option<DateTime<Utc>>,
+        follow: bool,
+    ) -> Result<LogLineStream> {
+        let url = format!("{}/v1/agents/{}/logs", self.base_url, agent_id.to_hex());
+
+        let mut query = vec![("follow", follow.to_string())];
+        if let Some(tail) = tail {
+            query.push(("tail", tail.to_string()));
+        }
+        if let Some(since) = since {
+            query.push(("since", since.to_rfc3339_opts(SecondsFormat::AutoSi, true)));
+        }
+
+        // The timeout covers reading the whole body, so followed streams get
+        // a much longer one
+        let timeout = if follow {
+            self.config.log_follow_timeout
+        } else {
+            self.config.request_timeout
+        };
+
+        let response = self
+            .execute("stream_pod_logs", timeout, true, || {
+                self.client.get(&url).query(&query)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            Ok(response_lines(response))
+        } else if response.status() == reqwest::StatusCode::NOT_FOUND {
+            Err(ControlError::AgentNotFound(*agent_id))
+        } else {
+            let status = response.status();
+            let error = response.json::<ErrorResponse>().await.map_or_else(
+                |_| format!("Scheduler returned status {status}"),
+                |e| e.error,
+            );
+
+            Err(ControlError::Internal(format!("Scheduler error: {error}")))
+        }
+    }
+
+    async fn copy_agent_state(&self, source: &AgentId, target: &AgentId) -> Result<()> {
+        let url = format!("{}/v1/agents/{}/state/copy", self.base_url, target.to_hex());
+
+        let request = CopyStateRequest {
+            source_agent_id: source.to_hex(),
+        };
+
+        let response = self
+            .execute(
+                "copy_agent_state",
+                self.config.copy_state_timeout,
+                false,
+                || self.client.post(&url).json(&request),
+            )
+            .await?;
+
+        if response.status().is_success() {
+            tracing::debug!(
+                source = %source,
+                target = %target,
+                "Copied agent state via scheduler API"
+            );
+            Ok(())
+        } else {
+            let status = response.status();
+            let error = response.json::<ErrorResponse>().await.map_or_else(
+                |_| format!("Scheduler returned status {status}"),
+                |e| e.error,
+            );
+
+            tracing::error!(
+                source = %source,
+                target = %target,
+                status = %status,
+                error = %error,
+                "Failed to copy agent state"
+            );
+
+            Err(ControlError::Internal(format!("Scheduler error: {error}")))
+        }
+    }
+
+    async fn delete_agent_state(&self, agent_id: &AgentId) -> Result<()> {
+        let url = format!("{}/v1/agents/{}/state", self.base_url, agent_id.to_hex());
+
+        let response = self
+            .execute(
+                "delete_agent_state",
+                self.config.copy_state_timeout,
+                true,
+                || self.client.delete(&url),
+            )
+            .await?;
+
+        if response.status().is_success() {
+            tracing::debug!(agent_id = %agent_id, "Deleted agent state via scheduler API");
+            Ok(())
+        } else {
+            let status = response.status();
+            let error = response.json::<ErrorResponse>().await.map_or_else(
+                |_| format!("Scheduler returned status {status}"),
+                |e| e.error,
+            );
+
+            tracing::error!(
+                agent_id = %agent_id,
+                status = %status,
+                error = %error,
+                "Failed to delete agent state"
+            );
+
+            Err(ControlError::Internal(format!("Scheduler error: {error}")))
+        }
+    }
+
+    async fn list_pods(&self) -> Result<Vec<PodSummary>> {
+        let url = format!("{}/v1/pods", self.base_url);
+
+        let response = self
+            .execute("list_pods", self.config.query_timeout, true, || {
+                self.client.get(&url)
+            })
+            .await?;
+
+        if response.status().is_success() {
+            response
+                .json::<Vec<PodSummary>>()
+                .await
+                .map_err(|e| ControlError::Internal(format!("Failed to parse response: {e}")))
+        } else {
+            let status = response.status();
+            let error = response.json::<ErrorResponse>().await.map_or_else(
+                |_| format!("Scheduler returned status {status}"),
+                |e| e.error,
+            );
+
+            Err(ControlError::Internal(format!("Scheduler error: {error}")))
+        }
+    }
+
+    async fn put_agent_secrets(
+        &self,
+        agent_id: &AgentId,
+        secrets: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        let url = format!("{}/v1/agents/{}/secrets", self.base_url, agent_id.to_hex());
+        let request = PutSecretsRequest { secrets };
+
+        let response = self
+            .execute(
+                "put_agent_secrets",
+                self.config.request_timeout,
+                true,
+                || self.client.put(&url).json(&request),
+            )
+            .await?;
+
+        if response.status().is_success() {
+            tracing::debug!(
+                agent_id = %agent_id,
+                count = secrets.len(),
+                "Wrote agent secrets via scheduler API"
+            );
+            Ok(())
+        } else {
+            let status = response.status();
+            let error = response.json::<ErrorResponse>().await.map_or_else(
+                |_| format!("Scheduler returned status {status}"),
+                |e| e.error,
+            );
+
+            tracing::error!(
+                agent_id = %agent_id,
+                status = %status,
+                error = %error,
+                "Failed to write agent secrets"
+            );
+
+            Err(ControlError::Internal(format!("Scheduler error: {error}")))
+        }
+    }
+
+    async fn delete_agent_secrets(&self, agent_id: &AgentId) -> Result<()> {
+        let url = format!("{}/v1/agents/{}/secrets", self.base_url, agent_id.to_hex());
+
+        let response = self
+            .execute(
+                "delete_agent_secrets",
+                self.config.request_timeout,
+                true,
+                || self.client.delete(&url),
+            )
+            .await?;
+
+        if response.status().is_success() {
+            tracing::debug!(agent_id = %agent_id, "Deleted agent secrets via scheduler API");
+            Ok(())
+        } else {
+            let status = response.status();
+            let error = response.json::<ErrorResponse>().await.map_or_else(
+                |_| format!("Scheduler returned status {status}"),
+                |e| e.error,
+            );
+
+            Err(ControlError::Internal(format!("Scheduler error: {error}")))
+        }
+    }
+
+    async fn delete_agent_network_policy(&self, agent_id: &AgentId) -> Result<()> {
+        let url = format!(
+            "{}/v1/agents/{}/network-policy",
+            self.base_url,
+            agent_id.to_hex()
+        );
+
+        let response = self
+            .execute(
+                "delete_agent_network_policy",
+                self.config.request_timeout,
+                true,
+                || self.client.delete(&url),
+            )
+            .await?;
+
+        if response.status().is_success() {
+            tracing::debug!(agent_id = %agent_id, "Deleted agent network policy via scheduler API");
+            Ok(())
+        } else {
+            let status = response.status();
+            let error = response.json::<ErrorResponse>().await.map_or_else(
+                |_| format!("Scheduler returned status {status}"),
+                |e| e.error,
+            );
+
+            Err(ControlError::Internal(format!("Scheduler error: {error}")))
+        }
+    }
 }
 
 /// A no-op scheduler client for when scheduler integration is disabled.
@@ -343,6 +1050,78 @@ impl SchedulerClient for NoopSchedulerClient {
         // Return a mock endpoint for local dev
         Ok(Some("localhost:8080".to_string()))
     }
+
+    async fn get_pod_metrics(&self, agent_id: &AgentId) -> Result<Option<PodMetricsResponse>> {
+        tracing::warn!(
+            agent_id = %agent_id,
+            "NoopSchedulerClient: get_pod_metrics called but no scheduler configured"
+        );
+        Ok(None)
+    }
+
+    async fn stream_pod_logs(
+        &self,
+        agent_id: &AgentId,
+        _tail: Option<u32>,
+        _since: Option<DateTime<Utc>>,
+        _follow: bool,
+    ) -> Result<LogLineStream> {
+        tracing::warn!(
+            agent_id = %agent_id,
+            "NoopSchedulerClient: stream_pod_logs called but no scheduler configured"
+        );
+        Ok(futures::stream::empty().boxed())
+    }
+
+    async fn copy_agent_state(&self, source: &AgentId, target: &AgentId) -> Result<()> {
+        tracing::warn!(
+            source = %source,
+            target = %target,
+            "NoopSchedulerClient: copy_agent_state called but no scheduler configured"
+        );
+        Ok(())
+    }
+
+    async fn delete_agent_state(&self, agent_id: &AgentId) -> Result<()> {
+        tracing::warn!(
+            agent_id = %agent_id,
+            "NoopSchedulerClient: delete_agent_state called but no scheduler configured"
+        );
+        Ok(())
+    }
+
+    async fn list_pods(&self) -> Result<Vec<PodSummary>> {
+        tracing::warn!("NoopSchedulerClient: list_pods called but no scheduler configured");
+        Ok(Vec::new())
+    }
+
+    async fn put_agent_secrets(
+        &self,
+        agent_id: &AgentId,
+        _secrets: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        tracing::warn!(
+            agent_id = %agent_id,
+            "NoopSchedulerClient: put_agent_secrets called but no scheduler configured"
+        );
+        Ok(())
+    }
+
+    async fn delete_agent_secrets(&self, agent_id: &AgentId) -> Result<()> {
+        tracing::warn!(
+            agent_id = %agent_id,
+            "NoopSchedulerClient: delete_agent_secrets called but no scheduler configured"
+        );
+        Ok(())
+    }
+
+    async fn delete_agent_network_policy(&self, agent_id: &AgentId) -> Result<()> {
+        tracing::warn!(
+            agent_id = %agent_id,
+            "NoopSchedulerClient: delete_agent_network_policy called but no scheduler configured"
+        );
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -359,5 +1138,324 @@ mod tests {
     fn http_client_creation() {
         let client = HttpSchedulerClient::new("http://localhost:8080");
         assert_eq!(client.base_url(), "http://localhost:8080");
+        assert_eq!(client.circuit_state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn retry_delay_is_jittered_and_capped() {
+        let config = SchedulerClientConfig {
+            retry_base_delay: Duration::from_millis(100),
+            retry_max_delay: Duration::from_millis(300),
+            ..SchedulerClientConfig::default()
+        };
+
+        for _ in 0..50 {
+            let first = retry_delay(&config, 1);
+            assert!(first >= Duration::from_millis(50) && first <= Duration::from_millis(100));
+            let capped = retry_delay(&config, 10);
+            assert!(capped >= Duration::from_millis(150) && capped <= Duration::from_millis(300));
+        }
+    }
+
+    mod http {
+        use super::*;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        fn agent_id() -> AgentId {
+            AgentId::from_bytes([7u8; 32])
+        }
+
+        fn fast_config() -> SchedulerClientConfig {
+            SchedulerClientConfig {
+                query_timeout: Duration::from_millis(200),
+                request_timeout: Duration::from_millis(200),
+                max_retries: 2,
+                retry_base_delay: Duration::from_millis(1),
+                retry_max_delay: Duration::from_millis(5),
+                failure_threshold: 100,
+                ..SchedulerClientConfig::default()
+            }
+        }
+
+        fn status_body() -> serde_json::Value {
+            serde_json::json!({
+                "phase": "running",
+                "ready": true,
+                "restart_count": 0,
+                "message": null,
+            })
+        }
+
+        fn status_path() -> String {
+            format!("/v1/agents/{}/status", agent_id().to_hex())
+        }
+
+        #[tokio::test]
+        async fn idempotent_calls_retry_server_errors() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path(status_path()))
+                .respond_with(ResponseTemplate::new(503))
+                .up_to_n_times(2)
+                .with_priority(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .and(path(status_path()))
+                .respond_with(ResponseTemplate::new(200).set_body_json(status_body()))
+                .mount(&server)
+                .await;
+
+            let client = HttpSchedulerClient::with_config(server.uri(), fast_config());
+            let status = client.get_pod_status(&agent_id()).await.unwrap();
+
+            assert!(status.ready);
+            assert_eq!(server.received_requests().await.unwrap().len(), 3);
+        }
+
+        #[tokio::test]
+        async fn retries_are_bounded() {
+            let server = MockServer::start().await;
+            Mock::given(method("DELETE"))
+                .respond_with(ResponseTemplate::new(500))
+                .expect(3)
+                .mount(&server)
+                .await;
+
+            let client = HttpSchedulerClient::with_config(server.uri(), fast_config());
+            let result = client.terminate_agent(&agent_id()).await;
+
+            assert!(matches!(result, Err(ControlError::Internal(_))));
+        }
+
+        #[tokio::test]
+        async fn get_pod_metrics_parses_usage() {
+            let server = MockServer::start().await;
+            let metrics_path = format!("/v1/agents/{}/metrics", agent_id().to_hex());
+            Mock::given(method("GET"))
+                .and(path(metrics_path))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "metrics": {
+                        "cpu_millicores": 250,
+                        "memory_bytes": 134_217_728,
+                        "timestamp": "2026-01-01T00:00:00Z",
+                    },
+                })))
+                .up_to_n_times(1)
+                .with_priority(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(serde_json::json!({ "metrics": null })),
+                )
+                .mount(&server)
+                .await;
+
+            let client = HttpSchedulerClient::with_config(server.uri(), fast_config());
+            let metrics = client.get_pod_metrics(&agent_id()).await.unwrap().unwrap();
+            assert_eq!(metrics.cpu_millicores, 250);
+            assert_eq!(metrics.memory_bytes, 134_217_728);
+            assert!(metrics.timestamp.is_some());
+
+            assert_eq!(client.get_pod_metrics(&agent_id()).await.unwrap(), None);
+        }
+
+        #[tokio::test]
+        async fn stream_pod_logs_splits_lines() {
+            use wiremock::matchers::query_param;
+
+            let server = MockServer::start().await;
+            let logs_path = format!("/v1/agents/{}/logs", agent_id().to_hex());
+            Mock::given(method("GET"))
+                .and(path(logs_path))
+                .and(query_param("tail", "2"))
+                .and(query_param("follow", "false"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_string("2026-01-01T00:00:00Z one\r\n2026-01-01T00:00:01Z two"),
+                )
+                .mount(&server)
+                .await;
+
+            let client = HttpSchedulerClient::with_config(server.uri(), fast_config());
+            let lines: Vec<_> = client
+                .stream_pod_logs(&agent_id(), Some(2), None, false)
+                .await
+                .unwrap()
+                .map(Result::unwrap)
+                .collect()
+                .await;
+
+            assert_eq!(
+                lines,
+                vec!["2026-01-01T00:00:00Z one", "2026-01-01T00:00:01Z two"]
+            );
+        }
+
+        #[tokio::test]
+        async fn stream_pod_logs_without_pod_is_not_found() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(404))
+                .mount(&server)
+                .await;
+
+            let client = HttpSchedulerClient::with_config(server.uri(), fast_config());
+            let result = client.stream_pod_logs(&agent_id(), None, None, true).await;
+
+            assert!(matches!(result, Err(ControlError::AgentNotFound(_))));
+        }
+
+        #[tokio::test]
+        async fn client_errors_are_not_retried() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(404))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let client = HttpSchedulerClient::with_config(server.uri(), fast_config());
+            let result = client.get_pod_status(&agent_id()).await;
+
+            assert!(matches!(result, Err(ControlError::AgentNotFound(_))));
+        }
+
+        #[tokio::test]
+        async fn schedule_is_not_retried() {
+            let server = MockServer::start().await;
+            Mock::given(method("POST"))
+                .respond_with(ResponseTemplate::new(503))
+                .expect(1)
+                .mount(&server)
+                .await;
+
+            let client = HttpSchedulerClient::with_config(server.uri(), fast_config());
+            let result = client
+                .schedule_agent(&agent_id(), "user", &AgentSpec::default())
+                .await;
+
+            assert!(result.is_err());
+        }
+
+        #[tokio::test]
+        async fn slow_responses_time_out() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(
+                    ResponseTemplate::new(200)
+                        .set_body_json(status_body())
+                        .set_delay(Duration::from_secs(2)),
+                )
+                .mount(&server)
+                .await;
+
+            let config = SchedulerClientConfig {
+                max_retries: 1,
+                ..fast_config()
+            };
+            let client = HttpSchedulerClient::with_config(server.uri(), config);
+            let result = client.get_pod_status(&agent_id()).await;
+
+            assert!(matches!(result, Err(ControlError::SchedulerUnavailable(_))));
+            assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        }
+
+        /// A response slower than `fast_config`'s timeouts.
+        fn too_slow() -> ResponseTemplate {
+            ResponseTemplate::new(200)
+                .set_body_json(status_body())
+                .set_delay(Duration::from_millis(400))
+        }
+
+        #[tokio::test]
+        async fn circuit_opens_fails_fast_and_recovers() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(too_slow())
+                .up_to_n_times(2)
+                .with_priority(1)
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(status_body()))
+                .mount(&server)
+                .await;
+
+            let config = SchedulerClientConfig {
+                max_retries: 0,
+                failure_threshold: 2,
+                open_duration: Duration::from_millis(100),
+                ..fast_config()
+            };
+            let client = HttpSchedulerClient::with_config(server.uri(), config);
+
+            assert!(client.get_pod_status(&agent_id()).await.is_err());
+            assert_eq!(client.circuit_state(), CircuitState::Closed);
+            assert!(client.get_pod_status(&agent_id()).await.is_err());
+            assert_eq!(client.circuit_state(), CircuitState::Open);
+
+            // While open, calls fail without reaching the scheduler
+            let result = client.get_pod_status(&agent_id()).await;
+            assert!(matches!(result, Err(ControlError::SchedulerUnavailable(_))));
+            assert_eq!(server.received_requests().await.unwrap().len(), 2);
+
+            // After the open period a probe is allowed and closes the circuit
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            assert_eq!(client.circuit_state(), CircuitState::HalfOpen);
+            assert!(client.get_pod_status(&agent_id()).await.is_ok());
+            assert_eq!(client.circuit_state(), CircuitState::Closed);
+        }
+
+        #[tokio::test]
+        async fn failed_probe_reopens_circuit() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(too_slow())
+                .mount(&server)
+                .await;
+
+            let config = SchedulerClientConfig {
+                max_retries: 0,
+                failure_threshold: 1,
+                open_duration: Duration::from_millis(50),
+                ..fast_config()
+            };
+            let client = HttpSchedulerClient::with_config(server.uri(), config);
+
+            assert!(client.list_pods().await.is_err());
+            assert_eq!(client.circuit_state(), CircuitState::Open);
+
+            tokio::time::sleep(Duration::from_millis(80)).await;
+            assert!(client.list_pods().await.is_err());
+            assert_eq!(client.circuit_state(), CircuitState::Open);
+            assert_eq!(server.received_requests().await.unwrap().len(), 2);
+        }
+
+        #[tokio::test]
+        async fn error_responses_do_not_open_circuit() {
+            // The scheduler reports Kubernetes API failures as 503
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(503))
+                .mount(&server)
+                .await;
+
+            let config = SchedulerClientConfig {
+                max_retries: 0,
+                failure_threshold: 1,
+                ..fast_config()
+            };
+            let client = HttpSchedulerClient::with_config(server.uri(), config);
+
+            for _ in 0..3 {
+                assert!(client.get_pod_status(&agent_id()).await.is_err());
+                assert_eq!(client.circuit_state(), CircuitState::Closed);
+            }
+            assert_eq!(server.received_requests().await.unwrap().len(), 3);
+        }
     }
 }
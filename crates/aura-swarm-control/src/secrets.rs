@@ -0,0 +1,318 @@
+//! Agent secrets.
+//!
+//! Users attach secrets (API tokens, credentials) to their agents. Values are
+//! write-only through the API and sealed with AES-256-GCM before they reach
+//! the store. The agent ID and secret name are bound to each ciphertext as
+//! associated data, so a sealed value cannot be replayed under another agent
+//! or name.
+//!
+//! Values are only unsealed when the control plane hands them to the
+//! scheduler, which materializes them as a per-agent Kubernetes Secret before
+//! every pod placement.
+//!
+//! The same cipher seals webhook signing secrets, bound to their webhook ID.
+
+use aura_swarm_core::{AgentId, WebhookId};
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use crate::error::{ControlError, Result};
+
+/// Length of a secret encryption key in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// Longest accepted secret name.
+pub const MAX_NAME_LEN: usize = 128;
+
+/// Largest accepted secret value in bytes.
+pub const MAX_VALUE_BYTES: usize = 32 * 1024;
+
+/// Environment variables set by the scheduler that secrets and spec
+/// environment variables may not replace.
+pub const RESERVED_NAMES: [&str; 7] = [
+    "AGENT_ID",
+    "USER_ID",
+    "STATE_DIR",
+    "AURA_LISTEN_ADDR",
+    "CONTROL_PLANE_URL",
+    "AURA_SYSTEM_PROMPT",
+    "AURA_STANDBY",
+];
+
+/// Seals and unseals agent secret values.
+#[derive(Debug)]
+pub struct SecretCipher {
+    key: LessSafeKey,
+    rng: SystemRandom,
+}
+
+impl SecretCipher {
+    /// Create a cipher from a 32-byte key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is not 32 bytes long.
+    pub fn new(key: &[u8]) -> std::result::Result<Self, String> {
+        if key.len() != KEY_LEN {
+            return Err(format!(
+                "secret key must be {KEY_LEN} bytes, got {}",
+                key.len()
+            ));
+        }
+        let key = UnboundKey::new(&AES_256_GCM, key).map_err(|_| "invalid secret key")?;
+
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            rng: SystemRandom::new(),
+        })
+    }
+
+    /// Create a cipher from a hex-encoded 32-byte key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the key is not valid hex or not 32 bytes long.
+    pub fn from_hex(key: &str) -> std::result::Result<Self, String> {
+        let key = hex::decode(key.trim()).map_err(|e| format!("invalid secret key: {e}"))?;
+        Self::new(&key)
+    }
+
+    /// Seal a secret value.
+    ///
+    /// The result is the random nonce followed by the ciphertext and tag.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no nonce could be generated.
+    pub fn seal(&self, agent_id: &AgentId, name: &str, value: &str) -> Result<Vec<u8>> {
+        self.seal_with(&associated_data(agent_id, name), value)
+            .map_err(|()| ControlError::Internal(format!("failed to seal secret {name}")))
+    }
+
+    /// Unseal a value produced by [`seal`](Self::seal) for the same agent and
+    /// name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value was sealed with another key, for another
+    /// agent or name, or has been tampered with.
+    pub fn open(&self, agent_id: &AgentId, name: &str, sealed: &[u8]) -> Result<String> {
+        self.open_with(&associated_data(agent_id, name), sealed)
+            .map_err(|()| ControlError::Internal(format!("failed to unseal secret {name}")))
+    }
+
+    /// Seal a webhook's signing secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no nonce could be generated.
+    pub fn seal_webhook_secret(&self, webhook_id: &WebhookId, secret: &str) -> Result<Vec<u8>> {
+        self.seal_with(&webhook_associated_data(webhook_id), secret)
+            .map_err(|()| {
+                ControlError::Internal(format!("failed to seal secret of webhook {webhook_id}"))
+            })
+    }
+
+    /// Unseal a webhook signing secret produced by
+    /// [`seal_webhook_secret`](Self::seal_webhook_secret).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the secret was sealed with another key, for another
+    /// webhook, or has been tampered with.
+    pub fn open_webhook_secret(&self, webhook_id: &WebhookId, sealed: &[u8]) -> Result<String> {
+        self.open_with(&webhook_associated_data(webhook_id), sealed)
+            .map_err(|()| {
+                ControlError::Internal(format!("failed to unseal secret of webhook {webhook_id}"))
+            })
+    }
+
+    fn seal_with(&self, aad: &[u8], value: &str) -> std::result::Result<Vec<u8>, ()> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng.fill(&mut nonce).map_err(|_| ())?;
+
+        let mut in_out = value.as_bytes().to_vec();
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(aad),
+                &mut in_out,
+            )
+            .map_err(|_| ())?;
+
+        let mut sealed = Vec::with_capacity(NONCE_LEN + in_out.len());
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&in_out);
+        Ok(sealed)
+    }
+
+    fn open_with(&self, aad: &[u8], sealed: &[u8]) -> std::result::Result<String, ()> {
+        if sealed.len() < NONCE_LEN {
+            return Err(());
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce).map_err(|_| ())?;
+
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(aad), &mut in_out)
+            .map_err(|_| ())?;
+
+        String::from_utf8(plaintext.to_vec()).map_err(|_| ())
+    }
+}
+
+/// Check that a secret name is a usable environment variable name.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the name is empty, too long,
+/// not of the form `[A-Za-z_][A-Za-z0-9_]*`, or reserved.
+pub fn validate_name(name: &str) -> Result<()> {
+    check_env_name("secret name", name)
+}
+
+/// Check an environment variable name from an agent spec by the same rules
+/// as secret names.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the name is empty, too long,
+/// not of the form `[A-Za-z_][A-Za-z0-9_]*`, or reserved.
+pub fn validate_env_name(name: &str) -> Result<()> {
+    check_env_name("environment variable name", name)
+}
+
+/// Shared rules for names that end up in the runtime's environment.
+fn check_env_name(what: &str, name: &str) -> Result<()> {
+    let invalid = |reason: &str| Err(ControlError::InvalidRequest(format!("{what} {reason}")));
+
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return invalid(&format!("must be 1 to {MAX_NAME_LEN} characters"));
+    }
+    if name.starts_with(|c: char| c.is_ascii_digit())
+        || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return invalid("must be a valid environment variable name");
+    }
+    if RESERVED_NAMES.contains(&name) {
+        return invalid(&format!("{name} is reserved"));
+    }
+    Ok(())
+}
+
+/// Check that a secret value is within the size limit.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the value is too large.
+pub fn validate_value(value: &str) -> Result<()> {
+    if value.len() > MAX_VALUE_BYTES {
+        return Err(ControlError::InvalidRequest(format!(
+            "secret value exceeds {MAX_VALUE_BYTES} bytes"
+        )));
+    }
+    Ok(())
+}
+
+/// Associated data binding a sealed value to its agent and name.
+fn associated_data(agent_id: &AgentId, name: &str) -> Vec<u8> {
+    let mut aad = Vec::with_capacity(32 + name.len());
+    aad.extend_from_slice(agent_id.as_bytes());
+    aad.extend_from_slice(name.as_bytes());
+    aad
+}
+
+/// Associated data binding a sealed webhook secret to its webhook.
+fn webhook_associated_data(webhook_id: &WebhookId) -> Vec<u8> {
+    let mut aad = b"webhook:".to_vec();
+    aad.extend_from_slice(webhook_id.as_bytes());
+    aad
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher() -> SecretCipher {
+        SecretCipher::new(&[7u8; KEY_LEN]).unwrap()
+    }
+
+    #[test]
+    fn sealed_values_round_trip() {
+        let cipher = cipher();
+        let agent_id = AgentId::from_bytes([1u8; 32]);
+
+        let sealed = cipher.seal(&agent_id, "GITHUB_TOKEN", "ghp_123").unwrap();
+        assert!(!sealed.windows(7).any(|w| w == b"ghp_123"));
+        assert_eq!(
+            cipher.open(&agent_id, "GITHUB_TOKEN", &sealed).unwrap(),
+            "ghp_123"
+        );
+
+        // Sealing twice uses a fresh nonce
+        assert_ne!(
+            sealed,
+            cipher.seal(&agent_id, "GITHUB_TOKEN", "ghp_123").unwrap()
+        );
+    }
+
+    #[test]
+    fn sealed_values_are_bound_to_agent_name_and_key() {
+        let cipher = cipher();
+        let agent_id = AgentId::from_bytes([1u8; 32]);
+        let other = AgentId::from_bytes([2u8; 32]);
+        let sealed = cipher.seal(&agent_id, "TOKEN", "value").unwrap();
+
+        assert!(cipher.open(&other, "TOKEN", &sealed).is_err());
+        assert!(cipher.open(&agent_id, "OTHER", &sealed).is_err());
+        assert!(SecretCipher::new(&[8u8; KEY_LEN])
+            .unwrap()
+            .open(&agent_id, "TOKEN", &sealed)
+            .is_err());
+        assert!(cipher.open(&agent_id, "TOKEN", &sealed[..4]).is_err());
+    }
+
+    #[test]
+    fn webhook_secrets_are_bound_to_their_webhook() {
+        let cipher = cipher();
+        let webhook_id = WebhookId::generate();
+        let sealed = cipher
+            .seal_webhook_secret(&webhook_id, "whsec_abc")
+            .unwrap();
+
+        assert_eq!(
+            cipher.open_webhook_secret(&webhook_id, &sealed).unwrap(),
+            "whsec_abc"
+        );
+        assert!(cipher
+            .open_webhook_secret(&WebhookId::generate(), &sealed)
+            .is_err());
+    }
+
+    #[test]
+    fn keys_must_be_32_bytes() {
+        assert!(SecretCipher::new(&[0u8; 16]).is_err());
+        assert!(SecretCipher::from_hex(&"ab".repeat(32)).is_ok());
+        assert!(SecretCipher::from_hex("not-hex").is_err());
+    }
+
+    #[test]
+    fn names_must_be_environment_variables() {
+        assert!(validate_name("GITHUB_TOKEN").is_ok());
+        assert!(validate_name("_private2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("2FA").is_err());
+        assert!(validate_name("MY-TOKEN").is_err());
+        assert!(validate_name("AGENT_ID").is_err());
+        assert!(validate_name(&"A".repeat(MAX_NAME_LEN + 1)).is_err());
+
+        assert!(validate_env_name("LOG_LEVEL").is_ok());
+        assert!(validate_env_name("AURA_STANDBY").is_err());
+        assert!(matches!(
+            validate_env_name("MY-VAR"),
+            Err(ControlError::InvalidRequest(msg)) if msg.starts_with("environment variable name")
+        ));
+    }
+}
@@ -3,74 +3,445 @@
 //! This module provides the `ControlPlane` trait and `ControlPlaneService` implementation
 //! that coordinates agent lifecycle and session management.
 
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use aura_swarm_core::{AgentId, SessionId, UserId};
-use aura_swarm_store::{Agent, AgentState, Session, Store};
-use chrono::Utc;
-
+use aura_swarm_core::{
+    AgentId, DeliveryId, IdentityId, NamespaceId, RolloutId, SessionId, UserId, WebhookId,
+};
+use aura_swarm_store::{
+    Agent, AgentDeletion, AgentGrant, AgentRole, AgentSchedule, AgentSecret, AgentSpec, AgentState,
+    AgentTemplate, AuditEvent, AuditOutcome, AuditQuery, DeletionStatus, DeletionStep,
+    DeliveryAttempt, DeliveryStatus, HibernationCheckpoint, MissedRunPolicy, NamespaceQuota,
+    RestartPolicy, RestartState, Rollout, RolloutStatus, RolloutTargetState, ScheduleAction,
+    ScheduleRun, ScheduleRunOutcome, Session, StateCopy, Store, StoreError, Webhook,
+    WebhookDelivery, WebhookEvent,
+};
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+
+use crate::access;
+use crate::audit::{self, AuditLogger, StoreAuditLogger};
+use crate::deletion;
+use crate::egress;
 use crate::error::{ControlError, Result};
 use crate::lifecycle;
+use crate::logs::{self, LogEntryStream};
+use crate::metering::{self, UsageQuery, UsageSummary};
+use crate::namespace;
+use crate::placement;
+use crate::reconcile::{self, DriftAction, DriftCorrection, DriftReport};
+use crate::rollout;
+use crate::runtime::{RuntimeClient, RuntimeError};
+use crate::schedule;
 use crate::scheduler_client::SchedulerClient;
+use crate::secrets::{self, SecretCipher};
 use crate::session;
-use crate::types::{ControlConfig, CreateAgentRequest};
+use crate::types::{
+    AgentStatus, Caller, CloneAgentRequest, ControlConfig, CreateAgentRequest,
+    CreateRolloutRequest, CreateTemplateRequest, CreateWebhookRequest, CreatedWebhook, LogOptions,
+    NamespaceUsage, SetNamespaceQuotaRequest, SetScheduleRequest, SharedAgent,
+};
+use crate::webhook::{self, WebhookSender};
 
 /// Trait defining the control plane operations.
 ///
 /// This trait provides the complete API for managing agents and sessions.
 /// Implementations handle state persistence, validation, and coordination.
+///
+/// Methods that act on an agent check the caller's role on it (see
+/// [`crate::access`]): reads need `viewer`, lifecycle changes and sessions
+/// need `operator`, and deletion, access and secret management need `owner`.
 #[async_trait]
 pub trait ControlPlane: Send + Sync {
     // =========================================================================
     // Agent CRUD Operations
     // =========================================================================
 
-    /// Create a new agent for the given user.
+    /// Create a new agent owned by the caller, in the caller's namespace.
     ///
     /// # Errors
     ///
     /// Returns `ControlError::QuotaExceeded` if the user has reached their limit.
-    async fn create_agent(&self, user_id: &UserId, request: CreateAgentRequest) -> Result<Agent>;
+    /// Returns `ControlError::NamespaceQuotaExceeded` if the namespace has reached its limit.
+    /// Returns `ControlError::TemplateNotFound` if the named template doesn't exist.
+    /// Returns `ControlError::InvalidRequest` if the resolved spec exceeds resource limits.
+    async fn create_agent(&self, caller: &Caller, request: CreateAgentRequest) -> Result<Agent>;
 
-    /// Get an agent by ID, verifying ownership.
+    /// Get an agent by ID, verifying the caller may access it.
     ///
     /// # Errors
     ///
-    /// Returns `ControlError::AgentNotFound` if the agent doesn't exist.
-    /// Returns `ControlError::NotOwner` if the user doesn't own the agent.
-    async fn get_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent>;
+    /// Returns `ControlError::AgentNotFound` if the agent doesn't exist or is
+    /// in another namespace.
+    /// Returns `ControlError::NotOwner` if the caller has no access to the agent.
+    async fn get_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent>;
+
+    /// Get an agent's health, resource usage and active session count.
+    ///
+    /// Resource usage comes from the cluster's metrics API and is reported as
+    /// zero while the agent is not active or no sample is available.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`ControlPlane::get_agent`].
+    async fn get_agent_status(&self, caller: &Caller, agent_id: &AgentId) -> Result<AgentStatus>;
 
-    /// List all agents for a user.
-    async fn list_agents(&self, user_id: &UserId) -> Result<Vec<Agent>>;
+    /// Stream an agent's runtime logs.
+    ///
+    /// Without `follow` the stream ends after the most recent `lines`
+    /// entries; with it, the stream stays open for new entries. An agent
+    /// without a running pod has no logs.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same errors as [`ControlPlane::get_agent`].
+    /// Returns `ControlError::InvalidRequest` if the options are invalid.
+    async fn agent_logs(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        options: LogOptions,
+    ) -> Result<LogEntryStream>;
+
+    /// List the caller's own agents in their namespace.
+    async fn list_agents(&self, caller: &Caller) -> Result<Vec<Agent>>;
 
     /// Delete an agent.
     ///
-    /// The agent must be in a stopped state before deletion.
+    /// The agent must be in a stopped state before deletion. Requires the
+    /// owner role. Deletion runs as a resumable job (see the [`deletion`]
+    /// module); if a step fails, the returned job is marked failed and is
+    /// retried in the background. Deleting an agent whose deletion failed
+    /// resumes it.
     ///
     /// # Errors
     ///
+    /// Returns `ControlError::InsufficientRole` if the caller is not an owner.
     /// Returns `ControlError::InvalidState` if the agent is not stopped.
-    async fn delete_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<()>;
+    async fn delete_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<AgentDeletion>;
+
+    /// Get the progress of an agent's deletion.
+    ///
+    /// Visible to the agent's owner, the user who requested the deletion, and
+    /// admins of the agent's namespace. Completed deletions remain visible
+    /// after the agent record is gone.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::DeletionNotFound` if no deletion was requested
+    /// or the caller may not see it.
+    async fn get_agent_deletion(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+    ) -> Result<AgentDeletion>;
+
+    /// Clone an agent, including a copy of its persistent state directory.
+    ///
+    /// The source agent must be stopped or hibernating so its state is not
+    /// being written during the copy. The clone is created with the source's
+    /// spec and returned in `Provisioning` straight away; the clone worker
+    /// copies the state in the background and then starts the clone's pod.
+    /// A failed copy moves the clone to `Error`. Until the copy finishes, the
+    /// source cannot be started or deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::InvalidState` if the source is not stopped or hibernating.
+    /// Returns `ControlError::AgentDeleting` if the source is being deleted.
+    /// Returns `ControlError::QuotaExceeded` if the user has reached their limit.
+    async fn clone_agent(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        request: CloneAgentRequest,
+    ) -> Result<Agent>;
+
+    // =========================================================================
+    // Access Operations
+    // =========================================================================
+
+    /// List agents other users have shared with the caller.
+    async fn list_shared_agents(&self, caller: &Caller) -> Result<Vec<SharedAgent>>;
+
+    /// List the access grants on an agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent is not found or the caller is not an owner.
+    async fn list_agent_grants(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+    ) -> Result<Vec<AgentGrant>>;
+
+    /// Grant a user a role on an agent, replacing any existing grant.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The agent is not found or the caller is not an owner
+    /// - The user is the agent's creator
+    async fn grant_agent_access(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        user_id: &UserId,
+        role: AgentRole,
+    ) -> Result<AgentGrant>;
+
+    /// Revoke a user's access to an agent.
+    ///
+    /// Owners may revoke any grant; collaborators may revoke their own.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent or grant is not found, or the caller
+    /// may not revoke it.
+    async fn revoke_agent_access(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        user_id: &UserId,
+    ) -> Result<()>;
+
+    // =========================================================================
+    // Secret Operations
+    // =========================================================================
+
+    /// List an agent's secrets, ordered by name.
+    ///
+    /// Values stay sealed; callers should expose names and timestamps only.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent is not found or the caller is not an owner.
+    async fn list_agent_secrets(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+    ) -> Result<Vec<AgentSecret>>;
+
+    /// Create or replace an agent secret.
+    ///
+    /// The value is exposed to the agent as an environment variable of the
+    /// same name the next time its pod is started.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The agent is not found or the caller is not an owner
+    /// - Secrets are not enabled, or the name or value is invalid
+    /// - The name is already an environment variable in the agent's spec
+    /// - The agent already has the maximum number of secrets
+    async fn set_agent_secret(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        name: &str,
+        value: &str,
+    ) -> Result<AgentSecret>;
+
+    /// Delete an agent secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agent or secret is not found or the caller is
+    /// not an owner.
+    async fn delete_agent_secret(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        name: &str,
+    ) -> Result<()>;
+
+    // =========================================================================
+    // Namespace Operations
+    // =========================================================================
+
+    /// List every agent in the caller's namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::NotNamespaceAdmin` if the caller is not a namespace admin.
+    async fn list_namespace_agents(&self, caller: &Caller) -> Result<Vec<Agent>>;
+
+    /// Get the current usage and limits of the caller's namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::NotNamespaceAdmin` if the caller is not a namespace admin.
+    async fn get_namespace_usage(&self, caller: &Caller) -> Result<NamespaceUsage>;
+
+    /// Set the quota overrides for a namespace.
+    ///
+    /// This is a platform-level operation; authorization is the caller's
+    /// responsibility.
+    async fn set_namespace_quota(
+        &self,
+        namespace_id: &NamespaceId,
+        request: SetNamespaceQuotaRequest,
+    ) -> Result<NamespaceQuota>;
+
+    // =========================================================================
+    // Template Operations
+    // =========================================================================
+
+    /// Create an agent template.
+    ///
+    /// Pass `None` as the owner to create an admin-defined template visible
+    /// to all users; authorization for that is the caller's responsibility.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::TemplateExists` if the name is taken in that scope.
+    /// Returns `ControlError::InvalidRequest` if the spec exceeds resource limits.
+    async fn create_template(
+        &self,
+        owner: Option<&UserId>,
+        request: CreateTemplateRequest,
+    ) -> Result<AgentTemplate>;
+
+    /// List the templates visible to a user (admin-defined and their own).
+    async fn list_templates(&self, user_id: &UserId) -> Result<Vec<AgentTemplate>>;
+
+    /// Get a template visible to a user, preferring their own over an
+    /// admin-defined template of the same name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::TemplateNotFound` if no visible template has that name.
+    async fn get_template(&self, user_id: &UserId, name: &str) -> Result<AgentTemplate>;
+
+    /// Delete a template.
+    ///
+    /// Pass `None` as the owner to delete an admin-defined template;
+    /// authorization for that is the caller's responsibility. Agents already
+    /// created from the template are not affected.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::TemplateNotFound` if the template doesn't exist in that scope.
+    async fn delete_template(&self, owner: Option<&UserId>, name: &str) -> Result<()>;
 
     // =========================================================================
     // Lifecycle Operations
     // =========================================================================
 
     /// Start an agent (transition from Stopped to Provisioning).
-    async fn start_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent>;
+    async fn start_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent>;
 
     /// Stop an agent gracefully.
-    async fn stop_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent>;
+    async fn stop_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent>;
 
     /// Restart an agent (stop then start).
-    async fn restart_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent>;
+    async fn restart_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent>;
 
-    /// Hibernate an agent (save state, terminate pod).
-    async fn hibernate_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent>;
+    /// Hibernate an agent: ask its runtime to flush state, record a checkpoint,
+    /// then terminate the pod.
+    async fn hibernate_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent>;
 
     /// Wake a hibernating agent.
-    async fn wake_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent>;
+    async fn wake_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent>;
+
+    // =========================================================================
+    // Schedule Operations
+    // =========================================================================
+
+    /// Set (create or replace) the wake/hibernate schedule for an agent.
+    ///
+    /// Only fire times after this call are considered; past runs are not replayed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::InvalidRequest` if a cron rule or the time zone is invalid.
+    async fn set_schedule(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        request: SetScheduleRequest,
+    ) -> Result<AgentSchedule>;
+
+    /// Get the schedule for an agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::ScheduleNotFound` if the agent has no schedule.
+    async fn get_schedule(&self, caller: &Caller, agent_id: &AgentId) -> Result<AgentSchedule>;
+
+    /// Remove the schedule for an agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::ScheduleNotFound` if the agent has no schedule.
+    async fn delete_schedule(&self, caller: &Caller, agent_id: &AgentId) -> Result<()>;
+
+    // =========================================================================
+    // Webhook Operations
+    // =========================================================================
+
+    /// Register a webhook endpoint for the user's agent lifecycle events.
+    ///
+    /// The signing secret is stored sealed and returned only here.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the URL is invalid or not public, the user has
+    /// reached the webhook limit, or no secret key is configured.
+    async fn create_webhook(
+        &self,
+        user_id: &UserId,
+        request: CreateWebhookRequest,
+    ) -> Result<CreatedWebhook>;
+
+    /// List the user's webhooks.
+    async fn list_webhooks(&self, user_id: &UserId) -> Result<Vec<Webhook>>;
+
+    /// Get a webhook by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::WebhookNotFound` if the webhook doesn't exist or
+    /// belongs to another user.
+    async fn get_webhook(&self, user_id: &UserId, webhook_id: &WebhookId) -> Result<Webhook>;
+
+    /// Delete a webhook, its delivery log, and any pending deliveries.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::WebhookNotFound` if the webhook doesn't exist or
+    /// belongs to another user.
+    async fn delete_webhook(&self, user_id: &UserId, webhook_id: &WebhookId) -> Result<()>;
+
+    /// List the most recent deliveries for a webhook, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::WebhookNotFound` if the webhook doesn't exist or
+    /// belongs to another user.
+    async fn list_webhook_deliveries(
+        &self,
+        user_id: &UserId,
+        webhook_id: &WebhookId,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>>;
+
+    /// Send a test `ping` event to a webhook immediately.
+    ///
+    /// The returned delivery includes the outcome of the first attempt; failed
+    /// pings are retried like any other delivery.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::WebhookNotFound` if the webhook doesn't exist or
+    /// belongs to another user.
+    async fn ping_webhook(
+        &self,
+        user_id: &UserId,
+        webhook_id: &WebhookId,
+    ) -> Result<WebhookDelivery>;
 
     // =========================================================================
     // Session Operations
@@ -79,16 +450,22 @@ pub trait ControlPlane: Send + Sync {
     /// Create a new session for an agent.
     ///
     /// If the agent is hibernating, it will be automatically woken.
-    async fn create_session(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Session>;
+    async fn create_session(&self, caller: &Caller, agent_id: &AgentId) -> Result<Session>;
 
     /// Get a session by ID.
-    async fn get_session(&self, user_id: &UserId, session_id: &SessionId) -> Result<Session>;
+    async fn get_session(&self, caller: &Caller, session_id: &SessionId) -> Result<Session>;
 
     /// Close a session.
-    async fn close_session(&self, user_id: &UserId, session_id: &SessionId) -> Result<()>;
+    async fn close_session(&self, caller: &Caller, session_id: &SessionId) -> Result<()>;
 
     /// List all sessions for an agent.
-    async fn list_sessions(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Vec<Session>>;
+    async fn list_sessions(&self, caller: &Caller, agent_id: &AgentId) -> Result<Vec<Session>>;
+
+    /// Record traffic on a session, resetting its idle timer.
+    ///
+    /// Returns the current session record; callers should disconnect if it
+    /// is no longer active (for example, after being reaped).
+    async fn touch_session(&self, caller: &Caller, session_id: &SessionId) -> Result<Session>;
 
     // =========================================================================
     // Operational
@@ -102,6 +479,89 @@ pub trait ControlPlane: Send + Sync {
     /// Returns the endpoint URL if the agent is running.
     async fn resolve_agent_endpoint(&self, agent_id: &AgentId) -> Result<Option<String>>;
 
+    // =========================================================================
+    // Audit Operations
+    // =========================================================================
+
+    /// Record an audit event.
+    ///
+    /// Persistence failures are logged rather than returned so that auditing
+    /// never fails the request being audited.
+    async fn record_audit_event(&self, event: AuditEvent);
+
+    /// List audit events matching a query, newest first.
+    ///
+    /// This does NOT check who is asking; callers must restrict the query to
+    /// what the requester is allowed to see.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the audit log cannot be read.
+    async fn list_audit_events(&self, query: AuditQuery) -> Result<Vec<AuditEvent>>;
+
+    // =========================================================================
+    // Usage Operations
+    // =========================================================================
+
+    /// Summarize metered agent usage.
+    ///
+    /// Like [`ControlPlane::list_audit_events`], this does NOT check who is
+    /// asking; callers must restrict the query to what the requester may see.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::InvalidRequest` if `from` is not before `to`.
+    async fn get_usage(&self, query: UsageQuery) -> Result<Vec<UsageSummary>>;
+
+    // =========================================================================
+    // Rollout Operations
+    // =========================================================================
+
+    /// Start rolling the agents matching the request's selector out to a new
+    /// runtime version.
+    ///
+    /// The rollout itself is advanced by the rollout controller (see
+    /// [`ControlPlaneService::run_rollout_controller`]). This is a
+    /// platform-level operation; authorization is the caller's responsibility.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The request is invalid or no agent matches the selector
+    /// - Another rollout is still in progress
+    async fn create_rollout(
+        &self,
+        created_by: &IdentityId,
+        request: CreateRolloutRequest,
+    ) -> Result<Rollout>;
+
+    /// List all rollouts, newest first.
+    async fn list_rollouts(&self) -> Result<Vec<Rollout>>;
+
+    /// Get a rollout.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ControlError::RolloutNotFound` if the rollout doesn't exist.
+    async fn get_rollout(&self, rollout_id: &RolloutId) -> Result<Rollout>;
+
+    /// Stop an in-progress rollout, leaving already updated agents as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rollout doesn't exist or is not in progress.
+    async fn cancel_rollout(&self, rollout_id: &RolloutId) -> Result<Rollout>;
+
+    /// Move every agent a rollout updated back to its previous runtime version.
+    ///
+    /// Agents the rollout restarted are restarted again on their previous
+    /// version. An in-progress rollout is stopped first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the rollout doesn't exist or was already rolled back.
+    async fn rollback_rollout(&self, rollout_id: &RolloutId) -> Result<Rollout>;
+
     // =========================================================================
     // Internal Operations (for scheduler callbacks)
     // =========================================================================
@@ -135,16 +595,31 @@ pub struct ControlPlaneService<S: Store, SC: SchedulerClient = crate::scheduler_
     store: Arc<S>,
     config: ControlConfig,
     scheduler: Option<Arc<SC>>,
+    webhooks: WebhookSender,
+    runtime: RuntimeClient,
+    audit: StoreAuditLogger<S>,
+    secrets: Option<SecretCipher>,
 }
 
 impl<S: Store> ControlPlaneService<S, crate::scheduler_client::NoopSchedulerClient> {
     /// Create a new control plane service without scheduler integration.
     #[must_use]
     pub fn new(store: Arc<S>, config: ControlConfig) -> Self {
+        let webhooks = WebhookSender::new(
+            std::time::Duration::from_secs(config.webhook_timeout_seconds),
+            config.webhook_allow_private_targets,
+        );
+        let runtime = RuntimeClient::new(std::time::Duration::from_secs(
+            config.hibernate_timeout_seconds,
+        ));
         Self {
+            audit: StoreAuditLogger::new(Arc::clone(&store)),
             store,
             config,
             scheduler: None,
+            webhooks,
+            runtime,
+            secrets: None,
         }
     }
 
@@ -159,11 +634,7 @@ impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
     /// Create a new control plane service with scheduler integration.
     #[must_use]
     pub fn with_scheduler(store: Arc<S>, config: ControlConfig, scheduler: Arc<SC>) -> Self {
-        Self {
-            store,
-            config,
-            scheduler: Some(scheduler),
-        }
+        Self::with_optional_scheduler(store, config, Some(scheduler))
     }
 
     /// Create a new control plane service with optional scheduler integration.
@@ -175,13 +646,33 @@ impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
         config: ControlConfig,
         scheduler: Option<Arc<SC>>,
     ) -> Self {
+        let webhooks = WebhookSender::new(
+            std::time::Duration::from_secs(config.webhook_timeout_seconds),
+            config.webhook_allow_private_targets,
+        );
+        let runtime = RuntimeClient::new(std::time::Duration::from_secs(
+            config.hibernate_timeout_seconds,
+        ));
         Self {
+            audit: StoreAuditLogger::new(Arc::clone(&store)),
             store,
             config,
             scheduler,
+            webhooks,
+            runtime,
+            secrets: None,
         }
     }
 
+    /// Enable agent secrets, sealing their values with `cipher`.
+    ///
+    /// Without a cipher, agent secrets cannot be set.
+    #[must_use]
+    pub fn with_secret_cipher(mut self, cipher: SecretCipher) -> Self {
+        self.secrets = Some(cipher);
+        self
+    }
+
     /// Get a reference to the store.
     #[must_use]
     pub fn store(&self) -> &S {
@@ -200,57 +691,433 @@ impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
         self.scheduler.is_some()
     }
 
-    /// Verify that the user owns the given agent.
-    fn verify_ownership(user_id: &UserId, agent: &Agent) -> Result<()> {
-        if agent.user_id != *user_id {
-            return Err(ControlError::NotOwner {
-                user_id: *user_id,
-                agent_id: agent.agent_id,
-            });
-        }
-        Ok(())
-    }
-
-    /// Get an agent and verify ownership.
-    fn get_and_verify(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent> {
+    /// Get an agent and verify the caller holds at least `required` on it.
+    fn get_and_verify(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        required: AgentRole,
+    ) -> Result<Agent> {
         let agent = self
             .store
             .get_agent(agent_id)?
             .ok_or(ControlError::AgentNotFound(*agent_id))?;
 
-        Self::verify_ownership(user_id, &agent)?;
+        access::authorize(&*self.store, caller, &agent, required)?;
         Ok(agent)
     }
 
-    /// Perform a validated state transition.
-    fn transition_state(&self, agent: &mut Agent, target: AgentState) -> Result<()> {
-        lifecycle::validate_transition(&agent.agent_id, agent.status, target)?;
-        agent.status = target;
-        agent.updated_at = Utc::now();
-        self.store.put_agent(agent)?;
-        Ok(())
+    /// Ensure the caller and their namespace can hold another agent.
+    fn check_quota(&self, caller: &Caller) -> Result<()> {
+        let count = self.store.count_agents_by_user(&caller.user_id)?;
+        if count >= self.config.max_agents_per_user {
+            return Err(ControlError::QuotaExceeded {
+                user_id: caller.user_id,
+                limit: self.config.max_agents_per_user,
+            });
+        }
+        namespace::check_agent_quota(&*self.store, &self.config, &caller.namespace_id)
     }
 
-    /// Schedule an agent pod via the scheduler service.
-    async fn schedule_agent_pod(&self, agent: &Agent) -> Result<()> {
-        if let Some(scheduler) = &self.scheduler {
-            scheduler
-                .schedule_agent(&agent.agent_id, &agent.user_id.to_hex(), &agent.spec)
-                .await?;
-            tracing::info!(
-                agent_id = %agent.agent_id,
-                "Scheduled agent pod via scheduler"
-            );
-        } else {
-            tracing::debug!(
-                agent_id = %agent.agent_id,
-                "No scheduler configured, skipping pod scheduling"
-            );
+    /// Check a spec against the configured resource limits.
+    fn validate_spec(&self, spec: &AgentSpec) -> Result<()> {
+        if spec.cpu_millicores == 0 || spec.memory_mb == 0 {
+            return Err(ControlError::InvalidRequest(
+                "cpu_millicores and memory_mb must be greater than zero".to_string(),
+            ));
+        }
+        if spec.cpu_millicores > self.config.max_cpu_millicores {
+            return Err(ControlError::InvalidRequest(format!(
+                "CPU request {}m exceeds maximum {}m",
+                spec.cpu_millicores, self.config.max_cpu_millicores
+            )));
+        }
+        if spec.memory_mb > self.config.max_memory_mb {
+            return Err(ControlError::InvalidRequest(format!(
+                "Memory request {}Mi exceeds maximum {}Mi",
+                spec.memory_mb, self.config.max_memory_mb
+            )));
+        }
+        match spec.storage_gb {
+            Some(0) => {
+                return Err(ControlError::InvalidRequest(
+                    "storage_gb must be greater than zero".to_string(),
+                ));
+            }
+            Some(storage_gb) if storage_gb > self.config.max_storage_gb => {
+                return Err(ControlError::InvalidRequest(format!(
+                    "Storage request {storage_gb}Gi exceeds maximum {}Gi",
+                    self.config.max_storage_gb
+                )));
+            }
+            _ => {}
+        }
+        rollout::validate_version(&spec.runtime_version)?;
+        if spec.env.len() > self.config.max_env_vars as usize {
+            return Err(ControlError::InvalidRequest(format!(
+                "at most {} environment variables are allowed",
+                self.config.max_env_vars
+            )));
+        }
+        for name in spec.env.keys() {
+            secrets::validate_env_name(name)?;
+        }
+        if let Some(prompt) = &spec.system_prompt {
+            if prompt.len() > self.config.max_system_prompt_bytes as usize {
+                return Err(ControlError::InvalidRequest(format!(
+                    "system prompt exceeds {} bytes",
+                    self.config.max_system_prompt_bytes
+                )));
+            }
+        }
+        if let Some(egress) = &spec.egress {
+            egress::validate(egress)?;
+        }
+        if let Some(placement) = &spec.placement {
+            placement::validate(placement)?;
         }
         Ok(())
     }
 
-    /// Terminate an agent pod via the scheduler service.
+    /// Check agent or template labels against the configured limit.
+    fn validate_labels(&self, labels: &BTreeMap<String, String>) -> Result<()> {
+        if labels.len() > self.config.max_labels as usize {
+            return Err(ControlError::InvalidRequest(format!(
+                "at most {} labels are allowed",
+                self.config.max_labels
+            )));
+        }
+        Ok(())
+    }
+
+    /// Validate a restart policy against configured limits.
+    fn validate_restart_policy(&self, policy: RestartPolicy) -> Result<()> {
+        let max_attempts = policy.max_attempts();
+        let in_range = (1..=self.config.max_restart_attempts).contains(&max_attempts);
+        if !policy.is_never() && !in_range {
+            return Err(ControlError::InvalidRequest(format!(
+                "restart policy max_attempts must be between 1 and {}",
+                self.config.max_restart_attempts
+            )));
+        }
+        Ok(())
+    }
+
+    /// Look up a template visible to the user, preferring their own over admin-defined.
+    fn resolve_template(&self, user_id: &UserId, name: &str) -> Result<AgentTemplate> {
+        if let Some(template) = self.store.get_template(Some(user_id), name)? {
+            return Ok(template);
+        }
+        self.store
+            .get_template(None, name)?
+            .ok_or_else(|| ControlError::TemplateNotFound(name.to_string()))
+    }
+
+    /// Copy one agent's state directory into another's via the scheduler service.
+    async fn copy_agent_state(&self, source: &AgentId, target: &AgentId) -> Result<()> {
+        if let Some(scheduler) = &self.scheduler {
+            scheduler.copy_agent_state(source, target).await?;
+            tracing::info!(
+                source = %source,
+                target = %target,
+                "Copied agent state via scheduler"
+            );
+        } else {
+            tracing::debug!(
+                source = %source,
+                target = %target,
+                "No scheduler configured, skipping state copy"
+            );
+        }
+        Ok(())
+    }
+
+    /// Perform a validated state transition.
+    fn transition_state(&self, agent: &mut Agent, target: AgentState) -> Result<()> {
+        lifecycle::validate_transition(&agent.agent_id, agent.status, target)?;
+        self.apply_state(agent, target)
+    }
+
+    /// Record a state change without consulting the state machine.
+    ///
+    /// Only drift corrections call this directly, since an agent whose pod
+    /// vanished goes from `Running` back to `Provisioning`, which users can't
+    /// ask for.
+    fn apply_state(&self, agent: &mut Agent, target: AgentState) -> Result<()> {
+        if target == AgentState::Provisioning {
+            self.check_not_deleting(&agent.agent_id)?;
+            self.check_not_copying(&agent.agent_id)?;
+        }
+        if target == AgentState::Provisioning && agent.status != target {
+            agent.provisioning_started_at = Some(Utc::now());
+        }
+        agent.status = target;
+        agent.updated_at = Utc::now();
+        if target != AgentState::Error {
+            agent.restarts.next_restart_at = None;
+        }
+        self.store.put_agent(agent)?;
+        self.enqueue_state_event(agent);
+        Ok(())
+    }
+
+    /// Ask the agent's runtime to flush its state before its pod is removed.
+    ///
+    /// If the runtime cannot be reached or does not confirm the flush in
+    /// time, the checkpoint is marked as forced and the pod is stopped anyway.
+    async fn checkpoint_runtime(&self, agent: &Agent) -> HibernationCheckpoint {
+        let Some(scheduler) = &self.scheduler else {
+            tracing::debug!(
+                agent_id = %agent.agent_id,
+                "No scheduler configured, skipping runtime hibernate"
+            );
+            return HibernationCheckpoint {
+                hibernated_at: Utc::now(),
+                state_size_bytes: None,
+                forced: false,
+            };
+        };
+
+        let result = match scheduler.get_pod_endpoint(&agent.agent_id).await {
+            Ok(Some(endpoint)) => self
+                .runtime
+                .hibernate(&endpoint)
+                .await
+                .map_err(ControlError::from),
+            Ok(None) => Err(RuntimeError::NoEndpoint.into()),
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(ack) => {
+                tracing::info!(
+                    agent_id = %agent.agent_id,
+                    state_size_bytes = ?ack.state_size_bytes,
+                    "Runtime flushed state for hibernation"
+                );
+                HibernationCheckpoint {
+                    hibernated_at: Utc::now(),
+                    state_size_bytes: ack.state_size_bytes,
+                    forced: false,
+                }
+            }
+            Err(error) => {
+                tracing::warn!(
+                    agent_id = %agent.agent_id,
+                    error = %error,
+                    "Runtime did not acknowledge hibernation, forcing stop"
+                );
+                HibernationCheckpoint {
+                    hibernated_at: Utc::now(),
+                    state_size_bytes: None,
+                    forced: true,
+                }
+            }
+        }
+    }
+
+    /// Enqueue webhook deliveries for an agent whose status was just changed.
+    fn notify_state_change(&self, agent_id: &AgentId) {
+        match self.store.get_agent(agent_id) {
+            Ok(Some(agent)) => self.enqueue_state_event(&agent),
+            Ok(None) => {}
+            Err(e) => {
+                tracing::warn!(
+                    agent_id = %agent_id,
+                    error = %e,
+                    "Failed to load agent for webhooks"
+                );
+            }
+        }
+    }
+
+    /// Reject starting an agent whose deletion has not finished.
+    fn check_not_deleting(&self, agent_id: &AgentId) -> Result<()> {
+        match self.store.get_agent_deletion(agent_id)? {
+            Some(deletion) if deletion::is_pending(&deletion) => {
+                Err(ControlError::AgentDeleting(*agent_id))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Clones whose state has not been copied yet.
+    fn pending_clones(&self) -> Result<HashSet<AgentId>> {
+        Ok(self
+            .store
+            .list_state_copies()?
+            .into_iter()
+            .map(|copy| copy.agent_id)
+            .collect())
+    }
+
+    /// Reject changing an agent whose state is still being copied to a clone.
+    fn check_not_copying(&self, agent_id: &AgentId) -> Result<()> {
+        if self
+            .store
+            .list_state_copies()?
+            .iter()
+            .any(|copy| copy.source_id == *agent_id)
+        {
+            return Err(ControlError::StateCopyPending(*agent_id));
+        }
+        Ok(())
+    }
+
+    /// Close all of an agent's active sessions.
+    fn close_agent_sessions(&self, agent_id: &AgentId) -> Result<()> {
+        for session in self.store.list_sessions_by_agent(agent_id)? {
+            if session.status == aura_swarm_store::SessionStatus::Active {
+                self.store.update_session_status(
+                    &session.session_id,
+                    aura_swarm_store::SessionStatus::Closed,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Record an action the control plane took on an agent on its own.
+    fn audit_agent_action(
+        &self,
+        event_type: &str,
+        agent: &Agent,
+        outcome: AuditOutcome,
+        error: Option<&str>,
+    ) {
+        let action = event_type.rsplit('.').next().unwrap_or(event_type);
+        let resource = audit::agent_resource(&agent.agent_id);
+        let mut event = audit::system_event(event_type, action, resource, outcome);
+        event.namespace_id = agent.namespace_id;
+        if let Some(error) = error {
+            event.details.insert("error".to_string(), error.to_string());
+        }
+        self.audit.log(event);
+    }
+
+    /// Enqueue webhook deliveries for the agent's current state.
+    ///
+    /// Failures are logged rather than returned so that webhook problems never
+    /// block lifecycle operations.
+    fn enqueue_state_event(&self, agent: &Agent) {
+        let Some(event) = WebhookEvent::for_state(agent.status) else {
+            return;
+        };
+
+        let data = serde_json::json!({
+            "agent_id": agent.agent_id.to_string(),
+            "name": agent.name,
+            "state": agent.status,
+            "error_message": agent.error_message,
+        });
+
+        if let Err(e) = self.enqueue_event(&agent.user_id, event, &data) {
+            tracing::warn!(
+                agent_id = %agent.agent_id,
+                event = event.as_str(),
+                error = %e,
+                "Failed to enqueue webhook deliveries"
+            );
+        }
+    }
+
+    /// Write one pending delivery per subscribed webhook to the outbox.
+    fn enqueue_event(
+        &self,
+        user_id: &UserId,
+        event: WebhookEvent,
+        data: &serde_json::Value,
+    ) -> Result<()> {
+        for hook in self.store.list_webhooks_by_user(user_id)? {
+            if hook.subscribes_to(event) {
+                let delivery = Self::new_delivery(&hook, event, data);
+                self.store.put_webhook_delivery(&delivery)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Build a pending delivery that is due immediately.
+    fn new_delivery(
+        hook: &Webhook,
+        event: WebhookEvent,
+        data: &serde_json::Value,
+    ) -> WebhookDelivery {
+        let delivery_id = DeliveryId::generate();
+        let now = Utc::now();
+        WebhookDelivery {
+            delivery_id,
+            webhook_id: hook.webhook_id,
+            event,
+            payload: webhook::build_payload(&delivery_id, event, now, data),
+            status: DeliveryStatus::Pending,
+            attempts: Vec::new(),
+            next_attempt_at: Some(now),
+            created_at: now,
+        }
+    }
+
+    /// Get a webhook and verify ownership.
+    ///
+    /// Webhooks owned by other users are reported as not found.
+    fn get_webhook_verified(&self, user_id: &UserId, webhook_id: &WebhookId) -> Result<Webhook> {
+        self.store
+            .get_webhook(webhook_id)?
+            .filter(|hook| hook.user_id == *user_id)
+            .ok_or(ControlError::WebhookNotFound(*webhook_id))
+    }
+
+    /// Unseal all of an agent's secrets.
+    fn unseal_agent_secrets(&self, agent_id: &AgentId) -> Result<BTreeMap<String, String>> {
+        let stored = self.store.list_agent_secrets(agent_id)?;
+        if stored.is_empty() {
+            return Ok(BTreeMap::new());
+        }
+
+        let cipher = self.secrets.as_ref().ok_or_else(|| {
+            ControlError::Internal("agent has secrets but no secret key is configured".to_string())
+        })?;
+        stored
+            .into_iter()
+            .map(|secret| {
+                let value = cipher.open(agent_id, &secret.name, &secret.ciphertext)?;
+                Ok((secret.name, value))
+            })
+            .collect()
+    }
+
+    /// Write the agent's current secrets to the cluster, then schedule its pod.
+    ///
+    /// Secrets are rewritten on every placement, so changed values take effect
+    /// when the agent next starts.
+    async fn place_agent_pod(&self, scheduler: &SC, agent: &Agent) -> Result<()> {
+        let secrets = self.unseal_agent_secrets(&agent.agent_id)?;
+        scheduler
+            .put_agent_secrets(&agent.agent_id, &secrets)
+            .await?;
+        scheduler
+            .schedule_agent(&agent.agent_id, &agent.user_id.to_hex(), &agent.spec)
+            .await
+    }
+
+    /// Schedule an agent pod via the scheduler service.
+    async fn schedule_agent_pod(&self, agent: &Agent) -> Result<()> {
+        if let Some(scheduler) = &self.scheduler {
+            self.place_agent_pod(scheduler, agent).await?;
+            tracing::info!(
+                agent_id = %agent.agent_id,
+                "Scheduled agent pod via scheduler"
+            );
+        } else {
+            tracing::debug!(
+                agent_id = %agent.agent_id,
+                "No scheduler configured, skipping pod scheduling"
+            );
+        }
+        Ok(())
+    }
+
+    /// Terminate an agent pod via the scheduler service.
     async fn terminate_agent_pod(&self, agent_id: &AgentId) -> Result<()> {
         if let Some(scheduler) = &self.scheduler {
             scheduler.terminate_agent(agent_id).await?;
@@ -274,23 +1141,34 @@ impl<S: Store + 'static, SC: SchedulerClient + 'static> ControlPlane for Control
     // Agent CRUD Operations
     // =========================================================================
 
-    async fn create_agent(&self, user_id: &UserId, request: CreateAgentRequest) -> Result<Agent> {
-        // Check quota
-        let count = self.store.count_agents_by_user(user_id)?;
-        if count >= self.config.max_agents_per_user {
-            return Err(ControlError::QuotaExceeded {
-                user_id: *user_id,
-                limit: self.config.max_agents_per_user,
-            });
-        }
+    async fn create_agent(&self, caller: &Caller, request: CreateAgentRequest) -> Result<Agent> {
+        self.check_quota(caller)?;
+
+        let (base, mut labels) = match (request.template.as_deref(), request.spec) {
+            (Some(_), Some(_)) => {
+                return Err(ControlError::InvalidRequest(
+                    "spec cannot be combined with template; use overrides instead".to_string(),
+                ))
+            }
+            (Some(name), None) => {
+                let template = self.resolve_template(&caller.user_id, name)?;
+                (template.spec, template.labels)
+            }
+            (None, spec) => (spec.unwrap_or_default(), BTreeMap::new()),
+        };
+        let spec = request.overrides.apply(base);
+        self.validate_spec(&spec)?;
+        self.validate_restart_policy(request.restart_policy)?;
+        labels.extend(request.labels);
+        self.validate_labels(&labels)?;
 
         let now = Utc::now();
-        let spec = request.spec.unwrap_or_default();
-        let agent_id = AgentId::generate(user_id, &request.name);
+        let agent_id = AgentId::generate(&caller.user_id, &request.name);
 
         let agent = Agent {
             agent_id,
-            user_id: *user_id,
+            user_id: caller.user_id,
+            namespace_id: Some(caller.namespace_id),
             name: request.name,
             status: AgentState::Provisioning,
             spec,
@@ -298,6 +1176,13 @@ impl<S: Store + 'static, SC: SchedulerClient + 'static> ControlPlane for Control
             updated_at: now,
             last_heartbeat_at: None,
             error_message: None,
+            cloned_from: None,
+            template: request.template,
+            labels,
+            restart_policy: request.restart_policy,
+            restarts: RestartState::default(),
+            hibernation: None,
+            provisioning_started_at: Some(now),
         };
 
         self.store.put_agent(&agent)?;
@@ -317,12 +1202,13 @@ impl<S: Store + 'static, SC: SchedulerClient + 'static> ControlPlane for Control
                     Some(e.to_string()),
                 )
                 .ok();
+            self.notify_state_change(&agent.agent_id);
             return Err(e);
         }
 
         tracing::info!(
             agent_id = %agent.agent_id,
-            user_id = %user_id,
+            user_id = %caller.user_id,
             name = %agent.name,
             "Created agent"
         );
@@ -330,145 +1216,584 @@ impl<S: Store + 'static, SC: SchedulerClient + 'static> ControlPlane for Control
         Ok(agent)
     }
 
-    async fn get_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent> {
-        self.get_and_verify(user_id, agent_id)
-    }
-
-    async fn list_agents(&self, user_id: &UserId) -> Result<Vec<Agent>> {
-        Ok(self.store.list_agents_by_user(user_id)?)
+    async fn get_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent> {
+        self.get_and_verify(caller, agent_id, AgentRole::Viewer)
     }
 
-    async fn delete_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<()> {
-        let agent = self.get_and_verify(user_id, agent_id)?;
-
-        // Can only delete stopped or error agents
-        if !lifecycle::is_terminal(agent.status) {
-            return Err(ControlError::InvalidState {
-                agent_id: *agent_id,
-                from: agent.status,
-                to: AgentState::Stopped, // Indicate they need to stop first
-            });
+    #[allow(clippy::cast_precision_loss)]
+    async fn get_agent_status(&self, caller: &Caller, agent_id: &AgentId) -> Result<AgentStatus> {
+        let agent = self.get_and_verify(caller, agent_id, AgentRole::Viewer)?;
+        let active_sessions = session::count_active_sessions(&*self.store, agent_id)?;
+
+        // A status read shouldn't fail because metrics-server is missing or slow
+        let metrics = match &self.scheduler {
+            Some(scheduler) if lifecycle::is_active(agent.status) => {
+                match scheduler.get_pod_metrics(agent_id).await {
+                    Ok(metrics) => metrics,
+                    Err(e) => {
+                        tracing::warn!(
+                            agent_id = %agent_id,
+                            error = %e,
+                            "Failed to fetch pod metrics, reporting no usage"
+                        );
+                        None
+                    }
+                }
+            }
+            _ => None,
         }
+        .unwrap_or_default();
 
-        // Delete all sessions for this agent
-        let sessions = self.store.list_sessions_by_agent(agent_id)?;
-        for session in sessions {
-            self.store.delete_session(&session.session_id)?;
-        }
+        let cpu_usage = if agent.spec.cpu_millicores == 0 {
+            0.0
+        } else {
+            metrics.cpu_millicores as f64 / f64::from(agent.spec.cpu_millicores)
+        };
 
-        self.store.delete_agent(agent_id)?;
+        Ok(AgentStatus {
+            healthy: matches!(agent.status, AgentState::Running | AgentState::Idle),
+            cpu_usage,
+            memory_bytes: metrics.memory_bytes,
+            active_sessions: u32::try_from(active_sessions).unwrap_or(u32::MAX),
+            uptime_seconds: u64::try_from((Utc::now() - agent.created_at).num_seconds())
+                .unwrap_or(0),
+        })
+    }
 
-        tracing::info!(
-            agent_id = %agent_id,
-            user_id = %user_id,
-            "Deleted agent"
-        );
+    async fn agent_logs(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        options: LogOptions,
+    ) -> Result<LogEntryStream> {
+        self.get_and_verify(caller, agent_id, AgentRole::Viewer)?;
 
-        Ok(())
+        if let Some(scheduler) = &self.scheduler {
+            logs::stream(&**scheduler, agent_id, &options).await
+        } else {
+            logs::validate(&options)?;
+            Ok(futures::stream::empty().boxed())
+        }
     }
 
-    // =========================================================================
-    // Lifecycle Operations
-    // =========================================================================
+    async fn list_agents(&self, caller: &Caller) -> Result<Vec<Agent>> {
+        let agents = self.store.list_agents_by_user(&caller.user_id)?;
+        Ok(agents
+            .into_iter()
+            .filter(|agent| namespace::in_scope(caller, agent))
+            .collect())
+    }
 
-    async fn start_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent> {
-        let mut agent = self.get_and_verify(user_id, agent_id)?;
+    async fn delete_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<AgentDeletion> {
+        let agent = self.get_and_verify(caller, agent_id, AgentRole::Owner)?;
+
+        let mut deletion = match self.store.get_agent_deletion(agent_id)? {
+            // Resume a deletion that failed or was interrupted
+            Some(deletion) if deletion::is_pending(&deletion) => deletion,
+            _ => {
+                // Can only delete stopped or error agents
+                if !lifecycle::is_terminal(agent.status) {
+                    return Err(ControlError::InvalidState {
+                        agent_id: *agent_id,
+                        from: agent.status,
+                        to: AgentState::Stopped, // Indicate they need to stop first
+                    });
+                }
+                self.check_not_copying(agent_id)?;
+                deletion::new_deletion(&agent, caller.user_id, Utc::now())
+            }
+        };
 
-        // Can only start from Stopped state
-        self.transition_state(&mut agent, AgentState::Provisioning)?;
+        self.run_deletion(&mut deletion).await?;
 
-        // Schedule the agent pod
-        if let Err(e) = self.schedule_agent_pod(&agent).await {
-            tracing::error!(
+        if deletion.status == DeletionStatus::Completed {
+            tracing::info!(
                 agent_id = %agent_id,
-                error = %e,
-                "Failed to schedule agent pod on start"
+                user_id = %caller.user_id,
+                "Deleted agent"
             );
-            self.store
-                .update_agent_status(agent_id, AgentState::Error)
-                .ok();
-            return Err(e);
         }
 
-        tracing::info!(agent_id = %agent_id, "Starting agent");
+        Ok(deletion)
+    }
 
-        Ok(agent)
+    async fn get_agent_deletion(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+    ) -> Result<AgentDeletion> {
+        self.store
+            .get_agent_deletion(agent_id)?
+            .filter(|deletion| deletion::can_view(caller, deletion))
+            .ok_or(ControlError::DeletionNotFound(*agent_id))
     }
 
-    async fn stop_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent> {
-        let mut agent = self.get_and_verify(user_id, agent_id)?;
+    async fn clone_agent(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        request: CloneAgentRequest,
+    ) -> Result<Agent> {
+        let source = self.get_and_verify(caller, agent_id, AgentRole::Owner)?;
+        self.check_not_deleting(agent_id)?;
 
-        // Close all active sessions
-        let sessions = self.store.list_sessions_by_agent(agent_id)?;
-        for session in sessions {
-            if session.status == aura_swarm_store::SessionStatus::Active {
-                self.store.update_session_status(
-                    &session.session_id,
-                    aura_swarm_store::SessionStatus::Closed,
-                )?;
-            }
+        // The source's state must not change while it is being copied
+        if !lifecycle::can_clone(source.status) {
+            return Err(ControlError::InvalidState {
+                agent_id: *agent_id,
+                from: source.status,
+                to: AgentState::Stopped, // Indicate they need to stop first
+            });
         }
 
-        // Transition to Stopping
-        self.transition_state(&mut agent, AgentState::Stopping)?;
+        self.check_quota(caller)?;
 
-        // Terminate the agent pod
-        if let Err(e) = self.terminate_agent_pod(agent_id).await {
-            tracing::error!(
-                agent_id = %agent_id,
-                error = %e,
-                "Failed to terminate agent pod on stop"
-            );
-            // Don't fail the stop operation, just log - the scheduler will clean up
-        }
+        let now = Utc::now();
+        let name = request
+            .name
+            .unwrap_or_else(|| CloneAgentRequest::default_name(&source.name));
+        let clone_id = AgentId::generate(&caller.user_id, &name);
 
-        tracing::info!(agent_id = %agent_id, "Stopping agent");
+        let agent = Agent {
+            agent_id: clone_id,
+            user_id: caller.user_id,
+            namespace_id: Some(caller.namespace_id),
+            name,
+            status: AgentState::Provisioning,
+            spec: source.spec.clone(),
+            created_at: now,
+            updated_at: now,
+            last_heartbeat_at: None,
+            error_message: None,
+            cloned_from: Some(*agent_id),
+            template: source.template.clone(),
+            labels: source.labels.clone(),
+            restart_policy: source.restart_policy,
+            restarts: RestartState::default(),
+            hibernation: None,
+            // Set once the state copy finishes and the pod is scheduled
+            provisioning_started_at: None,
+        };
 
-        Ok(agent)
-    }
+        self.store.put_agent(&agent)?;
 
-    async fn restart_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent> {
-        // Stop the agent (this will terminate the pod)
-        let mut agent = self.stop_agent(user_id, agent_id).await?;
+        // The clone worker copies the state, then starts the clone's pod
+        self.store.put_state_copy(&StateCopy {
+            agent_id: clone_id,
+            source_id: *agent_id,
+            attempts: 0,
+            next_attempt_at: None,
+            created_at: now,
+        })?;
 
-        // Transition to Stopped state
-        self.transition_state(&mut agent, AgentState::Stopped)?;
+        tracing::info!(
+            agent_id = %clone_id,
+            source = %agent_id,
+            user_id = %caller.user_id,
+            name = %agent.name,
+            "Cloned agent, state copy queued"
+        );
 
-        // Start again (this will schedule a new pod)
-        self.transition_state(&mut agent, AgentState::Provisioning)?;
+        Ok(agent)
+    }
 
-        // Schedule the new pod
-        if let Err(e) = self.schedule_agent_pod(&agent).await {
-            tracing::error!(
-                agent_id = %agent_id,
-                error = %e,
-                "Failed to schedule agent pod on restart"
-            );
-            self.store
-                .update_agent_status(agent_id, AgentState::Error)
-                .ok();
-            return Err(e);
-        }
+    // =========================================================================
+    // Access Operations
+    // =========================================================================
 
-        tracing::info!(agent_id = %agent_id, "Restarting agent");
+    async fn list_shared_agents(&self, caller: &Caller) -> Result<Vec<SharedAgent>> {
+        let mut shared = Vec::new();
+        for grant in self.store.list_grants_by_user(&caller.user_id)? {
+            let Some(agent) = self.store.get_agent(&grant.agent_id)? else {
+                continue;
+            };
+            if namespace::in_scope(caller, &agent) {
+                shared.push(SharedAgent {
+                    agent,
+                    role: grant.role,
+                    granted_by: grant.granted_by,
+                });
+            }
+        }
+        Ok(shared)
+    }
 
-        Ok(agent)
+    async fn list_agent_grants(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+    ) -> Result<Vec<AgentGrant>> {
+        self.get_and_verify(caller, agent_id, AgentRole::Owner)?;
+        Ok(self.store.list_agent_grants(agent_id)?)
     }
 
-    async fn hibernate_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent> {
-        let mut agent = self.get_and_verify(user_id, agent_id)?;
+    async fn grant_agent_access(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        user_id: &UserId,
+        role: AgentRole,
+    ) -> Result<AgentGrant> {
+        let agent = self.get_and_verify(caller, agent_id, AgentRole::Owner)?;
+        if agent.user_id == *user_id {
+            return Err(ControlError::InvalidRequest(
+                "the agent's creator is always an owner".to_string(),
+            ));
+        }
 
-        // Close all active sessions
-        let sessions = self.store.list_sessions_by_agent(agent_id)?;
-        for session in sessions {
-            if session.status == aura_swarm_store::SessionStatus::Active {
-                self.store.update_session_status(
-                    &session.session_id,
-                    aura_swarm_store::SessionStatus::Closed,
-                )?;
+        let now = Utc::now();
+        let existing = self.store.get_agent_grant(agent_id, user_id)?;
+        let grant = AgentGrant {
+            agent_id: *agent_id,
+            user_id: *user_id,
+            role,
+            granted_by: caller.user_id,
+            created_at: existing.map_or(now, |g| g.created_at),
+            updated_at: now,
+        };
+        self.store.put_agent_grant(&grant)?;
+
+        tracing::info!(
+            agent_id = %agent_id,
+            user_id = %user_id,
+            role = ?role,
+            granted_by = %caller.user_id,
+            "Granted agent access"
+        );
+
+        Ok(grant)
+    }
+
+    async fn revoke_agent_access(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        user_id: &UserId,
+    ) -> Result<()> {
+        // Collaborators may always drop their own access
+        let required = if *user_id == caller.user_id {
+            AgentRole::Viewer
+        } else {
+            AgentRole::Owner
+        };
+        self.get_and_verify(caller, agent_id, required)?;
+
+        match self.store.delete_agent_grant(agent_id, user_id) {
+            Ok(()) => {}
+            Err(StoreError::NotFound) => {
+                return Err(ControlError::GrantNotFound {
+                    agent_id: *agent_id,
+                    user_id: *user_id,
+                })
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        tracing::info!(
+            agent_id = %agent_id,
+            user_id = %user_id,
+            revoked_by = %caller.user_id,
+            "Revoked agent access"
+        );
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // Secret Operations
+    // =========================================================================
+
+    async fn list_agent_secrets(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+    ) -> Result<Vec<AgentSecret>> {
+        self.get_and_verify(caller, agent_id, AgentRole::Owner)?;
+        Ok(self.store.list_agent_secrets(agent_id)?)
+    }
+
+    async fn set_agent_secret(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        name: &str,
+        value: &str,
+    ) -> Result<AgentSecret> {
+        let agent = self.get_and_verify(caller, agent_id, AgentRole::Owner)?;
+        let cipher = self.secrets.as_ref().ok_or_else(|| {
+            ControlError::InvalidRequest("agent secrets are not enabled".to_string())
+        })?;
+        secrets::validate_name(name)?;
+        secrets::validate_value(value)?;
+        if agent.spec.env.contains_key(name) {
+            return Err(ControlError::InvalidRequest(format!(
+                "secret name {name} is already an environment variable in the agent spec"
+            )));
+        }
+
+        let existing = self.store.get_agent_secret(agent_id, name)?;
+        if existing.is_none() {
+            let count = self.store.list_agent_secrets(agent_id)?.len();
+            if count >= self.config.max_secrets_per_agent as usize {
+                return Err(ControlError::InvalidRequest(format!(
+                    "agent already has the maximum of {} secrets",
+                    self.config.max_secrets_per_agent
+                )));
+            }
+        }
+
+        let now = Utc::now();
+        let secret = AgentSecret {
+            agent_id: *agent_id,
+            name: name.to_string(),
+            ciphertext: cipher.seal(agent_id, name, value)?,
+            updated_by: caller.user_id,
+            created_at: existing.map_or(now, |s| s.created_at),
+            updated_at: now,
+        };
+        self.store.put_agent_secret(&secret)?;
+
+        tracing::info!(
+            agent_id = %agent_id,
+            name = %name,
+            user_id = %caller.user_id,
+            "Set agent secret"
+        );
+
+        Ok(secret)
+    }
+
+    async fn delete_agent_secret(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        name: &str,
+    ) -> Result<()> {
+        self.get_and_verify(caller, agent_id, AgentRole::Owner)?;
+
+        match self.store.delete_agent_secret(agent_id, name) {
+            Ok(()) => {}
+            Err(StoreError::NotFound) => {
+                return Err(ControlError::SecretNotFound {
+                    agent_id: *agent_id,
+                    name: name.to_string(),
+                })
             }
+            Err(e) => return Err(e.into()),
+        }
+
+        tracing::info!(
+            agent_id = %agent_id,
+            name = %name,
+            user_id = %caller.user_id,
+            "Deleted agent secret"
+        );
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // Namespace Operations
+    // =========================================================================
+
+    async fn list_namespace_agents(&self, caller: &Caller) -> Result<Vec<Agent>> {
+        namespace::require_admin(caller)?;
+        Ok(self.store.list_agents_by_namespace(&caller.namespace_id)?)
+    }
+
+    async fn get_namespace_usage(&self, caller: &Caller) -> Result<NamespaceUsage> {
+        namespace::require_admin(caller)?;
+        namespace::usage(&*self.store, &self.config, &caller.namespace_id)
+    }
+
+    async fn set_namespace_quota(
+        &self,
+        namespace_id: &NamespaceId,
+        request: SetNamespaceQuotaRequest,
+    ) -> Result<NamespaceQuota> {
+        let quota = NamespaceQuota {
+            namespace_id: *namespace_id,
+            max_agents: request.max_agents,
+            max_sessions: request.max_sessions,
+            updated_at: Utc::now(),
+        };
+        self.store.put_namespace_quota(&quota)?;
+
+        tracing::info!(
+            namespace_id = %namespace_id,
+            max_agents = ?quota.max_agents,
+            max_sessions = ?quota.max_sessions,
+            "Set namespace quota"
+        );
+
+        Ok(quota)
+    }
+
+    // =========================================================================
+    // Template Operations
+    // =========================================================================
+
+    async fn create_template(
+        &self,
+        owner: Option<&UserId>,
+        request: CreateTemplateRequest,
+    ) -> Result<AgentTemplate> {
+        let spec = request.spec.unwrap_or_default();
+        self.validate_spec(&spec)?;
+        self.validate_labels(&request.labels)?;
+
+        if self.store.get_template(owner, &request.name)?.is_some() {
+            return Err(ControlError::TemplateExists(request.name));
+        }
+        if owner.is_some() {
+            let count = self.store.list_templates(owner)?.len();
+            if count >= self.config.max_templates_per_user as usize {
+                return Err(ControlError::InvalidRequest(format!(
+                    "template limit reached: at most {} templates per user",
+                    self.config.max_templates_per_user
+                )));
+            }
+        }
+
+        let now = Utc::now();
+        let template = AgentTemplate {
+            name: request.name,
+            owner: owner.copied(),
+            description: request.description,
+            spec,
+            labels: request.labels,
+            created_at: now,
+            updated_at: now,
+        };
+
+        self.store.put_template(&template)?;
+
+        tracing::info!(
+            name = %template.name,
+            global = owner.is_none(),
+            "Created agent template"
+        );
+
+        Ok(template)
+    }
+
+    async fn list_templates(&self, user_id: &UserId) -> Result<Vec<AgentTemplate>> {
+        let mut templates = self.store.list_templates(None)?;
+        templates.extend(self.store.list_templates(Some(user_id))?);
+        Ok(templates)
+    }
+
+    async fn get_template(&self, user_id: &UserId, name: &str) -> Result<AgentTemplate> {
+        self.resolve_template(user_id, name)
+    }
+
+    async fn delete_template(&self, owner: Option<&UserId>, name: &str) -> Result<()> {
+        if self.store.get_template(owner, name)?.is_none() {
+            return Err(ControlError::TemplateNotFound(name.to_string()));
+        }
+        self.store.delete_template(owner, name)?;
+
+        tracing::info!(name = %name, global = owner.is_none(), "Deleted agent template");
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // Lifecycle Operations
+    // =========================================================================
+
+    async fn start_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent> {
+        let mut agent = self.get_and_verify(caller, agent_id, AgentRole::Operator)?;
+
+        // A manual start gives automatic restarts a fresh budget
+        agent.restarts = RestartState::default();
+
+        // Can only start from Stopped state
+        self.transition_state(&mut agent, AgentState::Provisioning)?;
+
+        // Schedule the agent pod
+        if let Err(e) = self.schedule_agent_pod(&agent).await {
+            tracing::error!(
+                agent_id = %agent_id,
+                error = %e,
+                "Failed to schedule agent pod on start"
+            );
+            self.store
+                .update_agent_status(agent_id, AgentState::Error)
+                .ok();
+            self.notify_state_change(agent_id);
+            return Err(e);
+        }
+
+        tracing::info!(agent_id = %agent_id, "Starting agent");
+
+        Ok(agent)
+    }
+
+    async fn stop_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent> {
+        let mut agent = self.get_and_verify(caller, agent_id, AgentRole::Operator)?;
+
+        // Close all active sessions
+        self.close_agent_sessions(agent_id)?;
+
+        // Transition to Stopping
+        self.transition_state(&mut agent, AgentState::Stopping)?;
+
+        // Terminate the agent pod
+        if let Err(e) = self.terminate_agent_pod(agent_id).await {
+            tracing::error!(
+                agent_id = %agent_id,
+                error = %e,
+                "Failed to terminate agent pod on stop"
+            );
+            // Don't fail the stop operation, just log - the scheduler will clean up
+        }
+
+        tracing::info!(agent_id = %agent_id, "Stopping agent");
+
+        Ok(agent)
+    }
+
+    async fn restart_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent> {
+        // Stop the agent (this will terminate the pod)
+        let mut agent = self.stop_agent(caller, agent_id).await?;
+
+        // Transition to Stopped state
+        self.transition_state(&mut agent, AgentState::Stopped)?;
+
+        // Start again (this will schedule a new pod)
+        agent.restarts = RestartState::default();
+        self.transition_state(&mut agent, AgentState::Provisioning)?;
+
+        // Schedule the new pod
+        if let Err(e) = self.schedule_agent_pod(&agent).await {
+            tracing::error!(
+                agent_id = %agent_id,
+                error = %e,
+                "Failed to schedule agent pod on restart"
+            );
+            self.store
+                .update_agent_status(agent_id, AgentState::Error)
+                .ok();
+            self.notify_state_change(agent_id);
+            return Err(e);
         }
 
+        tracing::info!(agent_id = %agent_id, "Restarting agent");
+
+        Ok(agent)
+    }
+
+    async fn hibernate_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent> {
+        let mut agent = self.get_and_verify(caller, agent_id, AgentRole::Operator)?;
+
+        // Close all active sessions
+        self.close_agent_sessions(agent_id)?;
+
+        // Check the transition before asking the runtime to shut down
+        lifecycle::validate_transition(agent_id, agent.status, AgentState::Hibernating)?;
+
+        agent.hibernation = Some(self.checkpoint_runtime(&agent).await);
         self.transition_state(&mut agent, AgentState::Hibernating)?;
 
         // Terminate the agent pod (but keep state saved)
@@ -486,8 +1811,8 @@ impl<S: Store + 'static, SC: SchedulerClient + 'static> ControlPlane for Control
         Ok(agent)
     }
 
-    async fn wake_agent(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Agent> {
-        let mut agent = self.get_and_verify(user_id, agent_id)?;
+    async fn wake_agent(&self, caller: &Caller, agent_id: &AgentId) -> Result<Agent> {
+        let mut agent = self.get_and_verify(caller, agent_id, AgentRole::Operator)?;
 
         if !lifecycle::can_wake(agent.status) {
             return Err(ControlError::InvalidState {
@@ -511,6 +1836,7 @@ impl<S: Store + 'static, SC: SchedulerClient + 'static> ControlPlane for Control
             self.store
                 .update_agent_status(agent_id, AgentState::Error)
                 .ok();
+            self.notify_state_change(agent_id);
             return Err(e);
         }
 
@@ -520,77 +1846,362 @@ impl<S: Store + 'static, SC: SchedulerClient + 'static> ControlPlane for Control
     }
 
     // =========================================================================
-    // Session Operations
+    // Schedule Operations
     // =========================================================================
 
-    async fn create_session(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Session> {
-        let (session, state_change) = session::create_session(&*self.store, user_id, agent_id)?;
+    async fn set_schedule(
+        &self,
+        caller: &Caller,
+        agent_id: &AgentId,
+        request: SetScheduleRequest,
+    ) -> Result<AgentSchedule> {
+        self.get_and_verify(caller, agent_id, AgentRole::Operator)?;
+        schedule::validate(&request.timezone, &request.rules)?;
+
+        let now = Utc::now();
+        let existing = self.store.get_schedule(agent_id)?;
+
+        let schedule = AgentSchedule {
+            agent_id: *agent_id,
+            user_id: caller.user_id,
+            timezone: request.timezone,
+            rules: request.rules,
+            enabled: request.enabled,
+            missed_runs: request.missed_runs,
+            last_checked_at: now,
+            last_run: existing.as_ref().and_then(|s| s.last_run.clone()),
+            created_at: existing.map_or(now, |s| s.created_at),
+            updated_at: now,
+        };
+
+        self.store.put_schedule(&schedule)?;
 
         tracing::info!(
-            session_id = %session.session_id,
             agent_id = %agent_id,
-            state_change = ?state_change,
-            "Created session"
+            rules = schedule.rules.len(),
+            timezone = %schedule.timezone,
+            enabled = schedule.enabled,
+            "Set agent schedule"
         );
 
-        Ok(session)
+        Ok(schedule)
     }
 
-    async fn get_session(&self, user_id: &UserId, session_id: &SessionId) -> Result<Session> {
-        session::get_session(&*self.store, user_id, session_id)
+    async fn get_schedule(&self, caller: &Caller, agent_id: &AgentId) -> Result<AgentSchedule> {
+        self.get_and_verify(caller, agent_id, AgentRole::Viewer)?;
+        self.store
+            .get_schedule(agent_id)?
+            .ok_or(ControlError::ScheduleNotFound(*agent_id))
     }
 
-    async fn close_session(&self, user_id: &UserId, session_id: &SessionId) -> Result<()> {
-        let closed = session::close_session(&*self.store, user_id, session_id)?;
+    async fn delete_schedule(&self, caller: &Caller, agent_id: &AgentId) -> Result<()> {
+        self.get_and_verify(caller, agent_id, AgentRole::Operator)?;
 
-        if closed {
-            tracing::info!(session_id = %session_id, "Closed session");
+        match self.store.delete_schedule(agent_id) {
+            Ok(()) => {
+                tracing::info!(agent_id = %agent_id, "Deleted agent schedule");
+                Ok(())
+            }
+            Err(StoreError::NotFound) => Err(ControlError::ScheduleNotFound(*agent_id)),
+            Err(e) => Err(e.into()),
         }
-
-        Ok(())
-    }
-
-    async fn list_sessions(&self, user_id: &UserId, agent_id: &AgentId) -> Result<Vec<Session>> {
-        session::list_sessions(&*self.store, user_id, agent_id)
     }
 
     // =========================================================================
-    // Operational
+    // Webhook Operations
     // =========================================================================
 
-    async fn process_heartbeat(&self, agent_id: &AgentId) -> Result<()> {
-        let mut agent = self
-            .store
-            .get_agent(agent_id)?
-            .ok_or(ControlError::AgentNotFound(*agent_id))?;
+    async fn create_webhook(
+        &self,
+        user_id: &UserId,
+        request: CreateWebhookRequest,
+    ) -> Result<CreatedWebhook> {
+        let cipher = self.secrets.as_ref().ok_or_else(|| {
+            ControlError::InvalidRequest(
+                "webhooks are not enabled: no secret key is configured".to_string(),
+            )
+        })?;
+        webhook::validate_url(&request.url)?;
+        if !self.config.webhook_allow_private_targets {
+            webhook::check_destination(&request.url).await?;
+        }
+        if request.events.contains(&WebhookEvent::Ping) {
+            return Err(ControlError::InvalidRequest(
+                "ping cannot be used as an event filter".to_string(),
+            ));
+        }
 
-        agent.last_heartbeat_at = Some(Utc::now());
-        agent.updated_at = Utc::now();
-        self.store.put_agent(&agent)?;
+        let existing = self.store.list_webhooks_by_user(user_id)?;
+        if existing.len() >= self.config.max_webhooks_per_user as usize {
+            return Err(ControlError::InvalidRequest(format!(
+                "webhook limit reached: at most {} webhooks per user",
+                self.config.max_webhooks_per_user
+            )));
+        }
 
-        tracing::debug!(agent_id = %agent_id, "Processed heartbeat");
+        let now = Utc::now();
+        let webhook_id = WebhookId::generate();
+        let secret = request.secret.unwrap_or_else(webhook::generate_secret);
+        let hook = Webhook {
+            webhook_id,
+            user_id: *user_id,
+            url: request.url,
+            sealed_secret: cipher.seal_webhook_secret(&webhook_id, &secret)?,
+            events: request.events,
+            description: request.description,
+            created_at: now,
+            updated_at: now,
+        };
 
-        Ok(())
-    }
+        self.store.put_webhook(&hook)?;
 
-    async fn resolve_agent_endpoint(&self, agent_id: &AgentId) -> Result<Option<String>> {
-        let agent = self
-            .store
-            .get_agent(agent_id)?
-            .ok_or(ControlError::AgentNotFound(*agent_id))?;
+        tracing::info!(
+            webhook_id = %hook.webhook_id,
+            user_id = %user_id,
+            events = hook.events.len(),
+            "Created webhook"
+        );
 
-        // Only return endpoint for active agents
-        if lifecycle::is_active(agent.status) {
-            // Query the scheduler for the pod's actual endpoint (IP:port)
-            if let Some(scheduler) = &self.scheduler {
-                scheduler.get_pod_endpoint(agent_id).await
-            } else {
-                // No scheduler configured, return mock endpoint for local dev
-                Ok(Some("localhost:8080".to_string()))
-            }
-        } else {
-            Ok(None)
-        }
+        Ok(CreatedWebhook {
+            webhook: hook,
+            secret,
+        })
+    }
+
+    async fn list_webhooks(&self, user_id: &UserId) -> Result<Vec<Webhook>> {
+        Ok(self.store.list_webhooks_by_user(user_id)?)
+    }
+
+    async fn get_webhook(&self, user_id: &UserId, webhook_id: &WebhookId) -> Result<Webhook> {
+        self.get_webhook_verified(user_id, webhook_id)
+    }
+
+    async fn delete_webhook(&self, user_id: &UserId, webhook_id: &WebhookId) -> Result<()> {
+        self.get_webhook_verified(user_id, webhook_id)?;
+        self.store.delete_webhook(webhook_id)?;
+
+        tracing::info!(webhook_id = %webhook_id, "Deleted webhook");
+
+        Ok(())
+    }
+
+    async fn list_webhook_deliveries(
+        &self,
+        user_id: &UserId,
+        webhook_id: &WebhookId,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>> {
+        self.get_webhook_verified(user_id, webhook_id)?;
+        Ok(self.store.list_webhook_deliveries(webhook_id, limit)?)
+    }
+
+    async fn ping_webhook(
+        &self,
+        user_id: &UserId,
+        webhook_id: &WebhookId,
+    ) -> Result<WebhookDelivery> {
+        let hook = self.get_webhook_verified(user_id, webhook_id)?;
+
+        let data = serde_json::json!({ "webhook_id": webhook_id.to_string() });
+        let delivery = Self::new_delivery(&hook, WebhookEvent::Ping, &data);
+        self.store.put_webhook_delivery(&delivery)?;
+
+        self.attempt_delivery(&hook, delivery, Utc::now()).await
+    }
+
+    // =========================================================================
+    // Session Operations
+    // =========================================================================
+
+    async fn create_session(&self, caller: &Caller, agent_id: &AgentId) -> Result<Session> {
+        let (session, state_change) =
+            session::create_session(&*self.store, &self.config, caller, agent_id)?;
+
+        if state_change.is_some() {
+            self.notify_state_change(agent_id);
+        }
+
+        tracing::info!(
+            session_id = %session.session_id,
+            agent_id = %agent_id,
+            state_change = ?state_change,
+            "Created session"
+        );
+
+        Ok(session)
+    }
+
+    async fn get_session(&self, caller: &Caller, session_id: &SessionId) -> Result<Session> {
+        session::get_session(&*self.store, caller, session_id)
+    }
+
+    async fn close_session(&self, caller: &Caller, session_id: &SessionId) -> Result<()> {
+        let session = session::get_session(&*self.store, caller, session_id)?;
+
+        if session.status == aura_swarm_store::SessionStatus::Active {
+            if session::close_active_session(&*self.store, &session)? {
+                self.notify_state_change(&session.agent_id);
+            }
+            tracing::info!(session_id = %session_id, "Closed session");
+        }
+
+        Ok(())
+    }
+
+    async fn list_sessions(&self, caller: &Caller, agent_id: &AgentId) -> Result<Vec<Session>> {
+        session::list_sessions(&*self.store, caller, agent_id)
+    }
+
+    async fn touch_session(&self, caller: &Caller, session_id: &SessionId) -> Result<Session> {
+        session::touch_session(&*self.store, caller, session_id, Utc::now())
+    }
+
+    // =========================================================================
+    // Operational
+    // =========================================================================
+
+    async fn process_heartbeat(&self, agent_id: &AgentId) -> Result<()> {
+        let mut agent = self
+            .store
+            .get_agent(agent_id)?
+            .ok_or(ControlError::AgentNotFound(*agent_id))?;
+
+        agent.last_heartbeat_at = Some(Utc::now());
+        agent.updated_at = Utc::now();
+        self.store.put_agent(&agent)?;
+
+        tracing::debug!(agent_id = %agent_id, "Processed heartbeat");
+
+        Ok(())
+    }
+
+    async fn resolve_agent_endpoint(&self, agent_id: &AgentId) -> Result<Option<String>> {
+        let agent = self
+            .store
+            .get_agent(agent_id)?
+            .ok_or(ControlError::AgentNotFound(*agent_id))?;
+
+        // Only return endpoint for active agents
+        if lifecycle::is_active(agent.status) {
+            // Query the scheduler for the pod's actual endpoint (IP:port)
+            if let Some(scheduler) = &self.scheduler {
+                scheduler.get_pod_endpoint(agent_id).await
+            } else {
+                // No scheduler configured, return mock endpoint for local dev
+                Ok(Some("localhost:8080".to_string()))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    // =========================================================================
+    // Audit Operations
+    // =========================================================================
+
+    async fn record_audit_event(&self, event: AuditEvent) {
+        self.audit.log(event);
+    }
+
+    async fn list_audit_events(&self, query: AuditQuery) -> Result<Vec<AuditEvent>> {
+        Ok(self.store.list_audit_events(&query)?)
+    }
+
+    // =========================================================================
+    // Usage Operations
+    // =========================================================================
+
+    async fn get_usage(&self, query: UsageQuery) -> Result<Vec<UsageSummary>> {
+        if query.from >= query.to {
+            return Err(ControlError::InvalidRequest(
+                "from must be before to".to_string(),
+            ));
+        }
+
+        // Include the bucket for the hour `from` falls in
+        let records: Vec<_> = self
+            .store
+            .list_usage(metering::hour_start(query.from), query.to)?
+            .into_iter()
+            .filter(|record| metering::matches(&query, record))
+            .collect();
+
+        Ok(metering::summarize(&records, &query.group_by))
+    }
+
+    // =========================================================================
+    // Rollout Operations
+    // =========================================================================
+
+    async fn create_rollout(
+        &self,
+        created_by: &IdentityId,
+        request: CreateRolloutRequest,
+    ) -> Result<Rollout> {
+        if let Some(active) = self
+            .store
+            .list_rollouts()?
+            .into_iter()
+            .find(|rollout| rollout.status == RolloutStatus::InProgress)
+        {
+            return Err(ControlError::RolloutInProgress(active.rollout_id));
+        }
+
+        let agents = self.store.list_all_agents()?;
+        let rollout = rollout::plan(request, &agents, *created_by, Utc::now())?;
+        self.store.put_rollout(&rollout)?;
+
+        tracing::info!(
+            rollout_id = %rollout.rollout_id,
+            target_version = %rollout.target_version,
+            targets = rollout.targets.len(),
+            "Created rollout"
+        );
+
+        Ok(rollout)
+    }
+
+    async fn list_rollouts(&self) -> Result<Vec<Rollout>> {
+        Ok(self.store.list_rollouts()?)
+    }
+
+    async fn get_rollout(&self, rollout_id: &RolloutId) -> Result<Rollout> {
+        self.store
+            .get_rollout(rollout_id)?
+            .ok_or(ControlError::RolloutNotFound(*rollout_id))
+    }
+
+    async fn cancel_rollout(&self, rollout_id: &RolloutId) -> Result<Rollout> {
+        let mut rollout = self.get_rollout(rollout_id).await?;
+        if rollout.status != RolloutStatus::InProgress {
+            return Err(ControlError::InvalidRequest(format!(
+                "rollout {rollout_id} is not in progress"
+            )));
+        }
+
+        rollout.status = RolloutStatus::Cancelled;
+        rollout.next_batch_at = None;
+        rollout.updated_at = Utc::now();
+        self.store.put_rollout(&rollout)?;
+
+        tracing::info!(rollout_id = %rollout_id, "Cancelled rollout");
+
+        Ok(rollout)
+    }
+
+    async fn rollback_rollout(&self, rollout_id: &RolloutId) -> Result<Rollout> {
+        let mut rollout = self.get_rollout(rollout_id).await?;
+        if rollout.status == RolloutStatus::RolledBack {
+            return Err(ControlError::InvalidRequest(format!(
+                "rollout {rollout_id} was already rolled back"
+            )));
+        }
+
+        self.revert_rollout(&mut rollout).await?;
+        rollout.message = Some("rolled back on request".to_string());
+        self.store.put_rollout(&rollout)?;
+
+        Ok(rollout)
     }
 
     async fn update_agent_status_internal(
@@ -611,6 +2222,7 @@ impl<S: Store + 'static, SC: SchedulerClient + 'static> ControlPlane for Control
         } else {
             self.store.update_agent_status(agent_id, status)?;
         }
+        self.notify_state_change(agent_id);
 
         tracing::info!(
             agent_id = %agent_id,
@@ -623,233 +2235,3975 @@ impl<S: Store + 'static, SC: SchedulerClient + 'static> ControlPlane for Control
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::scheduler_client::NoopSchedulerClient;
-    use aura_swarm_store::RocksStore;
-    use tempfile::TempDir;
+// =============================================================================
+// Schedule Runner
+// =============================================================================
 
-    fn setup() -> (
-        ControlPlaneService<RocksStore, NoopSchedulerClient>,
-        TempDir,
-        UserId,
-    ) {
-        let dir = TempDir::new().unwrap();
-        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
-        let config = ControlConfig {
-            max_agents_per_user: 3,
-            ..Default::default()
+impl<S: Store + 'static, SC: SchedulerClient + 'static> ControlPlaneService<S, SC> {
+    /// Run the schedule loop, evaluating agent schedules at the configured interval.
+    ///
+    /// This method runs indefinitely and should be spawned as a background task.
+    /// Because each schedule remembers how far it has been evaluated, runs that
+    /// fell due while the loop was not running are handled on the next tick
+    /// according to the schedule's [`MissedRunPolicy`].
+    pub async fn run_schedule_loop(&self) {
+        let period = std::time::Duration::from_secs(self.config.schedule_interval_seconds);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        tracing::info!(
+            interval_seconds = self.config.schedule_interval_seconds,
+            "Starting agent schedule loop"
+        );
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.process_schedules(Utc::now()).await {
+                tracing::error!(error = %e, "Failed to process agent schedules");
+            }
+        }
+    }
+
+    /// Evaluate all schedules up to `now` and perform any due actions.
+    ///
+    /// Only the latest due action of each schedule is considered, so a long
+    /// outage results in at most one action per agent.
+    ///
+    /// Returns the number of lifecycle operations that were executed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if schedules cannot be read from the store.
+    pub async fn process_schedules(&self, now: DateTime<Utc>) -> Result<usize> {
+        let mut executed = 0;
+
+        for mut schedule in self.store.list_schedules()? {
+            if !schedule.enabled || schedule.last_checked_at >= now {
+                continue;
+            }
+
+            let due = match schedule::latest_due(&schedule, schedule.last_checked_at, now) {
+                Ok(due) => due,
+                Err(e) => {
+                    tracing::warn!(
+                        agent_id = %schedule.agent_id,
+                        error = %e,
+                        "Skipping invalid agent schedule"
+                    );
+                    None
+                }
+            };
+
+            if let Some((action, scheduled_for)) = due {
+                let run = self
+                    .run_scheduled_action(&schedule, action, scheduled_for, now)
+                    .await;
+                if run.outcome == ScheduleRunOutcome::Executed {
+                    executed += 1;
+                }
+                schedule.last_run = Some(run);
+            }
+
+            schedule.last_checked_at = now;
+            self.store.put_schedule(&schedule)?;
+        }
+
+        Ok(executed)
+    }
+
+    /// Perform a single due schedule action and record the outcome.
+    async fn run_scheduled_action(
+        &self,
+        schedule: &AgentSchedule,
+        action: ScheduleAction,
+        scheduled_for: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> ScheduleRun {
+        let agent_id = &schedule.agent_id;
+        let skipped = |message: String| ScheduleRun {
+            action,
+            scheduled_for,
+            executed_at: now,
+            outcome: ScheduleRunOutcome::Skipped,
+            message: Some(message),
         };
-        let service = ControlPlaneService::new(store, config);
-        let user_id = UserId::from_bytes([1u8; 32]);
-        (service, dir, user_id)
+
+        let lateness = (now - scheduled_for).num_seconds().unsigned_abs();
+        if schedule.missed_runs == MissedRunPolicy::Skip
+            && lateness > self.config.schedule_grace_seconds
+        {
+            tracing::info!(
+                agent_id = %agent_id,
+                action = ?action,
+                scheduled_for = %scheduled_for,
+                "Skipping missed scheduled run"
+            );
+            return skipped(format!("missed by {lateness}s"));
+        }
+
+        let agent = match self.store.get_agent(agent_id) {
+            Ok(Some(agent)) => agent,
+            Ok(None) => return skipped("agent not found".to_string()),
+            Err(e) => {
+                return ScheduleRun {
+                    action,
+                    scheduled_for,
+                    executed_at: now,
+                    outcome: ScheduleRunOutcome::Failed,
+                    message: Some(e.to_string()),
+                }
+            }
+        };
+
+        // Only act when the agent is in a state the action applies to
+        let result = match action {
+            ScheduleAction::Wake if lifecycle::can_wake(agent.status) => {
+                self.wake_agent(&namespace::owner_of(&agent), agent_id)
+                    .await
+            }
+            ScheduleAction::Hibernate
+                if matches!(agent.status, AgentState::Running | AgentState::Idle) =>
+            {
+                self.hibernate_agent(&namespace::owner_of(&agent), agent_id)
+                    .await
+            }
+            _ => return skipped(format!("agent is {:?}", agent.status)),
+        };
+
+        let event_type = match action {
+            ScheduleAction::Wake => "schedule.wake",
+            ScheduleAction::Hibernate => "schedule.hibernate",
+        };
+
+        match result {
+            Ok(_) => {
+                tracing::info!(
+                    agent_id = %agent_id,
+                    action = ?action,
+                    scheduled_for = %scheduled_for,
+                    "Executed scheduled action"
+                );
+                self.audit_agent_action(event_type, &agent, AuditOutcome::Success, None);
+                ScheduleRun {
+                    action,
+                    scheduled_for,
+                    executed_at: now,
+                    outcome: ScheduleRunOutcome::Executed,
+                    message: None,
+                }
+            }
+            Err(e) => {
+                tracing::error!(
+                    agent_id = %agent_id,
+                    action = ?action,
+                    error = %e,
+                    "Scheduled action failed"
+                );
+                let error = e.to_string();
+                self.audit_agent_action(event_type, &agent, AuditOutcome::Failure, Some(&error));
+                ScheduleRun {
+                    action,
+                    scheduled_for,
+                    executed_at: now,
+                    outcome: ScheduleRunOutcome::Failed,
+                    message: Some(e.to_string()),
+                }
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Restart Supervisor
+// =============================================================================
+
+impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
+    /// Run the restart supervisor, enforcing provisioning deadlines and
+    /// restart policies at the configured interval.
+    ///
+    /// This method runs indefinitely and should be spawned as a background task.
+    pub async fn run_restart_supervisor(&self) {
+        let period = std::time::Duration::from_secs(self.config.restart_check_interval_seconds);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        tracing::info!(
+            interval_seconds = self.config.restart_check_interval_seconds,
+            "Starting restart supervisor"
+        );
+
+        loop {
+            interval.tick().await;
+            match self.supervise_agents(Utc::now()).await {
+                Ok(0) => {}
+                Ok(actions) => tracing::debug!(actions, "Supervised agents"),
+                Err(e) => tracing::error!(error = %e, "Failed to supervise agents"),
+            }
+        }
+    }
+
+    /// Enforce provisioning deadlines and restart policies as of `now`.
+    ///
+    /// - `Provisioning` agents past `provisioning_timeout_seconds` go to `Error`.
+    /// - `Error` agents with an `on_failure` policy get a restart scheduled with
+    ///   exponential backoff, and are re-provisioned once it is due.
+    /// - Agents that have stayed up for `restart_reset_seconds` after an
+    ///   automatic restart get their attempt count reset.
+    ///
+    /// Returns the number of agents acted on.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if agents cannot be listed or updated.
+    pub async fn supervise_agents(&self, now: DateTime<Utc>) -> Result<usize> {
+        let timeout = secs(self.config.provisioning_timeout_seconds);
+        let reset_after = secs(self.config.restart_reset_seconds);
+        let mut actions = 0;
+
+        // Clones waiting for their state have not started provisioning a pod
+        let copying = self.pending_clones()?;
+
+        for mut agent in self.store.list_all_agents()? {
+            // Records from before provisioning start times were kept fall
+            // back to their last modification.
+            let started = agent.provisioning_started_at.unwrap_or(agent.updated_at);
+            if agent.status == AgentState::Provisioning
+                && !copying.contains(&agent.agent_id)
+                && now - started >= timeout
+            {
+                self.fail_provisioning(&mut agent).await?;
+                actions += 1;
+            }
+
+            let changed = match agent.status {
+                AgentState::Error if !agent.restart_policy.is_never() => {
+                    self.apply_restart_policy(&mut agent, now).await?
+                }
+                AgentState::Running | AgentState::Idle
+                    if agent.restarts.attempts > 0
+                        && agent
+                            .restarts
+                            .last_restart_at
+                            .is_some_and(|at| now - at >= reset_after) =>
+                {
+                    agent.restarts = RestartState::default();
+                    self.store.put_agent(&agent)?;
+                    tracing::info!(agent_id = %agent.agent_id, "Reset restart attempts");
+                    true
+                }
+                _ => false,
+            };
+            if changed {
+                actions += 1;
+            }
+        }
+
+        Ok(actions)
+    }
+
+    /// Move an agent that never became ready to `Error` and clean up its pod.
+    async fn fail_provisioning(&self, agent: &mut Agent) -> Result<()> {
+        let message = format!(
+            "agent did not become ready within {}s",
+            self.config.provisioning_timeout_seconds
+        );
+
+        tracing::warn!(agent_id = %agent.agent_id, "Provisioning deadline exceeded");
+
+        self.audit_agent_action(
+            "agent.provisioning_timeout",
+            agent,
+            AuditOutcome::Failure,
+            Some(&message),
+        );
+        agent.error_message = Some(message);
+        self.transition_state(agent, AgentState::Error)?;
+
+        if let Err(e) = self.terminate_agent_pod(&agent.agent_id).await {
+            tracing::warn!(
+                agent_id = %agent.agent_id,
+                error = %e,
+                "Failed to terminate pod after provisioning timeout"
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Schedule or perform an automatic restart for an agent in `Error`.
+    ///
+    /// Returns true if the agent was changed.
+    async fn apply_restart_policy(&self, agent: &mut Agent, now: DateTime<Utc>) -> Result<bool> {
+        let max_attempts = agent.restart_policy.max_attempts();
+
+        if agent.restarts.attempts >= max_attempts {
+            if agent.restarts.next_restart_at.take().is_some() {
+                self.store.put_agent(agent)?;
+                return Ok(true);
+            }
+            return Ok(false);
+        }
+
+        let Some(due) = agent.restarts.next_restart_at else {
+            let attempt = agent.restarts.attempts + 1;
+            let delay = lifecycle::restart_delay(&self.config, attempt);
+            let due = now + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX);
+            agent.restarts.next_restart_at = Some(due);
+            self.store.put_agent(agent)?;
+
+            tracing::info!(
+                agent_id = %agent.agent_id,
+                attempt,
+                max_attempts,
+                next_restart_at = %due,
+                "Scheduled automatic restart"
+            );
+            return Ok(true);
+        };
+
+        if due > now {
+            return Ok(false);
+        }
+
+        agent.restarts.attempts += 1;
+        agent.restarts.last_restart_at = Some(now);
+        agent.error_message = None;
+        self.transition_state(agent, AgentState::Provisioning)?;
+
+        tracing::info!(
+            agent_id = %agent.agent_id,
+            attempt = agent.restarts.attempts,
+            max_attempts,
+            "Automatically restarting agent"
+        );
+        self.audit_agent_action("agent.auto_restart", agent, AuditOutcome::Success, None);
+
+        // Clear out whatever is left of the failed pod before scheduling a new one
+        self.terminate_agent_pod(&agent.agent_id).await.ok();
+
+        if let Err(e) = self.schedule_agent_pod(agent).await {
+            tracing::error!(
+                agent_id = %agent.agent_id,
+                error = %e,
+                "Failed to schedule agent pod on automatic restart"
+            );
+            agent.error_message = Some(e.to_string());
+            self.transition_state(agent, AgentState::Error)?;
+        }
+
+        Ok(true)
+    }
+}
+
+/// Convert a configured number of seconds to a `chrono::Duration`, saturating
+/// absurdly large values.
+fn secs(seconds: u64) -> chrono::Duration {
+    i64::try_from(seconds)
+        .ok()
+        .and_then(chrono::Duration::try_seconds)
+        .unwrap_or(chrono::Duration::MAX)
+}
+
+// =============================================================================
+// Drift Reconciler
+// =============================================================================
+
+impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
+    /// Run the drift reconciler, comparing the store against the scheduler's
+    /// pods at the configured interval.
+    ///
+    /// This method runs indefinitely and should be spawned as a background task.
+    /// It does nothing useful without a scheduler, so callers should only spawn
+    /// it when [`has_scheduler`](Self::has_scheduler) is true.
+    pub async fn run_drift_reconciler(&self) {
+        let period = std::time::Duration::from_secs(self.config.drift_interval_seconds);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        tracing::info!(
+            interval_seconds = self.config.drift_interval_seconds,
+            "Starting drift reconciler"
+        );
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.reconcile_drift(Utc::now()).await {
+                tracing::error!(error = %e, "Failed to reconcile drift");
+            }
+        }
+    }
+
+    /// Compare every agent record against the scheduler's pods and correct
+    /// any drift.
+    ///
+    /// Every correction is logged and returned in the report, including ones
+    /// that failed to apply. Without a scheduler this returns an empty report.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the agents or pods cannot be listed.
+    pub async fn reconcile_drift(&self, now: DateTime<Utc>) -> Result<DriftReport> {
+        let Some(scheduler) = &self.scheduler else {
+            tracing::debug!("No scheduler configured, skipping drift reconciliation");
+            return Ok(DriftReport {
+                started_at: now,
+                finished_at: now,
+                agents_checked: 0,
+                pods_checked: 0,
+                corrections: Vec::new(),
+            });
+        };
+
+        let started_at = Utc::now();
+        let pods = scheduler.list_pods().await?;
+        let agents = self.store.list_all_agents()?;
+
+        let grace = secs(self.config.drift_grace_seconds);
+        let mut plan = reconcile::plan_corrections(&agents, &pods, now, grace);
+
+        // Agents being deleted or copied are left to the workers handling
+        // them; clones have no pod until their state has been copied.
+        let mut busy = self.pending_clones()?;
+        busy.extend(
+            self.store
+                .list_state_copies()?
+                .into_iter()
+                .map(|copy| copy.source_id),
+        );
+        busy.extend(
+            self.store
+                .list_agent_deletions()?
+                .into_iter()
+                .filter(deletion::is_pending)
+                .map(|deletion| deletion.agent_id),
+        );
+        plan.retain(|correction| !busy.contains(&correction.agent_id));
+
+        let mut corrections = Vec::with_capacity(plan.len());
+        for mut correction in plan {
+            if !self.drift_still_applies(&correction)? {
+                continue;
+            }
+
+            self.apply_correction(scheduler, &mut correction).await;
+
+            tracing::warn!(
+                agent_id = %correction.agent_id,
+                action = ?correction.action,
+                agent_state = ?correction.agent_state,
+                pod_name = ?correction.pod_name,
+                new_state = ?correction.new_state,
+                error = ?correction.error,
+                "Corrected drift between store and scheduler"
+            );
+            corrections.push(correction);
+        }
+
+        let report = DriftReport {
+            started_at,
+            finished_at: Utc::now(),
+            agents_checked: agents.len(),
+            pods_checked: pods.len(),
+            corrections,
+        };
+
+        if !report.is_clean() {
+            tracing::info!(
+                agents_checked = report.agents_checked,
+                pods_checked = report.pods_checked,
+                corrections = report.corrections.len(),
+                failures = report.failures(),
+                "Drift reconciliation finished"
+            );
+        }
+
+        Ok(report)
+    }
+
+    /// Re-read the agent to make sure it hasn't changed since the plan was made.
+    fn drift_still_applies(&self, correction: &DriftCorrection) -> Result<bool> {
+        let current = self
+            .store
+            .get_agent(&correction.agent_id)?
+            .map(|a| a.status);
+        Ok(current == correction.agent_state)
+    }
+
+    /// Apply one planned correction, recording the outcome on it.
+    async fn apply_correction(&self, scheduler: &SC, correction: &mut DriftCorrection) {
+        let agent_id = correction.agent_id;
+
+        let result = match correction.action {
+            DriftAction::TerminateOrphanPod | DriftAction::TerminateUnwantedPod => {
+                scheduler.terminate_agent(&agent_id).await
+            }
+            DriftAction::MarkStopped => self.mark_stopped(correction),
+            DriftAction::Reschedule => self.reschedule_missing_pod(scheduler, correction).await,
+        };
+
+        if let Err(e) = result {
+            correction.error = Some(e.to_string());
+            let busy = matches!(
+                e,
+                ControlError::AgentDeleting(_) | ControlError::StateCopyPending(_)
+            );
+            if correction.action == DriftAction::Reschedule && !busy {
+                if let Ok(Some(mut agent)) = self.store.get_agent(&agent_id) {
+                    agent.error_message = Some(e.to_string());
+                    if self.apply_state(&mut agent, AgentState::Error).is_ok() {
+                        correction.new_state = Some(AgentState::Error);
+                    }
+                }
+            }
+        }
+
+        let mut event = audit::system_event(
+            "drift.correction",
+            correction.action.as_str(),
+            audit::agent_resource(&agent_id),
+            if correction.error.is_some() {
+                AuditOutcome::Failure
+            } else {
+                AuditOutcome::Success
+            },
+        );
+        if let Some(pod_name) = &correction.pod_name {
+            event
+                .details
+                .insert("pod_name".to_string(), pod_name.clone());
+        }
+        if let Some(error) = &correction.error {
+            event.details.insert("error".to_string(), error.clone());
+        }
+        self.audit.log(event);
+    }
+
+    /// Finish stopping an agent whose pod is already gone.
+    fn mark_stopped(&self, correction: &mut DriftCorrection) -> Result<()> {
+        let mut agent = self
+            .store
+            .get_agent(&correction.agent_id)?
+            .ok_or(ControlError::AgentNotFound(correction.agent_id))?;
+
+        self.transition_state(&mut agent, AgentState::Stopped)?;
+        correction.new_state = Some(AgentState::Stopped);
+        Ok(())
+    }
+
+    /// Schedule a new pod for an agent whose pod has disappeared.
+    ///
+    /// Agents that were `Running` or `Idle` go back to `Provisioning` until the
+    /// new pod reports in.
+    async fn reschedule_missing_pod(
+        &self,
+        scheduler: &SC,
+        correction: &mut DriftCorrection,
+    ) -> Result<()> {
+        let mut agent = self
+            .store
+            .get_agent(&correction.agent_id)?
+            .ok_or(ControlError::AgentNotFound(correction.agent_id))?;
+        self.check_not_deleting(&agent.agent_id)?;
+        self.check_not_copying(&agent.agent_id)?;
+
+        self.place_agent_pod(scheduler, &agent).await?;
+
+        if agent.status != AgentState::Provisioning {
+            self.apply_state(&mut agent, AgentState::Provisioning)?;
+            correction.new_state = Some(AgentState::Provisioning);
+        }
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Webhook Dispatcher
+// =============================================================================
+
+impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
+    /// Run the webhook dispatcher, delivering due outbox entries at the
+    /// configured interval and pruning old delivery logs.
+    ///
+    /// This method runs indefinitely and should be spawned as a background task.
+    /// Because the outbox lives in the store, deliveries that were pending
+    /// when the process stopped are resumed after a restart.
+    pub async fn run_webhook_dispatcher(&self) {
+        let period = std::time::Duration::from_secs(self.config.webhook_dispatch_interval_seconds);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut last_pruned: Option<std::time::Instant> = None;
+
+        tracing::info!(
+            interval_seconds = self.config.webhook_dispatch_interval_seconds,
+            "Starting webhook dispatcher"
+        );
+
+        loop {
+            interval.tick().await;
+            let now = Utc::now();
+
+            if let Err(e) = self.dispatch_webhooks(now).await {
+                tracing::error!(error = %e, "Failed to dispatch webhook deliveries");
+            }
+
+            if last_pruned.is_none_or(|at| at.elapsed() >= std::time::Duration::from_hours(1)) {
+                last_pruned = Some(std::time::Instant::now());
+                let retention = secs(self.config.webhook_retention_hours.saturating_mul(3600));
+                let cutoff = now
+                    .checked_sub_signed(retention)
+                    .unwrap_or(DateTime::<Utc>::MIN_UTC);
+                match self.store.prune_webhook_deliveries(cutoff) {
+                    Ok(0) => {}
+                    Ok(pruned) => tracing::info!(pruned, "Pruned webhook delivery log"),
+                    Err(e) => tracing::error!(error = %e, "Failed to prune webhook deliveries"),
+                }
+            }
+        }
+    }
+
+    /// Attempt every pending delivery whose next attempt is due at `now`.
+    ///
+    /// Returns the number of attempts made.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the outbox cannot be read or updated.
+    pub async fn dispatch_webhooks(&self, now: DateTime<Utc>) -> Result<usize> {
+        let mut attempted = 0;
+
+        for delivery in self.store.list_pending_webhook_deliveries()? {
+            if delivery.next_attempt_at.is_some_and(|at| at > now) {
+                continue;
+            }
+
+            let Some(hook) = self.store.get_webhook(&delivery.webhook_id)? else {
+                // The webhook was deleted after this delivery was read
+                continue;
+            };
+
+            self.attempt_delivery(&hook, delivery, now).await?;
+            attempted += 1;
+        }
+
+        Ok(attempted)
+    }
+
+    /// Make one delivery attempt and record the outcome.
+    ///
+    /// Failed deliveries are rescheduled with exponential backoff until
+    /// `webhook_max_attempts` is reached.
+    async fn attempt_delivery(
+        &self,
+        hook: &Webhook,
+        mut delivery: WebhookDelivery,
+        now: DateTime<Utc>,
+    ) -> Result<WebhookDelivery> {
+        let secret = self
+            .secrets
+            .as_ref()
+            .ok_or_else(|| {
+                ControlError::Internal(
+                    "webhook secret cannot be unsealed without a key".to_string(),
+                )
+            })
+            .and_then(|cipher| cipher.open_webhook_secret(&hook.webhook_id, &hook.sealed_secret));
+        let attempt = match secret {
+            Ok(secret) => self.webhooks.send(hook, &secret, &delivery, now).await,
+            Err(e) => DeliveryAttempt {
+                attempted_at: now,
+                status_code: None,
+                error: Some(e.to_string()),
+                duration_ms: 0,
+            },
+        };
+        let succeeded = attempt.error.is_none();
+        delivery.attempts.push(attempt);
+
+        let attempts = u32::try_from(delivery.attempts.len()).unwrap_or(u32::MAX);
+        if succeeded {
+            delivery.status = DeliveryStatus::Succeeded;
+            delivery.next_attempt_at = None;
+        } else if attempts >= self.config.webhook_max_attempts {
+            delivery.status = DeliveryStatus::Failed;
+            delivery.next_attempt_at = None;
+        } else {
+            let delay = webhook::retry_delay(&self.config, attempts);
+            delivery.next_attempt_at =
+                Some(now + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX));
+        }
+
+        self.store.put_webhook_delivery(&delivery)?;
+
+        tracing::info!(
+            webhook_id = %hook.webhook_id,
+            delivery_id = %delivery.delivery_id,
+            event = delivery.event.as_str(),
+            attempts,
+            status = ?delivery.status,
+            "Attempted webhook delivery"
+        );
+
+        Ok(delivery)
+    }
+}
+
+// =============================================================================
+// Audit Retention
+// =============================================================================
+
+impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
+    /// Run the audit retention loop, pruning events older than the configured
+    /// retention period at the configured interval.
+    ///
+    /// This method runs indefinitely and should be spawned as a background task.
+    pub async fn run_audit_retention(&self) {
+        let period = std::time::Duration::from_secs(self.config.audit_prune_interval_seconds);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        tracing::info!(
+            interval_seconds = self.config.audit_prune_interval_seconds,
+            retention_days = self.config.audit_retention_days,
+            "Starting audit retention"
+        );
+
+        loop {
+            interval.tick().await;
+            match self.prune_audit_log(Utc::now()) {
+                Ok(0) => {}
+                Ok(pruned) => tracing::info!(pruned, "Pruned audit log"),
+                Err(e) => tracing::error!(error = %e, "Failed to prune audit log"),
+            }
+        }
+    }
+
+    /// Delete audit events older than the retention period as of `now`.
+    ///
+    /// Returns the number of events deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the audit log cannot be pruned.
+    pub fn prune_audit_log(&self, now: DateTime<Utc>) -> Result<usize> {
+        let retention = secs(self.config.audit_retention_days.saturating_mul(86_400));
+        let cutoff = now
+            .checked_sub_signed(retention)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+        Ok(self.store.prune_audit_events(cutoff)?)
+    }
+}
+
+// =============================================================================
+// Usage Meter
+// =============================================================================
+
+impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
+    /// Run the usage meter, charging agent runtime to the hourly usage
+    /// buckets at the configured interval.
+    ///
+    /// This method runs indefinitely and should be spawned as a background task.
+    /// Time while the meter is not running is not charged.
+    pub async fn run_usage_meter(&self) {
+        let period = std::time::Duration::from_secs(self.config.metering_interval_seconds);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        tracing::info!(
+            interval_seconds = self.config.metering_interval_seconds,
+            "Starting usage meter"
+        );
+
+        let mut last_metered_at = Utc::now();
+        loop {
+            interval.tick().await;
+            let now = Utc::now();
+            match self.meter_usage(last_metered_at, now) {
+                Ok(_) => last_metered_at = now,
+                // Keep the start so the interval is charged on the next tick
+                Err(e) => tracing::error!(error = %e, "Failed to meter agent usage"),
+            }
+        }
+    }
+
+    /// Charge the interval `[from, to)` to every metered agent in its current
+    /// state.
+    ///
+    /// Returns the number of usage records written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if agents cannot be listed or usage cannot be written.
+    pub fn meter_usage(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<usize> {
+        let agents = self.store.list_all_agents()?;
+        let records = metering::meter(&agents, from, to);
+        self.store.add_usage(&records)?;
+
+        tracing::debug!(
+            agents = agents.len(),
+            records = records.len(),
+            "Metered agent usage"
+        );
+
+        Ok(records.len())
+    }
+}
+
+// =============================================================================
+// Rollout Controller
+// =============================================================================
+
+impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
+    /// Run the rollout controller, advancing in-progress rollouts at the
+    /// configured interval.
+    ///
+    /// This method runs indefinitely and should be spawned as a background task.
+    pub async fn run_rollout_controller(&self) {
+        let period = std::time::Duration::from_secs(self.config.rollout_interval_seconds);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        tracing::info!(
+            interval_seconds = self.config.rollout_interval_seconds,
+            "Starting rollout controller"
+        );
+
+        loop {
+            interval.tick().await;
+            match self.advance_rollouts(Utc::now()).await {
+                Ok(0) => {}
+                Ok(rollouts) => tracing::debug!(rollouts, "Advanced rollouts"),
+                Err(e) => tracing::error!(error = %e, "Failed to advance rollouts"),
+            }
+        }
+    }
+
+    /// Advance every in-progress rollout by one step as of `now`.
+    ///
+    /// See the [`rollout`] module for how a rollout progresses.
+    ///
+    /// Returns the number of rollouts that were in progress.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if rollouts or agents cannot be read or written.
+    pub async fn advance_rollouts(&self, now: DateTime<Utc>) -> Result<usize> {
+        let mut advanced = 0;
+
+        for mut rollout in self.store.list_rollouts()? {
+            if rollout.status != RolloutStatus::InProgress {
+                continue;
+            }
+            self.advance_rollout(&mut rollout, now).await?;
+            rollout.updated_at = now;
+            self.store.put_rollout(&rollout)?;
+            advanced += 1;
+        }
+
+        Ok(advanced)
+    }
+
+    /// Advance one in-progress rollout.
+    async fn advance_rollout(&self, rollout: &mut Rollout, now: DateTime<Utc>) -> Result<()> {
+        self.check_rollout_targets(rollout, now).await?;
+
+        if rollout::exceeds_failure_budget(rollout) {
+            let failed = rollout.count(RolloutTargetState::Failed);
+            tracing::warn!(rollout_id = %rollout.rollout_id, failed, "Halting rollout");
+
+            rollout.status = RolloutStatus::Halted;
+            rollout.next_batch_at = None;
+            rollout.message = Some(format!("halted after {failed} agents failed"));
+            if rollout.strategy.rollback_on_failure {
+                self.revert_rollout(rollout).await?;
+                rollout.message = Some(format!("rolled back after {failed} agents failed"));
+            }
+            return Ok(());
+        }
+
+        if rollout.count(RolloutTargetState::Updating) > 0 {
+            return Ok(());
+        }
+
+        let batch = rollout::next_batch(rollout);
+        if batch.is_empty() {
+            rollout.status = RolloutStatus::Completed;
+            rollout.next_batch_at = None;
+            tracing::info!(rollout_id = %rollout.rollout_id, "Completed rollout");
+            return Ok(());
+        }
+
+        if rollout::has_started(rollout) && rollout.strategy.pause_seconds > 0 {
+            let pause = secs(rollout.strategy.pause_seconds);
+            if *rollout.next_batch_at.get_or_insert(now + pause) > now {
+                return Ok(());
+            }
+        }
+        rollout.next_batch_at = None;
+
+        tracing::info!(
+            rollout_id = %rollout.rollout_id,
+            agents = batch.len(),
+            "Starting rollout batch"
+        );
+        for index in batch {
+            self.update_rollout_target(rollout, index, now).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Check whether the restarted agents of a rollout became ready.
+    async fn check_rollout_targets(&self, rollout: &mut Rollout, now: DateTime<Utc>) -> Result<()> {
+        let timeout = secs(rollout.strategy.ready_timeout_seconds);
+
+        for target in &mut rollout.targets {
+            if target.state != RolloutTargetState::Updating {
+                continue;
+            }
+
+            let Some(agent) = self.store.get_agent(&target.agent_id)? else {
+                target.state = RolloutTargetState::Skipped;
+                target.error = Some("agent was deleted".to_string());
+                continue;
+            };
+            let error = match agent.status {
+                AgentState::Error => Some(
+                    agent
+                        .error_message
+                        .unwrap_or_else(|| "agent failed to start".to_string()),
+                ),
+                AgentState::Provisioning | AgentState::Running | AgentState::Idle => None,
+                _ => {
+                    target.state = RolloutTargetState::Skipped;
+                    target.error = Some("agent was stopped during the rollout".to_string());
+                    continue;
+                }
+            };
+
+            let pod = match &self.scheduler {
+                Some(scheduler) if error.is_none() => {
+                    scheduler.get_pod_status(&target.agent_id).await.ok()
+                }
+                _ => None,
+            };
+            let error = match pod {
+                Some(pod) if pod.ready => {
+                    target.state = RolloutTargetState::Succeeded;
+                    continue;
+                }
+                Some(pod) if pod.phase.eq_ignore_ascii_case("failed") => {
+                    Some(pod.message.unwrap_or_else(|| "pod failed".to_string()))
+                }
+                _ => error,
+            }
+            .or_else(|| {
+                target
+                    .started_at
+                    .is_some_and(|at| now - at >= timeout)
+                    .then(|| {
+                        format!(
+                            "agent did not become ready within {}s",
+                            rollout.strategy.ready_timeout_seconds
+                        )
+                    })
+            });
+
+            if let Some(error) = error {
+                tracing::warn!(
+                    rollout_id = %rollout.rollout_id,
+                    agent_id = %target.agent_id,
+                    error = %error,
+                    "Agent failed rollout"
+                );
+                target.state = RolloutTargetState::Failed;
+                target.error = Some(error);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move one rollout target to the target version, restarting its pod.
+    async fn update_rollout_target(
+        &self,
+        rollout: &mut Rollout,
+        index: usize,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let target = &mut rollout.targets[index];
+        let Some(mut agent) = self.store.get_agent(&target.agent_id)? else {
+            target.state = RolloutTargetState::Skipped;
+            target.error = Some("agent was deleted".to_string());
+            return Ok(());
+        };
+
+        target
+            .previous_version
+            .clone_from(&agent.spec.runtime_version);
+        agent
+            .spec
+            .runtime_version
+            .clone_from(&rollout.target_version);
+        agent.updated_at = Utc::now();
+        self.store.put_agent(&agent)?;
+
+        match self.restart_for_rollout(&mut agent).await {
+            Ok(false) => target.state = RolloutTargetState::Succeeded,
+            Ok(true) => {
+                target.restarted = true;
+                target.state = RolloutTargetState::Updating;
+                target.started_at = Some(now);
+                self.audit_agent_action(
+                    "agent.rollout_restart",
+                    &agent,
+                    AuditOutcome::Success,
+                    None,
+                );
+            }
+            Err(e) => {
+                tracing::error!(
+                    rollout_id = %rollout.rollout_id,
+                    agent_id = %agent.agent_id,
+                    error = %e,
+                    "Failed to restart agent for rollout"
+                );
+                target.restarted = true;
+                target.state = RolloutTargetState::Failed;
+                target.error = Some(e.to_string());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Restart an agent's pod so it picks up a changed spec.
+    ///
+    /// Returns false if the agent has no pod to restart.
+    async fn restart_for_rollout(&self, agent: &mut Agent) -> Result<bool> {
+        if self.scheduler.is_none() {
+            return Ok(false);
+        }
+
+        match agent.status {
+            AgentState::Running | AgentState::Idle => {
+                self.close_agent_sessions(&agent.agent_id)?;
+                self.transition_state(agent, AgentState::Stopping)?;
+                if let Err(e) = self.terminate_agent_pod(&agent.agent_id).await {
+                    tracing::warn!(
+                        agent_id = %agent.agent_id,
+                        error = %e,
+                        "Failed to terminate agent pod for rollout"
+                    );
+                }
+                self.transition_state(agent, AgentState::Stopped)?;
+                agent.restarts = RestartState::default();
+                self.transition_state(agent, AgentState::Provisioning)?;
+            }
+            AgentState::Provisioning => {
+                self.terminate_agent_pod(&agent.agent_id).await.ok();
+            }
+            _ => return Ok(false),
+        }
+
+        if let Err(e) = self.schedule_agent_pod(agent).await {
+            agent.error_message = Some(e.to_string());
+            self.transition_state(agent, AgentState::Error)?;
+            return Err(e);
+        }
+
+        Ok(true)
+    }
+
+    /// Move the agents a rollout updated back to their previous versions and
+    /// mark the rollout rolled back.
+    ///
+    /// Agents that have since moved to another version are left alone.
+    async fn revert_rollout(&self, rollout: &mut Rollout) -> Result<()> {
+        for target in &mut rollout.targets {
+            if !rollout::is_updated(target.state) {
+                continue;
+            }
+
+            let Some(mut agent) = self.store.get_agent(&target.agent_id)? else {
+                target.state = RolloutTargetState::Skipped;
+                target.error = Some("agent was deleted".to_string());
+                continue;
+            };
+            if agent.spec.runtime_version != rollout.target_version {
+                target.state = RolloutTargetState::Skipped;
+                target.error = Some("agent runtime version changed since the rollout".to_string());
+                continue;
+            }
+
+            agent
+                .spec
+                .runtime_version
+                .clone_from(&target.previous_version);
+            agent.updated_at = Utc::now();
+            self.store.put_agent(&agent)?;
+            target.state = RolloutTargetState::RolledBack;
+
+            if !target.restarted {
+                continue;
+            }
+            match self.restart_for_rollout(&mut agent).await {
+                Ok(true) => self.audit_agent_action(
+                    "agent.rollout_rollback",
+                    &agent,
+                    AuditOutcome::Success,
+                    None,
+                ),
+                Ok(false) => {}
+                Err(e) => {
+                    self.audit_agent_action(
+                        "agent.rollout_rollback",
+                        &agent,
+                        AuditOutcome::Failure,
+                        Some(&e.to_string()),
+                    );
+                    tracing::error!(
+                        rollout_id = %rollout.rollout_id,
+                        agent_id = %agent.agent_id,
+                        error = %e,
+                        "Failed to restart agent for rollback"
+                    );
+                }
+            }
+        }
+
+        rollout.status = RolloutStatus::RolledBack;
+        rollout.next_batch_at = None;
+        rollout.updated_at = Utc::now();
+
+        tracing::info!(rollout_id = %rollout.rollout_id, "Rolled back rollout");
+
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Agent Deletion
+// =============================================================================
+
+impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
+    /// Run the deletion worker, resuming failed and interrupted agent
+    /// deletions at the configured retry interval and pruning completed ones
+    /// once past their retention period.
+    ///
+    /// This method runs indefinitely and should be spawned as a background task.
+    pub async fn run_deletion_worker(&self) {
+        let period = std::time::Duration::from_secs(self.config.deletion_retry_seconds);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut last_pruned: Option<std::time::Instant> = None;
+
+        tracing::info!(
+            interval_seconds = self.config.deletion_retry_seconds,
+            "Starting deletion worker"
+        );
+
+        loop {
+            interval.tick().await;
+            match self.resume_deletions(Utc::now()).await {
+                Ok(0) => {}
+                Ok(deletions) => tracing::info!(deletions, "Resumed agent deletions"),
+                Err(e) => tracing::error!(error = %e, "Failed to resume agent deletions"),
+            }
+
+            if last_pruned.is_none_or(|at| at.elapsed() >= std::time::Duration::from_hours(1)) {
+                last_pruned = Some(std::time::Instant::now());
+                match self.prune_agent_deletions(Utc::now()) {
+                    Ok(0) => {}
+                    Ok(pruned) => tracing::info!(pruned, "Pruned completed agent deletions"),
+                    Err(e) => tracing::error!(error = %e, "Failed to prune agent deletions"),
+                }
+            }
+        }
+    }
+
+    /// Delete completed deletions that finished longer than the retention
+    /// period before `now`.
+    ///
+    /// Returns the number of deletions removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if deletions cannot be read or removed.
+    pub fn prune_agent_deletions(&self, now: DateTime<Utc>) -> Result<usize> {
+        let retention = secs(self.config.deletion_retention_hours.saturating_mul(3600));
+        let cutoff = now
+            .checked_sub_signed(retention)
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+        let mut pruned = 0;
+        for deletion in self.store.list_agent_deletions()? {
+            if deletion.status == DeletionStatus::Completed && deletion.updated_at < cutoff {
+                self.store.delete_agent_deletion(&deletion.agent_id)?;
+                pruned += 1;
+            }
+        }
+        Ok(pruned)
+    }
+
+    /// Resume every deletion that is due as of `now`.
+    ///
+    /// Returns the number of deletions that were resumed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if deletions cannot be read or their progress saved.
+    pub async fn resume_deletions(&self, now: DateTime<Utc>) -> Result<usize> {
+        let mut resumed = 0;
+
+        for mut deletion in self.store.list_agent_deletions()? {
+            if !deletion::is_due(&deletion, now) {
+                continue;
+            }
+            tracing::info!(
+                agent_id = %deletion.agent_id,
+                attempts = deletion.attempts,
+                "Resuming agent deletion"
+            );
+            self.run_deletion(&mut deletion).await?;
+            resumed += 1;
+        }
+
+        Ok(resumed)
+    }
+
+    /// Run a deletion's remaining steps, saving its progress after each one.
+    ///
+    /// A failing step marks the deletion as failed and schedules a retry.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error only if the deletion's progress cannot be saved.
+    async fn run_deletion(&self, deletion: &mut AgentDeletion) -> Result<()> {
+        let now = Utc::now();
+        deletion.status = DeletionStatus::InProgress;
+        deletion.failed_step = None;
+        deletion.error = None;
+        deletion.attempts += 1;
+        deletion.next_attempt_at = Some(now + deletion::lease());
+        deletion.updated_at = now;
+        self.store.put_agent_deletion(deletion)?;
+
+        while let Some(step) = deletion.next_step() {
+            if let Err(e) = self.run_deletion_step(&deletion.agent_id, step).await {
+                tracing::warn!(
+                    agent_id = %deletion.agent_id,
+                    step = ?step,
+                    error = %e,
+                    "Agent deletion step failed"
+                );
+                let now = Utc::now();
+                deletion.status = DeletionStatus::Failed;
+                deletion.failed_step = Some(step);
+                deletion.error = Some(e.to_string());
+                deletion.next_attempt_at = Some(now + secs(self.config.deletion_retry_seconds));
+                deletion.updated_at = now;
+                self.store.put_agent_deletion(deletion)?;
+                return Ok(());
+            }
+
+            deletion.completed_steps.push(step);
+            deletion.updated_at = Utc::now();
+            if deletion.next_step().is_none() {
+                deletion.status = DeletionStatus::Completed;
+                deletion.next_attempt_at = None;
+            }
+            self.store.put_agent_deletion(deletion)?;
+        }
+
+        Ok(())
+    }
+
+    /// Run one deletion step. Every step can safely be repeated.
+    async fn run_deletion_step(&self, agent_id: &AgentId, step: DeletionStep) -> Result<()> {
+        match step {
+            DeletionStep::PurgeSessions => {
+                self.close_agent_sessions(agent_id)?;
+                for session in self.store.list_sessions_by_agent(agent_id)? {
+                    self.store.delete_session(&session.session_id)?;
+                }
+            }
+            DeletionStep::TerminatePod => self.terminate_agent_pod(agent_id).await?,
+            DeletionStep::DeleteState => {
+                if let Some(scheduler) = &self.scheduler {
+                    scheduler.delete_agent_state(agent_id).await?;
+                }
+            }
+            DeletionStep::DeleteSecrets => {
+                if let Some(scheduler) = &self.scheduler {
+                    scheduler.delete_agent_secrets(agent_id).await?;
+                }
+            }
+            DeletionStep::DeleteNetworkPolicy => {
+                if let Some(scheduler) = &self.scheduler {
+                    scheduler.delete_agent_network_policy(agent_id).await?;
+                }
+            }
+            DeletionStep::DeleteRecord => {
+                match self.store.delete_schedule(agent_id) {
+                    Ok(()) | Err(StoreError::NotFound) => {}
+                    Err(e) => return Err(e.into()),
+                }
+                match self.store.delete_agent(agent_id) {
+                    Ok(()) | Err(StoreError::NotFound) => {}
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Agent Cloning
+// =============================================================================
+
+/// How long a running state copy is leased before the clone worker may pick
+/// it up again.
+///
+/// This is longer than the scheduler waits for the state copy job, so the
+/// worker only picks up a running copy if the process running it died.
+fn state_copy_lease() -> chrono::Duration {
+    chrono::Duration::minutes(30)
+}
+
+impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
+    /// Run the clone worker, copying the state of new clones and starting
+    /// their pods at the configured interval.
+    ///
+    /// This method runs indefinitely and should be spawned as a background task.
+    pub async fn run_clone_worker(&self) {
+        let period = std::time::Duration::from_secs(self.config.state_copy_interval_seconds);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        tracing::info!(
+            interval_seconds = self.config.state_copy_interval_seconds,
+            "Starting clone worker"
+        );
+
+        loop {
+            interval.tick().await;
+            match self.run_state_copies(Utc::now()).await {
+                Ok(0) => {}
+                Ok(copies) => tracing::info!(copies, "Ran clone state copies"),
+                Err(e) => tracing::error!(error = %e, "Failed to run clone state copies"),
+            }
+        }
+    }
+
+    /// Run every pending state copy that is due as of `now`.
+    ///
+    /// Returns the number of copies that were run.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the copies or their clones cannot be read or saved.
+    pub async fn run_state_copies(&self, now: DateTime<Utc>) -> Result<usize> {
+        let mut ran = 0;
+
+        for mut copy in self.store.list_state_copies()? {
+            if copy.next_attempt_at.is_some_and(|at| at > now) {
+                continue;
+            }
+
+            copy.attempts += 1;
+            copy.next_attempt_at = Some(now + state_copy_lease());
+            self.store.put_state_copy(&copy)?;
+
+            self.run_state_copy(&copy).await?;
+            self.store.delete_state_copy(&copy.agent_id)?;
+            ran += 1;
+        }
+
+        Ok(ran)
+    }
+
+    /// Copy a clone's state from its source, then schedule the clone's pod.
+    ///
+    /// A failed copy or schedule moves the clone to `Error`.
+    async fn run_state_copy(&self, copy: &StateCopy) -> Result<()> {
+        let Some(mut clone) = self.store.get_agent(&copy.agent_id)? else {
+            return Ok(());
+        };
+        if clone.status != AgentState::Provisioning {
+            return Ok(());
+        }
+
+        let result = match self
+            .copy_agent_state(&copy.source_id, &clone.agent_id)
+            .await
+        {
+            Ok(()) => {
+                // The provisioning deadline starts with the pod, not the copy
+                clone.provisioning_started_at = Some(Utc::now());
+                self.store.put_agent(&clone)?;
+                self.schedule_agent_pod(&clone).await
+            }
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            tracing::error!(
+                agent_id = %clone.agent_id,
+                source = %copy.source_id,
+                error = %e,
+                "Failed to start cloned agent, marking as error"
+            );
+            self.store.update_agent_error(
+                &clone.agent_id,
+                AgentState::Error,
+                Some(e.to_string()),
+            )?;
+            self.notify_state_change(&clone.agent_id);
+            return Ok(());
+        }
+
+        tracing::info!(
+            agent_id = %clone.agent_id,
+            source = %copy.source_id,
+            "Copied agent state and scheduled clone"
+        );
+        Ok(())
+    }
+}
+
+// =============================================================================
+// Session Reaper
+// =============================================================================
+
+impl<S: Store, SC: SchedulerClient> ControlPlaneService<S, SC> {
+    /// Run the session reaper, closing expired sessions at the configured interval.
+    ///
+    /// This method runs indefinitely and should be spawned as a background task.
+    /// WebSocket proxies re-read their session on every activity interval,
+    /// whether or not there was traffic, and disconnect once it is closed.
+    pub async fn run_session_reaper(&self) {
+        let period = std::time::Duration::from_secs(self.config.session_reap_interval_seconds);
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        tracing::info!(
+            interval_seconds = self.config.session_reap_interval_seconds,
+            "Starting session reaper"
+        );
+
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.reap_sessions(Utc::now()) {
+                tracing::error!(error = %e, "Failed to reap expired sessions");
+            }
+        }
+    }
+
+    /// Close all sessions that are idle past the timeout or older than the
+    /// maximum lifetime.
+    ///
+    /// Returns the number of sessions closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if sessions cannot be read from or written to the store.
+    pub fn reap_sessions(&self, now: DateTime<Utc>) -> Result<usize> {
+        let mut reaped = 0;
+
+        for session in self.store.list_active_sessions()? {
+            let Some(reason) = session::expiry_reason(&session, &self.config, now) else {
+                continue;
+            };
+
+            if session::close_active_session(&*self.store, &session)? {
+                self.notify_state_change(&session.agent_id);
+            }
+            reaped += 1;
+
+            let mut event = audit::system_event(
+                "session.expired",
+                "close",
+                audit::session_resource(&session.session_id),
+                AuditOutcome::Success,
+            );
+            event.namespace_id = session.namespace_id;
+            event
+                .details
+                .insert("agent_id".to_string(), session.agent_id.to_string());
+            event
+                .details
+                .insert("reason".to_string(), reason.to_string());
+            self.audit.log(event);
+
+            tracing::info!(
+                session_id = %session.session_id,
+                agent_id = %session.agent_id,
+                reason,
+                "Closed expired session"
+            );
+        }
+
+        Ok(reaped)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scheduler_client::NoopSchedulerClient;
+    use aura_swarm_store::{AgentPlacement, EgressPolicy, EgressRule, RocksStore};
+    use tempfile::TempDir;
+
+    fn caller() -> Caller {
+        Caller::member(
+            UserId::from_bytes([1u8; 32]),
+            NamespaceId::from_uuid(uuid::Uuid::from_bytes([1u8; 16])),
+        )
+    }
+
+    fn setup() -> (
+        ControlPlaneService<RocksStore, NoopSchedulerClient>,
+        TempDir,
+        Caller,
+    ) {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let config = ControlConfig {
+            max_agents_per_user: 3,
+            ..Default::default()
+        };
+        let service = ControlPlaneService::new(store, config);
+        (service, dir, caller())
+    }
+
+    #[tokio::test]
+    async fn create_agent_success() {
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+
+        assert_eq!(agent.name, "test-agent");
+        assert_eq!(agent.user_id, caller.user_id);
+        assert_eq!(agent.namespace_id, Some(caller.namespace_id));
+        assert_eq!(agent.status, AgentState::Provisioning);
+    }
+
+    #[tokio::test]
+    async fn create_agent_quota_exceeded() {
+        let (service, _dir, caller) = setup();
+
+        // Create max agents
+        for i in 0..3 {
+            let request = CreateAgentRequest::new(format!("agent-{i}"));
+            service.create_agent(&caller, request).await.unwrap();
+        }
+
+        // Try to create one more
+        let request = CreateAgentRequest::new("agent-overflow");
+        let result = service.create_agent(&caller, request).await;
+
+        assert!(matches!(
+            result,
+            Err(ControlError::QuotaExceeded { limit: 3, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn get_agent_not_owner() {
+        let (service, _dir, caller) = setup();
+        let other_user = Caller::member(UserId::from_bytes([99u8; 32]), caller.namespace_id);
+
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+
+        let result = service.get_agent(&other_user, &agent.agent_id).await;
+        assert!(matches!(result, Err(ControlError::NotOwner { .. })));
+    }
+
+    #[tokio::test]
+    async fn namespaces_are_isolated() {
+        let (service, _dir, caller) = setup();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("tenant-a"))
+            .await
+            .unwrap();
+
+        // The same user acting in another tenant sees nothing
+        let elsewhere = Caller::namespace_admin(
+            caller.user_id,
+            NamespaceId::from_uuid(uuid::Uuid::from_bytes([2u8; 16])),
+        );
+        assert!(service.list_agents(&elsewhere).await.unwrap().is_empty());
+        assert!(service
+            .list_namespace_agents(&elsewhere)
+            .await
+            .unwrap()
+            .is_empty());
+        let result = service.get_agent(&elsewhere, &agent.agent_id).await;
+        assert!(matches!(result, Err(ControlError::AgentNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn namespace_admin_manages_tenant_agents() {
+        let (service, _dir, caller) = setup();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("member-agent"))
+            .await
+            .unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+
+        let result = service.list_namespace_agents(&caller).await;
+        assert!(matches!(result, Err(ControlError::NotNamespaceAdmin(_))));
+
+        let admin = Caller::namespace_admin(UserId::from_bytes([7u8; 32]), caller.namespace_id);
+        let agents = service.list_namespace_agents(&admin).await.unwrap();
+        assert_eq!(agents.len(), 1);
+        assert!(service.list_agents(&admin).await.unwrap().is_empty());
+
+        let stopped = service.stop_agent(&admin, &agent.agent_id).await.unwrap();
+        assert_eq!(stopped.status, AgentState::Stopping);
+
+        let usage = service.get_namespace_usage(&admin).await.unwrap();
+        assert_eq!(usage.agents, 1);
+        assert_eq!(usage.max_agents, service.config.max_agents_per_namespace);
+    }
+
+    #[tokio::test]
+    async fn namespace_quota_applies_across_users() {
+        let (service, _dir, caller) = setup();
+        service
+            .set_namespace_quota(
+                &caller.namespace_id,
+                SetNamespaceQuotaRequest {
+                    max_agents: Some(2),
+                    max_sessions: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        service
+            .create_agent(&caller, CreateAgentRequest::new("first"))
+            .await
+            .unwrap();
+        let colleague = Caller::member(UserId::from_bytes([2u8; 32]), caller.namespace_id);
+        service
+            .create_agent(&colleague, CreateAgentRequest::new("second"))
+            .await
+            .unwrap();
+
+        let result = service
+            .create_agent(&colleague, CreateAgentRequest::new("third"))
+            .await;
+        assert!(matches!(
+            result,
+            Err(ControlError::NamespaceQuotaExceeded { limit: 2, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn shared_agents_follow_the_granted_role() {
+        let (service, _dir, caller) = setup();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("shared"))
+            .await
+            .unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+        let colleague = Caller::member(UserId::from_bytes([2u8; 32]), caller.namespace_id);
+
+        let result = service.get_agent(&colleague, &agent.agent_id).await;
+        assert!(matches!(result, Err(ControlError::NotOwner { .. })));
+
+        // Viewers can read and list sessions but not operate the agent
+        service
+            .grant_agent_access(
+                &caller,
+                &agent.agent_id,
+                &colleague.user_id,
+                AgentRole::Viewer,
+            )
+            .await
+            .unwrap();
+        service
+            .get_agent(&colleague, &agent.agent_id)
+            .await
+            .unwrap();
+        service
+            .list_sessions(&colleague, &agent.agent_id)
+            .await
+            .unwrap();
+        let result = service.create_session(&colleague, &agent.agent_id).await;
+        assert!(matches!(
+            result,
+            Err(ControlError::InsufficientRole {
+                required: AgentRole::Operator,
+                ..
+            })
+        ));
+
+        let shared = service.list_shared_agents(&colleague).await.unwrap();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].role, AgentRole::Viewer);
+        assert_eq!(shared[0].granted_by, caller.user_id);
+
+        // Operators can open sessions and stop the agent, but not delete or clone it
+        service
+            .grant_agent_access(
+                &caller,
+                &agent.agent_id,
+                &colleague.user_id,
+                AgentRole::Operator,
+            )
+            .await
+            .unwrap();
+        let session = service
+            .create_session(&colleague, &agent.agent_id)
+            .await
+            .unwrap();
+        service
+            .get_session(&caller, &session.session_id)
+            .await
+            .unwrap();
+        service
+            .stop_agent(&colleague, &agent.agent_id)
+            .await
+            .unwrap();
+        let result = service.delete_agent(&colleague, &agent.agent_id).await;
+        assert!(matches!(result, Err(ControlError::InsufficientRole { .. })));
+        let result = service
+            .clone_agent(&colleague, &agent.agent_id, CloneAgentRequest::default())
+            .await;
+        assert!(matches!(result, Err(ControlError::InsufficientRole { .. })));
+        let result = service.list_agent_grants(&colleague, &agent.agent_id).await;
+        assert!(matches!(result, Err(ControlError::InsufficientRole { .. })));
+
+        // Collaborators may drop their own access
+        service
+            .revoke_agent_access(&colleague, &agent.agent_id, &colleague.user_id)
+            .await
+            .unwrap();
+        let result = service.get_agent(&colleague, &agent.agent_id).await;
+        assert!(matches!(result, Err(ControlError::NotOwner { .. })));
+        assert!(service
+            .list_agent_grants(&caller, &agent.agent_id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn grants_do_not_cross_namespaces() {
+        let (service, _dir, caller) = setup();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("shared"))
+            .await
+            .unwrap();
+        let outsider = Caller::member(
+            UserId::from_bytes([2u8; 32]),
+            NamespaceId::from_uuid(uuid::Uuid::from_bytes([2u8; 16])),
+        );
+
+        service
+            .grant_agent_access(
+                &caller,
+                &agent.agent_id,
+                &outsider.user_id,
+                AgentRole::Owner,
+            )
+            .await
+            .unwrap();
+
+        let result = service.get_agent(&outsider, &agent.agent_id).await;
+        assert!(matches!(result, Err(ControlError::AgentNotFound(_))));
+        assert!(service
+            .list_shared_agents(&outsider)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let result = service
+            .grant_agent_access(&caller, &agent.agent_id, &caller.user_id, AgentRole::Viewer)
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn agent_lifecycle() {
+        let (service, _dir, caller) = setup();
+
+        // Create agent (starts in Provisioning)
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+        assert_eq!(agent.status, AgentState::Provisioning);
+
+        // Simulate provisioning complete (normally done by scheduler)
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+
+        // Hibernate
+        let agent = service
+            .hibernate_agent(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+        assert_eq!(agent.status, AgentState::Hibernating);
+
+        // Wake (goes through Provisioning for scheduler)
+        let agent = service.wake_agent(&caller, &agent.agent_id).await.unwrap();
+        assert_eq!(agent.status, AgentState::Provisioning);
+
+        // Simulate provisioning complete
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+
+        // Stop
+        let agent = service.stop_agent(&caller, &agent.agent_id).await.unwrap();
+        assert_eq!(agent.status, AgentState::Stopping);
+
+        // Simulate stop complete
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Stopped)
+            .unwrap();
+
+        // Delete
+        service
+            .delete_agent(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+        assert!(service.store.get_agent(&agent.agent_id).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_requires_stopped() {
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+
+        // Simulate running
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+
+        // Try to delete while running
+        let result = service.delete_agent(&caller, &agent.agent_id).await;
+        assert!(matches!(result, Err(ControlError::InvalidState { .. })));
+    }
+
+    #[tokio::test]
+    async fn create_agent_from_template_with_overrides() {
+        let (service, _dir, caller) = setup();
+
+        let mut spec = AgentSpec {
+            cpu_millicores: 1000,
+            ..Default::default()
+        };
+        spec.system_prompt = Some("You review code.".to_string());
+        let mut template = CreateTemplateRequest::new("reviewer", spec);
+        template
+            .labels
+            .insert("team".to_string(), "infra".to_string());
+        service.create_template(None, template).await.unwrap();
+
+        let overrides = crate::types::AgentSpecOverrides {
+            memory_mb: Some(2048),
+            ..Default::default()
+        };
+        let request =
+            CreateAgentRequest::from_template("my-reviewer", "reviewer").with_overrides(overrides);
+        let agent = service.create_agent(&caller, request).await.unwrap();
+
+        assert_eq!(agent.template.as_deref(), Some("reviewer"));
+        assert_eq!(agent.spec.cpu_millicores, 1000);
+        assert_eq!(agent.spec.memory_mb, 2048);
+        assert_eq!(
+            agent.spec.system_prompt.as_deref(),
+            Some("You review code.")
+        );
+        assert_eq!(agent.labels.get("team").map(String::as_str), Some("infra"));
+    }
+
+    #[tokio::test]
+    async fn user_template_shadows_global_template() {
+        let (service, _dir, caller) = setup();
+
+        let global = AgentSpec {
+            cpu_millicores: 1000,
+            ..Default::default()
+        };
+        let own = AgentSpec {
+            cpu_millicores: 250,
+            ..Default::default()
+        };
+        service
+            .create_template(None, CreateTemplateRequest::new("base", global))
+            .await
+            .unwrap();
+        service
+            .create_template(
+                Some(&caller.user_id),
+                CreateTemplateRequest::new("base", own),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            service.list_templates(&caller.user_id).await.unwrap().len(),
+            2
+        );
+
+        let request = CreateAgentRequest::from_template("agent", "base");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+        assert_eq!(agent.spec.cpu_millicores, 250);
+
+        let result = service
+            .create_template(
+                Some(&caller.user_id),
+                CreateTemplateRequest::new("base", AgentSpec::default()),
+            )
+            .await;
+        assert!(matches!(result, Err(ControlError::TemplateExists(_))));
+    }
+
+    #[tokio::test]
+    async fn get_and_delete_templates_by_scope() {
+        let (service, _dir, caller) = setup();
+
+        service
+            .create_template(
+                None,
+                CreateTemplateRequest::new("base", AgentSpec::default()),
+            )
+            .await
+            .unwrap();
+        let own = AgentSpec {
+            cpu_millicores: 250,
+            ..Default::default()
+        };
+        service
+            .create_template(
+                Some(&caller.user_id),
+                CreateTemplateRequest::new("base", own),
+            )
+            .await
+            .unwrap();
+
+        let template = service.get_template(&caller.user_id, "base").await.unwrap();
+        assert_eq!(template.owner, Some(caller.user_id));
+
+        service
+            .delete_template(Some(&caller.user_id), "base")
+            .await
+            .unwrap();
+        let template = service.get_template(&caller.user_id, "base").await.unwrap();
+        assert!(template.owner.is_none());
+
+        let result = service.delete_template(Some(&caller.user_id), "base").await;
+        assert!(matches!(result, Err(ControlError::TemplateNotFound(_))));
+
+        service.delete_template(None, "base").await.unwrap();
+        let result = service.get_template(&caller.user_id, "base").await;
+        assert!(matches!(result, Err(ControlError::TemplateNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn template_and_label_limits_come_from_config() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let config = ControlConfig {
+            max_templates_per_user: 1,
+            max_labels: 1,
+            ..Default::default()
+        };
+        let service = ControlPlaneService::new(store, config);
+        let caller = caller();
+
+        service
+            .create_template(
+                Some(&caller.user_id),
+                CreateTemplateRequest::new("first", AgentSpec::default()),
+            )
+            .await
+            .unwrap();
+        let result = service
+            .create_template(
+                Some(&caller.user_id),
+                CreateTemplateRequest::new("second", AgentSpec::default()),
+            )
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+
+        let mut request = CreateAgentRequest::from_template("agent", "first");
+        request
+            .labels
+            .insert("team".to_string(), "infra".to_string());
+        request
+            .labels
+            .insert("tier".to_string(), "gold".to_string());
+        let result = service.create_agent(&caller, request).await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn create_agent_rejects_unknown_template_and_excess_resources() {
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::from_template("agent", "missing");
+        let result = service.create_agent(&caller, request).await;
+        assert!(matches!(result, Err(ControlError::TemplateNotFound(_))));
+
+        let spec = AgentSpec {
+            memory_mb: 1024 * 1024,
+            ..Default::default()
+        };
+        let result = service
+            .create_agent(&caller, CreateAgentRequest::with_spec("agent", spec))
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+
+        let spec = AgentSpec {
+            storage_gb: Some(10_000),
+            ..Default::default()
+        };
+        let result = service
+            .create_agent(&caller, CreateAgentRequest::with_spec("agent", spec))
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+
+        let spec = AgentSpec {
+            runtime_version: "v1@sha256:abc".to_string(),
+            ..Default::default()
+        };
+        let result = service
+            .create_agent(&caller, CreateAgentRequest::with_spec("agent", spec))
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+
+        let spec = AgentSpec {
+            egress: Some(EgressPolicy::Allowlist {
+                rules: vec![EgressRule {
+                    cidr: Some("10.0.0.0/99".to_string()),
+                    host: None,
+                    ports: Vec::new(),
+                }],
+            }),
+            ..Default::default()
+        };
+        let result = service
+            .create_agent(&caller, CreateAgentRequest::with_spec("agent", spec))
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+
+        let spec = AgentSpec {
+            placement: Some(AgentPlacement {
+                priority_class: Some("system-node-critical".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let result = service
+            .create_agent(&caller, CreateAgentRequest::with_spec("agent", spec))
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+        assert_eq!(
+            service.store.count_agents_by_user(&caller.user_id).unwrap(),
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn create_agent_validates_env_and_system_prompt() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let config = ControlConfig {
+            max_env_vars: 2,
+            max_system_prompt_bytes: 16,
+            ..Default::default()
+        };
+        let service = ControlPlaneService::new(store, config);
+        let caller = caller();
+
+        let with_env = |names: &[&str]| AgentSpec {
+            env: names
+                .iter()
+                .map(|name| ((*name).to_string(), "1".to_string()))
+                .collect(),
+            ..Default::default()
+        };
+        for spec in [
+            with_env(&["AGENT_ID"]),
+            with_env(&["AURA_STANDBY"]),
+            with_env(&["LOG-LEVEL"]),
+            with_env(&["1ST"]),
+            with_env(&["A", "B", "C"]),
+            AgentSpec {
+                system_prompt: Some("x".repeat(17)),
+                ..Default::default()
+            },
+        ] {
+            let result = service
+                .create_agent(&caller, CreateAgentRequest::with_spec("agent", spec))
+                .await;
+            assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+        }
+
+        let spec = AgentSpec {
+            system_prompt: Some("x".repeat(16)),
+            ..with_env(&["LOG_LEVEL", "_DEBUG"])
+        };
+        service
+            .create_agent(&caller, CreateAgentRequest::with_spec("agent", spec))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn clone_agent_copies_spec_and_records_source() {
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let source = service.create_agent(&caller, request).await.unwrap();
+        service
+            .store
+            .update_agent_status(&source.agent_id, AgentState::Stopped)
+            .unwrap();
+
+        let clone = service
+            .clone_agent(&caller, &source.agent_id, CloneAgentRequest::default())
+            .await
+            .unwrap();
+
+        assert_ne!(clone.agent_id, source.agent_id);
+        assert_eq!(clone.name, "test-agent-clone");
+        assert_eq!(clone.status, AgentState::Provisioning);
+        assert_eq!(clone.spec.cpu_millicores, source.spec.cpu_millicores);
+        assert_eq!(clone.cloned_from, Some(source.agent_id));
+
+        let stored = service.store.get_agent(&clone.agent_id).unwrap().unwrap();
+        assert_eq!(stored.cloned_from, Some(source.agent_id));
+    }
+
+    #[tokio::test]
+    async fn clone_agent_requires_stopped_or_hibernating() {
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let source = service.create_agent(&caller, request).await.unwrap();
+        service
+            .store
+            .update_agent_status(&source.agent_id, AgentState::Running)
+            .unwrap();
+
+        let result = service
+            .clone_agent(&caller, &source.agent_id, CloneAgentRequest::named("copy"))
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidState { .. })));
+        assert_eq!(
+            service.store.count_agents_by_user(&caller.user_id).unwrap(),
+            1
+        );
+    }
+
+    fn working_hours() -> Vec<aura_swarm_store::ScheduleRule> {
+        vec![
+            aura_swarm_store::ScheduleRule {
+                action: ScheduleAction::Wake,
+                cron: "0 8 * * *".to_string(),
+            },
+            aura_swarm_store::ScheduleRule {
+                action: ScheduleAction::Hibernate,
+                cron: "0 19 * * *".to_string(),
+            },
+        ]
+    }
+
+    #[tokio::test]
+    async fn schedule_wakes_and_hibernates_agent() {
+        use chrono::TimeZone;
+
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Hibernating)
+            .unwrap();
+
+        service
+            .set_schedule(
+                &caller,
+                &agent.agent_id,
+                SetScheduleRequest::new("UTC", working_hours()),
+            )
+            .await
+            .unwrap();
+
+        // Pretend the schedule was last evaluated just before the wake time
+        let mut schedule = service
+            .store
+            .get_schedule(&agent.agent_id)
+            .unwrap()
+            .unwrap();
+        schedule.last_checked_at = Utc.with_ymd_and_hms(2024, 1, 15, 7, 59, 0).unwrap();
+        service.store.put_schedule(&schedule).unwrap();
+
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 8, 0, 30).unwrap();
+        assert_eq!(service.process_schedules(now).await.unwrap(), 1);
+
+        let woken = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(woken.status, AgentState::Provisioning);
+
+        let schedule = service
+            .get_schedule(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+        let run = schedule.last_run.unwrap();
+        assert_eq!(run.action, ScheduleAction::Wake);
+        assert_eq!(run.outcome, ScheduleRunOutcome::Executed);
+        assert_eq!(schedule.last_checked_at, now);
+
+        // Running again for the same window does nothing
+        assert_eq!(service.process_schedules(now).await.unwrap(), 0);
+
+        // Evening: hibernate
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+        let evening = Utc.with_ymd_and_hms(2024, 1, 15, 19, 0, 10).unwrap();
+        assert_eq!(service.process_schedules(evening).await.unwrap(), 1);
+        let agent = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(agent.status, AgentState::Hibernating);
+    }
+
+    #[tokio::test]
+    async fn schedule_missed_runs_policy() {
+        use chrono::TimeZone;
+
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Hibernating)
+            .unwrap();
+
+        let mut request = SetScheduleRequest::new("UTC", working_hours());
+        request.missed_runs = MissedRunPolicy::Skip;
+        service
+            .set_schedule(&caller, &agent.agent_id, request)
+            .await
+            .unwrap();
+
+        // Control plane was down from before 08:00 until 12:00
+        let mut schedule = service
+            .store
+            .get_schedule(&agent.agent_id)
+            .unwrap()
+            .unwrap();
+        schedule.last_checked_at = Utc.with_ymd_and_hms(2024, 1, 15, 6, 0, 0).unwrap();
+        service.store.put_schedule(&schedule).unwrap();
+
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        assert_eq!(service.process_schedules(now).await.unwrap(), 0);
+
+        let schedule = service
+            .store
+            .get_schedule(&agent.agent_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            schedule.last_run.unwrap().outcome,
+            ScheduleRunOutcome::Skipped
+        );
+        let agent_after = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(agent_after.status, AgentState::Hibernating);
+
+        // With the default policy the latest missed run is performed
+        service
+            .set_schedule(
+                &caller,
+                &agent.agent_id,
+                SetScheduleRequest::new("UTC", working_hours()),
+            )
+            .await
+            .unwrap();
+        let mut schedule = service
+            .store
+            .get_schedule(&agent.agent_id)
+            .unwrap()
+            .unwrap();
+        schedule.last_checked_at = Utc.with_ymd_and_hms(2024, 1, 15, 6, 0, 0).unwrap();
+        service.store.put_schedule(&schedule).unwrap();
+
+        assert_eq!(service.process_schedules(now).await.unwrap(), 1);
+        let agent_after = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(agent_after.status, AgentState::Provisioning);
+    }
+
+    #[tokio::test]
+    async fn set_schedule_rejects_invalid_rules() {
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+
+        let result = service
+            .set_schedule(
+                &caller,
+                &agent.agent_id,
+                SetScheduleRequest::new("Nowhere/Special", working_hours()),
+            )
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+
+        let result = service.get_schedule(&caller, &agent.agent_id).await;
+        assert!(matches!(result, Err(ControlError::ScheduleNotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn reap_sessions_closes_idle_sessions() {
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+
+        let idle = service
+            .create_session(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+        let busy = service
+            .create_session(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+
+        // Only the busy session sees traffic later on
+        let later = idle.created_at + chrono::Duration::seconds(1700);
+        session::touch_session(&*service.store, &caller, &busy.session_id, later).unwrap();
+
+        let now = idle.created_at + chrono::Duration::seconds(1900);
+        assert_eq!(service.reap_sessions(now).unwrap(), 1);
+
+        let idle = service
+            .get_session(&caller, &idle.session_id)
+            .await
+            .unwrap();
+        assert_eq!(idle.status, aura_swarm_store::SessionStatus::Closed);
+
+        // The proxy sees the closure on its next activity report
+        let touched = service
+            .touch_session(&caller, &idle.session_id)
+            .await
+            .unwrap();
+        assert_eq!(touched.status, aura_swarm_store::SessionStatus::Closed);
+
+        // Agent stays running while a session remains
+        let agent_now = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(agent_now.status, AgentState::Running);
+
+        // Once the last session expires the agent goes idle
+        let much_later = later + chrono::Duration::seconds(1800);
+        assert_eq!(service.reap_sessions(much_later).unwrap(), 1);
+        let agent_now = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(agent_now.status, AgentState::Idle);
+    }
+
+    #[tokio::test]
+    async fn session_lifecycle() {
+        let (service, _dir, caller) = setup();
+
+        // Create and start agent
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+
+        // Create session
+        let session = service
+            .create_session(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+        assert_eq!(session.status, aura_swarm_store::SessionStatus::Active);
+
+        // Get session
+        let retrieved = service
+            .get_session(&caller, &session.session_id)
+            .await
+            .unwrap();
+        assert_eq!(retrieved.session_id, session.session_id);
+
+        // List sessions
+        let sessions = service
+            .list_sessions(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+        assert_eq!(sessions.len(), 1);
+
+        // Close session
+        service
+            .close_session(&caller, &session.session_id)
+            .await
+            .unwrap();
+
+        // Agent should transition to Idle
+        let agent = service.get_agent(&caller, &agent.agent_id).await.unwrap();
+        assert_eq!(agent.status, AgentState::Idle);
+    }
+
+    #[tokio::test]
+    async fn heartbeat_updates_timestamp() {
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+
+        assert!(agent.last_heartbeat_at.is_none());
+
+        service.process_heartbeat(&agent.agent_id).await.unwrap();
+
+        let updated = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert!(updated.last_heartbeat_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn resolve_endpoint_active() {
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+
+        let endpoint = service
+            .resolve_agent_endpoint(&agent.agent_id)
+            .await
+            .unwrap();
+        assert!(endpoint.is_some());
+    }
+
+    #[tokio::test]
+    async fn resolve_endpoint_stopped() {
+        let (service, _dir, caller) = setup();
+
+        let request = CreateAgentRequest::new("test-agent");
+        let agent = service.create_agent(&caller, request).await.unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Stopped)
+            .unwrap();
+
+        let endpoint = service
+            .resolve_agent_endpoint(&agent.agent_id)
+            .await
+            .unwrap();
+        assert!(endpoint.is_none());
+    }
+
+    /// Service with a secret key for webhook secrets, allowed to deliver to
+    /// the loopback mock servers used in tests.
+    fn webhook_setup(
+        mut config: ControlConfig,
+    ) -> (
+        ControlPlaneService<RocksStore, NoopSchedulerClient>,
+        TempDir,
+        Caller,
+    ) {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        config.webhook_allow_private_targets = true;
+        let service = ControlPlaneService::new(store, config)
+            .with_secret_cipher(SecretCipher::new(&[7u8; secrets::KEY_LEN]).unwrap());
+        (service, dir, caller())
+    }
+
+    async fn running_agent_with_webhook(
+        service: &ControlPlaneService<RocksStore, NoopSchedulerClient>,
+        caller: &Caller,
+        url: String,
+        events: Vec<WebhookEvent>,
+    ) -> (Agent, CreatedWebhook) {
+        let hook = service
+            .create_webhook(
+                &caller.user_id,
+                CreateWebhookRequest {
+                    events,
+                    ..CreateWebhookRequest::new(url)
+                },
+            )
+            .await
+            .unwrap();
+        let agent = service
+            .create_agent(caller, CreateAgentRequest::new("hooked"))
+            .await
+            .unwrap();
+        service
+            .update_agent_status_internal(&agent.agent_id, AgentState::Running, None)
+            .await
+            .unwrap();
+        (agent, hook)
+    }
+
+    #[tokio::test]
+    async fn state_change_delivers_signed_webhook() {
+        use wiremock::matchers::{header, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header(webhook::EVENT_HEADER, "agent.running"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (service, _dir, caller) = webhook_setup(ControlConfig::default());
+        let (agent, created) =
+            running_agent_with_webhook(&service, &caller, server.uri(), Vec::new()).await;
+
+        let pending = service.store.list_pending_webhook_deliveries().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].event, WebhookEvent::AgentRunning);
+
+        let attempted = service.dispatch_webhooks(Utc::now()).await.unwrap();
+        assert_eq!(attempted, 1);
+        assert!(service
+            .store
+            .list_pending_webhook_deliveries()
+            .unwrap()
+            .is_empty());
+
+        let requests = server.received_requests().await.unwrap();
+        let body = String::from_utf8(requests[0].body.clone()).unwrap();
+        let signature = requests[0].headers[webhook::SIGNATURE_HEADER]
+            .to_str()
+            .unwrap();
+        assert!(webhook::verify_signature(&created.secret, signature, &body));
+        assert!(body.contains(&agent.agent_id.to_string()));
+
+        // Only the sealed secret is stored
+        let stored = service
+            .store
+            .get_webhook(&created.webhook.webhook_id)
+            .unwrap()
+            .unwrap();
+        assert!(!stored
+            .sealed_secret
+            .windows(created.secret.len())
+            .any(|w| w == created.secret.as_bytes()));
+
+        let log = service
+            .list_webhook_deliveries(&caller.user_id, &created.webhook.webhook_id, 10)
+            .await
+            .unwrap();
+        assert_eq!(log[0].status, DeliveryStatus::Succeeded);
+        assert_eq!(log[0].attempts[0].status_code, Some(204));
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_backs_off_then_fails() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500).set_body_string("boom"))
+            .mount(&server)
+            .await;
+
+        let (service, _dir, caller) = webhook_setup(ControlConfig {
+            webhook_max_attempts: 2,
+            webhook_backoff_base_seconds: 60,
+            ..Default::default()
+        });
+        let (_, created) =
+            running_agent_with_webhook(&service, &caller, server.uri(), Vec::new()).await;
+
+        let now = Utc::now();
+        assert_eq!(service.dispatch_webhooks(now).await.unwrap(), 1);
+
+        let pending = service.store.list_pending_webhook_deliveries().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].attempts.len(), 1);
+        // The status is recorded, the response body is not
+        assert_eq!(
+            pending[0].attempts[0].error.as_deref(),
+            Some("endpoint returned 500 Internal Server Error")
+        );
+        assert_eq!(
+            pending[0].next_attempt_at,
+            Some(now + chrono::Duration::seconds(60))
+        );
+
+        // Not yet due
+        assert_eq!(service.dispatch_webhooks(now).await.unwrap(), 0);
+
+        let later = now + chrono::Duration::seconds(61);
+        assert_eq!(service.dispatch_webhooks(later).await.unwrap(), 1);
+        assert!(service
+            .store
+            .list_pending_webhook_deliveries()
+            .unwrap()
+            .is_empty());
+
+        let log = service
+            .list_webhook_deliveries(&caller.user_id, &created.webhook.webhook_id, 10)
+            .await
+            .unwrap();
+        assert_eq!(log[0].status, DeliveryStatus::Failed);
+        assert_eq!(log[0].attempts.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn webhook_event_filter_and_ownership() {
+        let (service, _dir, caller) = webhook_setup(ControlConfig::default());
+        let (_, created) = running_agent_with_webhook(
+            &service,
+            &caller,
+            "http://127.0.0.1:9/hook".to_string(),
+            vec![WebhookEvent::AgentError],
+        )
+        .await;
+
+        // Running is not subscribed
+        assert!(service
+            .store
+            .list_pending_webhook_deliveries()
+            .unwrap()
+            .is_empty());
+
+        let hook = created.webhook;
+        let other = UserId::from_bytes([2u8; 32]);
+        let result = service.get_webhook(&other, &hook.webhook_id).await;
+        assert!(matches!(result, Err(ControlError::WebhookNotFound(_))));
+        let result = service.delete_webhook(&other, &hook.webhook_id).await;
+        assert!(matches!(result, Err(ControlError::WebhookNotFound(_))));
+
+        let result = service
+            .create_webhook(
+                &caller.user_id,
+                CreateWebhookRequest::new("ftp://example.com"),
+            )
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+    }
+
+    #[tokio::test]
+    async fn ping_webhook_attempts_immediately() {
+        use wiremock::matchers::{header, method};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(header(webhook::EVENT_HEADER, "ping"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let (service, _dir, caller) = webhook_setup(ControlConfig::default());
+        let created = service
+            .create_webhook(&caller.user_id, CreateWebhookRequest::new(server.uri()))
+            .await
+            .unwrap();
+
+        let delivery = service
+            .ping_webhook(&caller.user_id, &created.webhook.webhook_id)
+            .await
+            .unwrap();
+        assert_eq!(delivery.status, DeliveryStatus::Succeeded);
+        assert_eq!(delivery.event, WebhookEvent::Ping);
+    }
+
+    #[tokio::test]
+    async fn webhooks_need_a_secret_key_and_a_public_target() {
+        let (service, dir, caller) = setup();
+        let result = service
+            .create_webhook(
+                &caller.user_id,
+                CreateWebhookRequest::new("https://93.184.216.34/hook"),
+            )
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+
+        let store = Arc::new(RocksStore::open(dir.path().join("keyed")).unwrap());
+        let service = ControlPlaneService::new(store, ControlConfig::default())
+            .with_secret_cipher(SecretCipher::new(&[7u8; secrets::KEY_LEN]).unwrap());
+        for url in [
+            "http://127.0.0.1:9000/hook",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://localhost/hook",
+        ] {
+            let result = service
+                .create_webhook(&caller.user_id, CreateWebhookRequest::new(url))
+                .await;
+            assert!(
+                matches!(result, Err(ControlError::InvalidRequest(_))),
+                "{url}"
+            );
+        }
+        service
+            .create_webhook(
+                &caller.user_id,
+                CreateWebhookRequest::new("https://93.184.216.34/hook"),
+            )
+            .await
+            .unwrap();
+    }
+
+    /// Scheduler fake that serves a fixed pod list and records calls.
+    #[derive(Default)]
+    struct FakeScheduler {
+        pods: std::sync::Mutex<Vec<crate::scheduler_client::PodSummary>>,
+        scheduled: std::sync::Mutex<Vec<AgentId>>,
+        terminated: std::sync::Mutex<Vec<AgentId>>,
+        copied: std::sync::Mutex<Vec<(AgentId, AgentId)>>,
+        secrets: std::sync::Mutex<std::collections::HashMap<AgentId, BTreeMap<String, String>>>,
+        state_deleted: std::sync::Mutex<Vec<AgentId>>,
+        network_policies_deleted: std::sync::Mutex<Vec<AgentId>>,
+        fail_schedule: bool,
+        fail_delete_state: bool,
+        endpoint: Option<String>,
+        metrics: Option<crate::scheduler_client::PodMetricsResponse>,
+        logs: Vec<String>,
+    }
+
+    #[async_trait]
+    impl SchedulerClient for FakeScheduler {
+        async fn schedule_agent(&self, agent_id: &AgentId, _: &str, _: &AgentSpec) -> Result<()> {
+            if self.fail_schedule {
+                return Err(ControlError::Internal("cluster full".to_string()));
+            }
+            self.scheduled.lock().unwrap().push(*agent_id);
+            Ok(())
+        }
+
+        async fn terminate_agent(&self, agent_id: &AgentId) -> Result<()> {
+            self.terminated.lock().unwrap().push(*agent_id);
+            Ok(())
+        }
+
+        async fn get_pod_status(
+            &self,
+            agent_id: &AgentId,
+        ) -> Result<crate::scheduler_client::PodStatusResponse> {
+            self.pods
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|pod| pod.agent_id == *agent_id)
+                .map(|pod| pod.status.clone())
+                .ok_or(ControlError::AgentNotFound(*agent_id))
+        }
+
+        async fn get_pod_endpoint(&self, _: &AgentId) -> Result<Option<String>> {
+            Ok(self.endpoint.clone())
+        }
+
+        async fn get_pod_metrics(
+            &self,
+            _: &AgentId,
+        ) -> Result<Option<crate::scheduler_client::PodMetricsResponse>> {
+            Ok(self.metrics.clone())
+        }
+
+        async fn stream_pod_logs(
+            &self,
+            _: &AgentId,
+            tail: Option<u32>,
+            _: Option<DateTime<Utc>>,
+            _: bool,
+        ) -> Result<crate::scheduler_client::LogLineStream> {
+            let tail = tail.map_or(self.logs.len(), |tail| usize::try_from(tail).unwrap());
+            let skip = self.logs.len().saturating_sub(tail);
+            let lines: Vec<_> = self.logs[skip..].iter().cloned().map(Ok).collect();
+            Ok(futures::stream::iter(lines).boxed())
+        }
+
+        async fn copy_agent_state(&self, source: &AgentId, target: &AgentId) -> Result<()> {
+            self.copied.lock().unwrap().push((*source, *target));
+            Ok(())
+        }
+
+        async fn delete_agent_state(&self, agent_id: &AgentId) -> Result<()> {
+            if self.fail_delete_state {
+                return Err(ControlError::SchedulerUnavailable(
+                    "job timed out".to_string(),
+                ));
+            }
+            self.state_deleted.lock().unwrap().push(*agent_id);
+            Ok(())
+        }
+
+        async fn list_pods(&self) -> Result<Vec<crate::scheduler_client::PodSummary>> {
+            Ok(self.pods.lock().unwrap().clone())
+        }
+
+        async fn put_agent_secrets(
+            &self,
+            agent_id: &AgentId,
+            secrets: &BTreeMap<String, String>,
+        ) -> Result<()> {
+            let mut stored = self.secrets.lock().unwrap();
+            if secrets.is_empty() {
+                stored.remove(agent_id);
+            } else {
+                stored.insert(*agent_id, secrets.clone());
+            }
+            Ok(())
+        }
+
+        async fn delete_agent_secrets(&self, agent_id: &AgentId) -> Result<()> {
+            self.secrets.lock().unwrap().remove(agent_id);
+            Ok(())
+        }
+
+        async fn delete_agent_network_policy(&self, agent_id: &AgentId) -> Result<()> {
+            self.network_policies_deleted
+                .lock()
+                .unwrap()
+                .push(*agent_id);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn agent_status_reports_pod_metrics_and_sessions() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let scheduler = Arc::new(FakeScheduler {
+            metrics: Some(crate::scheduler_client::PodMetricsResponse {
+                cpu_millicores: 250,
+                memory_bytes: 256 * 1024 * 1024,
+                timestamp: None,
+            }),
+            ..Default::default()
+        });
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler);
+        let caller = caller();
+
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("agent"))
+            .await
+            .unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+        service
+            .create_session(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+
+        let status = service
+            .get_agent_status(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+        assert!(status.healthy);
+        assert!((status.cpu_usage - 0.5).abs() < f64::EPSILON);
+        assert_eq!(status.memory_bytes, 256 * 1024 * 1024);
+        assert_eq!(status.active_sessions, 1);
+
+        // Stopped agents have no pod to measure
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Stopped)
+            .unwrap();
+        let status = service
+            .get_agent_status(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+        assert!(!status.healthy);
+        assert_eq!(status.memory_bytes, 0);
+    }
+
+    #[tokio::test]
+    async fn agent_logs_parses_runtime_lines() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let scheduler = Arc::new(FakeScheduler {
+            logs: vec![
+                "2026-01-01T00:00:00Z booting".to_string(),
+                r#"2026-01-01T00:00:01Z {"level":"ERROR","fields":{"message":"boom"}}"#.to_string(),
+                r#"2026-01-01T00:00:02Z {"level":"INFO","fields":{"message":"later"}}"#.to_string(),
+            ],
+            ..Default::default()
+        });
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler);
+        let caller = caller();
+
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("agent"))
+            .await
+            .unwrap();
+
+        let options = LogOptions {
+            until: Some("2026-01-01T00:00:01Z".to_string()),
+            ..LogOptions::default()
+        };
+        let entries: Vec<_> = service
+            .agent_logs(&caller, &agent.agent_id, options)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].message, "booting");
+        assert_eq!(entries[1].level, "error");
+        assert_eq!(entries[1].message, "boom");
+
+        let result = service
+            .agent_logs(&caller, &agent.agent_id, LogOptions::tail(0))
+            .await;
+        assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
+    }
+
+    fn fake_pod(agent_id: AgentId) -> crate::scheduler_client::PodSummary {
+        crate::scheduler_client::PodSummary {
+            agent_id,
+            pod_name: format!("agent-{}", &agent_id.to_hex()[..8]),
+            node_name: None,
+            status: crate::scheduler_client::PodStatusResponse {
+                phase: "running".to_string(),
+                ready: true,
+                restart_count: 0,
+                message: None,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn drift_reconciler_corrects_store_and_cluster() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let scheduler = Arc::new(FakeScheduler::default());
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler.clone());
+        let caller = caller();
+
+        let running = service
+            .create_agent(&caller, CreateAgentRequest::new("running"))
+            .await
+            .unwrap();
+        let stopping = service
+            .create_agent(&caller, CreateAgentRequest::new("stopping"))
+            .await
+            .unwrap();
+        let healthy = service
+            .create_agent(&caller, CreateAgentRequest::new("healthy"))
+            .await
+            .unwrap();
+        service
+            .store
+            .update_agent_status(&running.agent_id, AgentState::Running)
+            .unwrap();
+        service
+            .store
+            .update_agent_status(&stopping.agent_id, AgentState::Stopping)
+            .unwrap();
+
+        let orphan = AgentId::from_bytes([9u8; 32]);
+        *scheduler.pods.lock().unwrap() = vec![fake_pod(healthy.agent_id), fake_pod(orphan)];
+        scheduler.scheduled.lock().unwrap().clear();
+
+        // Within the grace period nothing but the orphan pod is touched
+        let report = service.reconcile_drift(Utc::now()).await.unwrap();
+        assert_eq!(report.corrections.len(), 1);
+        assert_eq!(
+            report.corrections[0].action,
+            DriftAction::TerminateOrphanPod
+        );
+        assert_eq!(*scheduler.terminated.lock().unwrap(), vec![orphan]);
+
+        let later = Utc::now() + chrono::Duration::minutes(10);
+        let report = service.reconcile_drift(later).await.unwrap();
+        assert_eq!(report.agents_checked, 3);
+        assert_eq!(report.pods_checked, 2);
+        assert_eq!(report.failures(), 0);
+
+        let running = service.store.get_agent(&running.agent_id).unwrap().unwrap();
+        assert_eq!(running.status, AgentState::Provisioning);
+        assert_eq!(*scheduler.scheduled.lock().unwrap(), vec![running.agent_id]);
+
+        let stopping = service
+            .store
+            .get_agent(&stopping.agent_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(stopping.status, AgentState::Stopped);
+
+        let healthy = service.store.get_agent(&healthy.agent_id).unwrap().unwrap();
+        assert_eq!(healthy.status, AgentState::Provisioning);
+    }
+
+    #[tokio::test]
+    async fn drift_reschedule_failure_marks_error() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let scheduler = Arc::new(FakeScheduler::default());
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler);
+        let caller = caller();
+
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("lost"))
+            .await
+            .unwrap();
+
+        // Swap in a scheduler that can no longer place pods
+        let failing = Arc::new(FakeScheduler {
+            fail_schedule: true,
+            ..FakeScheduler::default()
+        });
+        let service = ControlPlaneService::with_scheduler(
+            Arc::clone(&service.store),
+            ControlConfig::default(),
+            failing,
+        );
+
+        let later = Utc::now() + chrono::Duration::minutes(10);
+        let report = service.reconcile_drift(later).await.unwrap();
+        assert_eq!(report.corrections.len(), 1);
+        assert_eq!(report.corrections[0].action, DriftAction::Reschedule);
+        assert_eq!(report.corrections[0].new_state, Some(AgentState::Error));
+        assert_eq!(report.failures(), 1);
+
+        let agent = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(agent.status, AgentState::Error);
+        assert!(agent.error_message.unwrap().contains("cluster full"));
+    }
+
+    #[tokio::test]
+    async fn drift_reconciler_leaves_deleting_and_copying_agents_alone() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let scheduler = Arc::new(FakeScheduler::default());
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler.clone());
+        let caller = caller();
+
+        let mut ids = Vec::new();
+        for name in ["lost", "copying", "deleting"] {
+            let agent = service
+                .create_agent(&caller, CreateAgentRequest::new(name))
+                .await
+                .unwrap();
+            service
+                .store
+                .update_agent_status(&agent.agent_id, AgentState::Running)
+                .unwrap();
+            ids.push(agent.agent_id);
+        }
+        let (lost, copying, deleting) = (ids[0], ids[1], ids[2]);
+
+        service
+            .store
+            .put_state_copy(&StateCopy {
+                agent_id: AgentId::from_bytes([9u8; 32]),
+                source_id: copying,
+                attempts: 0,
+                next_attempt_at: None,
+                created_at: Utc::now(),
+            })
+            .unwrap();
+        let agent = service.store.get_agent(&deleting).unwrap().unwrap();
+        service
+            .store
+            .put_agent_deletion(&deletion::new_deletion(&agent, caller.user_id, Utc::now()))
+            .unwrap();
+        scheduler.scheduled.lock().unwrap().clear();
+
+        let later = Utc::now() + chrono::Duration::minutes(10);
+        let report = service.reconcile_drift(later).await.unwrap();
+        assert_eq!(report.corrections.len(), 1);
+        assert_eq!(report.corrections[0].agent_id, lost);
+        assert_eq!(*scheduler.scheduled.lock().unwrap(), vec![lost]);
+
+        // The rescheduled agent gets a fresh provisioning deadline
+        let agent = service.store.get_agent(&lost).unwrap().unwrap();
+        assert_eq!(agent.status, AgentState::Provisioning);
+        assert!(agent.provisioning_started_at.unwrap() > agent.created_at);
+
+        for id in [copying, deleting] {
+            let agent = service.store.get_agent(&id).unwrap().unwrap();
+            assert_eq!(agent.status, AgentState::Running);
+        }
+    }
+
+    #[tokio::test]
+    async fn hibernate_flushes_runtime_state_before_stopping() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let runtime = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hibernate"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "status": "hibernating",
+                "state_saved": true,
+                "state_size_bytes": 1_048_576,
+            })))
+            .expect(1)
+            .mount(&runtime)
+            .await;
+
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let scheduler = Arc::new(FakeScheduler {
+            endpoint: Some(runtime.address().to_string()),
+            ..FakeScheduler::default()
+        });
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler.clone());
+        let caller = caller();
+
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("sleepy"))
+            .await
+            .unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+
+        let agent = service
+            .hibernate_agent(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+        assert_eq!(agent.status, AgentState::Hibernating);
+        let checkpoint = agent.hibernation.unwrap();
+        assert!(!checkpoint.forced);
+        assert_eq!(checkpoint.state_size_bytes, Some(1_048_576));
+        assert_eq!(*scheduler.terminated.lock().unwrap(), vec![agent.agent_id]);
+
+        let stored = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(stored.hibernation, Some(checkpoint));
+    }
+
+    #[tokio::test]
+    async fn hibernate_forces_stop_when_runtime_does_not_answer() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let scheduler = Arc::new(FakeScheduler::default());
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler.clone());
+        let caller = caller();
+
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("unreachable"))
+            .await
+            .unwrap();
+        service
+            .store
+            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .unwrap();
+
+        let agent = service
+            .hibernate_agent(&caller, &agent.agent_id)
+            .await
+            .unwrap();
+        assert_eq!(agent.status, AgentState::Hibernating);
+        let checkpoint = agent.hibernation.unwrap();
+        assert!(checkpoint.forced);
+        assert_eq!(checkpoint.state_size_bytes, None);
+        assert_eq!(*scheduler.terminated.lock().unwrap(), vec![agent.agent_id]);
+    }
+
+    #[tokio::test]
+    async fn provisioning_deadline_marks_error() {
+        let (service, _dir, caller) = setup();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("stuck"))
+            .await
+            .unwrap();
+
+        assert_eq!(service.supervise_agents(Utc::now()).await.unwrap(), 0);
+
+        let later = Utc::now() + chrono::Duration::minutes(11);
+        assert_eq!(service.supervise_agents(later).await.unwrap(), 1);
+
+        let agent = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(agent.status, AgentState::Error);
+        assert!(agent
+            .error_message
+            .unwrap()
+            .contains("did not become ready"));
+        // The default policy never restarts
+        assert!(agent.restarts.is_empty());
     }
 
     #[tokio::test]
-    async fn create_agent_success() {
-        let (service, _dir, user_id) = setup();
+    async fn provisioning_deadline_ignores_unrelated_updates() {
+        let (service, _dir, caller) = setup();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("stuck"))
+            .await
+            .unwrap();
+        let started = agent.provisioning_started_at.unwrap();
+
+        // Touching the record (labels, heartbeats, ...) doesn't move the deadline
+        let mut touched = agent.clone();
+        touched.updated_at = started + chrono::Duration::minutes(9);
+        service.store.put_agent(&touched).unwrap();
+
+        let later = started + chrono::Duration::minutes(11);
+        assert_eq!(service.supervise_agents(later).await.unwrap(), 1);
+        let agent = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(agent.status, AgentState::Error);
+
+        // Re-entering Provisioning restarts the clock
+        service.start_agent(&caller, &agent.agent_id).await.unwrap();
+        let agent = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert!(agent.provisioning_started_at.unwrap() > started);
+    }
 
-        let request = CreateAgentRequest::new("test-agent");
-        let agent = service.create_agent(&user_id, request).await.unwrap();
+    #[tokio::test]
+    async fn on_failure_policy_restarts_with_backoff() {
+        let (service, _dir, caller) = setup();
+        let request = CreateAgentRequest {
+            restart_policy: RestartPolicy::OnFailure { max_attempts: 2 },
+            ..CreateAgentRequest::new("flaky")
+        };
+        let agent = service.create_agent(&caller, request).await.unwrap();
+        let agent_id = agent.agent_id;
+
+        let crash = || async {
+            service
+                .update_agent_status_internal(&agent_id, AgentState::Error, Some("crash".into()))
+                .await
+                .unwrap();
+        };
+        let get = || service.store.get_agent(&agent_id).unwrap().unwrap();
 
-        assert_eq!(agent.name, "test-agent");
-        assert_eq!(agent.user_id, user_id);
-        assert_eq!(agent.status, AgentState::Provisioning);
+        // First failure: restart scheduled after the base delay
+        crash().await;
+        let now = Utc::now();
+        assert_eq!(service.supervise_agents(now).await.unwrap(), 1);
+        assert_eq!(
+            get().restarts.next_restart_at,
+            Some(now + chrono::Duration::seconds(10))
+        );
+        assert_eq!(
+            service
+                .supervise_agents(now + chrono::Duration::seconds(5))
+                .await
+                .unwrap(),
+            0
+        );
+
+        let now = now + chrono::Duration::seconds(11);
+        service.supervise_agents(now).await.unwrap();
+        let restarted = get();
+        assert_eq!(restarted.status, AgentState::Provisioning);
+        assert_eq!(restarted.restarts.attempts, 1);
+        assert!(restarted.restarts.next_restart_at.is_none());
+        assert!(restarted.error_message.is_none());
+
+        // Second failure backs off twice as long
+        crash().await;
+        service.supervise_agents(now).await.unwrap();
+        assert_eq!(
+            get().restarts.next_restart_at,
+            Some(now + chrono::Duration::seconds(20))
+        );
+        service
+            .supervise_agents(now + chrono::Duration::seconds(21))
+            .await
+            .unwrap();
+        assert_eq!(get().restarts.attempts, 2);
+
+        // Out of attempts: the agent stays in Error
+        crash().await;
+        assert_eq!(service.supervise_agents(now).await.unwrap(), 0);
+        let exhausted = get();
+        assert_eq!(exhausted.status, AgentState::Error);
+        assert!(exhausted.restarts.next_restart_at.is_none());
+
+        // A manual start restores the restart budget
+        let started = service.start_agent(&caller, &agent_id).await.unwrap();
+        assert!(started.restarts.is_empty());
     }
 
     #[tokio::test]
-    async fn create_agent_quota_exceeded() {
-        let (service, _dir, user_id) = setup();
+    async fn restart_attempts_reset_after_stable_run() {
+        let (service, _dir, caller) = setup();
+        let mut agent = service
+            .create_agent(&caller, CreateAgentRequest::new("recovered"))
+            .await
+            .unwrap();
+        let restarted_at = Utc::now();
+        agent.status = AgentState::Running;
+        agent.restart_policy = RestartPolicy::OnFailure { max_attempts: 3 };
+        agent.restarts.attempts = 2;
+        agent.restarts.last_restart_at = Some(restarted_at);
+        service.store.put_agent(&agent).unwrap();
+
+        let soon = restarted_at + chrono::Duration::minutes(5);
+        assert_eq!(service.supervise_agents(soon).await.unwrap(), 0);
+
+        let later = restarted_at + chrono::Duration::minutes(11);
+        assert_eq!(service.supervise_agents(later).await.unwrap(), 1);
+        let agent = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert!(agent.restarts.is_empty());
+    }
 
-        // Create max agents
-        for i in 0..3 {
-            let request = CreateAgentRequest::new(format!("agent-{i}"));
-            service.create_agent(&user_id, request).await.unwrap();
+    #[tokio::test]
+    async fn invalid_restart_policy_rejected() {
+        let (service, _dir, caller) = setup();
+        for max_attempts in [0, 1000] {
+            let request = CreateAgentRequest {
+                restart_policy: RestartPolicy::OnFailure { max_attempts },
+                ..CreateAgentRequest::new(format!("agent-{max_attempts}"))
+            };
+            let result = service.create_agent(&caller, request).await;
+            assert!(matches!(result, Err(ControlError::InvalidRequest(_))));
         }
+    }
 
-        // Try to create one more
-        let request = CreateAgentRequest::new("agent-overflow");
-        let result = service.create_agent(&user_id, request).await;
+    #[tokio::test]
+    async fn system_actions_are_audited_and_pruned() {
+        let (service, _dir, caller) = setup();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("stuck"))
+            .await
+            .unwrap();
 
-        assert!(matches!(
-            result,
-            Err(ControlError::QuotaExceeded { limit: 3, .. })
-        ));
+        let later = Utc::now() + chrono::Duration::minutes(11);
+        service.supervise_agents(later).await.unwrap();
+
+        let events = service
+            .list_audit_events(AuditQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].event_type, "agent.provisioning_timeout");
+        assert_eq!(events[0].outcome, AuditOutcome::Failure);
+        assert!(events[0].actor.is_system());
+        assert_eq!(events[0].namespace_id, agent.namespace_id);
+
+        assert_eq!(service.prune_audit_log(Utc::now()).unwrap(), 0);
+        let expired = Utc::now() + chrono::Duration::days(91);
+        assert_eq!(service.prune_audit_log(expired).unwrap(), 1);
     }
 
     #[tokio::test]
-    async fn get_agent_not_owner() {
-        let (service, _dir, user_id) = setup();
-        let other_user = UserId::from_bytes([99u8; 32]);
+    async fn usage_is_metered_and_summarized() {
+        let (service, _dir, caller) = setup();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("metered"))
+            .await
+            .unwrap();
 
-        let request = CreateAgentRequest::new("test-agent");
-        let agent = service.create_agent(&user_id, request).await.unwrap();
+        let from = Utc::now();
+        let to = from + chrono::Duration::minutes(10);
+        assert!(service.meter_usage(from, to).unwrap() >= 1);
 
-        let result = service.get_agent(&other_user, &agent.agent_id).await;
-        assert!(matches!(result, Err(ControlError::NotOwner { .. })));
+        let query = UsageQuery {
+            from,
+            to,
+            namespace_id: Some(caller.namespace_id),
+            user_id: None,
+            group_by: vec![metering::UsageGroupBy::State],
+        };
+        let usage = service.get_usage(query.clone()).await.unwrap();
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].state, Some(AgentState::Provisioning));
+        assert_eq!(usage[0].agent_seconds, 600);
+        assert_eq!(
+            usage[0].cpu_millicore_seconds,
+            600 * u64::from(agent.spec.cpu_millicores)
+        );
+
+        let other = UsageQuery {
+            user_id: Some(UserId::from_bytes([9u8; 32])),
+            ..query.clone()
+        };
+        assert!(service.get_usage(other).await.unwrap().is_empty());
+
+        let backwards = UsageQuery {
+            from: to,
+            to: from,
+            ..query
+        };
+        assert!(matches!(
+            service.get_usage(backwards).await,
+            Err(ControlError::InvalidRequest(_))
+        ));
     }
 
     #[tokio::test]
-    async fn agent_lifecycle() {
-        let (service, _dir, user_id) = setup();
+    async fn agent_secrets_are_sealed_and_materialized_on_start() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let scheduler = Arc::new(FakeScheduler::default());
+        let cipher = SecretCipher::new(&[3u8; secrets::KEY_LEN]).unwrap();
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler.clone())
+                .with_secret_cipher(cipher);
+        let caller = caller();
+
+        let mut spec = AgentSpec::default();
+        spec.env
+            .insert("LOG_LEVEL".to_string(), "debug".to_string());
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::with_spec("secretive", spec))
+            .await
+            .unwrap();
+        let id = agent.agent_id;
 
-        // Create agent (starts in Provisioning)
-        let request = CreateAgentRequest::new("test-agent");
-        let agent = service.create_agent(&user_id, request).await.unwrap();
-        assert_eq!(agent.status, AgentState::Provisioning);
+        let secret = service
+            .set_agent_secret(&caller, &id, "GITHUB_TOKEN", "ghp_old")
+            .await
+            .unwrap();
+        assert!(!secret.ciphertext.windows(7).any(|w| w == b"ghp_old"));
+        assert!(matches!(
+            service
+                .set_agent_secret(&caller, &id, "AGENT_ID", "x")
+                .await,
+            Err(ControlError::InvalidRequest(_))
+        ));
+        // Secrets may not shadow the spec's environment variables
+        assert!(matches!(
+            service
+                .set_agent_secret(&caller, &id, "LOG_LEVEL", "x")
+                .await,
+            Err(ControlError::InvalidRequest(_))
+        ));
 
-        // Simulate provisioning complete (normally done by scheduler)
+        // Secrets only reach the cluster when the pod is next placed
+        assert!(scheduler.secrets.lock().unwrap().is_empty());
         service
             .store
-            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .update_agent_status(&id, AgentState::Stopped)
             .unwrap();
+        service.start_agent(&caller, &id).await.unwrap();
+        assert_eq!(
+            scheduler.secrets.lock().unwrap()[&id]["GITHUB_TOKEN"],
+            "ghp_old"
+        );
 
-        // Hibernate
-        let agent = service
-            .hibernate_agent(&user_id, &agent.agent_id)
+        // Rotated values take effect on restart
+        service
+            .set_agent_secret(&caller, &id, "GITHUB_TOKEN", "ghp_new")
             .await
             .unwrap();
-        assert_eq!(agent.status, AgentState::Hibernating);
-
-        // Wake (goes through Provisioning for scheduler)
-        let agent = service.wake_agent(&user_id, &agent.agent_id).await.unwrap();
-        assert_eq!(agent.status, AgentState::Provisioning);
-
-        // Simulate provisioning complete
         service
             .store
-            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .update_agent_status(&id, AgentState::Stopped)
             .unwrap();
+        service.start_agent(&caller, &id).await.unwrap();
+        assert_eq!(
+            scheduler.secrets.lock().unwrap()[&id]["GITHUB_TOKEN"],
+            "ghp_new"
+        );
 
-        // Stop
-        let agent = service.stop_agent(&user_id, &agent.agent_id).await.unwrap();
-        assert_eq!(agent.status, AgentState::Stopping);
+        let listed = service.list_agent_secrets(&caller, &id).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].created_at, secret.created_at);
 
-        // Simulate stop complete
         service
-            .store
-            .update_agent_status(&agent.agent_id, AgentState::Stopped)
+            .delete_agent_secret(&caller, &id, "GITHUB_TOKEN")
+            .await
             .unwrap();
+        assert!(matches!(
+            service
+                .delete_agent_secret(&caller, &id, "GITHUB_TOKEN")
+                .await,
+            Err(ControlError::SecretNotFound { .. })
+        ));
 
-        // Delete
         service
-            .delete_agent(&user_id, &agent.agent_id)
+            .set_agent_secret(&caller, &id, "OTHER", "value")
             .await
             .unwrap();
-        assert!(service.store.get_agent(&agent.agent_id).unwrap().is_none());
+        service
+            .store
+            .update_agent_status(&id, AgentState::Stopped)
+            .unwrap();
+        service.start_agent(&caller, &id).await.unwrap();
+        service
+            .store
+            .update_agent_status(&id, AgentState::Stopped)
+            .unwrap();
+        service.delete_agent(&caller, &id).await.unwrap();
+        assert!(scheduler.secrets.lock().unwrap().is_empty());
+        assert!(service.store.list_agent_secrets(&id).unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn delete_requires_stopped() {
-        let (service, _dir, user_id) = setup();
+    async fn delete_agent_runs_every_cleanup_step() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let scheduler = Arc::new(FakeScheduler::default());
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler.clone());
+        let caller = caller();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("doomed"))
+            .await
+            .unwrap();
+        let id = agent.agent_id;
+        service
+            .store
+            .update_agent_status(&id, AgentState::Running)
+            .unwrap();
+        service.create_session(&caller, &id).await.unwrap();
+        service
+            .store
+            .update_agent_status(&id, AgentState::Stopped)
+            .unwrap();
 
-        let request = CreateAgentRequest::new("test-agent");
-        let agent = service.create_agent(&user_id, request).await.unwrap();
+        let deletion = service.delete_agent(&caller, &id).await.unwrap();
+        assert_eq!(deletion.status, DeletionStatus::Completed);
+        assert_eq!(deletion.completed_steps, DeletionStep::ALL.to_vec());
+        assert_eq!(deletion.attempts, 1);
+        assert!(deletion.next_attempt_at.is_none());
 
-        // Simulate running
+        assert!(service.store.get_agent(&id).unwrap().is_none());
+        assert!(service
+            .store
+            .list_sessions_by_agent(&id)
+            .unwrap()
+            .is_empty());
+        assert_eq!(*scheduler.terminated.lock().unwrap(), vec![id]);
+        assert_eq!(*scheduler.state_deleted.lock().unwrap(), vec![id]);
+        assert_eq!(
+            *scheduler.network_policies_deleted.lock().unwrap(),
+            vec![id]
+        );
+
+        // The finished job stays visible to the owner but not to others
+        let visible = service.get_agent_deletion(&caller, &id).await.unwrap();
+        assert_eq!(visible.status, DeletionStatus::Completed);
+        let colleague = Caller::member(UserId::from_bytes([2u8; 32]), caller.namespace_id);
+        assert!(matches!(
+            service.get_agent_deletion(&colleague, &id).await,
+            Err(ControlError::DeletionNotFound(_))
+        ));
+
+        // ...until its retention period is over
+        assert_eq!(service.prune_agent_deletions(Utc::now()).unwrap(), 0);
+        let later = Utc::now() + chrono::Duration::hours(169);
+        assert_eq!(service.prune_agent_deletions(later).unwrap(), 1);
+        assert!(matches!(
+            service.get_agent_deletion(&caller, &id).await,
+            Err(ControlError::DeletionNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn failed_deletion_blocks_start_and_is_resumed() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let failing = Arc::new(FakeScheduler {
+            fail_delete_state: true,
+            ..FakeScheduler::default()
+        });
+        let service = ControlPlaneService::with_scheduler(
+            Arc::clone(&store),
+            ControlConfig::default(),
+            failing,
+        );
+        let caller = caller();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("stuck"))
+            .await
+            .unwrap();
+        let id = agent.agent_id;
         service
             .store
-            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .update_agent_status(&id, AgentState::Stopped)
             .unwrap();
 
-        // Try to delete while running
-        let result = service.delete_agent(&user_id, &agent.agent_id).await;
-        assert!(matches!(result, Err(ControlError::InvalidState { .. })));
+        let deletion = service.delete_agent(&caller, &id).await.unwrap();
+        assert_eq!(deletion.status, DeletionStatus::Failed);
+        assert_eq!(deletion.failed_step, Some(DeletionStep::DeleteState));
+        assert_eq!(
+            deletion.completed_steps,
+            vec![DeletionStep::PurgeSessions, DeletionStep::TerminatePod]
+        );
+        assert!(deletion.error.unwrap().contains("job timed out"));
+        let retry_at = deletion.next_attempt_at.unwrap();
+
+        // The record is kept until the job finishes, but the agent cannot start
+        assert!(service.store.get_agent(&id).unwrap().is_some());
+        assert!(matches!(
+            service.start_agent(&caller, &id).await,
+            Err(ControlError::AgentDeleting(_))
+        ));
+        // ...and cannot be cloned
+        assert!(matches!(
+            service
+                .clone_agent(&caller, &id, CloneAgentRequest::default())
+                .await,
+            Err(ControlError::AgentDeleting(_))
+        ));
+
+        // The worker leaves it alone until the retry is due, then resumes it
+        let scheduler = Arc::new(FakeScheduler::default());
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler.clone());
+        assert_eq!(service.resume_deletions(Utc::now()).await.unwrap(), 0);
+        assert_eq!(service.resume_deletions(retry_at).await.unwrap(), 1);
+
+        let deletion = service.get_agent_deletion(&caller, &id).await.unwrap();
+        assert_eq!(deletion.status, DeletionStatus::Completed);
+        assert_eq!(deletion.attempts, 2);
+        assert!(deletion.failed_step.is_none());
+        assert!(service.store.get_agent(&id).unwrap().is_none());
+        assert_eq!(*scheduler.state_deleted.lock().unwrap(), vec![id]);
+        // Steps that already finished are not repeated
+        assert!(scheduler.terminated.lock().unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn session_lifecycle() {
-        let (service, _dir, user_id) = setup();
-
-        // Create and start agent
-        let request = CreateAgentRequest::new("test-agent");
-        let agent = service.create_agent(&user_id, request).await.unwrap();
+    async fn clone_state_is_copied_in_the_background() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let scheduler = Arc::new(FakeScheduler::default());
+        let service =
+            ControlPlaneService::with_scheduler(store, ControlConfig::default(), scheduler.clone());
+        let caller = caller();
+        let source = service
+            .create_agent(&caller, CreateAgentRequest::new("s".repeat(60)))
+            .await
+            .unwrap();
         service
             .store
-            .update_agent_status(&agent.agent_id, AgentState::Running)
+            .update_agent_status(&source.agent_id, AgentState::Stopped)
             .unwrap();
+        scheduler.scheduled.lock().unwrap().clear();
 
-        // Create session
-        let session = service
-            .create_session(&user_id, &agent.agent_id)
+        let clone = service
+            .clone_agent(&caller, &source.agent_id, CloneAgentRequest::default())
             .await
             .unwrap();
-        assert_eq!(session.status, aura_swarm_store::SessionStatus::Active);
+        assert_eq!(clone.status, AgentState::Provisioning);
+        assert_eq!(clone.name.len(), crate::types::MAX_AGENT_NAME_LEN);
 
-        // Get session
-        let retrieved = service
-            .get_session(&user_id, &session.session_id)
+        // Nothing is copied or scheduled until the worker runs
+        assert!(scheduler.copied.lock().unwrap().is_empty());
+        assert!(scheduler.scheduled.lock().unwrap().is_empty());
+
+        // The source cannot change until its state has been copied
+        assert!(matches!(
+            service.start_agent(&caller, &source.agent_id).await,
+            Err(ControlError::StateCopyPending(_))
+        ));
+        assert!(matches!(
+            service.delete_agent(&caller, &source.agent_id).await,
+            Err(ControlError::StateCopyPending(_))
+        ));
+
+        assert_eq!(service.run_state_copies(Utc::now()).await.unwrap(), 1);
+        assert_eq!(
+            *scheduler.copied.lock().unwrap(),
+            vec![(source.agent_id, clone.agent_id)]
+        );
+        assert_eq!(*scheduler.scheduled.lock().unwrap(), vec![clone.agent_id]);
+        assert!(service.store.list_state_copies().unwrap().is_empty());
+        assert_eq!(service.run_state_copies(Utc::now()).await.unwrap(), 0);
+
+        service
+            .start_agent(&caller, &source.agent_id)
             .await
             .unwrap();
-        assert_eq!(retrieved.session_id, session.session_id);
+    }
 
-        // List sessions
-        let sessions = service
-            .list_sessions(&user_id, &agent.agent_id)
+    #[tokio::test]
+    async fn failed_clone_start_marks_the_clone_as_error() {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let service = ControlPlaneService::with_scheduler(
+            Arc::clone(&store),
+            ControlConfig::default(),
+            Arc::new(FakeScheduler::default()),
+        );
+        let caller = caller();
+        let source = service
+            .create_agent(&caller, CreateAgentRequest::new("source"))
             .await
             .unwrap();
-        assert_eq!(sessions.len(), 1);
-
-        // Close session
         service
-            .close_session(&user_id, &session.session_id)
+            .store
+            .update_agent_status(&source.agent_id, AgentState::Stopped)
+            .unwrap();
+        let clone = service
+            .clone_agent(&caller, &source.agent_id, CloneAgentRequest::named("copy"))
             .await
             .unwrap();
 
-        // Agent should transition to Idle
-        let agent = service.get_agent(&user_id, &agent.agent_id).await.unwrap();
-        assert_eq!(agent.status, AgentState::Idle);
+        let failing = Arc::new(FakeScheduler {
+            fail_schedule: true,
+            ..FakeScheduler::default()
+        });
+        let service = ControlPlaneService::with_scheduler(store, ControlConfig::default(), failing);
+        assert_eq!(service.run_state_copies(Utc::now()).await.unwrap(), 1);
+
+        let clone = service.store.get_agent(&clone.agent_id).unwrap().unwrap();
+        assert_eq!(clone.status, AgentState::Error);
+        assert!(clone.error_message.unwrap().contains("cluster full"));
+        assert!(service.store.list_state_copies().unwrap().is_empty());
     }
 
     #[tokio::test]
-    async fn heartbeat_updates_timestamp() {
-        let (service, _dir, user_id) = setup();
+    async fn agent_secrets_require_a_cipher_and_ownership() {
+        let (service, _dir, caller) = setup();
+        let agent = service
+            .create_agent(&caller, CreateAgentRequest::new("plain"))
+            .await
+            .unwrap();
 
-        let request = CreateAgentRequest::new("test-agent");
-        let agent = service.create_agent(&user_id, request).await.unwrap();
+        assert!(matches!(
+            service
+                .set_agent_secret(&caller, &agent.agent_id, "TOKEN", "value")
+                .await,
+            Err(ControlError::InvalidRequest(_))
+        ));
 
-        assert!(agent.last_heartbeat_at.is_none());
+        let service =
+            ControlPlaneService::new(Arc::clone(&service.store), ControlConfig::default())
+                .with_secret_cipher(SecretCipher::new(&[3u8; secrets::KEY_LEN]).unwrap());
+        let operator = Caller::member(UserId::from_bytes([5u8; 32]), caller.namespace_id);
+        service
+            .grant_agent_access(
+                &caller,
+                &agent.agent_id,
+                &operator.user_id,
+                AgentRole::Operator,
+            )
+            .await
+            .unwrap();
+        assert!(matches!(
+            service
+                .set_agent_secret(&operator, &agent.agent_id, "TOKEN", "value")
+                .await,
+            Err(ControlError::InsufficientRole { .. })
+        ));
+    }
 
-        service.process_heartbeat(&agent.agent_id).await.unwrap();
+    async fn rollout_service(
+        scheduler: FakeScheduler,
+        agents: usize,
+    ) -> (
+        ControlPlaneService<RocksStore, FakeScheduler>,
+        Arc<FakeScheduler>,
+        Vec<AgentId>,
+        TempDir,
+    ) {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let setup = ControlPlaneService::with_scheduler(
+            Arc::clone(&store),
+            ControlConfig::default(),
+            Arc::new(FakeScheduler::default()),
+        );
 
-        let updated = service.store.get_agent(&agent.agent_id).unwrap().unwrap();
-        assert!(updated.last_heartbeat_at.is_some());
+        let mut ids = Vec::new();
+        for i in 0..agents {
+            let mut request = CreateAgentRequest::new(format!("agent-{i}"));
+            request.spec = Some(AgentSpec {
+                runtime_version: "v1".to_string(),
+                ..AgentSpec::default()
+            });
+            let agent = setup.create_agent(&caller(), request).await.unwrap();
+            setup
+                .update_agent_status_internal(&agent.agent_id, AgentState::Running, None)
+                .await
+                .unwrap();
+            ids.push(agent.agent_id);
+        }
+
+        let scheduler = Arc::new(scheduler);
+        let service = ControlPlaneService::with_scheduler(
+            store,
+            ControlConfig::default(),
+            Arc::clone(&scheduler),
+        );
+
+        (service, scheduler, ids, dir)
+    }
+
+    fn rollout_request(strategy: aura_swarm_store::RolloutStrategy) -> CreateRolloutRequest {
+        CreateRolloutRequest {
+            target_version: "v2".to_string(),
+            strategy,
+            ..CreateRolloutRequest::default()
+        }
     }
 
     #[tokio::test]
-    async fn resolve_endpoint_active() {
-        let (service, _dir, user_id) = setup();
+    async fn rollout_restarts_agents_in_batches() {
+        let (service, scheduler, ids, _dir) = rollout_service(FakeScheduler::default(), 3).await;
+        let admin = IdentityId::from_uuid(uuid::Uuid::nil());
+        let strategy = aura_swarm_store::RolloutStrategy {
+            max_unavailable: 2,
+            pause_seconds: 60,
+            ..Default::default()
+        };
 
-        let request = CreateAgentRequest::new("test-agent");
-        let agent = service.create_agent(&user_id, request).await.unwrap();
-        service
-            .store
-            .update_agent_status(&agent.agent_id, AgentState::Running)
+        let rollout = service
+            .create_rollout(&admin, rollout_request(strategy.clone()))
+            .await
             .unwrap();
+        assert_eq!(rollout.targets.len(), 3);
+        assert!(matches!(
+            service
+                .create_rollout(&admin, rollout_request(strategy))
+                .await,
+            Err(ControlError::RolloutInProgress(_))
+        ));
 
-        let endpoint = service
-            .resolve_agent_endpoint(&agent.agent_id)
+        // First batch: two agents are restarted on the new version
+        let now = Utc::now();
+        assert_eq!(service.advance_rollouts(now).await.unwrap(), 1);
+        let rollout = service.get_rollout(&rollout.rollout_id).await.unwrap();
+        assert_eq!(rollout.count(RolloutTargetState::Updating), 2);
+        assert_eq!(*scheduler.scheduled.lock().unwrap(), ids[..2]);
+        let agent = service.store.get_agent(&ids[0]).unwrap().unwrap();
+        assert_eq!(agent.spec.runtime_version, "v2");
+        assert_eq!(agent.status, AgentState::Provisioning);
+
+        // Nothing moves until the restarted pods are ready
+        service.advance_rollouts(now).await.unwrap();
+        let rollout = service.get_rollout(&rollout.rollout_id).await.unwrap();
+        assert_eq!(rollout.count(RolloutTargetState::Updating), 2);
+
+        scheduler
+            .pods
+            .lock()
+            .unwrap()
+            .extend([fake_pod(ids[0]), fake_pod(ids[1])]);
+        service.advance_rollouts(now).await.unwrap();
+        let rollout = service.get_rollout(&rollout.rollout_id).await.unwrap();
+        assert_eq!(rollout.count(RolloutTargetState::Succeeded), 2);
+
+        // The next batch waits for the pause
+        assert!(rollout.next_batch_at.is_some());
+        service.advance_rollouts(now).await.unwrap();
+        let rollout = service.get_rollout(&rollout.rollout_id).await.unwrap();
+        assert_eq!(rollout.count(RolloutTargetState::Pending), 1);
+
+        let later = now + chrono::Duration::seconds(61);
+        service.advance_rollouts(later).await.unwrap();
+        scheduler.pods.lock().unwrap().push(fake_pod(ids[2]));
+        service.advance_rollouts(later).await.unwrap();
+        service.advance_rollouts(later).await.unwrap();
+
+        let rollout = service.get_rollout(&rollout.rollout_id).await.unwrap();
+        assert_eq!(rollout.status, RolloutStatus::Completed);
+        assert_eq!(rollout.count(RolloutTargetState::Succeeded), 3);
+        assert_eq!(service.advance_rollouts(later).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn rollout_halts_and_rolls_back_on_failure() {
+        let (service, _scheduler, ids, _dir) = rollout_service(
+            FakeScheduler {
+                fail_schedule: true,
+                ..FakeScheduler::default()
+            },
+            2,
+        )
+        .await;
+        let admin = IdentityId::from_uuid(uuid::Uuid::nil());
+        let strategy = aura_swarm_store::RolloutStrategy {
+            max_unavailable: 1,
+            rollback_on_failure: true,
+            ..Default::default()
+        };
+
+        let rollout = service
+            .create_rollout(&admin, rollout_request(strategy))
             .await
             .unwrap();
-        assert!(endpoint.is_some());
+
+        let now = Utc::now();
+        service.advance_rollouts(now).await.unwrap();
+        let agent = service.store.get_agent(&ids[0]).unwrap().unwrap();
+        assert_eq!(agent.status, AgentState::Error);
+
+        service.advance_rollouts(now).await.unwrap();
+        let rollout = service.get_rollout(&rollout.rollout_id).await.unwrap();
+        assert_eq!(rollout.status, RolloutStatus::RolledBack);
+        assert_eq!(rollout.targets[0].state, RolloutTargetState::RolledBack);
+        assert_eq!(rollout.targets[1].state, RolloutTargetState::Pending);
+        assert!(rollout.message.unwrap().contains("rolled back"));
+
+        for agent_id in &ids {
+            let agent = service.store.get_agent(agent_id).unwrap().unwrap();
+            assert_eq!(agent.spec.runtime_version, "v1");
+        }
+
+        // A rollout can only be rolled back once, and cancelled while in progress
+        assert!(service.rollback_rollout(&rollout.rollout_id).await.is_err());
+        assert!(service.cancel_rollout(&rollout.rollout_id).await.is_err());
     }
 
     #[tokio::test]
-    async fn resolve_endpoint_stopped() {
-        let (service, _dir, user_id) = setup();
+    async fn rollout_fails_agents_that_never_become_ready() {
+        let (service, _scheduler, _ids, _dir) = rollout_service(FakeScheduler::default(), 1).await;
+        let admin = IdentityId::from_uuid(uuid::Uuid::nil());
+        let strategy = aura_swarm_store::RolloutStrategy {
+            ready_timeout_seconds: 30,
+            ..Default::default()
+        };
 
-        let request = CreateAgentRequest::new("test-agent");
-        let agent = service.create_agent(&user_id, request).await.unwrap();
+        let rollout = service
+            .create_rollout(&admin, rollout_request(strategy))
+            .await
+            .unwrap();
+
+        let now = Utc::now();
+        service.advance_rollouts(now).await.unwrap();
         service
-            .store
-            .update_agent_status(&agent.agent_id, AgentState::Stopped)
+            .advance_rollouts(now + chrono::Duration::seconds(29))
+            .await
             .unwrap();
+        let rollout = service.get_rollout(&rollout.rollout_id).await.unwrap();
+        assert_eq!(rollout.targets[0].state, RolloutTargetState::Updating);
 
-        let endpoint = service
-            .resolve_agent_endpoint(&agent.agent_id)
+        // One failure exceeds the default budget of zero
+        service
+            .advance_rollouts(now + chrono::Duration::seconds(31))
             .await
             .unwrap();
-        assert!(endpoint.is_none());
+        let rollout = service.get_rollout(&rollout.rollout_id).await.unwrap();
+        assert_eq!(rollout.status, RolloutStatus::Halted);
+        assert_eq!(rollout.targets[0].state, RolloutTargetState::Failed);
+        assert!(rollout.targets[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("did not become ready"));
+
+        let rolled_back = service.rollback_rollout(&rollout.rollout_id).await.unwrap();
+        assert_eq!(rolled_back.status, RolloutStatus::RolledBack);
     }
 }
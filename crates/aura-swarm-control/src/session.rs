@@ -3,13 +3,23 @@
 //! This module provides session lifecycle operations including creation,
 //! retrieval, and closing of sessions. Sessions are the primary way users
 //! interact with their agents.
+//!
+//! Opening or closing a session requires the operator role on its agent;
+//! viewers may read and watch sessions.
+//!
+//! Sessions are bounded in number per agent, per user and per namespace, and
+//! expire after a period without traffic or once they exceed a maximum
+//! lifetime.
 
 use aura_swarm_core::{AgentId, SessionId, UserId};
-use aura_swarm_store::{Agent, AgentState, Session, SessionStatus, Store};
-use chrono::Utc;
+use aura_swarm_store::{Agent, AgentRole, AgentState, Session, SessionStatus, Store};
+use chrono::{DateTime, Utc};
 
+use crate::access;
 use crate::error::{ControlError, Result};
 use crate::lifecycle;
+use crate::namespace;
+use crate::types::{Caller, ControlConfig};
 
 /// Create a new session for an agent.
 ///
@@ -19,25 +29,23 @@ use crate::lifecycle;
 /// # Errors
 ///
 /// Returns an error if:
-/// - The agent is not found
-/// - The user is not the owner
+/// - The agent is not found or is in another namespace
+/// - The caller is not an operator of the agent
 /// - The agent is not in a state that can accept sessions
+/// - The agent, user or namespace already has the maximum number of active sessions
 pub fn create_session<S: Store>(
     store: &S,
-    user_id: &UserId,
+    config: &ControlConfig,
+    caller: &Caller,
     agent_id: &AgentId,
 ) -> Result<(Session, Option<AgentState>)> {
     let agent = store
         .get_agent(agent_id)?
         .ok_or(ControlError::AgentNotFound(*agent_id))?;
 
-    // Verify ownership
-    if agent.user_id != *user_id {
-        return Err(ControlError::NotOwner {
-            user_id: *user_id,
-            agent_id: *agent_id,
-        });
-    }
+    access::authorize(store, caller, &agent, AgentRole::Operator)?;
+
+    check_session_limits(store, config, &caller.user_id, &agent)?;
 
     // Determine if we need to wake the agent
     let state_change = determine_state_for_session(&agent)?;
@@ -48,13 +56,16 @@ pub fn create_session<S: Store>(
     }
 
     // Create the session
+    let now = Utc::now();
     let session = Session {
         session_id: SessionId::generate(),
         agent_id: *agent_id,
-        user_id: *user_id,
+        user_id: caller.user_id,
+        namespace_id: agent.namespace_id,
         status: SessionStatus::Active,
-        created_at: Utc::now(),
+        created_at: now,
         closed_at: None,
+        last_activity_at: Some(now),
     };
 
     store.put_session(&session)?;
@@ -62,6 +73,48 @@ pub fn create_session<S: Store>(
     Ok((session, state_change))
 }
 
+/// Ensure neither the agent, the user nor the namespace is at its active
+/// session limit.
+fn check_session_limits<S: Store>(
+    store: &S,
+    config: &ControlConfig,
+    user_id: &UserId,
+    agent: &Agent,
+) -> Result<()> {
+    let agent_sessions = count_active_sessions(store, &agent.agent_id)?;
+    if agent_sessions >= config.max_sessions_per_agent as usize {
+        return Err(ControlError::SessionLimitExceeded {
+            scope: "agent",
+            limit: config.max_sessions_per_agent,
+        });
+    }
+
+    // Sessions belong to the user who opened them, whoever owns the agent
+    let user_sessions = store
+        .list_active_sessions()?
+        .iter()
+        .filter(|s| s.user_id == *user_id)
+        .count();
+    if user_sessions >= config.max_sessions_per_user as usize {
+        return Err(ControlError::SessionLimitExceeded {
+            scope: "user",
+            limit: config.max_sessions_per_user,
+        });
+    }
+
+    if let Some(namespace_id) = &agent.namespace_id {
+        let limit = namespace::limits(store, config, namespace_id)?.max_sessions;
+        if namespace::count_active_sessions(store, namespace_id)? >= limit as usize {
+            return Err(ControlError::SessionLimitExceeded {
+                scope: "namespace",
+                limit,
+            });
+        }
+    }
+
+    Ok(())
+}
+
 /// Determine what state change (if any) is needed for a session to be created.
 fn determine_state_for_session(agent: &Agent) -> Result<Option<AgentState>> {
     match agent.status {
@@ -81,29 +134,38 @@ fn determine_state_for_session(agent: &Agent) -> Result<Option<AgentState>> {
     }
 }
 
-/// Get a session by ID, verifying ownership.
+/// Get a session by ID, verifying the caller may view its agent.
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - The session is not found
-/// - The user is not the owner
+/// - The session is not found or is in another namespace
+/// - The caller has no access to the session's agent
 pub fn get_session<S: Store>(
     store: &S,
-    user_id: &UserId,
+    caller: &Caller,
     session_id: &SessionId,
 ) -> Result<Session> {
-    let session = store
-        .get_session(session_id)?
-        .ok_or(ControlError::SessionNotFound(*session_id))?;
-
-    if session.user_id != *user_id {
-        return Err(ControlError::NotOwner {
-            user_id: *user_id,
-            agent_id: session.agent_id,
-        });
+    get_authorized_session(store, caller, session_id, AgentRole::Viewer)
+}
+
+/// Load a session and check the caller's role on its agent.
+fn get_authorized_session<S: Store>(
+    store: &S,
+    caller: &Caller,
+    session_id: &SessionId,
+    required: AgentRole,
+) -> Result<Session> {
+    let not_found = || ControlError::SessionNotFound(*session_id);
+
+    let session = store.get_session(session_id)?.ok_or_else(not_found)?;
+    let agent = store.get_agent(&session.agent_id)?.ok_or_else(not_found)?;
+    if !namespace::in_scope(caller, &agent) {
+        return Err(not_found());
     }
 
+    access::authorize(store, caller, &agent, required)?;
+
     Ok(session)
 }
 
@@ -115,21 +177,68 @@ pub fn get_session<S: Store>(
 /// # Errors
 ///
 /// Returns an error if:
-/// - The session is not found
-/// - The user is not the owner
-pub fn close_session<S: Store>(
-    store: &S,
-    user_id: &UserId,
-    session_id: &SessionId,
-) -> Result<bool> {
-    let session = get_session(store, user_id, session_id)?;
+/// - The session is not found or is in another namespace
+/// - The caller is not an operator of the session's agent
+pub fn close_session<S: Store>(store: &S, caller: &Caller, session_id: &SessionId) -> Result<bool> {
+    let session = get_authorized_session(store, caller, session_id, AgentRole::Operator)?;
 
     if session.status == SessionStatus::Closed {
         return Ok(false); // Already closed
     }
 
-    // Close the session
-    store.update_session_status(session_id, SessionStatus::Closed)?;
+    close_active_session(store, &session)?;
+    Ok(true)
+}
+
+/// Record traffic on a session.
+///
+/// Closed sessions are returned unchanged so callers (such as the WebSocket
+/// proxy) can detect that the session has ended.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The session is not found or is in another namespace
+/// - The caller has no access to the session's agent
+pub fn touch_session<S: Store>(
+    store: &S,
+    caller: &Caller,
+    session_id: &SessionId,
+    now: DateTime<Utc>,
+) -> Result<Session> {
+    let mut session = get_session(store, caller, session_id)?;
+
+    if session.status == SessionStatus::Active {
+        session.last_activity_at = Some(now);
+        store.put_session(&session)?;
+    }
+
+    Ok(session)
+}
+
+/// Why a session should be expired at `now`, if at all.
+#[must_use]
+pub fn expiry_reason(
+    session: &Session,
+    config: &ControlConfig,
+    now: DateTime<Utc>,
+) -> Option<&'static str> {
+    let seconds_since = |at: DateTime<Utc>| (now - at).num_seconds().max(0).unsigned_abs();
+
+    if seconds_since(session.created_at) >= config.session_max_lifetime_seconds {
+        Some("max lifetime exceeded")
+    } else if seconds_since(session.last_active_at()) >= config.session_idle_timeout_seconds {
+        Some("idle timeout")
+    } else {
+        None
+    }
+}
+
+/// Close an active session and idle its agent if no sessions remain.
+///
+/// Returns true if the agent was transitioned to Idle.
+pub(crate) fn close_active_session<S: Store>(store: &S, session: &Session) -> Result<bool> {
+    store.update_session_status(&session.session_id, SessionStatus::Closed)?;
 
     // Check if this was the last active session
     let active_sessions = count_active_sessions(store, &session.agent_id)?;
@@ -141,35 +250,31 @@ pub fn close_session<S: Store>(
                 && lifecycle::is_valid_transition(AgentState::Running, AgentState::Idle)
             {
                 store.update_agent_status(&session.agent_id, AgentState::Idle)?;
+                return Ok(true);
             }
         }
     }
 
-    Ok(true)
+    Ok(false)
 }
 
-/// List all sessions for an agent, verifying ownership.
+/// List all sessions for an agent, verifying the caller may access it.
 ///
 /// # Errors
 ///
 /// Returns an error if:
-/// - The agent is not found
-/// - The user is not the owner
+/// - The agent is not found or is in another namespace
+/// - The caller has no access to the agent
 pub fn list_sessions<S: Store>(
     store: &S,
-    user_id: &UserId,
+    caller: &Caller,
     agent_id: &AgentId,
 ) -> Result<Vec<Session>> {
     let agent = store
         .get_agent(agent_id)?
         .ok_or(ControlError::AgentNotFound(*agent_id))?;
 
-    if agent.user_id != *user_id {
-        return Err(ControlError::NotOwner {
-            user_id: *user_id,
-            agent_id: *agent_id,
-        });
-    }
+    access::authorize(store, caller, &agent, AgentRole::Viewer)?;
 
     Ok(store.list_sessions_by_agent(agent_id)?)
 }
@@ -186,16 +291,21 @@ pub(crate) fn count_active_sessions<S: Store>(store: &S, agent_id: &AgentId) ->
 #[cfg(test)]
 mod tests {
     use super::*;
-    use aura_swarm_store::{AgentSpec, RocksStore};
+    use aura_swarm_core::NamespaceId;
+    use aura_swarm_store::{AgentGrant, AgentSpec, RestartPolicy, RestartState, RocksStore};
     use tempfile::TempDir;
 
-    fn setup() -> (RocksStore, TempDir, UserId, Agent) {
+    fn setup() -> (RocksStore, TempDir, Caller, Agent) {
         let dir = TempDir::new().unwrap();
         let store = RocksStore::open(dir.path()).unwrap();
-        let user_id = UserId::from_bytes([1u8; 32]);
+        let caller = Caller::member(
+            UserId::from_bytes([1u8; 32]),
+            NamespaceId::from_uuid(uuid::Uuid::from_bytes([1u8; 16])),
+        );
         let agent = Agent {
-            agent_id: AgentId::generate_deterministic(&user_id, "test-agent", 42),
-            user_id,
+            agent_id: AgentId::generate_deterministic(&caller.user_id, "test-agent", 42),
+            user_id: caller.user_id,
+            namespace_id: Some(caller.namespace_id),
             name: "test-agent".to_string(),
             status: AgentState::Running,
             spec: AgentSpec::default(),
@@ -203,32 +313,46 @@ mod tests {
             updated_at: Utc::now(),
             last_heartbeat_at: None,
             error_message: None,
+            cloned_from: None,
+            template: None,
+            labels: std::collections::BTreeMap::new(),
+            restart_policy: RestartPolicy::Never,
+            restarts: RestartState::default(),
+            hibernation: None,
+            provisioning_started_at: None,
         };
         store.put_agent(&agent).unwrap();
-        (store, dir, user_id, agent)
+        (store, dir, caller, agent)
+    }
+
+    fn config() -> ControlConfig {
+        ControlConfig::default()
     }
 
     #[test]
     fn create_session_running_agent() {
-        let (store, _dir, user_id, agent) = setup();
+        let (store, _dir, caller, agent) = setup();
 
-        let (session, state_change) = create_session(&store, &user_id, &agent.agent_id).unwrap();
+        let (session, state_change) =
+            create_session(&store, &config(), &caller, &agent.agent_id).unwrap();
 
         assert_eq!(session.agent_id, agent.agent_id);
-        assert_eq!(session.user_id, user_id);
+        assert_eq!(session.user_id, caller.user_id);
+        assert_eq!(session.namespace_id, Some(caller.namespace_id));
         assert_eq!(session.status, SessionStatus::Active);
         assert!(state_change.is_none()); // No state change needed
     }
 
     #[test]
     fn create_session_idle_agent() {
-        let (store, _dir, user_id, mut agent) = setup();
+        let (store, _dir, caller, mut agent) = setup();
 
         // Set agent to Idle
         agent.status = AgentState::Idle;
         store.put_agent(&agent).unwrap();
 
-        let (session, state_change) = create_session(&store, &user_id, &agent.agent_id).unwrap();
+        let (session, state_change) =
+            create_session(&store, &config(), &caller, &agent.agent_id).unwrap();
 
         assert_eq!(session.status, SessionStatus::Active);
         assert_eq!(state_change, Some(AgentState::Running));
@@ -240,13 +364,14 @@ mod tests {
 
     #[test]
     fn create_session_hibernating_agent() {
-        let (store, _dir, user_id, mut agent) = setup();
+        let (store, _dir, caller, mut agent) = setup();
 
         // Set agent to Hibernating
         agent.status = AgentState::Hibernating;
         store.put_agent(&agent).unwrap();
 
-        let (session, state_change) = create_session(&store, &user_id, &agent.agent_id).unwrap();
+        let (session, state_change) =
+            create_session(&store, &config(), &caller, &agent.agent_id).unwrap();
 
         assert_eq!(session.status, SessionStatus::Active);
         assert_eq!(state_change, Some(AgentState::Running));
@@ -254,36 +379,36 @@ mod tests {
 
     #[test]
     fn create_session_not_owner() {
-        let (store, _dir, _user_id, agent) = setup();
-        let other_user = UserId::from_bytes([99u8; 32]);
+        let (store, _dir, caller, agent) = setup();
+        let other_user = Caller::member(UserId::from_bytes([99u8; 32]), caller.namespace_id);
 
-        let result = create_session(&store, &other_user, &agent.agent_id);
+        let result = create_session(&store, &config(), &other_user, &agent.agent_id);
 
         assert!(matches!(result, Err(ControlError::NotOwner { .. })));
     }
 
     #[test]
     fn create_session_not_runnable() {
-        let (store, _dir, user_id, mut agent) = setup();
+        let (store, _dir, caller, mut agent) = setup();
 
         // Set agent to Error state
         agent.status = AgentState::Error;
         store.put_agent(&agent).unwrap();
 
-        let result = create_session(&store, &user_id, &agent.agent_id);
+        let result = create_session(&store, &config(), &caller, &agent.agent_id);
 
         assert!(matches!(result, Err(ControlError::AgentNotRunnable(_))));
     }
 
     #[test]
     fn close_session_transitions_to_idle() {
-        let (store, _dir, user_id, agent) = setup();
+        let (store, _dir, caller, agent) = setup();
 
         // Create a session
-        let (session, _) = create_session(&store, &user_id, &agent.agent_id).unwrap();
+        let (session, _) = create_session(&store, &config(), &caller, &agent.agent_id).unwrap();
 
         // Close it
-        let closed = close_session(&store, &user_id, &session.session_id).unwrap();
+        let closed = close_session(&store, &caller, &session.session_id).unwrap();
         assert!(closed);
 
         // Verify session is closed
@@ -295,13 +420,141 @@ mod tests {
         assert_eq!(updated_agent.status, AgentState::Idle);
     }
 
+    #[test]
+    fn create_session_enforces_limits() {
+        let (store, _dir, caller, agent) = setup();
+        let config = ControlConfig {
+            max_sessions_per_agent: 2,
+            max_sessions_per_user: 3,
+            ..ControlConfig::default()
+        };
+
+        create_session(&store, &config, &caller, &agent.agent_id).unwrap();
+        let (second, _) = create_session(&store, &config, &caller, &agent.agent_id).unwrap();
+
+        let result = create_session(&store, &config, &caller, &agent.agent_id);
+        assert!(matches!(
+            result,
+            Err(ControlError::SessionLimitExceeded { scope: "agent", .. })
+        ));
+
+        // Closing a session frees a slot
+        close_session(&store, &caller, &second.session_id).unwrap();
+        create_session(&store, &config, &caller, &agent.agent_id).unwrap();
+
+        // The user limit spans agents
+        let mut other = agent.clone();
+        other.agent_id = AgentId::generate_deterministic(&caller.user_id, "other-agent", 7);
+        other.name = "other-agent".to_string();
+        store.put_agent(&other).unwrap();
+
+        create_session(&store, &config, &caller, &other.agent_id).unwrap();
+        let result = create_session(&store, &config, &caller, &other.agent_id);
+        assert!(matches!(
+            result,
+            Err(ControlError::SessionLimitExceeded { scope: "user", .. })
+        ));
+    }
+
+    #[test]
+    fn user_limit_counts_sessions_on_shared_agents() {
+        let (store, _dir, owner, agent) = setup();
+        let config = ControlConfig {
+            max_sessions_per_user: 2,
+            ..ControlConfig::default()
+        };
+
+        let operator = Caller::member(UserId::from_bytes([2u8; 32]), owner.namespace_id);
+        store
+            .put_agent_grant(&AgentGrant {
+                agent_id: agent.agent_id,
+                user_id: operator.user_id,
+                role: AgentRole::Operator,
+                granted_by: owner.user_id,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .unwrap();
+
+        // Sessions the operator opens on the shared agent count against them
+        create_session(&store, &config, &operator, &agent.agent_id).unwrap();
+        create_session(&store, &config, &operator, &agent.agent_id).unwrap();
+        let result = create_session(&store, &config, &operator, &agent.agent_id);
+        assert!(matches!(
+            result,
+            Err(ControlError::SessionLimitExceeded { scope: "user", .. })
+        ));
+
+        // ...and not against the owner
+        create_session(&store, &config, &owner, &agent.agent_id).unwrap();
+    }
+
+    #[test]
+    fn session_expiry() {
+        let (store, _dir, caller, agent) = setup();
+        let config = config();
+
+        let (session, _) = create_session(&store, &config, &caller, &agent.agent_id).unwrap();
+        let start = session.created_at;
+        assert_eq!(expiry_reason(&session, &config, start), None);
+
+        // Idle past the timeout
+        let idle = start + chrono::Duration::seconds(1800);
+        assert_eq!(expiry_reason(&session, &config, idle), Some("idle timeout"));
+
+        // Activity resets the idle clock
+        let touched = touch_session(
+            &store,
+            &caller,
+            &session.session_id,
+            start + chrono::Duration::seconds(1000),
+        )
+        .unwrap();
+        assert_eq!(expiry_reason(&touched, &config, idle), None);
+
+        // Lifetime applies regardless of activity
+        let old = start + chrono::Duration::seconds(86_400);
+        let touched = touch_session(&store, &caller, &session.session_id, old).unwrap();
+        assert_eq!(
+            expiry_reason(&touched, &config, old),
+            Some("max lifetime exceeded")
+        );
+    }
+
     #[test]
     fn list_sessions_verifies_ownership() {
-        let (store, _dir, _user_id, agent) = setup();
-        let other_user = UserId::from_bytes([99u8; 32]);
+        let (store, _dir, caller, agent) = setup();
+        let other_user = Caller::member(UserId::from_bytes([99u8; 32]), caller.namespace_id);
 
         let result = list_sessions(&store, &other_user, &agent.agent_id);
 
         assert!(matches!(result, Err(ControlError::NotOwner { .. })));
     }
+
+    #[test]
+    fn create_session_enforces_namespace_limit() {
+        let (store, _dir, caller, agent) = setup();
+        let config = ControlConfig {
+            max_sessions_per_namespace: 1,
+            ..ControlConfig::default()
+        };
+
+        create_session(&store, &config, &caller, &agent.agent_id).unwrap();
+
+        // A colleague's agent in the same namespace shares the limit
+        let colleague = Caller::member(UserId::from_bytes([2u8; 32]), caller.namespace_id);
+        let mut other = agent.clone();
+        other.agent_id = AgentId::generate_deterministic(&colleague.user_id, "theirs", 7);
+        other.user_id = colleague.user_id;
+        store.put_agent(&other).unwrap();
+
+        let result = create_session(&store, &config, &colleague, &other.agent_id);
+        assert!(matches!(
+            result,
+            Err(ControlError::SessionLimitExceeded {
+                scope: "namespace",
+                limit: 1
+            })
+        ));
+    }
 }
@@ -2,17 +2,93 @@
 //!
 //! These types define the API contracts for agent and session management.
 
-use aura_swarm_store::AgentSpec;
+use std::collections::BTreeMap;
+
+use aura_swarm_core::{NamespaceId, UserId};
+use aura_swarm_store::{
+    Agent, AgentPlacement, AgentRole, AgentSpec, EgressPolicy, IsolationLevel, MissedRunPolicy,
+    RestartPolicy, RolloutSelector, RolloutStrategy, ScheduleRule, Webhook, WebhookEvent,
+};
 use serde::{Deserialize, Serialize};
 
+/// The user on whose behalf an agent or session operation runs.
+///
+/// Agents and sessions are scoped to the caller's namespace (tenant).
+/// Namespace admins may additionally manage every agent in their namespace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Caller {
+    /// The calling user.
+    pub user_id: UserId,
+    /// The namespace the caller is acting in.
+    pub namespace_id: NamespaceId,
+    /// The caller's role within the namespace.
+    pub role: NamespaceRole,
+}
+
+impl Caller {
+    /// Create a caller with the `Member` role.
+    #[must_use]
+    pub const fn member(user_id: UserId, namespace_id: NamespaceId) -> Self {
+        Self {
+            user_id,
+            namespace_id,
+            role: NamespaceRole::Member,
+        }
+    }
+
+    /// Create a caller with the `Admin` role.
+    #[must_use]
+    pub const fn namespace_admin(user_id: UserId, namespace_id: NamespaceId) -> Self {
+        Self {
+            user_id,
+            namespace_id,
+            role: NamespaceRole::Admin,
+        }
+    }
+
+    /// Returns true if the caller administers their namespace.
+    #[must_use]
+    pub const fn is_namespace_admin(&self) -> bool {
+        matches!(self.role, NamespaceRole::Admin)
+    }
+}
+
+/// A caller's role within their namespace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NamespaceRole {
+    /// Can manage their own agents.
+    #[default]
+    Member,
+    /// Can list and manage every agent in the namespace.
+    Admin,
+}
+
 /// Request to create a new agent.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// The base spec comes from either `spec` or a named `template`, never both.
+/// `overrides` and `labels` are applied on top of whichever base is used.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CreateAgentRequest {
     /// Human-readable name for the agent.
     pub name: String,
     /// Optional resource specification. Uses defaults if not provided.
     #[serde(default)]
     pub spec: Option<AgentSpec>,
+    /// Name of a template to create the agent from.
+    ///
+    /// User-defined templates take precedence over admin-defined ones.
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Per-field overrides applied to the base spec.
+    #[serde(default)]
+    pub overrides: AgentSpecOverrides,
+    /// Labels for the agent, merged over any template labels.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Automatic restart policy (default: never).
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
 }
 
 impl CreateAgentRequest {
@@ -21,7 +97,7 @@ impl CreateAgentRequest {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
-            spec: None,
+            ..Default::default()
         }
     }
 
@@ -31,6 +107,239 @@ impl CreateAgentRequest {
         Self {
             name: name.into(),
             spec: Some(spec),
+            ..Default::default()
+        }
+    }
+
+    /// Create a new request based on a named template.
+    #[must_use]
+    pub fn from_template(name: impl Into<String>, template: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            template: Some(template.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the per-field overrides for this request.
+    #[must_use]
+    pub fn with_overrides(mut self, overrides: AgentSpecOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+}
+
+/// Per-field overrides for an [`AgentSpec`].
+///
+/// Unset fields keep the base value; `env` entries are merged into the base.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AgentSpecOverrides {
+    /// CPU allocation in millicores.
+    #[serde(default)]
+    pub cpu_millicores: Option<u32>,
+    /// Memory allocation in megabytes.
+    #[serde(default)]
+    pub memory_mb: Option<u32>,
+    /// Aura runtime version.
+    #[serde(default)]
+    pub runtime_version: Option<String>,
+    /// Isolation level for the agent runtime.
+    #[serde(default)]
+    pub isolation: Option<IsolationLevel>,
+    /// Additional environment variables.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    /// Initial system prompt.
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+    /// Size of the state volume in gigabytes.
+    #[serde(default)]
+    pub storage_gb: Option<u32>,
+    /// Storage class for the state volume.
+    #[serde(default)]
+    pub storage_class: Option<String>,
+    /// Outbound network access policy.
+    #[serde(default)]
+    pub egress: Option<EgressPolicy>,
+    /// Node placement.
+    #[serde(default)]
+    pub placement: Option<AgentPlacement>,
+}
+
+impl AgentSpecOverrides {
+    /// Apply these overrides to a base spec.
+    #[must_use]
+    pub fn apply(self, mut spec: AgentSpec) -> AgentSpec {
+        if let Some(cpu) = self.cpu_millicores {
+            spec.cpu_millicores = cpu;
+        }
+        if let Some(memory) = self.memory_mb {
+            spec.memory_mb = memory;
+        }
+        if let Some(version) = self.runtime_version {
+            spec.runtime_version = version;
+        }
+        if let Some(isolation) = self.isolation {
+            spec.isolation = Some(isolation);
+        }
+        if let Some(prompt) = self.system_prompt {
+            spec.system_prompt = Some(prompt);
+        }
+        if let Some(storage_gb) = self.storage_gb {
+            spec.storage_gb = Some(storage_gb);
+        }
+        if let Some(class) = self.storage_class {
+            spec.storage_class = Some(class);
+        }
+        if let Some(egress) = self.egress {
+            spec.egress = Some(egress);
+        }
+        if let Some(placement) = self.placement {
+            spec.placement = Some(placement);
+        }
+        spec.env.extend(self.env);
+        spec
+    }
+}
+
+/// Request to create an agent template.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateTemplateRequest {
+    /// Template name, unique within its scope.
+    pub name: String,
+    /// Human-readable description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Spec applied to agents created from the template. Uses defaults if not provided.
+    #[serde(default)]
+    pub spec: Option<AgentSpec>,
+    /// Labels applied to agents created from the template.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+impl CreateTemplateRequest {
+    /// Create a new template request with the given name and spec.
+    #[must_use]
+    pub fn new(name: impl Into<String>, spec: AgentSpec) -> Self {
+        Self {
+            name: name.into(),
+            spec: Some(spec),
+            ..Default::default()
+        }
+    }
+}
+
+/// Maximum length of an agent name, in bytes.
+pub const MAX_AGENT_NAME_LEN: usize = 64;
+
+/// Request to clone an existing agent, including its state directory.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CloneAgentRequest {
+    /// Name for the new agent. Defaults to `"{source name}-clone"`.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl CloneAgentRequest {
+    /// Suffix appended to the source's name to name a clone.
+    const DEFAULT_SUFFIX: &'static str = "-clone";
+
+    /// Create a clone request with an explicit name for the new agent.
+    #[must_use]
+    pub fn named(name: impl Into<String>) -> Self {
+        Self {
+            name: Some(name.into()),
+        }
+    }
+
+    /// The default name for a clone of `source_name`.
+    ///
+    /// The source's name is shortened as needed to keep the result within
+    /// [`MAX_AGENT_NAME_LEN`].
+    #[must_use]
+    pub fn default_name(source_name: &str) -> String {
+        let mut end = source_name
+            .len()
+            .min(MAX_AGENT_NAME_LEN - Self::DEFAULT_SUFFIX.len());
+        while !source_name.is_char_boundary(end) {
+            end -= 1;
+        }
+        format!("{}{}", &source_name[..end], Self::DEFAULT_SUFFIX)
+    }
+}
+
+/// Request to register a webhook endpoint.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateWebhookRequest {
+    /// Endpoint URL that receives deliveries.
+    pub url: String,
+    /// Events to deliver. Empty means all events.
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    /// Human-readable description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Signing secret. A random secret is generated if not provided.
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+impl CreateWebhookRequest {
+    /// Create a request for all events with a generated secret.
+    #[must_use]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A newly registered webhook together with its signing secret.
+///
+/// The secret is stored sealed and only returned here, at creation.
+#[derive(Debug, Clone)]
+pub struct CreatedWebhook {
+    /// The registered webhook.
+    pub webhook: Webhook,
+    /// Signing secret in plain text.
+    pub secret: String,
+}
+
+/// Request to set (create or replace) an agent's wake/hibernate schedule.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SetScheduleRequest {
+    /// IANA time zone the rules are evaluated in.
+    #[serde(default = "SetScheduleRequest::default_timezone")]
+    pub timezone: String,
+    /// Cron rules, each triggering a lifecycle action.
+    pub rules: Vec<ScheduleRule>,
+    /// Whether the schedule is active.
+    #[serde(default = "SetScheduleRequest::default_enabled")]
+    pub enabled: bool,
+    /// Handling of runs missed during control-plane downtime.
+    #[serde(default)]
+    pub missed_runs: MissedRunPolicy,
+}
+
+impl SetScheduleRequest {
+    fn default_timezone() -> String {
+        "UTC".to_string()
+    }
+
+    const fn default_enabled() -> bool {
+        true
+    }
+
+    /// Create an enabled request with the given time zone and rules.
+    #[must_use]
+    pub fn new(timezone: impl Into<String>, rules: Vec<ScheduleRule>) -> Self {
+        Self {
+            timezone: timezone.into(),
+            rules,
+            enabled: true,
+            missed_runs: MissedRunPolicy::default(),
         }
     }
 }
@@ -91,12 +400,64 @@ impl LogOptions {
     }
 }
 
+/// Request to set a namespace's quota overrides.
+///
+/// Unset limits fall back to the configured defaults.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SetNamespaceQuotaRequest {
+    /// Maximum number of agents in the namespace.
+    #[serde(default)]
+    pub max_agents: Option<u32>,
+    /// Maximum number of active sessions across the namespace.
+    #[serde(default)]
+    pub max_sessions: Option<u32>,
+}
+
+/// Request to start a fleet-wide runtime version rollout.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CreateRolloutRequest {
+    /// Runtime version to move agents to.
+    pub target_version: String,
+    /// Which agents to update. Empty matches every agent.
+    #[serde(default)]
+    pub selector: RolloutSelector,
+    /// Batch size, pacing and failure handling.
+    #[serde(default)]
+    pub strategy: RolloutStrategy,
+}
+
+/// Current usage and effective limits of a namespace.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceUsage {
+    /// The namespace.
+    pub namespace_id: NamespaceId,
+    /// Number of agents in the namespace.
+    pub agents: u32,
+    /// Maximum number of agents allowed.
+    pub max_agents: u32,
+    /// Number of active sessions across the namespace.
+    pub active_sessions: u32,
+    /// Maximum number of active sessions allowed.
+    pub max_sessions: u32,
+}
+
+/// An agent shared with the caller, with the role they hold on it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SharedAgent {
+    /// The shared agent.
+    pub agent: Agent,
+    /// The caller's role on the agent.
+    pub role: AgentRole,
+    /// User who shared the agent.
+    pub granted_by: UserId,
+}
+
 /// Agent status information returned from status queries.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AgentStatus {
     /// Whether the agent is healthy.
     pub healthy: bool,
-    /// Current CPU usage (0.0 - 1.0).
+    /// Current CPU usage as a fraction of the agent's CPU allocation (0.0 - 1.0).
     pub cpu_usage: f64,
     /// Current memory usage in bytes.
     pub memory_bytes: u64,
@@ -111,6 +472,8 @@ pub struct AgentStatus {
 pub struct ControlConfig {
     /// Maximum number of agents per user.
     pub max_agents_per_user: u32,
+    /// Default maximum number of agents per namespace.
+    pub max_agents_per_namespace: u32,
     /// How long an agent can be idle before transitioning to Idle state (seconds).
     pub idle_timeout_seconds: u64,
     /// How long an Idle agent waits before auto-hibernating (seconds).
@@ -119,20 +482,207 @@ pub struct ControlConfig {
     pub heartbeat_interval_seconds: u64,
     /// How long without heartbeat before marking agent as Error (seconds).
     pub heartbeat_timeout_seconds: u64,
+    /// Maximum CPU an agent may request, in millicores (should match the scheduler).
+    pub max_cpu_millicores: u32,
+    /// Maximum memory an agent may request, in megabytes (should match the scheduler).
+    pub max_memory_mb: u32,
+    /// Maximum state volume size an agent may request, in gigabytes (should match the scheduler).
+    pub max_storage_gb: u32,
+    /// Maximum number of user-defined templates per user.
+    pub max_templates_per_user: u32,
+    /// Maximum number of labels on an agent or template.
+    pub max_labels: u32,
+    /// Maximum number of environment variables in an agent spec.
+    pub max_env_vars: u32,
+    /// Maximum length of an agent's system prompt, in bytes.
+    pub max_system_prompt_bytes: u32,
+    /// How often agent schedules are evaluated (seconds).
+    pub schedule_interval_seconds: u64,
+    /// How late a run may be processed under `MissedRunPolicy::Skip` (seconds).
+    pub schedule_grace_seconds: u64,
+    /// Maximum concurrent active sessions per agent.
+    pub max_sessions_per_agent: u32,
+    /// Maximum concurrent active sessions per user, across all agents.
+    pub max_sessions_per_user: u32,
+    /// Default maximum concurrent active sessions per namespace.
+    pub max_sessions_per_namespace: u32,
+    /// How long a session may go without traffic before it is closed (seconds).
+    pub session_idle_timeout_seconds: u64,
+    /// Maximum lifetime of a session regardless of activity (seconds).
+    pub session_max_lifetime_seconds: u64,
+    /// How often expired sessions are reaped (seconds).
+    pub session_reap_interval_seconds: u64,
+    /// Maximum number of webhooks per user.
+    pub max_webhooks_per_user: u32,
+    /// How often the webhook outbox is checked for due deliveries (seconds).
+    pub webhook_dispatch_interval_seconds: u64,
+    /// Timeout for a single webhook delivery attempt (seconds).
+    pub webhook_timeout_seconds: u64,
+    /// Attempts made before a delivery is marked as failed.
+    pub webhook_max_attempts: u32,
+    /// Delay before the first retry; doubled on each further retry (seconds).
+    pub webhook_backoff_base_seconds: u64,
+    /// Upper bound on the delay between retries (seconds).
+    pub webhook_backoff_max_seconds: u64,
+    /// How long finished deliveries are kept in the delivery log (hours).
+    pub webhook_retention_hours: u64,
+    /// Allow webhook URLs that resolve to loopback, private or link-local
+    /// addresses (for local development only).
+    pub webhook_allow_private_targets: bool,
+    /// How often the store is reconciled against the scheduler's pods (seconds).
+    pub drift_interval_seconds: u64,
+    /// How long an agent or pod must be unchanged before drift is corrected (seconds).
+    pub drift_grace_seconds: u64,
+    /// How long an agent may stay in `Provisioning` before it is marked `Error` (seconds).
+    pub provisioning_timeout_seconds: u64,
+    /// Upper bound for a restart policy's `max_attempts`.
+    pub max_restart_attempts: u32,
+    /// Delay before the first automatic restart; doubles per attempt (seconds).
+    pub restart_backoff_base_seconds: u64,
+    /// Maximum delay between automatic restarts (seconds).
+    pub restart_backoff_max_seconds: u64,
+    /// How long a restarted agent must stay up before its attempt count resets (seconds).
+    pub restart_reset_seconds: u64,
+    /// How often provisioning deadlines and restart policies are evaluated (seconds).
+    pub restart_check_interval_seconds: u64,
+    /// How long audit events are kept (days).
+    pub audit_retention_days: u64,
+    /// How often expired audit events are pruned (seconds).
+    pub audit_prune_interval_seconds: u64,
+    /// How often agent runtime is metered into the hourly usage buckets (seconds).
+    pub metering_interval_seconds: u64,
+    /// How long the runtime may take to flush its state on hibernate (seconds).
+    pub hibernate_timeout_seconds: u64,
+    /// Maximum number of secrets per agent.
+    pub max_secrets_per_agent: u32,
+    /// How often in-progress rollouts are advanced (seconds).
+    pub rollout_interval_seconds: u64,
+    /// How long to wait before retrying a failed agent deletion (seconds).
+    pub deletion_retry_seconds: u64,
+    /// How long completed agent deletions are kept (hours).
+    pub deletion_retention_hours: u64,
+    /// How often pending clone state copies are picked up (seconds).
+    pub state_copy_interval_seconds: u64,
 }
 
 impl Default for ControlConfig {
     fn default() -> Self {
         Self {
             max_agents_per_user: 10,
+            max_agents_per_namespace: 100,
             idle_timeout_seconds: 300,          // 5 minutes
             hibernate_after_idle_seconds: 1800, // 30 minutes
             heartbeat_interval_seconds: 30,
             heartbeat_timeout_seconds: 90,
+            max_cpu_millicores: 4000,
+            max_memory_mb: 8192,
+            max_storage_gb: 100,
+            max_templates_per_user: 50,
+            max_labels: 32,
+            max_env_vars: 64,
+            max_system_prompt_bytes: 32 * 1024,
+            schedule_interval_seconds: 30,
+            schedule_grace_seconds: 300, // 5 minutes
+            max_sessions_per_agent: 5,
+            max_sessions_per_user: 20,
+            max_sessions_per_namespace: 200,
+            session_idle_timeout_seconds: 1800,   // 30 minutes
+            session_max_lifetime_seconds: 86_400, // 24 hours
+            session_reap_interval_seconds: 60,
+            max_webhooks_per_user: 10,
+            webhook_dispatch_interval_seconds: 5,
+            webhook_timeout_seconds: 10,
+            webhook_max_attempts: 8,
+            webhook_backoff_base_seconds: 10,
+            webhook_backoff_max_seconds: 3600, // 1 hour
+            webhook_retention_hours: 168,      // 7 days
+            webhook_allow_private_targets: false,
+            drift_interval_seconds: 120,
+            drift_grace_seconds: 180,
+            provisioning_timeout_seconds: 600, // 10 minutes
+            max_restart_attempts: 20,
+            restart_backoff_base_seconds: 10,
+            restart_backoff_max_seconds: 900, // 15 minutes
+            restart_reset_seconds: 600,       // 10 minutes
+            restart_check_interval_seconds: 15,
+            audit_retention_days: 90,
+            audit_prune_interval_seconds: 3600, // 1 hour
+            metering_interval_seconds: 60,
+            hibernate_timeout_seconds: 20,
+            max_secrets_per_agent: 50,
+            rollout_interval_seconds: 10,
+            deletion_retry_seconds: 60,
+            deletion_retention_hours: 168, // 7 days
+            state_copy_interval_seconds: 2,
         }
     }
 }
 
+impl ControlConfig {
+    /// Create configuration from environment variables, falling back to the
+    /// defaults for anything unset or unparseable.
+    ///
+    /// Environment variables:
+    /// - `MAX_AGENTS_PER_USER`: Maximum number of agents per user
+    /// - `MAX_AGENTS_PER_NAMESPACE`: Default maximum number of agents per namespace
+    /// - `MAX_CPU_MILLICORES`: Maximum CPU an agent may request
+    /// - `MAX_MEMORY_MB`: Maximum memory an agent may request
+    /// - `MAX_STORAGE_GB`: Maximum state volume size an agent may request
+    /// - `MAX_TEMPLATES_PER_USER`: Maximum number of user-defined templates per user
+    /// - `MAX_LABELS`: Maximum number of labels on an agent or template
+    /// - `MAX_ENV_VARS`: Maximum number of environment variables in an agent spec
+    /// - `MAX_SYSTEM_PROMPT_BYTES`: Maximum length of an agent's system prompt
+    /// - `MAX_SESSIONS_PER_AGENT`: Maximum concurrent sessions per agent
+    /// - `MAX_SESSIONS_PER_USER`: Maximum concurrent sessions per user
+    /// - `MAX_SESSIONS_PER_NAMESPACE`: Default maximum concurrent sessions per namespace
+    /// - `MAX_WEBHOOKS_PER_USER`: Maximum number of webhooks per user
+    /// - `MAX_SECRETS_PER_AGENT`: Maximum number of secrets per agent
+    /// - `MAX_RESTART_ATTEMPTS`: Upper bound for a restart policy's `max_attempts`
+    /// - `WEBHOOK_ALLOW_PRIVATE_TARGETS`: Allow webhooks to non-public addresses (`true`/`false`)
+    #[must_use]
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        env_override("MAX_AGENTS_PER_USER", &mut config.max_agents_per_user);
+        env_override(
+            "MAX_AGENTS_PER_NAMESPACE",
+            &mut config.max_agents_per_namespace,
+        );
+        env_override("MAX_CPU_MILLICORES", &mut config.max_cpu_millicores);
+        env_override("MAX_MEMORY_MB", &mut config.max_memory_mb);
+        env_override("MAX_STORAGE_GB", &mut config.max_storage_gb);
+        env_override("MAX_TEMPLATES_PER_USER", &mut config.max_templates_per_user);
+        env_override("MAX_LABELS", &mut config.max_labels);
+        env_override("MAX_ENV_VARS", &mut config.max_env_vars);
+        env_override(
+            "MAX_SYSTEM_PROMPT_BYTES",
+            &mut config.max_system_prompt_bytes,
+        );
+        env_override("MAX_SESSIONS_PER_AGENT", &mut config.max_sessions_per_agent);
+        env_override("MAX_SESSIONS_PER_USER", &mut config.max_sessions_per_user);
+        env_override(
+            "MAX_SESSIONS_PER_NAMESPACE",
+            &mut config.max_sessions_per_namespace,
+        );
+        env_override("MAX_WEBHOOKS_PER_USER", &mut config.max_webhooks_per_user);
+        env_override("MAX_SECRETS_PER_AGENT", &mut config.max_secrets_per_agent);
+        env_override("MAX_RESTART_ATTEMPTS", &mut config.max_restart_attempts);
+        env_override(
+            "WEBHOOK_ALLOW_PRIVATE_TARGETS",
+            &mut config.webhook_allow_private_targets,
+        );
+
+        config
+    }
+}
+
+/// Overwrite `value` with the named environment variable if it is set and parses.
+fn env_override<T: std::str::FromStr>(name: &str, value: &mut T) {
+    if let Some(parsed) = std::env::var(name).ok().and_then(|v| v.parse().ok()) {
+        *value = parsed;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,13 +700,60 @@ mod tests {
             cpu_millicores: 1000,
             memory_mb: 1024,
             runtime_version: "v1.0.0".to_string(),
-            isolation: None,
+            ..Default::default()
         };
         let req = CreateAgentRequest::with_spec("my-agent", spec.clone());
         assert_eq!(req.name, "my-agent");
         assert_eq!(req.spec.unwrap().cpu_millicores, 1000);
     }
 
+    #[test]
+    fn spec_overrides_replace_only_set_fields() {
+        let mut base = AgentSpec::default();
+        base.env.insert("A".to_string(), "1".to_string());
+        base.system_prompt = Some("base".to_string());
+
+        let mut overrides = AgentSpecOverrides {
+            memory_mb: Some(2048),
+            ..Default::default()
+        };
+        overrides.env.insert("B".to_string(), "2".to_string());
+
+        let spec = overrides.apply(base);
+        assert_eq!(spec.cpu_millicores, 500);
+        assert_eq!(spec.memory_mb, 2048);
+        assert_eq!(spec.system_prompt.as_deref(), Some("base"));
+        assert_eq!(spec.env.len(), 2);
+    }
+
+    #[test]
+    fn clone_agent_request_name_is_optional() {
+        let req = CloneAgentRequest::default();
+        assert!(req.name.is_none());
+
+        let req = CloneAgentRequest::named("copy");
+        assert_eq!(req.name.as_deref(), Some("copy"));
+    }
+
+    #[test]
+    fn default_clone_names_fit_the_name_limit() {
+        assert_eq!(
+            CloneAgentRequest::default_name("reviewer"),
+            "reviewer-clone"
+        );
+
+        let long = "a".repeat(60);
+        let name = CloneAgentRequest::default_name(&long);
+        assert_eq!(name.len(), MAX_AGENT_NAME_LEN);
+        assert!(name.ends_with("-clone"));
+
+        // Never splits a character
+        let wide = "é".repeat(32);
+        let name = CloneAgentRequest::default_name(&wide);
+        assert!(name.len() <= MAX_AGENT_NAME_LEN);
+        assert_eq!(name, format!("{}-clone", "é".repeat(29)));
+    }
+
     #[test]
     fn log_options_defaults() {
         let opts = LogOptions::default();
@@ -0,0 +1,483 @@
+//! Webhook delivery for agent lifecycle events.
+//!
+//! Events are written to an outbox in the store when an agent changes state
+//! and delivered asynchronously by the control plane's webhook dispatcher.
+//! This module builds payloads, signs requests, computes retry backoff, and
+//! performs individual HTTP delivery attempts.
+//!
+//! # Signatures
+//!
+//! Each delivery carries an `X-Aura-Signature` header of the form
+//! `t=<unix seconds>,v1=<hex>`, where `<hex>` is the HMAC-SHA256 of
+//! `"<t>.<body>"` keyed with the webhook's secret. Receivers should recompute
+//! the signature (see [`verify_signature`]) and reject stale timestamps.
+//!
+//! # Destinations
+//!
+//! Unless private targets are explicitly allowed, webhook URLs must resolve
+//! to publicly routable addresses. This is checked when a webhook is
+//! registered and again on every delivery, when the sender's resolver
+//! refuses loopback, private and link-local addresses (including cloud
+//! metadata endpoints such as 169.254.169.254).
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use aura_swarm_core::DeliveryId;
+use aura_swarm_store::{DeliveryAttempt, Webhook, WebhookDelivery, WebhookEvent};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::error::{ControlError, Result};
+use crate::types::ControlConfig;
+
+/// Header carrying the delivery signature.
+pub const SIGNATURE_HEADER: &str = "X-Aura-Signature";
+
+/// Header carrying the event name.
+pub const EVENT_HEADER: &str = "X-Aura-Event";
+
+/// Header carrying the delivery ID, stable across retries.
+pub const DELIVERY_HEADER: &str = "X-Aura-Delivery";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Generate a random webhook signing secret.
+#[must_use]
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+    bytes[16..].copy_from_slice(uuid::Uuid::new_v4().as_bytes());
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+/// Validate a webhook endpoint URL.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the URL cannot be parsed or is
+/// not HTTP(S).
+pub fn validate_url(url: &str) -> Result<()> {
+    parse_url(url).map(|_| ())
+}
+
+/// Check that a webhook URL only resolves to publicly routable addresses.
+///
+/// # Errors
+///
+/// Returns `ControlError::InvalidRequest` if the URL is invalid, its host
+/// cannot be resolved, or any resolved address is not public.
+pub async fn check_destination(url: &str) -> Result<()> {
+    let parsed = parse_url(url)?;
+    let host = parsed.host_str().unwrap_or_default();
+    let port = parsed.port_or_known_default().unwrap_or(80);
+
+    let addrs: Vec<SocketAddr> = match literal_ip(host) {
+        Some(ip) => vec![SocketAddr::new(ip, port)],
+        None => tokio::net::lookup_host((host, port))
+            .await
+            .map_err(|e| {
+                ControlError::InvalidRequest(format!("cannot resolve webhook host {host}: {e}"))
+            })?
+            .collect(),
+    };
+
+    if addrs.is_empty() || addrs.iter().any(|addr| !is_public_address(addr.ip())) {
+        return Err(ControlError::InvalidRequest(format!(
+            "webhook host {host} does not resolve to a public address"
+        )));
+    }
+    Ok(())
+}
+
+/// Returns true if deliveries may be sent to `ip`.
+///
+/// Loopback, private, link-local, shared (CGNAT), unspecified, broadcast,
+/// multicast and documentation ranges are not public, nor are IPv6
+/// unique-local addresses or IPv4 addresses mapped into IPv6.
+#[must_use]
+pub fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_multicast()
+                || v4.is_documentation()
+                || a == 0
+                || (a == 100 && (64..128).contains(&b)))
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_address(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                || (first & 0xfe00) == 0xfc00
+                || (first & 0xffc0) == 0xfe80)
+        }
+    }
+}
+
+fn parse_url(url: &str) -> Result<reqwest::Url> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| ControlError::InvalidRequest(format!("invalid webhook URL: {e}")))?;
+
+    match parsed.scheme() {
+        "http" | "https" if parsed.host().is_some() => Ok(parsed),
+        _ => Err(ControlError::InvalidRequest(
+            "webhook URL must be an http(s) URL with a host".to_string(),
+        )),
+    }
+}
+
+/// The IP address a URL host names directly, if it is not a domain name.
+fn literal_ip(host: &str) -> Option<IpAddr> {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+/// DNS resolver that refuses names resolving to non-public addresses, so a
+/// webhook host cannot be re-pointed at internal services after it was
+/// registered.
+#[derive(Debug)]
+struct PublicResolver;
+
+impl reqwest::dns::Resolve for PublicResolver {
+    fn resolve(&self, name: reqwest::dns::Name) -> reqwest::dns::Resolving {
+        let host = name.as_str().to_string();
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> =
+                tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if addrs.iter().any(|addr| !is_public_address(addr.ip())) {
+                return Err(format!("{host} resolves to a non-public address").into());
+            }
+            let addrs: reqwest::dns::Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Compute the signature header value for a payload.
+#[must_use]
+pub fn sign(secret: &str, timestamp: i64, payload: &str) -> String {
+    format!(
+        "t={timestamp},v1={}",
+        hex::encode(mac(secret, timestamp, payload))
+    )
+}
+
+/// Verify a signature header value against a payload.
+///
+/// Returns false if the header is malformed or the signature does not match.
+/// Callers are responsible for checking the timestamp's freshness.
+#[must_use]
+pub fn verify_signature(secret: &str, header: &str, payload: &str) -> bool {
+    let mut timestamp = None;
+    let mut signature = None;
+    for part in header.split(',') {
+        match part.split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signature = hex::decode(value).ok(),
+            _ => {}
+        }
+    }
+
+    let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+        return false;
+    };
+
+    let mut mac = new_mac(secret);
+    mac.update(format!("{timestamp}.{payload}").as_bytes());
+    mac.verify_slice(&signature).is_ok()
+}
+
+fn new_mac(secret: &str) -> HmacSha256 {
+    HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length")
+}
+
+fn mac(secret: &str, timestamp: i64, payload: &str) -> Vec<u8> {
+    let mut mac = new_mac(secret);
+    mac.update(format!("{timestamp}.{payload}").as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Build the JSON body for a delivery.
+#[must_use]
+pub fn build_payload(
+    delivery_id: &DeliveryId,
+    event: WebhookEvent,
+    created_at: DateTime<Utc>,
+    data: &serde_json::Value,
+) -> String {
+    serde_json::json!({
+        "id": delivery_id.to_string(),
+        "event": event.as_str(),
+        "created_at": created_at,
+        "data": data,
+    })
+    .to_string()
+}
+
+/// Delay before the next attempt after `attempts` failed attempts.
+///
+/// Doubles from `webhook_backoff_base_seconds`, capped at
+/// `webhook_backoff_max_seconds`.
+#[must_use]
+pub fn retry_delay(config: &ControlConfig, attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(31);
+    let delay = config
+        .webhook_backoff_base_seconds
+        .saturating_mul(1u64 << exponent);
+    Duration::from_secs(delay.min(config.webhook_backoff_max_seconds))
+}
+
+/// HTTP sender for webhook deliveries.
+#[derive(Debug, Clone)]
+pub struct WebhookSender {
+    client: reqwest::Client,
+    allow_private: bool,
+}
+
+impl WebhookSender {
+    /// Create a sender whose requests time out after `timeout`.
+    ///
+    /// Unless `allow_private` is set, requests to hosts that are or resolve
+    /// to non-public addresses fail without being sent.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the HTTP client cannot be created.
+    #[must_use]
+    pub fn new(timeout: Duration, allow_private: bool) -> Self {
+        let mut builder = reqwest::Client::builder()
+            .timeout(timeout)
+            .connect_timeout(Duration::from_secs(5))
+            .redirect(reqwest::redirect::Policy::none());
+        if !allow_private {
+            builder = builder.dns_resolver(Arc::new(PublicResolver));
+        }
+        let client = builder.build().expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            allow_private,
+        }
+    }
+
+    /// Make a single delivery attempt, signing it with `secret`.
+    ///
+    /// Any 2xx response counts as success; everything else is recorded as a
+    /// failed attempt. Only the status code of a failed response is kept, not
+    /// its body.
+    pub async fn send(
+        &self,
+        webhook: &Webhook,
+        secret: &str,
+        delivery: &WebhookDelivery,
+        now: DateTime<Utc>,
+    ) -> DeliveryAttempt {
+        let started = Instant::now();
+        let signature = sign(secret, now.timestamp(), &delivery.payload);
+
+        // IP literals bypass the resolver, so check them here.
+        let host = reqwest::Url::parse(&webhook.url)
+            .ok()
+            .and_then(|url| url.host_str().and_then(literal_ip));
+        if let Some(ip) = host.filter(|ip| !self.allow_private && !is_public_address(*ip)) {
+            return DeliveryAttempt {
+                attempted_at: now,
+                status_code: None,
+                error: Some(format!("{ip} is not a public address")),
+                duration_ms: 0,
+            };
+        }
+
+        let result = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, signature)
+            .header(EVENT_HEADER, delivery.event.as_str())
+            .header(DELIVERY_HEADER, delivery.delivery_id.to_string())
+            .body(delivery.payload.clone())
+            .send()
+            .await;
+
+        let (status_code, error) = match result {
+            Ok(response) if response.status().is_success() => {
+                (Some(response.status().as_u16()), None)
+            }
+            Ok(response) => {
+                let status = response.status();
+                (
+                    Some(status.as_u16()),
+                    Some(format!("endpoint returned {status}")),
+                )
+            }
+            Err(e) => (None, Some(e.to_string())),
+        };
+
+        DeliveryAttempt {
+            attempted_at: now,
+            status_code,
+            error,
+            duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signature_roundtrip() {
+        let payload = r#"{"event":"ping"}"#;
+        let header = sign("whsec_test", 1_700_000_000, payload);
+
+        assert!(header.starts_with("t=1700000000,v1="));
+        assert!(verify_signature("whsec_test", &header, payload));
+        assert!(!verify_signature("whsec_other", &header, payload));
+        assert!(!verify_signature(
+            "whsec_test",
+            &header,
+            r#"{"event":"pong"}"#
+        ));
+        assert!(!verify_signature("whsec_test", "garbage", payload));
+    }
+
+    #[test]
+    fn secrets_are_unique() {
+        let a = generate_secret();
+        assert!(a.starts_with("whsec_"));
+        assert_eq!(a.len(), "whsec_".len() + 64);
+        assert_ne!(a, generate_secret());
+    }
+
+    #[test]
+    fn url_validation() {
+        assert!(validate_url("https://ci.example.com/hooks/aura").is_ok());
+        assert!(validate_url("http://localhost:9000/").is_ok());
+        assert!(validate_url("ftp://example.com/").is_err());
+        assert!(validate_url("not a url").is_err());
+    }
+
+    #[test]
+    fn internal_addresses_are_not_public() {
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_address(ip.parse().unwrap()), "{ip}");
+        }
+        assert!(is_public_address("93.184.216.34".parse().unwrap()));
+        assert!(is_public_address("2606:4700::1111".parse().unwrap()));
+    }
+
+    #[tokio::test]
+    async fn destinations_must_be_public() {
+        for url in [
+            "http://127.0.0.1:9000/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://[::1]/",
+            "http://localhost:9000/",
+        ] {
+            assert!(
+                matches!(
+                    check_destination(url).await,
+                    Err(ControlError::InvalidRequest(_))
+                ),
+                "{url}"
+            );
+        }
+        assert!(check_destination("https://93.184.216.34/hook")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn sender_refuses_internal_addresses() {
+        let webhook = Webhook {
+            webhook_id: aura_swarm_core::WebhookId::generate(),
+            user_id: aura_swarm_core::UserId::from_bytes([1u8; 32]),
+            url: "http://169.254.169.254/latest/meta-data/".to_string(),
+            sealed_secret: Vec::new(),
+            events: Vec::new(),
+            description: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let delivery = WebhookDelivery {
+            delivery_id: DeliveryId::generate(),
+            webhook_id: webhook.webhook_id,
+            event: WebhookEvent::Ping,
+            payload: "{}".to_string(),
+            status: aura_swarm_store::DeliveryStatus::Pending,
+            attempts: Vec::new(),
+            next_attempt_at: None,
+            created_at: Utc::now(),
+        };
+
+        let sender = WebhookSender::new(Duration::from_secs(1), false);
+        let attempt = sender
+            .send(&webhook, "whsec_test", &delivery, Utc::now())
+            .await;
+        assert!(attempt.status_code.is_none());
+        assert!(attempt.error.unwrap().contains("not a public address"));
+
+        // Names resolving to loopback are refused by the resolver
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::any())
+            .respond_with(wiremock::ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        let mut webhook = webhook;
+        webhook.url = format!("http://localhost:{}/", server.address().port());
+
+        let attempt = sender
+            .send(&webhook, "whsec_test", &delivery, Utc::now())
+            .await;
+        assert!(attempt.status_code.is_none());
+        assert!(server.received_requests().await.unwrap().is_empty());
+
+        let sender = WebhookSender::new(Duration::from_secs(1), true);
+        let attempt = sender
+            .send(&webhook, "whsec_test", &delivery, Utc::now())
+            .await;
+        assert_eq!(attempt.status_code, Some(200));
+    }
+
+    #[test]
+    fn backoff_is_exponential_and_capped() {
+        let config = ControlConfig {
+            webhook_backoff_base_seconds: 10,
+            webhook_backoff_max_seconds: 250,
+            ..ControlConfig::default()
+        };
+
+        assert_eq!(retry_delay(&config, 1), Duration::from_secs(10));
+        assert_eq!(retry_delay(&config, 2), Duration::from_secs(20));
+        assert_eq!(retry_delay(&config, 4), Duration::from_secs(80));
+        assert_eq!(retry_delay(&config, 10), Duration::from_secs(250));
+        assert_eq!(retry_delay(&config, 200), Duration::from_secs(250));
+    }
+}
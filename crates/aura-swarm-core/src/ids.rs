@@ -398,6 +398,303 @@ impl AsRef<[u8]> for NamespaceId {
     }
 }
 
+/// A 16-byte webhook identifier based on UUID v4.
+///
+/// Webhook IDs are randomly generated when a webhook endpoint is registered.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct WebhookId(uuid::Uuid);
+
+/// A 16-byte webhook delivery identifier based on UUID v4.
+///
+/// Each event sent to a webhook gets its own delivery ID, which receivers
+/// can use to deduplicate retried deliveries.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct DeliveryId(uuid::Uuid);
+
+/// A 16-byte audit event identifier based on UUID v4.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AuditEventId(uuid::Uuid);
+
+/// A 16-byte runtime rollout identifier based on UUID v4.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct RolloutId(uuid::Uuid);
+
+impl WebhookId {
+    /// Create a new `WebhookId` from a UUID.
+    #[must_use]
+    pub const fn from_uuid(uuid: uuid::Uuid) -> Self {
+        Self(uuid)
+    }
+
+    /// Generate a new random `WebhookId`.
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(uuid::Uuid::new_v4())
+    }
+
+    /// Return the underlying UUID.
+    #[must_use]
+    pub const fn as_uuid(&self) -> &uuid::Uuid {
+        &self.0
+    }
+
+    /// Return the bytes of the UUID.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        self.0.as_bytes()
+    }
+}
+
+impl FromStr for WebhookId {
+    type Err = IdError;
+
+    /// Parse a `WebhookId` from a UUID string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let uuid = uuid::Uuid::parse_str(s).map_err(|_| IdError::InvalidUuid)?;
+        Ok(Self(uuid))
+    }
+}
+
+impl fmt::Debug for WebhookId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "WebhookId({})", self.0)
+    }
+}
+
+impl fmt::Display for WebhookId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for WebhookId {
+    type Error = IdError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<WebhookId> for String {
+    fn from(id: WebhookId) -> Self {
+        id.0.to_string()
+    }
+}
+
+impl AsRef<[u8]> for WebhookId {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl DeliveryId {
+    /// Create a new `DeliveryId` from a UUID.
+    #[must_use]
+    pub const fn from_uuid(uuid: uuid::Uuid) -> Self {
+        Self(uuid)
+    }
+
+    /// Generate a new random `DeliveryId`.
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(uuid::Uuid::new_v4())
+    }
+
+    /// Return the underlying UUID.
+    #[must_use]
+    pub const fn as_uuid(&self) -> &uuid::Uuid {
+        &self.0
+    }
+
+    /// Return the bytes of the UUID.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        self.0.as_bytes()
+    }
+}
+
+impl FromStr for DeliveryId {
+    type Err = IdError;
+
+    /// Parse a `DeliveryId` from a UUID string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let uuid = uuid::Uuid::parse_str(s).map_err(|_| IdError::InvalidUuid)?;
+        Ok(Self(uuid))
+    }
+}
+
+impl fmt::Debug for DeliveryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DeliveryId({})", self.0)
+    }
+}
+
+impl fmt::Display for DeliveryId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for DeliveryId {
+    type Error = IdError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<DeliveryId> for String {
+    fn from(id: DeliveryId) -> Self {
+        id.0.to_string()
+    }
+}
+
+impl AsRef<[u8]> for DeliveryId {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl AuditEventId {
+    /// Create a new `AuditEventId` from a UUID.
+    #[must_use]
+    pub const fn from_uuid(uuid: uuid::Uuid) -> Self {
+        Self(uuid)
+    }
+
+    /// Generate a new random `AuditEventId`.
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(uuid::Uuid::new_v4())
+    }
+
+    /// Return the underlying UUID.
+    #[must_use]
+    pub const fn as_uuid(&self) -> &uuid::Uuid {
+        &self.0
+    }
+
+    /// Return the bytes of the UUID.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        self.0.as_bytes()
+    }
+}
+
+impl FromStr for AuditEventId {
+    type Err = IdError;
+
+    /// Parse a `AuditEventId` from a UUID string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let uuid = uuid::Uuid::parse_str(s).map_err(|_| IdError::InvalidUuid)?;
+        Ok(Self(uuid))
+    }
+}
+
+impl fmt::Debug for AuditEventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AuditEventId({})", self.0)
+    }
+}
+
+impl fmt::Display for AuditEventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for AuditEventId {
+    type Error = IdError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AuditEventId> for String {
+    fn from(id: AuditEventId) -> Self {
+        id.0.to_string()
+    }
+}
+
+impl AsRef<[u8]> for AuditEventId {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
+impl RolloutId {
+    /// Create a new `RolloutId` from a UUID.
+    #[must_use]
+    pub const fn from_uuid(uuid: uuid::Uuid) -> Self {
+        Self(uuid)
+    }
+
+    /// Generate a new random `RolloutId`.
+    #[must_use]
+    pub fn generate() -> Self {
+        Self(uuid::Uuid::new_v4())
+    }
+
+    /// Return the underlying UUID.
+    #[must_use]
+    pub const fn as_uuid(&self) -> &uuid::Uuid {
+        &self.0
+    }
+
+    /// Return the bytes of the UUID.
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        self.0.as_bytes()
+    }
+}
+
+impl FromStr for RolloutId {
+    type Err = IdError;
+
+    /// Parse a `RolloutId` from a UUID string.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let uuid = uuid::Uuid::parse_str(s).map_err(|_| IdError::InvalidUuid)?;
+        Ok(Self(uuid))
+    }
+}
+
+impl fmt::Debug for RolloutId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "RolloutId({})", self.0)
+    }
+}
+
+impl fmt::Display for RolloutId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl TryFrom<String> for RolloutId {
+    type Error = IdError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<RolloutId> for String {
+    fn from(id: RolloutId) -> Self {
+        id.0.to_string()
+    }
+}
+
+impl AsRef<[u8]> for RolloutId {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
+
 /// Errors that can occur when parsing identifiers.
 #[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
 pub enum IdError {
@@ -472,6 +769,22 @@ mod tests {
         assert_eq!(id, parsed);
     }
 
+    #[test]
+    fn webhook_ids_roundtrip() {
+        let id = WebhookId::generate();
+        assert_eq!(WebhookId::from_str(&id.to_string()).unwrap(), id);
+
+        let id = DeliveryId::generate();
+        let json = serde_json::to_string(&id).unwrap();
+        assert_eq!(serde_json::from_str::<DeliveryId>(&json).unwrap(), id);
+
+        let id = AuditEventId::generate();
+        assert_eq!(AuditEventId::from_str(&id.to_string()).unwrap(), id);
+
+        let id = RolloutId::generate();
+        assert_eq!(RolloutId::from_str(&id.to_string()).unwrap(), id);
+    }
+
     #[test]
     fn user_id_serde_json() {
         let bytes = [0xab; 32];
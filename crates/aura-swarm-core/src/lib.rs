@@ -31,4 +31,7 @@ pub mod error;
 pub mod ids;
 
 pub use error::{CoreError, Result};
-pub use ids::{AgentId, IdError, IdentityId, NamespaceId, SessionId, UserId};
+pub use ids::{
+    AgentId, AuditEventId, DeliveryId, IdError, IdentityId, NamespaceId, RolloutId, SessionId,
+    UserId, WebhookId,
+};
@@ -0,0 +1,379 @@
+//! Audit middleware.
+//!
+//! Every mutating request (`POST`, `PUT`, `PATCH`, `DELETE`) and every request
+//! rejected with `401` or `403` is recorded in the control plane's audit log
+//! once the response is ready. The [`AuthUser`] extractor fills in who made
+//! the request through the [`AuditContext`] placed in the request extensions.
+//! Scheduler callbacks under `/internal/` are not user actions, so they are
+//! only recorded when rejected.
+//!
+//! Requests are tagged with an `x-request-id`, taken from the client if
+//! present and generated otherwise, which is echoed on the response and
+//! stored with the event.
+
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::Utc;
+
+use aura_swarm_auth::JwtValidator;
+use aura_swarm_control::{
+    AuditActor, AuditEvent, AuditEventId, AuditOutcome, AuditResource, ControlPlane,
+};
+
+use crate::auth::AuthUser;
+use crate::config::GatewayConfig;
+use crate::state::GatewayState;
+
+/// Header carrying the request ID.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-supplied request ID that is accepted as is.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Per-request slot for the authenticated user, shared between the audit
+/// middleware and the [`AuthUser`] extractor.
+#[derive(Debug, Clone, Default)]
+pub struct AuditContext(Arc<Mutex<Option<AuthUser>>>);
+
+impl AuditContext {
+    /// Record the user a request was authenticated as.
+    pub fn identify(&self, user: &AuthUser) {
+        if let Ok(mut slot) = self.0.lock() {
+            *slot = Some(user.clone());
+        }
+    }
+
+    fn user(&self) -> Option<AuthUser> {
+        self.0.lock().ok().and_then(|slot| slot.clone())
+    }
+}
+
+/// Middleware recording mutating requests and authorization failures.
+pub async fn audit_requests<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    mut request: Request,
+    next: Next,
+) -> Response
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let request_id = request_id(request.headers());
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    let ip_address = peer.map(|peer| client_ip(&state.config, peer, request.headers()));
+    let user_agent = request
+        .headers()
+        .get("user-agent")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+
+    let context = AuditContext::default();
+    request.extensions_mut().insert(context.clone());
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        request
+            .headers_mut()
+            .insert(REQUEST_ID_HEADER, value.clone());
+    }
+
+    let mut response = next.run(request).await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    let status = response.status();
+    if !is_recorded(&method, &path, status) {
+        return response;
+    }
+
+    let (event_type, action, resource) = classify(&method, &path);
+    let user = context.user();
+    let (event_type, action) = if status == StatusCode::UNAUTHORIZED {
+        ("auth.failure".to_string(), "authenticate".to_string())
+    } else {
+        (event_type, action)
+    };
+
+    let mut event = AuditEvent {
+        event_id: AuditEventId::generate(),
+        timestamp: Utc::now(),
+        event_type,
+        actor: AuditActor {
+            user_id: user.as_ref().map(|u| u.user_id),
+            identity_id: user.as_ref().map(|u| u.identity_id),
+            ip_address,
+            user_agent,
+        },
+        namespace_id: user.as_ref().map(|u| u.namespace_id),
+        resource,
+        action,
+        outcome: outcome(status),
+        request_id: Some(request_id),
+        details: std::collections::BTreeMap::new(),
+    };
+    event
+        .details
+        .insert("method".to_string(), method.to_string());
+    event.details.insert("path".to_string(), path);
+    event
+        .details
+        .insert("status".to_string(), status.as_u16().to_string());
+
+    state.control.record_audit_event(event).await;
+
+    response
+}
+
+/// The client's request ID, or a freshly generated one.
+fn request_id(headers: &HeaderMap) -> String {
+    headers
+        .get(REQUEST_ID_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .filter(|v| !v.is_empty() && v.len() <= MAX_REQUEST_ID_LEN)
+        .map_or_else(|| uuid::Uuid::new_v4().to_string(), str::to_string)
+}
+
+/// The client's address.
+///
+/// `X-Forwarded-For` is only believed when the peer is a trusted proxy, and
+/// then only as far as the chain of trusted proxies goes: the right-most hop
+/// that is not itself a trusted proxy is the client. Anything to its left was
+/// supplied by the client and may be forged.
+fn client_ip(config: &GatewayConfig, peer: IpAddr, headers: &HeaderMap) -> String {
+    if !config.is_trusted_proxy(peer) {
+        return peer.to_string();
+    }
+
+    let hops: Vec<&str> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .collect();
+
+    let mut client = peer.to_string();
+    for hop in hops.into_iter().rev() {
+        client = hop.to_string();
+        match hop.parse() {
+            Ok(ip) if config.is_trusted_proxy(ip) => {}
+            _ => break,
+        }
+    }
+    client
+}
+
+/// Whether a finished request belongs in the audit log.
+fn is_recorded(method: &Method, path: &str, status: StatusCode) -> bool {
+    if is_denied(status) {
+        return true;
+    }
+    is_mutating(method) && !path.starts_with("/internal/")
+}
+
+fn is_mutating(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+    )
+}
+
+fn is_denied(status: StatusCode) -> bool {
+    status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN
+}
+
+fn outcome(status: StatusCode) -> AuditOutcome {
+    if is_denied(status) {
+        AuditOutcome::Denied
+    } else if status.is_success() || status.is_redirection() || status.is_informational() {
+        AuditOutcome::Success
+    } else {
+        AuditOutcome::Failure
+    }
+}
+
+/// Derive the event type, action and resource from a request.
+///
+/// Event types are `<resource>.<action>`, e.g. `agent.start` or
+/// `access.grant`.
+fn classify(method: &Method, path: &str) -> (String, String, Option<AuditResource>) {
+    let verb = match *method {
+        Method::POST => "create",
+        Method::PUT | Method::PATCH => "update",
+        Method::DELETE => "delete",
+        _ => "read",
+    };
+    let resource = |resource_type: &str, id: &str| {
+        Some(AuditResource {
+            resource_type: resource_type.to_string(),
+            id: id.to_string(),
+        })
+    };
+
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let (kind, action, resource) = match segments.as_slice() {
+        ["v1", "agents", "shared"] => ("access", "list", None),
+        ["v1", "agents"] => ("agent", verb, None),
+        ["v1", "agents", id] => ("agent", verb, resource("agent", id)),
+        ["v1", "agents", id, "access", ..] => {
+            let action = match *method {
+                Method::PUT => "grant",
+                Method::DELETE => "revoke",
+                _ => "list",
+            };
+            ("access", action, resource("agent", id))
+        }
+        ["v1", "agents", id, "secrets", ..] => {
+            let action = match *method {
+                Method::PUT => "set",
+                Method::DELETE => "delete",
+                _ => "list",
+            };
+            ("secret", action, resource("agent", id))
+        }
+        ["v1", "agents", id, "schedule"] => ("schedule", verb, resource("agent", id)),
+        ["v1", "agents", id, "sessions"] => ("session", verb, resource("agent", id)),
+        ["v1", "agents", id, operation] => ("agent", *operation, resource("agent", id)),
+        ["v1", "sessions", id, ..] => ("session", verb, resource("session", id)),
+        ["v1", "webhooks"] => ("webhook", verb, None),
+        ["v1", "webhooks", id] => ("webhook", verb, resource("webhook", id)),
+        ["v1", "webhooks", id, operation] => ("webhook", *operation, resource("webhook", id)),
+        ["v1", "templates"] => ("template", verb, None),
+        ["v1", "templates", name] => ("template", verb, resource("template", name)),
+        ["v1", "admin", "rollouts"] => ("rollout", verb, None),
+        ["v1", "admin", "rollouts", id, operation] => {
+            ("rollout", *operation, resource("rollout", id))
+        }
+        ["v1", "admin", "namespaces", id, "quota"] => {
+            ("namespace", "set_quota", resource("namespace", id))
+        }
+        ["v1", "namespace", ..] => ("namespace", verb, None),
+        ["v1", "audit"] => ("audit", "list", None),
+        ["internal", "agents", id, "status"] => ("agent", "report_status", resource("agent", id)),
+        _ => ("request", verb, None),
+    };
+
+    (format!("{kind}.{action}"), action.to_string(), resource)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_requests() {
+        let agent = "ab".repeat(32);
+
+        let (event_type, action, resource) =
+            classify(&Method::POST, &format!("/v1/agents/{agent}/start"));
+        assert_eq!(event_type, "agent.start");
+        assert_eq!(action, "start");
+        let resource = resource.unwrap();
+        assert_eq!(resource.resource_type, "agent");
+        assert_eq!(resource.id, agent);
+
+        let (event_type, ..) = classify(&Method::DELETE, &format!("/v1/agents/{agent}"));
+        assert_eq!(event_type, "agent.delete");
+
+        let (event_type, ..) =
+            classify(&Method::PUT, &format!("/v1/agents/{agent}/access/{agent}"));
+        assert_eq!(event_type, "access.grant");
+
+        let (event_type, ..) = classify(&Method::PUT, &format!("/v1/agents/{agent}/secrets/TOKEN"));
+        assert_eq!(event_type, "secret.set");
+
+        let (event_type, _, resource) = classify(&Method::DELETE, "/v1/sessions/abc");
+        assert_eq!(event_type, "session.delete");
+        assert_eq!(resource.unwrap().resource_type, "session");
+
+        let (event_type, _, resource) = classify(&Method::POST, "/v1/admin/rollouts/abc/rollback");
+        assert_eq!(event_type, "rollout.rollback");
+        assert_eq!(resource.unwrap().resource_type, "rollout");
+
+        let (event_type, _, resource) = classify(&Method::POST, "/v1/unknown");
+        assert_eq!(event_type, "request.create");
+        assert!(resource.is_none());
+    }
+
+    #[test]
+    fn internal_callbacks_are_recorded_only_when_denied() {
+        let status_path = format!("/internal/agents/{}/status", "ab".repeat(32));
+
+        assert!(is_recorded(
+            &Method::POST,
+            "/v1/agents",
+            StatusCode::CREATED
+        ));
+        assert!(!is_recorded(&Method::GET, "/v1/agents", StatusCode::OK));
+        assert!(!is_recorded(&Method::PATCH, &status_path, StatusCode::OK));
+        assert!(is_recorded(
+            &Method::PATCH,
+            &status_path,
+            StatusCode::UNAUTHORIZED
+        ));
+    }
+
+    #[test]
+    fn outcomes_follow_status() {
+        assert_eq!(outcome(StatusCode::CREATED), AuditOutcome::Success);
+        assert_eq!(outcome(StatusCode::FORBIDDEN), AuditOutcome::Denied);
+        assert_eq!(outcome(StatusCode::UNAUTHORIZED), AuditOutcome::Denied);
+        assert_eq!(outcome(StatusCode::CONFLICT), AuditOutcome::Failure);
+    }
+
+    #[test]
+    fn forwarded_for_is_only_believed_from_trusted_proxies() {
+        let config = GatewayConfig {
+            trusted_proxies: vec!["10.0.0.0/8".parse().unwrap()],
+            ..GatewayConfig::default()
+        };
+        let proxy: IpAddr = "10.0.0.5".parse().unwrap();
+        let mut headers = HeaderMap::new();
+
+        // Without a header the proxy is all we know
+        assert_eq!(client_ip(&config, proxy, &headers), "10.0.0.5");
+
+        // A client connecting directly cannot claim another address
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("198.51.100.1, 10.0.0.9, 203.0.113.7"),
+        );
+        let direct: IpAddr = "203.0.113.7".parse().unwrap();
+        assert_eq!(client_ip(&config, direct, &headers), "203.0.113.7");
+
+        // Behind the proxy, the right-most untrusted hop is the client and
+        // the spoofed left-most hop is ignored
+        assert_eq!(client_ip(&config, proxy, &headers), "203.0.113.7");
+
+        // Trusted hops are skipped
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("198.51.100.1, 203.0.113.7, 10.0.0.9"),
+        );
+        assert_eq!(client_ip(&config, proxy, &headers), "203.0.113.7");
+
+        // Without trusted proxies the header is never used
+        let untrusting = GatewayConfig::default();
+        assert_eq!(client_ip(&untrusting, proxy, &headers), "10.0.0.5");
+    }
+
+    #[test]
+    fn request_id_is_reused_or_generated() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(request_id(&headers).len(), 36);
+
+        headers.insert(REQUEST_ID_HEADER, HeaderValue::from_static("req-1"));
+        assert_eq!(request_id(&headers), "req-1");
+    }
+}
@@ -1,7 +1,8 @@
 //! Authentication middleware and extractors.
 //!
 //! This module provides the `AuthUser` extractor that validates JWT tokens
-//! and extracts user identity from requests.
+//! and extracts user identity from requests. Tokens carrying the
+//! [`NAMESPACE_ADMIN_ROLE`] role may manage every agent in their namespace.
 
 use std::sync::Arc;
 
@@ -9,12 +10,16 @@ use axum::extract::FromRequestParts;
 use axum::http::request::Parts;
 
 use aura_swarm_auth::{JwtValidator, ValidatedClaims};
-use aura_swarm_control::ControlPlane;
+use aura_swarm_control::{Caller, ControlPlane};
 use aura_swarm_core::{IdentityId, NamespaceId, SessionId, UserId};
 
+use crate::audit::AuditContext;
 use crate::error::ApiError;
 use crate::state::GatewayState;
 
+/// Token role granting administration of the caller's namespace.
+pub const NAMESPACE_ADMIN_ROLE: &str = "namespace_admin";
+
 /// An authenticated user extracted from a JWT token.
 ///
 /// This extractor validates the `Authorization: Bearer <token>` header
@@ -31,6 +36,8 @@ pub struct AuthUser {
     pub mfa_verified: bool,
     /// The internal user ID derived from `identity_id`.
     pub user_id: UserId,
+    /// Whether the token grants the namespace admin role.
+    pub namespace_admin: bool,
 }
 
 impl AuthUser {
@@ -49,6 +56,17 @@ impl AuthUser {
             zid_session_id: claims.session_id,
             mfa_verified: claims.mfa_verified,
             user_id,
+            namespace_admin: claims.has_role(NAMESPACE_ADMIN_ROLE),
+        }
+    }
+
+    /// The control plane caller for this user, scoped to their namespace.
+    #[must_use]
+    pub const fn caller(&self) -> Caller {
+        if self.namespace_admin {
+            Caller::namespace_admin(self.user_id, self.namespace_id)
+        } else {
+            Caller::member(self.user_id, self.namespace_id)
         }
     }
 }
@@ -91,7 +109,14 @@ where
             // Validate the token
             let claims = state.jwt_validator.validate(token).await?;
 
-            Ok(AuthUser::from_claims(&claims))
+            let user = AuthUser::from_claims(&claims);
+
+            // Let the audit middleware know who made the request
+            if let Some(context) = parts.extensions.get::<AuditContext>() {
+                context.identify(&user);
+            }
+
+            Ok(user)
         })
     }
 }
@@ -115,6 +140,7 @@ mod tests {
             session_id,
             mfa_verified: true,
             expires_at: Utc::now() + Duration::hours(1),
+            roles: vec![NAMESPACE_ADMIN_ROLE.to_string()],
         };
 
         let user = AuthUser::from_claims(&claims);
@@ -123,5 +149,7 @@ mod tests {
         assert!(user.mfa_verified);
         // user_id should be derived from identity_id
         assert_eq!(user.user_id.as_bytes().len(), 32);
+        assert!(user.caller().is_namespace_admin());
+        assert_eq!(user.caller().namespace_id, namespace_id);
     }
 }
@@ -2,8 +2,11 @@
 //!
 //! This module defines configuration structures for the HTTP/WebSocket gateway.
 
+use std::net::IpAddr;
+use std::str::FromStr;
 use std::time::Duration;
 
+use aura_swarm_core::IdentityId;
 use serde::Deserialize;
 
 /// Configuration for the gateway service.
@@ -25,6 +28,11 @@ pub struct GatewayConfig {
     #[serde(default = "GatewayConfig::default_ws_timeout")]
     pub websocket_timeout_seconds: u64,
 
+    /// How often WebSocket proxies report session activity and check that
+    /// the session is still open, in seconds.
+    #[serde(default = "GatewayConfig::default_session_activity_interval")]
+    pub session_activity_interval_seconds: u64,
+
     /// Maximum request body size in bytes.
     #[serde(default = "GatewayConfig::default_max_body")]
     pub max_body_bytes: usize,
@@ -32,6 +40,82 @@ pub struct GatewayConfig {
     /// Request timeout in seconds.
     #[serde(default = "GatewayConfig::default_request_timeout")]
     pub request_timeout_seconds: u64,
+
+    /// Identities allowed to perform administrative operations
+    /// (e.g., managing admin-defined templates).
+    #[serde(default)]
+    pub admin_identities: Vec<IdentityId>,
+
+    /// Proxies whose `X-Forwarded-For` header is believed when recording the
+    /// client address. Requests from any other peer are attributed to the
+    /// peer itself.
+    #[serde(default)]
+    pub trusted_proxies: Vec<TrustedProxy>,
+}
+
+/// An address or CIDR range of trusted reverse proxies, e.g. `10.0.0.0/8`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct TrustedProxy {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+/// Error returned when a trusted proxy is not an address or CIDR range.
+#[derive(Debug, thiserror::Error)]
+#[error("invalid trusted proxy: {0}")]
+pub struct InvalidTrustedProxy(String);
+
+impl TrustedProxy {
+    /// Returns true if the address belongs to this proxy range.
+    #[must_use]
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        let same_prefix = |a: u128, b: u128, bits: u32| {
+            let shift = bits - u32::from(self.prefix_len);
+            a.checked_shr(shift) == b.checked_shr(shift)
+        };
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => {
+                same_prefix(u32::from(net).into(), u32::from(ip).into(), 32)
+            }
+            (IpAddr::V6(net), IpAddr::V6(ip)) => same_prefix(net.into(), ip.into(), 128),
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for TrustedProxy {
+    type Err = InvalidTrustedProxy;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidTrustedProxy(s.to_string());
+        let (addr, prefix_len) = match s.split_once('/') {
+            Some((addr, prefix_len)) => (addr, Some(prefix_len)),
+            None => (s, None),
+        };
+        let network: IpAddr = addr.parse().map_err(|_| invalid())?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = match prefix_len {
+            Some(len) => len
+                .parse()
+                .ok()
+                .filter(|len| *len <= max_len)
+                .ok_or_else(invalid)?,
+            None => max_len,
+        };
+        Ok(Self {
+            network,
+            prefix_len,
+        })
+    }
+}
+
+impl TryFrom<String> for TrustedProxy {
+    type Error = InvalidTrustedProxy;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
 }
 
 impl GatewayConfig {
@@ -47,6 +131,10 @@ impl GatewayConfig {
         300 // 5 minutes
     }
 
+    const fn default_session_activity_interval() -> u64 {
+        30
+    }
+
     const fn default_max_body() -> usize {
         1024 * 1024 // 1 MB
     }
@@ -55,12 +143,30 @@ impl GatewayConfig {
         30
     }
 
+    /// Returns true if the peer address is a trusted proxy.
+    #[must_use]
+    pub fn is_trusted_proxy(&self, ip: IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|proxy| proxy.contains(ip))
+    }
+
+    /// Returns true if the identity is configured as an administrator.
+    #[must_use]
+    pub fn is_admin(&self, identity_id: &IdentityId) -> bool {
+        self.admin_identities.contains(identity_id)
+    }
+
     /// Get the WebSocket timeout as a `Duration`.
     #[must_use]
     pub fn websocket_timeout(&self) -> Duration {
         Duration::from_secs(self.websocket_timeout_seconds)
     }
 
+    /// Get the session activity reporting interval as a `Duration`.
+    #[must_use]
+    pub fn session_activity_interval(&self) -> Duration {
+        Duration::from_secs(self.session_activity_interval_seconds)
+    }
+
     /// Get the request timeout as a `Duration`.
     #[must_use]
     pub fn request_timeout(&self) -> Duration {
@@ -75,8 +181,11 @@ impl Default for GatewayConfig {
             cors_origins: vec!["*".to_string()],
             rate_limit_rps: Self::default_rate_limit(),
             websocket_timeout_seconds: Self::default_ws_timeout(),
+            session_activity_interval_seconds: Self::default_session_activity_interval(),
             max_body_bytes: Self::default_max_body(),
             request_timeout_seconds: Self::default_request_timeout(),
+            admin_identities: Vec::new(),
+            trusted_proxies: Vec::new(),
         }
     }
 }
@@ -99,5 +208,37 @@ mod tests {
         let config = GatewayConfig::default();
         assert_eq!(config.websocket_timeout(), Duration::from_secs(300));
         assert_eq!(config.request_timeout(), Duration::from_secs(30));
+        assert_eq!(config.session_activity_interval(), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn admin_identities() {
+        let admin: IdentityId = "550e8400-e29b-41d4-a716-446655440000".parse().unwrap();
+        let other: IdentityId = "6ba7b810-9dad-11d1-80b4-00c04fd430c8".parse().unwrap();
+        let config = GatewayConfig {
+            admin_identities: vec![admin],
+            ..Default::default()
+        };
+        assert!(config.is_admin(&admin));
+        assert!(!config.is_admin(&other));
+    }
+
+    #[test]
+    fn trusted_proxies() {
+        let config = GatewayConfig {
+            trusted_proxies: vec!["10.0.0.0/8".parse().unwrap(), "fd00::1".parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(config.is_trusted_proxy("10.1.2.3".parse().unwrap()));
+        assert!(config.is_trusted_proxy("::ffff:10.1.2.3".parse().unwrap()));
+        assert!(config.is_trusted_proxy("fd00::1".parse().unwrap()));
+        assert!(!config.is_trusted_proxy("fd00::2".parse().unwrap()));
+        assert!(!config.is_trusted_proxy("11.0.0.1".parse().unwrap()));
+        assert!(!GatewayConfig::default().is_trusted_proxy("10.1.2.3".parse().unwrap()));
+
+        let any: TrustedProxy = "0.0.0.0/0".parse().unwrap();
+        assert!(any.contains("203.0.113.7".parse().unwrap()));
+        assert!("10.0.0.0/33".parse::<TrustedProxy>().is_err());
+        assert!("proxy.local".parse::<TrustedProxy>().is_err());
     }
 }
@@ -45,6 +45,10 @@ pub enum ApiError {
     /// Agent pod is not reachable.
     #[error("agent unavailable")]
     AgentUnavailable,
+
+    /// A backing service is temporarily unavailable.
+    #[error("service unavailable: {0}")]
+    ServiceUnavailable(String),
 }
 
 /// Error response body.
@@ -72,7 +76,7 @@ impl ApiError {
             Self::RateLimited => StatusCode::TOO_MANY_REQUESTS,
             Self::BadRequest(_) => StatusCode::BAD_REQUEST,
             Self::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            Self::AgentUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            Self::AgentUnavailable | Self::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
         }
     }
 
@@ -88,6 +92,7 @@ impl ApiError {
             Self::BadRequest(_) => "bad_request",
             Self::Internal(_) => "internal_error",
             Self::AgentUnavailable => "agent_unavailable",
+            Self::ServiceUnavailable(_) => "service_unavailable",
         }
     }
 }
@@ -138,21 +143,63 @@ impl From<ControlError> for ApiError {
             ControlError::QuotaExceeded { limit, .. } => {
                 Self::Conflict(format!("agent quota exceeded: limit is {limit}"))
             }
-            ControlError::NotOwner { .. } => Self::Forbidden,
+            ControlError::NamespaceQuotaExceeded { limit, .. } => {
+                Self::Conflict(format!("namespace agent quota exceeded (limit: {limit})"))
+            }
+            ControlError::NotOwner { .. }
+            | ControlError::InsufficientRole { .. }
+            | ControlError::NotNamespaceAdmin(_) => Self::Forbidden,
             ControlError::InvalidState { from, to, .. } => {
                 Self::Conflict(format!("cannot transition from {from:?} to {to:?}"))
             }
             ControlError::AgentNotRunnable(id) => {
                 Self::Conflict(format!("agent {id} is not in a runnable state"))
             }
+            ControlError::SessionLimitExceeded { scope, limit } => Self::Conflict(format!(
+                "session limit exceeded: at most {limit} active sessions per {scope}"
+            )),
             ControlError::SessionAlreadyActive(id) => {
                 Self::Conflict(format!("agent {id} already has an active session"))
             }
+            ControlError::TemplateNotFound(name) => Self::NotFound(format!("template {name}")),
+            ControlError::ScheduleNotFound(id) => {
+                Self::NotFound(format!("schedule for agent {id}"))
+            }
+            ControlError::WebhookNotFound(id) => Self::NotFound(format!("webhook {id}")),
+            ControlError::GrantNotFound { user_id, .. } => {
+                Self::NotFound(format!("access grant for user {user_id}"))
+            }
+            ControlError::SecretNotFound { name, .. } => Self::NotFound(format!("secret {name}")),
+            ControlError::RolloutNotFound(id) => Self::NotFound(format!("rollout {id}")),
+            ControlError::RolloutInProgress(id) => {
+                Self::Conflict(format!("rollout {id} is already in progress"))
+            }
+            ControlError::AgentDeleting(id) => {
+                Self::Conflict(format!("agent {id} is being deleted"))
+            }
+            ControlError::StateCopyPending(id) => {
+                Self::Conflict(format!("agent {id} state is being copied to a clone"))
+            }
+            ControlError::DeletionNotFound(id) => {
+                Self::NotFound(format!("deletion for agent {id}"))
+            }
+            ControlError::TemplateExists(name) => {
+                Self::Conflict(format!("template {name} already exists"))
+            }
+            ControlError::InvalidRequest(msg) => Self::BadRequest(msg),
             ControlError::Auth(auth_err) => Self::from(auth_err),
             ControlError::Store(store_err) => {
                 tracing::error!(error = %store_err, "Store error");
                 Self::Internal("storage error".to_string())
             }
+            ControlError::SchedulerUnavailable(msg) => {
+                tracing::warn!(error = %msg, "Scheduler unavailable");
+                Self::ServiceUnavailable("scheduler".to_string())
+            }
+            ControlError::Runtime(runtime_err) => {
+                tracing::warn!(error = %runtime_err, "Agent runtime unavailable");
+                Self::AgentUnavailable
+            }
             ControlError::Internal(msg) => {
                 tracing::error!(error = %msg, "Internal error");
                 Self::Internal(msg)
@@ -192,6 +239,10 @@ mod tests {
             ApiError::AgentUnavailable.status_code(),
             StatusCode::SERVICE_UNAVAILABLE
         );
+        assert_eq!(
+            ApiError::ServiceUnavailable("scheduler".into()).status_code(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
     }
 
     #[test]
@@ -0,0 +1,299 @@
+//! Agent sharing endpoints.
+//!
+//! Owners grant collaborators a role on an agent (`viewer`, `operator` or
+//! `owner`) and revoke it again. Collaborators list the agents shared with
+//! them and may drop their own access.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use aura_swarm_auth::JwtValidator;
+use aura_swarm_control::{AgentGrant, AgentRole, ControlPlane, SharedAgent, UserId};
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::handlers::agents::{parse_agent_id, AgentResponse};
+use crate::state::GatewayState;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Request to grant a user access to an agent.
+#[derive(Debug, Deserialize)]
+pub struct GrantAccessBody {
+    /// Role to grant.
+    pub role: AgentRole,
+}
+
+/// Response for an access grant.
+#[derive(Debug, Serialize)]
+pub struct GrantResponse {
+    /// User the agent is shared with.
+    pub user_id: String,
+    /// Granted role.
+    pub role: AgentRole,
+    /// User who granted the access.
+    pub granted_by: String,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<AgentGrant> for GrantResponse {
+    fn from(grant: AgentGrant) -> Self {
+        Self {
+            user_id: grant.user_id.to_string(),
+            role: grant.role,
+            granted_by: grant.granted_by.to_string(),
+            created_at: grant.created_at,
+            updated_at: grant.updated_at,
+        }
+    }
+}
+
+/// Response for an agent shared with the caller.
+#[derive(Debug, Serialize)]
+pub struct SharedAgentResponse {
+    /// The shared agent.
+    #[serde(flatten)]
+    pub agent: AgentResponse,
+    /// The caller's role on the agent.
+    pub role: AgentRole,
+    /// User who shared the agent.
+    pub granted_by: String,
+}
+
+impl From<SharedAgent> for SharedAgentResponse {
+    fn from(shared: SharedAgent) -> Self {
+        Self {
+            agent: AgentResponse::from(shared.agent),
+            role: shared.role,
+            granted_by: shared.granted_by.to_string(),
+        }
+    }
+}
+
+/// Response for the shared agent list.
+#[derive(Debug, Serialize)]
+pub struct ListSharedAgentsResponse {
+    /// Agents shared with the caller.
+    pub agents: Vec<SharedAgentResponse>,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List agents other users have shared with the caller.
+///
+/// # Errors
+///
+/// Returns an error if the grants cannot be loaded.
+pub async fn list_shared_agents<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let shared = state.control.list_shared_agents(&user.caller()).await?;
+
+    Ok(Json(ListSharedAgentsResponse {
+        agents: shared.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// List the access grants on an agent.
+///
+/// # Errors
+///
+/// Returns an error if the agent is not found or the caller is not an owner.
+pub async fn list_grants<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(agent_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+    let grants = state
+        .control
+        .list_agent_grants(&user.caller(), &agent_id)
+        .await?;
+    let response: Vec<GrantResponse> = grants.into_iter().map(Into::into).collect();
+
+    Ok(Json(response))
+}
+
+/// Grant a user a role on an agent, replacing any existing grant.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The agent or user ID is invalid
+/// - The agent is not found or the caller is not an owner
+pub async fn grant_access<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path((agent_id, user_id)): Path<(String, String)>,
+    Json(body): Json<GrantAccessBody>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+    let user_id = parse_user_id(&user_id)?;
+    let grant = state
+        .control
+        .grant_agent_access(&user.caller(), &agent_id, &user_id, body.role)
+        .await?;
+
+    Ok(Json(GrantResponse::from(grant)))
+}
+
+/// Revoke a user's access to an agent.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The agent or user ID is invalid
+/// - The agent or grant is not found
+/// - The caller is neither an owner nor revoking their own access
+pub async fn revoke_access<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path((agent_id, user_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+    let user_id = parse_user_id(&user_id)?;
+    state
+        .control
+        .revoke_agent_access(&user.caller(), &agent_id, &user_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Parse a user ID from a path parameter.
+pub(crate) fn parse_user_id(s: &str) -> Result<UserId, ApiError> {
+    UserId::from_hex(s).map_err(|_| ApiError::BadRequest(format!("invalid user ID: {s}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+
+    use crate::testing::{TestGateway, TestUser};
+
+    #[tokio::test]
+    async fn owners_grant_and_list_access() {
+        let gateway = TestGateway::new();
+        let owner = TestUser::member(1, 1);
+        let viewer = TestUser::member(2, 1);
+        let operator = TestUser::member(3, 1);
+        let outsider = TestUser::member(4, 2);
+
+        let agent_id = gateway.create_agent(&owner, "shared").await;
+        let access = format!("/v1/agents/{agent_id}/access");
+        let operator_access = format!("{access}/{}", operator.user_id().to_hex());
+
+        let response = gateway
+            .put(&owner, &format!("{access}/{}", viewer.user_id().to_hex()))
+            .json(&json!({ "role": "viewer" }))
+            .await;
+        response.assert_status_ok();
+        let grant: Value = response.json();
+        assert_eq!(grant["role"], "viewer");
+        assert_eq!(grant["granted_by"], owner.user_id().to_hex());
+
+        // Viewers can't share the agent further
+        gateway
+            .put(&viewer, &operator_access)
+            .json(&json!({ "role": "operator" }))
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .put(&outsider, &operator_access)
+            .json(&json!({ "role": "operator" }))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        gateway
+            .put(&owner, &format!("{access}/not-a-user"))
+            .json(&json!({ "role": "operator" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        let response = gateway.get(&owner, &access).await;
+        response.assert_status_ok();
+        let grants: Value = response.json();
+        assert_eq!(grants.as_array().unwrap().len(), 1);
+        assert_eq!(grants[0]["user_id"], viewer.user_id().to_hex());
+
+        gateway
+            .get(&viewer, &access)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn grantees_see_shared_agents_and_may_leave() {
+        let gateway = TestGateway::new();
+        let owner = TestUser::member(1, 1);
+        let viewer = TestUser::member(2, 1);
+        let operator = TestUser::member(3, 1);
+
+        let agent_id = gateway.create_agent(&owner, "shared").await;
+        gateway.grant(&owner, &agent_id, &viewer, "viewer").await;
+        gateway
+            .grant(&owner, &agent_id, &operator, "operator")
+            .await;
+
+        let response = gateway.get(&viewer, "/v1/agents/shared").await;
+        response.assert_status_ok();
+        let shared: Value = response.json();
+        assert_eq!(shared["agents"][0]["agent_id"], agent_id.as_str());
+        assert_eq!(shared["agents"][0]["role"], "viewer");
+
+        // Grantees may drop their own access but not anyone else's
+        let access = format!("/v1/agents/{agent_id}/access");
+        gateway
+            .delete(
+                &viewer,
+                &format!("{access}/{}", operator.user_id().to_hex()),
+            )
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .delete(&viewer, &format!("{access}/{}", viewer.user_id().to_hex()))
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        gateway
+            .delete(&owner, &format!("{access}/{}", viewer.user_id().to_hex()))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+
+        let response = gateway.get(&viewer, "/v1/agents/shared").await;
+        assert_eq!(response.json::<Value>()["agents"], json!([]));
+    }
+}
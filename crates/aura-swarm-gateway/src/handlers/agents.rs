@@ -2,17 +2,24 @@
 //!
 //! This module provides handlers for agent CRUD operations and lifecycle management.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
-use axum::response::IntoResponse;
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
 use axum::Json;
 use chrono::{DateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
 
 use aura_swarm_auth::JwtValidator;
-use aura_swarm_control::{Agent, AgentSpec, AgentState, ControlPlane, CreateAgentRequest};
+use aura_swarm_control::{
+    Agent, AgentDeletion, AgentSpec, AgentSpecOverrides, AgentState, CloneAgentRequest,
+    ControlPlane, CreateAgentRequest, DeletionStatus, DeletionStep, HibernationCheckpoint,
+    LogEntry, LogOptions, RestartPolicy, MAX_AGENT_NAME_LEN,
+};
 use aura_swarm_core::AgentId;
 
 use crate::auth::AuthUser;
@@ -28,6 +35,11 @@ use crate::state::GatewayState;
 pub struct AgentResponse {
     /// Agent ID.
     pub agent_id: String,
+    /// Owner user ID.
+    pub user_id: String,
+    /// Namespace the agent belongs to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<String>,
     /// Human-readable name.
     pub name: String,
     /// Current status.
@@ -45,12 +57,33 @@ pub struct AgentResponse {
     /// Error message if agent failed (e.g., provisioning error).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// ID of the agent this one was cloned from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cloned_from: Option<String>,
+    /// Template the agent was created from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Agent labels.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Automatic restart policy.
+    pub restart_policy: RestartPolicy,
+    /// Automatic restarts since the agent was last started manually or ran stably.
+    pub restart_attempts: u32,
+    /// When the next automatic restart is due.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_restart_at: Option<DateTime<Utc>>,
+    /// Checkpoint recorded the last time the agent hibernated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hibernation: Option<HibernationCheckpoint>,
 }
 
 impl From<Agent> for AgentResponse {
     fn from(agent: Agent) -> Self {
         Self {
             agent_id: agent.agent_id.to_string(),
+            user_id: agent.user_id.to_string(),
+            namespace_id: agent.namespace_id.map(|id| id.to_string()),
             name: agent.name,
             status: agent.status,
             spec: Some(agent.spec),
@@ -58,6 +91,13 @@ impl From<Agent> for AgentResponse {
             updated_at: agent.updated_at,
             last_heartbeat_at: agent.last_heartbeat_at,
             error_message: agent.error_message,
+            cloned_from: agent.cloned_from.map(|id| id.to_string()),
+            template: agent.template,
+            labels: agent.labels,
+            restart_policy: agent.restart_policy,
+            restart_attempts: agent.restarts.attempts,
+            next_restart_at: agent.restarts.next_restart_at,
+            hibernation: agent.hibernation,
         }
     }
 }
@@ -77,6 +117,68 @@ pub struct CreateAgentBody {
     /// Optional resource specification.
     #[serde(default)]
     pub spec: Option<AgentSpec>,
+    /// Optional template to create the agent from (mutually exclusive with `spec`).
+    #[serde(default)]
+    pub template: Option<String>,
+    /// Per-field overrides applied to the template or spec.
+    #[serde(default)]
+    pub overrides: AgentSpecOverrides,
+    /// Labels for the agent.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Automatic restart policy (default: never).
+    #[serde(default)]
+    pub restart_policy: RestartPolicy,
+}
+
+/// Request to clone an agent.
+#[derive(Debug, Default, Deserialize)]
+pub struct CloneAgentBody {
+    /// Name for the clone. Defaults to the source name with a `-clone` suffix.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Response describing an agent deletion's progress.
+#[derive(Debug, Serialize)]
+pub struct DeletionResponse {
+    /// The agent being deleted.
+    pub agent_id: String,
+    /// Current status.
+    pub status: DeletionStatus,
+    /// Steps that have finished, in order.
+    pub completed_steps: Vec<DeletionStep>,
+    /// The step that failed on the last attempt.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failed_step: Option<DeletionStep>,
+    /// Why the last attempt failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Number of attempts so far.
+    pub attempts: u32,
+    /// When the deletion is next retried.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// When the deletion was requested.
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<AgentDeletion> for DeletionResponse {
+    fn from(deletion: AgentDeletion) -> Self {
+        Self {
+            agent_id: deletion.agent_id.to_string(),
+            status: deletion.status,
+            completed_steps: deletion.completed_steps,
+            failed_step: deletion.failed_step,
+            error: deletion.error,
+            attempts: deletion.attempts,
+            next_attempt_at: deletion.next_attempt_at,
+            created_at: deletion.created_at,
+            updated_at: deletion.updated_at,
+        }
+    }
 }
 
 /// Response for lifecycle operations (start, stop, etc.).
@@ -94,9 +196,15 @@ pub struct LogQuery {
     /// Number of lines to retrieve (default: 100).
     #[serde(default = "default_tail")]
     pub tail: u32,
-    /// Retrieve logs since this timestamp.
+    /// Retrieve logs since this timestamp (RFC 3339).
     #[serde(default)]
     pub since: Option<String>,
+    /// Retrieve logs up to this timestamp (RFC 3339).
+    #[serde(default)]
+    pub until: Option<String>,
+    /// Keep the connection open and stream new entries as Server-Sent Events.
+    #[serde(default)]
+    pub follow: bool,
 }
 
 const fn default_tail() -> u32 {
@@ -110,17 +218,6 @@ pub struct LogsResponse {
     pub logs: Vec<LogEntry>,
 }
 
-/// A single log entry.
-#[derive(Debug, Serialize)]
-pub struct LogEntry {
-    /// Timestamp of the log.
-    pub timestamp: DateTime<Utc>,
-    /// Log level.
-    pub level: String,
-    /// Log message.
-    pub message: String,
-}
-
 /// Response for agent status.
 #[derive(Debug, Serialize)]
 pub struct StatusResponse {
@@ -163,7 +260,7 @@ where
     C: ControlPlane + 'static,
     V: JwtValidator + 'static,
 {
-    let agents = state.control.list_agents(&user.user_id).await?;
+    let agents = state.control.list_agents(&user.caller()).await?;
 
     let response = ListAgentsResponse {
         agents: agents.into_iter().map(AgentResponse::from).collect(),
@@ -178,6 +275,7 @@ where
 ///
 /// Returns an error if:
 /// - The agent name is invalid
+/// - The template doesn't exist or the resolved spec exceeds resource limits
 /// - The user has reached their quota
 /// - The control plane operation fails
 pub async fn create_agent<C, V>(
@@ -189,31 +287,18 @@ where
     C: ControlPlane + 'static,
     V: JwtValidator + 'static,
 {
-    // Validate name
-    if body.name.is_empty() || body.name.len() > 64 {
-        return Err(ApiError::BadRequest(
-            "name must be 1-64 characters".to_string(),
-        ));
-    }
-
-    // Check for valid characters (alphanumeric + hyphens)
-    if !body
-        .name
-        .chars()
-        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
-    {
-        return Err(ApiError::BadRequest(
-            "name must contain only alphanumeric characters, hyphens, or underscores".to_string(),
-        ));
-    }
-
-    let request = if let Some(spec) = body.spec {
-        CreateAgentRequest::with_spec(body.name, spec)
-    } else {
-        CreateAgentRequest::new(body.name)
+    validate_name(&body.name)?;
+
+    let request = CreateAgentRequest {
+        name: body.name,
+        spec: body.spec,
+        template: body.template,
+        overrides: body.overrides,
+        labels: body.labels,
+        restart_policy: body.restart_policy,
     };
 
-    let agent = state.control.create_agent(&user.user_id, request).await?;
+    let agent = state.control.create_agent(&user.caller(), request).await?;
 
     Ok((StatusCode::CREATED, Json(AgentResponse::from(agent))))
 }
@@ -233,13 +318,17 @@ where
     V: JwtValidator + 'static,
 {
     let agent_id = parse_agent_id(&agent_id)?;
-    let agent = state.control.get_agent(&user.user_id, &agent_id).await?;
+    let agent = state.control.get_agent(&user.caller(), &agent_id).await?;
 
     Ok(Json(AgentResponse::from(agent)))
 }
 
 /// Delete an agent.
 ///
+/// Returns `204 No Content` once the agent is fully deleted. If a cleanup
+/// step fails, returns `202 Accepted` with the deletion's progress; it is
+/// retried in the background.
+///
 /// # Errors
 ///
 /// Returns an error if the agent is not found, the user doesn't own it,
@@ -254,9 +343,82 @@ where
     V: JwtValidator + 'static,
 {
     let agent_id = parse_agent_id(&agent_id)?;
-    state.control.delete_agent(&user.user_id, &agent_id).await?;
+    let deletion = state
+        .control
+        .delete_agent(&user.caller(), &agent_id)
+        .await?;
+
+    if deletion.status == DeletionStatus::Completed {
+        Ok(StatusCode::NO_CONTENT.into_response())
+    } else {
+        Ok((StatusCode::ACCEPTED, Json(DeletionResponse::from(deletion))).into_response())
+    }
+}
+
+/// Get the progress of an agent's deletion.
+///
+/// # Errors
+///
+/// Returns an error if no deletion was requested for the agent or the user
+/// may not see it.
+pub async fn get_deletion<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(agent_id): Path<String>,
+) -> Result<Json<DeletionResponse>, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+    let deletion = state
+        .control
+        .get_agent_deletion(&user.caller(), &agent_id)
+        .await?;
+
+    Ok(Json(DeletionResponse::from(deletion)))
+}
+
+/// Clone an agent, including its state directory.
+///
+/// The body is optional; an empty body clones with the default name. The
+/// clone is returned with `202 Accepted` while it is `provisioning`; its
+/// state is copied in the background before its pod starts.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The agent is not found or the user doesn't own it
+/// - The agent is not stopped or hibernating
+/// - The clone name is invalid
+/// - The user has reached their quota
+pub async fn clone_agent<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(agent_id): Path<String>,
+    body: Option<Json<CloneAgentBody>>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+    let Json(body) = body.unwrap_or_default();
+
+    if let Some(name) = &body.name {
+        validate_name(name)?;
+    }
+
+    let agent = state
+        .control
+        .clone_agent(
+            &user.caller(),
+            &agent_id,
+            CloneAgentRequest { name: body.name },
+        )
+        .await?;
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok((StatusCode::ACCEPTED, Json(AgentResponse::from(agent))))
 }
 
 /// Start an agent.
@@ -275,7 +437,7 @@ where
     V: JwtValidator + 'static,
 {
     let agent_id = parse_agent_id(&agent_id)?;
-    let agent = state.control.start_agent(&user.user_id, &agent_id).await?;
+    let agent = state.control.start_agent(&user.caller(), &agent_id).await?;
 
     Ok(Json(LifecycleResponse {
         agent_id: agent.agent_id.to_string(),
@@ -299,7 +461,7 @@ where
     V: JwtValidator + 'static,
 {
     let agent_id = parse_agent_id(&agent_id)?;
-    let agent = state.control.stop_agent(&user.user_id, &agent_id).await?;
+    let agent = state.control.stop_agent(&user.caller(), &agent_id).await?;
 
     Ok(Json(LifecycleResponse {
         agent_id: agent.agent_id.to_string(),
@@ -325,7 +487,7 @@ where
     let agent_id = parse_agent_id(&agent_id)?;
     let agent = state
         .control
-        .restart_agent(&user.user_id, &agent_id)
+        .restart_agent(&user.caller(), &agent_id)
         .await?;
 
     Ok(Json(LifecycleResponse {
@@ -352,7 +514,7 @@ where
     let agent_id = parse_agent_id(&agent_id)?;
     let agent = state
         .control
-        .hibernate_agent(&user.user_id, &agent_id)
+        .hibernate_agent(&user.caller(), &agent_id)
         .await?;
 
     Ok(Json(LifecycleResponse {
@@ -377,7 +539,7 @@ where
     V: JwtValidator + 'static,
 {
     let agent_id = parse_agent_id(&agent_id)?;
-    let agent = state.control.wake_agent(&user.user_id, &agent_id).await?;
+    let agent = state.control.wake_agent(&user.caller(), &agent_id).await?;
 
     Ok(Json(LifecycleResponse {
         agent_id: agent.agent_id.to_string(),
@@ -387,46 +549,59 @@ where
 
 /// Get agent logs.
 ///
-/// Note: This is a placeholder implementation. Real logs would come from
-/// the Kubernetes pod or a log aggregation service.
+/// Returns the most recent `tail` entries as JSON. With `follow`, the
+/// response is a Server-Sent Events stream of `log` events, each carrying a
+/// JSON entry, that stays open for new entries; a read failure is sent as an
+/// `error` event before the stream ends.
 ///
 /// # Errors
 ///
-/// Returns an error if the agent ID is invalid.
+/// Returns an error if the agent ID or query is invalid, the agent is not
+/// found, or the user cannot view it.
 pub async fn get_logs<C, V>(
-    State(_state): State<Arc<GatewayState<C, V>>>,
+    State(state): State<Arc<GatewayState<C, V>>>,
     user: AuthUser,
     Path(agent_id): Path<String>,
     axum::extract::Query(query): axum::extract::Query<LogQuery>,
-) -> Result<impl IntoResponse, ApiError>
+) -> Result<Response, ApiError>
 where
     C: ControlPlane + 'static,
     V: JwtValidator + 'static,
 {
-    // Validate agent ID format
-    parse_agent_id(&agent_id)?;
-
-    // Placeholder: In a real implementation, this would fetch logs from K8s or a log service
-    tracing::debug!(
-        agent_id = %agent_id,
-        user_id = %user.user_id,
-        tail = query.tail,
-        since = ?query.since,
-        "Fetching agent logs"
-    );
-
-    Ok(Json(LogsResponse { logs: vec![] }))
+    let agent_id = parse_agent_id(&agent_id)?;
+    let options = LogOptions {
+        lines: query.tail,
+        follow: query.follow,
+        since: query.since,
+        until: query.until,
+    };
+    let entries = state
+        .control
+        .agent_logs(&user.caller(), &agent_id, options)
+        .await?;
+
+    if !query.follow {
+        let logs: Vec<LogEntry> = entries.try_collect().await?;
+        return Ok(Json(LogsResponse { logs }).into_response());
+    }
+
+    let events = entries.map(|entry| match entry {
+        Ok(entry) => Event::default().event("log").json_data(entry),
+        Err(e) => Ok(Event::default().event("error").data(e.to_string())),
+    });
+    Ok(Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response())
 }
 
 /// Get agent status.
 ///
-/// Note: This is a placeholder implementation. Real status would come from
-/// the control plane with metrics from the scheduler.
+/// Resource usage comes from the cluster's metrics API via the scheduler and
+/// is zero while the agent is not running.
 ///
 /// # Errors
 ///
 /// Returns an error if the agent is not found or the user doesn't own it.
-#[allow(clippy::cast_sign_loss)]
 pub async fn get_status<C, V>(
     State(state): State<Arc<GatewayState<C, V>>>,
     user: AuthUser,
@@ -437,20 +612,19 @@ where
     V: JwtValidator + 'static,
 {
     let agent_id = parse_agent_id(&agent_id)?;
-    let agent = state.control.get_agent(&user.user_id, &agent_id).await?;
-
-    // Calculate uptime (placeholder - would come from pod start time)
-    // Safe: max(0) ensures non-negative
-    let uptime_seconds = (Utc::now() - agent.created_at).num_seconds().max(0) as u64;
+    let caller = user.caller();
+    let agent = state.control.get_agent(&caller, &agent_id).await?;
+    let status = state.control.get_agent_status(&caller, &agent_id).await?;
 
     Ok(Json(StatusResponse {
         status: agent.status,
-        uptime_seconds,
-        active_sessions: 0, // Placeholder
+        uptime_seconds: status.uptime_seconds,
+        active_sessions: status.active_sessions,
         last_heartbeat_at: agent.last_heartbeat_at,
         resource_usage: ResourceUsage {
-            cpu_percent: 0.0,
-            memory_mb: 0,
+            // Rounded to two decimal places
+            cpu_percent: (status.cpu_usage * 10_000.0).round() / 100.0,
+            memory_mb: status.memory_bytes / (1024 * 1024),
         },
     }))
 }
@@ -459,7 +633,188 @@ where
 // Helpers
 // =============================================================================
 
+/// Validate a user-supplied agent or template name.
+pub(crate) fn validate_name(name: &str) -> Result<(), ApiError> {
+    if name.is_empty() || name.len() > MAX_AGENT_NAME_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "name must be 1-{MAX_AGENT_NAME_LEN} characters"
+        )));
+    }
+
+    // Check for valid characters (alphanumeric + hyphens)
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err(ApiError::BadRequest(
+            "name must contain only alphanumeric characters, hyphens, or underscores".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
 /// Parse an agent ID from a string.
-fn parse_agent_id(s: &str) -> Result<AgentId, ApiError> {
+pub(crate) fn parse_agent_id(s: &str) -> Result<AgentId, ApiError> {
     AgentId::from_hex(s).map_err(|_| ApiError::BadRequest(format!("invalid agent ID: {s}")))
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+
+    use crate::testing::{TestGateway, TestUser};
+
+    #[tokio::test]
+    async fn agents_are_visible_to_their_owner_only() {
+        let gateway = TestGateway::new();
+        let owner = TestUser::member(1, 1);
+        let colleague = TestUser::member(2, 1);
+        let outsider = TestUser::member(3, 2);
+
+        let agent_id = gateway.create_agent(&owner, "agent").await;
+        let path = format!("/v1/agents/{agent_id}");
+
+        let response = gateway.get(&owner, &path).await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["name"], "agent");
+
+        gateway
+            .get(&colleague, &path)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .get(&outsider, &path)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+
+        let listed = gateway.get(&colleague, "/v1/agents").await;
+        assert_eq!(listed.json::<Value>()["agents"], json!([]));
+    }
+
+    #[tokio::test]
+    async fn clone_requires_an_owner_and_a_stopped_agent() {
+        let gateway = TestGateway::new();
+        let owner = TestUser::member(1, 1);
+        let operator = TestUser::member(2, 1);
+
+        let agent_id = gateway.create_agent(&owner, "source").await;
+        gateway
+            .grant(&owner, &agent_id, &operator, "operator")
+            .await;
+        let path = format!("/v1/agents/{agent_id}/clone");
+
+        // Still provisioning
+        gateway
+            .post(&owner, &path)
+            .await
+            .assert_status(StatusCode::CONFLICT);
+
+        gateway.set_status(&agent_id, "stopped").await;
+        gateway
+            .post(&operator, &path)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .post(&owner, &path)
+            .json(&json!({ "name": "bad name" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        let response = gateway
+            .post(&owner, &path)
+            .json(&json!({ "name": "copy" }))
+            .await;
+        response.assert_status(StatusCode::ACCEPTED);
+        let clone: Value = response.json();
+        assert_eq!(clone["name"], "copy");
+        assert_eq!(clone["cloned_from"], agent_id.as_str());
+        assert_eq!(clone["status"], "provisioning");
+    }
+
+    #[tokio::test]
+    async fn deleted_agents_report_their_deletion() {
+        let gateway = TestGateway::new();
+        let owner = TestUser::member(1, 1);
+        let viewer = TestUser::member(2, 1);
+
+        let agent_id = gateway.create_agent(&owner, "doomed").await;
+        gateway.grant(&owner, &agent_id, &viewer, "viewer").await;
+        let path = format!("/v1/agents/{agent_id}");
+        let deletion_path = format!("/v1/agents/{agent_id}/deletion");
+
+        gateway
+            .get(&owner, &deletion_path)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+
+        // Only stopped agents can be deleted, and only by an owner
+        gateway
+            .delete(&owner, &path)
+            .await
+            .assert_status(StatusCode::CONFLICT);
+        gateway.set_status(&agent_id, "stopped").await;
+        gateway
+            .delete(&viewer, &path)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+
+        gateway
+            .delete(&owner, &path)
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        gateway
+            .get(&owner, &path)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+
+        let response = gateway.get(&owner, &deletion_path).await;
+        response.assert_status_ok();
+        let deletion: Value = response.json();
+        assert_eq!(deletion["agent_id"], agent_id.as_str());
+        assert_eq!(deletion["status"], "completed");
+
+        gateway
+            .get(&TestUser::member(3, 2), &deletion_path)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn logs_and_status_need_view_access() {
+        let gateway = TestGateway::new();
+        let owner = TestUser::member(1, 1);
+        let viewer = TestUser::member(2, 1);
+        let stranger = TestUser::member(3, 1);
+
+        let agent_id = gateway.create_agent(&owner, "agent").await;
+        gateway.grant(&owner, &agent_id, &viewer, "viewer").await;
+
+        for path in [
+            format!("/v1/agents/{agent_id}/logs"),
+            format!("/v1/agents/{agent_id}/status"),
+        ] {
+            gateway.get(&viewer, &path).await.assert_status_ok();
+            gateway
+                .get(&stranger, &path)
+                .await
+                .assert_status(StatusCode::FORBIDDEN);
+        }
+
+        // Without a scheduler there are no logs
+        let response = gateway
+            .get(&owner, &format!("/v1/agents/{agent_id}/logs"))
+            .await;
+        assert_eq!(response.json::<Value>()["logs"], json!([]));
+        gateway
+            .get(&owner, &format!("/v1/agents/{agent_id}/logs?tail=0"))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        let response = gateway
+            .get(&owner, &format!("/v1/agents/{agent_id}/status"))
+            .await;
+        assert_eq!(response.json::<Value>()["status"], "provisioning");
+    }
+}
@@ -0,0 +1,237 @@
+//! Audit log endpoint.
+//!
+//! Platform administrators can query the audit log across all namespaces.
+//! Namespace admins can query events in their own namespace.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use aura_swarm_auth::JwtValidator;
+use aura_swarm_control::{AuditEvent, AuditOutcome, AuditQuery, AuditResource, ControlPlane};
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::handlers::access::parse_user_id;
+use crate::handlers::namespaces::admin_scope;
+use crate::state::GatewayState;
+
+/// Maximum number of events returned per request.
+const MAX_AUDIT_LIMIT: usize = 1000;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Query parameters for the audit log.
+#[derive(Debug, Deserialize)]
+pub struct AuditLogQuery {
+    /// Only events at or after this time.
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// Only events before this time.
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+    /// Only events by this user.
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Only events in this namespace (platform administrators only).
+    #[serde(default)]
+    pub namespace_id: Option<String>,
+    /// Number of events to return (default: 100, max: 1000).
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+const fn default_limit() -> usize {
+    100
+}
+
+/// Who performed an audited action.
+#[derive(Debug, Serialize)]
+pub struct AuditActorResponse {
+    /// Internal user ID (absent for system actions).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// Zero-ID identity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity_id: Option<String>,
+    /// Source IP address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    /// Client user agent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+}
+
+/// Response for an audit event.
+#[derive(Debug, Serialize)]
+pub struct AuditEventResponse {
+    /// Event ID.
+    pub event_id: String,
+    /// When the event occurred.
+    pub timestamp: DateTime<Utc>,
+    /// Event type, e.g. `agent.start`.
+    pub event_type: String,
+    /// Who performed the action.
+    pub actor: AuditActorResponse,
+    /// Namespace the action was performed in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<String>,
+    /// Resource the action applied to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<AuditResource>,
+    /// Action performed.
+    pub action: String,
+    /// Whether the action succeeded.
+    pub outcome: AuditOutcome,
+    /// Request ID.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Additional details.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, String>,
+}
+
+impl From<AuditEvent> for AuditEventResponse {
+    fn from(event: AuditEvent) -> Self {
+        Self {
+            event_id: event.event_id.to_string(),
+            timestamp: event.timestamp,
+            event_type: event.event_type,
+            actor: AuditActorResponse {
+                user_id: event.actor.user_id.map(|id| id.to_string()),
+                identity_id: event.actor.identity_id.map(|id| id.to_string()),
+                ip_address: event.actor.ip_address,
+                user_agent: event.actor.user_agent,
+            },
+            namespace_id: event.namespace_id.map(|id| id.to_string()),
+            resource: event.resource,
+            action: event.action,
+            outcome: event.outcome,
+            request_id: event.request_id,
+            details: event.details,
+        }
+    }
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Query the audit log, newest events first.
+///
+/// Namespace admins only see events in their own namespace.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The caller is neither a platform administrator nor a namespace admin
+/// - A namespace admin asks for another namespace
+/// - A parameter is invalid
+pub async fn list_audit_events<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let namespace_id = admin_scope(&state.config, &user, query.namespace_id.as_deref())?;
+
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from >= to {
+            return Err(ApiError::BadRequest("from must be before to".to_string()));
+        }
+    }
+
+    let audit_query = AuditQuery {
+        from: query.from,
+        to: query.to,
+        user_id: query.user_id.as_deref().map(parse_user_id).transpose()?,
+        namespace_id,
+        limit: query.limit.clamp(1, MAX_AUDIT_LIMIT),
+    };
+
+    let events = state.control.list_audit_events(audit_query).await?;
+    let response: Vec<AuditEventResponse> = events.into_iter().map(Into::into).collect();
+
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::Value;
+
+    use crate::testing::{TestGateway, TestUser};
+
+    /// The namespaces of the events in an audit log response.
+    fn namespaces(events: &Value) -> Vec<String> {
+        events
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|event| event["namespace_id"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn audit_log_is_scoped_by_admin_kind() {
+        let platform_admin = TestUser::member(1, 1);
+        let namespace_admin = TestUser::namespace_admin(2, 1);
+        let member = TestUser::member(3, 1);
+        let outsider = TestUser::member(4, 2);
+        let gateway = TestGateway::with_admin(&platform_admin);
+        let ours = member.namespace_id.to_string();
+        let theirs = outsider.namespace_id.to_string();
+
+        gateway.create_agent(&member, "ours").await;
+        gateway.create_agent(&outsider, "theirs").await;
+
+        gateway
+            .get(&member, "/v1/audit")
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+
+        let response = gateway.get(&namespace_admin, "/v1/audit").await;
+        response.assert_status_ok();
+        let seen = namespaces(&response.json());
+        assert!(seen.contains(&ours));
+        assert!(seen.iter().all(|ns| *ns == ours));
+        gateway
+            .get(&namespace_admin, &format!("/v1/audit?namespace_id={ours}"))
+            .await
+            .assert_status_ok();
+        gateway
+            .get(
+                &namespace_admin,
+                &format!("/v1/audit?namespace_id={theirs}"),
+            )
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+
+        let response = gateway.get(&platform_admin, "/v1/audit").await;
+        response.assert_status_ok();
+        let seen = namespaces(&response.json());
+        assert!(seen.contains(&ours));
+        assert!(seen.contains(&theirs));
+        let response = gateway
+            .get(&platform_admin, &format!("/v1/audit?namespace_id={theirs}"))
+            .await;
+        response.assert_status_ok();
+        let seen = namespaces(&response.json());
+        assert!(!seen.is_empty());
+        assert!(seen.iter().all(|ns| *ns == theirs));
+        gateway
+            .get(&platform_admin, "/v1/audit?namespace_id=nope")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+}
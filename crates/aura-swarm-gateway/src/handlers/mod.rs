@@ -2,8 +2,17 @@
 //!
 //! This module contains all the endpoint handlers for the gateway API.
 
+pub mod access;
 pub mod agents;
+pub mod audit;
 pub mod health;
 pub mod internal;
+pub mod namespaces;
+pub mod rollouts;
+pub mod schedules;
+pub mod secrets;
 pub mod sessions;
+pub mod templates;
+pub mod usage;
+pub mod webhooks;
 pub mod ws;
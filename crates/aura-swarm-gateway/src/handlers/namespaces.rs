@@ -0,0 +1,224 @@
+//! Namespace (tenant) endpoints.
+//!
+//! Namespace admins can see every agent in their tenant and its quota usage.
+//! Platform administrators set per-namespace quota overrides.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Deserialize;
+
+use aura_swarm_auth::JwtValidator;
+use aura_swarm_control::{ControlPlane, NamespaceId, SetNamespaceQuotaRequest};
+
+use crate::auth::AuthUser;
+use crate::config::GatewayConfig;
+use crate::error::ApiError;
+use crate::handlers::agents::{AgentResponse, ListAgentsResponse};
+use crate::state::GatewayState;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Request to set a namespace's quota overrides.
+#[derive(Debug, Deserialize)]
+pub struct SetNamespaceQuotaBody {
+    /// Maximum number of agents (omit to use the default).
+    #[serde(default)]
+    pub max_agents: Option<u32>,
+    /// Maximum number of active sessions (omit to use the default).
+    #[serde(default)]
+    pub max_sessions: Option<u32>,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List every agent in the caller's namespace.
+///
+/// # Errors
+///
+/// Returns an error if the caller is not a namespace admin.
+pub async fn list_namespace_agents<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agents = state.control.list_namespace_agents(&user.caller()).await?;
+
+    Ok(Json(ListAgentsResponse {
+        agents: agents.into_iter().map(AgentResponse::from).collect(),
+    }))
+}
+
+/// Get the caller's namespace usage and effective quotas.
+///
+/// # Errors
+///
+/// Returns an error if the caller is not a namespace admin.
+pub async fn get_namespace_usage<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let usage = state.control.get_namespace_usage(&user.caller()).await?;
+
+    Ok(Json(usage))
+}
+
+/// Set the quota overrides for a namespace.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The caller is not a platform administrator
+/// - The namespace ID is invalid
+pub async fn set_namespace_quota<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(namespace_id): Path<String>,
+    Json(body): Json<SetNamespaceQuotaBody>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    if !state.config.is_admin(&user.identity_id) {
+        return Err(ApiError::Forbidden);
+    }
+
+    let namespace_id: NamespaceId = namespace_id
+        .parse()
+        .map_err(|_| ApiError::BadRequest(format!("invalid namespace ID: {namespace_id}")))?;
+    let request = SetNamespaceQuotaRequest {
+        max_agents: body.max_agents,
+        max_sessions: body.max_sessions,
+    };
+
+    let quota = state
+        .control
+        .set_namespace_quota(&namespace_id, request)
+        .await?;
+
+    Ok(Json(quota))
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Resolve the namespace an administrative report may cover.
+///
+/// Platform administrators may ask for any namespace, or all of them.
+/// Namespace admins are limited to their own namespace.
+///
+/// # Errors
+///
+/// Returns `Forbidden` if the caller is neither, or if a namespace admin
+/// asks for another namespace, and `BadRequest` if the namespace ID is invalid.
+pub(crate) fn admin_scope(
+    config: &GatewayConfig,
+    user: &AuthUser,
+    requested: Option<&str>,
+) -> Result<Option<NamespaceId>, ApiError> {
+    let requested: Option<NamespaceId> = requested
+        .map(|id| {
+            id.parse()
+                .map_err(|_| ApiError::BadRequest(format!("invalid namespace ID: {id}")))
+        })
+        .transpose()?;
+
+    if config.is_admin(&user.identity_id) {
+        Ok(requested)
+    } else if user.namespace_admin && requested.is_none_or(|id| id == user.namespace_id) {
+        Ok(Some(user.namespace_id))
+    } else {
+        Err(ApiError::Forbidden)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+
+    use crate::testing::{TestGateway, TestUser};
+
+    #[tokio::test]
+    async fn namespace_admins_see_their_namespace() {
+        let namespace_admin = TestUser::namespace_admin(1, 1);
+        let member = TestUser::member(2, 1);
+        let outsider = TestUser::member(3, 2);
+        let gateway = TestGateway::new();
+
+        gateway.create_agent(&member, "ours").await;
+        gateway.create_agent(&outsider, "theirs").await;
+
+        gateway
+            .get(&member, "/v1/namespace/agents")
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .get(&member, "/v1/namespace/usage")
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+
+        let response = gateway.get(&namespace_admin, "/v1/namespace/agents").await;
+        response.assert_status_ok();
+        let agents: Value = response.json();
+        let agents = agents["agents"].as_array().unwrap();
+        assert_eq!(agents.len(), 1);
+        assert_eq!(agents[0]["name"], "ours");
+
+        let response = gateway.get(&namespace_admin, "/v1/namespace/usage").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["agents"], 1);
+    }
+
+    #[tokio::test]
+    async fn only_platform_admins_set_quotas() {
+        let platform_admin = TestUser::member(1, 2);
+        let namespace_admin = TestUser::namespace_admin(2, 1);
+        let gateway = TestGateway::with_admin(&platform_admin);
+        let path = format!(
+            "/v1/admin/namespaces/{}/quota",
+            namespace_admin.namespace_id
+        );
+        let quota = json!({ "max_agents": 1 });
+
+        gateway
+            .put(&namespace_admin, &path)
+            .json(&quota)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .put(&platform_admin, "/v1/admin/namespaces/nope/quota")
+            .json(&quota)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        gateway
+            .put(&platform_admin, &path)
+            .json(&quota)
+            .await
+            .assert_status_ok();
+
+        let response = gateway.get(&namespace_admin, "/v1/namespace/usage").await;
+        assert_eq!(response.json::<Value>()["max_agents"], 1);
+        gateway.create_agent(&namespace_admin, "first").await;
+        gateway
+            .post(&namespace_admin, "/v1/agents")
+            .json(&json!({ "name": "second" }))
+            .await
+            .assert_status(StatusCode::CONFLICT);
+    }
+}
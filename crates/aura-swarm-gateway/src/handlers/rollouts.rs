@@ -0,0 +1,281 @@
+//! Runtime version rollout endpoints.
+//!
+//! Platform administrators roll a new runtime version out to every agent
+//! matching a selector. The control plane's rollout controller restarts the
+//! agents in batches; these endpoints start, inspect, cancel and roll back
+//! rollouts.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::Serialize;
+
+use aura_swarm_auth::JwtValidator;
+use aura_swarm_control::{ControlPlane, CreateRolloutRequest, Rollout, RolloutId};
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::state::GatewayState;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Response for the rollout list.
+#[derive(Debug, Serialize)]
+pub struct ListRolloutsResponse {
+    /// All rollouts, newest first.
+    pub rollouts: Vec<Rollout>,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Start a rollout.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The caller is not a platform administrator
+/// - The request is invalid or matches no agents
+/// - Another rollout is still in progress
+pub async fn create_rollout<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Json(request): Json<CreateRolloutRequest>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    require_admin(&state, &user)?;
+
+    let rollout = state
+        .control
+        .create_rollout(&user.identity_id, request)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(rollout)))
+}
+
+/// List all rollouts.
+///
+/// # Errors
+///
+/// Returns an error if the caller is not a platform administrator.
+pub async fn list_rollouts<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    require_admin(&state, &user)?;
+
+    let rollouts = state.control.list_rollouts().await?;
+
+    Ok(Json(ListRolloutsResponse { rollouts }))
+}
+
+/// Get a rollout and the progress of each targeted agent.
+///
+/// # Errors
+///
+/// Returns an error if the caller is not a platform administrator or the
+/// rollout is not found.
+pub async fn get_rollout<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(rollout_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    require_admin(&state, &user)?;
+
+    let rollout = state
+        .control
+        .get_rollout(&parse_rollout_id(&rollout_id)?)
+        .await?;
+
+    Ok(Json(rollout))
+}
+
+/// Cancel an in-progress rollout.
+///
+/// # Errors
+///
+/// Returns an error if the caller is not a platform administrator or the
+/// rollout is not found or not in progress.
+pub async fn cancel_rollout<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(rollout_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    require_admin(&state, &user)?;
+
+    let rollout = state
+        .control
+        .cancel_rollout(&parse_rollout_id(&rollout_id)?)
+        .await?;
+
+    Ok(Json(rollout))
+}
+
+/// Roll the agents a rollout updated back to their previous versions.
+///
+/// # Errors
+///
+/// Returns an error if the caller is not a platform administrator or the
+/// rollout is not found or was already rolled back.
+pub async fn rollback_rollout<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(rollout_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    require_admin(&state, &user)?;
+
+    let rollout = state
+        .control
+        .rollback_rollout(&parse_rollout_id(&rollout_id)?)
+        .await?;
+
+    Ok(Json(rollout))
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Reject callers who are not platform administrators.
+fn require_admin<C, V>(state: &GatewayState<C, V>, user: &AuthUser) -> Result<(), ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    if state.config.is_admin(&user.identity_id) {
+        Ok(())
+    } else {
+        Err(ApiError::Forbidden)
+    }
+}
+
+/// Parse a rollout ID from a path segment.
+fn parse_rollout_id(rollout_id: &str) -> Result<RolloutId, ApiError> {
+    rollout_id
+        .parse()
+        .map_err(|_| ApiError::BadRequest(format!("invalid rollout ID: {rollout_id}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+
+    use crate::testing::{TestGateway, TestUser};
+
+    #[tokio::test]
+    async fn rollouts_are_for_platform_admins_only() {
+        let admin = TestUser::member(1, 1);
+        let namespace_admin = TestUser::namespace_admin(2, 1);
+        let gateway = TestGateway::with_admin(&admin);
+        let request = json!({ "target_version": "v2" });
+
+        for user in [&namespace_admin, &TestUser::member(3, 1)] {
+            gateway
+                .get(user, "/v1/admin/rollouts")
+                .await
+                .assert_status(StatusCode::FORBIDDEN);
+            gateway
+                .post(user, "/v1/admin/rollouts")
+                .json(&request)
+                .await
+                .assert_status(StatusCode::FORBIDDEN);
+        }
+
+        gateway
+            .post(&admin, "/v1/admin/rollouts")
+            .json(&request)
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        gateway.create_agent(&namespace_admin, "agent").await;
+
+        let response = gateway
+            .post(&admin, "/v1/admin/rollouts")
+            .json(&request)
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let rollout: Value = response.json();
+        assert_eq!(rollout["status"], "in_progress");
+        assert_eq!(rollout["targets"].as_array().unwrap().len(), 1);
+        gateway
+            .post(&admin, "/v1/admin/rollouts")
+            .json(&request)
+            .await
+            .assert_status(StatusCode::CONFLICT);
+
+        let path = format!(
+            "/v1/admin/rollouts/{}",
+            rollout["rollout_id"].as_str().unwrap()
+        );
+        gateway
+            .get(&namespace_admin, &path)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway.get(&admin, &path).await.assert_status_ok();
+        gateway
+            .get(&admin, "/v1/admin/rollouts/not-an-id")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        gateway
+            .get(&admin, &format!("/v1/admin/rollouts/{}", uuid::Uuid::nil()))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        let response = gateway.get(&admin, "/v1/admin/rollouts").await;
+        assert_eq!(
+            response.json::<Value>()["rollouts"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        gateway
+            .post(&namespace_admin, &format!("{path}/cancel"))
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        let response = gateway.post(&admin, &format!("{path}/cancel")).await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["status"], "cancelled");
+        gateway
+            .post(&admin, &format!("{path}/cancel"))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        gateway
+            .post(&namespace_admin, &format!("{path}/rollback"))
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        let response = gateway.post(&admin, &format!("{path}/rollback")).await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["status"], "rolled_back");
+        gateway
+            .post(&admin, &format!("{path}/rollback"))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,269 @@
+//! Agent schedule endpoints.
+//!
+//! This module provides handlers for managing an agent's recurring
+//! wake/hibernate schedule. Responses include the next few planned runs so
+//! clients can show what the schedule will do.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use aura_swarm_auth::JwtValidator;
+use aura_swarm_control::schedule;
+use aura_swarm_control::{
+    AgentSchedule, ControlPlane, MissedRunPolicy, ScheduleAction, ScheduleRule, ScheduleRun,
+    SetScheduleRequest,
+};
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::handlers::agents::parse_agent_id;
+use crate::state::GatewayState;
+
+/// Number of upcoming runs included in schedule responses.
+const UPCOMING_RUNS: usize = 5;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// A planned schedule run.
+#[derive(Debug, Serialize)]
+pub struct PlannedRun {
+    /// Action that will be performed.
+    pub action: ScheduleAction,
+    /// When the action will fire.
+    pub at: DateTime<Utc>,
+}
+
+/// Response for an agent schedule.
+#[derive(Debug, Serialize)]
+pub struct ScheduleResponse {
+    /// Agent ID.
+    pub agent_id: String,
+    /// IANA time zone the cron rules are evaluated in.
+    pub timezone: String,
+    /// Cron rules.
+    pub rules: Vec<ScheduleRule>,
+    /// Whether the schedule is active.
+    pub enabled: bool,
+    /// How runs missed during an outage are handled.
+    pub missed_runs: MissedRunPolicy,
+    /// Most recent run, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<ScheduleRun>,
+    /// Upcoming runs (empty when the schedule is disabled).
+    pub next_runs: Vec<PlannedRun>,
+    /// Last update timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ScheduleResponse {
+    fn new(schedule: AgentSchedule, now: DateTime<Utc>) -> Result<Self, ApiError> {
+        let next_runs = if schedule.enabled {
+            schedule::next_runs(&schedule, now, UPCOMING_RUNS)?
+                .into_iter()
+                .map(|(action, at)| PlannedRun { action, at })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            agent_id: schedule.agent_id.to_string(),
+            timezone: schedule.timezone,
+            rules: schedule.rules,
+            enabled: schedule.enabled,
+            missed_runs: schedule.missed_runs,
+            last_run: schedule.last_run,
+            next_runs,
+            updated_at: schedule.updated_at,
+        })
+    }
+}
+
+/// Request to set an agent schedule.
+#[derive(Debug, Deserialize)]
+pub struct SetScheduleBody {
+    /// IANA time zone (default: UTC).
+    #[serde(default)]
+    pub timezone: Option<String>,
+    /// Cron rules.
+    pub rules: Vec<ScheduleRule>,
+    /// Whether the schedule is active (default: true).
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// How runs missed during an outage are handled (default: run latest).
+    #[serde(default)]
+    pub missed_runs: MissedRunPolicy,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Get an agent's schedule, including upcoming runs.
+///
+/// # Errors
+///
+/// Returns an error if the agent is not found, the user doesn't own it,
+/// or the agent has no schedule.
+pub async fn get_schedule<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(agent_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+    let schedule = state
+        .control
+        .get_schedule(&user.caller(), &agent_id)
+        .await?;
+
+    Ok(Json(ScheduleResponse::new(schedule, Utc::now())?))
+}
+
+/// Set (create or replace) an agent's schedule.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The agent is not found or the user doesn't own it
+/// - A cron expression or the time zone is invalid
+pub async fn set_schedule<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(agent_id): Path<String>,
+    Json(body): Json<SetScheduleBody>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+
+    let mut request =
+        SetScheduleRequest::new(body.timezone.as_deref().unwrap_or("UTC"), body.rules);
+    request.enabled = body.enabled.unwrap_or(true);
+    request.missed_runs = body.missed_runs;
+
+    let schedule = state
+        .control
+        .set_schedule(&user.caller(), &agent_id, request)
+        .await?;
+
+    Ok(Json(ScheduleResponse::new(schedule, Utc::now())?))
+}
+
+/// Remove an agent's schedule.
+///
+/// # Errors
+///
+/// Returns an error if the agent is not found, the user doesn't own it,
+/// or the agent has no schedule.
+pub async fn delete_schedule<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(agent_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+    state
+        .control
+        .delete_schedule(&user.caller(), &agent_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+
+    use crate::testing::{TestGateway, TestUser};
+
+    fn working_hours() -> Value {
+        json!({
+            "timezone": "Europe/Berlin",
+            "rules": [
+                { "action": "wake", "cron": "0 8 * * Mon-Fri" },
+                { "action": "hibernate", "cron": "0 19 * * Mon-Fri" }
+            ]
+        })
+    }
+
+    #[tokio::test]
+    async fn operators_set_schedules_and_viewers_read_them() {
+        let gateway = TestGateway::new();
+        let owner = TestUser::member(1, 1);
+        let operator = TestUser::member(2, 1);
+        let viewer = TestUser::member(3, 1);
+        let outsider = TestUser::member(4, 2);
+
+        let agent_id = gateway.create_agent(&owner, "agent").await;
+        gateway
+            .grant(&owner, &agent_id, &operator, "operator")
+            .await;
+        gateway.grant(&owner, &agent_id, &viewer, "viewer").await;
+        let path = format!("/v1/agents/{agent_id}/schedule");
+
+        gateway
+            .get(&viewer, &path)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        gateway
+            .put(&viewer, &path)
+            .json(&working_hours())
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .put(&outsider, &path)
+            .json(&working_hours())
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        gateway
+            .put(&operator, &path)
+            .json(&json!({ "rules": [{ "action": "wake", "cron": "* * * * * *" }] }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        let response = gateway.put(&operator, &path).json(&working_hours()).await;
+        response.assert_status_ok();
+        let schedule: Value = response.json();
+        assert_eq!(schedule["timezone"], "Europe/Berlin");
+        assert_eq!(schedule["enabled"], true);
+        assert!(!schedule["next_runs"].as_array().unwrap().is_empty());
+
+        let response = gateway.get(&viewer, &path).await;
+        response.assert_status_ok();
+        assert_eq!(
+            response.json::<Value>()["rules"].as_array().unwrap().len(),
+            2
+        );
+
+        gateway
+            .delete(&viewer, &path)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .delete(&operator, &path)
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        gateway
+            .get(&owner, &path)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+}
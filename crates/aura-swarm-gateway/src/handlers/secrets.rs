@@ -0,0 +1,222 @@
+//! Agent secret endpoints.
+//!
+//! Owners attach secrets to an agent; each is exposed to the agent as an
+//! environment variable of the same name the next time it starts. Values are
+//! write-only: responses never include them.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use aura_swarm_auth::JwtValidator;
+use aura_swarm_control::{AgentSecret, ControlPlane};
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::handlers::agents::parse_agent_id;
+use crate::state::GatewayState;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Request to set a secret's value.
+#[derive(Deserialize)]
+pub struct SetSecretBody {
+    /// The secret value.
+    pub value: String,
+}
+
+impl std::fmt::Debug for SetSecretBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SetSecretBody")
+            .field("value", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Response for an agent secret, without its value.
+#[derive(Debug, Serialize)]
+pub struct SecretResponse {
+    /// Secret name, also the environment variable name.
+    pub name: String,
+    /// User who last set the value.
+    pub updated_by: String,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<AgentSecret> for SecretResponse {
+    fn from(secret: AgentSecret) -> Self {
+        Self {
+            name: secret.name,
+            updated_by: secret.updated_by.to_string(),
+            created_at: secret.created_at,
+            updated_at: secret.updated_at,
+        }
+    }
+}
+
+/// Response for the secret list.
+#[derive(Debug, Serialize)]
+pub struct ListSecretsResponse {
+    /// The agent's secrets, ordered by name.
+    pub secrets: Vec<SecretResponse>,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List an agent's secrets.
+///
+/// # Errors
+///
+/// Returns an error if the agent is not found or the caller is not an owner.
+pub async fn list_secrets<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(agent_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+    let secrets = state
+        .control
+        .list_agent_secrets(&user.caller(), &agent_id)
+        .await?;
+
+    Ok(Json(ListSecretsResponse {
+        secrets: secrets.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Create or replace an agent secret.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The agent ID, secret name or value is invalid
+/// - The agent is not found or the caller is not an owner
+/// - Secrets are not enabled or the agent has too many
+pub async fn set_secret<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path((agent_id, name)): Path<(String, String)>,
+    Json(body): Json<SetSecretBody>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+    let secret = state
+        .control
+        .set_agent_secret(&user.caller(), &agent_id, &name, &body.value)
+        .await?;
+
+    Ok(Json(SecretResponse::from(secret)))
+}
+
+/// Delete an agent secret.
+///
+/// # Errors
+///
+/// Returns an error if the agent or secret is not found or the caller is
+/// not an owner.
+pub async fn delete_secret<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path((agent_id, name)): Path<(String, String)>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let agent_id = parse_agent_id(&agent_id)?;
+    state
+        .control
+        .delete_agent_secret(&user.caller(), &agent_id, &name)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+
+    use crate::testing::{TestGateway, TestUser};
+
+    #[tokio::test]
+    async fn only_owners_manage_secrets() {
+        let gateway = TestGateway::new();
+        let owner = TestUser::member(1, 1);
+        let operator = TestUser::member(2, 1);
+        let outsider = TestUser::member(3, 2);
+
+        let agent_id = gateway.create_agent(&owner, "agent").await;
+        gateway
+            .grant(&owner, &agent_id, &operator, "operator")
+            .await;
+        let secrets = format!("/v1/agents/{agent_id}/secrets");
+        let token = format!("{secrets}/GITHUB_TOKEN");
+
+        gateway
+            .put(&operator, &token)
+            .json(&json!({ "value": "ghp_x" }))
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .put(&outsider, &token)
+            .json(&json!({ "value": "ghp_x" }))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        gateway
+            .get(&operator, &secrets)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .put(&owner, &format!("{secrets}/AGENT_ID"))
+            .json(&json!({ "value": "spoofed" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        let response = gateway
+            .put(&owner, &token)
+            .json(&json!({ "value": "ghp_x" }))
+            .await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["name"], "GITHUB_TOKEN");
+
+        // Values are never returned
+        let response = gateway.get(&owner, &secrets).await;
+        response.assert_status_ok();
+        let listed: Value = response.json();
+        assert_eq!(listed["secrets"][0]["name"], "GITHUB_TOKEN");
+        assert!(!response.text().contains("ghp_x"));
+
+        gateway
+            .delete(&operator, &token)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .delete(&owner, &token)
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        gateway
+            .delete(&owner, &token)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+}
@@ -50,6 +50,8 @@ pub struct SessionResponse {
     /// When the session was closed (if closed).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub closed_at: Option<DateTime<Utc>>,
+    /// When traffic last flowed over the session.
+    pub last_activity_at: DateTime<Utc>,
 }
 
 impl From<Session> for SessionResponse {
@@ -60,6 +62,7 @@ impl From<Session> for SessionResponse {
             status: session.status,
             created_at: session.created_at,
             closed_at: session.closed_at,
+            last_activity_at: session.last_active_at(),
         }
     }
 }
@@ -96,7 +99,7 @@ where
 
     let session = state
         .control
-        .create_session(&user.user_id, &agent_id)
+        .create_session(&user.caller(), &agent_id)
         .await?;
 
     let response = CreateSessionResponse {
@@ -127,7 +130,7 @@ where
 
     let session = state
         .control
-        .get_session(&user.user_id, &session_id)
+        .get_session(&user.caller(), &session_id)
         .await?;
 
     Ok(Json(SessionResponse::from(session)))
@@ -151,7 +154,7 @@ where
 
     let sessions = state
         .control
-        .list_sessions(&user.user_id, &agent_id)
+        .list_sessions(&user.caller(), &agent_id)
         .await?;
 
     let response = ListSessionsResponse {
@@ -179,7 +182,7 @@ where
 
     state
         .control
-        .close_session(&user.user_id, &session_id)
+        .close_session(&user.caller(), &session_id)
         .await?;
 
     Ok(StatusCode::NO_CONTENT)
@@ -0,0 +1,338 @@
+//! Agent template endpoints.
+//!
+//! This module provides handlers for listing, creating, reading and deleting
+//! agent templates.
+//! Admin-defined (global) templates are visible to everyone; user-defined
+//! templates are visible only to their owner.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use aura_swarm_auth::JwtValidator;
+use aura_swarm_control::{AgentSpec, AgentTemplate, ControlPlane, CreateTemplateRequest};
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::handlers::agents::validate_name;
+use crate::state::GatewayState;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Visibility scope of a template.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateScope {
+    /// Admin-defined, visible to all users.
+    Global,
+    /// User-defined, visible only to its owner.
+    #[default]
+    User,
+}
+
+/// Response for a single template.
+#[derive(Debug, Serialize)]
+pub struct TemplateResponse {
+    /// Template name.
+    pub name: String,
+    /// Visibility scope.
+    pub scope: TemplateScope,
+    /// Human-readable description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Spec applied to agents created from the template.
+    pub spec: AgentSpec,
+    /// Labels applied to agents created from the template.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<AgentTemplate> for TemplateResponse {
+    fn from(template: AgentTemplate) -> Self {
+        Self {
+            name: template.name,
+            scope: if template.owner.is_some() {
+                TemplateScope::User
+            } else {
+                TemplateScope::Global
+            },
+            description: template.description,
+            spec: template.spec,
+            labels: template.labels,
+            created_at: template.created_at,
+        }
+    }
+}
+
+/// Response for template list.
+#[derive(Debug, Serialize)]
+pub struct ListTemplatesResponse {
+    /// List of templates.
+    pub templates: Vec<TemplateResponse>,
+}
+
+/// Request to create a template.
+#[derive(Debug, Deserialize)]
+pub struct CreateTemplateBody {
+    /// Template name.
+    pub name: String,
+    /// Visibility scope (default: user). Global templates require an admin.
+    #[serde(default)]
+    pub scope: TemplateScope,
+    /// Human-readable description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Optional spec. Uses defaults if not provided.
+    #[serde(default)]
+    pub spec: Option<AgentSpec>,
+    /// Labels applied to agents created from the template.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// Query parameters selecting the scope of a template to delete.
+#[derive(Debug, Default, Deserialize)]
+pub struct TemplateScopeQuery {
+    /// Scope of the template (default: user). Global templates require an admin.
+    #[serde(default)]
+    pub scope: TemplateScope,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List templates visible to the authenticated user.
+///
+/// # Errors
+///
+/// Returns an error if the control plane operation fails.
+pub async fn list_templates<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let templates = state.control.list_templates(&user.user_id).await?;
+
+    Ok(Json(ListTemplatesResponse {
+        templates: templates.into_iter().map(TemplateResponse::from).collect(),
+    }))
+}
+
+/// Create a template.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The template name is invalid
+/// - A global template is requested by a non-admin
+/// - A template with the same name already exists in the scope
+/// - The spec exceeds resource limits
+pub async fn create_template<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Json(body): Json<CreateTemplateBody>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    validate_name(&body.name)?;
+
+    let owner = match body.scope {
+        TemplateScope::Global => {
+            if !state.config.is_admin(&user.identity_id) {
+                return Err(ApiError::Forbidden);
+            }
+            None
+        }
+        TemplateScope::User => Some(&user.user_id),
+    };
+
+    let request = CreateTemplateRequest {
+        name: body.name,
+        description: body.description,
+        spec: body.spec,
+        labels: body.labels,
+    };
+
+    let template = state.control.create_template(owner, request).await?;
+
+    Ok((StatusCode::CREATED, Json(TemplateResponse::from(template))))
+}
+
+/// Get a template visible to the authenticated user.
+///
+/// The user's own template takes precedence over an admin-defined one of
+/// the same name, as when creating an agent from it.
+///
+/// # Errors
+///
+/// Returns an error if no visible template has that name.
+pub async fn get_template<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let template = state.control.get_template(&user.user_id, &name).await?;
+
+    Ok(Json(TemplateResponse::from(template)))
+}
+
+/// Delete a template.
+///
+/// Deletes the user's own template unless `?scope=global` is given.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - A global template is requested by a non-admin
+/// - The template doesn't exist in the scope
+pub async fn delete_template<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(name): Path<String>,
+    Query(query): Query<TemplateScopeQuery>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let owner = match query.scope {
+        TemplateScope::Global => {
+            if !state.config.is_admin(&user.identity_id) {
+                return Err(ApiError::Forbidden);
+            }
+            None
+        }
+        TemplateScope::User => Some(&user.user_id),
+    };
+
+    state.control.delete_template(owner, &name).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+
+    use crate::testing::{TestGateway, TestUser};
+
+    #[tokio::test]
+    async fn only_admins_manage_global_templates() {
+        let admin = TestUser::member(1, 1);
+        let member = TestUser::member(2, 1);
+        let gateway = TestGateway::with_admin(&admin);
+        let global = json!({ "name": "base", "scope": "global", "description": "global" });
+
+        gateway
+            .post(&member, "/v1/templates")
+            .json(&global)
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        let response = gateway.post(&admin, "/v1/templates").json(&global).await;
+        response.assert_status(StatusCode::CREATED);
+        assert_eq!(response.json::<Value>()["scope"], "global");
+        gateway
+            .post(&admin, "/v1/templates")
+            .json(&global)
+            .await
+            .assert_status(StatusCode::CONFLICT);
+
+        let response = gateway.get(&member, "/v1/templates/base").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["scope"], "global");
+
+        gateway
+            .delete(&member, "/v1/templates/base?scope=global")
+            .await
+            .assert_status(StatusCode::FORBIDDEN);
+        gateway
+            .delete(&admin, "/v1/templates/base?scope=global")
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        gateway
+            .get(&member, "/v1/templates/base")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn user_templates_are_private_and_shadow_global_ones() {
+        let admin = TestUser::member(1, 1);
+        let member = TestUser::member(2, 1);
+        let other = TestUser::member(3, 1);
+        let gateway = TestGateway::with_admin(&admin);
+
+        gateway
+            .post(&admin, "/v1/templates")
+            .json(&json!({ "name": "base", "scope": "global", "description": "global" }))
+            .await
+            .assert_status(StatusCode::CREATED);
+        gateway
+            .post(&member, "/v1/templates")
+            .json(&json!({ "name": "base", "description": "mine" }))
+            .await
+            .assert_status(StatusCode::CREATED);
+        gateway
+            .post(&member, "/v1/templates")
+            .json(&json!({ "name": "Not A Name" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        let response = gateway.get(&member, "/v1/templates/base").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["description"], "mine");
+        let response = gateway.get(&other, "/v1/templates/base").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["description"], "global");
+
+        let response = gateway.get(&member, "/v1/templates").await;
+        response.assert_status_ok();
+        assert_eq!(
+            response.json::<Value>()["templates"]
+                .as_array()
+                .unwrap()
+                .len(),
+            2
+        );
+        let response = gateway.get(&other, "/v1/templates").await;
+        assert_eq!(
+            response.json::<Value>()["templates"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        gateway
+            .delete(&other, "/v1/templates/base")
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        gateway
+            .delete(&member, "/v1/templates/base")
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        let response = gateway.get(&member, "/v1/templates/base").await;
+        assert_eq!(response.json::<Value>()["description"], "global");
+    }
+}
@@ -0,0 +1,281 @@
+//! Usage reporting endpoint.
+//!
+//! Reports metered agent runtime for chargeback. Platform administrators can
+//! report on any namespace; namespace admins on their own. Reports are JSON
+//! by default and CSV with `format=csv` or `Accept: text/csv`.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+use axum::extract::{Query, State};
+use axum::http::{header, HeaderMap};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use aura_swarm_auth::JwtValidator;
+use aura_swarm_control::{AgentState, ControlPlane, UsageGroupBy, UsageQuery, UsageSummary};
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::handlers::access::parse_user_id;
+use crate::handlers::namespaces::admin_scope;
+use crate::state::GatewayState;
+
+/// Default report period when `from` is omitted (days).
+const DEFAULT_PERIOD_DAYS: i64 = 30;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Query parameters for a usage report.
+#[derive(Debug, Deserialize)]
+pub struct UsageReportQuery {
+    /// Start of the report (default: 30 days before `to`).
+    #[serde(default)]
+    pub from: Option<DateTime<Utc>>,
+    /// End of the report (default: now).
+    #[serde(default)]
+    pub to: Option<DateTime<Utc>>,
+    /// Comma-separated dimensions: `hour`, `namespace`, `user`, `state`.
+    #[serde(default)]
+    pub group_by: Option<String>,
+    /// Only usage in this namespace (platform administrators only).
+    #[serde(default)]
+    pub namespace_id: Option<String>,
+    /// Only usage of agents owned by this user.
+    #[serde(default)]
+    pub user_id: Option<String>,
+    /// Response format: `json` (default) or `csv`.
+    #[serde(default)]
+    pub format: Option<String>,
+}
+
+/// One row of a usage report.
+#[derive(Debug, Serialize)]
+pub struct UsageRowResponse {
+    /// Hour the row covers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hour: Option<DateTime<Utc>>,
+    /// Namespace the row covers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<String>,
+    /// Agent owner the row covers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<String>,
+    /// Agent state the row covers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<AgentState>,
+    /// Agent-seconds.
+    pub agent_seconds: u64,
+    /// CPU millicore-seconds.
+    pub cpu_millicore_seconds: u64,
+    /// Memory megabyte-seconds.
+    pub memory_mb_seconds: u64,
+}
+
+impl From<UsageSummary> for UsageRowResponse {
+    fn from(row: UsageSummary) -> Self {
+        Self {
+            hour: row.hour,
+            namespace_id: row.namespace_id.map(|id| id.to_string()),
+            user_id: row.user_id.map(|id| id.to_string()),
+            state: row.state,
+            agent_seconds: row.agent_seconds,
+            cpu_millicore_seconds: row.cpu_millicore_seconds,
+            memory_mb_seconds: row.memory_mb_seconds,
+        }
+    }
+}
+
+/// Response for a usage report.
+#[derive(Debug, Serialize)]
+pub struct UsageReportResponse {
+    /// Start of the report.
+    pub from: DateTime<Utc>,
+    /// End of the report.
+    pub to: DateTime<Utc>,
+    /// Dimensions the rows are grouped by.
+    pub group_by: Vec<UsageGroupBy>,
+    /// Report rows.
+    pub usage: Vec<UsageRowResponse>,
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// Report metered agent usage.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The caller is neither a platform administrator nor a namespace admin
+/// - A namespace admin asks for another namespace
+/// - A parameter is invalid
+pub async fn get_usage<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    headers: HeaderMap,
+    Query(query): Query<UsageReportQuery>,
+) -> Result<Response, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let namespace_id = admin_scope(&state.config, &user, query.namespace_id.as_deref())?;
+    let group_by = parse_group_by(query.group_by.as_deref())?;
+    let csv = wants_csv(query.format.as_deref(), &headers)?;
+
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query
+        .from
+        .unwrap_or_else(|| to - Duration::days(DEFAULT_PERIOD_DAYS));
+
+    let usage_query = UsageQuery {
+        from,
+        to,
+        namespace_id,
+        user_id: query.user_id.as_deref().map(parse_user_id).transpose()?,
+        group_by: group_by.clone(),
+    };
+    let rows = state.control.get_usage(usage_query).await?;
+
+    if csv {
+        let body = to_csv(&group_by, &rows);
+        return Ok((
+            [
+                (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+                (
+                    header::CONTENT_DISPOSITION,
+                    "attachment; filename=\"usage.csv\"",
+                ),
+            ],
+            body,
+        )
+            .into_response());
+    }
+
+    Ok(Json(UsageReportResponse {
+        from,
+        to,
+        group_by,
+        usage: rows.into_iter().map(Into::into).collect(),
+    })
+    .into_response())
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Parse a comma-separated list of dimensions, ignoring duplicates.
+fn parse_group_by(value: Option<&str>) -> Result<Vec<UsageGroupBy>, ApiError> {
+    let mut group_by = Vec::new();
+    for part in value.unwrap_or_default().split(',').map(str::trim) {
+        if part.is_empty() {
+            continue;
+        }
+        let dimension: UsageGroupBy = part.parse().map_err(ApiError::BadRequest)?;
+        if !group_by.contains(&dimension) {
+            group_by.push(dimension);
+        }
+    }
+    Ok(group_by)
+}
+
+/// Whether the report should be rendered as CSV.
+fn wants_csv(format: Option<&str>, headers: &HeaderMap) -> Result<bool, ApiError> {
+    match format {
+        Some("csv") => Ok(true),
+        Some("json") => Ok(false),
+        Some(other) => Err(ApiError::BadRequest(format!("unknown format: {other}"))),
+        None => Ok(headers
+            .get(header::ACCEPT)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|accept| accept.contains("text/csv"))),
+    }
+}
+
+/// Render report rows as CSV, one column per grouped dimension followed by
+/// the totals.
+fn to_csv(group_by: &[UsageGroupBy], rows: &[UsageSummary]) -> String {
+    let mut csv = String::new();
+
+    for dimension in group_by {
+        let _ = write!(csv, "{dimension},");
+    }
+    csv.push_str("agent_seconds,cpu_millicore_seconds,memory_mb_seconds\n");
+
+    for row in rows {
+        for dimension in group_by {
+            match dimension {
+                UsageGroupBy::Hour => {
+                    if let Some(hour) = row.hour {
+                        csv.push_str(&hour.to_rfc3339_opts(SecondsFormat::Secs, true));
+                    }
+                }
+                UsageGroupBy::Namespace => {
+                    if let Some(id) = row.namespace_id {
+                        let _ = write!(csv, "{id}");
+                    }
+                }
+                UsageGroupBy::User => {
+                    if let Some(id) = row.user_id {
+                        let _ = write!(csv, "{id}");
+                    }
+                }
+                UsageGroupBy::State => {
+                    if let Some(state) = row.state {
+                        csv.push_str(state.as_str());
+                    }
+                }
+            }
+            csv.push(',');
+        }
+        let _ = writeln!(
+            csv,
+            "{},{},{}",
+            row.agent_seconds, row.cpu_millicore_seconds, row.memory_mb_seconds
+        );
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_by_is_parsed_and_validated() {
+        assert!(parse_group_by(None).unwrap().is_empty());
+        assert_eq!(
+            parse_group_by(Some("user, state,user")).unwrap(),
+            vec![UsageGroupBy::User, UsageGroupBy::State]
+        );
+        assert!(parse_group_by(Some("agent")).is_err());
+    }
+
+    #[test]
+    fn csv_has_a_column_per_dimension() {
+        let rows = vec![UsageSummary {
+            hour: None,
+            namespace_id: None,
+            user_id: None,
+            state: Some(AgentState::Running),
+            agent_seconds: 60,
+            cpu_millicore_seconds: 30_000,
+            memory_mb_seconds: 15_360,
+        }];
+
+        let csv = to_csv(&[UsageGroupBy::State], &rows);
+        assert_eq!(
+            csv,
+            "state,agent_seconds,cpu_millicore_seconds,memory_mb_seconds\n\
+             running,60,30000,15360\n"
+        );
+    }
+}
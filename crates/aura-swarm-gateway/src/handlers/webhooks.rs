@@ -0,0 +1,357 @@
+//! Webhook endpoints.
+//!
+//! This module provides handlers for registering webhooks that receive agent
+//! lifecycle events, inspecting their delivery log, and sending test pings.
+//! The signing secret is only returned when a webhook is created.
+
+use std::sync::Arc;
+
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use aura_swarm_auth::JwtValidator;
+use aura_swarm_control::{
+    ControlPlane, CreateWebhookRequest, DeliveryAttempt, DeliveryStatus, Webhook, WebhookDelivery,
+    WebhookEvent, WebhookId,
+};
+
+use crate::auth::AuthUser;
+use crate::error::ApiError;
+use crate::state::GatewayState;
+
+/// Maximum number of deliveries returned by the delivery log endpoint.
+const MAX_DELIVERY_LIMIT: usize = 100;
+
+// =============================================================================
+// Request/Response Types
+// =============================================================================
+
+/// Request to register a webhook.
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookBody {
+    /// Endpoint URL (http or https).
+    pub url: String,
+    /// Events to subscribe to (empty means all lifecycle events).
+    #[serde(default)]
+    pub events: Vec<WebhookEvent>,
+    /// Optional description.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Response for a webhook.
+#[derive(Debug, Serialize)]
+pub struct WebhookResponse {
+    /// Webhook ID.
+    pub webhook_id: String,
+    /// Endpoint URL.
+    pub url: String,
+    /// Subscribed events (empty means all lifecycle events).
+    pub events: Vec<WebhookEvent>,
+    /// Description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Signing secret (only present in the create response).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Webhook> for WebhookResponse {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            webhook_id: webhook.webhook_id.to_string(),
+            url: webhook.url,
+            events: webhook.events,
+            description: webhook.description,
+            secret: None,
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+/// Response for a webhook delivery.
+#[derive(Debug, Serialize)]
+pub struct DeliveryResponse {
+    /// Delivery ID (sent as `X-Aura-Delivery`).
+    pub delivery_id: String,
+    /// Event that was delivered.
+    pub event: WebhookEvent,
+    /// Delivery status.
+    pub status: DeliveryStatus,
+    /// Attempts made so far, oldest first.
+    pub attempts: Vec<DeliveryAttempt>,
+    /// When the next retry is due (pending deliveries only).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// When the event occurred.
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<WebhookDelivery> for DeliveryResponse {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            delivery_id: delivery.delivery_id.to_string(),
+            event: delivery.event,
+            status: delivery.status,
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            created_at: delivery.created_at,
+        }
+    }
+}
+
+/// Query parameters for the delivery log.
+#[derive(Debug, Deserialize)]
+pub struct DeliveryQuery {
+    /// Number of deliveries to return (default: 20, max: 100).
+    #[serde(default = "default_limit")]
+    pub limit: usize,
+}
+
+const fn default_limit() -> usize {
+    20
+}
+
+// =============================================================================
+// Handlers
+// =============================================================================
+
+/// List the user's webhooks.
+///
+/// # Errors
+///
+/// Returns an error if the webhooks cannot be loaded.
+pub async fn list_webhooks<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let webhooks = state.control.list_webhooks(&user.user_id).await?;
+    let response: Vec<WebhookResponse> = webhooks.into_iter().map(Into::into).collect();
+
+    Ok(Json(response))
+}
+
+/// Register a webhook.
+///
+/// The response includes the signing secret, which is not returned again.
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The URL is invalid or does not resolve to a public address
+/// - The user has reached the webhook limit
+/// - Webhooks are not enabled (no secret key is configured)
+pub async fn create_webhook<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Json(body): Json<CreateWebhookBody>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let request = CreateWebhookRequest {
+        events: body.events,
+        description: body.description,
+        ..CreateWebhookRequest::new(body.url)
+    };
+
+    let created = state.control.create_webhook(&user.user_id, request).await?;
+
+    let mut response = WebhookResponse::from(created.webhook);
+    response.secret = Some(created.secret);
+
+    Ok((StatusCode::CREATED, Json(response)))
+}
+
+/// Get a webhook.
+///
+/// # Errors
+///
+/// Returns an error if the webhook is not found or the user doesn't own it.
+pub async fn get_webhook<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(webhook_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let webhook_id = parse_webhook_id(&webhook_id)?;
+    let webhook = state
+        .control
+        .get_webhook(&user.user_id, &webhook_id)
+        .await?;
+
+    Ok(Json(WebhookResponse::from(webhook)))
+}
+
+/// Delete a webhook and its delivery log.
+///
+/// # Errors
+///
+/// Returns an error if the webhook is not found or the user doesn't own it.
+pub async fn delete_webhook<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(webhook_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let webhook_id = parse_webhook_id(&webhook_id)?;
+    state
+        .control
+        .delete_webhook(&user.user_id, &webhook_id)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// List recent deliveries for a webhook, newest first.
+///
+/// # Errors
+///
+/// Returns an error if the webhook is not found or the user doesn't own it.
+pub async fn list_deliveries<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(webhook_id): Path<String>,
+    Query(query): Query<DeliveryQuery>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let webhook_id = parse_webhook_id(&webhook_id)?;
+    let deliveries = state
+        .control
+        .list_webhook_deliveries(
+            &user.user_id,
+            &webhook_id,
+            query.limit.clamp(1, MAX_DELIVERY_LIMIT),
+        )
+        .await?;
+    let response: Vec<DeliveryResponse> = deliveries.into_iter().map(Into::into).collect();
+
+    Ok(Json(response))
+}
+
+/// Send a test `ping` event to a webhook.
+///
+/// # Errors
+///
+/// Returns an error if the webhook is not found or the user doesn't own it.
+pub async fn ping_webhook<C, V>(
+    State(state): State<Arc<GatewayState<C, V>>>,
+    user: AuthUser,
+    Path(webhook_id): Path<String>,
+) -> Result<impl IntoResponse, ApiError>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    let webhook_id = parse_webhook_id(&webhook_id)?;
+    let delivery = state
+        .control
+        .ping_webhook(&user.user_id, &webhook_id)
+        .await?;
+
+    Ok(Json(DeliveryResponse::from(delivery)))
+}
+
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/// Parse a webhook ID from a path parameter.
+fn parse_webhook_id(s: &str) -> Result<WebhookId, ApiError> {
+    s.parse()
+        .map_err(|_| ApiError::BadRequest(format!("invalid webhook ID: {s}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+
+    use crate::testing::{TestGateway, TestUser};
+
+    #[tokio::test]
+    async fn webhooks_are_private_to_their_owner() {
+        let gateway = TestGateway::new();
+        let owner = TestUser::member(1, 1);
+        let other = TestUser::member(2, 1);
+
+        gateway
+            .post(&owner, "/v1/webhooks")
+            .json(&json!({ "url": "ftp://example.com/hook" }))
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+        let response = gateway
+            .post(&owner, "/v1/webhooks")
+            .json(&json!({ "url": "http://127.0.0.1:1/hook", "events": ["agent.running"] }))
+            .await;
+        response.assert_status(StatusCode::CREATED);
+        let created: Value = response.json();
+        assert!(created["secret"].as_str().is_some());
+        let path = format!("/v1/webhooks/{}", created["webhook_id"].as_str().unwrap());
+
+        let response = gateway.get(&owner, &path).await;
+        response.assert_status_ok();
+        assert!(response.json::<Value>().get("secret").is_none());
+        let response = gateway.get(&owner, "/v1/webhooks").await;
+        assert_eq!(response.json::<Value>().as_array().unwrap().len(), 1);
+        let response = gateway.get(&other, "/v1/webhooks").await;
+        assert!(response.json::<Value>().as_array().unwrap().is_empty());
+
+        gateway
+            .get(&other, &path)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        gateway
+            .post(&other, &format!("{path}/ping"))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        gateway
+            .get(&other, &format!("{path}/deliveries"))
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        gateway
+            .delete(&other, &path)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+        gateway
+            .get(&owner, "/v1/webhooks/not-an-id")
+            .await
+            .assert_status(StatusCode::BAD_REQUEST);
+
+        let response = gateway.post(&owner, &format!("{path}/ping")).await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>()["event"], "ping");
+        let response = gateway.get(&owner, &format!("{path}/deliveries")).await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<Value>().as_array().unwrap().len(), 1);
+
+        gateway
+            .delete(&owner, &path)
+            .await
+            .assert_status(StatusCode::NO_CONTENT);
+        gateway
+            .get(&owner, &path)
+            .await
+            .assert_status(StatusCode::NOT_FOUND);
+    }
+}
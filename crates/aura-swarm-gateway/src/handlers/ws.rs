@@ -1,7 +1,12 @@
 //! WebSocket proxy handler.
 //!
 //! This module provides bidirectional WebSocket proxying between clients and agent pods.
+//!
+//! While a connection is open the proxy periodically reports activity to the
+//! control plane and disconnects once the session has been closed, whether
+//! explicitly or by the session reaper.
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use axum::extract::ws::{Message, WebSocket};
@@ -13,7 +18,7 @@ use tokio_tungstenite::tungstenite::Message as TungsteniteMessage;
 use tokio_tungstenite::MaybeTlsStream;
 
 use aura_swarm_auth::JwtValidator;
-use aura_swarm_control::{ControlPlane, SessionStatus};
+use aura_swarm_control::{Caller, ControlPlane, SessionStatus};
 use aura_swarm_core::SessionId;
 
 use crate::auth::AuthUser;
@@ -44,7 +49,7 @@ where
     // Validate session ownership
     let session = state
         .control
-        .get_session(&user.user_id, &session_id)
+        .get_session(&user.caller(), &session_id)
         .await?;
 
     // Check session is active
@@ -60,7 +65,9 @@ where
         .ok_or(ApiError::AgentUnavailable)?;
 
     let timeout = state.config.websocket_timeout();
+    let activity_interval = state.config.session_activity_interval();
     let agent_id_str = session.agent_id.to_string();
+    let control = Arc::clone(&state.control);
 
     tracing::info!(
         session_id = %session_id,
@@ -69,17 +76,67 @@ where
         "WebSocket connection initiated"
     );
 
-    Ok(ws.on_upgrade(move |socket| {
-        handle_websocket(
+    let caller = user.caller();
+    Ok(ws.on_upgrade(move |socket| async move {
+        let activity = Arc::new(AtomicBool::new(true));
+        let watch = watch_session(
+            control,
+            caller,
+            session_id,
+            Arc::clone(&activity),
+            activity_interval,
+        );
+        let proxy = handle_websocket(
             socket,
             endpoint,
             session_id.to_string(),
             agent_id_str,
             timeout,
-        )
+            activity,
+        );
+
+        tokio::select! {
+            () = proxy => {}
+            () = watch => {
+                tracing::info!(session_id = %session_id, "Session closed, disconnecting WebSocket");
+            }
+        }
     }))
 }
 
+/// Report session activity periodically until the session is no longer active.
+///
+/// Returns once the session has been closed or can no longer be read.
+async fn watch_session<C: ControlPlane>(
+    control: Arc<C>,
+    caller: Caller,
+    session_id: SessionId,
+    activity: Arc<AtomicBool>,
+    interval: std::time::Duration,
+) {
+    let mut ticker = tokio::time::interval(interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+
+        let result = if activity.swap(false, Ordering::Relaxed) {
+            control.touch_session(&caller, &session_id).await
+        } else {
+            control.get_session(&caller, &session_id).await
+        };
+
+        match result {
+            Ok(session) if session.status == SessionStatus::Active => {}
+            Ok(_) => return,
+            Err(e) => {
+                tracing::warn!(session_id = %session_id, error = %e, "Failed to check session");
+                return;
+            }
+        }
+    }
+}
+
 /// Handle the WebSocket connection after upgrade.
 ///
 /// Connects to the agent's `/stream` endpoint for real-time streaming.
@@ -89,6 +146,7 @@ async fn handle_websocket(
     session_id: String,
     agent_id: String,
     timeout: std::time::Duration,
+    activity: Arc<AtomicBool>,
 ) {
     // Connect to agent's streaming endpoint
     let agent_url = format!("ws://{agent_endpoint}/stream");
@@ -126,8 +184,8 @@ async fn handle_websocket(
     let (agent_write, agent_read) = agent_socket.split();
 
     // Run both directions concurrently
-    let client_to_agent = forward_client_to_agent(client_read, agent_write, &session_id);
-    let agent_to_client = forward_agent_to_client(agent_read, client_write, &session_id);
+    let client_to_agent = forward_client_to_agent(client_read, agent_write, &session_id, &activity);
+    let agent_to_client = forward_agent_to_client(agent_read, client_write, &session_id, &activity);
 
     tokio::select! {
         result = client_to_agent => {
@@ -153,10 +211,12 @@ async fn forward_client_to_agent(
         TungsteniteMessage,
     >,
     session_id: &str,
+    activity: &AtomicBool,
 ) -> Result<(), String> {
     while let Some(msg_result) = client_read.next().await {
         match msg_result {
             Ok(msg) => {
+                activity.store(true, Ordering::Relaxed);
                 let tungstenite_msg = match msg {
                     Message::Text(text) => TungsteniteMessage::Text(text.clone()),
                     Message::Binary(data) => TungsteniteMessage::Binary(data.clone()),
@@ -187,10 +247,12 @@ async fn forward_agent_to_client(
     >,
     mut client_write: SplitSink<WebSocket, Message>,
     session_id: &str,
+    activity: &AtomicBool,
 ) -> Result<(), String> {
     while let Some(msg_result) = agent_read.next().await {
         match msg_result {
             Ok(msg) => {
+                activity.store(true, Ordering::Relaxed);
                 let axum_msg = match msg {
                     TungsteniteMessage::Text(text) => Message::Text(text),
                     TungsteniteMessage::Binary(data) => Message::Binary(data),
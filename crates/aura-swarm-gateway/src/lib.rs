@@ -68,6 +68,7 @@
 #![warn(clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod audit;
 pub mod auth;
 pub mod config;
 pub mod error;
@@ -75,6 +76,9 @@ pub mod handlers;
 pub mod routes;
 pub mod state;
 
+#[cfg(test)]
+mod testing;
+
 pub use config::GatewayConfig;
 pub use error::ApiError;
 pub use routes::create_router;
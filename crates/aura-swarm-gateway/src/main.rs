@@ -8,13 +8,29 @@
 //!
 //! Build with `--features dev-mode` and set `DEV_MODE=true` to use a mock
 //! JWT validator that doesn't require network access to Zero-ID.
-//! Use tokens in format: `test-token:<identity-uuid>:<namespace-uuid>`
+//! Use tokens in format: `test-token:<identity-uuid>:<namespace-uuid>[:<roles>]`,
+//! e.g. append `:namespace_admin` to act as a namespace admin.
 //!
 //! # Scheduler Integration
 //!
 //! Set `SCHEDULER_URL` environment variable to enable scheduler integration.
 //! If not set, the gateway operates without scheduler (local-only mode).
+//!
+//! # Agent Secrets
+//!
+//! Set `AGENT_SECRETS_KEY` to a hex-encoded 32-byte key to enable per-agent
+//! secrets. Secret values are sealed with this key before they are stored.
+//! Webhook signing secrets are sealed with the same key, so registering
+//! webhooks also requires it.
+//!
+//! # Client Addresses
+//!
+//! Set `TRUSTED_PROXIES` to a comma-separated list of addresses or CIDR
+//! ranges (e.g. the ingress controller's pod network) to record the client
+//! address from `X-Forwarded-For` in the audit log. Without it, the address
+//! of the connecting peer is recorded.
 
+use std::net::SocketAddr;
 use std::sync::Arc;
 
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
@@ -23,7 +39,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use aura_swarm_auth::{AuthConfig, JwksValidator};
 #[cfg(feature = "dev-mode")]
 use aura_swarm_auth::MockJwtValidator;
-use aura_swarm_control::{ControlConfig, ControlPlaneService, HttpSchedulerClient};
+use aura_swarm_control::{ControlConfig, ControlPlaneService, HttpSchedulerClient, SecretCipher};
 use aura_swarm_gateway::{create_router, GatewayConfig, GatewayState};
 use aura_swarm_store::RocksStore;
 
@@ -71,22 +87,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         tracing::warn!("No SCHEDULER_URL set - running without scheduler integration");
     }
 
-    let control = Arc::new(ControlPlaneService::with_optional_scheduler(
+    let mut control = ControlPlaneService::with_optional_scheduler(
         store,
-        ControlConfig::default(),
+        ControlConfig::from_env(),
         scheduler_client,
-    ));
+    );
+    if let Some(cipher) = secret_cipher_from_env()? {
+        control = control.with_secret_cipher(cipher);
+    }
+    let control = Arc::new(control);
 
     tracing::info!(
         has_scheduler = control.has_scheduler(),
         "Control plane initialized"
     );
 
+    spawn_background_tasks(&control);
+
     // Initialize JWT validator
     #[cfg(feature = "dev-mode")]
     let jwt_validator = {
         tracing::warn!("DEV MODE ENABLED - using mock JWT validator");
-        tracing::warn!("Use tokens in format: test-token:<identity-uuid>:<namespace-uuid>");
+        tracing::warn!(
+            "Use tokens in format: test-token:<identity-uuid>:<namespace-uuid>[:<roles>]"
+        );
         Arc::new(MockJwtValidator::default())
     };
 
@@ -102,7 +126,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("JWT validator initialized");
 
     // Build gateway state and configuration
-    let gateway_config = GatewayConfig::default();
+    let admin_identities = std::env::var("ADMIN_IDENTITY_IDS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    let trusted_proxies = std::env::var("TRUSTED_PROXIES")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    let gateway_config = GatewayConfig {
+        admin_identities,
+        trusted_proxies,
+        ..GatewayConfig::default()
+    };
     let state = GatewayState::new(control, jwt_validator, gateway_config);
 
     // Create the full router with all API endpoints
@@ -112,7 +154,72 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Start HTTP server
     tracing::info!(listen_addr = %listen_addr, "Starting HTTP server");
     let listener = tokio::net::TcpListener::bind(&listen_addr).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
+
+/// Spawn the control plane's background loops.
+fn spawn_background_tasks(control: &Arc<ControlPlaneService<RocksStore, HttpSchedulerClient>>) {
+    // Run agent wake/hibernate schedules in the background
+    let schedule_runner = Arc::clone(control);
+    tokio::spawn(async move { schedule_runner.run_schedule_loop().await });
+
+    // Close idle and expired sessions in the background
+    let session_reaper = Arc::clone(control);
+    tokio::spawn(async move { session_reaper.run_session_reaper().await });
+
+    // Deliver lifecycle webhooks from the outbox in the background
+    let webhook_dispatcher = Arc::clone(control);
+    tokio::spawn(async move { webhook_dispatcher.run_webhook_dispatcher().await });
+
+    // Enforce provisioning deadlines and restart policies in the background
+    let restart_supervisor = Arc::clone(control);
+    tokio::spawn(async move { restart_supervisor.run_restart_supervisor().await });
+
+    // Prune audit events past their retention period in the background
+    let audit_retention = Arc::clone(control);
+    tokio::spawn(async move { audit_retention.run_audit_retention().await });
+
+    // Meter agent runtime into hourly usage buckets in the background
+    let usage_meter = Arc::clone(control);
+    tokio::spawn(async move { usage_meter.run_usage_meter().await });
+
+    // Advance runtime version rollouts in the background
+    let rollout_controller = Arc::clone(control);
+    tokio::spawn(async move { rollout_controller.run_rollout_controller().await });
+
+    // Retry failed agent deletions in the background
+    let deletion_worker = Arc::clone(control);
+    tokio::spawn(async move { deletion_worker.run_deletion_worker().await });
+
+    // Copy cloned agents' state and start their pods in the background
+    let clone_worker = Arc::clone(control);
+    tokio::spawn(async move { clone_worker.run_clone_worker().await });
+
+    // Correct drift between the store and the cluster in the background
+    if control.has_scheduler() {
+        let drift_reconciler = Arc::clone(control);
+        tokio::spawn(async move { drift_reconciler.run_drift_reconciler().await });
+    }
+}
+
+/// Load the agent secret key from `AGENT_SECRETS_KEY`, if set.
+fn secret_cipher_from_env() -> Result<Option<SecretCipher>, Box<dyn std::error::Error>> {
+    match std::env::var("AGENT_SECRETS_KEY") {
+        Ok(key) if !key.trim().is_empty() => {
+            let cipher = SecretCipher::from_hex(&key)
+                .map_err(|e| format!("invalid AGENT_SECRETS_KEY: {e}"))?;
+            tracing::info!("Agent secrets and webhooks enabled");
+            Ok(Some(cipher))
+        }
+        _ => {
+            tracing::warn!("No AGENT_SECRETS_KEY set - agent secrets and webhooks are disabled");
+            Ok(None)
+        }
+    }
+}
@@ -5,7 +5,8 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use axum::routing::{get, patch, post};
+use axum::middleware;
+use axum::routing::{get, patch, post, put};
 use axum::Router;
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::limit::RequestBodyLimitLayer;
@@ -15,7 +16,11 @@ use tower_http::trace::TraceLayer;
 use aura_swarm_auth::JwtValidator;
 use aura_swarm_control::ControlPlane;
 
-use crate::handlers::{agents, health, internal, sessions, ws};
+use crate::audit::audit_requests;
+use crate::handlers::{
+    access, agents, audit, health, internal, namespaces, rollouts, schedules, secrets, sessions,
+    templates, usage, webhooks, ws,
+};
 use crate::state::GatewayState;
 
 /// Create the gateway router with all routes and middleware.
@@ -29,7 +34,9 @@ use crate::state::GatewayState;
 /// - `GET /v1/agents` - List agents
 /// - `POST /v1/agents` - Create agent
 /// - `GET /v1/agents/:agent_id` - Get agent
-/// - `DELETE /v1/agents/:agent_id` - Delete agent
+/// - `DELETE /v1/agents/:agent_id` - Delete agent (`202` with progress if a cleanup step fails)
+/// - `GET /v1/agents/:agent_id/deletion` - Get deletion progress
+/// - `POST /v1/agents/:agent_id/clone` - Clone agent (including state)
 /// - `POST /v1/agents/:agent_id/start` - Start agent
 /// - `POST /v1/agents/:agent_id/stop` - Stop agent
 /// - `POST /v1/agents/:agent_id/restart` - Restart agent
@@ -38,6 +45,54 @@ use crate::state::GatewayState;
 /// - `GET /v1/agents/:agent_id/logs` - Get agent logs
 /// - `GET /v1/agents/:agent_id/status` - Get agent status
 ///
+/// ## Schedules (authenticated)
+/// - `GET /v1/agents/:agent_id/schedule` - Get schedule and upcoming runs
+/// - `PUT /v1/agents/:agent_id/schedule` - Set schedule
+/// - `DELETE /v1/agents/:agent_id/schedule` - Remove schedule
+///
+/// ## Sharing (authenticated)
+/// - `GET /v1/agents/shared` - List agents shared with the caller
+/// - `GET /v1/agents/:agent_id/access` - List access grants (owner)
+/// - `PUT /v1/agents/:agent_id/access/:user_id` - Grant a role (owner)
+/// - `DELETE /v1/agents/:agent_id/access/:user_id` - Revoke access (owner, or the grantee)
+///
+/// ## Secrets (authenticated, owner)
+/// - `GET /v1/agents/:agent_id/secrets` - List secret names (values are never returned)
+/// - `PUT /v1/agents/:agent_id/secrets/:name` - Set a secret (applied on next start)
+/// - `DELETE /v1/agents/:agent_id/secrets/:name` - Delete a secret
+///
+/// ## Namespace (authenticated, namespace admin)
+/// - `GET /v1/namespace/agents` - List every agent in the caller's namespace
+/// - `GET /v1/namespace/usage` - Namespace usage and effective quotas
+///
+/// ## Admin (authenticated, platform admin)
+/// - `PUT /v1/admin/namespaces/:namespace_id/quota` - Set namespace quota overrides
+/// - `GET /v1/admin/rollouts` - List runtime version rollouts
+/// - `POST /v1/admin/rollouts` - Start a rollout (`target_version`, `selector`, `strategy`)
+/// - `GET /v1/admin/rollouts/:rollout_id` - Get a rollout and its per-agent progress
+/// - `POST /v1/admin/rollouts/:rollout_id/cancel` - Cancel an in-progress rollout
+/// - `POST /v1/admin/rollouts/:rollout_id/rollback` - Revert updated agents
+///
+/// ## Audit (authenticated, platform admin or namespace admin)
+/// - `GET /v1/audit` - Query the audit log (`from`, `to`, `user_id`, `namespace_id`, `limit`)
+///
+/// ## Usage (authenticated, platform admin or namespace admin)
+/// - `GET /v1/usage` - Metered agent usage (`from`, `to`, `group_by`; `format=csv` for CSV)
+///
+/// ## Templates (authenticated)
+/// - `GET /v1/templates` - List templates
+/// - `POST /v1/templates` - Create template (global scope requires admin)
+/// - `GET /v1/templates/:name` - Get template (own before admin-defined)
+/// - `DELETE /v1/templates/:name` - Delete template (`?scope=global` requires admin)
+///
+/// ## Webhooks (authenticated)
+/// - `GET /v1/webhooks` - List webhooks
+/// - `POST /v1/webhooks` - Register webhook (the signing secret is returned once)
+/// - `GET /v1/webhooks/:webhook_id` - Get webhook
+/// - `DELETE /v1/webhooks/:webhook_id` - Delete webhook
+/// - `GET /v1/webhooks/:webhook_id/deliveries` - Recent delivery attempts
+/// - `POST /v1/webhooks/:webhook_id/ping` - Send a test event
+///
 /// ## Sessions (authenticated)
 /// - `POST /v1/agents/:agent_id/sessions` - Create session
 /// - `GET /v1/agents/:agent_id/sessions` - List sessions
@@ -48,6 +103,9 @@ use crate::state::GatewayState;
 /// ## Internal (no auth, cluster-only)
 /// - `PATCH /internal/agents/:agent_id/status` - Update agent status (scheduler callback)
 /// - `GET /internal/health` - Internal health check
+///
+/// Every mutating request and every `401`/`403` response is recorded in the
+/// audit log (see [`crate::audit`]).
 pub fn create_router<C, V>(state: GatewayState<C, V>) -> Router
 where
     C: ControlPlane + 'static,
@@ -76,32 +134,64 @@ where
             "/v1/agents/:agent_id",
             get(agents::get_agent::<C, V>).delete(agents::delete_agent::<C, V>),
         )
+        .route(
+            "/v1/agents/:agent_id/deletion",
+            get(agents::get_deletion::<C, V>),
+        )
+        .route(
+            "/v1/agents/:agent_id/clone",
+            post(agents::clone_agent::<C, V>),
+        )
         // Agent lifecycle
+        .merge(lifecycle_routes::<C, V>())
+        // Agent observability
+        .route("/v1/agents/:agent_id/logs", get(agents::get_logs::<C, V>))
         .route(
-            "/v1/agents/:agent_id/start",
-            post(agents::start_agent::<C, V>),
+            "/v1/agents/:agent_id/status",
+            get(agents::get_status::<C, V>),
         )
+        // Schedules
         .route(
-            "/v1/agents/:agent_id/stop",
-            post(agents::stop_agent::<C, V>),
+            "/v1/agents/:agent_id/schedule",
+            get(schedules::get_schedule::<C, V>)
+                .put(schedules::set_schedule::<C, V>)
+                .delete(schedules::delete_schedule::<C, V>),
         )
+        // Sharing
+        .merge(access_routes::<C, V>())
+        // Secrets
+        .merge(secret_routes::<C, V>())
+        // Namespace
+        .merge(namespace_routes::<C, V>())
+        // Audit and usage reports
+        .merge(report_routes::<C, V>())
+        // Runtime rollouts
+        .merge(rollout_routes::<C, V>())
+        // Templates
         .route(
-            "/v1/agents/:agent_id/restart",
-            post(agents::restart_agent::<C, V>),
+            "/v1/templates",
+            get(templates::list_templates::<C, V>).post(templates::create_template::<C, V>),
         )
         .route(
-            "/v1/agents/:agent_id/hibernate",
-            post(agents::hibernate_agent::<C, V>),
+            "/v1/templates/:name",
+            get(templates::get_template::<C, V>).delete(templates::delete_template::<C, V>),
         )
+        // Webhooks
         .route(
-            "/v1/agents/:agent_id/wake",
-            post(agents::wake_agent::<C, V>),
+            "/v1/webhooks",
+            get(webhooks::list_webhooks::<C, V>).post(webhooks::create_webhook::<C, V>),
         )
-        // Agent observability
-        .route("/v1/agents/:agent_id/logs", get(agents::get_logs::<C, V>))
         .route(
-            "/v1/agents/:agent_id/status",
-            get(agents::get_status::<C, V>),
+            "/v1/webhooks/:webhook_id",
+            get(webhooks::get_webhook::<C, V>).delete(webhooks::delete_webhook::<C, V>),
+        )
+        .route(
+            "/v1/webhooks/:webhook_id/deliveries",
+            get(webhooks::list_deliveries::<C, V>),
+        )
+        .route(
+            "/v1/webhooks/:webhook_id/ping",
+            post(webhooks::ping_webhook::<C, V>),
         )
         // Sessions
         .route(
@@ -124,6 +214,10 @@ where
         )
         .route("/internal/health", get(internal::internal_health))
         // Middleware
+        .layer(middleware::from_fn_with_state(
+            Arc::clone(&state),
+            audit_requests::<C, V>,
+        ))
         .layer(TraceLayer::new_for_http())
         .layer(cors)
         .layer(RequestBodyLimitLayer::new(max_body_bytes))
@@ -133,6 +227,127 @@ where
         .with_state(state)
 }
 
+/// Agent lifecycle routes.
+fn lifecycle_routes<C, V>() -> Router<Arc<GatewayState<C, V>>>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    Router::new()
+        .route(
+            "/v1/agents/:agent_id/start",
+            post(agents::start_agent::<C, V>),
+        )
+        .route(
+            "/v1/agents/:agent_id/stop",
+            post(agents::stop_agent::<C, V>),
+        )
+        .route(
+            "/v1/agents/:agent_id/restart",
+            post(agents::restart_agent::<C, V>),
+        )
+        .route(
+            "/v1/agents/:agent_id/hibernate",
+            post(agents::hibernate_agent::<C, V>),
+        )
+        .route(
+            "/v1/agents/:agent_id/wake",
+            post(agents::wake_agent::<C, V>),
+        )
+}
+
+/// Agent sharing routes.
+fn access_routes<C, V>() -> Router<Arc<GatewayState<C, V>>>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    Router::new()
+        .route("/v1/agents/shared", get(access::list_shared_agents::<C, V>))
+        .route(
+            "/v1/agents/:agent_id/access",
+            get(access::list_grants::<C, V>),
+        )
+        .route(
+            "/v1/agents/:agent_id/access/:user_id",
+            put(access::grant_access::<C, V>).delete(access::revoke_access::<C, V>),
+        )
+}
+
+/// Agent secret routes.
+fn secret_routes<C, V>() -> Router<Arc<GatewayState<C, V>>>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    Router::new()
+        .route(
+            "/v1/agents/:agent_id/secrets",
+            get(secrets::list_secrets::<C, V>),
+        )
+        .route(
+            "/v1/agents/:agent_id/secrets/:name",
+            put(secrets::set_secret::<C, V>).delete(secrets::delete_secret::<C, V>),
+        )
+}
+
+/// Namespace admin and namespace quota routes.
+fn namespace_routes<C, V>() -> Router<Arc<GatewayState<C, V>>>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    Router::new()
+        .route(
+            "/v1/namespace/agents",
+            get(namespaces::list_namespace_agents::<C, V>),
+        )
+        .route(
+            "/v1/namespace/usage",
+            get(namespaces::get_namespace_usage::<C, V>),
+        )
+        .route(
+            "/v1/admin/namespaces/:namespace_id/quota",
+            put(namespaces::set_namespace_quota::<C, V>),
+        )
+}
+
+/// Audit log and usage report routes.
+fn report_routes<C, V>() -> Router<Arc<GatewayState<C, V>>>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    Router::new()
+        .route("/v1/audit", get(audit::list_audit_events::<C, V>))
+        .route("/v1/usage", get(usage::get_usage::<C, V>))
+}
+
+/// Runtime version rollout routes.
+fn rollout_routes<C, V>() -> Router<Arc<GatewayState<C, V>>>
+where
+    C: ControlPlane + 'static,
+    V: JwtValidator + 'static,
+{
+    Router::new()
+        .route(
+            "/v1/admin/rollouts",
+            get(rollouts::list_rollouts::<C, V>).post(rollouts::create_rollout::<C, V>),
+        )
+        .route(
+            "/v1/admin/rollouts/:rollout_id",
+            get(rollouts::get_rollout::<C, V>),
+        )
+        .route(
+            "/v1/admin/rollouts/:rollout_id/cancel",
+            post(rollouts::cancel_rollout::<C, V>),
+        )
+        .route(
+            "/v1/admin/rollouts/:rollout_id/rollback",
+            post(rollouts::rollback_rollout::<C, V>),
+        )
+}
+
 /// Build the CORS layer from configured origins.
 fn build_cors_layer(origins: &[String]) -> CorsLayer {
     if origins.iter().any(|o| o == "*") {
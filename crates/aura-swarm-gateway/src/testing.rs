@@ -0,0 +1,170 @@
+//! Helpers for router-level handler tests.
+//!
+//! [`TestGateway`] serves the full router over a temporary store, so tests
+//! go through authentication, the audit middleware and error mapping just
+//! like real requests. Callers authenticate with
+//! [`MockJwtValidator`] tokens built by [`TestUser`].
+
+use std::sync::Arc;
+
+use aura_swarm_auth::{MockJwtValidator, ValidatedClaims};
+use aura_swarm_control::{ControlConfig, ControlPlaneService, NamespaceId, SecretCipher, UserId};
+use aura_swarm_core::{IdentityId, SessionId};
+use aura_swarm_store::RocksStore;
+use axum_test::{TestRequest, TestServer};
+use chrono::Utc;
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+use crate::auth::{AuthUser, NAMESPACE_ADMIN_ROLE};
+use crate::config::GatewayConfig;
+use crate::routes::create_router;
+use crate::state::GatewayState;
+
+/// A caller authenticated with a mock token.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TestUser {
+    pub(crate) identity_id: IdentityId,
+    pub(crate) namespace_id: NamespaceId,
+    namespace_admin: bool,
+}
+
+impl TestUser {
+    /// A regular member of namespace `namespace`.
+    pub(crate) fn member(identity: u8, namespace: u8) -> Self {
+        Self {
+            identity_id: IdentityId::from_uuid(uuid::Uuid::from_bytes([identity; 16])),
+            namespace_id: NamespaceId::from_uuid(uuid::Uuid::from_bytes([namespace; 16])),
+            namespace_admin: false,
+        }
+    }
+
+    /// A member whose token carries the namespace admin role.
+    pub(crate) fn namespace_admin(identity: u8, namespace: u8) -> Self {
+        Self {
+            namespace_admin: true,
+            ..Self::member(identity, namespace)
+        }
+    }
+
+    /// The bearer token the mock validator accepts for this user.
+    pub(crate) fn token(&self) -> String {
+        let mut token = format!("test-token:{}:{}", self.identity_id, self.namespace_id);
+        if self.namespace_admin {
+            token.push(':');
+            token.push_str(NAMESPACE_ADMIN_ROLE);
+        }
+        token
+    }
+
+    /// The user ID the gateway derives for this user.
+    pub(crate) fn user_id(&self) -> UserId {
+        let claims = ValidatedClaims {
+            identity_id: self.identity_id,
+            namespace_id: self.namespace_id,
+            session_id: SessionId::generate(),
+            mfa_verified: false,
+            expires_at: Utc::now(),
+            roles: Vec::new(),
+        };
+        AuthUser::from_claims(&claims).user_id
+    }
+}
+
+/// A gateway serving the full router over a temporary store.
+pub(crate) struct TestGateway {
+    pub(crate) server: TestServer,
+    _dir: TempDir,
+}
+
+impl TestGateway {
+    /// A gateway with the default configuration and no platform admins.
+    pub(crate) fn new() -> Self {
+        Self::with_config(GatewayConfig::default())
+    }
+
+    /// A gateway with the given configuration.
+    ///
+    /// Secrets and webhooks are enabled, and webhooks may target local
+    /// addresses.
+    pub(crate) fn with_config(config: GatewayConfig) -> Self {
+        let dir = TempDir::new().unwrap();
+        let store = Arc::new(RocksStore::open(dir.path()).unwrap());
+        let control_config = ControlConfig {
+            webhook_allow_private_targets: true,
+            ..ControlConfig::default()
+        };
+        let control = Arc::new(
+            ControlPlaneService::new(store, control_config)
+                .with_secret_cipher(SecretCipher::new(&[7u8; 32]).unwrap()),
+        );
+        let state = GatewayState::new(control, Arc::new(MockJwtValidator::default()), config);
+        let server = TestServer::new(create_router(state)).unwrap();
+
+        Self { server, _dir: dir }
+    }
+
+    /// A gateway where `admin` is a platform administrator.
+    pub(crate) fn with_admin(admin: &TestUser) -> Self {
+        Self::with_config(GatewayConfig {
+            admin_identities: vec![admin.identity_id],
+            ..GatewayConfig::default()
+        })
+    }
+
+    pub(crate) fn get(&self, user: &TestUser, path: &str) -> TestRequest {
+        self.server.get(path).authorization_bearer(user.token())
+    }
+
+    pub(crate) fn post(&self, user: &TestUser, path: &str) -> TestRequest {
+        self.server.post(path).authorization_bearer(user.token())
+    }
+
+    pub(crate) fn put(&self, user: &TestUser, path: &str) -> TestRequest {
+        self.server.put(path).authorization_bearer(user.token())
+    }
+
+    pub(crate) fn delete(&self, user: &TestUser, path: &str) -> TestRequest {
+        self.server.delete(path).authorization_bearer(user.token())
+    }
+
+    /// Move an agent to `status` through the scheduler callback.
+    pub(crate) async fn set_status(&self, agent_id: &str, status: &str) {
+        self.server
+            .patch(&format!("/internal/agents/{agent_id}/status"))
+            .json(&json!({ "status": status }))
+            .await
+            .assert_status_ok();
+    }
+
+    /// Grant `grantee` a role on an agent as its owner.
+    pub(crate) async fn grant(
+        &self,
+        owner: &TestUser,
+        agent_id: &str,
+        grantee: &TestUser,
+        role: &str,
+    ) {
+        let path = format!(
+            "/v1/agents/{agent_id}/access/{}",
+            grantee.user_id().to_hex()
+        );
+        self.put(owner, &path)
+            .json(&json!({ "role": role }))
+            .await
+            .assert_status_ok();
+    }
+
+    /// Create an agent through the API and return its ID.
+    pub(crate) async fn create_agent(&self, user: &TestUser, name: &str) -> String {
+        let response = self
+            .post(user, "/v1/agents")
+            .json(&json!({ "name": name }))
+            .await;
+        response.assert_status(axum::http::StatusCode::CREATED);
+        response.json::<Value>()["agent_id"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+}
@@ -36,6 +36,21 @@ pub enum SchedulerError {
     /// Health check failed.
     #[error("Health check failed: {0}")]
     HealthCheckFailed(String),
+
+    /// A state maintenance job failed.
+    #[error("Job failed: {0}")]
+    JobFailed(String),
+
+    /// The metrics API could not be queried.
+    ///
+    /// Kept apart from [`SchedulerError::KubeApi`] so that a missing or broken
+    /// metrics-server is not mistaken for the scheduler being unavailable.
+    #[error("Metrics API error: {0}")]
+    Metrics(String),
+
+    /// A local file or process operation failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 impl SchedulerError {
@@ -54,7 +69,7 @@ impl SchedulerError {
         match self {
             Self::PodNotFound(_) => 404,
             Self::InvalidAgentId(_) | Self::Config(_) => 400,
-            Self::PodCreationFailed(_) => 500,
+            Self::PodCreationFailed(_) | Self::JobFailed(_) | Self::Metrics(_) | Self::Io(_) => 500,
             Self::KubeApi(_) | Self::Timeout(_) | Self::Store(_) | Self::HealthCheckFailed(_) => {
                 503
             }
@@ -0,0 +1,578 @@
+//! An in-process fake of the Kubernetes API for testing [`K8sScheduler`].
+//!
+//! [`FakeKubeApi`] serves the pod and event endpoints the scheduler's
+//! reconciler uses — list, get, create, delete and watch — from memory, so
+//! [`K8sScheduler::with_client`] can be pointed at it with
+//! [`FakeKubeApi::client`]. Tests then drive the reconciler by changing pods
+//! and adding events:
+//!
+//! ```ignore
+//! let api = FakeKubeApi::start().await;
+//! let scheduler = K8sScheduler::with_client(api.client(), config);
+//! tokio::spawn(async move { scheduler.run_reconciler().await });
+//!
+//! api.apply_pod(&pod);
+//! api.delete_pod("agent-0123456789abcdef");
+//! ```
+//!
+//! Lists and watches support equality label selectors (`key=value,...`);
+//! field selectors are ignored. All namespaces share one set of objects.
+//!
+//! Persistent volume claims can be listed, read, created and patched, and
+//! secrets read, so [`K8sScheduler::schedule_agent`] works with either state
+//! volume mode. Pods and claims accept JSON merge patches, which fail with a
+//! conflict if they name a stale `resourceVersion`. Network policies are
+//! accepted and discarded, and any other object is reported as not found.
+//!
+//! Deleted pods disappear at once unless [`FakeKubeApi::hold_deletions`] is
+//! called; then they are only marked as terminating, as the kubelet would
+//! leave them while the runtime shuts down, until
+//! [`FakeKubeApi::delete_pod`] removes them.
+//!
+//! [`K8sScheduler`]: crate::K8sScheduler
+//! [`K8sScheduler::with_client`]: crate::K8sScheduler::with_client
+//! [`K8sScheduler::schedule_agent`]: crate::Scheduler::schedule_agent
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Path, Query, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, patch};
+use axum::{Json, Router};
+use futures::StreamExt;
+use k8s_openapi::api::core::v1::{Event, PersistentVolumeClaim, Pod, Secret};
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Watch events buffered for each watcher.
+const WATCH_BUFFER: usize = 256;
+
+/// Kinds of objects the fake serves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Kind {
+    Pod,
+    Event,
+    PersistentVolumeClaim,
+    Secret,
+}
+
+impl Kind {
+    fn list_kind(self) -> &'static str {
+        match self {
+            Self::Pod => "PodList",
+            Self::Event => "EventList",
+            Self::PersistentVolumeClaim => "PersistentVolumeClaimList",
+            Self::Secret => "SecretList",
+        }
+    }
+
+    fn plural(self) -> &'static str {
+        match self {
+            Self::Pod => "pods",
+            Self::Event => "events",
+            Self::PersistentVolumeClaim => "persistentvolumeclaims",
+            Self::Secret => "secrets",
+        }
+    }
+}
+
+/// A change to an object, as reported to watchers.
+#[derive(Debug)]
+struct Change {
+    resource_version: u64,
+    kind: Kind,
+    event_type: &'static str,
+    object: Value,
+}
+
+#[derive(Debug, Default)]
+struct Store {
+    resource_version: u64,
+    objects: BTreeMap<(Kind, String), Value>,
+    /// Every change so far, for watches that start from an older version.
+    history: Vec<Arc<Change>>,
+}
+
+#[derive(Debug)]
+struct Shared {
+    store: Mutex<Store>,
+    changes: broadcast::Sender<Arc<Change>>,
+    /// Whether deleted pods are kept as terminating.
+    hold_deletions: AtomicBool,
+}
+
+impl Shared {
+    /// Create or replace an object, returning it with its new resource version.
+    fn apply(&self, kind: Kind, mut object: Value) -> Value {
+        let name = object["metadata"]["name"]
+            .as_str()
+            .expect("objects need a name")
+            .to_string();
+
+        let mut store = self.store.lock();
+        store.resource_version += 1;
+        object["metadata"]["resourceVersion"] = json!(store.resource_version.to_string());
+
+        let existed = store.objects.insert((kind, name), object.clone()).is_some();
+        let event_type = if existed { "MODIFIED" } else { "ADDED" };
+        self.record(&mut store, kind, event_type, object.clone());
+        object
+    }
+
+    /// Remove an object, returning its last state.
+    fn remove(&self, kind: Kind, name: &str) -> Option<Value> {
+        let mut store = self.store.lock();
+        let mut object = store.objects.remove(&(kind, name.to_string()))?;
+        store.resource_version += 1;
+        object["metadata"]["resourceVersion"] = json!(store.resource_version.to_string());
+
+        self.record(&mut store, kind, "DELETED", object.clone());
+        Some(object)
+    }
+
+    fn get(&self, kind: Kind, name: &str) -> Option<Value> {
+        self.store
+            .lock()
+            .objects
+            .get(&(kind, name.to_string()))
+            .cloned()
+    }
+
+    /// Publish a change. Called with the store locked, so that watches
+    /// starting concurrently see each change exactly once.
+    fn record(&self, store: &mut Store, kind: Kind, event_type: &'static str, object: Value) {
+        let change = Arc::new(Change {
+            resource_version: store.resource_version,
+            kind,
+            event_type,
+            object,
+        });
+        store.history.push(Arc::clone(&change));
+        // Nobody watching is fine
+        let _ = self.changes.send(change);
+    }
+}
+
+/// An in-process fake Kubernetes API server serving pods, events, claims
+/// and secrets.
+///
+/// The server stops when this is dropped.
+#[derive(Debug)]
+pub struct FakeKubeApi {
+    shared: Arc<Shared>,
+    addr: SocketAddr,
+    server: JoinHandle<()>,
+}
+
+impl FakeKubeApi {
+    /// Start a fake API server on a free local port.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no local port can be bound.
+    pub async fn start() -> Self {
+        let shared = Arc::new(Shared {
+            store: Mutex::new(Store::default()),
+            changes: broadcast::channel(WATCH_BUFFER).0,
+            hold_deletions: AtomicBool::new(false),
+        });
+
+        let router = Router::new()
+            .route(
+                "/api/v1/namespaces/:namespace/pods",
+                get(list_pods).post(create_pod),
+            )
+            .route(
+                "/api/v1/namespaces/:namespace/pods/:name",
+                get(get_pod).delete(delete_pod).patch(patch_pod),
+            )
+            .route("/api/v1/namespaces/:namespace/events", get(list_events))
+            .route(
+                "/api/v1/namespaces/:namespace/persistentvolumeclaims",
+                get(list_claims).post(create_claim),
+            )
+            .route(
+                "/api/v1/namespaces/:namespace/persistentvolumeclaims/:name",
+                get(get_claim).patch(patch_claim),
+            )
+            .route(
+                "/api/v1/namespaces/:namespace/secrets/:name",
+                get(get_secret),
+            )
+            .route(
+                "/apis/networking.k8s.io/v1/namespaces/:namespace/networkpolicies/:name",
+                patch(apply_network_policy),
+            )
+            .fallback(|| async { status_response(StatusCode::NOT_FOUND, "NotFound", "not found") })
+            .with_state(Arc::clone(&shared));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind fake Kubernetes API");
+        let addr = listener
+            .local_addr()
+            .expect("fake Kubernetes API has no address");
+        let server = tokio::spawn(async move {
+            let _ = axum::serve(listener, router).await;
+        });
+
+        Self {
+            shared,
+            addr,
+            server,
+        }
+    }
+
+    /// The base URL of the server.
+    #[must_use]
+    pub fn uri(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// A Kubernetes client talking to the server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the client cannot be created.
+    #[must_use]
+    pub fn client(&self) -> kube::Client {
+        let config = kube::Config::new(self.uri().parse().expect("invalid fake API URL"));
+        kube::Client::try_from(config).expect("failed to create client for fake API")
+    }
+
+    /// Create or replace a pod, notifying watchers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pod has no name.
+    pub fn apply_pod(&self, pod: &Pod) {
+        self.shared.apply(Kind::Pod, to_value(pod));
+    }
+
+    /// Keep pods deleted through the API as terminating, with a deletion
+    /// timestamp, until [`FakeKubeApi::delete_pod`] removes them.
+    pub fn hold_deletions(&self) {
+        self.shared.hold_deletions.store(true, Ordering::SeqCst);
+    }
+
+    /// Delete a pod, notifying watchers. Returns the deleted pod, if any.
+    #[must_use = "the pod may not have existed"]
+    pub fn delete_pod(&self, name: &str) -> Option<Pod> {
+        self.shared.remove(Kind::Pod, name).map(from_value)
+    }
+
+    /// Get a pod by name.
+    #[must_use]
+    pub fn pod(&self, name: &str) -> Option<Pod> {
+        self.shared.get(Kind::Pod, name).map(from_value)
+    }
+
+    /// Add or update an event, notifying watchers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the event has no name.
+    pub fn add_event(&self, event: &Event) {
+        self.shared.apply(Kind::Event, to_value(event));
+    }
+
+    /// Get a persistent volume claim by name.
+    #[must_use]
+    pub fn claim(&self, name: &str) -> Option<PersistentVolumeClaim> {
+        self.shared
+            .get(Kind::PersistentVolumeClaim, name)
+            .map(from_value)
+    }
+
+    /// Create or replace a secret.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the secret has no name.
+    pub fn apply_secret(&self, secret: &Secret) {
+        self.shared.apply(Kind::Secret, to_value(secret));
+    }
+}
+
+impl Drop for FakeKubeApi {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+fn to_value<T: serde::Serialize>(object: &T) -> Value {
+    serde_json::to_value(object).expect("Kubernetes objects serialize to JSON")
+}
+
+fn from_value<T: serde::de::DeserializeOwned>(object: Value) -> T {
+    serde_json::from_value(object).expect("stored objects are valid")
+}
+
+type Params = Query<HashMap<String, String>>;
+
+async fn list_pods(State(shared): State<Arc<Shared>>, Query(params): Params) -> Response {
+    list_or_watch(&shared, Kind::Pod, &params)
+}
+
+async fn list_events(State(shared): State<Arc<Shared>>, Query(params): Params) -> Response {
+    list_or_watch(&shared, Kind::Event, &params)
+}
+
+async fn list_claims(State(shared): State<Arc<Shared>>, Query(params): Params) -> Response {
+    list_or_watch(&shared, Kind::PersistentVolumeClaim, &params)
+}
+
+async fn get_pod(
+    State(shared): State<Arc<Shared>>,
+    Path((_namespace, name)): Path<(String, String)>,
+) -> Response {
+    get_object(&shared, Kind::Pod, &name)
+}
+
+async fn get_claim(
+    State(shared): State<Arc<Shared>>,
+    Path((_namespace, name)): Path<(String, String)>,
+) -> Response {
+    get_object(&shared, Kind::PersistentVolumeClaim, &name)
+}
+
+async fn get_secret(
+    State(shared): State<Arc<Shared>>,
+    Path((_namespace, name)): Path<(String, String)>,
+) -> Response {
+    get_object(&shared, Kind::Secret, &name)
+}
+
+fn get_object(shared: &Shared, kind: Kind, name: &str) -> Response {
+    match shared.get(kind, name) {
+        Some(object) => Json(object).into_response(),
+        None => not_found(kind, name),
+    }
+}
+
+async fn create_pod(State(shared): State<Arc<Shared>>, Json(pod): Json<Value>) -> Response {
+    create_object(&shared, Kind::Pod, pod)
+}
+
+async fn create_claim(State(shared): State<Arc<Shared>>, Json(claim): Json<Value>) -> Response {
+    create_object(&shared, Kind::PersistentVolumeClaim, claim)
+}
+
+fn create_object(shared: &Shared, kind: Kind, object: Value) -> Response {
+    let name = object["metadata"]["name"].as_str().unwrap_or_default();
+    if shared.get(kind, name).is_some() {
+        return status_response(
+            StatusCode::CONFLICT,
+            "AlreadyExists",
+            &format!("{} \"{name}\" already exists", kind.plural()),
+        );
+    }
+    (StatusCode::CREATED, Json(shared.apply(kind, object))).into_response()
+}
+
+async fn patch_pod(
+    State(shared): State<Arc<Shared>>,
+    Path((_namespace, name)): Path<(String, String)>,
+    body: Bytes,
+) -> Response {
+    patch_object(&shared, Kind::Pod, &name, &body)
+}
+
+async fn patch_claim(
+    State(shared): State<Arc<Shared>>,
+    Path((_namespace, name)): Path<(String, String)>,
+    body: Bytes,
+) -> Response {
+    patch_object(&shared, Kind::PersistentVolumeClaim, &name, &body)
+}
+
+fn patch_object(shared: &Shared, kind: Kind, name: &str, body: &[u8]) -> Response {
+    let patch: Value = match serde_json::from_slice(body) {
+        Ok(patch) => patch,
+        Err(e) => return status_response(StatusCode::BAD_REQUEST, "BadRequest", &e.to_string()),
+    };
+    let Some(mut object) = shared.get(kind, name) else {
+        return not_found(kind, name);
+    };
+
+    let expected = &patch["metadata"]["resourceVersion"];
+    if !expected.is_null() && *expected != object["metadata"]["resourceVersion"] {
+        return status_response(
+            StatusCode::CONFLICT,
+            "Conflict",
+            &format!("{} \"{name}\" has been modified", kind.plural()),
+        );
+    }
+
+    merge(&mut object, patch);
+    Json(shared.apply(kind, object)).into_response()
+}
+
+/// Apply a JSON merge patch (RFC 7386): objects are merged, nulls remove
+/// fields and anything else replaces the target.
+fn merge(target: &mut Value, patch: Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch;
+        return;
+    };
+    if !target.is_object() {
+        *target = json!({});
+    }
+    let target = target.as_object_mut().expect("target was made an object");
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(&key);
+        } else {
+            merge(target.entry(key).or_insert(Value::Null), value);
+        }
+    }
+}
+
+async fn delete_pod(
+    State(shared): State<Arc<Shared>>,
+    Path((_namespace, name)): Path<(String, String)>,
+) -> Response {
+    if !shared.hold_deletions.load(Ordering::SeqCst) {
+        return match shared.remove(Kind::Pod, &name) {
+            Some(pod) => Json(pod).into_response(),
+            None => not_found(Kind::Pod, &name),
+        };
+    }
+
+    match shared.get(Kind::Pod, &name) {
+        Some(pod) if !pod["metadata"]["deletionTimestamp"].is_null() => Json(pod).into_response(),
+        Some(mut pod) => {
+            pod["metadata"]["deletionTimestamp"] =
+                json!(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+            Json(shared.apply(Kind::Pod, pod)).into_response()
+        }
+        None => not_found(Kind::Pod, &name),
+    }
+}
+
+/// Accept a server-side apply of a network policy without storing it.
+async fn apply_network_policy(body: Bytes) -> Response {
+    match serde_json::from_slice::<Value>(&body) {
+        Ok(policy) => Json(policy).into_response(),
+        Err(e) => status_response(StatusCode::BAD_REQUEST, "BadRequest", &e.to_string()),
+    }
+}
+
+fn list_or_watch(shared: &Shared, kind: Kind, params: &HashMap<String, String>) -> Response {
+    let selector = params.get("labelSelector").cloned().unwrap_or_default();
+
+    if params
+        .get("watch")
+        .is_some_and(|watch| watch == "true" || watch == "1")
+    {
+        let since = params
+            .get("resourceVersion")
+            .and_then(|version| version.parse().ok())
+            .unwrap_or(0);
+        return watch(shared, kind, selector, since);
+    }
+
+    let store = shared.store.lock();
+    let items: Vec<&Value> = store
+        .objects
+        .iter()
+        .filter(|((k, _), object)| *k == kind && matches_labels(object, &selector))
+        .map(|(_, object)| object)
+        .collect();
+
+    Json(json!({
+        "apiVersion": "v1",
+        "kind": kind.list_kind(),
+        "metadata": { "resourceVersion": store.resource_version.to_string() },
+        "items": items,
+    }))
+    .into_response()
+}
+
+/// Stream changes after `since` as newline-delimited watch events.
+fn watch(shared: &Shared, kind: Kind, selector: String, since: u64) -> Response {
+    let (backlog, live) = {
+        let store = shared.store.lock();
+        let backlog: Vec<Arc<Change>> = store
+            .history
+            .iter()
+            .filter(|change| change.resource_version > since)
+            .cloned()
+            .collect();
+        (backlog, shared.changes.subscribe())
+    };
+
+    let live = futures::stream::unfold(live, |mut live| async move {
+        loop {
+            match live.recv().await {
+                Ok(change) => return Some((change, live)),
+                Err(broadcast::error::RecvError::Lagged(_)) => {}
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    let lines = futures::stream::iter(backlog)
+        .chain(live)
+        .filter(move |change| {
+            futures::future::ready(change.kind == kind && matches_labels(&change.object, &selector))
+        })
+        .map(|change| {
+            let mut event = serde_json::to_vec(&json!({
+                "type": change.event_type,
+                "object": change.object,
+            }))
+            .expect("watch events serialize to JSON");
+            event.push(b'\n');
+            Ok::<_, Infallible>(Bytes::from(event))
+        });
+
+    (
+        [(header::CONTENT_TYPE, "application/json")],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+/// Whether an object's labels satisfy an equality label selector.
+fn matches_labels(object: &Value, selector: &str) -> bool {
+    selector
+        .split(',')
+        .filter_map(|requirement| requirement.split_once('='))
+        .all(|(key, value)| {
+            object["metadata"]["labels"][key.trim()].as_str()
+                == Some(value.trim_start_matches('=').trim())
+        })
+}
+
+fn not_found(kind: Kind, name: &str) -> Response {
+    status_response(
+        StatusCode::NOT_FOUND,
+        "NotFound",
+        &format!("{} \"{name}\" not found", kind.plural()),
+    )
+}
+
+fn status_response(code: StatusCode, reason: &str, message: &str) -> Response {
+    (
+        code,
+        Json(json!({
+            "kind": "Status",
+            "apiVersion": "v1",
+            "metadata": {},
+            "status": "Failure",
+            "message": message,
+            "reason": reason,
+            "code": code.as_u16(),
+        })),
+    )
+        .into_response()
+}
@@ -0,0 +1,275 @@
+//! Job specification builder for state maintenance tasks.
+//!
+//! Operations on whole agent state volumes (cloning and deleting state) run
+//! as short-lived Kubernetes Jobs. On the shared state PVC, where each agent
+//! has its own subdirectory, the job mounts the whole volume. With per-agent
+//! volumes the job mounts the agents' claims directly, and deleting state
+//! removes the claim instead of running a job.
+
+use aura_swarm_core::AgentId;
+use k8s_openapi::api::batch::v1::{Job, JobSpec};
+use k8s_openapi::api::core::v1::{Container, PodSpec, PodTemplateSpec, Volume, VolumeMount};
+use kube::api::ObjectMeta;
+use std::collections::BTreeMap;
+
+use crate::pod::{build_claim_volume, build_security_context, truncate_for_label};
+use crate::types::StateVolumeMode;
+use crate::volume::pvc_name_for_agent;
+use crate::SchedulerConfig;
+
+/// How long finished jobs are kept before Kubernetes garbage-collects them.
+const JOB_TTL_SECONDS: i32 = 300;
+
+/// Build a Kubernetes Job that copies one agent's state into another's.
+///
+/// On the shared state PVC the job mounts the entire volume at `/state` and
+/// copies the contents of `/state/{source}` into `/state/{target}`; a missing
+/// source directory is treated as empty state. With per-agent volumes it
+/// mounts the source's claim, `source_claim`, read-only at `/source` and the
+/// target's claim at `/target`, which must both exist. Ownership and modes
+/// are preserved.
+#[must_use]
+pub fn build_state_copy_job(
+    source: &AgentId,
+    source_claim: &str,
+    target: &AgentId,
+    config: &SchedulerConfig,
+) -> Job {
+    let source_hex = source.to_hex();
+    let target_hex = target.to_hex();
+
+    let (script, volumes, mounts) = match config.state_volume_mode {
+        StateVolumeMode::Shared => (
+            format!(
+                "set -e; mkdir -p /state/{target_hex}; \
+                 if [ -d /state/{source_hex} ]; then \
+                 cp -a /state/{source_hex}/. /state/{target_hex}/; fi"
+            ),
+            vec![build_claim_volume("state", config.state_pvc_name.clone())],
+            vec![build_job_mount("state", "/state", false)],
+        ),
+        StateVolumeMode::PerAgent => (
+            "set -e; cp -a /source/. /target/".to_string(),
+            vec![
+                build_claim_volume("source", source_claim.to_string()),
+                build_claim_volume("target", pvc_name_for_agent(target)),
+            ],
+            vec![
+                build_job_mount("source", "/source", true),
+                build_job_mount("target", "/target", false),
+            ],
+        ),
+    };
+
+    let mut annotations = BTreeMap::new();
+    annotations.insert("swarm.io/source-agent-id-full".to_string(), source_hex);
+
+    build_state_job(
+        "state-copy",
+        script,
+        target,
+        annotations,
+        volumes,
+        mounts,
+        config,
+    )
+}
+
+/// Build a Kubernetes Job that removes an agent's directory from the shared
+/// state PVC.
+///
+/// The job mounts the entire state PVC at `/state` and deletes
+/// `/state/{agent}`. A missing directory is not an error.
+#[must_use]
+pub fn build_state_cleanup_job(agent_id: &AgentId, config: &SchedulerConfig) -> Job {
+    let script = format!("set -e; rm -rf /state/{}", agent_id.to_hex());
+
+    build_state_job(
+        "state-cleanup",
+        script,
+        agent_id,
+        BTreeMap::new(),
+        vec![build_claim_volume("state", config.state_pvc_name.clone())],
+        vec![build_job_mount("state", "/state", false)],
+        config,
+    )
+}
+
+fn build_job_mount(name: &str, mount_path: &str, read_only: bool) -> VolumeMount {
+    VolumeMount {
+        name: name.to_string(),
+        mount_path: mount_path.to_string(),
+        read_only: read_only.then_some(true),
+        ..Default::default()
+    }
+}
+
+/// Build a single-attempt Job named after `kind` and the agent that runs
+/// `script` with the given volumes mounted.
+fn build_state_job(
+    kind: &str,
+    script: String,
+    agent_id: &AgentId,
+    mut annotations: BTreeMap<String, String>,
+    volumes: Vec<Volume>,
+    mounts: Vec<VolumeMount>,
+    config: &SchedulerConfig,
+) -> Job {
+    let agent_hex = agent_id.to_hex();
+    let job_name = format!(
+        "{kind}-{}-{}",
+        &agent_hex[..16],
+        chrono::Utc::now().timestamp()
+    );
+
+    let mut labels = BTreeMap::new();
+    labels.insert("app".to_string(), "swarm-state-job".to_string());
+    labels.insert(
+        "swarm.io/agent-id".to_string(),
+        truncate_for_label(&agent_hex),
+    );
+    annotations.insert("swarm.io/agent-id-full".to_string(), agent_hex);
+
+    let container = Container {
+        name: kind.to_string(),
+        image: Some(config.state_job_image.clone()),
+        command: Some(vec!["sh".to_string(), "-c".to_string(), script]),
+        volume_mounts: Some(mounts),
+        ..Default::default()
+    };
+
+    Job {
+        metadata: ObjectMeta {
+            name: Some(job_name),
+            namespace: Some(config.namespace.clone()),
+            labels: Some(labels.clone()),
+            annotations: Some(annotations),
+            ..Default::default()
+        },
+        spec: Some(JobSpec {
+            backoff_limit: Some(0),
+            ttl_seconds_after_finished: Some(JOB_TTL_SECONDS),
+            template: PodTemplateSpec {
+                metadata: Some(ObjectMeta {
+                    labels: Some(labels),
+                    ..Default::default()
+                }),
+                spec: Some(PodSpec {
+                    containers: vec![container],
+                    volumes: Some(volumes),
+                    restart_policy: Some("Never".to_string()),
+                    security_context: Some(build_security_context()),
+                    ..Default::default()
+                }),
+            },
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::UserId;
+
+    #[test]
+    fn state_copy_job_copies_between_agent_dirs() {
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let source = AgentId::generate(&user_id, "source");
+        let target = AgentId::generate(&user_id, "target");
+        let config = SchedulerConfig {
+            state_volume_mode: StateVolumeMode::Shared,
+            ..SchedulerConfig::default()
+        };
+
+        let job = build_state_copy_job(&source, &config.state_pvc_name, &target, &config);
+
+        let name = job.metadata.name.as_ref().unwrap();
+        assert!(name.starts_with(&format!("state-copy-{}", &target.to_hex()[..16])));
+
+        let spec = job.spec.as_ref().unwrap();
+        assert_eq!(spec.backoff_limit, Some(0));
+
+        let pod_spec = spec.template.spec.as_ref().unwrap();
+        assert_eq!(pod_spec.restart_policy.as_deref(), Some("Never"));
+
+        let volume = &pod_spec.volumes.as_ref().unwrap()[0];
+        assert_eq!(
+            volume.persistent_volume_claim.as_ref().unwrap().claim_name,
+            config.state_pvc_name
+        );
+
+        let container = &pod_spec.containers[0];
+        assert_eq!(container.image.as_deref(), Some("busybox:1.36"));
+        let mount = &container.volume_mounts.as_ref().unwrap()[0];
+        assert_eq!(mount.mount_path, "/state");
+        assert!(mount.sub_path.is_none());
+
+        let script = &container.command.as_ref().unwrap()[2];
+        assert!(script.contains(&format!(
+            "cp -a /state/{}/. /state/{}/",
+            source.to_hex(),
+            target.to_hex()
+        )));
+    }
+
+    #[test]
+    fn state_copy_job_mounts_per_agent_claims() {
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let source = AgentId::generate(&user_id, "source");
+        let target = AgentId::generate(&user_id, "target");
+        let config = SchedulerConfig::default();
+
+        let job = build_state_copy_job(&source, &pvc_name_for_agent(&source), &target, &config);
+        let pod_spec = job.spec.as_ref().unwrap().template.spec.as_ref().unwrap();
+
+        let claims: Vec<_> = pod_spec
+            .volumes
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|v| {
+                v.persistent_volume_claim
+                    .as_ref()
+                    .unwrap()
+                    .claim_name
+                    .clone()
+            })
+            .collect();
+        assert_eq!(
+            claims,
+            vec![pvc_name_for_agent(&source), pvc_name_for_agent(&target)]
+        );
+
+        let container = &pod_spec.containers[0];
+        let mounts = container.volume_mounts.as_ref().unwrap();
+        assert_eq!(mounts[0].mount_path, "/source");
+        assert_eq!(mounts[0].read_only, Some(true));
+        assert_eq!(mounts[1].mount_path, "/target");
+        assert_eq!(
+            container.command.as_ref().unwrap()[2],
+            "set -e; cp -a /source/. /target/"
+        );
+    }
+
+    #[test]
+    fn state_cleanup_job_removes_agent_dir() {
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let agent_id = AgentId::generate(&user_id, "doomed");
+        let config = SchedulerConfig::default();
+
+        let job = build_state_cleanup_job(&agent_id, &config);
+
+        let name = job.metadata.name.as_ref().unwrap();
+        assert!(name.starts_with(&format!("state-cleanup-{}", &agent_id.to_hex()[..16])));
+        assert_eq!(
+            job.metadata.annotations.as_ref().unwrap()["swarm.io/agent-id-full"],
+            agent_id.to_hex()
+        );
+
+        let pod_spec = job.spec.as_ref().unwrap().template.spec.as_ref().unwrap();
+        let script = &pod_spec.containers[0].command.as_ref().unwrap()[2];
+        assert!(script.ends_with(&format!("rm -rf /state/{}", agent_id.to_hex())));
+    }
+}
@@ -3,25 +3,59 @@
 //! This module provides the `K8sScheduler` which manages agent pods in a
 //! Kubernetes cluster using the Kata Containers runtime for microVM isolation.
 
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use futures::StreamExt;
-use k8s_openapi::api::core::v1::{Event, Pod};
-use kube::api::{Api, DeleteParams, ListParams, PostParams};
+use chrono::Utc;
+use futures::stream::BoxStream;
+use futures::{AsyncBufReadExt, StreamExt};
+use k8s_openapi::api::batch::v1::Job;
+use k8s_openapi::api::core::v1::{Event, PersistentVolumeClaim, Pod, Secret};
+use k8s_openapi::api::networking::v1::NetworkPolicy;
+use kube::api::{
+    Api, DeleteParams, DynamicObject, ListParams, LogParams, Patch, PatchParams, PostParams,
+};
+use kube::runtime::wait::await_condition;
 use kube::runtime::watcher::{self, watcher, Config as WatcherConfig};
 use kube::Client;
-use serde::Serialize;
 use tracing::{debug, error, info, warn};
 
 use aura_swarm_core::AgentId;
 use aura_swarm_store::{AgentSpec, AgentState};
 
 use crate::cache::EndpointCache;
-use crate::pod::{build_pod, pod_name_for_agent};
-use crate::types::{PodInfo, PodPhase, PodStatus, SchedulerConfig};
+use crate::job::{build_state_cleanup_job, build_state_copy_job};
+use crate::metrics::{pod_metrics_from_object, pod_metrics_resource};
+use crate::network::{
+    build_network_policy, egress_for_spec, hosts_to_resolve, network_policy_name_for_agent,
+    HostAddresses,
+};
+use crate::outbox::{StatusOutbox, StatusUpdate};
+use crate::pod::{
+    agent_label_selector, build_activation_env, build_pod, pod_name_for_agent, AURA_CONTAINER,
+    AURA_PORT,
+};
+use crate::pool::{
+    bind_claim_patch, bind_pod_patch, build_warm_claim, build_warm_pod, claim_patch,
+    claimed_selector, is_abandoned_claim, is_claimable, needs_recycling, pool_for_spec,
+    sort_for_claiming, warm_claim_name, warm_pod_name, warm_selector, ActivationRequest,
+    WARM_POD_PREFIX,
+};
+use crate::secret::{build_agent_secret, secret_name_for_agent};
+use crate::types::{
+    PodInfo, PodLogOptions, PodMetrics, PodPhase, PodStatus, SchedulerConfig, StateVolumeMode,
+    WarmPoolConfig,
+};
+use crate::volume::{build_agent_pvc, build_cloned_pvc, pvc_name_for_agent, storage_gb_for_spec};
 use crate::{Result, SchedulerError};
 
+/// A stream of log lines from an agent's pod.
+///
+/// Each line starts with the RFC 3339 time it was written, followed by a space.
+pub type LogStream = BoxStream<'static, Result<String>>;
+
 /// The `Scheduler` trait defines the interface for pod lifecycle management.
 #[async_trait]
 pub trait Scheduler: Send + Sync {
@@ -71,8 +105,93 @@ pub trait Scheduler: Send + Sync {
     ///
     /// Returns an error if the health check fails.
     async fn check_agent_health(&self, agent_id: &AgentId) -> Result<bool>;
+
+    /// Get the current resource usage of an agent's pod.
+    ///
+    /// Returns `None` if the metrics API has no sample for the pod, which is
+    /// the case for pods that don't exist or have only just started.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the metrics API cannot be queried.
+    async fn get_pod_metrics(&self, agent_id: &AgentId) -> Result<Option<PodMetrics>>;
+
+    /// Read the logs of an agent's runtime container.
+    ///
+    /// With `follow`, the stream stays open until the container stops or the
+    /// stream is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SchedulerError::PodNotFound` if the agent has no pod, or an
+    /// error if the log stream cannot be opened. Errors while reading are
+    /// returned by the stream.
+    async fn pod_logs(&self, agent_id: &AgentId, options: &PodLogOptions) -> Result<LogStream>;
+
+    /// Copy one agent's persistent state into another agent's.
+    ///
+    /// The target's state volume or directory is created if it does not
+    /// exist. A source agent without any state results in empty target state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the copy cannot be started or does not complete successfully.
+    async fn copy_agent_state(&self, source: &AgentId, target: &AgentId) -> Result<()>;
+
+    /// Remove an agent's persistent state (its own volume, or its directory
+    /// on the shared volume) and forget its cached endpoint.
+    ///
+    /// Removing state that does not exist succeeds.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the cleanup cannot be started or does not complete successfully.
+    async fn delete_agent_state(&self, agent_id: &AgentId) -> Result<()>;
+
+    /// Replace the agent's Secret with `secrets`.
+    ///
+    /// Pods scheduled afterwards reference every key of the Secret. An empty
+    /// map deletes the Secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the Secret cannot be written.
+    async fn put_agent_secrets(
+        &self,
+        agent_id: &AgentId,
+        secrets: &BTreeMap<String, String>,
+    ) -> Result<()>;
+
+    /// Delete the agent's Secret, if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if Secret deletion fails (except 404).
+    async fn delete_agent_secrets(&self, agent_id: &AgentId) -> Result<()>;
+
+    /// Delete the agent's `NetworkPolicy`, if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if policy deletion fails (except 404).
+    async fn delete_network_policy(&self, agent_id: &AgentId) -> Result<()>;
 }
 
+/// Field manager used for server-side apply.
+const FIELD_MANAGER: &str = "aura-swarm-scheduler";
+
+/// Log lines buffered between the Kubernetes log stream and its reader.
+const LOG_BUFFER_LINES: usize = 256;
+
+/// How often warm pools are refilled and their old pods replaced.
+const WARM_POOL_SYNC_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How often a terminating pod is checked while waiting for it to go away.
+const POD_DELETION_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How long past its grace period a terminating pod is waited for.
+const POD_DELETION_SLACK: Duration = Duration::from_secs(10);
+
 /// Kubernetes-based scheduler for agent pods.
 ///
 /// This scheduler creates and manages pods in a Kubernetes cluster,
@@ -82,6 +201,7 @@ pub struct K8sScheduler {
     config: SchedulerConfig,
     endpoint_cache: EndpointCache,
     http_client: reqwest::Client,
+    status_outbox: Arc<StatusOutbox>,
 }
 
 impl K8sScheduler {
@@ -101,11 +221,14 @@ impl K8sScheduler {
             .build()
             .map_err(|e| SchedulerError::Config(format!("Failed to create HTTP client: {e}")))?;
 
+        let status_outbox = Self::build_status_outbox(&http_client, &config);
+
         Ok(Self {
             client,
             config,
             endpoint_cache: EndpointCache::new(),
             http_client,
+            status_outbox,
         })
     }
 
@@ -122,21 +245,40 @@ impl K8sScheduler {
             .timeout(Duration::from_secs(5))
             .build()
             .expect("Failed to create HTTP client");
+        let status_outbox = Self::build_status_outbox(&http_client, &config);
 
         Self {
             client,
             config,
             endpoint_cache: EndpointCache::new(),
             http_client,
+            status_outbox,
         }
     }
 
+    fn build_status_outbox(
+        http_client: &reqwest::Client,
+        config: &SchedulerConfig,
+    ) -> Arc<StatusOutbox> {
+        Arc::new(StatusOutbox::new(
+            http_client.clone(),
+            config.gateway_url.clone(),
+            Duration::from_secs(config.status_retry_max_seconds),
+        ))
+    }
+
     /// Get a reference to the scheduler config.
     #[must_use]
     pub fn config(&self) -> &SchedulerConfig {
         &self.config
     }
 
+    /// Get the queue of status updates waiting to be sent to the gateway.
+    #[must_use]
+    pub fn status_outbox(&self) -> &Arc<StatusOutbox> {
+        &self.status_outbox
+    }
+
     /// Get the pods API client for the configured namespace.
     fn pods_api(&self) -> Api<Pod> {
         Api::namespaced(self.client.clone(), &self.config.namespace)
@@ -147,15 +289,483 @@ impl K8sScheduler {
         Api::namespaced(self.client.clone(), &self.config.namespace)
     }
 
+    /// Get the jobs API client for the configured namespace.
+    fn jobs_api(&self) -> Api<Job> {
+        Api::namespaced(self.client.clone(), &self.config.namespace)
+    }
+
+    /// Get the persistent volume claims API client for the configured namespace.
+    fn pvcs_api(&self) -> Api<PersistentVolumeClaim> {
+        Api::namespaced(self.client.clone(), &self.config.namespace)
+    }
+
+    /// Get the secrets API client for the configured namespace.
+    fn secrets_api(&self) -> Api<Secret> {
+        Api::namespaced(self.client.clone(), &self.config.namespace)
+    }
+
+    /// Get the network policies API client for the configured namespace.
+    fn network_policies_api(&self) -> Api<NetworkPolicy> {
+        Api::namespaced(self.client.clone(), &self.config.namespace)
+    }
+
+    /// Get the pod metrics API client for the configured namespace.
+    fn pod_metrics_api(&self) -> Api<DynamicObject> {
+        Api::namespaced_with(
+            self.client.clone(),
+            &self.config.namespace,
+            &pod_metrics_resource(),
+        )
+    }
+
+    /// The keys of the agent's Secret, or none if it has no Secret.
+    async fn agent_secret_keys(&self, agent_id: &AgentId) -> Result<Vec<String>> {
+        let secret = self
+            .secrets_api()
+            .get_opt(&secret_name_for_agent(agent_id))
+            .await?;
+
+        Ok(secret
+            .and_then(|s| s.data)
+            .map(|data| data.into_keys().collect())
+            .unwrap_or_default())
+    }
+
+    /// Find an agent's pod: the one named after it or, for agents started on
+    /// a warm pod, the one labelled with its ID.
+    ///
+    /// Pods that are being deleted are ignored.
+    async fn find_agent_pod(&self, agent_id: &AgentId) -> Result<Option<Pod>> {
+        let pods = self.pods_api();
+        let live = |pod: &Pod| pod.metadata.deletion_timestamp.is_none();
+
+        if let Some(pod) = pods.get_opt(&pod_name_for_agent(agent_id)).await? {
+            return Ok(Some(pod).filter(live));
+        }
+
+        let params = ListParams::default().labels(&agent_label_selector(agent_id));
+        Ok(pods
+            .list(&params)
+            .await?
+            .items
+            .into_iter()
+            .find(|pod| live(pod) && Self::extract_agent_id(pod) == Some(*agent_id)))
+    }
+
+    /// Wait until the pod named after an agent, if it is terminating, is
+    /// gone, so that its replacement can take the name.
+    ///
+    /// The wait is bounded by the pod's termination grace period.
+    async fn wait_for_pod_deletion(&self, agent_id: &AgentId) -> Result<()> {
+        let pods = self.pods_api();
+        let pod_name = pod_name_for_agent(agent_id);
+
+        let Some(pod) = pods.get_opt(&pod_name).await? else {
+            return Ok(());
+        };
+        let grace_seconds = pod
+            .spec
+            .as_ref()
+            .and_then(|spec| spec.termination_grace_period_seconds)
+            .and_then(|seconds| u64::try_from(seconds).ok())
+            .unwrap_or(30);
+        let deadline =
+            tokio::time::Instant::now() + Duration::from_secs(grace_seconds) + POD_DELETION_SLACK;
+
+        let mut terminating = pod;
+        while terminating.metadata.deletion_timestamp.is_some() {
+            if tokio::time::Instant::now() >= deadline {
+                return Err(SchedulerError::Timeout(format!(
+                    "pod {pod_name} is still terminating"
+                )));
+            }
+            debug!(agent_id = %agent_id, pod_name, "Waiting for terminating pod to be deleted");
+            tokio::time::sleep(POD_DELETION_POLL_INTERVAL).await;
+            match pods.get_opt(&pod_name).await? {
+                Some(pod) => terminating = pod,
+                None => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Find an agent's own state claim: the one named after it or, for
+    /// agents started on a warm pod, the one labelled with its ID.
+    ///
+    /// Claims that are being deleted are ignored.
+    async fn find_state_claim(&self, agent_id: &AgentId) -> Result<Option<PersistentVolumeClaim>> {
+        let pvcs = self.pvcs_api();
+        let live = |pvc: &PersistentVolumeClaim| pvc.metadata.deletion_timestamp.is_none();
+
+        if let Some(pvc) = pvcs.get_opt(&pvc_name_for_agent(agent_id)).await? {
+            return Ok(Some(pvc).filter(live));
+        }
+
+        let agent_id_hex = agent_id.to_hex();
+        let params = ListParams::default().labels(&agent_label_selector(agent_id));
+        Ok(pvcs.list(&params).await?.items.into_iter().find(|pvc| {
+            live(pvc)
+                && pvc
+                    .metadata
+                    .annotations
+                    .as_ref()
+                    .and_then(|a| a.get("swarm.io/agent-id-full"))
+                    == Some(&agent_id_hex)
+        }))
+    }
+
+    /// Create or update the agent's `NetworkPolicy` from its egress policy.
+    ///
+    /// Host names that fail to resolve are left out of the policy.
+    async fn apply_network_policy(&self, agent_id: &AgentId, spec: &AgentSpec) -> Result<()> {
+        let egress = egress_for_spec(spec, &self.config);
+
+        let mut resolved = HostAddresses::new();
+        for host in hosts_to_resolve(egress) {
+            match tokio::net::lookup_host((host, 0)).await {
+                Ok(addrs) => {
+                    let mut ips: Vec<_> = addrs.map(|addr| addr.ip()).collect();
+                    ips.sort_unstable();
+                    ips.dedup();
+                    resolved.insert(host.to_string(), ips);
+                }
+                Err(e) => {
+                    warn!(
+                        agent_id = %agent_id,
+                        host,
+                        error = %e,
+                        "Failed to resolve egress host, leaving it out of the network policy"
+                    );
+                }
+            }
+        }
+
+        let policy = build_network_policy(agent_id, egress, &resolved, &self.config);
+        let policy_name = network_policy_name_for_agent(agent_id);
+        self.network_policies_api()
+            .patch(
+                &policy_name,
+                &PatchParams::apply(FIELD_MANAGER).force(),
+                &Patch::Apply(&policy),
+            )
+            .await?;
+
+        debug!(agent_id = %agent_id, policy_name, "Applied agent network policy");
+        Ok(())
+    }
+
+    /// Create a per-agent state claim unless it already exists.
+    async fn ensure_state_claim(
+        &self,
+        agent_id: &AgentId,
+        pvc: &PersistentVolumeClaim,
+    ) -> Result<()> {
+        let pvc_name = pvc_name_for_agent(agent_id);
+
+        match self.pvcs_api().create(&PostParams::default(), pvc).await {
+            Ok(_) => {
+                info!(agent_id = %agent_id, pvc_name, "Created agent state volume");
+                Ok(())
+            }
+            Err(kube::Error::Api(e)) if e.code == 409 => {
+                debug!(agent_id = %agent_id, pvc_name, "Agent state volume already exists");
+                Ok(())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Create a job and wait for it to complete or fail.
+    async fn run_job_to_completion(&self, job: &Job) -> Result<()> {
+        let jobs = self.jobs_api();
+        let job_name = job.metadata.name.clone().unwrap_or_default();
+
+        jobs.create(&PostParams::default(), job).await?;
+        debug!(job_name, "Created state job");
+
+        let timeout = Duration::from_secs(self.config.state_job_timeout_seconds);
+        let finished = await_condition(jobs, &job_name, is_job_finished);
+
+        let job = match tokio::time::timeout(timeout, finished).await {
+            Ok(Ok(job)) => job,
+            Ok(Err(e)) => {
+                return Err(SchedulerError::JobFailed(format!(
+                    "{job_name}: failed to watch job: {e}"
+                )))
+            }
+            Err(_) => {
+                return Err(SchedulerError::JobFailed(format!(
+                    "{job_name}: timed out after {}s",
+                    timeout.as_secs()
+                )))
+            }
+        };
+
+        match job.as_ref().and_then(job_failure_message) {
+            Some(message) => Err(SchedulerError::JobFailed(format!("{job_name}: {message}"))),
+            None => Ok(()),
+        }
+    }
+
+    /// Start an agent on one of a pool's warm pods.
+    ///
+    /// Returns whether the agent was started; if not, it should be scheduled
+    /// on a new pod.
+    async fn claim_warm_pod(
+        &self,
+        pool: &WarmPoolConfig,
+        agent_id: &AgentId,
+        user_id_hex: &str,
+        spec: &AgentSpec,
+    ) -> bool {
+        let pods = self.pods_api();
+        let params = ListParams::default().labels(&warm_selector(pool));
+        let mut candidates: Vec<Pod> = match pods.list(&params).await {
+            Ok(list) => list.items.into_iter().filter(is_claimable).collect(),
+            Err(e) => {
+                warn!(pool = %pool.key(), error = %e, "Failed to list warm pods");
+                return false;
+            }
+        };
+        sort_for_claiming(&mut candidates);
+
+        for pod in candidates {
+            let (Some(pod_name), Some(version), Some(ip)) = (
+                pod.metadata.name,
+                pod.metadata.resource_version,
+                pod.status.and_then(|s| s.pod_ip),
+            ) else {
+                continue;
+            };
+
+            let patch = claim_patch(&version, Utc::now());
+            match pods
+                .patch(&pod_name, &PatchParams::default(), &Patch::Merge(&patch))
+                .await
+            {
+                Ok(_) => {}
+                Err(kube::Error::Api(e)) if e.code == 409 => {
+                    debug!(pod_name, "Warm pod was claimed by someone else");
+                    continue;
+                }
+                Err(e) => {
+                    warn!(pod_name, error = %e, "Failed to claim warm pod");
+                    return false;
+                }
+            }
+
+            return match self
+                .bind_warm_pod(&pod_name, &ip, agent_id, user_id_hex, spec)
+                .await
+            {
+                Ok(()) => {
+                    info!(
+                        agent_id = %agent_id,
+                        pod_name,
+                        pool = %pool.key(),
+                        "Started agent on warm pod"
+                    );
+                    true
+                }
+                Err(e) => {
+                    warn!(
+                        agent_id = %agent_id,
+                        pod_name,
+                        error = %e,
+                        "Failed to bind warm pod, creating a new pod instead"
+                    );
+                    self.delete_warm_pod(&pod_name).await;
+                    false
+                }
+            };
+        }
+
+        debug!(pool = %pool.key(), "No warm pod available");
+        false
+    }
+
+    /// Hand a claimed warm pod and its state claim over to an agent.
+    async fn bind_warm_pod(
+        &self,
+        pod_name: &str,
+        ip: &str,
+        agent_id: &AgentId,
+        user_id_hex: &str,
+        spec: &AgentSpec,
+    ) -> Result<()> {
+        self.pvcs_api()
+            .patch(
+                &warm_claim_name(pod_name),
+                &PatchParams::default(),
+                &Patch::Merge(&bind_claim_patch(agent_id)),
+            )
+            .await?;
+
+        let request = ActivationRequest {
+            agent_id: agent_id.to_hex(),
+            user_id: user_id_hex.to_string(),
+            // Agents with secrets are never started on warm pods
+            env: build_activation_env(agent_id, user_id_hex, spec, &BTreeMap::new(), &self.config),
+        };
+        let response = self
+            .http_client
+            .post(format!("http://{ip}:{AURA_PORT}/activate"))
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| {
+                SchedulerError::PodCreationFailed(format!("failed to activate {pod_name}: {e}"))
+            })?;
+        if !response.status().is_success() {
+            return Err(SchedulerError::PodCreationFailed(format!(
+                "{pod_name} returned {} on activation",
+                response.status()
+            )));
+        }
+
+        self.pods_api()
+            .patch(
+                pod_name,
+                &PatchParams::default(),
+                &Patch::Merge(&bind_pod_patch(agent_id, user_id_hex, Utc::now())),
+            )
+            .await?;
+
+        self.endpoint_cache
+            .insert(*agent_id, format!("{ip}:{AURA_PORT}"));
+        Ok(())
+    }
+
+    /// Delete a warm pod and its state claim, logging failures.
+    async fn delete_warm_pod(&self, pod_name: &str) {
+        if let Err(e) = self
+            .pods_api()
+            .delete(pod_name, &DeleteParams::default())
+            .await
+        {
+            if !matches!(&e, kube::Error::Api(e) if e.code == 404) {
+                warn!(pod_name, error = %e, "Failed to delete warm pod");
+            }
+        }
+
+        let claim_name = warm_claim_name(pod_name);
+        if let Err(e) = self
+            .pvcs_api()
+            .delete(&claim_name, &DeleteParams::default())
+            .await
+        {
+            if !matches!(&e, kube::Error::Api(e) if e.code == 404) {
+                warn!(claim_name, error = %e, "Failed to delete warm pod state volume");
+            }
+        }
+    }
+
+    /// Keep the configured warm pools filled, replacing pods that are too old.
+    ///
+    /// This method runs indefinitely and should be spawned as a background
+    /// task. It returns at once if no pools are configured.
+    pub async fn run_warm_pools(&self) {
+        if self.config.warm_pools.is_empty() {
+            return;
+        }
+        if self.config.state_volume_mode != StateVolumeMode::PerAgent {
+            warn!("Warm pools need per-agent state volumes, not starting them");
+            return;
+        }
+
+        info!(
+            pools = self.config.warm_pools.len(),
+            "Starting warm pool maintenance"
+        );
+
+        let mut ticker = tokio::time::interval(WARM_POOL_SYNC_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            ticker.tick().await;
+            for pool in &self.config.warm_pools {
+                if let Err(e) = self.sync_warm_pool(pool).await {
+                    warn!(pool = %pool.key(), error = %e, "Failed to sync warm pool");
+                }
+            }
+        }
+    }
+
+    /// Clear out abandoned and expired pods of a pool and top it back up.
+    async fn sync_warm_pool(&self, pool: &WarmPoolConfig) -> Result<()> {
+        let pods = self.pods_api();
+        let now = Utc::now();
+
+        let claimed = pods
+            .list(&ListParams::default().labels(&claimed_selector(pool)))
+            .await?;
+        for pod in claimed.items {
+            if let Some(pod_name) = pod.metadata.name.as_deref() {
+                if is_abandoned_claim(&pod, now) {
+                    warn!(pod_name, "Deleting abandoned warm pod claim");
+                    self.delete_warm_pod(pod_name).await;
+                }
+            }
+        }
+
+        let mut warm = 0u32;
+        let unclaimed = pods
+            .list(&ListParams::default().labels(&warm_selector(pool)))
+            .await?;
+        for pod in unclaimed.items {
+            if pod.metadata.deletion_timestamp.is_some() {
+                continue;
+            }
+            if needs_recycling(&pod, now, &self.config) {
+                if let Some(pod_name) = pod.metadata.name.as_deref() {
+                    debug!(pod_name, "Replacing warm pod");
+                    self.delete_warm_pod(pod_name).await;
+                }
+                continue;
+            }
+            warm += 1;
+        }
+
+        for _ in warm..pool.size {
+            self.create_warm_pod(pool).await?;
+        }
+        Ok(())
+    }
+
+    /// Create a warm pod for a pool, along with its state claim.
+    async fn create_warm_pod(&self, pool: &WarmPoolConfig) -> Result<()> {
+        let suffix = format!("{:x}", Utc::now().timestamp_nanos_opt().unwrap_or_default());
+        let pod_name = warm_pod_name(&suffix);
+
+        let claim = build_warm_claim(pool, &pod_name, &self.config);
+        self.pvcs_api()
+            .create(&PostParams::default(), &claim)
+            .await?;
+
+        let pod = build_warm_pod(pool, &pod_name, &self.config);
+        if let Err(e) = self.pods_api().create(&PostParams::default(), &pod).await {
+            // Don't leave the claim behind
+            self.delete_warm_pod(&pod_name).await;
+            return Err(e.into());
+        }
+
+        debug!(pool = %pool.key(), pod_name, "Created warm pod");
+        Ok(())
+    }
+
     /// Run the reconciliation loop, watching for pod changes and notifying the gateway.
     ///
     /// This method runs indefinitely, processing pod events as they occur.
     /// It should be spawned as a background task.
     ///
-    /// Status updates are sent to the gateway's internal endpoint via HTTP.
+    /// Status updates are queued in the [`StatusOutbox`] and sent to the
+    /// gateway's internal endpoint via HTTP, retrying until acknowledged.
     pub async fn run_reconciler(&self) {
-        // Run both pod watcher and event watcher concurrently
-        tokio::join!(self.run_pod_watcher(), self.run_event_watcher());
+        // Run the pod watcher, event watcher and status delivery concurrently
+        tokio::join!(
+            self.run_pod_watcher(),
+            self.run_event_watcher(),
+            self.status_outbox.run()
+        );
     }
 
     /// Watch for pod changes.
@@ -185,7 +795,11 @@ impl K8sScheduler {
                     info!("Watcher initialized, starting reconciliation");
                 }
                 Ok(watcher::Event::InitDone) => {
-                    info!("Initial reconciliation complete");
+                    // Every pod's current state is now queued for the gateway
+                    info!(
+                        pending = self.status_outbox.depth(),
+                        "Initial reconciliation complete"
+                    );
                 }
                 Err(e) => {
                     error!(error = %e, "Watcher error, will retry");
@@ -243,8 +857,8 @@ impl K8sScheduler {
             return;
         };
 
-        // Only process events for our agent pods
-        if !pod_name.starts_with("agent-") {
+        // Only process events for our agent pods, including claimed warm pods
+        if !pod_name.starts_with("agent-") && !pod_name.starts_with(WARM_POD_PREFIX) {
             return;
         }
 
@@ -284,22 +898,12 @@ impl K8sScheduler {
 
             if let Some(agent_id) = agent_id {
                 let error_msg = format!("{reason}: {message}");
-                if let Err(e) = self
-                    .notify_status_change(&agent_id, AgentState::Error, Some(error_msg))
-                    .await
-                {
-                    error!(
-                        agent_id = %agent_id,
-                        error = %e,
-                        "Failed to notify gateway of pod error from event"
-                    );
-                } else {
-                    info!(
-                        agent_id = %agent_id,
-                        reason,
-                        "Notified gateway of pod error from Kubernetes event"
-                    );
-                }
+                self.notify_status_change(agent_id, AgentState::Error, Some(error_msg));
+                info!(
+                    agent_id = %agent_id,
+                    reason,
+                    "Queued pod error from Kubernetes event for the gateway"
+                );
             }
         }
     }
@@ -341,23 +945,15 @@ impl K8sScheduler {
             }
         };
 
-        // Notify the gateway of the status change
-        if let Err(e) = self.notify_status_change(&agent_id, new_state, message.clone()).await {
-            error!(
-                agent_id = %agent_id,
-                error = %e,
-                "Failed to notify gateway of status change"
-            );
-        } else {
-            info!(
-                agent_id = %agent_id,
-                phase,
-                ready,
-                new_state = ?new_state,
-                message = ?message,
-                "Notified gateway of agent status change"
-            );
-        }
+        debug!(
+            agent_id = %agent_id,
+            phase,
+            ready,
+            new_state = ?new_state,
+            message = ?message,
+            "Queued agent status change for the gateway"
+        );
+        self.notify_status_change(agent_id, new_state, message);
     }
 
     /// Extract container error information from pod status.
@@ -444,59 +1040,20 @@ impl K8sScheduler {
 
         // Notify gateway that pod is deleted (transition to Stopped)
         // Note: The gateway will check if agent is hibernating and skip if so
-        if let Err(e) = self
-            .notify_status_change(&agent_id, AgentState::Stopped, Some("Pod deleted".to_string()))
-            .await
-        {
-            error!(
-                agent_id = %agent_id,
-                error = %e,
-                "Failed to notify gateway of pod deletion"
-            );
-        } else {
-            info!(agent_id = %agent_id, "Notified gateway of pod deletion");
-        }
-    }
-
-    /// Notify the gateway of an agent status change via HTTP.
-    async fn notify_status_change(
-        &self,
-        agent_id: &AgentId,
-        status: AgentState,
-        message: Option<String>,
-    ) -> Result<()> {
-        #[derive(Serialize)]
-        struct StatusUpdate {
-            status: AgentState,
-            #[serde(skip_serializing_if = "Option::is_none")]
-            message: Option<String>,
-        }
-
-        let url = format!(
-            "{}/internal/agents/{}/status",
-            self.config.gateway_url,
-            agent_id.to_hex()
+        self.notify_status_change(
+            agent_id,
+            AgentState::Stopped,
+            Some("Pod deleted".to_string()),
         );
+        info!(agent_id = %agent_id, "Queued pod deletion for the gateway");
+    }
 
-        let body = StatusUpdate { status, message };
-
-        let response = self
-            .http_client
-            .patch(&url)
-            .json(&body)
-            .send()
-            .await
-            .map_err(|e| SchedulerError::Config(format!("Failed to call gateway: {e}")))?;
-
-        if response.status().is_success() {
-            Ok(())
-        } else {
-            let status_code = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            Err(SchedulerError::Config(format!(
-                "Gateway returned {status_code}: {error_text}"
-            )))
-        }
+    /// Queue an agent status change for delivery to the gateway.
+    ///
+    /// Replaces any change for the agent that hasn't been delivered yet.
+    fn notify_status_change(&self, agent_id: AgentId, status: AgentState, message: Option<String>) {
+        self.status_outbox
+            .enqueue(agent_id, StatusUpdate { status, message });
     }
 
     fn extract_agent_id(pod: &Pod) -> Option<AgentId> {
@@ -567,6 +1124,35 @@ impl K8sScheduler {
     }
 }
 
+/// Whether a job has reached a terminal `Complete` or `Failed` condition.
+fn is_job_finished(job: Option<&Job>) -> bool {
+    job.and_then(|j| j.status.as_ref())
+        .and_then(|s| s.conditions.as_ref())
+        .is_some_and(|conditions| {
+            conditions
+                .iter()
+                .any(|c| (c.type_ == "Complete" || c.type_ == "Failed") && c.status == "True")
+        })
+}
+
+/// Extract the failure message from a finished job, if it failed.
+fn job_failure_message(job: &Job) -> Option<String> {
+    job.status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref())
+        .and_then(|conditions| {
+            conditions
+                .iter()
+                .find(|c| c.type_ == "Failed" && c.status == "True")
+        })
+        .map(|c| {
+            c.message
+                .clone()
+                .or_else(|| c.reason.clone())
+                .unwrap_or_else(|| "Job failed".to_string())
+        })
+}
+
 #[async_trait]
 impl Scheduler for K8sScheduler {
     async fn schedule_agent(
@@ -578,22 +1164,70 @@ impl Scheduler for K8sScheduler {
         // Validate resources
         self.config
             .validate_resources(spec.cpu_millicores, spec.memory_mb)?;
+        if let Some(placement) = &spec.placement {
+            self.config.validate_placement(placement)?;
+        }
+        if self.config.state_volume_mode == StateVolumeMode::PerAgent {
+            self.config
+                .validate_storage(storage_gb_for_spec(spec, &self.config))?;
+        }
 
         let pods = self.pods_api();
         let pod_name = pod_name_for_agent(agent_id);
 
         // Check if pod already exists
-        if pods.get_opt(&pod_name).await?.is_some() {
+        if let Some(pod) = self.find_agent_pod(agent_id).await? {
             warn!(
                 agent_id = %agent_id,
-                pod_name,
+                pod_name = pod.metadata.name.as_deref().unwrap_or(&pod_name),
                 "Pod already exists, skipping creation"
             );
             return Ok(());
         }
 
+        // A pod terminated just before (e.g. for a restart) may still be
+        // shutting down under the name its replacement needs
+        self.wait_for_pod_deletion(agent_id).await?;
+
+        // The pod's traffic is restricted from the moment it starts
+        self.apply_network_policy(agent_id, spec).await?;
+
+        // Secrets only reach a runtime through its pod spec
+        let secret_keys = self.agent_secret_keys(agent_id).await?;
+
+        // The pod's state volume must exist before the pod
+        let state_claim = match self.config.state_volume_mode {
+            StateVolumeMode::PerAgent => {
+                if let Some(pvc) = self.find_state_claim(agent_id).await? {
+                    pvc.metadata
+                        .name
+                        .unwrap_or_else(|| pvc_name_for_agent(agent_id))
+                } else {
+                    // An agent without state or secrets can take over a warm
+                    // pod and its volume
+                    let pool = pool_for_spec(spec, &self.config).filter(|_| secret_keys.is_empty());
+                    if let Some(pool) = pool {
+                        if self.claim_warm_pod(pool, agent_id, user_id_hex, spec).await {
+                            return Ok(());
+                        }
+                    }
+                    let pvc = build_agent_pvc(agent_id, spec, &self.config);
+                    self.ensure_state_claim(agent_id, &pvc).await?;
+                    pvc_name_for_agent(agent_id)
+                }
+            }
+            StateVolumeMode::Shared => self.config.state_pvc_name.clone(),
+        };
+
         // Build and create the pod
-        let pod = build_pod(agent_id, user_id_hex, spec, &self.config);
+        let pod = build_pod(
+            agent_id,
+            user_id_hex,
+            spec,
+            &secret_keys,
+            &state_claim,
+            &self.config,
+        );
         pods.create(&PostParams::default(), &pod).await?;
 
         info!(
@@ -609,11 +1243,19 @@ impl Scheduler for K8sScheduler {
 
     async fn terminate_agent(&self, agent_id: &AgentId) -> Result<()> {
         let pods = self.pods_api();
-        let pod_name = pod_name_for_agent(agent_id);
 
         // Remove from endpoint cache
         self.endpoint_cache.remove(agent_id);
 
+        let Some(pod_name) = self
+            .find_agent_pod(agent_id)
+            .await?
+            .and_then(|pod| pod.metadata.name)
+        else {
+            warn!(agent_id = %agent_id, "Pod not found, already terminated");
+            return Ok(());
+        };
+
         match pods.delete(&pod_name, &DeleteParams::default()).await {
             Ok(_) => {
                 info!(agent_id = %agent_id, pod_name, "Terminated agent pod");
@@ -628,12 +1270,9 @@ impl Scheduler for K8sScheduler {
     }
 
     async fn get_pod_status(&self, agent_id: &AgentId) -> Result<PodStatus> {
-        let pods = self.pods_api();
-        let pod_name = pod_name_for_agent(agent_id);
-
-        match pods.get_opt(&pod_name).await? {
+        match self.find_agent_pod(agent_id).await? {
             Some(pod) => Ok(Self::extract_pod_status(&pod)),
-            None => Err(SchedulerError::PodNotFound(pod_name)),
+            None => Err(SchedulerError::PodNotFound(pod_name_for_agent(agent_id))),
         }
     }
 
@@ -644,10 +1283,7 @@ impl Scheduler for K8sScheduler {
         }
 
         // Fetch from K8s
-        let pods = self.pods_api();
-        let pod_name = pod_name_for_agent(agent_id);
-
-        if let Some(pod) = pods.get_opt(&pod_name).await? {
+        if let Some(pod) = self.find_agent_pod(agent_id).await? {
             if let Some(ip) = pod.status.as_ref().and_then(|s| s.pod_ip.as_ref()) {
                 let endpoint = format!("{ip}:8080");
 
@@ -722,6 +1358,205 @@ impl Scheduler for K8sScheduler {
             }
         }
     }
+
+    async fn get_pod_metrics(&self, agent_id: &AgentId) -> Result<Option<PodMetrics>> {
+        let Some(pod_name) = self
+            .find_agent_pod(agent_id)
+            .await?
+            .and_then(|pod| pod.metadata.name)
+        else {
+            return Ok(None);
+        };
+
+        let metrics = self
+            .pod_metrics_api()
+            .get_opt(&pod_name)
+            .await
+            .map_err(|e| SchedulerError::Metrics(e.to_string()))?
+            .map(|object| pod_metrics_from_object(&object));
+
+        debug!(agent_id = %agent_id, pod_name, metrics = ?metrics, "Fetched pod metrics");
+        Ok(metrics)
+    }
+
+    async fn pod_logs(&self, agent_id: &AgentId, options: &PodLogOptions) -> Result<LogStream> {
+        let pods = self.pods_api();
+        let Some(pod_name) = self
+            .find_agent_pod(agent_id)
+            .await?
+            .and_then(|pod| pod.metadata.name)
+        else {
+            return Err(SchedulerError::PodNotFound(pod_name_for_agent(agent_id)));
+        };
+
+        let params = LogParams {
+            container: Some(AURA_CONTAINER.to_string()),
+            follow: options.follow,
+            since_time: options.since,
+            tail_lines: options.tail.map(i64::from),
+            timestamps: true,
+            ..LogParams::default()
+        };
+
+        // The reader borrows the API client, so it is drained by a task that
+        // owns both and stops once the returned stream is dropped
+        let (tx, rx) = tokio::sync::mpsc::channel(LOG_BUFFER_LINES);
+        tokio::spawn(async move {
+            let lines = match pods.log_stream(&pod_name, &params).await {
+                Ok(reader) => reader.lines(),
+                Err(e) => {
+                    let _ = tx.send(Err(SchedulerError::from(e))).await;
+                    return;
+                }
+            };
+            futures::pin_mut!(lines);
+            while let Some(line) = lines.next().await {
+                let line = line.map_err(|e| {
+                    SchedulerError::Config(format!("Failed to read logs of {pod_name}: {e}"))
+                });
+                if tx.send(line).await.is_err() {
+                    break;
+                }
+            }
+            debug!(pod_name, "Agent log stream ended");
+        });
+
+        Ok(futures::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|line| (line, rx))
+        })
+        .boxed())
+    }
+
+    async fn copy_agent_state(&self, source: &AgentId, target: &AgentId) -> Result<()> {
+        let source_claim = match self.config.state_volume_mode {
+            StateVolumeMode::PerAgent => {
+                // Without a source volume there is no state to copy; the target's
+                // volume is created when it is first scheduled
+                let Some(source_pvc) = self.find_state_claim(source).await? else {
+                    info!(source = %source, target = %target, "Source has no state volume");
+                    return Ok(());
+                };
+                let target_pvc = build_cloned_pvc(target, &source_pvc, &self.config);
+                self.ensure_state_claim(target, &target_pvc).await?;
+                source_pvc
+                    .metadata
+                    .name
+                    .unwrap_or_else(|| pvc_name_for_agent(source))
+            }
+            StateVolumeMode::Shared => self.config.state_pvc_name.clone(),
+        };
+
+        let job = build_state_copy_job(source, &source_claim, target, &self.config);
+
+        info!(
+            source = %source,
+            target = %target,
+            "Copying agent state"
+        );
+
+        self.run_job_to_completion(&job).await?;
+
+        info!(source = %source, target = %target, "Copied agent state");
+        Ok(())
+    }
+
+    async fn delete_agent_state(&self, agent_id: &AgentId) -> Result<()> {
+        info!(agent_id = %agent_id, "Deleting agent state");
+
+        match self.config.state_volume_mode {
+            StateVolumeMode::PerAgent => {
+                let pvc_name = self
+                    .find_state_claim(agent_id)
+                    .await?
+                    .and_then(|pvc| pvc.metadata.name);
+                if let Some(pvc_name) = pvc_name {
+                    match self
+                        .pvcs_api()
+                        .delete(&pvc_name, &DeleteParams::default())
+                        .await
+                    {
+                        Ok(_) => {
+                            info!(agent_id = %agent_id, pvc_name, "Deleted agent state volume");
+                        }
+                        Err(kube::Error::Api(e)) if e.code == 404 => {}
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+            }
+            StateVolumeMode::Shared => {
+                let job = build_state_cleanup_job(agent_id, &self.config);
+                self.run_job_to_completion(&job).await?;
+            }
+        }
+        self.endpoint_cache.remove(agent_id);
+
+        info!(agent_id = %agent_id, "Deleted agent state");
+        Ok(())
+    }
+
+    async fn put_agent_secrets(
+        &self,
+        agent_id: &AgentId,
+        secrets: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        if secrets.is_empty() {
+            return self.delete_agent_secrets(agent_id).await;
+        }
+
+        let secret = build_agent_secret(agent_id, secrets, &self.config);
+        let secret_name = secret_name_for_agent(agent_id);
+
+        // Server-side apply drops keys that are no longer present
+        self.secrets_api()
+            .patch(
+                &secret_name,
+                &PatchParams::apply(FIELD_MANAGER).force(),
+                &Patch::Apply(&secret),
+            )
+            .await?;
+
+        info!(
+            agent_id = %agent_id,
+            secret_name,
+            keys = secrets.len(),
+            "Applied agent secrets"
+        );
+        Ok(())
+    }
+
+    async fn delete_agent_secrets(&self, agent_id: &AgentId) -> Result<()> {
+        let secret_name = secret_name_for_agent(agent_id);
+
+        match self
+            .secrets_api()
+            .delete(&secret_name, &DeleteParams::default())
+            .await
+        {
+            Ok(_) => {
+                info!(agent_id = %agent_id, secret_name, "Deleted agent secrets");
+                Ok(())
+            }
+            Err(kube::Error::Api(e)) if e.code == 404 => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete_network_policy(&self, agent_id: &AgentId) -> Result<()> {
+        let policy_name = network_policy_name_for_agent(agent_id);
+
+        match self
+            .network_policies_api()
+            .delete(&policy_name, &DeleteParams::default())
+            .await
+        {
+            Ok(_) => {
+                info!(agent_id = %agent_id, policy_name, "Deleted agent network policy");
+                Ok(())
+            }
+            Err(kube::Error::Api(e)) if e.code == 404 => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
 }
 
 /// A mock scheduler for testing without a real Kubernetes cluster.
@@ -736,6 +1571,10 @@ pub mod mock {
     #[derive(Default)]
     pub struct MockScheduler {
         pods: Mutex<HashMap<AgentId, MockPod>>,
+        state_copies: Mutex<Vec<(AgentId, AgentId)>>,
+        state_deletions: Mutex<Vec<AgentId>>,
+        secrets: Mutex<HashMap<AgentId, BTreeMap<String, String>>>,
+        network_policy_deletions: Mutex<Vec<AgentId>>,
     }
 
     struct MockPod {
@@ -743,6 +1582,8 @@ pub mod mock {
         spec: AgentSpec,
         status: PodStatus,
         endpoint: Option<String>,
+        metrics: Option<PodMetrics>,
+        logs: Vec<String>,
     }
 
     impl MockScheduler {
@@ -766,6 +1607,20 @@ pub mod mock {
             }
         }
 
+        /// Set the resource usage reported for a pod.
+        pub fn set_metrics(&self, agent_id: &AgentId, metrics: Option<PodMetrics>) {
+            if let Some(pod) = self.pods.lock().get_mut(agent_id) {
+                pod.metrics = metrics;
+            }
+        }
+
+        /// Append a log line to a pod's logs.
+        pub fn push_log(&self, agent_id: &AgentId, line: impl Into<String>) {
+            if let Some(pod) = self.pods.lock().get_mut(agent_id) {
+                pod.logs.push(line.into());
+            }
+        }
+
         /// Get the number of scheduled pods.
         #[must_use]
         pub fn pod_count(&self) -> usize {
@@ -786,6 +1641,30 @@ pub mod mock {
                 .get(agent_id)
                 .map(|p| p.user_id_hex.clone())
         }
+
+        /// Get the `(source, target)` pairs of all state copies performed.
+        #[must_use]
+        pub fn state_copies(&self) -> Vec<(AgentId, AgentId)> {
+            self.state_copies.lock().clone()
+        }
+
+        /// Get the agents whose state was deleted, in order.
+        #[must_use]
+        pub fn state_deletions(&self) -> Vec<AgentId> {
+            self.state_deletions.lock().clone()
+        }
+
+        /// Get the agents whose network policy was deleted, in order.
+        #[must_use]
+        pub fn network_policy_deletions(&self) -> Vec<AgentId> {
+            self.network_policy_deletions.lock().clone()
+        }
+
+        /// Get the secrets currently stored for an agent.
+        #[must_use]
+        pub fn get_secrets(&self, agent_id: &AgentId) -> Option<BTreeMap<String, String>> {
+            self.secrets.lock().get(agent_id).cloned()
+        }
     }
 
     #[async_trait]
@@ -815,6 +1694,8 @@ pub mod mock {
                         message: None,
                     },
                     endpoint: None,
+                    metrics: None,
+                    logs: Vec::new(),
                 },
             );
 
@@ -867,6 +1748,62 @@ pub mod mock {
                 .map(|p| p.status.ready)
                 .unwrap_or(false))
         }
+
+        async fn get_pod_metrics(&self, agent_id: &AgentId) -> Result<Option<PodMetrics>> {
+            Ok(self
+                .pods
+                .lock()
+                .get(agent_id)
+                .and_then(|p| p.metrics.clone()))
+        }
+
+        async fn pod_logs(&self, agent_id: &AgentId, options: &PodLogOptions) -> Result<LogStream> {
+            let pods = self.pods.lock();
+            let pod = pods
+                .get(agent_id)
+                .ok_or_else(|| SchedulerError::PodNotFound(agent_id.to_hex()))?;
+
+            let skip = options.tail.map_or(0, |tail| {
+                pod.logs
+                    .len()
+                    .saturating_sub(usize::try_from(tail).unwrap_or(usize::MAX))
+            });
+            let lines: Vec<_> = pod.logs[skip..].iter().cloned().map(Ok).collect();
+            Ok(futures::stream::iter(lines).boxed())
+        }
+
+        async fn copy_agent_state(&self, source: &AgentId, target: &AgentId) -> Result<()> {
+            self.state_copies.lock().push((*source, *target));
+            Ok(())
+        }
+
+        async fn delete_agent_state(&self, agent_id: &AgentId) -> Result<()> {
+            self.state_deletions.lock().push(*agent_id);
+            Ok(())
+        }
+
+        async fn put_agent_secrets(
+            &self,
+            agent_id: &AgentId,
+            secrets: &BTreeMap<String, String>,
+        ) -> Result<()> {
+            if secrets.is_empty() {
+                self.secrets.lock().remove(agent_id);
+            } else {
+                self.secrets.lock().insert(*agent_id, secrets.clone());
+            }
+            Ok(())
+        }
+
+        async fn delete_agent_secrets(&self, agent_id: &AgentId) -> Result<()> {
+            self.secrets.lock().remove(agent_id);
+            Ok(())
+        }
+
+        async fn delete_network_policy(&self, agent_id: &AgentId) -> Result<()> {
+            self.network_policy_deletions.lock().push(*agent_id);
+            Ok(())
+        }
     }
 }
 
@@ -981,4 +1918,601 @@ mod tests {
         let pods = scheduler.list_pods().await.unwrap();
         assert_eq!(pods.len(), 2);
     }
+
+    #[tokio::test]
+    async fn mock_scheduler_records_state_jobs() {
+        let scheduler = MockScheduler::new();
+        let user_id = UserId::from_bytes([1u8; 32]);
+
+        let source = AgentId::generate(&user_id, "source");
+        let target = AgentId::generate(&user_id, "target");
+
+        scheduler.copy_agent_state(&source, &target).await.unwrap();
+
+        assert_eq!(scheduler.state_copies(), vec![(source, target)]);
+
+        scheduler.delete_agent_state(&source).await.unwrap();
+        assert_eq!(scheduler.state_deletions(), vec![source]);
+
+        scheduler.delete_network_policy(&source).await.unwrap();
+        assert_eq!(scheduler.network_policy_deletions(), vec![source]);
+    }
+
+    #[tokio::test]
+    async fn mock_scheduler_replaces_and_deletes_secrets() {
+        let scheduler = MockScheduler::new();
+        let agent_id = test_agent_id();
+
+        let mut secrets = BTreeMap::new();
+        secrets.insert("GITHUB_TOKEN".to_string(), "ghp_1".to_string());
+        scheduler
+            .put_agent_secrets(&agent_id, &secrets)
+            .await
+            .unwrap();
+        assert_eq!(scheduler.get_secrets(&agent_id), Some(secrets));
+
+        scheduler
+            .put_agent_secrets(&agent_id, &BTreeMap::new())
+            .await
+            .unwrap();
+        assert_eq!(scheduler.get_secrets(&agent_id), None);
+    }
+
+    #[tokio::test]
+    async fn mock_scheduler_tails_logs() {
+        let scheduler = MockScheduler::new();
+        let agent_id = test_agent_id();
+        let user_id = UserId::from_bytes([1u8; 32]);
+
+        let result = scheduler
+            .pod_logs(&agent_id, &PodLogOptions::default())
+            .await;
+        assert!(matches!(result, Err(SchedulerError::PodNotFound(_))));
+
+        scheduler
+            .schedule_agent(&agent_id, &user_id.to_hex(), &test_spec())
+            .await
+            .unwrap();
+        for i in 0..3 {
+            scheduler.push_log(&agent_id, format!("2026-01-01T00:00:0{i}Z line {i}"));
+        }
+
+        let options = PodLogOptions {
+            tail: Some(2),
+            ..PodLogOptions::default()
+        };
+        let lines: Vec<_> = scheduler
+            .pod_logs(&agent_id, &options)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(
+            lines,
+            vec!["2026-01-01T00:00:01Z line 1", "2026-01-01T00:00:02Z line 2"]
+        );
+    }
+
+    mod metrics_api {
+        use super::*;
+        use crate::pod::pod_name_for_agent;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        fn scheduler_for(server: &MockServer) -> K8sScheduler {
+            let config = kube::Config::new(server.uri().parse().unwrap());
+            let client = Client::try_from(config).unwrap();
+            K8sScheduler::with_client(client, SchedulerConfig::with_namespace("agents"))
+        }
+
+        fn metrics_path(agent_id: &AgentId) -> String {
+            format!(
+                "/apis/metrics.k8s.io/v1beta1/namespaces/agents/pods/{}",
+                pod_name_for_agent(agent_id)
+            )
+        }
+
+        /// Serve the agent's pod, which the metrics are looked up for.
+        async fn mount_pod(server: &MockServer, agent_id: &AgentId) {
+            let pod_name = pod_name_for_agent(agent_id);
+            Mock::given(method("GET"))
+                .and(path(format!("/api/v1/namespaces/agents/pods/{pod_name}")))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "apiVersion": "v1",
+                    "kind": "Pod",
+                    "metadata": { "name": pod_name, "namespace": "agents" },
+                })))
+                .mount(server)
+                .await;
+        }
+
+        #[tokio::test]
+        async fn reads_pod_metrics() {
+            let server = MockServer::start().await;
+            let agent_id = test_agent_id();
+            mount_pod(&server, &agent_id).await;
+            Mock::given(method("GET"))
+                .and(path(metrics_path(&agent_id)))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "apiVersion": "metrics.k8s.io/v1beta1",
+                    "kind": "PodMetrics",
+                    "metadata": {
+                        "name": pod_name_for_agent(&agent_id),
+                        "namespace": "agents",
+                    },
+                    "timestamp": "2026-01-01T00:00:00Z",
+                    "window": "30s",
+                    "containers": [
+                        { "name": "aura", "usage": { "cpu": "250m", "memory": "131072Ki" } },
+                    ],
+                })))
+                .mount(&server)
+                .await;
+
+            let metrics = scheduler_for(&server)
+                .get_pod_metrics(&agent_id)
+                .await
+                .unwrap()
+                .unwrap();
+
+            assert_eq!(metrics.cpu_millicores, 250);
+            assert_eq!(metrics.memory_bytes, 128 * 1024 * 1024);
+            assert!(metrics.timestamp.is_some());
+        }
+
+        #[tokio::test]
+        async fn missing_pod_metrics_are_none() {
+            let server = MockServer::start().await;
+            let agent_id = test_agent_id();
+            mount_pod(&server, &agent_id).await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                    "kind": "Status",
+                    "apiVersion": "v1",
+                    "status": "Failure",
+                    "message": "pods.metrics.k8s.io not found",
+                    "reason": "NotFound",
+                    "code": 404,
+                })))
+                .mount(&server)
+                .await;
+
+            let metrics = scheduler_for(&server)
+                .get_pod_metrics(&agent_id)
+                .await
+                .unwrap();
+
+            assert!(metrics.is_none());
+        }
+
+        #[tokio::test]
+        async fn agents_without_pods_have_no_metrics() {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path("/api/v1/namespaces/agents/pods"))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "apiVersion": "v1",
+                    "kind": "PodList",
+                    "metadata": {},
+                    "items": [],
+                })))
+                .mount(&server)
+                .await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+                    "kind": "Status",
+                    "apiVersion": "v1",
+                    "status": "Failure",
+                    "message": "pods not found",
+                    "reason": "NotFound",
+                    "code": 404,
+                })))
+                .mount(&server)
+                .await;
+
+            let metrics = scheduler_for(&server)
+                .get_pod_metrics(&test_agent_id())
+                .await
+                .unwrap();
+
+            assert!(metrics.is_none());
+        }
+
+        #[tokio::test]
+        async fn metrics_api_errors_are_returned() {
+            let server = MockServer::start().await;
+            let agent_id = test_agent_id();
+            mount_pod(&server, &agent_id).await;
+            Mock::given(method("GET"))
+                .respond_with(ResponseTemplate::new(503).set_body_json(serde_json::json!({
+                    "kind": "Status",
+                    "apiVersion": "v1",
+                    "status": "Failure",
+                    "message": "the server is currently unable to handle the request",
+                    "reason": "ServiceUnavailable",
+                    "code": 503,
+                })))
+                .mount(&server)
+                .await;
+
+            let result = scheduler_for(&server).get_pod_metrics(&agent_id).await;
+
+            assert!(matches!(result, Err(SchedulerError::Metrics(_))));
+        }
+    }
+
+    mod reconciler {
+        use super::*;
+        use crate::fake_api::FakeKubeApi;
+        use crate::pod::{build_pod, pod_name_for_agent};
+        use crate::pool::{POOL_STATE_LABEL, WARM};
+        use serde_json::{json, Value};
+        use wiremock::matchers::{method, path_regex};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        /// A reconciling scheduler watching `api` and reporting to `gateway`.
+        async fn start_reconciler(api: &FakeKubeApi, gateway: &MockServer) -> Arc<K8sScheduler> {
+            Mock::given(method("PATCH"))
+                .and(path_regex("^/internal/agents/[0-9a-f]+/status$"))
+                .respond_with(ResponseTemplate::new(200))
+                .mount(gateway)
+                .await;
+
+            let mut config = SchedulerConfig::with_namespace("agents");
+            config.gateway_url = gateway.uri();
+            let scheduler = Arc::new(K8sScheduler::with_client(api.client(), config));
+
+            let reconciler = Arc::clone(&scheduler);
+            tokio::spawn(async move { reconciler.run_reconciler().await });
+            scheduler
+        }
+
+        /// The agent's pod, as the scheduler creates it, with the given status.
+        fn agent_pod(agent_id: &AgentId, status: Value) -> Pod {
+            let user_id = UserId::from_bytes([1u8; 32]);
+            let mut pod = build_pod(
+                agent_id,
+                &user_id.to_hex(),
+                &test_spec(),
+                &[],
+                "agent-state",
+                &SchedulerConfig::with_namespace("agents"),
+            );
+            pod.status = serde_json::from_value(status).unwrap();
+            pod
+        }
+
+        fn waiting(reason: &str, message: &str) -> Value {
+            json!({
+                "phase": "Pending",
+                "containerStatuses": [{
+                    "name": "aura",
+                    "image": "aura-runtime:latest",
+                    "imageID": "",
+                    "ready": false,
+                    "restartCount": 3,
+                    "state": { "waiting": { "reason": reason, "message": message } },
+                }],
+            })
+        }
+
+        /// Wait for the gateway to be told the agent has `status`, returning
+        /// the callback body.
+        async fn wait_for_callback(
+            gateway: &MockServer,
+            agent_id: &AgentId,
+            status: &str,
+        ) -> Value {
+            let callback_path = format!("/internal/agents/{}/status", agent_id.to_hex());
+            for _ in 0..100 {
+                let requests = gateway.received_requests().await.unwrap_or_default();
+                let body = requests
+                    .iter()
+                    .filter(|request| request.url.path() == callback_path)
+                    .filter_map(|request| serde_json::from_slice::<Value>(&request.body).ok())
+                    .find(|body| body["status"] == status);
+                if let Some(body) = body {
+                    return body;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+            panic!("gateway was never told agent {agent_id} is {status}");
+        }
+
+        #[tokio::test]
+        async fn readiness_transitions_are_reported() {
+            let api = FakeKubeApi::start().await;
+            let gateway = MockServer::start().await;
+            let scheduler = start_reconciler(&api, &gateway).await;
+            let agent_id = test_agent_id();
+
+            api.apply_pod(&agent_pod(&agent_id, json!({ "phase": "Pending" })));
+            wait_for_callback(&gateway, &agent_id, "provisioning").await;
+
+            api.apply_pod(&agent_pod(
+                &agent_id,
+                json!({
+                    "phase": "Running",
+                    "podIP": "10.0.0.7",
+                    "conditions": [{ "type": "Ready", "status": "True" }],
+                }),
+            ));
+            let body = wait_for_callback(&gateway, &agent_id, "running").await;
+
+            assert!(body["message"].is_null());
+            assert_eq!(
+                scheduler.get_pod_endpoint(&agent_id).await.unwrap(),
+                Some("10.0.0.7:8080".to_string())
+            );
+        }
+
+        #[tokio::test]
+        async fn image_pull_back_off_is_an_error() {
+            let api = FakeKubeApi::start().await;
+            let gateway = MockServer::start().await;
+            let _scheduler = start_reconciler(&api, &gateway).await;
+            let agent_id = test_agent_id();
+
+            api.apply_pod(&agent_pod(
+                &agent_id,
+                waiting(
+                    "ImagePullBackOff",
+                    "Back-off pulling image \"aura-runtime:latest\"",
+                ),
+            ));
+            let body = wait_for_callback(&gateway, &agent_id, "error").await;
+
+            assert_eq!(
+                body["message"],
+                "Back-off pulling image \"aura-runtime:latest\""
+            );
+        }
+
+        #[tokio::test]
+        async fn crash_loop_back_off_is_an_error() {
+            let api = FakeKubeApi::start().await;
+            let gateway = MockServer::start().await;
+            let _scheduler = start_reconciler(&api, &gateway).await;
+            let agent_id = test_agent_id();
+
+            api.apply_pod(&agent_pod(&agent_id, json!({ "phase": "Pending" })));
+            wait_for_callback(&gateway, &agent_id, "provisioning").await;
+
+            let mut status = waiting(
+                "CrashLoopBackOff",
+                "back-off 40s restarting failed container",
+            );
+            status["phase"] = json!("Running");
+            api.apply_pod(&agent_pod(&agent_id, status));
+            let body = wait_for_callback(&gateway, &agent_id, "error").await;
+
+            assert_eq!(body["message"], "back-off 40s restarting failed container");
+        }
+
+        #[tokio::test]
+        async fn pod_deletion_stops_the_agent() {
+            let api = FakeKubeApi::start().await;
+            let gateway = MockServer::start().await;
+            let scheduler = start_reconciler(&api, &gateway).await;
+            let agent_id = test_agent_id();
+
+            api.apply_pod(&agent_pod(
+                &agent_id,
+                json!({
+                    "phase": "Running",
+                    "podIP": "10.0.0.7",
+                    "conditions": [{ "type": "Ready", "status": "True" }],
+                }),
+            ));
+            wait_for_callback(&gateway, &agent_id, "running").await;
+
+            assert!(api.delete_pod(&pod_name_for_agent(&agent_id)).is_some());
+            let body = wait_for_callback(&gateway, &agent_id, "stopped").await;
+
+            assert_eq!(body["message"], "Pod deleted");
+            assert_eq!(scheduler.get_pod_endpoint(&agent_id).await.unwrap(), None);
+        }
+
+        #[tokio::test]
+        async fn warning_events_are_errors() {
+            let api = FakeKubeApi::start().await;
+            let gateway = MockServer::start().await;
+            let _scheduler = start_reconciler(&api, &gateway).await;
+            let agent_id = test_agent_id();
+            let pod_name = pod_name_for_agent(&agent_id);
+
+            api.apply_pod(&agent_pod(&agent_id, json!({ "phase": "Pending" })));
+            wait_for_callback(&gateway, &agent_id, "provisioning").await;
+
+            api.add_event(
+                &serde_json::from_value(json!({
+                    "apiVersion": "v1",
+                    "kind": "Event",
+                    "metadata": { "name": format!("{pod_name}.1"), "namespace": "agents" },
+                    "involvedObject": { "kind": "Pod", "name": pod_name, "namespace": "agents" },
+                    "type": "Warning",
+                    "reason": "FailedScheduling",
+                    "message": "0/3 nodes are available: 3 Insufficient memory.",
+                }))
+                .unwrap(),
+            );
+            let body = wait_for_callback(&gateway, &agent_id, "error").await;
+
+            assert_eq!(
+                body["message"],
+                "FailedScheduling: 0/3 nodes are available: 3 Insufficient memory."
+            );
+        }
+
+        #[tokio::test]
+        async fn endpoints_are_read_from_pods() {
+            let api = FakeKubeApi::start().await;
+            let scheduler =
+                K8sScheduler::with_client(api.client(), SchedulerConfig::with_namespace("agents"));
+            let agent_id = test_agent_id();
+
+            assert_eq!(scheduler.get_pod_endpoint(&agent_id).await.unwrap(), None);
+
+            api.apply_pod(&agent_pod(&agent_id, json!({ "phase": "Pending" })));
+            assert_eq!(scheduler.get_pod_endpoint(&agent_id).await.unwrap(), None);
+
+            api.apply_pod(&agent_pod(
+                &agent_id,
+                json!({ "phase": "Running", "podIP": "10.0.0.9" }),
+            ));
+            assert_eq!(
+                scheduler.get_pod_endpoint(&agent_id).await.unwrap(),
+                Some("10.0.0.9:8080".to_string())
+            );
+        }
+
+        #[tokio::test]
+        async fn restarts_wait_for_the_terminating_pod() {
+            let api = FakeKubeApi::start().await;
+            api.hold_deletions();
+            let config = SchedulerConfig {
+                state_volume_mode: StateVolumeMode::Shared,
+                ..SchedulerConfig::with_namespace("agents")
+            };
+            let scheduler = Arc::new(K8sScheduler::with_client(api.client(), config));
+            let agent_id = test_agent_id();
+            let user_id = UserId::from_bytes([1u8; 32]).to_hex();
+            let pod_name = pod_name_for_agent(&agent_id);
+
+            scheduler
+                .schedule_agent(&agent_id, &user_id, &test_spec())
+                .await
+                .unwrap();
+            assert!(api.pod(&pod_name).is_some());
+
+            // The old pod lingers while the runtime shuts down, and is no
+            // longer the agent's pod
+            scheduler.terminate_agent(&agent_id).await.unwrap();
+            let old = api.pod(&pod_name).unwrap();
+            assert!(old.metadata.deletion_timestamp.is_some());
+            assert!(matches!(
+                scheduler.get_pod_status(&agent_id).await,
+                Err(SchedulerError::PodNotFound(_))
+            ));
+
+            // Restarting on a new runtime version waits for it to go away
+            let rolled_out = AgentSpec {
+                runtime_version: "v2".to_string(),
+                ..test_spec()
+            };
+            let restart = {
+                let scheduler = Arc::clone(&scheduler);
+                tokio::spawn(async move {
+                    scheduler
+                        .schedule_agent(&agent_id, &user_id, &rolled_out)
+                        .await
+                })
+            };
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            assert!(!restart.is_finished());
+
+            assert!(api.delete_pod(&pod_name).is_some());
+            restart.await.unwrap().unwrap();
+
+            let pod = api.pod(&pod_name).unwrap();
+            assert!(pod.metadata.deletion_timestamp.is_none());
+            assert_eq!(
+                pod.spec.unwrap().containers[0].image.as_deref(),
+                Some("ghcr.io/cypher-asi/aura-runtime:v2")
+            );
+        }
+
+        #[tokio::test]
+        async fn agents_with_secrets_skip_warm_pools() {
+            let api = FakeKubeApi::start().await;
+            let mut config = SchedulerConfig::with_namespace("agents");
+            let pool = WarmPoolConfig {
+                isolation: config.default_isolation,
+                cpu_millicores: test_spec().cpu_millicores,
+                memory_mb: test_spec().memory_mb,
+                size: 1,
+            };
+            config.warm_pools = vec![pool.clone()];
+            let warm_name = warm_pod_name("ready");
+            let mut warm = build_warm_pod(&pool, &warm_name, &config);
+            warm.status = serde_json::from_value(json!({
+                "phase": "Running",
+                "podIP": "127.0.0.1",
+                "conditions": [{ "type": "Ready", "status": "True" }],
+            }))
+            .unwrap();
+            api.apply_pod(&warm);
+
+            let scheduler = K8sScheduler::with_client(api.client(), config);
+            let agent_id = test_agent_id();
+            api.apply_secret(&Secret {
+                metadata: kube::api::ObjectMeta {
+                    name: Some(secret_name_for_agent(&agent_id)),
+                    ..Default::default()
+                },
+                data: Some(BTreeMap::from([(
+                    "GITHUB_TOKEN".to_string(),
+                    k8s_openapi::ByteString(b"ghp_123".to_vec()),
+                )])),
+                ..Default::default()
+            });
+
+            scheduler
+                .schedule_agent(
+                    &agent_id,
+                    &UserId::from_bytes([1u8; 32]).to_hex(),
+                    &test_spec(),
+                )
+                .await
+                .unwrap();
+
+            // The agent gets its own pod and volume, reading the secret from
+            // its Secret, and the warm pod is left for agents without secrets
+            let pod = api.pod(&pod_name_for_agent(&agent_id)).unwrap();
+            let env = pod.spec.unwrap().containers[0].env.clone().unwrap();
+            let token = env.iter().find(|var| var.name == "GITHUB_TOKEN").unwrap();
+            assert!(token.value.is_none());
+            assert_eq!(
+                token
+                    .value_from
+                    .as_ref()
+                    .and_then(|source| source.secret_key_ref.as_ref())
+                    .map(|selector| selector.name.clone()),
+                Some(secret_name_for_agent(&agent_id))
+            );
+            assert!(api.claim(&pvc_name_for_agent(&agent_id)).is_some());
+            assert!(is_claimable(&api.pod(&warm_name).unwrap()));
+            assert_eq!(
+                api.pod(&warm_name).unwrap().metadata.labels.unwrap()[POOL_STATE_LABEL],
+                WARM
+            );
+        }
+
+        #[test]
+        fn failed_containers_are_errors() {
+            let agent_id = test_agent_id();
+            let pod = agent_pod(
+                &agent_id,
+                json!({
+                    "phase": "Running",
+                    "containerStatuses": [{
+                        "name": "aura",
+                        "image": "aura-runtime:latest",
+                        "imageID": "",
+                        "ready": false,
+                        "restartCount": 0,
+                        "state": { "terminated": { "exitCode": 137, "reason": "OOMKilled" } },
+                    }],
+                }),
+            );
+            assert_eq!(
+                K8sScheduler::extract_container_error(&pod),
+                (true, Some("OOMKilled".to_string()))
+            );
+
+            let pod = agent_pod(&agent_id, waiting("ContainerCreating", ""));
+            assert_eq!(K8sScheduler::extract_container_error(&pod), (false, None));
+        }
+    }
 }
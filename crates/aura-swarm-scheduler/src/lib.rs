@@ -6,7 +6,8 @@
 //! - Pod creation with Kata Containers runtime for microVM isolation
 //! - Pod lifecycle management (start, stop, health checks)
 //! - Endpoint caching for fast routing
-//! - Status reconciliation with the control plane
+//! - Warm pools of pre-provisioned pods for fast starts
+//! - Status reconciliation with the control plane, with retried callbacks
 //!
 //! # Architecture
 //!
@@ -73,6 +74,12 @@
 //! # }
 //! ```
 //!
+//! # Local Development
+//!
+//! [`LocalProcessScheduler`] implements [`Scheduler`] without a cluster, by
+//! running each agent's runtime as a local child process. The scheduler
+//! binary uses it when `SCHEDULER_BACKEND=local_process`.
+//!
 //! # Testing
 //!
 //! For testing without a real Kubernetes cluster, enable the `test-utils` feature
@@ -95,6 +102,10 @@
 //! # Ok(())
 //! # }
 //! ```
+//!
+//! To exercise `K8sScheduler` itself, including its reconciler, point it at
+//! a `FakeKubeApi`, an in-process fake of the pod and event API (see the
+//! `fake_api` module).
 
 #![forbid(unsafe_code)]
 #![warn(missing_docs)]
@@ -103,13 +114,30 @@
 
 pub mod cache;
 pub mod error;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod fake_api;
+pub mod job;
 pub mod k8s;
+pub mod local;
+pub mod metrics;
+pub mod network;
+pub mod outbox;
 pub mod pod;
+pub mod pool;
+pub mod secret;
 pub mod types;
+pub mod volume;
 
 pub use error::{Result, SchedulerError};
-pub use k8s::{K8sScheduler, Scheduler};
-pub use types::{PodInfo, PodPhase, PodStatus, SchedulerConfig};
+pub use k8s::{K8sScheduler, LogStream, Scheduler};
+pub use local::LocalProcessScheduler;
+pub use outbox::{OutboxStats, StatusOutbox, StatusUpdate};
+pub use types::{
+    PodInfo, PodLogOptions, PodMetrics, PodPhase, PodStatus, SchedulerBackend, SchedulerConfig,
+    StateVolumeMode, WarmPoolConfig,
+};
 
+#[cfg(any(test, feature = "test-utils"))]
+pub use fake_api::FakeKubeApi;
 #[cfg(any(test, feature = "test-utils"))]
 pub use k8s::mock::MockScheduler;
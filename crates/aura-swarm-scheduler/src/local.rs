@@ -0,0 +1,815 @@
+//! Local-process scheduler for development without Kubernetes.
+//!
+//! [`LocalProcessScheduler`] runs each agent's runtime as a child process of
+//! the scheduler instead of a pod. The process is started from
+//! `local_command` with the same environment an agent pod gets, except that
+//! it listens on a free port on `127.0.0.1` and keeps its state in
+//! `local_state_dir/<agent id>`. Shared LLM keys are inherited from the
+//! scheduler's own environment.
+//!
+//! Compared to [`K8sScheduler`](crate::K8sScheduler) there is no isolation,
+//! no resource limits, no network policy and no metrics. Agent secrets are
+//! kept in memory only, and processes are stopped with the scheduler.
+//!
+//! Status changes are reported to the gateway through the same
+//! [`StatusOutbox`] as pod changes: `provisioning` on start, `running` once
+//! `/health` answers, and `stopped` or `error` when the process exits.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::process::{ExitStatus, Stdio};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use aura_swarm_core::AgentId;
+use aura_swarm_store::{AgentSpec, AgentState};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::StreamExt;
+use parking_lot::Mutex;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+use tokio::process::{Child, Command};
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::k8s::{LogStream, Scheduler};
+use crate::outbox::{StatusOutbox, StatusUpdate};
+use crate::pod::{build_activation_env, pod_name_for_agent};
+use crate::types::{PodInfo, PodLogOptions, PodMetrics, PodPhase, PodStatus, SchedulerConfig};
+use crate::{Result, SchedulerError};
+
+/// Address local runtimes listen on.
+const LOCAL_HOST: &str = "127.0.0.1";
+
+/// Output lines kept per process.
+const MAX_LOG_LINES: usize = 10_000;
+
+/// Output lines buffered for each follower of a process's logs.
+const LOG_FEED_LINES: usize = 256;
+
+/// How often a starting process is checked for health.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long to wait for a process's output to be read after it exits.
+const OUTPUT_DRAIN_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Scheduler running agents as local child processes.
+pub struct LocalProcessScheduler {
+    config: SchedulerConfig,
+    http_client: reqwest::Client,
+    status_outbox: Arc<StatusOutbox>,
+    processes: Mutex<HashMap<AgentId, LocalProcess>>,
+    secrets: Mutex<HashMap<AgentId, BTreeMap<String, String>>>,
+}
+
+/// A started agent process.
+struct LocalProcess {
+    port: u16,
+    status: Arc<Mutex<PodStatus>>,
+    logs: Arc<LogBuffer>,
+    /// Kills the process when sent or dropped.
+    stop: oneshot::Sender<()>,
+}
+
+impl LocalProcessScheduler {
+    /// Create a local-process scheduler.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SchedulerError::Config` if the HTTP client cannot be created.
+    pub fn new(config: SchedulerConfig) -> Result<Self> {
+        let http_client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .map_err(|e| SchedulerError::Config(format!("Failed to create HTTP client: {e}")))?;
+
+        let status_outbox = Arc::new(StatusOutbox::new(
+            http_client.clone(),
+            config.gateway_url.clone(),
+            Duration::from_secs(config.status_retry_max_seconds),
+        ));
+
+        Ok(Self {
+            config,
+            http_client,
+            status_outbox,
+            processes: Mutex::new(HashMap::new()),
+            secrets: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Get a reference to the scheduler config.
+    #[must_use]
+    pub fn config(&self) -> &SchedulerConfig {
+        &self.config
+    }
+
+    /// Get the queue of status updates waiting to be sent to the gateway.
+    ///
+    /// The queue is only delivered while its [`run`](StatusOutbox::run) loop
+    /// is running.
+    #[must_use]
+    pub fn status_outbox(&self) -> &Arc<StatusOutbox> {
+        &self.status_outbox
+    }
+
+    /// The directory holding an agent's state.
+    fn state_dir(&self, agent_id: &AgentId) -> PathBuf {
+        self.config.local_state_dir.join(agent_id.to_hex())
+    }
+
+    fn notify_status_change(&self, agent_id: AgentId, status: AgentState, message: Option<String>) {
+        self.status_outbox
+            .enqueue(agent_id, StatusUpdate { status, message });
+    }
+
+    /// Start the runtime process for an agent.
+    async fn spawn_process(
+        &self,
+        agent_id: &AgentId,
+        user_id_hex: &str,
+        spec: &AgentSpec,
+    ) -> Result<(Child, u16)> {
+        let Some((program, args)) = self.config.local_command.split_first() else {
+            return Err(SchedulerError::Config(
+                "local runtime command is empty".to_string(),
+            ));
+        };
+
+        let state_dir = self.state_dir(agent_id);
+        tokio::fs::create_dir_all(&state_dir).await?;
+        let state_dir = tokio::fs::canonicalize(&state_dir).await?;
+        let port = free_port()?;
+
+        let secrets = self
+            .secrets
+            .lock()
+            .get(agent_id)
+            .cloned()
+            .unwrap_or_default();
+        let mut env = build_activation_env(agent_id, user_id_hex, spec, &secrets, &self.config);
+        env.insert(
+            "STATE_DIR".to_string(),
+            state_dir.to_string_lossy().into_owned(),
+        );
+        env.insert(
+            "AURA_LISTEN_ADDR".to_string(),
+            format!("{LOCAL_HOST}:{port}"),
+        );
+
+        let child = Command::new(program)
+            .args(args)
+            .envs(&env)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| {
+                SchedulerError::PodCreationFailed(format!("failed to start {program}: {e}"))
+            })?;
+
+        Ok((child, port))
+    }
+}
+
+#[async_trait]
+impl Scheduler for LocalProcessScheduler {
+    async fn schedule_agent(
+        &self,
+        agent_id: &AgentId,
+        user_id_hex: &str,
+        spec: &AgentSpec,
+    ) -> Result<()> {
+        self.config
+            .validate_resources(spec.cpu_millicores, spec.memory_mb)?;
+
+        let running = self
+            .processes
+            .lock()
+            .get(agent_id)
+            .is_some_and(|p| p.status.lock().phase.is_active());
+        if running {
+            warn!(agent_id = %agent_id, "Process already running, skipping start");
+            return Ok(());
+        }
+
+        let (mut child, port) = self.spawn_process(agent_id, user_id_hex, spec).await?;
+        let pid = child.id();
+
+        let logs = Arc::new(LogBuffer::new());
+        let readers = [
+            child.stdout.take().map(|out| capture_output(out, &logs)),
+            child.stderr.take().map(|err| capture_output(err, &logs)),
+        ];
+        let status = Arc::new(Mutex::new(PodStatus {
+            phase: PodPhase::Running,
+            started_at: Some(Utc::now()),
+            ..PodStatus::default()
+        }));
+        let (stop, stopped) = oneshot::channel();
+
+        tokio::spawn(supervise(
+            *agent_id,
+            child,
+            stopped,
+            readers.into_iter().flatten().collect(),
+            Arc::clone(&status),
+            Arc::clone(&logs),
+            Arc::clone(&self.status_outbox),
+        ));
+        tokio::spawn(await_healthy(
+            *agent_id,
+            self.http_client.clone(),
+            format!("{LOCAL_HOST}:{port}"),
+            Arc::downgrade(&status),
+            Arc::clone(&self.status_outbox),
+        ));
+
+        // Replaces, and so stops, any exited process of the agent
+        self.processes.lock().insert(
+            *agent_id,
+            LocalProcess {
+                port,
+                status,
+                logs,
+                stop,
+            },
+        );
+        self.notify_status_change(*agent_id, AgentState::Provisioning, None);
+
+        info!(agent_id = %agent_id, pid, port, "Started agent process");
+        Ok(())
+    }
+
+    async fn terminate_agent(&self, agent_id: &AgentId) -> Result<()> {
+        let Some(process) = self.processes.lock().remove(agent_id) else {
+            warn!(agent_id = %agent_id, "Process not found, already terminated");
+            return Ok(());
+        };
+
+        // The process may already have exited, dropping the receiver
+        let _ = process.stop.send(());
+        self.notify_status_change(
+            *agent_id,
+            AgentState::Stopped,
+            Some("Process terminated".to_string()),
+        );
+
+        info!(agent_id = %agent_id, "Terminated agent process");
+        Ok(())
+    }
+
+    async fn get_pod_status(&self, agent_id: &AgentId) -> Result<PodStatus> {
+        self.processes
+            .lock()
+            .get(agent_id)
+            .map(|p| p.status.lock().clone())
+            .ok_or_else(|| SchedulerError::PodNotFound(pod_name_for_agent(agent_id)))
+    }
+
+    async fn get_pod_endpoint(&self, agent_id: &AgentId) -> Result<Option<String>> {
+        Ok(self
+            .processes
+            .lock()
+            .get(agent_id)
+            .filter(|p| p.status.lock().phase == PodPhase::Running)
+            .map(|p| format!("{LOCAL_HOST}:{}", p.port)))
+    }
+
+    async fn list_pods(&self) -> Result<Vec<PodInfo>> {
+        Ok(self
+            .processes
+            .lock()
+            .iter()
+            .map(|(agent_id, p)| PodInfo {
+                agent_id: *agent_id,
+                pod_name: pod_name_for_agent(agent_id),
+                node_name: None,
+                pod_ip: Some(LOCAL_HOST.to_string()),
+                status: p.status.lock().clone(),
+            })
+            .collect())
+    }
+
+    async fn check_agent_health(&self, agent_id: &AgentId) -> Result<bool> {
+        let Some(endpoint) = self.get_pod_endpoint(agent_id).await? else {
+            return Ok(false);
+        };
+
+        match is_healthy(&self.http_client, &endpoint).await {
+            Ok(()) => Ok(true),
+            Err(reason) => {
+                warn!(agent_id = %agent_id, reason, "Health check failed");
+                Ok(false)
+            }
+        }
+    }
+
+    /// Local processes have no metrics.
+    async fn get_pod_metrics(&self, _agent_id: &AgentId) -> Result<Option<PodMetrics>> {
+        Ok(None)
+    }
+
+    async fn pod_logs(&self, agent_id: &AgentId, options: &PodLogOptions) -> Result<LogStream> {
+        let logs = self
+            .processes
+            .lock()
+            .get(agent_id)
+            .map(|p| Arc::clone(&p.logs))
+            .ok_or_else(|| SchedulerError::PodNotFound(pod_name_for_agent(agent_id)))?;
+
+        Ok(logs.stream(options))
+    }
+
+    async fn copy_agent_state(&self, source: &AgentId, target: &AgentId) -> Result<()> {
+        let source_dir = self.state_dir(source);
+        let target_dir = self.state_dir(target);
+
+        tokio::task::spawn_blocking(move || copy_dir(&source_dir, &target_dir))
+            .await
+            .map_err(|e| SchedulerError::JobFailed(format!("state copy panicked: {e}")))??;
+
+        info!(source = %source, target = %target, "Copied agent state");
+        Ok(())
+    }
+
+    async fn delete_agent_state(&self, agent_id: &AgentId) -> Result<()> {
+        match tokio::fs::remove_dir_all(self.state_dir(agent_id)).await {
+            Ok(()) => {
+                info!(agent_id = %agent_id, "Deleted agent state");
+                Ok(())
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn put_agent_secrets(
+        &self,
+        agent_id: &AgentId,
+        secrets: &BTreeMap<String, String>,
+    ) -> Result<()> {
+        if secrets.is_empty() {
+            return self.delete_agent_secrets(agent_id).await;
+        }
+
+        self.secrets.lock().insert(*agent_id, secrets.clone());
+        debug!(agent_id = %agent_id, keys = secrets.len(), "Stored agent secrets");
+        Ok(())
+    }
+
+    async fn delete_agent_secrets(&self, agent_id: &AgentId) -> Result<()> {
+        self.secrets.lock().remove(agent_id);
+        Ok(())
+    }
+
+    /// Local processes have no network policy.
+    async fn delete_network_policy(&self, _agent_id: &AgentId) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Wait for an agent process to exit or be stopped, and report how it ended.
+async fn supervise(
+    agent_id: AgentId,
+    mut child: Child,
+    stopped: oneshot::Receiver<()>,
+    readers: Vec<JoinHandle<()>>,
+    status: Arc<Mutex<PodStatus>>,
+    logs: Arc<LogBuffer>,
+    status_outbox: Arc<StatusOutbox>,
+) {
+    let exit = tokio::select! {
+        exit = child.wait() => Some(exit),
+        _ = stopped => {
+            if let Err(e) = child.kill().await {
+                warn!(agent_id = %agent_id, error = %e, "Failed to kill agent process");
+            }
+            None
+        }
+    };
+
+    for reader in readers {
+        let _ = tokio::time::timeout(OUTPUT_DRAIN_TIMEOUT, reader).await;
+    }
+    logs.close();
+
+    // A stopped process was terminated, which has already been reported
+    let Some(exit) = exit else {
+        return;
+    };
+
+    let (phase, state, message) = exit_outcome(&exit);
+    {
+        let mut status = status.lock();
+        status.phase = phase;
+        status.ready = false;
+        status.message.clone_from(&message);
+    }
+
+    info!(agent_id = %agent_id, ?phase, message = ?message, "Agent process exited");
+    status_outbox.enqueue(
+        agent_id,
+        StatusUpdate {
+            status: state,
+            message,
+        },
+    );
+}
+
+/// The phase and agent state a process ends up in after exiting.
+fn exit_outcome(exit: &std::io::Result<ExitStatus>) -> (PodPhase, AgentState, Option<String>) {
+    match exit {
+        Ok(exit) if exit.success() => (PodPhase::Succeeded, AgentState::Stopped, None),
+        Ok(exit) => (
+            PodPhase::Failed,
+            AgentState::Error,
+            Some(format!("Process {exit}")),
+        ),
+        Err(e) => (
+            PodPhase::Failed,
+            AgentState::Error,
+            Some(format!("Failed to wait for process: {e}")),
+        ),
+    }
+}
+
+/// Poll a starting process's health endpoint until it answers, then report
+/// the agent as running.
+///
+/// Gives up once the process exits or is terminated.
+async fn await_healthy(
+    agent_id: AgentId,
+    http_client: reqwest::Client,
+    endpoint: String,
+    status: Weak<Mutex<PodStatus>>,
+    status_outbox: Arc<StatusOutbox>,
+) {
+    loop {
+        tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+
+        let Some(status) = status.upgrade() else {
+            return;
+        };
+        if status.lock().phase != PodPhase::Running {
+            return;
+        }
+
+        if let Err(reason) = is_healthy(&http_client, &endpoint).await {
+            debug!(agent_id = %agent_id, reason, "Agent process not healthy yet");
+            continue;
+        }
+
+        status.lock().ready = true;
+        status_outbox.enqueue(
+            agent_id,
+            StatusUpdate {
+                status: AgentState::Running,
+                message: None,
+            },
+        );
+        info!(agent_id = %agent_id, endpoint, "Agent process is healthy");
+        return;
+    }
+}
+
+/// Check a runtime's `/health` endpoint, returning why it isn't healthy.
+async fn is_healthy(
+    http_client: &reqwest::Client,
+    endpoint: &str,
+) -> std::result::Result<(), String> {
+    match http_client
+        .get(format!("http://{endpoint}/health"))
+        .send()
+        .await
+    {
+        Ok(resp) if resp.status().is_success() => Ok(()),
+        Ok(resp) => Err(format!("health check returned {}", resp.status())),
+        Err(e) => Err(format!("health check request failed: {e}")),
+    }
+}
+
+/// Copy each line of a process's output into its log buffer.
+fn capture_output<R>(output: R, logs: &Arc<LogBuffer>) -> JoinHandle<()>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    let logs = Arc::clone(logs);
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(output).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            logs.push(&line);
+        }
+    })
+}
+
+/// Find a port on the local host that is free right now.
+fn free_port() -> Result<u16> {
+    let listener = std::net::TcpListener::bind((LOCAL_HOST, 0))?;
+    Ok(listener.local_addr()?.port())
+}
+
+/// Recursively copy `source` into `target`, creating `target` if needed.
+///
+/// A missing `source` leaves `target` empty.
+fn copy_dir(source: &Path, target: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(target)?;
+
+    let entries = match std::fs::read_dir(source) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let to = target.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &to)?;
+        } else {
+            std::fs::copy(entry.path(), to)?;
+        }
+    }
+    Ok(())
+}
+
+/// Output of a process, each line prefixed with when it was read, and a
+/// feed of new lines for followers.
+struct LogBuffer {
+    inner: Mutex<LogBufferInner>,
+}
+
+struct LogBufferInner {
+    lines: VecDeque<String>,
+    /// Closed once the process has exited.
+    feed: Option<broadcast::Sender<String>>,
+}
+
+impl LogBuffer {
+    fn new() -> Self {
+        Self {
+            inner: Mutex::new(LogBufferInner {
+                lines: VecDeque::new(),
+                feed: Some(broadcast::channel(LOG_FEED_LINES).0),
+            }),
+        }
+    }
+
+    fn push(&self, line: &str) {
+        let line = format!(
+            "{} {line}",
+            Utc::now().to_rfc3339_opts(SecondsFormat::Nanos, true)
+        );
+
+        let mut inner = self.inner.lock();
+        if inner.lines.len() == MAX_LOG_LINES {
+            inner.lines.pop_front();
+        }
+        if let Some(feed) = &inner.feed {
+            // Nobody following is fine
+            let _ = feed.send(line.clone());
+        }
+        inner.lines.push_back(line);
+    }
+
+    /// End the streams of followers.
+    fn close(&self) {
+        self.inner.lock().feed = None;
+    }
+
+    fn stream(&self, options: &PodLogOptions) -> LogStream {
+        let inner = self.inner.lock();
+
+        let mut lines: Vec<String> = inner
+            .lines
+            .iter()
+            .filter(|line| {
+                options
+                    .since
+                    .is_none_or(|since| line_time(line) >= Some(since))
+            })
+            .cloned()
+            .collect();
+        if let Some(tail) = options.tail {
+            let tail = usize::try_from(tail).unwrap_or(usize::MAX);
+            lines.drain(..lines.len().saturating_sub(tail));
+        }
+
+        // Subscribing under the lock means no line is missed or repeated
+        let feed = if options.follow {
+            inner.feed.as_ref().map(broadcast::Sender::subscribe)
+        } else {
+            None
+        };
+        drop(inner);
+
+        let backlog = futures::stream::iter(lines.into_iter().map(Ok));
+        let Some(feed) = feed else {
+            return backlog.boxed();
+        };
+
+        let followed = futures::stream::unfold(feed, |mut feed| async move {
+            loop {
+                match feed.recv().await {
+                    Ok(line) => return Some((Ok(line), feed)),
+                    // Lines dropped for a slow reader are skipped
+                    Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        });
+        backlog.chain(followed).boxed()
+    }
+}
+
+/// When a captured line was read.
+fn line_time(line: &str) -> Option<DateTime<Utc>> {
+    let (time, _) = line.split_once(' ')?;
+    DateTime::parse_from_rfc3339(time)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::UserId;
+    use futures::TryStreamExt;
+
+    fn test_agent_id() -> AgentId {
+        let user_id = UserId::from_bytes([1u8; 32]);
+        AgentId::generate(&user_id, "local-agent")
+    }
+
+    fn scheduler_running(dir: &Path, script: &str) -> LocalProcessScheduler {
+        let config = SchedulerConfig {
+            local_command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            local_state_dir: dir.to_path_buf(),
+            ..SchedulerConfig::default()
+        };
+        LocalProcessScheduler::new(config).unwrap()
+    }
+
+    async fn wait_for_phase(
+        scheduler: &LocalProcessScheduler,
+        agent_id: &AgentId,
+        phase: PodPhase,
+    ) -> PodStatus {
+        for _ in 0..100 {
+            let status = scheduler.get_pod_status(agent_id).await.unwrap();
+            if status.phase == phase {
+                return status;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("process never reached {phase:?}");
+    }
+
+    #[test]
+    fn log_buffer_applies_tail_and_since() {
+        let logs = LogBuffer::new();
+        logs.push("first");
+        let after_first = Utc::now();
+        logs.push("second");
+        logs.push("third");
+
+        let collect = |options: PodLogOptions| {
+            futures::executor::block_on(logs.stream(&options).try_collect::<Vec<_>>()).unwrap()
+        };
+
+        let tail = collect(PodLogOptions {
+            tail: Some(2),
+            ..PodLogOptions::default()
+        });
+        assert_eq!(tail.len(), 2);
+        assert!(tail[0].ends_with(" second"));
+
+        let since = collect(PodLogOptions {
+            since: Some(after_first),
+            ..PodLogOptions::default()
+        });
+        assert_eq!(since.len(), 2);
+        assert!(since
+            .iter()
+            .all(|line| line_time(line) >= Some(after_first)));
+    }
+
+    #[tokio::test]
+    async fn followed_logs_end_when_the_process_exits() {
+        let logs = LogBuffer::new();
+        logs.push("before");
+        let stream = logs.stream(&PodLogOptions {
+            follow: true,
+            ..PodLogOptions::default()
+        });
+        logs.push("after");
+        logs.close();
+
+        let lines: Vec<String> = stream.try_collect().await.unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].ends_with(" after"));
+    }
+
+    #[test]
+    fn copy_dir_copies_nested_state() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = dir.path().join("source");
+        std::fs::create_dir_all(source.join("sessions")).unwrap();
+        std::fs::write(source.join("sessions/1.json"), "{}").unwrap();
+
+        let target = dir.path().join("target");
+        copy_dir(&source, &target).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(target.join("sessions/1.json")).unwrap(),
+            "{}"
+        );
+
+        // Missing source state gives empty target state
+        let empty = dir.path().join("empty");
+        copy_dir(&dir.path().join("missing"), &empty).unwrap();
+        assert!(std::fs::read_dir(&empty).unwrap().next().is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn runs_agent_with_pod_environment() {
+        let dir = tempfile::tempdir().unwrap();
+        let scheduler = scheduler_running(
+            dir.path(),
+            "echo \"$AGENT_ID $AURA_LISTEN_ADDR $GITHUB_TOKEN\"; exec sleep 30",
+        );
+        let agent_id = test_agent_id();
+        let user_id = UserId::from_bytes([1u8; 32]);
+
+        let secrets = BTreeMap::from([("GITHUB_TOKEN".to_string(), "ghp_1".to_string())]);
+        scheduler
+            .put_agent_secrets(&agent_id, &secrets)
+            .await
+            .unwrap();
+        scheduler
+            .schedule_agent(&agent_id, &user_id.to_hex(), &AgentSpec::default())
+            .await
+            .unwrap();
+
+        assert!(dir.path().join(agent_id.to_hex()).is_dir());
+        let endpoint = scheduler
+            .get_pod_endpoint(&agent_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(endpoint.starts_with("127.0.0.1:"));
+
+        let mut line = None;
+        for _ in 0..100 {
+            let logs: Vec<String> = scheduler
+                .pod_logs(&agent_id, &PodLogOptions::default())
+                .await
+                .unwrap()
+                .try_collect()
+                .await
+                .unwrap();
+            if let Some(first) = logs.into_iter().next() {
+                line = Some(first);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        let line = line.expect("process wrote no output");
+        assert!(line.ends_with(&format!(" {} {endpoint} ghp_1", agent_id.to_hex())));
+
+        // Provisioning was reported
+        assert_eq!(scheduler.status_outbox().depth(), 1);
+
+        scheduler.terminate_agent(&agent_id).await.unwrap();
+        assert!(matches!(
+            scheduler.get_pod_status(&agent_id).await,
+            Err(SchedulerError::PodNotFound(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn reports_failed_processes() {
+        let dir = tempfile::tempdir().unwrap();
+        let scheduler = scheduler_running(dir.path(), "exit 3");
+        let agent_id = test_agent_id();
+
+        scheduler
+            .schedule_agent(&agent_id, "user", &AgentSpec::default())
+            .await
+            .unwrap();
+
+        let status = wait_for_phase(&scheduler, &agent_id, PodPhase::Failed).await;
+        assert!(!status.ready);
+        assert!(status.message.unwrap().contains('3'));
+        assert_eq!(scheduler.get_pod_endpoint(&agent_id).await.unwrap(), None);
+
+        // An exited agent can be started again
+        scheduler
+            .schedule_agent(&agent_id, "user", &AgentSpec::default())
+            .await
+            .unwrap();
+        assert!(scheduler.get_pod_status(&agent_id).await.is_ok());
+    }
+}
@@ -2,42 +2,65 @@
 //!
 //! This is the main entry point for the scheduler service.
 //! It manages agent pods in Kubernetes and provides health endpoints.
+//! With `SCHEDULER_BACKEND=local_process` it runs agents as local processes
+//! instead, for development without a cluster.
 //!
 //! # HTTP Endpoints
 //!
 //! ## Health & Readiness
 //! - `GET /health` - Health check
 //! - `GET /ready` - Readiness check
+//! - `GET /v1/status-outbox` - Depth of the queue of undelivered status callbacks
 //!
 //! ## Agent Pod Management
 //! - `POST /v1/agents/:agent_id/schedule` - Schedule (create) an agent pod
 //! - `DELETE /v1/agents/:agent_id` - Terminate an agent pod
 //! - `GET /v1/agents/:agent_id/status` - Get pod status
+//! - `GET /v1/agents/:agent_id/endpoint` - Get pod endpoint
+//! - `GET /v1/agents/:agent_id/metrics` - Get pod resource usage
+//! - `GET /v1/agents/:agent_id/logs` - Stream the runtime container's logs
+//! - `GET /v1/pods` - List all agent pods
+//!
+//! ## Agent State Management
+//! - `POST /v1/agents/:agent_id/state/copy` - Copy another agent's state into this agent
+//! - `DELETE /v1/agents/:agent_id/state` - Delete the agent's state
+//!
+//! ## Agent Secret Management
+//! - `PUT /v1/agents/:agent_id/secrets` - Replace the agent's Kubernetes Secret
+//! - `DELETE /v1/agents/:agent_id/secrets` - Delete the agent's Kubernetes Secret
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use aura_swarm_core::AgentId;
-use aura_swarm_scheduler::{K8sScheduler, Scheduler, SchedulerConfig, SchedulerError};
+use aura_swarm_scheduler::{
+    K8sScheduler, LocalProcessScheduler, PodLogOptions, PodMetrics, Scheduler, SchedulerBackend,
+    SchedulerConfig, SchedulerError, StatusOutbox,
+};
 use aura_swarm_store::AgentSpec;
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
     response::IntoResponse,
-    routing::{delete, get, post},
+    routing::{delete, get, post, put},
     Json, Router,
 };
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 /// Application state shared across handlers.
 struct AppState {
-    scheduler: Arc<K8sScheduler>,
+    scheduler: Arc<dyn Scheduler>,
+    status_outbox: Arc<StatusOutbox>,
 }
 
 impl Clone for AppState {
     fn clone(&self) -> Self {
         Self {
             scheduler: Arc::clone(&self.scheduler),
+            status_outbox: Arc::clone(&self.status_outbox),
         }
     }
 }
@@ -64,6 +87,10 @@ async fn ready_handler(State(_state): State<AppState>) -> impl IntoResponse {
     (StatusCode::OK, "ready")
 }
 
+async fn status_outbox_handler(State(state): State<AppState>) -> impl IntoResponse {
+    Json(state.status_outbox.stats())
+}
+
 // ============================================================================
 // Agent Pod Management Endpoints
 // ============================================================================
@@ -254,6 +281,345 @@ async fn endpoint_handler(
     }
 }
 
+/// Response for pod metrics lookup.
+#[derive(Debug, Serialize)]
+struct MetricsResponse {
+    /// The pod's resource usage, if the metrics API has a sample for it.
+    metrics: Option<PodMetrics>,
+}
+
+/// Get the current resource usage of an agent's pod.
+///
+/// GET /v1/agents/:agent_id/metrics
+async fn metrics_handler(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+) -> impl IntoResponse {
+    let agent_id = match AgentId::from_hex(&agent_id) {
+        Ok(id) => id,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(format!("Invalid agent ID: {e}"), 400)),
+            )
+                .into_response();
+        }
+    };
+
+    match state.scheduler.get_pod_metrics(&agent_id).await {
+        Ok(metrics) => Json(MetricsResponse { metrics }).into_response(),
+        Err(e) => {
+            tracing::warn!(
+                agent_id = %agent_id,
+                error = %e,
+                "Failed to fetch pod metrics"
+            );
+            let code = e.http_status_code();
+            (
+                StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(ErrorResponse::new(e.to_string(), code)),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Stream the logs of an agent's runtime container as plain text.
+///
+/// Each line starts with the RFC 3339 time it was written. With
+/// `follow=true` the response stays open until the container stops or the
+/// client disconnects.
+///
+/// GET /v1/agents/:agent_id/logs?tail=&since=&follow=
+async fn logs_handler(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+    Query(options): Query<PodLogOptions>,
+) -> impl IntoResponse {
+    let agent_id = match AgentId::from_hex(&agent_id) {
+        Ok(id) => id,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(format!("Invalid agent ID: {e}"), 400)),
+            )
+                .into_response();
+        }
+    };
+
+    match state.scheduler.pod_logs(&agent_id, &options).await {
+        Ok(lines) => {
+            let body = Body::from_stream(lines.map(|line| line.map(|line| format!("{line}\n"))));
+            ([(header::CONTENT_TYPE, "text/plain; charset=utf-8")], body).into_response()
+        }
+        Err(SchedulerError::PodNotFound(_)) => (
+            StatusCode::NOT_FOUND,
+            Json(ErrorResponse::new("Pod not found", 404)),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!(
+                agent_id = %agent_id,
+                error = %e,
+                "Failed to open agent log stream"
+            );
+            let code = e.http_status_code();
+            (
+                StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(ErrorResponse::new(e.to_string(), code)),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// List all agent pods.
+///
+/// Used by the control plane to detect drift between its store and the cluster.
+///
+/// GET /v1/pods
+async fn list_pods_handler(State(state): State<AppState>) -> impl IntoResponse {
+    match state.scheduler.list_pods().await {
+        Ok(pods) => Json(pods).into_response(),
+        Err(e) => {
+            let code = e.http_status_code();
+            (
+                StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(ErrorResponse::new(e.to_string(), code)),
+            )
+                .into_response()
+        }
+    }
+}
+
+// ============================================================================
+// Agent State Management Endpoints
+// ============================================================================
+
+/// Request body for copying agent state.
+#[derive(Debug, Deserialize)]
+struct CopyStateRequest {
+    /// The agent ID (hex-encoded) whose state should be copied.
+    source_agent_id: String,
+}
+
+/// Copy another agent's state directory into this agent's state directory.
+///
+/// Blocks until the copy job has finished.
+///
+/// `POST /v1/agents/:agent_id/state/copy`
+async fn copy_state_handler(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+    Json(req): Json<CopyStateRequest>,
+) -> impl IntoResponse {
+    let (target, source) = match (
+        AgentId::from_hex(&agent_id),
+        AgentId::from_hex(&req.source_agent_id),
+    ) {
+        (Ok(target), Ok(source)) => (target, source),
+        (Err(e), _) | (_, Err(e)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(format!("Invalid agent ID: {e}"), 400)),
+            )
+                .into_response();
+        }
+    };
+
+    match state.scheduler.copy_agent_state(&source, &target).await {
+        Ok(()) => {
+            tracing::info!(
+                source = %source,
+                target = %target,
+                "Copied agent state via HTTP API"
+            );
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => {
+            tracing::error!(
+                source = %source,
+                target = %target,
+                error = %e,
+                "Failed to copy agent state"
+            );
+            let code = e.http_status_code();
+            (
+                StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(ErrorResponse::new(e.to_string(), code)),
+            )
+                .into_response()
+        }
+    }
+}
+
+// ============================================================================
+// Agent Secret Management Endpoints
+// ============================================================================
+
+/// Delete the agent's persistent state.
+///
+/// Blocks until the state volume is deleted or the cleanup job has finished.
+///
+/// `DELETE /v1/agents/:agent_id/state`
+async fn delete_state_handler(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+) -> impl IntoResponse {
+    let agent_id = match AgentId::from_hex(&agent_id) {
+        Ok(id) => id,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(format!("Invalid agent ID: {e}"), 400)),
+            )
+                .into_response();
+        }
+    };
+
+    match state.scheduler.delete_agent_state(&agent_id).await {
+        Ok(()) => {
+            tracing::info!(agent_id = %agent_id, "Deleted agent state via HTTP API");
+            StatusCode::NO_CONTENT.into_response()
+        }
+        Err(e) => {
+            tracing::error!(
+                agent_id = %agent_id,
+                error = %e,
+                "Failed to delete agent state"
+            );
+            let code = e.http_status_code();
+            (
+                StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(ErrorResponse::new(e.to_string(), code)),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Request body for replacing an agent's secrets.
+#[derive(Deserialize)]
+struct PutSecretsRequest {
+    /// Secret values keyed by environment variable name.
+    secrets: BTreeMap<String, String>,
+}
+
+/// Replace the agent's Kubernetes Secret.
+///
+/// Pods scheduled afterwards see the new values. Values are never logged.
+///
+/// `PUT /v1/agents/:agent_id/secrets`
+async fn put_secrets_handler(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+    Json(req): Json<PutSecretsRequest>,
+) -> impl IntoResponse {
+    let agent_id = match AgentId::from_hex(&agent_id) {
+        Ok(id) => id,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(format!("Invalid agent ID: {e}"), 400)),
+            )
+                .into_response();
+        }
+    };
+
+    match state
+        .scheduler
+        .put_agent_secrets(&agent_id, &req.secrets)
+        .await
+    {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => {
+            tracing::error!(
+                agent_id = %agent_id,
+                error = %e,
+                "Failed to write agent secrets"
+            );
+            let code = e.http_status_code();
+            (
+                StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(ErrorResponse::new(e.to_string(), code)),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Delete the agent's Kubernetes Secret.
+///
+/// `DELETE /v1/agents/:agent_id/secrets`
+async fn delete_secrets_handler(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+) -> impl IntoResponse {
+    let agent_id = match AgentId::from_hex(&agent_id) {
+        Ok(id) => id,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(format!("Invalid agent ID: {e}"), 400)),
+            )
+                .into_response();
+        }
+    };
+
+    match state.scheduler.delete_agent_secrets(&agent_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => {
+            tracing::error!(
+                agent_id = %agent_id,
+                error = %e,
+                "Failed to delete agent secrets"
+            );
+            let code = e.http_status_code();
+            (
+                StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(ErrorResponse::new(e.to_string(), code)),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Delete the agent's Kubernetes `NetworkPolicy`.
+///
+/// `DELETE /v1/agents/:agent_id/network-policy`
+async fn delete_network_policy_handler(
+    State(state): State<AppState>,
+    Path(agent_id): Path<String>,
+) -> impl IntoResponse {
+    let agent_id = match AgentId::from_hex(&agent_id) {
+        Ok(id) => id,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse::new(format!("Invalid agent ID: {e}"), 400)),
+            )
+                .into_response();
+        }
+    };
+
+    match state.scheduler.delete_network_policy(&agent_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => {
+            tracing::error!(
+                agent_id = %agent_id,
+                error = %e,
+                "Failed to delete agent network policy"
+            );
+            let code = e.http_status_code();
+            (
+                StatusCode::from_u16(code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+                Json(ErrorResponse::new(e.to_string(), code)),
+            )
+                .into_response()
+        }
+    }
+}
+
 // ============================================================================
 // Router
 // ============================================================================
@@ -263,14 +629,76 @@ fn create_router(state: AppState) -> Router {
         // Health & readiness
         .route("/health", get(health_handler))
         .route("/ready", get(ready_handler))
+        .route("/v1/status-outbox", get(status_outbox_handler))
         // Agent pod management
         .route("/v1/agents/:agent_id/schedule", post(schedule_handler))
         .route("/v1/agents/:agent_id", delete(terminate_handler))
         .route("/v1/agents/:agent_id/status", get(status_handler))
         .route("/v1/agents/:agent_id/endpoint", get(endpoint_handler))
+        .route("/v1/agents/:agent_id/metrics", get(metrics_handler))
+        .route("/v1/agents/:agent_id/logs", get(logs_handler))
+        .route("/v1/pods", get(list_pods_handler))
+        // Agent state management
+        .route("/v1/agents/:agent_id/state", delete(delete_state_handler))
+        .route("/v1/agents/:agent_id/state/copy", post(copy_state_handler))
+        // Agent secret management
+        .route(
+            "/v1/agents/:agent_id/secrets",
+            put(put_secrets_handler).delete(delete_secrets_handler),
+        )
+        // Agent network policy management
+        .route(
+            "/v1/agents/:agent_id/network-policy",
+            delete(delete_network_policy_handler),
+        )
         .with_state(state)
 }
 
+/// Connect to the cluster and start the reconciler and warm pools.
+async fn start_k8s_scheduler(config: SchedulerConfig) -> Result<AppState, SchedulerError> {
+    let scheduler = Arc::new(K8sScheduler::new(config).await?);
+    tracing::info!("Connected to Kubernetes cluster");
+
+    // Start the reconciler as a background task
+    let reconciler_scheduler = Arc::clone(&scheduler);
+    tokio::spawn(async move {
+        reconciler_scheduler.run_reconciler().await;
+    });
+    tracing::info!("Started pod reconciliation loop");
+
+    // Keep the warm pools filled
+    let pool_scheduler = Arc::clone(&scheduler);
+    tokio::spawn(async move {
+        pool_scheduler.run_warm_pools().await;
+    });
+
+    Ok(AppState {
+        status_outbox: Arc::clone(scheduler.status_outbox()),
+        scheduler,
+    })
+}
+
+/// Run agents as local processes, delivering their status changes.
+fn start_local_scheduler(config: SchedulerConfig) -> Result<AppState, SchedulerError> {
+    tracing::info!(
+        command = ?config.local_command,
+        state_dir = %config.local_state_dir.display(),
+        "Running agents as local processes"
+    );
+    let scheduler = Arc::new(LocalProcessScheduler::new(config)?);
+
+    let status_outbox = Arc::clone(scheduler.status_outbox());
+    let delivery_outbox = Arc::clone(&status_outbox);
+    tokio::spawn(async move {
+        delivery_outbox.run().await;
+    });
+
+    Ok(AppState {
+        scheduler,
+        status_outbox,
+    })
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Initialize tracing
@@ -289,25 +717,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let config = SchedulerConfig::from_env();
 
     tracing::info!(
+        backend = ?config.backend,
         namespace = %config.namespace,
         image = %config.image,
         gateway_url = %config.gateway_url,
         "Loaded scheduler configuration"
     );
 
-    // Initialize K8s scheduler
-    let scheduler = Arc::new(K8sScheduler::new(config).await?);
-    tracing::info!("Connected to Kubernetes cluster");
-
-    // Start the reconciler as a background task
-    let reconciler_scheduler = Arc::clone(&scheduler);
-    tokio::spawn(async move {
-        reconciler_scheduler.run_reconciler().await;
-    });
-    tracing::info!("Started pod reconciliation loop");
-
-    // Create app state
-    let state = AppState { scheduler };
+    let state = match config.backend {
+        SchedulerBackend::Kubernetes => start_k8s_scheduler(config).await?,
+        SchedulerBackend::LocalProcess => start_local_scheduler(config)?,
+    };
 
     // Create router
     let app = create_router(state);
@@ -0,0 +1,182 @@
+//! Pod resource usage from the Kubernetes metrics API.
+//!
+//! `metrics.k8s.io` is served by metrics-server rather than the API server
+//! itself, and `k8s-openapi` has no types for it, so pod metrics are read as
+//! dynamic objects and their usage quantities parsed here.
+
+use chrono::{DateTime, Utc};
+use kube::api::{ApiResource, DynamicObject};
+
+use crate::types::PodMetrics;
+
+/// The `PodMetrics` resource of the `metrics.k8s.io/v1beta1` API.
+#[must_use]
+pub fn pod_metrics_resource() -> ApiResource {
+    ApiResource {
+        group: "metrics.k8s.io".to_string(),
+        version: "v1beta1".to_string(),
+        api_version: "metrics.k8s.io/v1beta1".to_string(),
+        kind: "PodMetrics".to_string(),
+        plural: "pods".to_string(),
+    }
+}
+
+/// Sum the usage of every container in a `PodMetrics` object.
+///
+/// Quantities that cannot be parsed count as zero.
+#[must_use]
+pub fn pod_metrics_from_object(object: &DynamicObject) -> PodMetrics {
+    let containers = object
+        .data
+        .get("containers")
+        .and_then(|c| c.as_array())
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let usage = |resource: &str, parse: fn(&str) -> Option<u64>| -> u64 {
+        containers
+            .iter()
+            .filter_map(|c| c.pointer(&format!("/usage/{resource}"))?.as_str())
+            .filter_map(parse)
+            .fold(0, u64::saturating_add)
+    };
+
+    let timestamp = object
+        .data
+        .get("timestamp")
+        .and_then(|t| t.as_str())
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Utc));
+
+    PodMetrics {
+        cpu_millicores: usage("cpu", parse_cpu_millicores),
+        memory_bytes: usage("memory", parse_memory_bytes),
+        timestamp,
+    }
+}
+
+/// Parse a CPU quantity such as `250m`, `2` or `1234567n` into millicores,
+/// rounding up.
+#[must_use]
+pub fn parse_cpu_millicores(quantity: &str) -> Option<u64> {
+    let (number, nanos_per_unit) = match quantity.as_bytes().last()? {
+        b'n' => (&quantity[..quantity.len() - 1], 1),
+        b'u' => (&quantity[..quantity.len() - 1], 1_000),
+        b'm' => (&quantity[..quantity.len() - 1], 1_000_000),
+        _ => (quantity, 1_000_000_000),
+    };
+    let nanos = parse_decimal(number, nanos_per_unit)?;
+    Some(nanos.div_ceil(1_000_000))
+}
+
+/// Parse a memory quantity such as `524288Ki`, `128Mi` or `1G` into bytes.
+#[must_use]
+pub fn parse_memory_bytes(quantity: &str) -> Option<u64> {
+    const SUFFIXES: [(&str, u64); 12] = [
+        ("Ki", 1 << 10),
+        ("Mi", 1 << 20),
+        ("Gi", 1 << 30),
+        ("Ti", 1 << 40),
+        ("Pi", 1 << 50),
+        ("Ei", 1 << 60),
+        ("k", 1_000),
+        ("M", 1_000_000),
+        ("G", 1_000_000_000),
+        ("T", 1_000_000_000_000),
+        ("P", 1_000_000_000_000_000),
+        ("E", 1_000_000_000_000_000_000),
+    ];
+
+    SUFFIXES
+        .iter()
+        .find_map(|(suffix, multiplier)| {
+            let number = quantity.strip_suffix(suffix)?;
+            parse_decimal(number, *multiplier)
+        })
+        .or_else(|| parse_decimal(quantity, 1))
+}
+
+/// Parse a non-negative decimal number and multiply it by `multiplier`,
+/// dropping any fraction that remains.
+fn parse_decimal(number: &str, multiplier: u64) -> Option<u64> {
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    if !whole
+        .bytes()
+        .chain(fraction.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let value = whole.checked_mul(multiplier)?;
+
+    // Digits beyond the 18th can't change the result for any multiplier used here
+    let fraction = &fraction[..fraction.len().min(18)];
+    if fraction.is_empty() {
+        return Some(value);
+    }
+    let numerator: u128 = fraction.parse().ok()?;
+    let denominator = 10u128.pow(u32::try_from(fraction.len()).ok()?);
+    let fraction = u64::try_from(numerator * u128::from(multiplier) / denominator).ok()?;
+    value.checked_add(fraction)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cpu_quantities() {
+        assert_eq!(parse_cpu_millicores("250m"), Some(250));
+        assert_eq!(parse_cpu_millicores("2"), Some(2000));
+        assert_eq!(parse_cpu_millicores("0.5"), Some(500));
+        assert_eq!(parse_cpu_millicores("1234567n"), Some(2));
+        assert_eq!(parse_cpu_millicores("1500u"), Some(2));
+        assert_eq!(parse_cpu_millicores("0"), Some(0));
+        assert_eq!(parse_cpu_millicores(""), None);
+        assert_eq!(parse_cpu_millicores("abc"), None);
+    }
+
+    #[test]
+    fn parses_memory_quantities() {
+        assert_eq!(parse_memory_bytes("524288Ki"), Some(512 * 1024 * 1024));
+        assert_eq!(parse_memory_bytes("128Mi"), Some(128 * 1024 * 1024));
+        assert_eq!(parse_memory_bytes("1.5Gi"), Some(1536 * 1024 * 1024));
+        assert_eq!(parse_memory_bytes("1G"), Some(1_000_000_000));
+        assert_eq!(parse_memory_bytes("4096"), Some(4096));
+        assert_eq!(parse_memory_bytes("Mi"), None);
+        assert_eq!(parse_memory_bytes("-1Mi"), None);
+    }
+
+    #[test]
+    fn sums_container_usage() {
+        let object: DynamicObject = serde_json::from_value(serde_json::json!({
+            "apiVersion": "metrics.k8s.io/v1beta1",
+            "kind": "PodMetrics",
+            "metadata": { "name": "agent-0101010101010101" },
+            "timestamp": "2026-01-01T00:00:00Z",
+            "window": "30s",
+            "containers": [
+                { "name": "aura", "usage": { "cpu": "120m", "memory": "256Mi" } },
+                { "name": "sidecar", "usage": { "cpu": "5000000n", "memory": "1024Ki" } },
+            ],
+        }))
+        .unwrap();
+
+        let metrics = pod_metrics_from_object(&object);
+        assert_eq!(metrics.cpu_millicores, 125);
+        assert_eq!(metrics.memory_bytes, 256 * 1024 * 1024 + 1024 * 1024);
+        assert_eq!(
+            metrics.timestamp.unwrap().to_rfc3339(),
+            "2026-01-01T00:00:00+00:00"
+        );
+    }
+}
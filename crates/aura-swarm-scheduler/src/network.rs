@@ -0,0 +1,423 @@
+//! `NetworkPolicy` builder for per-agent egress rules.
+//!
+//! Each agent gets its own policy, selected by its `swarm.io/agent-id` label,
+//! that limits its pod's traffic to what its [`EgressPolicy`] allows. Whatever
+//! the policy, the pod always accepts traffic from the gateway and control
+//! plane, and may always reach DNS and the gateway and control plane for
+//! status callbacks and heartbeats.
+//!
+//! Kubernetes `NetworkPolicy` rules only match addresses, so host names in an
+//! allowlist are resolved by the scheduler when the policy is applied, that
+//! is whenever the agent's pod is scheduled. The policy keeps those addresses
+//! until the pod is next scheduled, so a host whose DNS records change is
+//! only reachable at its new addresses after the agent restarts.
+//!
+//! No policy opens the private ranges in
+//! [`EgressPolicy::PRIVATE_IPV4_RANGES`] and
+//! [`EgressPolicy::PRIVATE_IPV6_RANGES`]: allowlist CIDRs overlapping them
+//! and host addresses inside them are left out.
+
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use aura_swarm_core::AgentId;
+use aura_swarm_store::{AgentSpec, EgressPolicy, EgressRule};
+use k8s_openapi::api::networking::v1::{
+    IPBlock, NetworkPolicy, NetworkPolicyEgressRule, NetworkPolicyIngressRule, NetworkPolicyPeer,
+    NetworkPolicyPort, NetworkPolicySpec,
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::api::ObjectMeta;
+
+use crate::pod::{truncate_for_label, AURA_PORT};
+use crate::types::SchedulerConfig;
+
+/// Platform services that may reach agents and be reached by them.
+const SYSTEM_APPS: [&str; 2] = ["aura-swarm-gateway", "aura-swarm-control"];
+
+/// Port the platform services listen on.
+const SYSTEM_PORT: i32 = 8080;
+
+/// Resolved addresses for the host names in an allowlist.
+pub type HostAddresses = BTreeMap<String, Vec<IpAddr>>;
+
+/// Generate the name of an agent's `NetworkPolicy`.
+///
+/// Uses the same 16-character agent ID prefix as the pod name.
+#[must_use]
+pub fn network_policy_name_for_agent(agent_id: &AgentId) -> String {
+    format!("agent-{}-network", &agent_id.to_hex()[..16])
+}
+
+/// The egress policy for an agent's spec.
+#[must_use]
+pub fn egress_for_spec<'a>(spec: &'a AgentSpec, config: &'a SchedulerConfig) -> &'a EgressPolicy {
+    spec.egress.as_ref().unwrap_or(&config.default_egress)
+}
+
+/// The host names an egress policy needs resolved.
+#[must_use]
+pub fn hosts_to_resolve(egress: &EgressPolicy) -> Vec<&str> {
+    match egress {
+        EgressPolicy::Allowlist { rules } => rules
+            .iter()
+            .filter_map(|rule| rule.host.as_deref())
+            .collect(),
+        EgressPolicy::DenyAll | EgressPolicy::AllowInternet => Vec::new(),
+    }
+}
+
+/// Build the `NetworkPolicy` for an agent.
+///
+/// Host rules use the public addresses in `resolved`. A rule with no public
+/// destination is left out, since a rule without destinations would allow
+/// every destination.
+#[must_use]
+pub fn build_network_policy(
+    agent_id: &AgentId,
+    egress: &EgressPolicy,
+    resolved: &HostAddresses,
+    config: &SchedulerConfig,
+) -> NetworkPolicy {
+    let agent_id_hex = agent_id.to_hex();
+    let agent_label = truncate_for_label(&agent_id_hex);
+
+    let mut labels = BTreeMap::new();
+    labels.insert("app".to_string(), "swarm-agent-network".to_string());
+    labels.insert("swarm.io/agent-id".to_string(), agent_label.clone());
+
+    let mut annotations = BTreeMap::new();
+    annotations.insert("swarm.io/agent-id-full".to_string(), agent_id_hex);
+
+    let mut selector = BTreeMap::new();
+    selector.insert("swarm.io/agent-id".to_string(), agent_label);
+
+    let ingress = vec![NetworkPolicyIngressRule {
+        from: Some(system_peers(config)),
+        ports: Some(vec![tcp_port(AURA_PORT)]),
+    }];
+
+    let mut egress_rules = vec![
+        dns_rule(),
+        NetworkPolicyEgressRule {
+            to: Some(system_peers(config)),
+            ports: Some(vec![tcp_port(SYSTEM_PORT)]),
+        },
+    ];
+    egress_rules.extend(policy_rules(egress, resolved));
+
+    NetworkPolicy {
+        metadata: ObjectMeta {
+            name: Some(network_policy_name_for_agent(agent_id)),
+            namespace: Some(config.namespace.clone()),
+            labels: Some(labels),
+            annotations: Some(annotations),
+            ..Default::default()
+        },
+        spec: Some(NetworkPolicySpec {
+            pod_selector: LabelSelector {
+                match_labels: Some(selector),
+                ..Default::default()
+            },
+            policy_types: Some(vec!["Ingress".to_string(), "Egress".to_string()]),
+            ingress: Some(ingress),
+            egress: Some(egress_rules),
+        }),
+    }
+}
+
+/// Egress rules granted by the agent's policy itself.
+fn policy_rules(egress: &EgressPolicy, resolved: &HostAddresses) -> Vec<NetworkPolicyEgressRule> {
+    match egress {
+        EgressPolicy::DenyAll => Vec::new(),
+        EgressPolicy::AllowInternet => vec![NetworkPolicyEgressRule {
+            to: Some(vec![
+                ip_block("0.0.0.0/0", &EgressPolicy::PRIVATE_IPV4_RANGES),
+                ip_block("::/0", &EgressPolicy::PRIVATE_IPV6_RANGES),
+            ]),
+            ports: None,
+        }],
+        EgressPolicy::Allowlist { rules } => rules
+            .iter()
+            .filter_map(|rule| allowlist_rule(rule, resolved))
+            .collect(),
+    }
+}
+
+fn allowlist_rule(rule: &EgressRule, resolved: &HostAddresses) -> Option<NetworkPolicyEgressRule> {
+    let peers: Vec<NetworkPolicyPeer> = match (&rule.cidr, &rule.host) {
+        // The control plane rejects private CIDRs; older specs may still have them
+        (Some(cidr), _) => is_public_cidr(cidr)
+            .then(|| ip_block(cidr, &[]))
+            .into_iter()
+            .collect(),
+        (None, Some(host)) => resolved
+            .get(host)
+            .into_iter()
+            .flatten()
+            .filter_map(|ip| {
+                let prefix = if ip.is_ipv4() { 32 } else { 128 };
+                (!EgressPolicy::overlaps_private_range(*ip, prefix))
+                    .then(|| ip_block(&format!("{ip}/{prefix}"), &[]))
+            })
+            .collect(),
+        (None, None) => Vec::new(),
+    };
+    if peers.is_empty() {
+        return None;
+    }
+
+    let ports = (!rule.ports.is_empty()).then(|| {
+        rule.ports
+            .iter()
+            .map(|&port| tcp_port(i32::from(port)))
+            .collect()
+    });
+
+    Some(NetworkPolicyEgressRule {
+        to: Some(peers),
+        ports,
+    })
+}
+
+/// Whether a CIDR is well-formed and outside every private range.
+fn is_public_cidr(cidr: &str) -> bool {
+    cidr.split_once('/')
+        .and_then(|(address, prefix)| Some((address.parse().ok()?, prefix.parse().ok()?)))
+        .is_some_and(|(address, prefix)| !EgressPolicy::overlaps_private_range(address, prefix))
+}
+
+/// Cluster DNS, which agents need to resolve any destination.
+fn dns_rule() -> NetworkPolicyEgressRule {
+    let mut labels = BTreeMap::new();
+    labels.insert("k8s-app".to_string(), "kube-dns".to_string());
+
+    let port = |protocol: &str| NetworkPolicyPort {
+        port: Some(IntOrString::Int(53)),
+        protocol: Some(protocol.to_string()),
+        end_port: None,
+    };
+
+    NetworkPolicyEgressRule {
+        to: Some(vec![NetworkPolicyPeer {
+            namespace_selector: Some(LabelSelector::default()),
+            pod_selector: Some(LabelSelector {
+                match_labels: Some(labels),
+                ..Default::default()
+            }),
+            ip_block: None,
+        }]),
+        ports: Some(vec![port("UDP"), port("TCP")]),
+    }
+}
+
+/// The gateway and control plane pods in the system namespace.
+fn system_peers(config: &SchedulerConfig) -> Vec<NetworkPolicyPeer> {
+    SYSTEM_APPS
+        .iter()
+        .map(|app| {
+            let mut namespace = BTreeMap::new();
+            namespace.insert(
+                "kubernetes.io/metadata.name".to_string(),
+                config.system_namespace.clone(),
+            );
+            let mut pod = BTreeMap::new();
+            pod.insert("app".to_string(), (*app).to_string());
+
+            NetworkPolicyPeer {
+                namespace_selector: Some(LabelSelector {
+                    match_labels: Some(namespace),
+                    ..Default::default()
+                }),
+                pod_selector: Some(LabelSelector {
+                    match_labels: Some(pod),
+                    ..Default::default()
+                }),
+                ip_block: None,
+            }
+        })
+        .collect()
+}
+
+fn ip_block(cidr: &str, except: &[&str]) -> NetworkPolicyPeer {
+    NetworkPolicyPeer {
+        ip_block: Some(IPBlock {
+            cidr: cidr.to_string(),
+            except: (!except.is_empty()).then(|| except.iter().map(ToString::to_string).collect()),
+        }),
+        namespace_selector: None,
+        pod_selector: None,
+    }
+}
+
+fn tcp_port(port: i32) -> NetworkPolicyPort {
+    NetworkPolicyPort {
+        port: Some(IntOrString::Int(port)),
+        protocol: Some("TCP".to_string()),
+        end_port: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::UserId;
+
+    fn test_agent_id() -> AgentId {
+        AgentId::generate(&UserId::from_bytes([1u8; 32]), "agent")
+    }
+
+    fn egress_rules(policy: &NetworkPolicy) -> &[NetworkPolicyEgressRule] {
+        policy.spec.as_ref().unwrap().egress.as_deref().unwrap()
+    }
+
+    fn cidrs(rule: &NetworkPolicyEgressRule) -> Vec<&str> {
+        rule.to
+            .iter()
+            .flatten()
+            .filter_map(|peer| peer.ip_block.as_ref())
+            .map(|block| block.cidr.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn deny_all_keeps_platform_traffic() {
+        let agent_id = test_agent_id();
+        let config = SchedulerConfig::default();
+        let policy = build_network_policy(
+            &agent_id,
+            &EgressPolicy::DenyAll,
+            &HostAddresses::new(),
+            &config,
+        );
+
+        assert_eq!(
+            policy.metadata.name,
+            Some(network_policy_name_for_agent(&agent_id))
+        );
+        let spec = policy.spec.as_ref().unwrap();
+        assert_eq!(
+            spec.pod_selector.match_labels.as_ref().unwrap()["swarm.io/agent-id"],
+            truncate_for_label(&agent_id.to_hex())
+        );
+        assert_eq!(
+            spec.policy_types,
+            Some(vec!["Ingress".to_string(), "Egress".to_string()])
+        );
+
+        // Gateway ingress, DNS, and heartbeats only
+        let ingress = spec.ingress.as_ref().unwrap();
+        assert_eq!(ingress.len(), 1);
+        assert_eq!(ingress[0].from.as_ref().unwrap().len(), SYSTEM_APPS.len());
+        let egress = egress_rules(&policy);
+        assert_eq!(egress.len(), 2);
+        assert!(egress.iter().all(|rule| cidrs(rule).is_empty()));
+    }
+
+    #[test]
+    fn allow_internet_excludes_private_ranges() {
+        let config = SchedulerConfig::default();
+        let policy = build_network_policy(
+            &test_agent_id(),
+            &EgressPolicy::AllowInternet,
+            &HostAddresses::new(),
+            &config,
+        );
+
+        let internet = &egress_rules(&policy)[2];
+        assert_eq!(cidrs(internet), vec!["0.0.0.0/0", "::/0"]);
+        let except = internet.to.as_ref().unwrap()[0]
+            .ip_block
+            .as_ref()
+            .unwrap()
+            .except
+            .clone()
+            .unwrap();
+        assert!(except.contains(&"10.0.0.0/8".to_string()));
+        assert!(except.contains(&"169.254.0.0/16".to_string()));
+        assert!(internet.ports.is_none());
+    }
+
+    #[test]
+    fn allowlist_uses_cidrs_and_resolved_hosts() {
+        let config = SchedulerConfig::default();
+        let egress = EgressPolicy::Allowlist {
+            rules: vec![
+                EgressRule {
+                    cidr: Some("203.0.113.0/24".to_string()),
+                    host: None,
+                    ports: Vec::new(),
+                },
+                EgressRule {
+                    cidr: None,
+                    host: Some("api.example.com".to_string()),
+                    ports: vec![443],
+                },
+                EgressRule {
+                    cidr: None,
+                    host: Some("unresolved.example.com".to_string()),
+                    ports: vec![443],
+                },
+                EgressRule {
+                    cidr: None,
+                    host: Some("internal.example.com".to_string()),
+                    ports: vec![443],
+                },
+                EgressRule {
+                    cidr: Some("169.254.169.254/32".to_string()),
+                    host: None,
+                    ports: Vec::new(),
+                },
+            ],
+        };
+        assert_eq!(
+            hosts_to_resolve(&egress),
+            vec![
+                "api.example.com",
+                "unresolved.example.com",
+                "internal.example.com"
+            ]
+        );
+
+        let mut resolved = HostAddresses::new();
+        resolved.insert(
+            "api.example.com".to_string(),
+            vec![
+                "10.0.0.7".parse().unwrap(),
+                "198.51.100.7".parse().unwrap(),
+                "2001:db8::7".parse().unwrap(),
+            ],
+        );
+        resolved.insert(
+            "internal.example.com".to_string(),
+            vec!["192.168.1.10".parse().unwrap(), "fd00::10".parse().unwrap()],
+        );
+        let policy = build_network_policy(&test_agent_id(), &egress, &resolved, &config);
+
+        // Private addresses are left out, and neither the unresolved host nor
+        // the private ones may become an allow-everything rule
+        let rules = &egress_rules(&policy)[2..];
+        assert_eq!(rules.len(), 2);
+        assert_eq!(cidrs(&rules[0]), vec!["203.0.113.0/24"]);
+        assert!(rules[0].ports.is_none());
+        assert_eq!(cidrs(&rules[1]), vec!["198.51.100.7/32", "2001:db8::7/128"]);
+        assert_eq!(rules[1].ports, Some(vec![tcp_port(443)]));
+    }
+
+    #[test]
+    fn spec_egress_overrides_default() {
+        let config = SchedulerConfig::default();
+        let spec = AgentSpec::default();
+        assert_eq!(
+            egress_for_spec(&spec, &config),
+            &EgressPolicy::AllowInternet
+        );
+
+        let spec = AgentSpec {
+            egress: Some(EgressPolicy::DenyAll),
+            ..AgentSpec::default()
+        };
+        assert_eq!(egress_for_spec(&spec, &config), &EgressPolicy::DenyAll);
+    }
+}
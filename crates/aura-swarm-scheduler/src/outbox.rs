@@ -0,0 +1,416 @@
+//! Reliable delivery of agent status updates to the gateway.
+//!
+//! The reconciler reports pod changes to the gateway's
+//! `PATCH /internal/agents/:agent_id/status` endpoint. Updates are queued in a
+//! [`StatusOutbox`] rather than sent inline, so a gateway that is restarting
+//! or briefly unreachable doesn't leave agents stuck in a stale state:
+//!
+//! - Only the latest update per agent is kept; an older one that hasn't been
+//!   delivered yet is replaced.
+//! - Failed deliveries are retried with exponential backoff until the gateway
+//!   acknowledges them. Updates the gateway rejects as invalid (a `4xx`
+//!   other than `408` or `429`) are dropped.
+//!
+//! The queue lives in memory. After a scheduler restart the pod watcher's
+//! initial listing queues the current state of every agent pod, so the
+//! gateway converges on the cluster's state without a persisted queue.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use aura_swarm_core::AgentId;
+use aura_swarm_store::AgentState;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use serde::Serialize;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+/// Delay before the first retry of a failed delivery.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Maximum number of deliveries in flight at once.
+const DELIVERY_CONCURRENCY: usize = 16;
+
+/// A status update for an agent, as sent to the gateway.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusUpdate {
+    /// The agent's new state.
+    pub status: AgentState,
+    /// Why the agent is in this state, if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// A queued update waiting to be delivered.
+#[derive(Debug)]
+struct Pending {
+    update: StatusUpdate,
+    /// Incremented on every enqueue, to detect updates replaced mid-delivery.
+    seq: u64,
+    attempts: u32,
+    /// When the agent's oldest undelivered update was queued.
+    queued_at: Instant,
+    next_attempt: Instant,
+}
+
+/// Result of one delivery attempt.
+#[derive(Debug)]
+enum Delivery {
+    Delivered,
+    /// The gateway will never accept this update.
+    Rejected(String),
+    Failed(String),
+}
+
+/// Queue depth and age, for monitoring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct OutboxStats {
+    /// Number of agents with an undelivered update.
+    pub pending: usize,
+    /// Number of those whose delivery has failed at least once.
+    pub retrying: usize,
+    /// How long the oldest undelivered update has been waiting, in seconds.
+    pub oldest_pending_seconds: Option<u64>,
+}
+
+/// A queue of agent status updates, delivered to the gateway with retries.
+///
+/// Updates are queued with [`enqueue`](Self::enqueue) and delivered by
+/// [`run`](Self::run), which should be spawned as a background task.
+#[derive(Debug)]
+pub struct StatusOutbox {
+    http_client: reqwest::Client,
+    gateway_url: String,
+    max_backoff: Duration,
+    pending: Mutex<HashMap<AgentId, Pending>>,
+    next_seq: AtomicU64,
+    wake: Notify,
+}
+
+impl StatusOutbox {
+    /// Create an empty outbox delivering to the gateway at `gateway_url`.
+    ///
+    /// Retries back off exponentially, up to `max_backoff` between attempts.
+    #[must_use]
+    pub fn new(http_client: reqwest::Client, gateway_url: String, max_backoff: Duration) -> Self {
+        Self {
+            http_client,
+            gateway_url,
+            max_backoff,
+            pending: Mutex::new(HashMap::new()),
+            next_seq: AtomicU64::new(0),
+            wake: Notify::new(),
+        }
+    }
+
+    /// Queue an update for an agent, replacing any undelivered one.
+    pub fn enqueue(&self, agent_id: AgentId, update: StatusUpdate) {
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let now = Instant::now();
+
+        let mut pending = self.pending.lock();
+        let queued_at = pending.get(&agent_id).map_or(now, |p| p.queued_at);
+        pending.insert(
+            agent_id,
+            Pending {
+                update,
+                seq,
+                attempts: 0,
+                queued_at,
+                next_attempt: now,
+            },
+        );
+        drop(pending);
+
+        self.wake.notify_one();
+    }
+
+    /// Number of agents with an undelivered update.
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        self.pending.lock().len()
+    }
+
+    /// Current queue depth and age.
+    #[must_use]
+    pub fn stats(&self) -> OutboxStats {
+        let pending = self.pending.lock();
+        let now = Instant::now();
+        OutboxStats {
+            pending: pending.len(),
+            retrying: pending.values().filter(|p| p.attempts > 0).count(),
+            oldest_pending_seconds: pending
+                .values()
+                .map(|p| now.saturating_duration_since(p.queued_at).as_secs())
+                .max(),
+        }
+    }
+
+    /// Deliver queued updates until the process exits.
+    ///
+    /// This method runs indefinitely and should be spawned as a background
+    /// task.
+    pub async fn run(&self) {
+        loop {
+            let next_attempt = self.deliver_due(Instant::now()).await;
+
+            match next_attempt {
+                Some(at) => {
+                    tokio::select! {
+                        () = self.wake.notified() => {}
+                        () = tokio::time::sleep_until(at) => {}
+                    }
+                }
+                None => self.wake.notified().await,
+            }
+        }
+    }
+
+    /// Attempt every update due by `now`, returning when the next remaining
+    /// update is due.
+    async fn deliver_due(&self, now: Instant) -> Option<Instant> {
+        let due: Vec<(AgentId, StatusUpdate, u64)> = self
+            .pending
+            .lock()
+            .iter()
+            .filter(|(_, p)| p.next_attempt <= now)
+            .map(|(agent_id, p)| (*agent_id, p.update.clone(), p.seq))
+            .collect();
+
+        futures::stream::iter(due)
+            .for_each_concurrent(DELIVERY_CONCURRENCY, |(agent_id, update, seq)| async move {
+                let delivery = self.deliver(&agent_id, &update).await;
+                self.complete(&agent_id, &update, seq, delivery);
+            })
+            .await;
+
+        self.pending.lock().values().map(|p| p.next_attempt).min()
+    }
+
+    /// Record the outcome of a delivery attempt.
+    fn complete(&self, agent_id: &AgentId, update: &StatusUpdate, seq: u64, delivery: Delivery) {
+        let mut pending = self.pending.lock();
+        let Some(entry) = pending.get_mut(agent_id) else {
+            return;
+        };
+        // A newer update was queued while this one was in flight
+        if entry.seq != seq {
+            return;
+        }
+
+        match delivery {
+            Delivery::Delivered => {
+                pending.remove(agent_id);
+                info!(
+                    agent_id = %agent_id,
+                    status = ?update.status,
+                    message = ?update.message,
+                    "Notified gateway of agent status change"
+                );
+            }
+            Delivery::Rejected(reason) => {
+                pending.remove(agent_id);
+                warn!(
+                    agent_id = %agent_id,
+                    status = ?update.status,
+                    reason,
+                    "Gateway rejected agent status change, dropping it"
+                );
+            }
+            Delivery::Failed(reason) => {
+                entry.attempts += 1;
+                let delay = backoff(entry.attempts, self.max_backoff);
+                entry.next_attempt = Instant::now() + delay;
+
+                if entry.attempts == 1 {
+                    warn!(
+                        agent_id = %agent_id,
+                        reason,
+                        retry_in = ?delay,
+                        "Failed to notify gateway of status change, will retry"
+                    );
+                } else {
+                    debug!(
+                        agent_id = %agent_id,
+                        attempts = entry.attempts,
+                        reason,
+                        retry_in = ?delay,
+                        "Failed to notify gateway of status change, will retry"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Send one update to the gateway.
+    async fn deliver(&self, agent_id: &AgentId, update: &StatusUpdate) -> Delivery {
+        let url = format!(
+            "{}/internal/agents/{}/status",
+            self.gateway_url,
+            agent_id.to_hex()
+        );
+
+        let response = match self.http_client.patch(&url).json(update).send().await {
+            Ok(response) => response,
+            Err(e) => return Delivery::Failed(format!("failed to call gateway: {e}")),
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            return Delivery::Delivered;
+        }
+
+        let error_text = response.text().await.unwrap_or_default();
+        let reason = format!("gateway returned {status}: {error_text}");
+        let retryable = status.is_server_error()
+            || status == reqwest::StatusCode::REQUEST_TIMEOUT
+            || status == reqwest::StatusCode::TOO_MANY_REQUESTS;
+        if retryable {
+            Delivery::Failed(reason)
+        } else {
+            Delivery::Rejected(reason)
+        }
+    }
+}
+
+/// Delay before retrying a delivery that has failed `attempts` times.
+fn backoff(attempts: u32, max: Duration) -> Duration {
+    let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+    INITIAL_BACKOFF.saturating_mul(factor).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn test_agent_id() -> AgentId {
+        AgentId::from_bytes([1u8; 32])
+    }
+
+    fn outbox_for(server: &MockServer) -> StatusOutbox {
+        StatusOutbox::new(reqwest::Client::new(), server.uri(), Duration::from_mins(1))
+    }
+
+    fn status_path(agent_id: &AgentId) -> String {
+        format!("/internal/agents/{}/status", agent_id.to_hex())
+    }
+
+    fn update(status: AgentState) -> StatusUpdate {
+        StatusUpdate {
+            status,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let max = Duration::from_mins(1);
+        assert_eq!(backoff(1, max), Duration::from_secs(1));
+        assert_eq!(backoff(2, max), Duration::from_secs(2));
+        assert_eq!(backoff(5, max), Duration::from_secs(16));
+        assert_eq!(backoff(7, max), max);
+        assert_eq!(backoff(u32::MAX, max), max);
+    }
+
+    #[tokio::test]
+    async fn coalesces_updates_per_agent() {
+        let server = MockServer::start().await;
+        let agent_id = test_agent_id();
+        Mock::given(method("PATCH"))
+            .and(path(status_path(&agent_id)))
+            .and(body_json(serde_json::json!({ "status": "running" })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let outbox = outbox_for(&server);
+        outbox.enqueue(agent_id, update(AgentState::Provisioning));
+        outbox.enqueue(agent_id, update(AgentState::Running));
+        assert_eq!(outbox.depth(), 1);
+
+        let next = outbox.deliver_due(Instant::now()).await;
+        assert!(next.is_none());
+        assert_eq!(outbox.depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn retries_until_acknowledged() {
+        let server = MockServer::start().await;
+        let agent_id = test_agent_id();
+        Mock::given(method("PATCH"))
+            .and(path(status_path(&agent_id)))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path(status_path(&agent_id)))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let outbox = outbox_for(&server);
+        outbox.enqueue(agent_id, update(AgentState::Running));
+
+        let next = outbox.deliver_due(Instant::now()).await;
+        assert!(next.is_some_and(|at| at > Instant::now()));
+        let stats = outbox.stats();
+        assert_eq!(stats.pending, 1);
+        assert_eq!(stats.retrying, 1);
+
+        // Not due yet
+        outbox.deliver_due(Instant::now()).await;
+        assert_eq!(outbox.depth(), 1);
+
+        let next = outbox
+            .deliver_due(Instant::now() + Duration::from_secs(2))
+            .await;
+        assert!(next.is_none());
+        assert_eq!(outbox.depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn drops_rejected_updates() {
+        let server = MockServer::start().await;
+        let agent_id = test_agent_id();
+        Mock::given(method("PATCH"))
+            .and(path(status_path(&agent_id)))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let outbox = outbox_for(&server);
+        outbox.enqueue(agent_id, update(AgentState::Stopped));
+        outbox.deliver_due(Instant::now()).await;
+
+        assert_eq!(outbox.depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn keeps_updates_queued_during_delivery() {
+        let server = MockServer::start().await;
+        let outbox = outbox_for(&server);
+        let agent_id = test_agent_id();
+
+        outbox.enqueue(agent_id, update(AgentState::Provisioning));
+        let (seq, sent) = {
+            let pending = outbox.pending.lock();
+            let entry = &pending[&agent_id];
+            (entry.seq, entry.update.clone())
+        };
+        outbox.enqueue(agent_id, update(AgentState::Running));
+
+        // The older update's acknowledgement doesn't clear the newer one
+        outbox.complete(&agent_id, &sent, seq, Delivery::Delivered);
+
+        let pending = outbox.pending.lock();
+        assert_eq!(pending[&agent_id].update.status, AgentState::Running);
+    }
+}
@@ -4,43 +4,74 @@
 //! for Aura agent pods with all necessary configuration.
 
 use aura_swarm_core::AgentId;
-use aura_swarm_store::AgentSpec;
+use aura_swarm_store::{AgentPlacement, AgentSpec, PlacementToleration, TopologySpread};
 use k8s_openapi::api::core::v1::{
     Container, ContainerPort, EnvVar, EnvVarSource, HTTPGetAction,
     PersistentVolumeClaimVolumeSource, Pod, PodSecurityContext, PodSpec, Probe,
-    ResourceRequirements, SecretKeySelector, Volume, VolumeMount,
+    ResourceRequirements, SecretKeySelector, Toleration, TopologySpreadConstraint, Volume,
+    VolumeMount,
 };
 use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
 use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
 use kube::api::ObjectMeta;
 use std::collections::BTreeMap;
 
+use crate::secret::secret_name_for_agent;
+use crate::types::StateVolumeMode;
 use crate::SchedulerConfig;
 
 /// The container port for the Aura runtime HTTP server.
-const AURA_PORT: i32 = 8080;
+pub(crate) const AURA_PORT: i32 = 8080;
+
+/// Name of the Aura runtime container in agent pods.
+pub(crate) const AURA_CONTAINER: &str = "aura";
 
 /// Build a Kubernetes pod spec for an agent.
 ///
 /// This creates a complete pod specification including:
 /// - Kata Containers runtime class for microVM isolation
+/// - Node placement from the scheduler's defaults and the agent's spec
 /// - Resource requests and limits
-/// - Environment variables for agent configuration
-/// - Volume mounts for persistent state
+/// - Environment variables for agent configuration, including the keys of
+///   the agent's own Secret listed in `secret_keys`
+/// - Volume mounts for persistent state from the claim `state_claim`
 /// - Health probes for readiness and liveness
 #[must_use]
 pub fn build_pod(
     agent_id: &AgentId,
     user_id_hex: &str,
     spec: &AgentSpec,
+    secret_keys: &[String],
+    state_claim: &str,
     config: &SchedulerConfig,
 ) -> Pod {
     let pod_name = pod_name_for_agent(agent_id);
     let agent_id_hex = agent_id.to_hex();
+    let env = build_env_vars(
+        &agent_id_hex,
+        user_id_hex,
+        spec,
+        &secret_name_for_agent(agent_id),
+        secret_keys,
+        config,
+    );
+
+    // On the shared claim each agent only sees its own subdirectory
+    let sub_path = match config.state_volume_mode {
+        StateVolumeMode::PerAgent => None,
+        StateVolumeMode::Shared => Some(agent_id_hex.clone()),
+    };
 
     Pod {
         metadata: build_metadata(&pod_name, &agent_id_hex, user_id_hex, config),
-        spec: Some(build_pod_spec(&agent_id_hex, user_id_hex, spec, config)),
+        spec: Some(build_pod_spec(
+            spec,
+            env,
+            state_claim.to_string(),
+            sub_path,
+            config,
+        )),
         ..Default::default()
     }
 }
@@ -87,8 +118,17 @@ fn build_metadata(
     }
 }
 
+/// Label selector matching the resources labelled with an agent's ID.
+#[must_use]
+pub(crate) fn agent_label_selector(agent_id: &AgentId) -> String {
+    format!(
+        "swarm.io/agent-id={}",
+        truncate_for_label(&agent_id.to_hex())
+    )
+}
+
 /// Truncate a string to fit Kubernetes label value limit (63 chars).
-fn truncate_for_label(s: &str) -> String {
+pub(crate) fn truncate_for_label(s: &str) -> String {
     if s.len() <= 63 {
         s.to_string()
     } else {
@@ -96,21 +136,36 @@ fn truncate_for_label(s: &str) -> String {
     }
 }
 
-fn build_pod_spec(
-    agent_id_hex: &str,
-    user_id_hex: &str,
+/// Build the spec of a runtime pod that mounts `state_claim`, or its
+/// `state_sub_path` subdirectory, at `/state`.
+pub(crate) fn build_pod_spec(
     spec: &AgentSpec,
+    env: Vec<EnvVar>,
+    state_claim: String,
+    state_sub_path: Option<String>,
     config: &SchedulerConfig,
 ) -> PodSpec {
     // Use agent's isolation level if specified, otherwise use scheduler default
     let isolation = spec.isolation.unwrap_or(config.default_isolation);
     // runtime_class() returns None for standard containers (uses default runtime)
     let runtime_class_name = isolation.runtime_class().map(String::from);
+    let placement = placement_for_spec(spec, config);
 
     PodSpec {
         runtime_class_name,
-        containers: vec![build_container(agent_id_hex, user_id_hex, spec, config)],
-        volumes: Some(vec![build_state_volume(config)]),
+        node_selector: (!placement.node_selector.is_empty()).then_some(placement.node_selector),
+        tolerations: (!placement.tolerations.is_empty())
+            .then(|| placement.tolerations.iter().map(build_toleration).collect()),
+        topology_spread_constraints: (!placement.topology_spread.is_empty()).then(|| {
+            placement
+                .topology_spread
+                .iter()
+                .map(build_topology_spread)
+                .collect()
+        }),
+        priority_class_name: placement.priority_class,
+        containers: vec![build_container(spec, env, state_sub_path, config)],
+        volumes: Some(vec![build_claim_volume("state", state_claim)]),
         restart_policy: Some("Always".to_string()),
         termination_grace_period_seconds: Some(30),
         security_context: Some(build_security_context()),
@@ -118,23 +173,72 @@ fn build_pod_spec(
     }
 }
 
+/// The agent's placement layered on the default for its isolation level.
+#[must_use]
+pub fn placement_for_spec(spec: &AgentSpec, config: &SchedulerConfig) -> AgentPlacement {
+    let isolation = spec.isolation.unwrap_or(config.default_isolation);
+    let defaults = config.placement_for(isolation);
+    match &spec.placement {
+        Some(placement) => defaults.merge(placement),
+        None => defaults.clone(),
+    }
+}
+
+fn build_toleration(toleration: &PlacementToleration) -> Toleration {
+    Toleration {
+        key: (!toleration.key.is_empty()).then(|| toleration.key.clone()),
+        operator: Some(toleration.operator.as_k8s_str().to_string()),
+        value: toleration.value.clone(),
+        effect: toleration
+            .effect
+            .map(|effect| effect.as_k8s_str().to_string()),
+        toleration_seconds: None,
+    }
+}
+
+/// Spread agent pods, counting only other agent pods.
+fn build_topology_spread(spread: &TopologySpread) -> TopologySpreadConstraint {
+    let mut labels = BTreeMap::new();
+    labels.insert("app".to_string(), "swarm-agent".to_string());
+
+    TopologySpreadConstraint {
+        topology_key: spread.topology_key.clone(),
+        max_skew: i32::try_from(spread.max_skew).unwrap_or(i32::MAX),
+        when_unsatisfiable: if spread.required {
+            "DoNotSchedule".to_string()
+        } else {
+            "ScheduleAnyway".to_string()
+        },
+        label_selector: Some(LabelSelector {
+            match_labels: Some(labels),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
 fn build_container(
-    agent_id_hex: &str,
-    user_id_hex: &str,
     spec: &AgentSpec,
+    env: Vec<EnvVar>,
+    state_sub_path: Option<String>,
     config: &SchedulerConfig,
 ) -> Container {
     Container {
-        name: "aura".to_string(),
-        image: Some(config.image.clone()),
+        name: AURA_CONTAINER.to_string(),
+        image: Some(config.image_for(&spec.runtime_version)),
         ports: Some(vec![ContainerPort {
             container_port: AURA_PORT,
             name: Some("http".to_string()),
             ..Default::default()
         }]),
-        env: Some(build_env_vars(agent_id_hex, user_id_hex, config)),
+        env: Some(env),
         resources: Some(build_resources(spec)),
-        volume_mounts: Some(vec![build_state_mount(agent_id_hex)]),
+        volume_mounts: Some(vec![VolumeMount {
+            name: "state".to_string(),
+            mount_path: "/state".to_string(),
+            sub_path: state_sub_path,
+            ..Default::default()
+        }]),
         readiness_probe: Some(build_readiness_probe()),
         liveness_probe: Some(build_liveness_probe()),
         ..Default::default()
@@ -144,8 +248,24 @@ fn build_container(
 /// Name of the Kubernetes secret containing LLM API keys.
 const LLM_SECRETS_NAME: &str = "aura-swarm-secrets";
 
-fn build_env_vars(agent_id_hex: &str, user_id_hex: &str, config: &SchedulerConfig) -> Vec<EnvVar> {
-    vec![
+/// LLM API keys injected from [`LLM_SECRETS_NAME`] unless the agent has its own.
+const LLM_API_KEYS: [&str; 2] = ["ANTHROPIC_API_KEY", "OPENAI_API_KEY"];
+
+/// Environment variable carrying the agent's initial system prompt.
+const SYSTEM_PROMPT_ENV: &str = "AURA_SYSTEM_PROMPT";
+
+/// Environment variable that starts the runtime without an agent identity.
+const STANDBY_ENV: &str = "AURA_STANDBY";
+
+fn build_env_vars(
+    agent_id_hex: &str,
+    user_id_hex: &str,
+    spec: &AgentSpec,
+    agent_secret_name: &str,
+    secret_keys: &[String],
+    config: &SchedulerConfig,
+) -> Vec<EnvVar> {
+    let mut env = vec![
         // Agent identity
         EnvVar {
             name: "AGENT_ID".to_string(),
@@ -157,7 +277,109 @@ fn build_env_vars(agent_id_hex: &str, user_id_hex: &str, config: &SchedulerConfi
             value: Some(user_id_hex.to_string()),
             ..Default::default()
         },
-        // Runtime configuration
+    ];
+    env.extend(build_runtime_env_vars(config));
+
+    // The agent's own secrets, which may replace the shared LLM API keys
+    for key in secret_keys {
+        if key == SYSTEM_PROMPT_ENV || env.iter().any(|e| &e.name == key) {
+            continue;
+        }
+        env.push(build_secret_env_var(key, agent_secret_name, key));
+    }
+
+    // LLM API keys (injected from the shared Kubernetes secret)
+    for key in LLM_API_KEYS {
+        if !env.iter().any(|e| e.name == key) {
+            env.push(build_secret_env_var(key, LLM_SECRETS_NAME, key));
+        }
+    }
+
+    if let Some(prompt) = &spec.system_prompt {
+        env.push(EnvVar {
+            name: SYSTEM_PROMPT_ENV.to_string(),
+            value: Some(prompt.clone()),
+            ..Default::default()
+        });
+    }
+
+    // User-supplied variables never override the ones set by the scheduler
+    for (name, value) in &spec.env {
+        if env.iter().any(|e| &e.name == name) {
+            continue;
+        }
+        env.push(EnvVar {
+            name: name.clone(),
+            value: Some(value.clone()),
+            ..Default::default()
+        });
+    }
+
+    env
+}
+
+/// Environment of a warm pod's runtime, which waits in standby until it is
+/// activated for an agent.
+pub(crate) fn build_standby_env_vars(config: &SchedulerConfig) -> Vec<EnvVar> {
+    let mut env = build_runtime_env_vars(config);
+    env.push(EnvVar {
+        name: STANDBY_ENV.to_string(),
+        value: Some("true".to_string()),
+        ..Default::default()
+    });
+    for key in LLM_API_KEYS {
+        env.push(build_secret_env_var(key, LLM_SECRETS_NAME, key));
+    }
+    env
+}
+
+/// The environment an agent's pod would get, resolved to plain values for
+/// runtimes that aren't started from a pod spec: warm pods being activated
+/// and local processes.
+///
+/// The agent's secret keys are resolved from `secrets`, which only local
+/// processes are given: agents with secrets are never started on warm pods.
+/// References to the shared LLM secret are left out, since those runtimes
+/// already have them.
+pub(crate) fn build_activation_env(
+    agent_id: &AgentId,
+    user_id_hex: &str,
+    spec: &AgentSpec,
+    secrets: &BTreeMap<String, String>,
+    config: &SchedulerConfig,
+) -> BTreeMap<String, String> {
+    let agent_secret_name = secret_name_for_agent(agent_id);
+    let secret_keys: Vec<String> = secrets.keys().cloned().collect();
+    let env = build_env_vars(
+        &agent_id.to_hex(),
+        user_id_hex,
+        spec,
+        &agent_secret_name,
+        &secret_keys,
+        config,
+    );
+
+    env.into_iter()
+        .filter_map(|var| {
+            let value = match (var.value, var.value_from) {
+                (Some(value), _) => value,
+                (None, Some(source)) => {
+                    let selector = source.secret_key_ref?;
+                    if selector.name != agent_secret_name {
+                        return None;
+                    }
+                    secrets.get(&selector.key)?.clone()
+                }
+                (None, None) => return None,
+            };
+            Some((var.name, value))
+        })
+        .collect()
+}
+
+/// Runtime configuration shared by agent and warm pods.
+fn build_runtime_env_vars(config: &SchedulerConfig) -> Vec<EnvVar> {
+    vec![
         EnvVar {
             name: "STATE_DIR".to_string(),
             value: Some("/state".to_string()),
@@ -173,9 +395,6 @@ fn build_env_vars(agent_id_hex: &str, user_id_hex: &str, config: &SchedulerConfi
             value: Some(config.control_plane_url.clone()),
             ..Default::default()
         },
-        // LLM API keys (injected from Kubernetes secret)
-        build_secret_env_var("ANTHROPIC_API_KEY", LLM_SECRETS_NAME, "ANTHROPIC_API_KEY"),
-        build_secret_env_var("OPENAI_API_KEY", LLM_SECRETS_NAME, "OPENAI_API_KEY"),
     ]
 }
 
@@ -214,26 +433,18 @@ fn build_resources(spec: &AgentSpec) -> ResourceRequirements {
     }
 }
 
-fn build_state_volume(config: &SchedulerConfig) -> Volume {
+/// Build a volume named `name` backed by the claim `claim_name`.
+pub(crate) fn build_claim_volume(name: &str, claim_name: String) -> Volume {
     Volume {
-        name: "state".to_string(),
+        name: name.to_string(),
         persistent_volume_claim: Some(PersistentVolumeClaimVolumeSource {
-            claim_name: config.state_pvc_name.clone(),
+            claim_name,
             ..Default::default()
         }),
         ..Default::default()
     }
 }
 
-fn build_state_mount(agent_id_hex: &str) -> VolumeMount {
-    VolumeMount {
-        name: "state".to_string(),
-        mount_path: "/state".to_string(),
-        sub_path: Some(agent_id_hex.to_string()),
-        ..Default::default()
-    }
-}
-
 fn build_readiness_probe() -> Probe {
     Probe {
         http_get: Some(HTTPGetAction {
@@ -264,7 +475,7 @@ fn build_liveness_probe() -> Probe {
     }
 }
 
-fn build_security_context() -> PodSecurityContext {
+pub(crate) fn build_security_context() -> PodSecurityContext {
     PodSecurityContext {
         run_as_non_root: Some(true),
         run_as_user: Some(1000),
@@ -276,8 +487,9 @@ fn build_security_context() -> PodSecurityContext {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::volume::state_claim_for_agent;
     use aura_swarm_core::UserId;
-    use aura_swarm_store::IsolationLevel;
+    use aura_swarm_store::{IsolationLevel, TaintEffect, TolerationOperator};
 
     fn test_agent_id() -> AgentId {
         let user_id = UserId::from_bytes([1u8; 32]);
@@ -289,7 +501,7 @@ mod tests {
             cpu_millicores: 500,
             memory_mb: 512,
             runtime_version: "latest".to_string(),
-            isolation: None, // Uses scheduler default
+            ..Default::default()
         }
     }
 
@@ -309,7 +521,14 @@ mod tests {
         let spec = test_spec();
         let config = SchedulerConfig::default();
 
-        let pod = build_pod(&agent_id, &user_id.to_hex(), &spec, &config);
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &spec,
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
 
         // Metadata
         let meta = &pod.metadata;
@@ -336,6 +555,16 @@ mod tests {
         assert!(container.readiness_probe.is_some());
         assert!(container.liveness_probe.is_some());
 
+        // State lives on the agent's own claim
+        let volume = &pod_spec.volumes.as_ref().unwrap()[0];
+        assert_eq!(
+            volume.persistent_volume_claim.as_ref().unwrap().claim_name,
+            crate::volume::pvc_name_for_agent(&agent_id)
+        );
+        let mount = &container.volume_mounts.as_ref().unwrap()[0];
+        assert_eq!(mount.mount_path, "/state");
+        assert!(mount.sub_path.is_none());
+
         // Environment variables
         let env = container.env.as_ref().unwrap();
         let env_names: Vec<_> = env.iter().map(|e| e.name.as_str()).collect();
@@ -348,6 +577,32 @@ mod tests {
         assert!(env_names.contains(&"OPENAI_API_KEY"));
     }
 
+    #[test]
+    fn build_pod_runs_the_spec_runtime_version() {
+        let agent_id = test_agent_id();
+        let config = SchedulerConfig::default();
+        let image = |spec: &AgentSpec| {
+            let pod = build_pod(
+                &agent_id,
+                &UserId::from_bytes([1u8; 32]).to_hex(),
+                spec,
+                &[],
+                &state_claim_for_agent(&agent_id, &config),
+                &config,
+            );
+            pod.spec.unwrap().containers[0].image.clone().unwrap()
+        };
+
+        assert_eq!(image(&test_spec()), config.image);
+
+        // After a rollout to v2 the agent's pod runs the v2 image
+        let rolled_out = AgentSpec {
+            runtime_version: "v2".to_string(),
+            ..test_spec()
+        };
+        assert_eq!(image(&rolled_out), "ghcr.io/cypher-asi/aura-runtime:v2");
+    }
+
     #[test]
     fn build_pod_uses_spec_resources() {
         let agent_id = test_agent_id();
@@ -356,11 +611,18 @@ mod tests {
             cpu_millicores: 1000,
             memory_mb: 2048,
             runtime_version: "v1.0".to_string(),
-            isolation: None,
+            ..Default::default()
         };
         let config = SchedulerConfig::default();
 
-        let pod = build_pod(&agent_id, &user_id.to_hex(), &spec, &config);
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &spec,
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
         let container = &pod.spec.as_ref().unwrap().containers[0];
         let resources = container.resources.as_ref().unwrap();
 
@@ -376,6 +638,34 @@ mod tests {
         assert_eq!(limits.get("memory"), Some(&Quantity("2048Mi".to_string())));
     }
 
+    #[test]
+    fn build_pod_mounts_subdirectory_of_shared_claim() {
+        let agent_id = test_agent_id();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let config = SchedulerConfig {
+            state_volume_mode: StateVolumeMode::Shared,
+            ..SchedulerConfig::default()
+        };
+
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &test_spec(),
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
+        let pod_spec = pod.spec.as_ref().unwrap();
+
+        let volume = &pod_spec.volumes.as_ref().unwrap()[0];
+        assert_eq!(
+            volume.persistent_volume_claim.as_ref().unwrap().claim_name,
+            "swarm-agent-state"
+        );
+        let mount = &pod_spec.containers[0].volume_mounts.as_ref().unwrap()[0];
+        assert_eq!(mount.sub_path, Some(agent_id.to_hex()));
+    }
+
     #[test]
     fn build_pod_uses_default_isolation_when_none_specified() {
         let agent_id = test_agent_id();
@@ -386,7 +676,14 @@ mod tests {
         };
         let config = SchedulerConfig::default();
 
-        let pod = build_pod(&agent_id, &user_id.to_hex(), &spec, &config);
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &spec,
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
         let pod_spec = pod.spec.as_ref().unwrap();
 
         assert_eq!(pod_spec.runtime_class_name.as_deref(), Some("kata-fc"));
@@ -402,7 +699,14 @@ mod tests {
         };
         let config = SchedulerConfig::default(); // Default is MicroVM
 
-        let pod = build_pod(&agent_id, &user_id.to_hex(), &spec, &config);
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &spec,
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
         let pod_spec = pod.spec.as_ref().unwrap();
 
         // Container isolation uses default runtime (no RuntimeClass specified)
@@ -420,13 +724,130 @@ mod tests {
         let mut config = SchedulerConfig::default();
         config.default_isolation = IsolationLevel::Container; // Change default
 
-        let pod = build_pod(&agent_id, &user_id.to_hex(), &spec, &config);
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &spec,
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
         let pod_spec = pod.spec.as_ref().unwrap();
 
         // Container isolation uses default runtime (no RuntimeClass specified)
         assert_eq!(pod_spec.runtime_class_name, None);
     }
 
+    #[test]
+    fn build_pod_applies_isolation_placement_defaults() {
+        let agent_id = test_agent_id();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let mut config = SchedulerConfig::default();
+        config.microvm_placement.node_selector.insert(
+            "katacontainers.io/kata-runtime".to_string(),
+            "true".to_string(),
+        );
+
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &test_spec(),
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
+        let pod_spec = pod.spec.as_ref().unwrap();
+        assert_eq!(
+            pod_spec.node_selector.as_ref().unwrap()["katacontainers.io/kata-runtime"],
+            "true"
+        );
+        let spread = &pod_spec.topology_spread_constraints.as_ref().unwrap()[0];
+        assert_eq!(spread.topology_key, "topology.kubernetes.io/zone");
+        assert_eq!(spread.max_skew, 1);
+        assert_eq!(spread.when_unsatisfiable, "ScheduleAnyway");
+        assert!(pod_spec.tolerations.is_none());
+        assert!(pod_spec.priority_class_name.is_none());
+
+        // Container agents get the container defaults instead
+        let spec = AgentSpec {
+            isolation: Some(IsolationLevel::Container),
+            ..test_spec()
+        };
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &spec,
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
+        assert!(pod.spec.as_ref().unwrap().node_selector.is_none());
+    }
+
+    #[test]
+    fn build_pod_layers_agent_placement_on_defaults() {
+        let agent_id = test_agent_id();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let mut config = SchedulerConfig::default();
+        config
+            .microvm_placement
+            .node_selector
+            .insert("pool".to_string(), "agents".to_string());
+
+        let mut placement = AgentPlacement::default();
+        placement
+            .node_selector
+            .insert("pool".to_string(), "gpu".to_string());
+        placement.tolerations.push(PlacementToleration {
+            key: "nvidia.com/gpu".to_string(),
+            operator: TolerationOperator::Exists,
+            value: None,
+            effect: Some(TaintEffect::NoSchedule),
+        });
+        placement.topology_spread.push(TopologySpread {
+            topology_key: "kubernetes.io/hostname".to_string(),
+            max_skew: 2,
+            required: true,
+        });
+        placement.priority_class = Some("agents-high".to_string());
+        let spec = AgentSpec {
+            placement: Some(placement),
+            ..test_spec()
+        };
+
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &spec,
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
+        let pod_spec = pod.spec.as_ref().unwrap();
+
+        assert_eq!(pod_spec.node_selector.as_ref().unwrap()["pool"], "gpu");
+        let toleration = &pod_spec.tolerations.as_ref().unwrap()[0];
+        assert_eq!(toleration.key.as_deref(), Some("nvidia.com/gpu"));
+        assert_eq!(toleration.operator.as_deref(), Some("Exists"));
+        assert_eq!(toleration.effect.as_deref(), Some("NoSchedule"));
+        let spreads = pod_spec.topology_spread_constraints.as_ref().unwrap();
+        assert_eq!(spreads.len(), 1);
+        assert_eq!(spreads[0].topology_key, "kubernetes.io/hostname");
+        assert_eq!(spreads[0].max_skew, 2);
+        assert_eq!(spreads[0].when_unsatisfiable, "DoNotSchedule");
+        assert_eq!(
+            spreads[0]
+                .label_selector
+                .as_ref()
+                .unwrap()
+                .match_labels
+                .as_ref()
+                .unwrap()["app"],
+            "swarm-agent"
+        );
+        assert_eq!(pod_spec.priority_class_name.as_deref(), Some("agents-high"));
+    }
+
     #[test]
     fn build_pod_injects_llm_api_keys_from_secret() {
         let agent_id = test_agent_id();
@@ -434,7 +855,14 @@ mod tests {
         let spec = test_spec();
         let config = SchedulerConfig::default();
 
-        let pod = build_pod(&agent_id, &user_id.to_hex(), &spec, &config);
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &spec,
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
         let container = &pod.spec.as_ref().unwrap().containers[0];
         let env = container.env.as_ref().unwrap();
 
@@ -457,4 +885,121 @@ mod tests {
         assert_eq!(secret_ref.name, "aura-swarm-secrets");
         assert_eq!(secret_ref.key, "OPENAI_API_KEY");
     }
+
+    #[test]
+    fn build_pod_references_agent_secrets() {
+        let agent_id = test_agent_id();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let spec = test_spec();
+        let config = SchedulerConfig::default();
+        let keys = vec![
+            "ANTHROPIC_API_KEY".to_string(),
+            "GITHUB_TOKEN".to_string(),
+            "AGENT_ID".to_string(),
+        ];
+
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &spec,
+            &keys,
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
+        let container = &pod.spec.as_ref().unwrap().containers[0];
+        let env = container.env.as_ref().unwrap();
+
+        let secret_of = |name: &str| {
+            let refs: Vec<_> = env
+                .iter()
+                .filter(|e| e.name == name)
+                .filter_map(|e| e.value_from.as_ref()?.secret_key_ref.as_ref())
+                .map(|r| (r.name.clone(), r.key.clone()))
+                .collect();
+            assert_eq!(refs.len(), 1, "{name} should be injected once");
+            refs[0].clone()
+        };
+        let agent_secret = secret_name_for_agent(&agent_id);
+
+        // The agent's own key replaces the shared one
+        assert_eq!(
+            secret_of("ANTHROPIC_API_KEY"),
+            (agent_secret.clone(), "ANTHROPIC_API_KEY".to_string())
+        );
+        assert_eq!(
+            secret_of("GITHUB_TOKEN"),
+            (agent_secret, "GITHUB_TOKEN".to_string())
+        );
+        assert_eq!(
+            secret_of("OPENAI_API_KEY"),
+            (
+                "aura-swarm-secrets".to_string(),
+                "OPENAI_API_KEY".to_string()
+            )
+        );
+
+        // Reserved variables cannot be overridden by secrets
+        let agent_env: Vec<_> = env.iter().filter(|e| e.name == "AGENT_ID").collect();
+        assert_eq!(agent_env.len(), 1);
+        assert_eq!(agent_env[0].value, Some(agent_id.to_hex()));
+    }
+
+    #[test]
+    fn build_pod_injects_spec_env_and_system_prompt() {
+        let agent_id = test_agent_id();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let mut spec = test_spec();
+        spec.system_prompt = Some("You are helpful.".to_string());
+        spec.env
+            .insert("LOG_LEVEL".to_string(), "debug".to_string());
+        spec.env
+            .insert("AGENT_ID".to_string(), "spoofed".to_string());
+        let config = SchedulerConfig::default();
+
+        let pod = build_pod(
+            &agent_id,
+            &user_id.to_hex(),
+            &spec,
+            &[],
+            &state_claim_for_agent(&agent_id, &config),
+            &config,
+        );
+        let container = &pod.spec.as_ref().unwrap().containers[0];
+        let env = container.env.as_ref().unwrap();
+
+        let value_of = |name: &str| {
+            env.iter()
+                .find(|e| e.name == name)
+                .and_then(|e| e.value.clone())
+        };
+        assert_eq!(value_of("LOG_LEVEL").as_deref(), Some("debug"));
+        assert_eq!(
+            value_of("AURA_SYSTEM_PROMPT").as_deref(),
+            Some("You are helpful.")
+        );
+
+        // Reserved variables cannot be overridden
+        assert_eq!(value_of("AGENT_ID"), Some(agent_id.to_hex()));
+        assert_eq!(env.iter().filter(|e| e.name == "AGENT_ID").count(), 1);
+    }
+
+    #[test]
+    fn build_activation_env_resolves_agent_secrets() {
+        let agent_id = test_agent_id();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let mut spec = test_spec();
+        spec.env
+            .insert("LOG_LEVEL".to_string(), "debug".to_string());
+        let config = SchedulerConfig::default();
+        let secrets = BTreeMap::from([("GITHUB_TOKEN".to_string(), "ghp_123".to_string())]);
+
+        let env = build_activation_env(&agent_id, &user_id.to_hex(), &spec, &secrets, &config);
+
+        assert_eq!(env.get("AGENT_ID"), Some(&agent_id.to_hex()));
+        assert_eq!(env.get("LOG_LEVEL").map(String::as_str), Some("debug"));
+        assert_eq!(env.get("GITHUB_TOKEN").map(String::as_str), Some("ghp_123"));
+        // The warm pod already references the shared LLM keys
+        assert!(!env.contains_key("ANTHROPIC_API_KEY"));
+        assert!(!env.contains_key("OPENAI_API_KEY"));
+    }
 }
@@ -0,0 +1,510 @@
+//! Warm pools of pre-provisioned runtime pods.
+//!
+//! Cold-starting an agent pod (pulling the image, booting a microVM and
+//! waiting for the runtime to become ready) is slow. The scheduler therefore
+//! keeps, for each configured [`WarmPoolConfig`], a number of generic runtime
+//! pods running in standby, without an agent identity. Each warm pod has its
+//! own state claim, since a volume cannot be added to a running pod.
+//!
+//! When an agent that has no state and no secrets yet is scheduled with a
+//! spec matching a pool, one of its ready pods is claimed instead of creating
+//! a new one:
+//! 1. The pod is marked `claimed`, guarded by its resource version so that
+//!    only one scheduler can claim it.
+//! 2. Its state claim is labelled as the agent's, and from then on is found
+//!    by that label rather than by name.
+//! 3. The runtime is given the agent's identity and plain environment, as
+//!    specified by the runtime contract v0.2.0 (`docs/spec/v0.2.0`). The
+//!    runtime persists it on the state volume, so a restarted container
+//!    resumes as the agent:
+//!
+//!    ```text
+//!    POST /activate
+//!    {"agent_id": "<hex>", "user_id": "<hex>", "env": {"AURA_SYSTEM_PROMPT": "..."}}
+//!    ```
+//!
+//! 4. The pod is relabelled as the agent's pod, which the reconciler then
+//!    reports like any other.
+//!
+//! Agents with secrets always get a new pod, which reads them from the
+//! agent's Secret, so secrets are never sent to a runtime over the network.
+//!
+//! If the pool is empty or any step fails, the claimed pod and claim are
+//! deleted and the agent is scheduled the normal way. Unclaimed pods older
+//! than `warm_pod_max_age_seconds` are replaced, so pools pick up new runtime
+//! images and node placement.
+
+use std::collections::BTreeMap;
+
+use aura_swarm_core::AgentId;
+use aura_swarm_store::AgentSpec;
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::core::v1::{
+    PersistentVolumeClaim, PersistentVolumeClaimSpec, Pod, VolumeResourceRequirements,
+};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use kube::api::ObjectMeta;
+use serde::Serialize;
+
+use crate::pod::{build_pod_spec, build_standby_env_vars, truncate_for_label};
+use crate::types::{SchedulerConfig, StateVolumeMode, WarmPoolConfig};
+use crate::volume::storage_gb_for_spec;
+
+/// Name prefix of warm pods.
+pub const WARM_POD_PREFIX: &str = "warm-";
+
+/// Label naming the pool a warm pod or its claim belongs to.
+pub const POOL_LABEL: &str = "swarm.io/pool";
+
+/// Label holding whether a warm pod is still [`WARM`] or has been [`CLAIMED`].
+pub const POOL_STATE_LABEL: &str = "swarm.io/pool-state";
+
+/// [`POOL_STATE_LABEL`] value of pods that can be claimed.
+pub const WARM: &str = "warm";
+
+/// [`POOL_STATE_LABEL`] value of pods being bound to an agent.
+pub const CLAIMED: &str = "claimed";
+
+/// Annotation recording when a warm pod was claimed.
+const CLAIMED_AT_ANNOTATION: &str = "swarm.io/claimed-at";
+
+/// How long a claimed pod may go without being bound before it is deleted.
+const CLAIM_TIMEOUT_SECONDS: i64 = 5 * 60;
+
+/// Body of the runtime's `POST /activate` request.
+#[derive(Debug, Clone, Serialize)]
+pub struct ActivationRequest {
+    /// The agent's ID as hex.
+    pub agent_id: String,
+    /// The owner's user ID as hex.
+    pub user_id: String,
+    /// Plain environment variables the agent's own pod would have had.
+    pub env: BTreeMap<String, String>,
+}
+
+/// The warm pool an agent can be started from, if any.
+///
+/// Only agents with exactly the pool's isolation level and resources, the
+/// default runtime image, node placement and storage qualify.
+#[must_use]
+pub fn pool_for_spec<'a>(
+    spec: &AgentSpec,
+    config: &'a SchedulerConfig,
+) -> Option<&'a WarmPoolConfig> {
+    if config.state_volume_mode != StateVolumeMode::PerAgent
+        || spec.placement.is_some()
+        || config.image_for(&spec.runtime_version) != config.image
+        || storage_gb_for_spec(spec, config) != config.default_storage_gb
+        || spec
+            .storage_class
+            .as_ref()
+            .is_some_and(|class| Some(class) != config.default_storage_class.as_ref())
+    {
+        return None;
+    }
+
+    let isolation = spec.isolation.unwrap_or(config.default_isolation);
+    config.warm_pools.iter().find(|pool| {
+        pool.size > 0
+            && pool.isolation == isolation
+            && pool.cpu_millicores == spec.cpu_millicores
+            && pool.memory_mb == spec.memory_mb
+    })
+}
+
+/// Generate the name of a warm pod from a random suffix.
+#[must_use]
+pub fn warm_pod_name(suffix: &str) -> String {
+    format!("{WARM_POD_PREFIX}{suffix}")
+}
+
+/// The name of a warm pod's state claim.
+#[must_use]
+pub fn warm_claim_name(pod_name: &str) -> String {
+    format!("{pod_name}-state")
+}
+
+/// Label selector for a pool's unclaimed pods.
+#[must_use]
+pub fn warm_selector(pool: &WarmPoolConfig) -> String {
+    format!("{POOL_LABEL}={},{POOL_STATE_LABEL}={WARM}", pool.key())
+}
+
+/// Label selector for a pool's pods that were claimed but not yet bound.
+#[must_use]
+pub fn claimed_selector(pool: &WarmPoolConfig) -> String {
+    format!("{POOL_LABEL}={},{POOL_STATE_LABEL}={CLAIMED}", pool.key())
+}
+
+/// Build an unclaimed runtime pod for a pool, mounting its own state claim.
+#[must_use]
+pub fn build_warm_pod(pool: &WarmPoolConfig, pod_name: &str, config: &SchedulerConfig) -> Pod {
+    let spec = AgentSpec {
+        cpu_millicores: pool.cpu_millicores,
+        memory_mb: pool.memory_mb,
+        isolation: Some(pool.isolation),
+        ..AgentSpec::default()
+    };
+
+    let mut labels = pool_labels(pool);
+    labels.insert("app".to_string(), "swarm-agent".to_string());
+    labels.insert(POOL_STATE_LABEL.to_string(), WARM.to_string());
+
+    Pod {
+        metadata: ObjectMeta {
+            name: Some(pod_name.to_string()),
+            namespace: Some(config.namespace.clone()),
+            labels: Some(labels),
+            ..Default::default()
+        },
+        spec: Some(build_pod_spec(
+            &spec,
+            build_standby_env_vars(config),
+            warm_claim_name(pod_name),
+            None,
+            config,
+        )),
+        ..Default::default()
+    }
+}
+
+/// Build the state claim of a warm pod, with the default size and class.
+#[must_use]
+pub fn build_warm_claim(
+    pool: &WarmPoolConfig,
+    pod_name: &str,
+    config: &SchedulerConfig,
+) -> PersistentVolumeClaim {
+    let mut labels = pool_labels(pool);
+    labels.insert("app".to_string(), "swarm-agent-state".to_string());
+
+    let mut requests = BTreeMap::new();
+    requests.insert(
+        "storage".to_string(),
+        Quantity(format!("{}Gi", config.default_storage_gb)),
+    );
+
+    PersistentVolumeClaim {
+        metadata: ObjectMeta {
+            name: Some(warm_claim_name(pod_name)),
+            namespace: Some(config.namespace.clone()),
+            labels: Some(labels),
+            ..Default::default()
+        },
+        spec: Some(PersistentVolumeClaimSpec {
+            access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+            storage_class_name: config.default_storage_class.clone(),
+            resources: Some(VolumeResourceRequirements {
+                requests: Some(requests),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// Labels shared by a pool's pods and their claims.
+fn pool_labels(pool: &WarmPoolConfig) -> BTreeMap<String, String> {
+    let mut labels = BTreeMap::new();
+    labels.insert(POOL_LABEL.to_string(), pool.key());
+    labels
+}
+
+/// Merge patch marking a warm pod as claimed.
+///
+/// The resource version makes the patch fail with a conflict if another
+/// scheduler changed the pod first.
+#[must_use]
+pub fn claim_patch(resource_version: &str, now: DateTime<Utc>) -> serde_json::Value {
+    serde_json::json!({
+        "metadata": {
+            "resourceVersion": resource_version,
+            "labels": { POOL_STATE_LABEL: CLAIMED },
+            "annotations": { CLAIMED_AT_ANNOTATION: now.to_rfc3339() },
+        }
+    })
+}
+
+/// Merge patch making a claimed pod the agent's pod.
+///
+/// The pool labels are removed, so the pool no longer counts the pod.
+#[must_use]
+pub fn bind_pod_patch(
+    agent_id: &AgentId,
+    user_id_hex: &str,
+    now: DateTime<Utc>,
+) -> serde_json::Value {
+    let agent_id_hex = agent_id.to_hex();
+    serde_json::json!({
+        "metadata": {
+            "labels": {
+                "swarm.io/agent-id": truncate_for_label(&agent_id_hex),
+                "swarm.io/user-id": truncate_for_label(user_id_hex),
+                POOL_LABEL: null,
+                POOL_STATE_LABEL: null,
+            },
+            "annotations": {
+                "swarm.io/created-at": now.to_rfc3339(),
+                "swarm.io/agent-id-full": agent_id_hex,
+                "swarm.io/user-id-full": user_id_hex,
+                CLAIMED_AT_ANNOTATION: null,
+            },
+        }
+    })
+}
+
+/// Merge patch making a warm pod's state claim the agent's.
+#[must_use]
+pub fn bind_claim_patch(agent_id: &AgentId) -> serde_json::Value {
+    let agent_id_hex = agent_id.to_hex();
+    serde_json::json!({
+        "metadata": {
+            "labels": {
+                "swarm.io/agent-id": truncate_for_label(&agent_id_hex),
+                POOL_LABEL: null,
+            },
+            "annotations": { "swarm.io/agent-id-full": agent_id_hex },
+        }
+    })
+}
+
+/// Whether an unclaimed warm pod is ready to be claimed.
+#[must_use]
+pub fn is_claimable(pod: &Pod) -> bool {
+    pod.metadata.deletion_timestamp.is_none()
+        && pod.status.as_ref().is_some_and(|status| {
+            status.phase.as_deref() == Some("Running")
+                && status.pod_ip.is_some()
+                && status.conditions.as_ref().is_some_and(|conditions| {
+                    conditions
+                        .iter()
+                        .any(|c| c.type_ == "Ready" && c.status == "True")
+                })
+        })
+}
+
+/// Whether an unclaimed warm pod should be replaced: it failed, finished or
+/// is older than the configured maximum age.
+#[must_use]
+pub fn needs_recycling(pod: &Pod, now: DateTime<Utc>, config: &SchedulerConfig) -> bool {
+    let phase = pod.status.as_ref().and_then(|s| s.phase.as_deref());
+    if matches!(phase, Some("Failed" | "Succeeded")) {
+        return true;
+    }
+
+    let max_age = i64::try_from(config.warm_pod_max_age_seconds).unwrap_or(i64::MAX);
+    pod.metadata
+        .creation_timestamp
+        .as_ref()
+        .is_some_and(|created| (now - created.0).num_seconds() >= max_age)
+}
+
+/// Whether a claimed pod was abandoned before it could be bound, for
+/// example because the scheduler restarted mid-claim.
+#[must_use]
+pub fn is_abandoned_claim(pod: &Pod, now: DateTime<Utc>) -> bool {
+    pod.metadata
+        .annotations
+        .as_ref()
+        .and_then(|a| a.get(CLAIMED_AT_ANNOTATION))
+        .and_then(|at| DateTime::parse_from_rfc3339(at).ok())
+        .is_none_or(|at| (now - at.with_timezone(&Utc)).num_seconds() >= CLAIM_TIMEOUT_SECONDS)
+}
+
+/// Order claimable pods oldest first, so pods are used before they expire.
+pub fn sort_for_claiming(pods: &mut [Pod]) {
+    pods.sort_by_key(|pod| pod.metadata.creation_timestamp.as_ref().map(|t| t.0));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::UserId;
+    use aura_swarm_store::{AgentPlacement, IsolationLevel};
+    use k8s_openapi::api::core::v1::{PodCondition, PodStatus};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::Time;
+
+    fn pool() -> WarmPoolConfig {
+        WarmPoolConfig {
+            isolation: IsolationLevel::MicroVM,
+            cpu_millicores: 500,
+            memory_mb: 512,
+            size: 2,
+        }
+    }
+
+    fn pooled_config() -> SchedulerConfig {
+        SchedulerConfig {
+            warm_pools: vec![pool()],
+            ..SchedulerConfig::default()
+        }
+    }
+
+    fn pooled_spec() -> AgentSpec {
+        AgentSpec {
+            cpu_millicores: 500,
+            memory_mb: 512,
+            ..AgentSpec::default()
+        }
+    }
+
+    fn ready_pod(created: DateTime<Utc>) -> Pod {
+        let mut pod = build_warm_pod(&pool(), "warm-abc", &pooled_config());
+        pod.metadata.creation_timestamp = Some(Time(created));
+        pod.status = Some(PodStatus {
+            phase: Some("Running".to_string()),
+            pod_ip: Some("10.0.0.7".to_string()),
+            conditions: Some(vec![PodCondition {
+                type_: "Ready".to_string(),
+                status: "True".to_string(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        });
+        pod
+    }
+
+    #[test]
+    fn only_matching_specs_use_a_pool() {
+        let config = pooled_config();
+        assert_eq!(pool_for_spec(&pooled_spec(), &config), Some(&pool()));
+
+        let bigger = AgentSpec {
+            memory_mb: 1024,
+            ..pooled_spec()
+        };
+        assert_eq!(pool_for_spec(&bigger, &config), None);
+
+        let container = AgentSpec {
+            isolation: Some(IsolationLevel::Container),
+            ..pooled_spec()
+        };
+        assert_eq!(pool_for_spec(&container, &config), None);
+
+        let placed = AgentSpec {
+            placement: Some(AgentPlacement::default()),
+            ..pooled_spec()
+        };
+        assert_eq!(pool_for_spec(&placed, &config), None);
+
+        let pinned = AgentSpec {
+            runtime_version: "v2".to_string(),
+            ..pooled_spec()
+        };
+        assert_eq!(pool_for_spec(&pinned, &config), None);
+
+        let large_disk = AgentSpec {
+            storage_gb: Some(50),
+            ..pooled_spec()
+        };
+        assert_eq!(pool_for_spec(&large_disk, &config), None);
+
+        let shared = SchedulerConfig {
+            state_volume_mode: StateVolumeMode::Shared,
+            ..pooled_config()
+        };
+        assert_eq!(pool_for_spec(&pooled_spec(), &shared), None);
+    }
+
+    #[test]
+    fn warm_pod_is_generic_and_mounts_its_own_claim() {
+        let config = pooled_config();
+        let pod = build_warm_pod(&pool(), "warm-abc", &config);
+
+        let labels = pod.metadata.labels.as_ref().unwrap();
+        assert_eq!(labels["app"], "swarm-agent");
+        assert_eq!(labels[POOL_LABEL], "microvm-500m-512mi");
+        assert_eq!(labels[POOL_STATE_LABEL], WARM);
+        assert!(!labels.contains_key("swarm.io/agent-id"));
+
+        let spec = pod.spec.as_ref().unwrap();
+        assert_eq!(spec.runtime_class_name.as_deref(), Some("kata-fc"));
+        let volume = &spec.volumes.as_ref().unwrap()[0];
+        assert_eq!(
+            volume.persistent_volume_claim.as_ref().unwrap().claim_name,
+            "warm-abc-state"
+        );
+
+        let env = spec.containers[0].env.as_ref().unwrap();
+        assert!(env.iter().any(|e| e.name == "AURA_STANDBY"));
+        assert!(!env.iter().any(|e| e.name == "AGENT_ID"));
+
+        let claim = build_warm_claim(&pool(), "warm-abc", &config);
+        assert_eq!(claim.metadata.name.as_deref(), Some("warm-abc-state"));
+        assert_eq!(
+            claim.metadata.labels.as_ref().unwrap()[POOL_LABEL],
+            "microvm-500m-512mi"
+        );
+    }
+
+    #[test]
+    fn binding_relabels_the_pod_for_its_agent() {
+        let agent_id = AgentId::generate(&UserId::from_bytes([1u8; 32]), "agent");
+        let patch = bind_pod_patch(&agent_id, &"ab".repeat(32), Utc::now());
+
+        let labels = &patch["metadata"]["labels"];
+        assert_eq!(labels["swarm.io/agent-id"], agent_id.to_hex()[..63]);
+        assert!(labels[POOL_LABEL].is_null());
+        assert!(labels[POOL_STATE_LABEL].is_null());
+        assert_eq!(
+            patch["metadata"]["annotations"]["swarm.io/agent-id-full"],
+            agent_id.to_hex()
+        );
+
+        let claim = claim_patch("42", Utc::now());
+        assert_eq!(claim["metadata"]["resourceVersion"], "42");
+        assert_eq!(claim["metadata"]["labels"][POOL_STATE_LABEL], CLAIMED);
+    }
+
+    #[test]
+    fn recycles_old_and_failed_pods() {
+        let config = pooled_config();
+        let now = Utc::now();
+
+        let fresh = ready_pod(now - chrono::Duration::minutes(5));
+        assert!(is_claimable(&fresh));
+        assert!(!needs_recycling(&fresh, now, &config));
+
+        let old = ready_pod(now - chrono::Duration::hours(7));
+        assert!(needs_recycling(&old, now, &config));
+
+        let mut failed = ready_pod(now);
+        failed.status.as_mut().unwrap().phase = Some("Failed".to_string());
+        assert!(!is_claimable(&failed));
+        assert!(needs_recycling(&failed, now, &config));
+    }
+
+    #[test]
+    fn claims_oldest_pods_first() {
+        let now = Utc::now();
+        let mut pods = vec![
+            ready_pod(now - chrono::Duration::minutes(1)),
+            ready_pod(now - chrono::Duration::minutes(30)),
+        ];
+        sort_for_claiming(&mut pods);
+        assert_eq!(
+            pods[0].metadata.creation_timestamp.as_ref().unwrap().0,
+            now - chrono::Duration::minutes(30)
+        );
+    }
+
+    #[test]
+    fn abandoned_claims_time_out() {
+        let now = Utc::now();
+        let mut pod = ready_pod(now);
+        let patch = claim_patch("1", now - chrono::Duration::minutes(10));
+        pod.metadata.annotations = Some(BTreeMap::from([(
+            CLAIMED_AT_ANNOTATION.to_string(),
+            patch["metadata"]["annotations"][CLAIMED_AT_ANNOTATION]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        )]));
+        assert!(is_abandoned_claim(&pod, now));
+        assert!(!is_abandoned_claim(
+            &pod,
+            now - chrono::Duration::minutes(9)
+        ));
+    }
+}
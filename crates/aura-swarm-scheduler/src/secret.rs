@@ -0,0 +1,92 @@
+//! Secret specification builder for per-agent secrets.
+//!
+//! Each agent's user-managed secrets are materialized as a dedicated
+//! Kubernetes Secret that the agent's pod references with `secretKeyRef`.
+//! The control plane rewrites the Secret before every pod placement, so new
+//! values take effect the next time the agent starts.
+
+use std::collections::BTreeMap;
+
+use aura_swarm_core::AgentId;
+use k8s_openapi::api::core::v1::Secret;
+use k8s_openapi::ByteString;
+use kube::api::ObjectMeta;
+
+use crate::pod::truncate_for_label;
+use crate::SchedulerConfig;
+
+/// Generate the name of an agent's Secret.
+///
+/// Uses the same 16-character agent ID prefix as the pod name.
+#[must_use]
+pub fn secret_name_for_agent(agent_id: &AgentId) -> String {
+    format!("agent-{}-secrets", &agent_id.to_hex()[..16])
+}
+
+/// Build the Kubernetes Secret holding an agent's secrets.
+#[must_use]
+pub fn build_agent_secret(
+    agent_id: &AgentId,
+    secrets: &BTreeMap<String, String>,
+    config: &SchedulerConfig,
+) -> Secret {
+    let agent_id_hex = agent_id.to_hex();
+
+    let mut labels = BTreeMap::new();
+    labels.insert("app".to_string(), "swarm-agent-secrets".to_string());
+    labels.insert(
+        "swarm.io/agent-id".to_string(),
+        truncate_for_label(&agent_id_hex),
+    );
+
+    let mut annotations = BTreeMap::new();
+    annotations.insert("swarm.io/agent-id-full".to_string(), agent_id_hex);
+
+    let data = secrets
+        .iter()
+        .map(|(name, value)| (name.clone(), ByteString(value.as_bytes().to_vec())))
+        .collect();
+
+    Secret {
+        metadata: ObjectMeta {
+            name: Some(secret_name_for_agent(agent_id)),
+            namespace: Some(config.namespace.clone()),
+            labels: Some(labels),
+            annotations: Some(annotations),
+            ..Default::default()
+        },
+        data: Some(data),
+        type_: Some("Opaque".to_string()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::UserId;
+
+    #[test]
+    fn agent_secret_holds_every_value() {
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let agent_id = AgentId::generate(&user_id, "agent");
+        let config = SchedulerConfig::default();
+
+        let mut secrets = BTreeMap::new();
+        secrets.insert("GITHUB_TOKEN".to_string(), "ghp_123".to_string());
+
+        let secret = build_agent_secret(&agent_id, &secrets, &config);
+
+        assert_eq!(
+            secret.metadata.name.as_deref(),
+            Some(format!("agent-{}-secrets", &agent_id.to_hex()[..16]).as_str())
+        );
+        assert_eq!(secret.metadata.namespace.as_deref(), Some("swarm-agents"));
+
+        let data = secret.data.unwrap();
+        assert_eq!(
+            data.get("GITHUB_TOKEN"),
+            Some(&ByteString(b"ghp_123".to_vec()))
+        );
+    }
+}
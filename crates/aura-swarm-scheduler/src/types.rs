@@ -1,9 +1,10 @@
 //! Types for the scheduler crate.
 
 use aura_swarm_core::AgentId;
-use aura_swarm_store::IsolationLevel;
+use aura_swarm_store::{AgentPlacement, EgressPolicy, IsolationLevel, TopologySpread};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Status of a pod in Kubernetes.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,6 +33,31 @@ impl Default for PodStatus {
     }
 }
 
+/// Resource usage of a pod, as reported by the Kubernetes metrics API.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PodMetrics {
+    /// CPU usage in millicores, summed across containers.
+    pub cpu_millicores: u64,
+    /// Memory working set in bytes, summed across containers.
+    pub memory_bytes: u64,
+    /// When the usage was sampled.
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+/// Options for reading an agent pod's logs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PodLogOptions {
+    /// Only return this many of the most recent lines.
+    #[serde(default)]
+    pub tail: Option<u32>,
+    /// Only return lines written at or after this time.
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    /// Keep the stream open and return lines as they are written.
+    #[serde(default)]
+    pub follow: bool,
+}
+
 /// Phase of the pod lifecycle.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -90,22 +116,113 @@ pub struct PodInfo {
     pub status: PodStatus,
 }
 
+/// Where agents keep their persistent state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum StateVolumeMode {
+    /// Each agent gets its own `PersistentVolumeClaim`, sized from its spec.
+    #[default]
+    PerAgent,
+    /// All agents share the `state_pvc_name` claim, each in its own
+    /// subdirectory. Storage is not limited per agent.
+    Shared,
+}
+
+impl StateVolumeMode {
+    /// Parse a mode from its configuration name.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "per_agent" | "per-agent" | "dedicated" => Some(Self::PerAgent),
+            "shared" => Some(Self::Shared),
+            _ => None,
+        }
+    }
+}
+
+/// What the scheduler runs agents on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulerBackend {
+    /// Pods in a Kubernetes cluster.
+    #[default]
+    Kubernetes,
+    /// Child processes of the scheduler, for development without a cluster.
+    LocalProcess,
+}
+
+impl SchedulerBackend {
+    /// Parse a backend from its configuration name.
+    #[must_use]
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "kubernetes" | "k8s" => Some(Self::Kubernetes),
+            "local_process" | "local-process" | "local" => Some(Self::LocalProcess),
+            _ => None,
+        }
+    }
+}
+
+/// A pool of warm runtime pods of one isolation level and size.
+///
+/// Agents whose spec matches a pool exactly can be started on one of its
+/// pods instead of a freshly created one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WarmPoolConfig {
+    /// Isolation level of the pool's pods.
+    pub isolation: IsolationLevel,
+    /// CPU allocation of each pod in millicores.
+    pub cpu_millicores: u32,
+    /// Memory allocation of each pod in megabytes.
+    pub memory_mb: u32,
+    /// Number of unclaimed pods to keep ready.
+    pub size: u32,
+}
+
+impl WarmPoolConfig {
+    /// The pool's name, used to label its pods (e.g. `microvm-500m-512mi`).
+    #[must_use]
+    pub fn key(&self) -> String {
+        let isolation = match self.isolation {
+            IsolationLevel::Container => "container",
+            IsolationLevel::MicroVM => "microvm",
+        };
+        format!("{isolation}-{}m-{}mi", self.cpu_millicores, self.memory_mb)
+    }
+}
+
 /// Configuration for the Kubernetes scheduler.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SchedulerConfig {
+    /// What agents are run on.
+    pub backend: SchedulerBackend,
     /// Kubernetes namespace for agent pods.
     pub namespace: String,
+    /// Kubernetes namespace of the gateway and control plane.
+    pub system_namespace: String,
     /// Default isolation level for agents that don't specify one.
     /// Determines whether pods run as containers or microVMs.
     pub default_isolation: IsolationLevel,
-    /// Container image for the Aura runtime.
+    /// Container image for the Aura runtime. Agents on a runtime version
+    /// other than `latest` run this image's repository tagged with the version.
     pub image: String,
     /// Internal URL of the control plane service (deprecated, use gateway_url).
     pub control_plane_url: String,
     /// Internal URL of the gateway service for status callbacks.
     pub gateway_url: String,
-    /// PVC name for agent state storage.
+    /// Longest delay between retries of a failed status callback (seconds).
+    pub status_retry_max_seconds: u64,
+    /// How agent state volumes are provisioned.
+    pub state_volume_mode: StateVolumeMode,
+    /// PVC name for agent state storage in [`StateVolumeMode::Shared`] mode.
     pub state_pvc_name: String,
+    /// Default state volume size in gigabytes for per-agent volumes.
+    pub default_storage_gb: u32,
+    /// Maximum state volume size allowed in gigabytes.
+    pub max_storage_gb: u32,
+    /// Default storage class for per-agent volumes.
+    /// If not specified, uses the cluster's default storage class.
+    pub default_storage_class: Option<String>,
     /// Default CPU allocation in millicores.
     pub default_cpu_millicores: u32,
     /// Default memory allocation in megabytes.
@@ -114,25 +231,93 @@ pub struct SchedulerConfig {
     pub max_cpu_millicores: u32,
     /// Maximum memory allowed in megabytes.
     pub max_memory_mb: u32,
+    /// Container image used by state maintenance jobs (e.g., cloning state).
+    pub state_job_image: String,
+    /// How long to wait for a state maintenance job to finish (seconds).
+    pub state_job_timeout_seconds: u64,
+    /// Egress policy for agents that don't specify one.
+    pub default_egress: EgressPolicy,
+    /// Default node placement for container-isolated agents.
+    pub container_placement: AgentPlacement,
+    /// Default node placement for microVM-isolated agents.
+    pub microvm_placement: AgentPlacement,
+    /// Node selector keys agents may set in their own placement.
+    pub allowed_node_selector_keys: Vec<String>,
+    /// Taint keys agents may tolerate in their own placement.
+    pub allowed_toleration_keys: Vec<String>,
+    /// Priority classes agents may request in their own placement.
+    pub allowed_priority_classes: Vec<String>,
+    /// Warm pools of pre-provisioned runtime pods. Only used with
+    /// [`StateVolumeMode::PerAgent`], and the runtime image must support
+    /// standby activation (runtime contract v0.2.0).
+    pub warm_pools: Vec<WarmPoolConfig>,
+    /// How long an unclaimed warm pod is kept before it is replaced (seconds).
+    pub warm_pod_max_age_seconds: u64,
+    /// Program and arguments that run the Aura runtime with the
+    /// [`SchedulerBackend::LocalProcess`] backend.
+    pub local_command: Vec<String>,
+    /// Directory holding each agent's state directory with the
+    /// [`SchedulerBackend::LocalProcess`] backend.
+    pub local_state_dir: PathBuf,
 }
 
 impl Default for SchedulerConfig {
     fn default() -> Self {
         Self {
+            backend: SchedulerBackend::Kubernetes,
             namespace: "swarm-agents".to_string(),
+            system_namespace: "swarm-system".to_string(),
             default_isolation: IsolationLevel::MicroVM,
             image: "ghcr.io/cypher-asi/aura-runtime:latest".to_string(),
             control_plane_url: "http://aura-swarm-gateway.swarm-system.svc:8080".to_string(),
             gateway_url: "http://aura-swarm-gateway.swarm-system.svc:8080".to_string(),
+            status_retry_max_seconds: 60,
+            state_volume_mode: StateVolumeMode::PerAgent,
             state_pvc_name: "swarm-agent-state".to_string(),
+            default_storage_gb: 10,
+            max_storage_gb: 100,
+            default_storage_class: None,
             default_cpu_millicores: 500,
             default_memory_mb: 512,
             max_cpu_millicores: 4000,
             max_memory_mb: 8192,
+            state_job_image: "busybox:1.36".to_string(),
+            state_job_timeout_seconds: 600,
+            default_egress: EgressPolicy::AllowInternet,
+            container_placement: zone_spread_placement(),
+            microvm_placement: zone_spread_placement(),
+            allowed_node_selector_keys: Vec::new(),
+            allowed_toleration_keys: Vec::new(),
+            allowed_priority_classes: Vec::new(),
+            warm_pools: Vec::new(),
+            warm_pod_max_age_seconds: 6 * 60 * 60,
+            local_command: vec!["aura-runtime".to_string()],
+            local_state_dir: PathBuf::from("data/agents"),
         }
     }
 }
 
+/// Split a comma-separated list, dropping empty items.
+fn split_list(val: &str) -> Vec<String> {
+    val.split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Spread agents evenly across zones where possible.
+fn zone_spread_placement() -> AgentPlacement {
+    AgentPlacement {
+        topology_spread: vec![TopologySpread {
+            topology_key: "topology.kubernetes.io/zone".to_string(),
+            max_skew: 1,
+            required: false,
+        }],
+        ..AgentPlacement::default()
+    }
+}
+
 impl SchedulerConfig {
     /// Create a new scheduler config with the given namespace.
     #[must_use]
@@ -146,23 +331,48 @@ impl SchedulerConfig {
     /// Load configuration from environment variables.
     ///
     /// Supported environment variables:
+    /// - `SCHEDULER_BACKEND`: What agents run on (`kubernetes` or `local_process`)
     /// - `SCHEDULER_NAMESPACE`: Kubernetes namespace for agent pods
+    /// - `SYSTEM_NAMESPACE`: Kubernetes namespace of the gateway and control plane
     /// - `AURA_RUNTIME_IMAGE`: Container image for the Aura runtime
     /// - `CONTROL_PLANE_URL`: Internal URL of the control plane service (deprecated)
     /// - `GATEWAY_URL`: Internal URL of the gateway service for status callbacks
-    /// - `STATE_PVC_NAME`: PVC name for agent state storage
+    /// - `STATUS_RETRY_MAX_SECONDS`: Longest delay between status callback retries
+    /// - `STATE_VOLUME_MODE`: How agent state volumes are provisioned (`per_agent` or `shared`)
+    /// - `STATE_PVC_NAME`: PVC name for agent state storage in shared mode
+    /// - `DEFAULT_STORAGE_GB`: Default per-agent state volume size
+    /// - `MAX_STORAGE_GB`: Maximum state volume size allowed
+    /// - `DEFAULT_STORAGE_CLASS`: Default storage class for per-agent volumes
     /// - `DEFAULT_ISOLATION`: Default isolation level ("container" or "microvm")
     /// - `DEFAULT_CPU_MILLICORES`: Default CPU allocation
     /// - `DEFAULT_MEMORY_MB`: Default memory allocation
     /// - `MAX_CPU_MILLICORES`: Maximum CPU allowed
     /// - `MAX_MEMORY_MB`: Maximum memory allowed
+    /// - `STATE_JOB_IMAGE`: Container image for state maintenance jobs
+    /// - `STATE_JOB_TIMEOUT_SECONDS`: Timeout for state maintenance jobs
+    /// - `DEFAULT_EGRESS`: Default egress policy (`deny_all` or `allow_internet`)
+    /// - `CONTAINER_PLACEMENT`: Default placement for container agents, as JSON
+    /// - `MICROVM_PLACEMENT`: Default placement for microVM agents, as JSON
+    /// - `PLACEMENT_NODE_SELECTOR_KEYS`: Node selector keys agents may set, comma-separated
+    /// - `PLACEMENT_TOLERATION_KEYS`: Taint keys agents may tolerate, comma-separated
+    /// - `PLACEMENT_PRIORITY_CLASSES`: Priority classes agents may request, comma-separated
+    /// - `WARM_POOLS`: Warm pools of runtime pods, as a JSON array
+    /// - `WARM_POD_MAX_AGE_SECONDS`: How long unclaimed warm pods are kept
+    /// - `LOCAL_RUNTIME_COMMAND`: Command running the runtime locally, split on whitespace
+    /// - `LOCAL_STATE_DIR`: Directory of local agents' state directories
     #[must_use]
     pub fn from_env() -> Self {
         let mut config = Self::default();
 
+        if let Ok(val) = std::env::var("SCHEDULER_BACKEND") {
+            config.backend = SchedulerBackend::parse(&val).unwrap_or(config.backend);
+        }
         if let Ok(val) = std::env::var("SCHEDULER_NAMESPACE") {
             config.namespace = val;
         }
+        if let Ok(val) = std::env::var("SYSTEM_NAMESPACE") {
+            config.system_namespace = val;
+        }
         if let Ok(val) = std::env::var("AURA_RUNTIME_IMAGE") {
             config.image = val;
         }
@@ -176,9 +386,33 @@ impl SchedulerConfig {
         if let Ok(val) = std::env::var("GATEWAY_URL") {
             config.gateway_url = val;
         }
+        if let Ok(val) = std::env::var("STATUS_RETRY_MAX_SECONDS") {
+            if let Ok(n) = val.parse() {
+                config.status_retry_max_seconds = n;
+            }
+        }
+        if let Ok(val) = std::env::var("STATE_VOLUME_MODE") {
+            config.state_volume_mode =
+                StateVolumeMode::parse(&val).unwrap_or(config.state_volume_mode);
+        }
         if let Ok(val) = std::env::var("STATE_PVC_NAME") {
             config.state_pvc_name = val;
         }
+        if let Ok(val) = std::env::var("DEFAULT_STORAGE_GB") {
+            if let Ok(n) = val.parse() {
+                config.default_storage_gb = n;
+            }
+        }
+        if let Ok(val) = std::env::var("MAX_STORAGE_GB") {
+            if let Ok(n) = val.parse() {
+                config.max_storage_gb = n;
+            }
+        }
+        if let Ok(val) = std::env::var("DEFAULT_STORAGE_CLASS") {
+            if !val.is_empty() {
+                config.default_storage_class = Some(val);
+            }
+        }
         if let Ok(val) = std::env::var("DEFAULT_ISOLATION") {
             config.default_isolation = match val.to_lowercase().as_str() {
                 "container" | "runc" => IsolationLevel::Container,
@@ -206,10 +440,138 @@ impl SchedulerConfig {
                 config.max_memory_mb = n;
             }
         }
+        if let Ok(val) = std::env::var("STATE_JOB_IMAGE") {
+            config.state_job_image = val;
+        }
+        if let Ok(val) = std::env::var("STATE_JOB_TIMEOUT_SECONDS") {
+            if let Ok(n) = val.parse() {
+                config.state_job_timeout_seconds = n;
+            }
+        }
+        if let Ok(val) = std::env::var("DEFAULT_EGRESS") {
+            config.default_egress = match val.to_lowercase().as_str() {
+                "deny_all" | "deny-all" | "none" => EgressPolicy::DenyAll,
+                "allow_internet" | "allow-internet" | "internet" => EgressPolicy::AllowInternet,
+                _ => config.default_egress,
+            };
+        }
+        if let Ok(val) = std::env::var("CONTAINER_PLACEMENT") {
+            if let Ok(placement) = serde_json::from_str(&val) {
+                config.container_placement = placement;
+            }
+        }
+        if let Ok(val) = std::env::var("MICROVM_PLACEMENT") {
+            if let Ok(placement) = serde_json::from_str(&val) {
+                config.microvm_placement = placement;
+            }
+        }
+        if let Ok(val) = std::env::var("PLACEMENT_NODE_SELECTOR_KEYS") {
+            config.allowed_node_selector_keys = split_list(&val);
+        }
+        if let Ok(val) = std::env::var("PLACEMENT_TOLERATION_KEYS") {
+            config.allowed_toleration_keys = split_list(&val);
+        }
+        if let Ok(val) = std::env::var("PLACEMENT_PRIORITY_CLASSES") {
+            config.allowed_priority_classes = split_list(&val);
+        }
+        if let Ok(val) = std::env::var("WARM_POOLS") {
+            if let Ok(pools) = serde_json::from_str(&val) {
+                config.warm_pools = pools;
+            }
+        }
+        if let Ok(val) = std::env::var("WARM_POD_MAX_AGE_SECONDS") {
+            if let Ok(n) = val.parse() {
+                config.warm_pod_max_age_seconds = n;
+            }
+        }
+        if let Ok(val) = std::env::var("LOCAL_RUNTIME_COMMAND") {
+            let command: Vec<String> = val.split_whitespace().map(str::to_string).collect();
+            if !command.is_empty() {
+                config.local_command = command;
+            }
+        }
+        if let Ok(val) = std::env::var("LOCAL_STATE_DIR") {
+            config.local_state_dir = PathBuf::from(val);
+        }
 
         config
     }
 
+    /// The runtime image for a runtime version.
+    ///
+    /// `latest` runs [`Self::image`] as configured; any other version runs the
+    /// same repository tagged with the version.
+    #[must_use]
+    pub fn image_for(&self, runtime_version: &str) -> String {
+        if runtime_version.is_empty() || runtime_version == "latest" {
+            return self.image.clone();
+        }
+
+        // Strip any digest, then any tag (a colon after the last slash; one
+        // before it separates a registry port)
+        let repository = self
+            .image
+            .split_once('@')
+            .map_or(self.image.as_str(), |(repository, _)| repository);
+        let name_start = repository.rfind('/').map_or(0, |i| i + 1);
+        let repository = match repository[name_start..].rfind(':') {
+            Some(i) => &repository[..name_start + i],
+            None => repository,
+        };
+        format!("{repository}:{runtime_version}")
+    }
+
+    /// The default node placement for an isolation level.
+    #[must_use]
+    pub fn placement_for(&self, isolation: IsolationLevel) -> &AgentPlacement {
+        match isolation {
+            IsolationLevel::Container => &self.container_placement,
+            IsolationLevel::MicroVM => &self.microvm_placement,
+        }
+    }
+
+    /// Validate an agent's own placement against the allowlists.
+    ///
+    /// Agents may only select nodes by, tolerate taints with, and request
+    /// priority classes that an administrator has allowed. Tolerations must
+    /// name a taint key, since a keyless one tolerates every taint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first part of the placement that is not allowed.
+    pub fn validate_placement(&self, placement: &AgentPlacement) -> crate::Result<()> {
+        if let Some(key) = placement
+            .node_selector
+            .keys()
+            .find(|key| !self.allowed_node_selector_keys.contains(key))
+        {
+            return Err(crate::SchedulerError::Config(format!(
+                "node selector key '{key}' is not allowed"
+            )));
+        }
+        for toleration in &placement.tolerations {
+            if toleration.key.is_empty() {
+                return Err(crate::SchedulerError::Config(
+                    "tolerations must name a taint key".to_string(),
+                ));
+            }
+            if !self.allowed_toleration_keys.contains(&toleration.key) {
+                return Err(crate::SchedulerError::Config(format!(
+                    "tolerating taint '{}' is not allowed",
+                    toleration.key
+                )));
+            }
+        }
+        if let Some(class) = &placement.priority_class {
+            if !self.allowed_priority_classes.contains(class) {
+                return Err(crate::SchedulerError::Config(format!(
+                    "priority class '{class}' is not allowed"
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Validate resource requests against limits.
     ///
     /// # Errors
@@ -230,6 +592,21 @@ impl SchedulerConfig {
         }
         Ok(())
     }
+
+    /// Validate a state volume size against the configured maximum.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the size is zero or exceeds the configured maximum.
+    pub fn validate_storage(&self, storage_gb: u32) -> crate::Result<()> {
+        if storage_gb == 0 || storage_gb > self.max_storage_gb {
+            return Err(crate::SchedulerError::Config(format!(
+                "Storage request {}Gi must be between 1Gi and {}Gi",
+                storage_gb, self.max_storage_gb
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -269,6 +646,32 @@ mod tests {
         assert_eq!(config.default_memory_mb, 512);
     }
 
+    #[test]
+    fn runtime_versions_tag_the_configured_repository() {
+        let config = SchedulerConfig::default();
+        assert_eq!(config.image_for("latest"), config.image);
+        assert_eq!(
+            config.image_for("v2.1.0"),
+            "ghcr.io/cypher-asi/aura-runtime:v2.1.0"
+        );
+
+        let pinned = SchedulerConfig {
+            image: "registry.local:5000/aura-runtime@sha256:abc".to_string(),
+            ..SchedulerConfig::default()
+        };
+        assert_eq!(pinned.image_for("latest"), pinned.image);
+        assert_eq!(
+            pinned.image_for("v2"),
+            "registry.local:5000/aura-runtime:v2"
+        );
+
+        let untagged = SchedulerConfig {
+            image: "aura-runtime".to_string(),
+            ..SchedulerConfig::default()
+        };
+        assert_eq!(untagged.image_for("v2"), "aura-runtime:v2");
+    }
+
     #[test]
     fn scheduler_config_validate_resources() {
         let config = SchedulerConfig::default();
@@ -280,5 +683,117 @@ mod tests {
         // Invalid resources
         assert!(config.validate_resources(5000, 512).is_err());
         assert!(config.validate_resources(500, 10000).is_err());
+
+        assert!(config.validate_storage(100).is_ok());
+        assert!(config.validate_storage(0).is_err());
+        assert!(config.validate_storage(101).is_err());
+    }
+
+    #[test]
+    fn agent_placement_is_limited_to_allowlists() {
+        use aura_swarm_store::{PlacementToleration, TolerationOperator};
+
+        let config = SchedulerConfig {
+            allowed_node_selector_keys: vec!["node.kubernetes.io/pool".to_string()],
+            allowed_toleration_keys: vec!["swarm.io/agents".to_string()],
+            allowed_priority_classes: vec!["agents-high".to_string()],
+            ..SchedulerConfig::default()
+        };
+        let toleration = |key: &str| PlacementToleration {
+            key: key.to_string(),
+            operator: TolerationOperator::Exists,
+            value: None,
+            effect: None,
+        };
+        let mut allowed = AgentPlacement::default();
+        allowed
+            .node_selector
+            .insert("node.kubernetes.io/pool".to_string(), "kata".to_string());
+        allowed.tolerations.push(toleration("swarm.io/agents"));
+        allowed.priority_class = Some("agents-high".to_string());
+
+        assert!(config.validate_placement(&allowed).is_ok());
+        assert!(config
+            .validate_placement(&AgentPlacement::default())
+            .is_ok());
+        // Nothing is allowed unless configured
+        assert!(SchedulerConfig::default()
+            .validate_placement(&allowed)
+            .is_err());
+
+        let mut other_selector = allowed.clone();
+        other_selector
+            .node_selector
+            .insert("kubernetes.io/hostname".to_string(), "node-1".to_string());
+
+        let mut keyless = allowed.clone();
+        keyless.tolerations.push(toleration(""));
+
+        let mut other_taint = allowed.clone();
+        other_taint
+            .tolerations
+            .push(toleration("node-role.kubernetes.io/control-plane"));
+
+        let mut system_priority = allowed.clone();
+        system_priority.priority_class = Some("system-node-critical".to_string());
+
+        for placement in [other_selector, keyless, other_taint, system_priority] {
+            assert!(
+                matches!(
+                    config.validate_placement(&placement),
+                    Err(crate::SchedulerError::Config(_))
+                ),
+                "accepted {placement:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn warm_pool_key_names_isolation_and_size() {
+        let pool: WarmPoolConfig = serde_json::from_str(
+            r#"{"isolation": "container", "cpu_millicores": 250, "memory_mb": 256, "size": 3}"#,
+        )
+        .unwrap();
+        assert_eq!(pool.key(), "container-250m-256mi");
+
+        let pool = WarmPoolConfig {
+            isolation: IsolationLevel::MicroVM,
+            ..pool
+        };
+        assert_eq!(pool.key(), "microvm-250m-256mi");
+    }
+
+    #[test]
+    fn state_volume_mode_parse() {
+        assert_eq!(
+            SchedulerConfig::default().state_volume_mode,
+            StateVolumeMode::PerAgent
+        );
+        assert_eq!(
+            StateVolumeMode::parse("per_agent"),
+            Some(StateVolumeMode::PerAgent)
+        );
+        assert_eq!(
+            StateVolumeMode::parse("Shared"),
+            Some(StateVolumeMode::Shared)
+        );
+        assert_eq!(StateVolumeMode::parse("nfs"), None);
+    }
+
+    #[test]
+    fn scheduler_backend_parse() {
+        assert_eq!(
+            SchedulerConfig::default().backend,
+            SchedulerBackend::Kubernetes
+        );
+        assert_eq!(
+            SchedulerBackend::parse("k8s"),
+            Some(SchedulerBackend::Kubernetes)
+        );
+        assert_eq!(
+            SchedulerBackend::parse("Local"),
+            Some(SchedulerBackend::LocalProcess)
+        );
+        assert_eq!(SchedulerBackend::parse("nomad"), None);
     }
 }
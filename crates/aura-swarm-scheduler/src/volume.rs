@@ -0,0 +1,199 @@
+//! `PersistentVolumeClaim` builder for per-agent state volumes.
+//!
+//! In [`StateVolumeMode::PerAgent`] mode each agent keeps its state on its
+//! own claim, sized from its spec. The scheduler creates the claim before the
+//! agent's first pod and deletes it when the agent's state is deleted. The
+//! claim outlives the agent's pods, so none of them owns it.
+//!
+//! In [`StateVolumeMode::Shared`] mode every agent mounts its own
+//! subdirectory of the shared `state_pvc_name` claim instead.
+
+use std::collections::BTreeMap;
+
+use aura_swarm_core::AgentId;
+use aura_swarm_store::AgentSpec;
+use k8s_openapi::api::core::v1::{
+    PersistentVolumeClaim, PersistentVolumeClaimSpec, VolumeResourceRequirements,
+};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use kube::api::ObjectMeta;
+
+use crate::pod::truncate_for_label;
+use crate::types::{SchedulerConfig, StateVolumeMode};
+
+/// Generate the name of an agent's own state claim.
+///
+/// Uses the same 16-character agent ID prefix as the pod name.
+#[must_use]
+pub fn pvc_name_for_agent(agent_id: &AgentId) -> String {
+    format!("agent-{}-state", &agent_id.to_hex()[..16])
+}
+
+/// The claim holding an agent's state in the configured volume mode.
+#[must_use]
+pub fn state_claim_for_agent(agent_id: &AgentId, config: &SchedulerConfig) -> String {
+    match config.state_volume_mode {
+        StateVolumeMode::PerAgent => pvc_name_for_agent(agent_id),
+        StateVolumeMode::Shared => config.state_pvc_name.clone(),
+    }
+}
+
+/// The state volume size in gigabytes for an agent's spec.
+#[must_use]
+pub fn storage_gb_for_spec(spec: &AgentSpec, config: &SchedulerConfig) -> u32 {
+    spec.storage_gb.unwrap_or(config.default_storage_gb)
+}
+
+/// Build the claim holding an agent's state, sized from its spec.
+#[must_use]
+pub fn build_agent_pvc(
+    agent_id: &AgentId,
+    spec: &AgentSpec,
+    config: &SchedulerConfig,
+) -> PersistentVolumeClaim {
+    let storage = Quantity(format!("{}Gi", storage_gb_for_spec(spec, config)));
+    let storage_class = spec
+        .storage_class
+        .clone()
+        .or_else(|| config.default_storage_class.clone());
+
+    build_pvc(agent_id, storage, storage_class, config)
+}
+
+/// Build a claim for `target` with the same size and storage class as
+/// `source`, used when an agent's state is cloned.
+#[must_use]
+pub fn build_cloned_pvc(
+    target: &AgentId,
+    source: &PersistentVolumeClaim,
+    config: &SchedulerConfig,
+) -> PersistentVolumeClaim {
+    let source_spec = source.spec.as_ref();
+    let storage = source_spec
+        .and_then(|spec| spec.resources.as_ref()?.requests.as_ref()?.get("storage"))
+        .cloned()
+        .unwrap_or_else(|| Quantity(format!("{}Gi", config.default_storage_gb)));
+    let storage_class = source_spec
+        .and_then(|spec| spec.storage_class_name.clone())
+        .or_else(|| config.default_storage_class.clone());
+
+    build_pvc(target, storage, storage_class, config)
+}
+
+fn build_pvc(
+    agent_id: &AgentId,
+    storage: Quantity,
+    storage_class: Option<String>,
+    config: &SchedulerConfig,
+) -> PersistentVolumeClaim {
+    let agent_id_hex = agent_id.to_hex();
+
+    let mut labels = BTreeMap::new();
+    labels.insert("app".to_string(), "swarm-agent-state".to_string());
+    labels.insert(
+        "swarm.io/agent-id".to_string(),
+        truncate_for_label(&agent_id_hex),
+    );
+
+    let mut annotations = BTreeMap::new();
+    annotations.insert("swarm.io/agent-id-full".to_string(), agent_id_hex);
+
+    let mut requests = BTreeMap::new();
+    requests.insert("storage".to_string(), storage);
+
+    PersistentVolumeClaim {
+        metadata: ObjectMeta {
+            name: Some(pvc_name_for_agent(agent_id)),
+            namespace: Some(config.namespace.clone()),
+            labels: Some(labels),
+            annotations: Some(annotations),
+            ..Default::default()
+        },
+        spec: Some(PersistentVolumeClaimSpec {
+            access_modes: Some(vec!["ReadWriteOnce".to_string()]),
+            storage_class_name: storage_class,
+            resources: Some(VolumeResourceRequirements {
+                requests: Some(requests),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aura_swarm_core::UserId;
+
+    fn storage_of(pvc: &PersistentVolumeClaim) -> &Quantity {
+        &pvc.spec
+            .as_ref()
+            .unwrap()
+            .resources
+            .as_ref()
+            .unwrap()
+            .requests
+            .as_ref()
+            .unwrap()["storage"]
+    }
+
+    #[test]
+    fn agent_pvc_uses_spec_or_defaults() {
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let agent_id = AgentId::generate(&user_id, "agent");
+        let config = SchedulerConfig {
+            default_storage_class: Some("gp3".to_string()),
+            ..SchedulerConfig::default()
+        };
+
+        let pvc = build_agent_pvc(&agent_id, &AgentSpec::default(), &config);
+        assert_eq!(pvc.metadata.name, Some(pvc_name_for_agent(&agent_id)));
+        assert_eq!(pvc.metadata.namespace.as_deref(), Some("swarm-agents"));
+        assert_eq!(storage_of(&pvc), &Quantity("10Gi".to_string()));
+        let spec = pvc.spec.as_ref().unwrap();
+        assert_eq!(spec.storage_class_name.as_deref(), Some("gp3"));
+        assert_eq!(spec.access_modes, Some(vec!["ReadWriteOnce".to_string()]));
+
+        let spec = AgentSpec {
+            storage_gb: Some(25),
+            storage_class: Some("fast".to_string()),
+            ..AgentSpec::default()
+        };
+        let pvc = build_agent_pvc(&agent_id, &spec, &config);
+        assert_eq!(storage_of(&pvc), &Quantity("25Gi".to_string()));
+        assert_eq!(
+            pvc.spec.as_ref().unwrap().storage_class_name.as_deref(),
+            Some("fast")
+        );
+
+        // A clone's claim matches its source's
+        let target = AgentId::generate(&user_id, "clone");
+        let clone = build_cloned_pvc(&target, &pvc, &config);
+        assert_eq!(clone.metadata.name, Some(pvc_name_for_agent(&target)));
+        assert_eq!(storage_of(&clone), &Quantity("25Gi".to_string()));
+        assert_eq!(
+            clone.spec.as_ref().unwrap().storage_class_name.as_deref(),
+            Some("fast")
+        );
+    }
+
+    #[test]
+    fn state_claim_follows_volume_mode() {
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let agent_id = AgentId::generate(&user_id, "agent");
+        let mut config = SchedulerConfig::default();
+
+        assert_eq!(
+            state_claim_for_agent(&agent_id, &config),
+            pvc_name_for_agent(&agent_id)
+        );
+
+        config.state_volume_mode = StateVolumeMode::Shared;
+        assert_eq!(
+            state_claim_for_agent(&agent_id, &config),
+            "swarm-agent-state"
+        );
+    }
+}
@@ -3,7 +3,11 @@
 //! This module provides functions to encode and decode keys for various indexes.
 //! All keys are designed to support efficient prefix scans.
 
-use aura_swarm_core::{AgentId, SessionId, UserId};
+use aura_swarm_core::{
+    AgentId, AuditEventId, DeliveryId, NamespaceId, RolloutId, SessionId, UserId, WebhookId,
+};
+
+use crate::types::UsageRecord;
 
 /// Encode an agent key (just the agent ID bytes).
 #[must_use]
@@ -40,6 +44,59 @@ pub fn extract_agent_id_from_user_agent_key(key: &[u8]) -> AgentId {
     AgentId::from_bytes(bytes)
 }
 
+/// Encode a namespace-agent index key: `namespace_id || agent_id`.
+///
+/// This allows efficient prefix scans for all agents in a namespace.
+#[must_use]
+pub fn namespace_agent_key(namespace_id: &NamespaceId, agent_id: &AgentId) -> Vec<u8> {
+    let mut key = Vec::with_capacity(48);
+    key.extend_from_slice(namespace_id.as_bytes());
+    key.extend_from_slice(agent_id.as_bytes());
+    key
+}
+
+/// Encode a namespace key (just the namespace ID bytes).
+///
+/// Also used as the prefix for scanning all agents in a namespace.
+#[must_use]
+pub fn namespace_key(namespace_id: &NamespaceId) -> Vec<u8> {
+    namespace_id.as_bytes().to_vec()
+}
+
+/// Extract the agent ID from a namespace-agent key.
+///
+/// # Panics
+///
+/// Panics if the key is not at least 48 bytes.
+#[must_use]
+pub fn extract_agent_id_from_namespace_agent_key(key: &[u8]) -> AgentId {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&key[16..48]);
+    AgentId::from_bytes(bytes)
+}
+
+/// Encode an agent grant key: `agent_id || user_id`.
+///
+/// Scanning with [`agent_prefix`] lists every grant on an agent.
+#[must_use]
+pub fn agent_grant_key(agent_id: &AgentId, user_id: &UserId) -> Vec<u8> {
+    let mut key = Vec::with_capacity(64);
+    key.extend_from_slice(agent_id.as_bytes());
+    key.extend_from_slice(user_id.as_bytes());
+    key
+}
+
+/// Encode an agent secret key: `agent_id || name`.
+///
+/// Scanning with [`agent_prefix`] lists every secret of an agent.
+#[must_use]
+pub fn agent_secret_key(agent_id: &AgentId, name: &str) -> Vec<u8> {
+    let mut key = Vec::with_capacity(32 + name.len());
+    key.extend_from_slice(agent_id.as_bytes());
+    key.extend_from_slice(name.as_bytes());
+    key
+}
+
 /// Encode a status-agent index key: `status || agent_id`.
 ///
 /// This allows efficient prefix scans for all agents with a given status.
@@ -98,10 +155,161 @@ pub fn user_key(user_id: &UserId) -> Vec<u8> {
     user_id.as_bytes().to_vec()
 }
 
+/// Encode a template scope prefix.
+///
+/// Admin-defined templates use the single byte `0`; user-defined templates
+/// use `1 || user_id`.
+#[must_use]
+pub fn template_scope_prefix(owner: Option<&UserId>) -> Vec<u8> {
+    match owner {
+        None => vec![0],
+        Some(user_id) => {
+            let mut key = Vec::with_capacity(33);
+            key.push(1);
+            key.extend_from_slice(user_id.as_bytes());
+            key
+        }
+    }
+}
+
+/// Encode a template key: `scope || name`.
+#[must_use]
+pub fn template_key(owner: Option<&UserId>, name: &str) -> Vec<u8> {
+    let mut key = template_scope_prefix(owner);
+    key.extend_from_slice(name.as_bytes());
+    key
+}
+
+/// Encode a webhook key (just the webhook ID bytes).
+#[must_use]
+pub fn webhook_key(webhook_id: &WebhookId) -> Vec<u8> {
+    webhook_id.as_bytes().to_vec()
+}
+
+/// Encode a user-webhook index key: `user_id || webhook_id`.
+#[must_use]
+pub fn user_webhook_key(user_id: &UserId, webhook_id: &WebhookId) -> Vec<u8> {
+    let mut key = Vec::with_capacity(48);
+    key.extend_from_slice(user_id.as_bytes());
+    key.extend_from_slice(webhook_id.as_bytes());
+    key
+}
+
+/// Extract the webhook ID from a user-webhook key.
+///
+/// # Panics
+///
+/// Panics if the key is not at least 48 bytes.
+#[must_use]
+pub fn extract_webhook_id_from_user_webhook_key(key: &[u8]) -> WebhookId {
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&key[32..48]);
+    WebhookId::from_uuid(uuid::Uuid::from_bytes(bytes))
+}
+
+/// Encode a webhook delivery key: `webhook_id || created_at_millis || delivery_id`.
+///
+/// The big-endian timestamp keeps each webhook's deliveries in creation order.
+#[must_use]
+pub fn webhook_delivery_key(
+    webhook_id: &WebhookId,
+    created_at_millis: i64,
+    delivery_id: &DeliveryId,
+) -> Vec<u8> {
+    let mut key = Vec::with_capacity(40);
+    key.extend_from_slice(webhook_id.as_bytes());
+    // Timestamps before the epoch are not expected; clamp to keep ordering sane.
+    key.extend_from_slice(&created_at_millis.max(0).to_be_bytes());
+    key.extend_from_slice(delivery_id.as_bytes());
+    key
+}
+
+/// Encode a webhook prefix for scanning all deliveries of a webhook.
+#[must_use]
+pub fn webhook_prefix(webhook_id: &WebhookId) -> Vec<u8> {
+    webhook_id.as_bytes().to_vec()
+}
+
+/// Encode an outbox key (just the delivery ID bytes).
+#[must_use]
+pub fn outbox_key(delivery_id: &DeliveryId) -> Vec<u8> {
+    delivery_id.as_bytes().to_vec()
+}
+
+/// Encode an audit log key: `timestamp_millis || event_id`.
+///
+/// The big-endian timestamp keeps the log in chronological order.
+#[must_use]
+pub fn audit_key(timestamp_millis: i64, event_id: &AuditEventId) -> Vec<u8> {
+    let mut key = Vec::with_capacity(24);
+    key.extend_from_slice(&timestamp_millis.max(0).to_be_bytes());
+    key.extend_from_slice(event_id.as_bytes());
+    key
+}
+
+/// Encode the smallest audit log key at `timestamp_millis`.
+///
+/// Events at or after the timestamp sort at or after this key.
+#[must_use]
+pub fn audit_time_key(timestamp_millis: i64) -> Vec<u8> {
+    timestamp_millis.max(0).to_be_bytes().to_vec()
+}
+
+/// Encode a usage key: `hour_seconds || namespace_id || user_id || state`.
+///
+/// Unscoped agents use an all-zero namespace. The big-endian hour keeps
+/// buckets in chronological order.
+#[must_use]
+pub fn usage_key(record: &UsageRecord) -> Vec<u8> {
+    let mut key = Vec::with_capacity(57);
+    key.extend_from_slice(&usage_hour_key(record.hour.timestamp()));
+    match &record.namespace_id {
+        Some(namespace_id) => key.extend_from_slice(namespace_id.as_bytes()),
+        None => key.extend_from_slice(&[0u8; 16]),
+    }
+    key.extend_from_slice(record.user_id.as_bytes());
+    key.push(record.state.as_u8());
+    key
+}
+
+/// Encode the smallest usage key for the hour starting at `hour_seconds`.
+#[must_use]
+pub fn usage_hour_key(hour_seconds: i64) -> Vec<u8> {
+    hour_seconds.max(0).to_be_bytes().to_vec()
+}
+
+/// Encode a rollout key (just the rollout ID bytes).
+#[must_use]
+pub fn rollout_key(rollout_id: &RolloutId) -> Vec<u8> {
+    rollout_id.as_bytes().to_vec()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn webhook_delivery_keys_sort_by_time() {
+        let webhook_id = WebhookId::generate();
+        let earlier = webhook_delivery_key(&webhook_id, 1_000, &DeliveryId::generate());
+        let later = webhook_delivery_key(&webhook_id, 2_000, &DeliveryId::generate());
+
+        assert_eq!(earlier.len(), 40);
+        assert!(earlier < later);
+        assert!(earlier.starts_with(&webhook_prefix(&webhook_id)));
+    }
+
+    #[test]
+    fn audit_keys_sort_by_time() {
+        let earlier = audit_key(1_000, &AuditEventId::generate());
+        let later = audit_key(2_000, &AuditEventId::generate());
+
+        assert_eq!(earlier.len(), 24);
+        assert!(earlier < later);
+        assert!(audit_time_key(1_000) <= earlier);
+        assert!(audit_time_key(1_001) > earlier);
+    }
+
     #[test]
     fn user_agent_key_roundtrip() {
         let user_id = UserId::from_bytes([1u8; 32]);
@@ -114,6 +322,17 @@ mod tests {
         assert_eq!(extracted, agent_id);
     }
 
+    #[test]
+    fn namespace_agent_key_roundtrip() {
+        let namespace_id = NamespaceId::from_uuid(uuid::Uuid::new_v4());
+        let agent_id = AgentId::from_bytes([2u8; 32]);
+
+        let key = namespace_agent_key(&namespace_id, &agent_id);
+        assert_eq!(key.len(), 48);
+        assert!(key.starts_with(&namespace_key(&namespace_id)));
+        assert_eq!(extract_agent_id_from_namespace_agent_key(&key), agent_id);
+    }
+
     #[test]
     fn agent_session_key_roundtrip() {
         let agent_id = AgentId::from_bytes([1u8; 32]);
@@ -140,4 +359,17 @@ mod tests {
         assert!(key1.starts_with(&prefix));
         assert!(key2.starts_with(&prefix));
     }
+
+    #[test]
+    fn template_keys_are_scoped() {
+        let user_id = UserId::from_bytes([1u8; 32]);
+
+        let global = template_key(None, "small");
+        let owned = template_key(Some(&user_id), "small");
+
+        assert!(global.starts_with(&template_scope_prefix(None)));
+        assert!(owned.starts_with(&template_scope_prefix(Some(&user_id))));
+        assert!(!owned.starts_with(&template_scope_prefix(None)));
+        assert_ne!(global, owned);
+    }
 }
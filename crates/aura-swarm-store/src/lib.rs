@@ -10,9 +10,19 @@
 //! - `agents`: Primary agent records, keyed by `agent_id`
 //! - `agents_by_status`: Index for listing agents by status
 //! - `agents_by_user`: Index for listing agents by user
+//! - `agents_by_namespace`: Index for listing agents by namespace (tenant)
+//! - `agent_grants`, `grants_by_user`: Agent sharing grants and the "shared with me" index
+//! - `namespace_quotas`: Per-namespace quota overrides
 //! - `sessions`: Primary session records, keyed by `session_id`
 //! - `sessions_by_agent`: Index for listing sessions by agent
 //! - `users`: User records synced from Zero-ID
+//! - `templates`: Agent templates, scoped to admins or individual users
+//! - `schedules`: Per-agent wake/hibernate schedules
+//! - `webhooks`, `webhooks_by_user`: User-registered webhook endpoints
+//! - `webhook_deliveries`: Per-webhook delivery log
+//! - `webhook_outbox`: Deliveries awaiting their next attempt
+//! - `audit_log`: Append-only audit log in chronological order
+//! - `usage`: Hourly agent runtime per namespace, user and state
 //!
 //! # Example
 //!
@@ -40,9 +50,22 @@ pub mod types;
 
 pub use error::{Result, StoreError};
 pub use rocks::RocksStore;
-pub use types::{Agent, AgentSpec, AgentState, IsolationLevel, Session, SessionStatus, User};
+pub use types::{
+    Agent, AgentDeletion, AgentGrant, AgentPlacement, AgentRole, AgentSchedule, AgentSecret,
+    AgentSpec, AgentState, AgentTemplate, DeletionStatus, DeletionStep, EgressPolicy, EgressRule,
+    HibernationCheckpoint, IsolationLevel, MissedRunPolicy, NamespaceQuota, PlacementToleration,
+    RestartPolicy, RestartState, ScheduleAction, ScheduleRule, ScheduleRun, ScheduleRunOutcome,
+    Session, SessionStatus, StateCopy, TaintEffect, TolerationOperator, TopologySpread,
+    UsageRecord, User,
+};
+pub use types::{AuditActor, AuditEvent, AuditOutcome, AuditQuery, AuditResource};
+pub use types::{DeliveryAttempt, DeliveryStatus, Webhook, WebhookDelivery, WebhookEvent};
+pub use types::{
+    Rollout, RolloutSelector, RolloutStatus, RolloutStrategy, RolloutTarget, RolloutTargetState,
+};
 
-use aura_swarm_core::{AgentId, SessionId, UserId};
+use aura_swarm_core::{AgentId, NamespaceId, RolloutId, SessionId, UserId, WebhookId};
+use chrono::{DateTime, Utc};
 
 /// The storage trait defining all database operations.
 ///
@@ -71,7 +94,8 @@ pub trait Store: Send + Sync {
 
     /// Delete an agent by ID.
     ///
-    /// This also removes the agent from all indexes.
+    /// This also removes the agent from all indexes, revokes its access grants
+    /// and deletes its secrets.
     ///
     /// # Errors
     ///
@@ -94,6 +118,22 @@ pub trait Store: Send + Sync {
     /// Returns an error if the database operation fails.
     fn count_agents_by_user(&self, user_id: &UserId) -> Result<u32>;
 
+    /// List all agents in a namespace.
+    ///
+    /// Agents created before namespace scoping are not included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_agents_by_namespace(&self, namespace_id: &NamespaceId) -> Result<Vec<Agent>>;
+
+    /// Count agents in a namespace.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn count_agents_by_namespace(&self, namespace_id: &NamespaceId) -> Result<u32>;
+
     /// List all agents with a given status.
     ///
     /// # Errors
@@ -133,6 +173,79 @@ pub trait Store: Send + Sync {
     /// Returns an error if the database operation fails.
     fn list_all_agents(&self) -> Result<Vec<Agent>>;
 
+    // =========================================================================
+    // Grant Operations
+    // =========================================================================
+
+    /// Insert or update an access grant.
+    ///
+    /// This also maintains the grantee index.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn put_agent_grant(&self, grant: &AgentGrant) -> Result<()>;
+
+    /// Get a user's grant on an agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn get_agent_grant(&self, agent_id: &AgentId, user_id: &UserId) -> Result<Option<AgentGrant>>;
+
+    /// List every grant on an agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_agent_grants(&self, agent_id: &AgentId) -> Result<Vec<AgentGrant>>;
+
+    /// List every grant held by a user (agents shared with them).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_grants_by_user(&self, user_id: &UserId) -> Result<Vec<AgentGrant>>;
+
+    /// Delete a user's grant on an agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the grant doesn't exist, or an error if the database operation fails.
+    fn delete_agent_grant(&self, agent_id: &AgentId, user_id: &UserId) -> Result<()>;
+
+    // =========================================================================
+    // Secret Operations
+    // =========================================================================
+
+    /// Insert or update an agent secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn put_agent_secret(&self, secret: &AgentSecret) -> Result<()>;
+
+    /// Get an agent secret by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn get_agent_secret(&self, agent_id: &AgentId, name: &str) -> Result<Option<AgentSecret>>;
+
+    /// List every secret of an agent, ordered by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_agent_secrets(&self, agent_id: &AgentId) -> Result<Vec<AgentSecret>>;
+
+    /// Delete an agent secret.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NotFound` if the secret doesn't exist, or an error if the database operation fails.
+    fn delete_agent_secret(&self, agent_id: &AgentId, name: &str) -> Result<()>;
+
     // =========================================================================
     // Session Operations
     // =========================================================================
@@ -165,6 +278,15 @@ pub trait Store: Send + Sync {
     /// Returns an error if the database operation fails.
     fn list_sessions_by_agent(&self, agent_id: &AgentId) -> Result<Vec<Session>>;
 
+    /// List all active sessions across all agents.
+    ///
+    /// This performs a full scan of the sessions column family.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_active_sessions(&self) -> Result<Vec<Session>>;
+
     /// Update a session's status.
     ///
     /// If setting to `Closed`, also sets `closed_at`.
@@ -191,4 +313,294 @@ pub trait Store: Send + Sync {
     ///
     /// Returns an error if the database operation fails.
     fn get_user(&self, user_id: &UserId) -> Result<Option<User>>;
+
+    // =========================================================================
+    // Namespace Operations
+    // =========================================================================
+
+    /// Insert or replace a namespace's quota overrides.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn put_namespace_quota(&self, quota: &NamespaceQuota) -> Result<()>;
+
+    /// Get a namespace's quota overrides, if any are set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn get_namespace_quota(&self, namespace_id: &NamespaceId) -> Result<Option<NamespaceQuota>>;
+
+    // =========================================================================
+    // Template Operations
+    // =========================================================================
+
+    /// Insert or update an agent template.
+    ///
+    /// The template's `owner` determines its scope.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn put_template(&self, template: &AgentTemplate) -> Result<()>;
+
+    /// Get a template by scope and name.
+    ///
+    /// Pass `None` as the owner to look up an admin-defined template.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn get_template(&self, owner: Option<&UserId>, name: &str) -> Result<Option<AgentTemplate>>;
+
+    /// List the templates in a single scope, ordered by name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_templates(&self, owner: Option<&UserId>) -> Result<Vec<AgentTemplate>>;
+
+    /// Delete a template by scope and name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StoreError::NotFound` if the template doesn't exist.
+    fn delete_template(&self, owner: Option<&UserId>, name: &str) -> Result<()>;
+
+    // =========================================================================
+    // Schedule Operations
+    // =========================================================================
+
+    /// Insert or update an agent's schedule.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn put_schedule(&self, schedule: &AgentSchedule) -> Result<()>;
+
+    /// Get the schedule for an agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn get_schedule(&self, agent_id: &AgentId) -> Result<Option<AgentSchedule>>;
+
+    /// Delete the schedule for an agent.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StoreError::NotFound` if the agent has no schedule.
+    fn delete_schedule(&self, agent_id: &AgentId) -> Result<()>;
+
+    /// List all schedules in the database.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_schedules(&self) -> Result<Vec<AgentSchedule>>;
+
+    // =========================================================================
+    // Webhook Operations
+    // =========================================================================
+
+    /// Insert or update a webhook.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn put_webhook(&self, webhook: &Webhook) -> Result<()>;
+
+    /// Get a webhook by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn get_webhook(&self, webhook_id: &WebhookId) -> Result<Option<Webhook>>;
+
+    /// List all webhooks registered by a user.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_webhooks_by_user(&self, user_id: &UserId) -> Result<Vec<Webhook>>;
+
+    /// Delete a webhook along with its delivery log and pending deliveries.
+    ///
+    /// # Errors
+    ///
+    /// Returns `StoreError::NotFound` if the webhook doesn't exist.
+    fn delete_webhook(&self, webhook_id: &WebhookId) -> Result<()>;
+
+    /// Insert or update a webhook delivery.
+    ///
+    /// Pending deliveries are also recorded in the outbox; deliveries in any
+    /// other status are removed from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn put_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()>;
+
+    /// List the most recent deliveries for a webhook, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_webhook_deliveries(
+        &self,
+        webhook_id: &WebhookId,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>>;
+
+    /// List all pending deliveries in the outbox.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_pending_webhook_deliveries(&self) -> Result<Vec<WebhookDelivery>>;
+
+    /// Delete finished deliveries created before `before`.
+    ///
+    /// Pending deliveries are never pruned. Returns the number deleted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn prune_webhook_deliveries(&self, before: DateTime<Utc>) -> Result<usize>;
+
+    // =========================================================================
+    // Audit Operations
+    // =========================================================================
+
+    /// Append an event to the audit log.
+    ///
+    /// Audit events are never updated once written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn append_audit_event(&self, event: &AuditEvent) -> Result<()>;
+
+    /// List audit events matching a query, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_audit_events(&self, query: &AuditQuery) -> Result<Vec<AuditEvent>>;
+
+    /// Delete audit events older than `before`.
+    ///
+    /// Returns the number of events removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn prune_audit_events(&self, before: DateTime<Utc>) -> Result<usize>;
+
+    // =========================================================================
+    // Usage Operations
+    // =========================================================================
+
+    /// Add usage to the matching hourly buckets, creating them as needed.
+    ///
+    /// All records are applied atomically.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn add_usage(&self, records: &[UsageRecord]) -> Result<()>;
+
+    /// List the hourly buckets whose hour starts in `[from, to)`, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_usage(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<UsageRecord>>;
+
+    // =========================================================================
+    // Rollout Operations
+    // =========================================================================
+
+    /// Insert or update a rollout.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn put_rollout(&self, rollout: &Rollout) -> Result<()>;
+
+    /// Get a rollout by ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn get_rollout(&self, rollout_id: &RolloutId) -> Result<Option<Rollout>>;
+
+    /// List all rollouts, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_rollouts(&self) -> Result<Vec<Rollout>>;
+
+    // =========================================================================
+    // Agent Deletion Operations
+    // =========================================================================
+
+    /// Insert or update an agent deletion.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn put_agent_deletion(&self, deletion: &AgentDeletion) -> Result<()>;
+
+    /// Get the deletion of an agent, if one was requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn get_agent_deletion(&self, agent_id: &AgentId) -> Result<Option<AgentDeletion>>;
+
+    /// List all agent deletions, including completed ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_agent_deletions(&self) -> Result<Vec<AgentDeletion>>;
+
+    /// Delete the deletion record of an agent.
+    ///
+    /// Deleting a record that does not exist is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn delete_agent_deletion(&self, agent_id: &AgentId) -> Result<()>;
+
+    // =========================================================================
+    // State Copy Operations
+    // =========================================================================
+
+    /// Insert or update a pending state copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn put_state_copy(&self, copy: &StateCopy) -> Result<()>;
+
+    /// List all pending state copies.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn list_state_copies(&self) -> Result<Vec<StateCopy>>;
+
+    /// Delete the pending state copy into an agent.
+    ///
+    /// Deleting a copy that does not exist is not an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database operation fails.
+    fn delete_state_copy(&self, agent_id: &AgentId) -> Result<()>;
 }
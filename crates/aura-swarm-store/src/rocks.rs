@@ -5,7 +5,8 @@
 use std::path::Path;
 use std::sync::Arc;
 
-use aura_swarm_core::{AgentId, SessionId, UserId};
+use aura_swarm_core::{AgentId, NamespaceId, RolloutId, SessionId, UserId, WebhookId};
+use chrono::{DateTime, Utc};
 use rocksdb::{
     BoundColumnFamily, ColumnFamilyDescriptor, DBWithThreadMode, IteratorMode, MultiThreaded,
     Options, WriteBatch,
@@ -14,7 +15,11 @@ use rocksdb::{
 use crate::error::{Result, StoreError};
 use crate::keys;
 use crate::schema::{all_column_families, cf};
-use crate::types::{Agent, AgentState, Session, SessionStatus, User};
+use crate::types::{
+    Agent, AgentDeletion, AgentGrant, AgentSchedule, AgentSecret, AgentState, AgentTemplate,
+    AuditEvent, AuditQuery, DeliveryStatus, NamespaceQuota, Rollout, Session, SessionStatus,
+    StateCopy, UsageRecord, User, Webhook, WebhookDelivery,
+};
 use crate::Store;
 
 /// RocksDB-backed storage implementation.
@@ -63,6 +68,43 @@ impl RocksStore {
     fn deserialize<T: serde::de::DeserializeOwned>(data: &[u8]) -> Result<T> {
         ciborium::from_reader(data).map_err(|e| StoreError::Serialization(e.to_string()))
     }
+
+    /// Encode the delivery log key for a delivery.
+    fn delivery_key(delivery: &WebhookDelivery) -> Vec<u8> {
+        keys::webhook_delivery_key(
+            &delivery.webhook_id,
+            delivery.created_at.timestamp_millis(),
+            &delivery.delivery_id,
+        )
+    }
+
+    /// Collect all `(key, delivery)` pairs in a webhook's delivery log, oldest first.
+    fn scan_webhook_deliveries(
+        &self,
+        webhook_id: &WebhookId,
+    ) -> Result<Vec<(Vec<u8>, WebhookDelivery)>> {
+        let cf = self.cf(cf::WEBHOOK_DELIVERIES)?;
+        let prefix = keys::webhook_prefix(webhook_id);
+
+        let mut deliveries = Vec::new();
+        let iter = self.db.iterator_cf(
+            &cf,
+            IteratorMode::From(&prefix, rocksdb::Direction::Forward),
+        );
+
+        for item in iter {
+            let (key, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+
+            // Stop if we're past the prefix
+            if !key.starts_with(&prefix) {
+                break;
+            }
+
+            deliveries.push((key.to_vec(), Self::deserialize(&value)?));
+        }
+
+        Ok(deliveries)
+    }
 }
 
 impl Store for RocksStore {
@@ -74,20 +116,20 @@ impl Store for RocksStore {
         let cf_agents = self.cf(cf::AGENTS)?;
         let cf_by_user = self.cf(cf::AGENTS_BY_USER)?;
         let cf_by_status = self.cf(cf::AGENTS_BY_STATUS)?;
+        let cf_by_namespace = self.cf(cf::AGENTS_BY_NAMESPACE)?;
 
         let agent_key = keys::agent_key(&agent.agent_id);
         let user_agent_key = keys::user_agent_key(&agent.user_id, &agent.agent_id);
         let status_agent_key = keys::status_agent_key(agent.status.as_u8(), &agent.agent_id);
         let value = Self::serialize(agent)?;
 
-        // Check if agent exists to handle status index updates
-        let old_status = self
+        // Check if agent exists to handle status and namespace index updates
+        let old = self
             .db
             .get_cf(&cf_agents, &agent_key)
             .map_err(|e| StoreError::Database(e.to_string()))?
             .map(|data| Self::deserialize::<Agent>(&data))
-            .transpose()?
-            .map(|a| a.status);
+            .transpose()?;
 
         let mut batch = WriteBatch::default();
 
@@ -97,16 +139,29 @@ impl Store for RocksStore {
         // Update user index (idempotent)
         batch.put_cf(&cf_by_user, &user_agent_key, []);
 
-        // Update status index if status changed
-        if let Some(old) = old_status {
-            if old != agent.status {
-                // Remove old status index
-                let old_status_key = keys::status_agent_key(old.as_u8(), &agent.agent_id);
+        if let Some(old) = &old {
+            // Remove old status index if status changed
+            if old.status != agent.status {
+                let old_status_key = keys::status_agent_key(old.status.as_u8(), &agent.agent_id);
                 batch.delete_cf(&cf_by_status, &old_status_key);
             }
+
+            // Remove old namespace index if the namespace changed
+            if let Some(old_namespace) = old.namespace_id {
+                if Some(old_namespace) != agent.namespace_id {
+                    let old_key = keys::namespace_agent_key(&old_namespace, &agent.agent_id);
+                    batch.delete_cf(&cf_by_namespace, &old_key);
+                }
+            }
         }
         batch.put_cf(&cf_by_status, &status_agent_key, []);
 
+        // Update namespace index (idempotent)
+        if let Some(namespace_id) = &agent.namespace_id {
+            let namespace_agent_key = keys::namespace_agent_key(namespace_id, &agent.agent_id);
+            batch.put_cf(&cf_by_namespace, &namespace_agent_key, []);
+        }
+
         self.db
             .write(batch)
             .map_err(|e| StoreError::Database(e.to_string()))?;
@@ -129,8 +184,9 @@ impl Store for RocksStore {
         let cf_agents = self.cf(cf::AGENTS)?;
         let cf_by_user = self.cf(cf::AGENTS_BY_USER)?;
         let cf_by_status = self.cf(cf::AGENTS_BY_STATUS)?;
+        let cf_by_namespace = self.cf(cf::AGENTS_BY_NAMESPACE)?;
 
-        // Get the agent to find user_id and status
+        // Get the agent to find user_id, namespace and status
         let agent = self.get_agent(agent_id)?.ok_or(StoreError::NotFound)?;
 
         let agent_key = keys::agent_key(agent_id);
@@ -141,6 +197,27 @@ impl Store for RocksStore {
         batch.delete_cf(&cf_agents, &agent_key);
         batch.delete_cf(&cf_by_user, &user_agent_key);
         batch.delete_cf(&cf_by_status, &status_agent_key);
+        if let Some(namespace_id) = &agent.namespace_id {
+            let namespace_agent_key = keys::namespace_agent_key(namespace_id, agent_id);
+            batch.delete_cf(&cf_by_namespace, &namespace_agent_key);
+        }
+
+        // Revoke all access grants on the agent
+        let cf_grants = self.cf(cf::AGENT_GRANTS)?;
+        let cf_grants_by_user = self.cf(cf::GRANTS_BY_USER)?;
+        for grant in self.list_agent_grants(agent_id)? {
+            batch.delete_cf(&cf_grants, keys::agent_grant_key(agent_id, &grant.user_id));
+            batch.delete_cf(
+                &cf_grants_by_user,
+                keys::user_agent_key(&grant.user_id, agent_id),
+            );
+        }
+
+        // Delete the agent's secrets
+        let cf_secrets = self.cf(cf::AGENT_SECRETS)?;
+        for secret in self.list_agent_secrets(agent_id)? {
+            batch.delete_cf(&cf_secrets, keys::agent_secret_key(agent_id, &secret.name));
+        }
 
         self.db
             .write(batch)
@@ -199,6 +276,55 @@ impl Store for RocksStore {
         Ok(count)
     }
 
+    fn list_agents_by_namespace(&self, namespace_id: &NamespaceId) -> Result<Vec<Agent>> {
+        let cf_by_namespace = self.cf(cf::AGENTS_BY_NAMESPACE)?;
+        let prefix = keys::namespace_key(namespace_id);
+
+        let mut agents = Vec::new();
+        let iter = self.db.iterator_cf(
+            &cf_by_namespace,
+            IteratorMode::From(&prefix, rocksdb::Direction::Forward),
+        );
+
+        for item in iter {
+            let (key, _) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+
+            if !key.starts_with(&prefix) {
+                break;
+            }
+
+            let agent_id = keys::extract_agent_id_from_namespace_agent_key(&key);
+            if let Some(agent) = self.get_agent(&agent_id)? {
+                agents.push(agent);
+            }
+        }
+
+        Ok(agents)
+    }
+
+    fn count_agents_by_namespace(&self, namespace_id: &NamespaceId) -> Result<u32> {
+        let cf_by_namespace = self.cf(cf::AGENTS_BY_NAMESPACE)?;
+        let prefix = keys::namespace_key(namespace_id);
+
+        let mut count = 0u32;
+        let iter = self.db.iterator_cf(
+            &cf_by_namespace,
+            IteratorMode::From(&prefix, rocksdb::Direction::Forward),
+        );
+
+        for item in iter {
+            let (key, _) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+
+            if !key.starts_with(&prefix) {
+                break;
+            }
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+
     fn list_agents_by_status(&self, status: AgentState) -> Result<Vec<Agent>> {
         let cf_by_status = self.cf(cf::AGENTS_BY_STATUS)?;
         let prefix = keys::status_prefix(status.as_u8());
@@ -231,6 +357,9 @@ impl Store for RocksStore {
 
     fn update_agent_status(&self, agent_id: &AgentId, status: AgentState) -> Result<()> {
         let mut agent = self.get_agent(agent_id)?.ok_or(StoreError::NotFound)?;
+        if status == AgentState::Provisioning && agent.status != status {
+            agent.provisioning_started_at = Some(chrono::Utc::now());
+        }
         agent.status = status;
         agent.updated_at = chrono::Utc::now();
         // Clear error message when not in error state
@@ -247,6 +376,9 @@ impl Store for RocksStore {
         error_message: Option<String>,
     ) -> Result<()> {
         let mut agent = self.get_agent(agent_id)?.ok_or(StoreError::NotFound)?;
+        if status == AgentState::Provisioning && agent.status != status {
+            agent.provisioning_started_at = Some(chrono::Utc::now());
+        }
         agent.status = status;
         agent.error_message = error_message;
         agent.updated_at = chrono::Utc::now();
@@ -268,6 +400,175 @@ impl Store for RocksStore {
         Ok(agents)
     }
 
+    // =========================================================================
+    // Grant Operations
+    // =========================================================================
+
+    fn put_agent_grant(&self, grant: &AgentGrant) -> Result<()> {
+        let cf_grants = self.cf(cf::AGENT_GRANTS)?;
+        let cf_by_user = self.cf(cf::GRANTS_BY_USER)?;
+
+        let grant_key = keys::agent_grant_key(&grant.agent_id, &grant.user_id);
+        let user_agent_key = keys::user_agent_key(&grant.user_id, &grant.agent_id);
+        let value = Self::serialize(grant)?;
+
+        let mut batch = WriteBatch::default();
+        batch.put_cf(&cf_grants, &grant_key, &value);
+        batch.put_cf(&cf_by_user, &user_agent_key, []);
+
+        self.db
+            .write(batch)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn get_agent_grant(&self, agent_id: &AgentId, user_id: &UserId) -> Result<Option<AgentGrant>> {
+        let cf = self.cf(cf::AGENT_GRANTS)?;
+        let key = keys::agent_grant_key(agent_id, user_id);
+
+        self.db
+            .get_cf(&cf, key)
+            .map_err(|e| StoreError::Database(e.to_string()))?
+            .map(|data| Self::deserialize(&data))
+            .transpose()
+    }
+
+    fn list_agent_grants(&self, agent_id: &AgentId) -> Result<Vec<AgentGrant>> {
+        let cf = self.cf(cf::AGENT_GRANTS)?;
+        let prefix = keys::agent_prefix(agent_id);
+
+        let mut grants = Vec::new();
+        let iter = self.db.iterator_cf(
+            &cf,
+            IteratorMode::From(&prefix, rocksdb::Direction::Forward),
+        );
+
+        for item in iter {
+            let (key, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+
+            // Stop if we're past the prefix
+            if !key.starts_with(&prefix) {
+                break;
+            }
+
+            grants.push(Self::deserialize(&value)?);
+        }
+
+        Ok(grants)
+    }
+
+    fn list_grants_by_user(&self, user_id: &UserId) -> Result<Vec<AgentGrant>> {
+        let cf_by_user = self.cf(cf::GRANTS_BY_USER)?;
+        let prefix = keys::user_prefix(user_id);
+
+        let mut grants = Vec::new();
+        let iter = self.db.iterator_cf(
+            &cf_by_user,
+            IteratorMode::From(&prefix, rocksdb::Direction::Forward),
+        );
+
+        for item in iter {
+            let (key, _) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+
+            // Stop if we're past the prefix
+            if !key.starts_with(&prefix) {
+                break;
+            }
+
+            let agent_id = keys::extract_agent_id_from_user_agent_key(&key);
+            if let Some(grant) = self.get_agent_grant(&agent_id, user_id)? {
+                grants.push(grant);
+            }
+        }
+
+        Ok(grants)
+    }
+
+    fn delete_agent_grant(&self, agent_id: &AgentId, user_id: &UserId) -> Result<()> {
+        let cf_grants = self.cf(cf::AGENT_GRANTS)?;
+        let cf_by_user = self.cf(cf::GRANTS_BY_USER)?;
+
+        if self.get_agent_grant(agent_id, user_id)?.is_none() {
+            return Err(StoreError::NotFound);
+        }
+
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(&cf_grants, keys::agent_grant_key(agent_id, user_id));
+        batch.delete_cf(&cf_by_user, keys::user_agent_key(user_id, agent_id));
+
+        self.db
+            .write(batch)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // Secret Operations
+    // =========================================================================
+
+    fn put_agent_secret(&self, secret: &AgentSecret) -> Result<()> {
+        let cf = self.cf(cf::AGENT_SECRETS)?;
+        let key = keys::agent_secret_key(&secret.agent_id, &secret.name);
+        let value = Self::serialize(secret)?;
+
+        self.db
+            .put_cf(&cf, key, value)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn get_agent_secret(&self, agent_id: &AgentId, name: &str) -> Result<Option<AgentSecret>> {
+        let cf = self.cf(cf::AGENT_SECRETS)?;
+        let key = keys::agent_secret_key(agent_id, name);
+
+        self.db
+            .get_cf(&cf, key)
+            .map_err(|e| StoreError::Database(e.to_string()))?
+            .map(|data| Self::deserialize(&data))
+            .transpose()
+    }
+
+    fn list_agent_secrets(&self, agent_id: &AgentId) -> Result<Vec<AgentSecret>> {
+        let cf = self.cf(cf::AGENT_SECRETS)?;
+        let prefix = keys::agent_prefix(agent_id);
+
+        let mut secrets = Vec::new();
+        let iter = self.db.iterator_cf(
+            &cf,
+            IteratorMode::From(&prefix, rocksdb::Direction::Forward),
+        );
+
+        for item in iter {
+            let (key, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+
+            // Stop if we're past the prefix
+            if !key.starts_with(&prefix) {
+                break;
+            }
+
+            secrets.push(Self::deserialize(&value)?);
+        }
+
+        Ok(secrets)
+    }
+
+    fn delete_agent_secret(&self, agent_id: &AgentId, name: &str) -> Result<()> {
+        let cf = self.cf(cf::AGENT_SECRETS)?;
+
+        if self.get_agent_secret(agent_id, name)?.is_none() {
+            return Err(StoreError::NotFound);
+        }
+
+        self.db
+            .delete_cf(&cf, keys::agent_secret_key(agent_id, name))
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
     // =========================================================================
     // Session Operations
     // =========================================================================
@@ -349,6 +650,21 @@ impl Store for RocksStore {
         Ok(sessions)
     }
 
+    fn list_active_sessions(&self) -> Result<Vec<Session>> {
+        let cf = self.cf(cf::SESSIONS)?;
+
+        let mut sessions = Vec::new();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (_, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+            let session: Session = Self::deserialize(&value)?;
+            if session.status == SessionStatus::Active {
+                sessions.push(session);
+            }
+        }
+
+        Ok(sessions)
+    }
+
     fn update_session_status(&self, session_id: &SessionId, status: SessionStatus) -> Result<()> {
         let mut session = self.get_session(session_id)?.ok_or(StoreError::NotFound)?;
         session.status = status;
@@ -384,57 +700,641 @@ impl Store for RocksStore {
             .map(|data| Self::deserialize(&data))
             .transpose()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::types::AgentSpec;
-    use tempfile::TempDir;
+    // =========================================================================
+    // Namespace Operations
+    // =========================================================================
 
-    fn create_test_store() -> (RocksStore, TempDir) {
-        let dir = TempDir::new().unwrap();
-        let store = RocksStore::open(dir.path()).unwrap();
-        (store, dir)
-    }
+    fn put_namespace_quota(&self, quota: &NamespaceQuota) -> Result<()> {
+        let cf = self.cf(cf::NAMESPACE_QUOTAS)?;
+        let key = keys::namespace_key(&quota.namespace_id);
+        let value = Self::serialize(quota)?;
 
-    fn create_test_agent(user_id: &UserId, name: &str) -> Agent {
-        Agent {
-            agent_id: AgentId::generate_deterministic(user_id, name, 42),
-            user_id: *user_id,
-            name: name.to_string(),
-            status: AgentState::Running,
-            spec: AgentSpec::default(),
-            created_at: chrono::Utc::now(),
-            updated_at: chrono::Utc::now(),
-            last_heartbeat_at: None,
-            error_message: None,
-        }
+        self.db
+            .put_cf(&cf, key, value)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
     }
 
-    #[test]
-    fn agent_crud() {
-        let (store, _dir) = create_test_store();
-        let user_id = UserId::from_bytes([1u8; 32]);
-        let agent = create_test_agent(&user_id, "test-agent");
+    fn get_namespace_quota(&self, namespace_id: &NamespaceId) -> Result<Option<NamespaceQuota>> {
+        let cf = self.cf(cf::NAMESPACE_QUOTAS)?;
+        let key = keys::namespace_key(namespace_id);
 
-        // Create
-        store.put_agent(&agent).unwrap();
+        self.db
+            .get_cf(&cf, key)
+            .map_err(|e| StoreError::Database(e.to_string()))?
+            .map(|data| Self::deserialize(&data))
+            .transpose()
+    }
 
-        // Read
-        let retrieved = store.get_agent(&agent.agent_id).unwrap().unwrap();
-        assert_eq!(retrieved.name, agent.name);
-        assert_eq!(retrieved.status, AgentState::Running);
+    // =========================================================================
+    // Template Operations
+    // =========================================================================
 
-        // Update
-        store
-            .update_agent_status(&agent.agent_id, AgentState::Idle)
-            .unwrap();
-        let updated = store.get_agent(&agent.agent_id).unwrap().unwrap();
-        assert_eq!(updated.status, AgentState::Idle);
+    fn put_template(&self, template: &AgentTemplate) -> Result<()> {
+        let cf = self.cf(cf::TEMPLATES)?;
+        let key = keys::template_key(template.owner.as_ref(), &template.name);
+        let value = Self::serialize(template)?;
 
-        // Delete
-        store.delete_agent(&agent.agent_id).unwrap();
+        self.db
+            .put_cf(&cf, key, value)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn get_template(&self, owner: Option<&UserId>, name: &str) -> Result<Option<AgentTemplate>> {
+        let cf = self.cf(cf::TEMPLATES)?;
+        let key = keys::template_key(owner, name);
+
+        self.db
+            .get_cf(&cf, key)
+            .map_err(|e| StoreError::Database(e.to_string()))?
+            .map(|data| Self::deserialize(&data))
+            .transpose()
+    }
+
+    fn list_templates(&self, owner: Option<&UserId>) -> Result<Vec<AgentTemplate>> {
+        let cf = self.cf(cf::TEMPLATES)?;
+        let prefix = keys::template_scope_prefix(owner);
+
+        let mut templates = Vec::new();
+        let iter = self.db.iterator_cf(
+            &cf,
+            IteratorMode::From(&prefix, rocksdb::Direction::Forward),
+        );
+
+        for item in iter {
+            let (key, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+
+            // Stop if we're past the prefix
+            if !key.starts_with(&prefix) {
+                break;
+            }
+
+            templates.push(Self::deserialize(&value)?);
+        }
+
+        Ok(templates)
+    }
+
+    fn delete_template(&self, owner: Option<&UserId>, name: &str) -> Result<()> {
+        let cf = self.cf(cf::TEMPLATES)?;
+        let key = keys::template_key(owner, name);
+
+        if self.get_template(owner, name)?.is_none() {
+            return Err(StoreError::NotFound);
+        }
+
+        self.db
+            .delete_cf(&cf, key)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // Schedule Operations
+    // =========================================================================
+
+    fn put_schedule(&self, schedule: &AgentSchedule) -> Result<()> {
+        let cf = self.cf(cf::SCHEDULES)?;
+        let key = keys::agent_key(&schedule.agent_id);
+        let value = Self::serialize(schedule)?;
+
+        self.db
+            .put_cf(&cf, key, value)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn get_schedule(&self, agent_id: &AgentId) -> Result<Option<AgentSchedule>> {
+        let cf = self.cf(cf::SCHEDULES)?;
+        let key = keys::agent_key(agent_id);
+
+        self.db
+            .get_cf(&cf, key)
+            .map_err(|e| StoreError::Database(e.to_string()))?
+            .map(|data| Self::deserialize(&data))
+            .transpose()
+    }
+
+    fn delete_schedule(&self, agent_id: &AgentId) -> Result<()> {
+        let cf = self.cf(cf::SCHEDULES)?;
+        let key = keys::agent_key(agent_id);
+
+        if self.get_schedule(agent_id)?.is_none() {
+            return Err(StoreError::NotFound);
+        }
+
+        self.db
+            .delete_cf(&cf, key)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn list_schedules(&self) -> Result<Vec<AgentSchedule>> {
+        let cf = self.cf(cf::SCHEDULES)?;
+
+        let mut schedules = Vec::new();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (_, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+            schedules.push(Self::deserialize(&value)?);
+        }
+
+        Ok(schedules)
+    }
+
+    // =========================================================================
+    // Webhook Operations
+    // =========================================================================
+
+    fn put_webhook(&self, webhook: &Webhook) -> Result<()> {
+        let cf_webhooks = self.cf(cf::WEBHOOKS)?;
+        let cf_by_user = self.cf(cf::WEBHOOKS_BY_USER)?;
+
+        let webhook_key = keys::webhook_key(&webhook.webhook_id);
+        let user_webhook_key = keys::user_webhook_key(&webhook.user_id, &webhook.webhook_id);
+        let value = Self::serialize(webhook)?;
+
+        let mut batch = WriteBatch::default();
+        batch.put_cf(&cf_webhooks, &webhook_key, &value);
+        batch.put_cf(&cf_by_user, &user_webhook_key, []);
+
+        self.db
+            .write(batch)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn get_webhook(&self, webhook_id: &WebhookId) -> Result<Option<Webhook>> {
+        let cf = self.cf(cf::WEBHOOKS)?;
+        let key = keys::webhook_key(webhook_id);
+
+        self.db
+            .get_cf(&cf, key)
+            .map_err(|e| StoreError::Database(e.to_string()))?
+            .map(|data| Self::deserialize(&data))
+            .transpose()
+    }
+
+    fn list_webhooks_by_user(&self, user_id: &UserId) -> Result<Vec<Webhook>> {
+        let cf_by_user = self.cf(cf::WEBHOOKS_BY_USER)?;
+        let prefix = keys::user_prefix(user_id);
+
+        let mut webhooks = Vec::new();
+        let iter = self.db.iterator_cf(
+            &cf_by_user,
+            IteratorMode::From(&prefix, rocksdb::Direction::Forward),
+        );
+
+        for item in iter {
+            let (key, _) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+
+            // Stop if we're past the prefix
+            if !key.starts_with(&prefix) {
+                break;
+            }
+
+            let webhook_id = keys::extract_webhook_id_from_user_webhook_key(&key);
+            if let Some(webhook) = self.get_webhook(&webhook_id)? {
+                webhooks.push(webhook);
+            }
+        }
+
+        Ok(webhooks)
+    }
+
+    fn delete_webhook(&self, webhook_id: &WebhookId) -> Result<()> {
+        let cf_webhooks = self.cf(cf::WEBHOOKS)?;
+        let cf_by_user = self.cf(cf::WEBHOOKS_BY_USER)?;
+        let cf_deliveries = self.cf(cf::WEBHOOK_DELIVERIES)?;
+        let cf_outbox = self.cf(cf::WEBHOOK_OUTBOX)?;
+
+        let webhook = self.get_webhook(webhook_id)?.ok_or(StoreError::NotFound)?;
+
+        let mut batch = WriteBatch::default();
+        batch.delete_cf(&cf_webhooks, keys::webhook_key(webhook_id));
+        batch.delete_cf(
+            &cf_by_user,
+            keys::user_webhook_key(&webhook.user_id, webhook_id),
+        );
+
+        for (key, delivery) in self.scan_webhook_deliveries(webhook_id)? {
+            batch.delete_cf(&cf_deliveries, &key);
+            batch.delete_cf(&cf_outbox, keys::outbox_key(&delivery.delivery_id));
+        }
+
+        self.db
+            .write(batch)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn put_webhook_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+        let cf_deliveries = self.cf(cf::WEBHOOK_DELIVERIES)?;
+        let cf_outbox = self.cf(cf::WEBHOOK_OUTBOX)?;
+
+        let delivery_key = Self::delivery_key(delivery);
+        let outbox_key = keys::outbox_key(&delivery.delivery_id);
+        let value = Self::serialize(delivery)?;
+
+        let mut batch = WriteBatch::default();
+        batch.put_cf(&cf_deliveries, &delivery_key, &value);
+        if delivery.status == DeliveryStatus::Pending {
+            batch.put_cf(&cf_outbox, &outbox_key, &delivery_key);
+        } else {
+            batch.delete_cf(&cf_outbox, &outbox_key);
+        }
+
+        self.db
+            .write(batch)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn list_webhook_deliveries(
+        &self,
+        webhook_id: &WebhookId,
+        limit: usize,
+    ) -> Result<Vec<WebhookDelivery>> {
+        Ok(self
+            .scan_webhook_deliveries(webhook_id)?
+            .into_iter()
+            .rev()
+            .take(limit)
+            .map(|(_, delivery)| delivery)
+            .collect())
+    }
+
+    fn list_pending_webhook_deliveries(&self) -> Result<Vec<WebhookDelivery>> {
+        let cf_outbox = self.cf(cf::WEBHOOK_OUTBOX)?;
+        let cf_deliveries = self.cf(cf::WEBHOOK_DELIVERIES)?;
+
+        let mut deliveries = Vec::new();
+        for item in self.db.iterator_cf(&cf_outbox, IteratorMode::Start) {
+            let (_, delivery_key) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+
+            let delivery = self
+                .db
+                .get_cf(&cf_deliveries, &delivery_key)
+                .map_err(|e| StoreError::Database(e.to_string()))?;
+            if let Some(data) = delivery {
+                deliveries.push(Self::deserialize(&data)?);
+            }
+        }
+
+        Ok(deliveries)
+    }
+
+    fn prune_webhook_deliveries(&self, before: DateTime<Utc>) -> Result<usize> {
+        let cf = self.cf(cf::WEBHOOK_DELIVERIES)?;
+
+        let mut batch = WriteBatch::default();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (key, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+            let delivery: WebhookDelivery = Self::deserialize(&value)?;
+            if delivery.status != DeliveryStatus::Pending && delivery.created_at < before {
+                batch.delete_cf(&cf, &key);
+            }
+        }
+
+        let pruned = batch.len();
+        self.db
+            .write(batch)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(pruned)
+    }
+
+    // =========================================================================
+    // Audit Operations
+    // =========================================================================
+
+    fn append_audit_event(&self, event: &AuditEvent) -> Result<()> {
+        let cf = self.cf(cf::AUDIT_LOG)?;
+        let key = keys::audit_key(event.timestamp.timestamp_millis(), &event.event_id);
+        let value = Self::serialize(event)?;
+
+        self.db
+            .put_cf(&cf, key, value)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn list_audit_events(&self, query: &AuditQuery) -> Result<Vec<AuditEvent>> {
+        let cf = self.cf(cf::AUDIT_LOG)?;
+        let lower = query
+            .from
+            .map(|from| keys::audit_time_key(from.timestamp_millis()));
+        // Every key strictly below the `to` time key is before `to`
+        let upper = query
+            .to
+            .map(|to| keys::audit_time_key(to.timestamp_millis()));
+
+        let iter = match &upper {
+            Some(upper) => self
+                .db
+                .iterator_cf(&cf, IteratorMode::From(upper, rocksdb::Direction::Reverse)),
+            None => self.db.iterator_cf(&cf, IteratorMode::End),
+        };
+
+        let mut events = Vec::new();
+        for item in iter {
+            let (key, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+
+            if upper.as_deref().is_some_and(|upper| *key >= *upper) {
+                continue;
+            }
+            // Stop once we're before the start of the range
+            if lower.as_deref().is_some_and(|lower| *key < *lower) {
+                break;
+            }
+
+            let event: AuditEvent = Self::deserialize(&value)?;
+            if query.matches(&event) {
+                events.push(event);
+                if query.limit != 0 && events.len() >= query.limit {
+                    break;
+                }
+            }
+        }
+
+        Ok(events)
+    }
+
+    fn prune_audit_events(&self, before: DateTime<Utc>) -> Result<usize> {
+        let cf = self.cf(cf::AUDIT_LOG)?;
+        let cutoff = keys::audit_time_key(before.timestamp_millis());
+
+        let mut batch = WriteBatch::default();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (key, _) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+            if *key >= *cutoff {
+                break;
+            }
+            batch.delete_cf(&cf, &key);
+        }
+
+        let pruned = batch.len();
+        self.db
+            .write(batch)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(pruned)
+    }
+
+    // =========================================================================
+    // Usage Operations
+    // =========================================================================
+
+    fn add_usage(&self, records: &[UsageRecord]) -> Result<()> {
+        let cf = self.cf(cf::USAGE)?;
+
+        // Merge records for the same bucket before reading the stored totals
+        let mut buckets: std::collections::BTreeMap<Vec<u8>, UsageRecord> =
+            std::collections::BTreeMap::new();
+        for record in records {
+            buckets
+                .entry(keys::usage_key(record))
+                .and_modify(|bucket| bucket.add(record))
+                .or_insert_with(|| record.clone());
+        }
+
+        let mut batch = WriteBatch::default();
+        for (key, mut record) in buckets {
+            let existing = self
+                .db
+                .get_cf(&cf, &key)
+                .map_err(|e| StoreError::Database(e.to_string()))?;
+            if let Some(data) = existing {
+                let stored: UsageRecord = Self::deserialize(&data)?;
+                record.add(&stored);
+            }
+            batch.put_cf(&cf, &key, Self::serialize(&record)?);
+        }
+
+        self.db
+            .write(batch)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn list_usage(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<UsageRecord>> {
+        let cf = self.cf(cf::USAGE)?;
+        let lower = keys::usage_hour_key(from.timestamp());
+        let upper = keys::usage_hour_key(to.timestamp());
+
+        let mut records = Vec::new();
+        let iter = self
+            .db
+            .iterator_cf(&cf, IteratorMode::From(&lower, rocksdb::Direction::Forward));
+        for item in iter {
+            let (key, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+            if *key >= *upper {
+                break;
+            }
+            records.push(Self::deserialize(&value)?);
+        }
+
+        Ok(records)
+    }
+
+    // =========================================================================
+    // Rollout Operations
+    // =========================================================================
+
+    fn put_rollout(&self, rollout: &Rollout) -> Result<()> {
+        let cf = self.cf(cf::ROLLOUTS)?;
+        let key = keys::rollout_key(&rollout.rollout_id);
+        let value = Self::serialize(rollout)?;
+
+        self.db
+            .put_cf(&cf, key, value)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn get_rollout(&self, rollout_id: &RolloutId) -> Result<Option<Rollout>> {
+        let cf = self.cf(cf::ROLLOUTS)?;
+        let key = keys::rollout_key(rollout_id);
+
+        self.db
+            .get_cf(&cf, key)
+            .map_err(|e| StoreError::Database(e.to_string()))?
+            .map(|data| Self::deserialize(&data))
+            .transpose()
+    }
+
+    fn list_rollouts(&self) -> Result<Vec<Rollout>> {
+        let cf = self.cf(cf::ROLLOUTS)?;
+
+        let mut rollouts: Vec<Rollout> = Vec::new();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (_, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+            rollouts.push(Self::deserialize(&value)?);
+        }
+        rollouts.sort_by_key(|rollout| std::cmp::Reverse(rollout.created_at));
+
+        Ok(rollouts)
+    }
+
+    // =========================================================================
+    // Agent Deletion Operations
+    // =========================================================================
+
+    fn put_agent_deletion(&self, deletion: &AgentDeletion) -> Result<()> {
+        let cf = self.cf(cf::AGENT_DELETIONS)?;
+        let key = keys::agent_key(&deletion.agent_id);
+        let value = Self::serialize(deletion)?;
+
+        self.db
+            .put_cf(&cf, key, value)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn get_agent_deletion(&self, agent_id: &AgentId) -> Result<Option<AgentDeletion>> {
+        let cf = self.cf(cf::AGENT_DELETIONS)?;
+        let key = keys::agent_key(agent_id);
+
+        self.db
+            .get_cf(&cf, key)
+            .map_err(|e| StoreError::Database(e.to_string()))?
+            .map(|data| Self::deserialize(&data))
+            .transpose()
+    }
+
+    fn list_agent_deletions(&self) -> Result<Vec<AgentDeletion>> {
+        let cf = self.cf(cf::AGENT_DELETIONS)?;
+
+        let mut deletions = Vec::new();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (_, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+            deletions.push(Self::deserialize(&value)?);
+        }
+
+        Ok(deletions)
+    }
+
+    fn delete_agent_deletion(&self, agent_id: &AgentId) -> Result<()> {
+        let cf = self.cf(cf::AGENT_DELETIONS)?;
+
+        self.db
+            .delete_cf(&cf, keys::agent_key(agent_id))
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    // =========================================================================
+    // State Copy Operations
+    // =========================================================================
+
+    fn put_state_copy(&self, copy: &StateCopy) -> Result<()> {
+        let cf = self.cf(cf::STATE_COPIES)?;
+        let key = keys::agent_key(&copy.agent_id);
+        let value = Self::serialize(copy)?;
+
+        self.db
+            .put_cf(&cf, key, value)
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+
+    fn list_state_copies(&self) -> Result<Vec<StateCopy>> {
+        let cf = self.cf(cf::STATE_COPIES)?;
+
+        let mut copies = Vec::new();
+        for item in self.db.iterator_cf(&cf, IteratorMode::Start) {
+            let (_, value) = item.map_err(|e| StoreError::Database(e.to_string()))?;
+            copies.push(Self::deserialize(&value)?);
+        }
+
+        Ok(copies)
+    }
+
+    fn delete_state_copy(&self, agent_id: &AgentId) -> Result<()> {
+        let cf = self.cf(cf::STATE_COPIES)?;
+
+        self.db
+            .delete_cf(&cf, keys::agent_key(agent_id))
+            .map_err(|e| StoreError::Database(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        AgentRole, AgentSpec, AuditActor, AuditOutcome, RestartPolicy, RestartState,
+    };
+    use tempfile::TempDir;
+
+    fn create_test_store() -> (RocksStore, TempDir) {
+        let dir = TempDir::new().unwrap();
+        let store = RocksStore::open(dir.path()).unwrap();
+        (store, dir)
+    }
+
+    fn create_test_agent(user_id: &UserId, name: &str) -> Agent {
+        Agent {
+            agent_id: AgentId::generate_deterministic(user_id, name, 42),
+            user_id: *user_id,
+            namespace_id: None,
+            name: name.to_string(),
+            status: AgentState::Running,
+            spec: AgentSpec::default(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+            last_heartbeat_at: None,
+            error_message: None,
+            cloned_from: None,
+            template: None,
+            labels: std::collections::BTreeMap::new(),
+            restart_policy: RestartPolicy::Never,
+            restarts: RestartState::default(),
+            hibernation: None,
+            provisioning_started_at: None,
+        }
+    }
+
+    #[test]
+    fn agent_crud() {
+        let (store, _dir) = create_test_store();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let agent = create_test_agent(&user_id, "test-agent");
+
+        // Create
+        store.put_agent(&agent).unwrap();
+
+        // Read
+        let retrieved = store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(retrieved.name, agent.name);
+        assert_eq!(retrieved.status, AgentState::Running);
+
+        // Update
+        store
+            .update_agent_status(&agent.agent_id, AgentState::Idle)
+            .unwrap();
+        let updated = store.get_agent(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(updated.status, AgentState::Idle);
+
+        // Delete
+        store.delete_agent(&agent.agent_id).unwrap();
         assert!(store.get_agent(&agent.agent_id).unwrap().is_none());
     }
 
@@ -527,6 +1427,155 @@ mod tests {
         );
     }
 
+    #[test]
+    fn namespace_index_follows_agent() {
+        let (store, _dir) = create_test_store();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let tenant_a = NamespaceId::from_uuid(uuid::Uuid::new_v4());
+        let tenant_b = NamespaceId::from_uuid(uuid::Uuid::new_v4());
+
+        let mut agent = create_test_agent(&user_id, "scoped");
+        agent.namespace_id = Some(tenant_a);
+        store.put_agent(&agent).unwrap();
+        store
+            .put_agent(&create_test_agent(&user_id, "unscoped"))
+            .unwrap();
+
+        assert_eq!(store.count_agents_by_namespace(&tenant_a).unwrap(), 1);
+        assert_eq!(
+            store.list_agents_by_namespace(&tenant_a).unwrap()[0].name,
+            "scoped"
+        );
+
+        // Moving the agent re-indexes it
+        agent.namespace_id = Some(tenant_b);
+        store.put_agent(&agent).unwrap();
+        assert_eq!(store.count_agents_by_namespace(&tenant_a).unwrap(), 0);
+        assert_eq!(store.count_agents_by_namespace(&tenant_b).unwrap(), 1);
+
+        store.delete_agent(&agent.agent_id).unwrap();
+        assert!(store
+            .list_agents_by_namespace(&tenant_b)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn namespace_quota_crud() {
+        let (store, _dir) = create_test_store();
+        let namespace_id = NamespaceId::from_uuid(uuid::Uuid::new_v4());
+        assert!(store.get_namespace_quota(&namespace_id).unwrap().is_none());
+
+        let quota = NamespaceQuota {
+            namespace_id,
+            max_agents: Some(25),
+            max_sessions: None,
+            updated_at: chrono::Utc::now(),
+        };
+        store.put_namespace_quota(&quota).unwrap();
+
+        assert_eq!(
+            store.get_namespace_quota(&namespace_id).unwrap(),
+            Some(quota)
+        );
+    }
+
+    #[test]
+    fn agent_grants_are_indexed_by_grantee() {
+        let (store, _dir) = create_test_store();
+        let owner = UserId::from_bytes([1u8; 32]);
+        let colleague = UserId::from_bytes([2u8; 32]);
+        let agent = create_test_agent(&owner, "shared");
+        store.put_agent(&agent).unwrap();
+
+        let now = chrono::Utc::now();
+        let grant = AgentGrant {
+            agent_id: agent.agent_id,
+            user_id: colleague,
+            role: AgentRole::Viewer,
+            granted_by: owner,
+            created_at: now,
+            updated_at: now,
+        };
+        store.put_agent_grant(&grant).unwrap();
+
+        assert_eq!(
+            store.get_agent_grant(&agent.agent_id, &colleague).unwrap(),
+            Some(grant.clone())
+        );
+        assert_eq!(
+            store.list_agent_grants(&agent.agent_id).unwrap(),
+            vec![grant.clone()]
+        );
+        assert_eq!(store.list_grants_by_user(&colleague).unwrap(), vec![grant]);
+        assert!(store.list_grants_by_user(&owner).unwrap().is_empty());
+
+        // Deleting the agent revokes its grants
+        store.delete_agent(&agent.agent_id).unwrap();
+        assert!(store.list_grants_by_user(&colleague).unwrap().is_empty());
+        assert!(matches!(
+            store.delete_agent_grant(&agent.agent_id, &colleague),
+            Err(StoreError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn agent_secrets_are_scoped_to_their_agent() {
+        let (store, _dir) = create_test_store();
+        let owner = UserId::from_bytes([1u8; 32]);
+        let agent = create_test_agent(&owner, "with-secrets");
+        let other = create_test_agent(&owner, "other");
+        store.put_agent(&agent).unwrap();
+        store.put_agent(&other).unwrap();
+
+        let now = chrono::Utc::now();
+        let secret = |agent_id: AgentId, name: &str| AgentSecret {
+            agent_id,
+            name: name.to_string(),
+            ciphertext: vec![1, 2, 3],
+            updated_by: owner,
+            created_at: now,
+            updated_at: now,
+        };
+        store
+            .put_agent_secret(&secret(agent.agent_id, "GITHUB_TOKEN"))
+            .unwrap();
+        store
+            .put_agent_secret(&secret(agent.agent_id, "API_KEY"))
+            .unwrap();
+        store
+            .put_agent_secret(&secret(other.agent_id, "API_KEY"))
+            .unwrap();
+
+        let names: Vec<_> = store
+            .list_agent_secrets(&agent.agent_id)
+            .unwrap()
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(names, vec!["API_KEY", "GITHUB_TOKEN"]);
+
+        store
+            .delete_agent_secret(&agent.agent_id, "API_KEY")
+            .unwrap();
+        assert!(store
+            .get_agent_secret(&agent.agent_id, "API_KEY")
+            .unwrap()
+            .is_none());
+        assert!(matches!(
+            store.delete_agent_secret(&agent.agent_id, "API_KEY"),
+            Err(StoreError::NotFound)
+        ));
+
+        // Deleting the agent deletes its secrets, but not other agents'
+        store.delete_agent(&agent.agent_id).unwrap();
+        assert!(store
+            .list_agent_secrets(&agent.agent_id)
+            .unwrap()
+            .is_empty());
+        assert_eq!(store.list_agent_secrets(&other.agent_id).unwrap().len(), 1);
+    }
+
     #[test]
     fn session_crud() {
         let (store, _dir) = create_test_store();
@@ -538,9 +1587,11 @@ mod tests {
             session_id: SessionId::generate(),
             agent_id: agent.agent_id,
             user_id,
+            namespace_id: None,
             status: SessionStatus::Active,
             created_at: chrono::Utc::now(),
             closed_at: None,
+            last_activity_at: None,
         };
 
         // Create
@@ -563,6 +1614,80 @@ mod tests {
         assert!(store.get_session(&session.session_id).unwrap().is_none());
     }
 
+    #[test]
+    fn webhook_outbox_and_delivery_log() {
+        use crate::types::{DeliveryAttempt, WebhookEvent};
+        use aura_swarm_core::DeliveryId;
+
+        let (store, _dir) = create_test_store();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let now = chrono::Utc::now();
+
+        let webhook = Webhook {
+            webhook_id: WebhookId::generate(),
+            user_id,
+            url: "https://example.com/hooks".to_string(),
+            sealed_secret: b"sealed".to_vec(),
+            events: vec![WebhookEvent::AgentRunning],
+            description: None,
+            created_at: now,
+            updated_at: now,
+        };
+        store.put_webhook(&webhook).unwrap();
+        assert_eq!(store.list_webhooks_by_user(&user_id).unwrap().len(), 1);
+
+        let delivery = |offset_ms: i64| WebhookDelivery {
+            delivery_id: DeliveryId::generate(),
+            webhook_id: webhook.webhook_id,
+            event: WebhookEvent::AgentRunning,
+            payload: "{}".to_string(),
+            status: DeliveryStatus::Pending,
+            attempts: Vec::new(),
+            next_attempt_at: Some(now),
+            created_at: now + chrono::Duration::milliseconds(offset_ms),
+        };
+
+        let mut first = delivery(0);
+        let second = delivery(10);
+        store.put_webhook_delivery(&first).unwrap();
+        store.put_webhook_delivery(&second).unwrap();
+        assert_eq!(store.list_pending_webhook_deliveries().unwrap().len(), 2);
+
+        // Completing a delivery removes it from the outbox but keeps the log
+        first.status = DeliveryStatus::Succeeded;
+        first.next_attempt_at = None;
+        first.attempts.push(DeliveryAttempt {
+            attempted_at: now,
+            status_code: Some(200),
+            error: None,
+            duration_ms: 12,
+        });
+        store.put_webhook_delivery(&first).unwrap();
+
+        let pending = store.list_pending_webhook_deliveries().unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].delivery_id, second.delivery_id);
+
+        let log = store
+            .list_webhook_deliveries(&webhook.webhook_id, 10)
+            .unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].delivery_id, second.delivery_id); // newest first
+        assert_eq!(log[1].attempts.len(), 1);
+
+        // Pruning only removes finished deliveries
+        let pruned = store
+            .prune_webhook_deliveries(now + chrono::Duration::hours(1))
+            .unwrap();
+        assert_eq!(pruned, 1);
+
+        // Deleting the webhook clears its log and outbox entries
+        store.delete_webhook(&webhook.webhook_id).unwrap();
+        assert!(store.get_webhook(&webhook.webhook_id).unwrap().is_none());
+        assert!(store.list_webhooks_by_user(&user_id).unwrap().is_empty());
+        assert!(store.list_pending_webhook_deliveries().unwrap().is_empty());
+    }
+
     #[test]
     fn list_sessions_by_agent() {
         let (store, _dir) = create_test_store();
@@ -579,9 +1704,11 @@ mod tests {
                 session_id: SessionId::generate(),
                 agent_id: agent1.agent_id,
                 user_id,
+                namespace_id: None,
                 status: SessionStatus::Active,
                 created_at: chrono::Utc::now(),
                 closed_at: None,
+                last_activity_at: None,
             };
             store.put_session(&session).unwrap();
         }
@@ -591,9 +1718,11 @@ mod tests {
             session_id: SessionId::generate(),
             agent_id: agent2.agent_id,
             user_id,
+            namespace_id: None,
             status: SessionStatus::Active,
             created_at: chrono::Utc::now(),
             closed_at: None,
+            last_activity_at: None,
         };
         store.put_session(&session2).unwrap();
 
@@ -603,6 +1732,13 @@ mod tests {
 
         let agent2_sessions = store.list_sessions_by_agent(&agent2.agent_id).unwrap();
         assert_eq!(agent2_sessions.len(), 1);
+
+        // Closed sessions are excluded from the active scan
+        assert_eq!(store.list_active_sessions().unwrap().len(), 4);
+        store
+            .update_session_status(&session2.session_id, SessionStatus::Closed)
+            .unwrap();
+        assert_eq!(store.list_active_sessions().unwrap().len(), 3);
     }
 
     #[test]
@@ -629,4 +1765,310 @@ mod tests {
         let other_id = UserId::from_bytes([2u8; 32]);
         assert!(store.get_user(&other_id).unwrap().is_none());
     }
+
+    #[test]
+    fn template_crud_is_scoped() {
+        let (store, _dir) = create_test_store();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let other_user = UserId::from_bytes([2u8; 32]);
+
+        let template = |owner: Option<UserId>, name: &str| AgentTemplate {
+            name: name.to_string(),
+            owner,
+            description: None,
+            spec: AgentSpec::default(),
+            labels: std::collections::BTreeMap::new(),
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+
+        store.put_template(&template(None, "small")).unwrap();
+        store.put_template(&template(None, "large")).unwrap();
+        store
+            .put_template(&template(Some(user_id), "small"))
+            .unwrap();
+        store
+            .put_template(&template(Some(other_user), "mine"))
+            .unwrap();
+
+        let global = store.list_templates(None).unwrap();
+        let names: Vec<_> = global.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["large", "small"]);
+
+        let owned = store.list_templates(Some(&user_id)).unwrap();
+        assert_eq!(owned.len(), 1);
+        assert_eq!(owned[0].owner, Some(user_id));
+
+        assert!(store
+            .get_template(Some(&user_id), "mine")
+            .unwrap()
+            .is_none());
+        assert!(store
+            .get_template(Some(&other_user), "mine")
+            .unwrap()
+            .is_some());
+
+        store.delete_template(None, "small").unwrap();
+        assert!(store.get_template(None, "small").unwrap().is_none());
+        assert!(store
+            .get_template(Some(&user_id), "small")
+            .unwrap()
+            .is_some());
+        assert!(matches!(
+            store.delete_template(None, "small"),
+            Err(StoreError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn schedule_crud() {
+        use crate::types::{MissedRunPolicy, ScheduleAction, ScheduleRule};
+
+        let (store, _dir) = create_test_store();
+        let user_id = UserId::from_bytes([1u8; 32]);
+        let agent = create_test_agent(&user_id, "scheduled");
+
+        let schedule = AgentSchedule {
+            agent_id: agent.agent_id,
+            user_id,
+            timezone: "Europe/Berlin".to_string(),
+            rules: vec![ScheduleRule {
+                action: ScheduleAction::Wake,
+                cron: "0 8 * * Mon-Fri".to_string(),
+            }],
+            enabled: true,
+            missed_runs: MissedRunPolicy::RunLatest,
+            last_checked_at: chrono::Utc::now(),
+            last_run: None,
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        };
+
+        store.put_schedule(&schedule).unwrap();
+
+        let retrieved = store.get_schedule(&agent.agent_id).unwrap().unwrap();
+        assert_eq!(retrieved.timezone, "Europe/Berlin");
+        assert_eq!(retrieved.rules, schedule.rules);
+        assert_eq!(store.list_schedules().unwrap().len(), 1);
+
+        store.delete_schedule(&agent.agent_id).unwrap();
+        assert!(store.get_schedule(&agent.agent_id).unwrap().is_none());
+        assert!(matches!(
+            store.delete_schedule(&agent.agent_id),
+            Err(StoreError::NotFound)
+        ));
+    }
+
+    #[test]
+    fn audit_log_queries_and_retention() {
+        let (store, _dir) = create_test_store();
+        let alice = UserId::from_bytes([1u8; 32]);
+        let bob = UserId::from_bytes([2u8; 32]);
+        let start = chrono::Utc::now() - chrono::Duration::hours(3);
+
+        let event = |hours: i64, user_id: UserId| AuditEvent {
+            event_id: aura_swarm_core::AuditEventId::generate(),
+            timestamp: start + chrono::Duration::hours(hours),
+            event_type: "agent.created".to_string(),
+            actor: AuditActor {
+                user_id: Some(user_id),
+                ..AuditActor::default()
+            },
+            namespace_id: None,
+            resource: None,
+            action: "create".to_string(),
+            outcome: AuditOutcome::Success,
+            request_id: None,
+            details: std::collections::BTreeMap::new(),
+        };
+        let events = [event(0, alice), event(1, bob), event(2, alice)];
+        for event in &events {
+            store.append_audit_event(event).unwrap();
+        }
+
+        let all = AuditQuery {
+            limit: 10,
+            ..AuditQuery::default()
+        };
+        let listed = store.list_audit_events(&all).unwrap();
+        assert_eq!(
+            listed,
+            vec![events[2].clone(), events[1].clone(), events[0].clone()]
+        );
+
+        // Time range is [from, to)
+        let range = AuditQuery {
+            from: Some(events[1].timestamp),
+            to: Some(events[2].timestamp),
+            ..all.clone()
+        };
+        assert_eq!(
+            store.list_audit_events(&range).unwrap(),
+            vec![events[1].clone()]
+        );
+
+        let by_actor = AuditQuery {
+            user_id: Some(alice),
+            limit: 1,
+            ..all.clone()
+        };
+        assert_eq!(
+            store.list_audit_events(&by_actor).unwrap(),
+            vec![events[2].clone()]
+        );
+
+        let pruned = store.prune_audit_events(events[1].timestamp).unwrap();
+        assert_eq!(pruned, 1);
+        assert_eq!(store.list_audit_events(&all).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn usage_accumulates_per_bucket() {
+        let (store, _dir) = create_test_store();
+        let hour = chrono::DateTime::from_timestamp(7200, 0).unwrap();
+        let record = |hour: DateTime<Utc>, state: AgentState, seconds: u64| UsageRecord {
+            hour,
+            namespace_id: None,
+            user_id: UserId::from_bytes([1u8; 32]),
+            state,
+            agent_seconds: seconds,
+            cpu_millicore_seconds: seconds * 500,
+            memory_mb_seconds: seconds * 512,
+        };
+
+        store
+            .add_usage(&[
+                record(hour, AgentState::Running, 60),
+                record(hour, AgentState::Running, 30),
+                record(hour, AgentState::Idle, 10),
+            ])
+            .unwrap();
+        let next_hour = hour + chrono::Duration::hours(1);
+        store
+            .add_usage(&[
+                record(hour, AgentState::Running, 10),
+                record(next_hour, AgentState::Running, 5),
+            ])
+            .unwrap();
+
+        let usage = store.list_usage(hour, next_hour).unwrap();
+        assert_eq!(usage.len(), 2);
+        let running = usage
+            .iter()
+            .find(|r| r.state == AgentState::Running)
+            .unwrap();
+        assert_eq!(running.agent_seconds, 100);
+        assert_eq!(running.cpu_millicore_seconds, 50_000);
+
+        let all = store
+            .list_usage(hour, next_hour + chrono::Duration::hours(1))
+            .unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all.last().unwrap().hour, next_hour);
+    }
+
+    #[test]
+    fn rollouts_are_listed_newest_first() {
+        use crate::types::{
+            RolloutSelector, RolloutStatus, RolloutStrategy, RolloutTarget, RolloutTargetState,
+        };
+
+        let (store, _dir) = create_test_store();
+        let rollout = |target_version: &str, minutes_ago: i64| Rollout {
+            rollout_id: RolloutId::generate(),
+            target_version: target_version.to_string(),
+            selector: RolloutSelector::default(),
+            strategy: RolloutStrategy::default(),
+            status: RolloutStatus::InProgress,
+            targets: vec![RolloutTarget {
+                agent_id: AgentId::from_bytes([1u8; 32]),
+                previous_version: "v1".to_string(),
+                state: RolloutTargetState::Pending,
+                restarted: false,
+                started_at: None,
+                error: None,
+            }],
+            next_batch_at: None,
+            message: None,
+            created_by: aura_swarm_core::IdentityId::from_uuid(uuid::Uuid::nil()),
+            created_at: Utc::now() - chrono::Duration::minutes(minutes_ago),
+            updated_at: Utc::now(),
+        };
+
+        let older = rollout("v2", 10);
+        let newer = rollout("v3", 1);
+        store.put_rollout(&older).unwrap();
+        store.put_rollout(&newer).unwrap();
+
+        let mut updated = older.clone();
+        updated.status = RolloutStatus::Completed;
+        store.put_rollout(&updated).unwrap();
+
+        assert_eq!(store.get_rollout(&older.rollout_id).unwrap(), Some(updated));
+        let listed = store.list_rollouts().unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].target_version, "v3");
+    }
+
+    #[test]
+    fn agent_deletions_round_trip() {
+        use crate::types::{DeletionStatus, DeletionStep};
+
+        let (store, _dir) = create_test_store();
+        let agent_id = AgentId::from_bytes([4u8; 32]);
+        let mut deletion = AgentDeletion {
+            agent_id,
+            user_id: UserId::from_bytes([1u8; 32]),
+            namespace_id: None,
+            requested_by: UserId::from_bytes([1u8; 32]),
+            status: DeletionStatus::InProgress,
+            completed_steps: Vec::new(),
+            failed_step: None,
+            error: None,
+            attempts: 1,
+            next_attempt_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        assert_eq!(deletion.next_step(), Some(DeletionStep::PurgeSessions));
+        store.put_agent_deletion(&deletion).unwrap();
+
+        deletion.completed_steps = DeletionStep::ALL[..2].to_vec();
+        deletion.status = DeletionStatus::Failed;
+        deletion.failed_step = Some(DeletionStep::DeleteState);
+        store.put_agent_deletion(&deletion).unwrap();
+
+        let stored = store.get_agent_deletion(&agent_id).unwrap().unwrap();
+        assert_eq!(stored, deletion);
+        assert_eq!(stored.next_step(), Some(DeletionStep::DeleteState));
+        assert_eq!(store.list_agent_deletions().unwrap().len(), 1);
+        assert!(store
+            .get_agent_deletion(&AgentId::from_bytes([5u8; 32]))
+            .unwrap()
+            .is_none());
+
+        store.delete_agent_deletion(&agent_id).unwrap();
+        assert!(store.get_agent_deletion(&agent_id).unwrap().is_none());
+        store.delete_agent_deletion(&agent_id).unwrap();
+    }
+
+    #[test]
+    fn state_copies_round_trip() {
+        let (store, _dir) = create_test_store();
+        let copy = StateCopy {
+            agent_id: AgentId::from_bytes([4u8; 32]),
+            source_id: AgentId::from_bytes([5u8; 32]),
+            attempts: 0,
+            next_attempt_at: None,
+            created_at: Utc::now(),
+        };
+        store.put_state_copy(&copy).unwrap();
+        assert_eq!(store.list_state_copies().unwrap(), vec![copy.clone()]);
+
+        store.delete_state_copy(&copy.agent_id).unwrap();
+        assert!(store.list_state_copies().unwrap().is_empty());
+
+        // Deleting again is fine
+        store.delete_state_copy(&copy.agent_id).unwrap();
+    }
 }
@@ -13,6 +13,21 @@ pub mod cf {
     /// Index: agents by user, keyed by `user_id || agent_id`.
     pub const AGENTS_BY_USER: &str = "agents_by_user";
 
+    /// Index: agents by namespace, keyed by `namespace_id || agent_id`.
+    pub const AGENTS_BY_NAMESPACE: &str = "agents_by_namespace";
+
+    /// Agent access grants, keyed by `agent_id || user_id`.
+    pub const AGENT_GRANTS: &str = "agent_grants";
+
+    /// Index: grants by grantee, keyed by `user_id || agent_id`.
+    pub const GRANTS_BY_USER: &str = "grants_by_user";
+
+    /// Encrypted per-agent secrets, keyed by `agent_id || name`.
+    pub const AGENT_SECRETS: &str = "agent_secrets";
+
+    /// Per-namespace quota overrides, keyed by `namespace_id`.
+    pub const NAMESPACE_QUOTAS: &str = "namespace_quotas";
+
     /// Primary session records, keyed by `session_id`.
     pub const SESSIONS: &str = "sessions";
 
@@ -21,6 +36,41 @@ pub mod cf {
 
     /// User records (synced from Zero-ID), keyed by `user_id`.
     pub const USERS: &str = "users";
+
+    /// Agent templates, keyed by `scope || name`.
+    pub const TEMPLATES: &str = "templates";
+
+    /// Agent wake/hibernate schedules, keyed by `agent_id`.
+    pub const SCHEDULES: &str = "schedules";
+
+    /// Webhook endpoints, keyed by `webhook_id`.
+    pub const WEBHOOKS: &str = "webhooks";
+
+    /// Index: webhooks by user, keyed by `user_id || webhook_id`.
+    pub const WEBHOOKS_BY_USER: &str = "webhooks_by_user";
+
+    /// Webhook delivery log, keyed by `webhook_id || created_at_millis || delivery_id`.
+    pub const WEBHOOK_DELIVERIES: &str = "webhook_deliveries";
+
+    /// Outbox of pending deliveries, keyed by `delivery_id`.
+    ///
+    /// Values are the corresponding `webhook_deliveries` key.
+    pub const WEBHOOK_OUTBOX: &str = "webhook_outbox";
+
+    /// Append-only audit log, keyed by `timestamp_millis || event_id`.
+    pub const AUDIT_LOG: &str = "audit_log";
+
+    /// Hourly usage buckets, keyed by `hour || namespace_id || user_id || state`.
+    pub const USAGE: &str = "usage";
+
+    /// Runtime rollouts, keyed by `rollout_id`.
+    pub const ROLLOUTS: &str = "rollouts";
+
+    /// Agent deletion jobs, keyed by `agent_id`.
+    pub const AGENT_DELETIONS: &str = "agent_deletions";
+
+    /// Pending clone state copies, keyed by the clone's `agent_id`.
+    pub const STATE_COPIES: &str = "state_copies";
 }
 
 /// Returns all column family names for database initialization.
@@ -30,8 +80,24 @@ pub fn all_column_families() -> Vec<&'static str> {
         cf::AGENTS,
         cf::AGENTS_BY_STATUS,
         cf::AGENTS_BY_USER,
+        cf::AGENTS_BY_NAMESPACE,
+        cf::AGENT_GRANTS,
+        cf::GRANTS_BY_USER,
+        cf::AGENT_SECRETS,
+        cf::NAMESPACE_QUOTAS,
         cf::SESSIONS,
         cf::SESSIONS_BY_AGENT,
         cf::USERS,
+        cf::TEMPLATES,
+        cf::SCHEDULES,
+        cf::WEBHOOKS,
+        cf::WEBHOOKS_BY_USER,
+        cf::WEBHOOK_DELIVERIES,
+        cf::WEBHOOK_OUTBOX,
+        cf::AUDIT_LOG,
+        cf::USAGE,
+        cf::ROLLOUTS,
+        cf::AGENT_DELETIONS,
+        cf::STATE_COPIES,
     ]
 }
@@ -1,8 +1,16 @@
 //! Domain types stored in the database.
 //!
-//! These types represent the persisted state of agents, sessions, and users.
+//! These types represent the persisted state of agents, sessions, users, and
+//! the supporting records (templates, schedules, webhooks, audit events)
+//! attached to them.
 
-use aura_swarm_core::{AgentId, SessionId, UserId};
+use std::collections::BTreeMap;
+use std::net::IpAddr;
+
+use aura_swarm_core::{
+    AgentId, AuditEventId, DeliveryId, IdentityId, NamespaceId, RolloutId, SessionId, UserId,
+    WebhookId,
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +21,12 @@ pub struct Agent {
     pub agent_id: AgentId,
     /// Owner user ID.
     pub user_id: UserId,
+    /// Namespace (tenant) the agent belongs to.
+    ///
+    /// `None` for agents created before namespace scoping; those remain
+    /// reachable by their owner only.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<NamespaceId>,
     /// Human-readable name.
     pub name: String,
     /// Current lifecycle state.
@@ -28,6 +42,98 @@ pub struct Agent {
     /// Error message when agent is in Error state (e.g., provisioning failure).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub error_message: Option<String>,
+    /// The agent this one was cloned from, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloned_from: Option<AgentId>,
+    /// Name of the template the agent was created from, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Free-form labels for grouping and selecting agents.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Policy for automatically re-provisioning the agent after errors.
+    #[serde(default, skip_serializing_if = "RestartPolicy::is_never")]
+    pub restart_policy: RestartPolicy,
+    /// Automatic restart bookkeeping.
+    #[serde(default, skip_serializing_if = "RestartState::is_empty")]
+    pub restarts: RestartState,
+    /// Outcome of the most recent hibernation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hibernation: Option<HibernationCheckpoint>,
+    /// When the agent last entered `Provisioning`.
+    ///
+    /// `None` if the agent has not started provisioning a pod yet (e.g. a
+    /// clone whose state is still being copied) or was stored before this
+    /// was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provisioning_started_at: Option<DateTime<Utc>>,
+}
+
+/// Policy for automatically re-provisioning agents that enter `Error`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(tag = "policy", rename_all = "snake_case")]
+pub enum RestartPolicy {
+    /// Leave failed agents in `Error` until a user restarts them.
+    #[default]
+    Never,
+    /// Re-provision failed agents with exponential backoff.
+    OnFailure {
+        /// Automatic restarts allowed before giving up.
+        max_attempts: u32,
+    },
+}
+
+impl RestartPolicy {
+    /// Whether automatic restarts are disabled.
+    #[must_use]
+    pub const fn is_never(&self) -> bool {
+        matches!(self, Self::Never)
+    }
+
+    /// Number of automatic restarts allowed (zero for `Never`).
+    #[must_use]
+    pub const fn max_attempts(&self) -> u32 {
+        match self {
+            Self::Never => 0,
+            Self::OnFailure { max_attempts } => *max_attempts,
+        }
+    }
+}
+
+/// Automatic restart bookkeeping for an agent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestartState {
+    /// Automatic restarts since the agent was last started by a user or ran stably.
+    #[serde(default)]
+    pub attempts: u32,
+    /// When the next automatic restart is due, if one is pending.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_restart_at: Option<DateTime<Utc>>,
+    /// When the last automatic restart happened.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_restart_at: Option<DateTime<Utc>>,
+}
+
+impl RestartState {
+    /// Whether no automatic restarts have been made or scheduled.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Record of an agent's most recent hibernation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HibernationCheckpoint {
+    /// When the agent was hibernated.
+    pub hibernated_at: DateTime<Utc>,
+    /// Size of the state the runtime flushed, if it reported one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_size_bytes: Option<u64>,
+    /// Whether the pod was stopped without the runtime acknowledging the
+    /// hibernation, so in-flight state may have been lost.
+    #[serde(default)]
+    pub forced: bool,
 }
 
 /// Resource specification for an agent.
@@ -43,6 +149,28 @@ pub struct AgentSpec {
     /// If not specified, uses the scheduler's default.
     #[serde(default)]
     pub isolation: Option<IsolationLevel>,
+    /// Additional environment variables passed to the agent runtime.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub env: BTreeMap<String, String>,
+    /// Initial system prompt for the agent runtime.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prompt: Option<String>,
+    /// Size of the agent's state volume in gigabytes.
+    /// If not specified, uses the scheduler's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_gb: Option<u32>,
+    /// Storage class for the agent's state volume.
+    /// If not specified, uses the scheduler's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage_class: Option<String>,
+    /// Outbound network access for the agent's pod.
+    /// If not specified, uses the scheduler's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub egress: Option<EgressPolicy>,
+    /// Node placement for the agent's pod, added to the scheduler's defaults
+    /// for its isolation level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub placement: Option<AgentPlacement>,
 }
 
 impl Default for AgentSpec {
@@ -52,6 +180,12 @@ impl Default for AgentSpec {
             memory_mb: 512,
             runtime_version: "latest".to_string(),
             isolation: None, // Uses scheduler default
+            env: BTreeMap::new(),
+            system_prompt: None,
+            storage_gb: None,    // Uses scheduler default
+            storage_class: None, // Uses scheduler default
+            egress: None,        // Uses scheduler default
+            placement: None,     // Uses scheduler default
         }
     }
 }
@@ -88,6 +222,220 @@ impl IsolationLevel {
     }
 }
 
+/// Outbound network access for an agent's pod.
+///
+/// DNS and the control plane are always reachable, whatever the policy.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum EgressPolicy {
+    /// Block all other outbound traffic.
+    DenyAll,
+    /// Allow only the listed destinations.
+    Allowlist {
+        /// Allowed destinations.
+        #[serde(default)]
+        rules: Vec<EgressRule>,
+    },
+    /// Allow any public internet destination. Private address ranges stay
+    /// blocked.
+    #[default]
+    AllowInternet,
+}
+
+impl EgressPolicy {
+    /// IPv4 ranges no policy lets an agent reach: private, shared, and
+    /// link-local ranges, which include the cluster network and cloud
+    /// metadata endpoints.
+    pub const PRIVATE_IPV4_RANGES: [&'static str; 5] = [
+        "10.0.0.0/8",
+        "100.64.0.0/10",
+        "169.254.0.0/16",
+        "172.16.0.0/12",
+        "192.168.0.0/16",
+    ];
+
+    /// IPv6 counterparts of [`EgressPolicy::PRIVATE_IPV4_RANGES`].
+    pub const PRIVATE_IPV6_RANGES: [&'static str; 2] = ["fc00::/7", "fe80::/10"];
+
+    /// Whether the address range `address/prefix_len` overlaps any of the
+    /// private ranges.
+    #[must_use]
+    pub fn overlaps_private_range(address: IpAddr, prefix_len: u8) -> bool {
+        let ranges: &[&str] = if address.is_ipv4() {
+            &Self::PRIVATE_IPV4_RANGES
+        } else {
+            &Self::PRIVATE_IPV6_RANGES
+        };
+        ranges
+            .iter()
+            .filter_map(|range| {
+                let (network, len) = range.split_once('/')?;
+                Some((network.parse::<IpAddr>().ok()?, len.parse::<u8>().ok()?))
+            })
+            .any(|(network, len)| {
+                let common = prefix_len.min(len);
+                network_bits(address, common) == network_bits(network, common)
+            })
+    }
+}
+
+/// The first `prefix_len` bits of an address.
+fn network_bits(address: IpAddr, prefix_len: u8) -> u128 {
+    let (bits, width) = match address {
+        IpAddr::V4(v4) => (u128::from(u32::from(v4)), 32),
+        IpAddr::V6(v6) => (u128::from(v6), 128),
+    };
+    bits.checked_shr(width - u32::from(prefix_len).min(width))
+        .unwrap_or(0)
+}
+
+/// A destination an agent may reach under [`EgressPolicy::Allowlist`].
+///
+/// Exactly one of `cidr` and `host` is set.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct EgressRule {
+    /// Destination address range, e.g. `203.0.113.0/24`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cidr: Option<String>,
+    /// Destination host name. It is resolved to addresses when the agent's
+    /// pod is scheduled, and those addresses are kept until the pod is next
+    /// scheduled, even if the name's DNS records change. Addresses in private
+    /// ranges are left out.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// Allowed TCP ports. Empty allows every port.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ports: Vec<u16>,
+}
+
+/// Which nodes an agent's pod may run on and how it is spread across them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentPlacement {
+    /// Node labels the pod's node must have.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub node_selector: BTreeMap<String, String>,
+    /// Node taints the pod tolerates.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tolerations: Vec<PlacementToleration>,
+    /// How agent pods are spread across topology domains such as zones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topology_spread: Vec<TopologySpread>,
+    /// Kubernetes `PriorityClass` for the pod.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority_class: Option<String>,
+}
+
+impl AgentPlacement {
+    /// Layer `overrides` on top of this placement.
+    ///
+    /// Node selector entries and the priority class in `overrides` win,
+    /// tolerations are added, and topology spread constraints replace these
+    /// if any are given.
+    #[must_use]
+    pub fn merge(&self, overrides: &Self) -> Self {
+        let mut merged = self.clone();
+        merged.node_selector.extend(
+            overrides
+                .node_selector
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+        merged
+            .tolerations
+            .extend(overrides.tolerations.iter().cloned());
+        if !overrides.topology_spread.is_empty() {
+            merged
+                .topology_spread
+                .clone_from(&overrides.topology_spread);
+        }
+        if overrides.priority_class.is_some() {
+            merged.priority_class.clone_from(&overrides.priority_class);
+        }
+        merged
+    }
+}
+
+/// A node taint an agent's pod tolerates.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlacementToleration {
+    /// Taint key. Empty with [`TolerationOperator::Exists`] matches every
+    /// taint.
+    #[serde(default)]
+    pub key: String,
+    /// How the taint's value is matched.
+    #[serde(default)]
+    pub operator: TolerationOperator,
+    /// Taint value, for [`TolerationOperator::Equal`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// Taint effect to tolerate. If not specified, every effect is tolerated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effect: Option<TaintEffect>,
+}
+
+/// How a toleration matches a taint's value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TolerationOperator {
+    /// The taint's value must equal the toleration's.
+    #[default]
+    Equal,
+    /// Any value matches.
+    Exists,
+}
+
+impl TolerationOperator {
+    /// The Kubernetes name of this operator.
+    #[must_use]
+    pub const fn as_k8s_str(self) -> &'static str {
+        match self {
+            Self::Equal => "Equal",
+            Self::Exists => "Exists",
+        }
+    }
+}
+
+/// The effect of a node taint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaintEffect {
+    /// New pods are not scheduled on the node.
+    NoSchedule,
+    /// The scheduler avoids the node if it can.
+    PreferNoSchedule,
+    /// Running pods are evicted from the node.
+    NoExecute,
+}
+
+impl TaintEffect {
+    /// The Kubernetes name of this effect.
+    #[must_use]
+    pub const fn as_k8s_str(self) -> &'static str {
+        match self {
+            Self::NoSchedule => "NoSchedule",
+            Self::PreferNoSchedule => "PreferNoSchedule",
+            Self::NoExecute => "NoExecute",
+        }
+    }
+}
+
+/// How agent pods are spread across a topology domain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TopologySpread {
+    /// Node label defining the domains, e.g. `topology.kubernetes.io/zone`.
+    pub topology_key: String,
+    /// Largest allowed difference in agent pod count between two domains.
+    #[serde(default = "default_max_skew")]
+    pub max_skew: u32,
+    /// Whether a pod that cannot satisfy the spread stays pending.
+    #[serde(default)]
+    pub required: bool,
+}
+
+fn default_max_skew() -> u32 {
+    1
+}
+
 /// Lifecycle states for an agent.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -130,6 +478,20 @@ impl AgentState {
             _ => None,
         }
     }
+
+    /// The state's wire name.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Provisioning => "provisioning",
+            Self::Running => "running",
+            Self::Idle => "idle",
+            Self::Hibernating => "hibernating",
+            Self::Stopping => "stopping",
+            Self::Stopped => "stopped",
+            Self::Error => "error",
+        }
+    }
 }
 
 /// A session record stored in the database.
@@ -141,12 +503,26 @@ pub struct Session {
     pub agent_id: AgentId,
     /// User who owns this session.
     pub user_id: UserId,
+    /// Namespace of the agent the session belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<NamespaceId>,
     /// Current session status.
     pub status: SessionStatus,
     /// Creation timestamp.
     pub created_at: DateTime<Utc>,
     /// When the session was closed (if closed).
     pub closed_at: Option<DateTime<Utc>>,
+    /// When traffic last flowed over the session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_activity_at: Option<DateTime<Utc>>,
+}
+
+impl Session {
+    /// When the session was last active, falling back to its creation time.
+    #[must_use]
+    pub fn last_active_at(&self) -> DateTime<Utc> {
+        self.last_activity_at.unwrap_or(self.created_at)
+    }
 }
 
 /// Status of a session.
@@ -182,3 +558,716 @@ pub struct User {
     /// Last login timestamp.
     pub last_login_at: Option<DateTime<Utc>>,
 }
+
+/// Quota overrides for a namespace (tenant).
+///
+/// Unset limits fall back to the control plane's configured defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NamespaceQuota {
+    /// Namespace the quota applies to.
+    pub namespace_id: NamespaceId,
+    /// Maximum number of agents in the namespace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_agents: Option<u32>,
+    /// Maximum number of active sessions across the namespace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_sessions: Option<u32>,
+    /// Last modification timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Access level granted on an agent.
+///
+/// Roles are ordered: each role includes everything the previous one allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AgentRole {
+    /// Read the agent's status and watch its sessions.
+    Viewer,
+    /// Open sessions and start, stop, hibernate or wake the agent.
+    Operator,
+    /// Delete or clone the agent and manage who can access it.
+    Owner,
+}
+
+/// A collaborator's access to an agent they do not own.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentGrant {
+    /// Agent the grant applies to.
+    pub agent_id: AgentId,
+    /// User the agent is shared with.
+    pub user_id: UserId,
+    /// Access level granted.
+    pub role: AgentRole,
+    /// User who granted the access.
+    pub granted_by: UserId,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A user-managed secret injected into an agent's pod as an environment
+/// variable.
+///
+/// The value is sealed by the control plane before it is stored; the store
+/// only ever sees the ciphertext.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentSecret {
+    /// Agent the secret belongs to.
+    pub agent_id: AgentId,
+    /// Secret name, also the environment variable it is exposed as.
+    pub name: String,
+    /// Encrypted value.
+    pub ciphertext: Vec<u8>,
+    /// User who last set the value.
+    pub updated_by: UserId,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A reusable agent template stored in the database.
+///
+/// Templates are either admin-defined (visible to all users) or
+/// user-defined (visible only to their owner).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentTemplate {
+    /// Template name, unique within its scope.
+    pub name: String,
+    /// Owner of a user-defined template; `None` for admin-defined templates.
+    pub owner: Option<UserId>,
+    /// Human-readable description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Agent specification applied to agents created from this template.
+    pub spec: AgentSpec,
+    /// Labels applied to agents created from this template.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// An automatic wake/hibernate schedule attached to an agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentSchedule {
+    /// The agent this schedule controls.
+    pub agent_id: AgentId,
+    /// Owner of the agent.
+    pub user_id: UserId,
+    /// IANA time zone the cron expressions are evaluated in (e.g., `Europe/Berlin`).
+    pub timezone: String,
+    /// Cron rules, each triggering a lifecycle action.
+    pub rules: Vec<ScheduleRule>,
+    /// Whether the schedule is currently active.
+    pub enabled: bool,
+    /// What to do with runs missed while the control plane was unavailable.
+    #[serde(default)]
+    pub missed_runs: MissedRunPolicy,
+    /// Fire times up to this instant have been processed.
+    pub last_checked_at: DateTime<Utc>,
+    /// The most recent run, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run: Option<ScheduleRun>,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+/// A single cron rule within an agent schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleRule {
+    /// The lifecycle action to perform.
+    pub action: ScheduleAction,
+    /// Cron expression (5-field `min hour dom month dow`, or 6/7-field with seconds).
+    pub cron: String,
+}
+
+/// Lifecycle action triggered by a schedule rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleAction {
+    /// Wake a hibernating or stopped agent.
+    Wake,
+    /// Hibernate a running or idle agent.
+    Hibernate,
+}
+
+/// Handling of schedule runs missed during control-plane downtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedRunPolicy {
+    /// Perform only the most recent missed action.
+    #[default]
+    RunLatest,
+    /// Drop runs that are older than the grace period.
+    Skip,
+}
+
+/// Record of a schedule run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRun {
+    /// The action that was due.
+    pub action: ScheduleAction,
+    /// When the run was due according to the schedule.
+    pub scheduled_for: DateTime<Utc>,
+    /// When the run was processed.
+    pub executed_at: DateTime<Utc>,
+    /// What happened.
+    pub outcome: ScheduleRunOutcome,
+    /// Additional detail (skip reason or error message).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Outcome of a schedule run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduleRunOutcome {
+    /// The lifecycle operation was performed.
+    Executed,
+    /// The run was not needed or was dropped by the missed-run policy.
+    Skipped,
+    /// The lifecycle operation failed.
+    Failed,
+}
+
+/// A user-registered webhook endpoint for agent lifecycle events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Webhook {
+    /// Unique identifier for the webhook.
+    pub webhook_id: WebhookId,
+    /// User who registered the webhook.
+    pub user_id: UserId,
+    /// Endpoint URL that receives deliveries.
+    pub url: String,
+    /// Shared secret used to sign deliveries, sealed by the control plane.
+    pub sealed_secret: Vec<u8>,
+    /// Events to deliver. Empty means all events.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<WebhookEvent>,
+    /// Human-readable description.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Last modification timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    /// Returns true if the webhook should receive the given event.
+    ///
+    /// Pings are always delivered regardless of the event filter.
+    #[must_use]
+    pub fn subscribes_to(&self, event: WebhookEvent) -> bool {
+        event == WebhookEvent::Ping || self.events.is_empty() || self.events.contains(&event)
+    }
+}
+
+/// An event that can be delivered to a webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WebhookEvent {
+    /// The agent became `Running`.
+    #[serde(rename = "agent.running")]
+    AgentRunning,
+    /// The agent became `Idle`.
+    #[serde(rename = "agent.idle")]
+    AgentIdle,
+    /// The agent became `Hibernating`.
+    #[serde(rename = "agent.hibernated")]
+    AgentHibernated,
+    /// The agent became `Stopped`.
+    #[serde(rename = "agent.stopped")]
+    AgentStopped,
+    /// The agent entered the `Error` state.
+    #[serde(rename = "agent.error")]
+    AgentError,
+    /// A test delivery requested by the user.
+    #[serde(rename = "ping")]
+    Ping,
+}
+
+impl WebhookEvent {
+    /// The event name as sent on the wire.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::AgentRunning => "agent.running",
+            Self::AgentIdle => "agent.idle",
+            Self::AgentHibernated => "agent.hibernated",
+            Self::AgentStopped => "agent.stopped",
+            Self::AgentError => "agent.error",
+            Self::Ping => "ping",
+        }
+    }
+
+    /// The event emitted when an agent enters `state`, if any.
+    #[must_use]
+    pub const fn for_state(state: AgentState) -> Option<Self> {
+        match state {
+            AgentState::Running => Some(Self::AgentRunning),
+            AgentState::Idle => Some(Self::AgentIdle),
+            AgentState::Hibernating => Some(Self::AgentHibernated),
+            AgentState::Stopped => Some(Self::AgentStopped),
+            AgentState::Error => Some(Self::AgentError),
+            AgentState::Provisioning | AgentState::Stopping => None,
+        }
+    }
+}
+
+/// A single event delivery to a webhook, including its attempt log.
+///
+/// Pending deliveries form the webhook outbox.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    /// Unique identifier for the delivery.
+    pub delivery_id: DeliveryId,
+    /// Webhook the delivery is addressed to.
+    pub webhook_id: WebhookId,
+    /// Event being delivered.
+    pub event: WebhookEvent,
+    /// JSON request body, signed as-is.
+    pub payload: String,
+    /// Current delivery status.
+    pub status: DeliveryStatus,
+    /// Attempts made so far, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attempts: Vec<DeliveryAttempt>,
+    /// When the next attempt is due (pending deliveries only).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+}
+
+/// Status of a webhook delivery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Waiting for its first or next attempt.
+    Pending,
+    /// The endpoint acknowledged the delivery with a 2xx response.
+    Succeeded,
+    /// All attempts failed.
+    Failed,
+}
+
+/// Record of a single delivery attempt.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryAttempt {
+    /// When the attempt was made.
+    pub attempted_at: DateTime<Utc>,
+    /// HTTP status returned by the endpoint, if a response was received.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_code: Option<u16>,
+    /// Transport error or non-success detail.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// How long the attempt took, in milliseconds.
+    pub duration_ms: u64,
+}
+
+/// An entry in the append-only audit log.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// Unique identifier for the event.
+    pub event_id: AuditEventId,
+    /// When the event occurred.
+    pub timestamp: DateTime<Utc>,
+    /// Event type, e.g. `agent.created` or `auth.failure`.
+    pub event_type: String,
+    /// Who performed the action.
+    pub actor: AuditActor,
+    /// Namespace the action was performed in, if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<NamespaceId>,
+    /// Resource the action applied to, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<AuditResource>,
+    /// Action performed, e.g. `create` or `start`.
+    pub action: String,
+    /// Whether the action succeeded.
+    pub outcome: AuditOutcome,
+    /// Request ID, for correlating with gateway logs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    /// Additional event-specific details.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub details: BTreeMap<String, String>,
+}
+
+/// The actor behind an audit event.
+///
+/// Events raised by the control plane itself (schedules, reapers) have no
+/// user or network identity.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditActor {
+    /// Internal user ID.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<UserId>,
+    /// Zero-ID identity.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_id: Option<IdentityId>,
+    /// Source IP address.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    /// Client user agent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+}
+
+impl AuditActor {
+    /// The control plane acting on its own behalf.
+    #[must_use]
+    pub fn system() -> Self {
+        Self::default()
+    }
+
+    /// Whether the event was raised by the control plane itself.
+    #[must_use]
+    pub const fn is_system(&self) -> bool {
+        self.user_id.is_none() && self.identity_id.is_none() && self.ip_address.is_none()
+    }
+}
+
+/// The resource an audit event applies to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditResource {
+    /// Resource type, e.g. `agent` or `session`.
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    /// Resource identifier.
+    pub id: String,
+}
+
+/// Outcome of an audited action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// The action completed.
+    Success,
+    /// The action was attempted but failed.
+    Failure,
+    /// The action was rejected by authentication or authorization.
+    Denied,
+}
+
+/// Filter for querying the audit log.
+#[derive(Debug, Clone, Default)]
+pub struct AuditQuery {
+    /// Only events at or after this time.
+    pub from: Option<DateTime<Utc>>,
+    /// Only events before this time.
+    pub to: Option<DateTime<Utc>>,
+    /// Only events performed by this user.
+    pub user_id: Option<UserId>,
+    /// Only events in this namespace.
+    pub namespace_id: Option<NamespaceId>,
+    /// Maximum number of events to return (`0` for no limit).
+    pub limit: usize,
+}
+
+impl AuditQuery {
+    /// Whether an event matches the actor and namespace filters.
+    ///
+    /// The time range is applied by the store's key scan.
+    #[must_use]
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        self.user_id
+            .is_none_or(|id| event.actor.user_id == Some(id))
+            && self
+                .namespace_id
+                .is_none_or(|id| event.namespace_id == Some(id))
+    }
+}
+
+/// Agent runtime accumulated in one hourly bucket.
+///
+/// Buckets are keyed by hour, namespace, owner and agent state. Resource
+/// usage is weighted by the agent's requested CPU and memory.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// Start of the hour this bucket covers.
+    pub hour: DateTime<Utc>,
+    /// Namespace of the metered agents.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<NamespaceId>,
+    /// Owner of the metered agents.
+    pub user_id: UserId,
+    /// State the agents were in.
+    pub state: AgentState,
+    /// Agent-seconds spent in the state.
+    pub agent_seconds: u64,
+    /// Agent-seconds weighted by requested CPU, in millicore-seconds.
+    pub cpu_millicore_seconds: u64,
+    /// Agent-seconds weighted by requested memory, in megabyte-seconds.
+    pub memory_mb_seconds: u64,
+}
+
+impl UsageRecord {
+    /// Add another record's totals to this one.
+    pub fn add(&mut self, other: &Self) {
+        self.agent_seconds = self.agent_seconds.saturating_add(other.agent_seconds);
+        self.cpu_millicore_seconds = self
+            .cpu_millicore_seconds
+            .saturating_add(other.cpu_millicore_seconds);
+        self.memory_mb_seconds = self
+            .memory_mb_seconds
+            .saturating_add(other.memory_mb_seconds);
+    }
+}
+
+/// A fleet-wide runtime version rollout.
+///
+/// The agents a rollout targets are fixed when it is created. The control
+/// plane's rollout controller then moves them to the target version in
+/// batches, restarting agents that have a pod and waiting for them to become
+/// ready before starting the next batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rollout {
+    /// Unique identifier for the rollout.
+    pub rollout_id: RolloutId,
+    /// Runtime version agents are moved to.
+    pub target_version: String,
+    /// Which agents the rollout was created for.
+    pub selector: RolloutSelector,
+    /// How quickly the rollout proceeds and when it gives up.
+    pub strategy: RolloutStrategy,
+    /// Current status.
+    pub status: RolloutStatus,
+    /// Every targeted agent, in the order they are updated.
+    pub targets: Vec<RolloutTarget>,
+    /// When the next batch may start, while pausing between batches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_batch_at: Option<DateTime<Utc>>,
+    /// Why the rollout stopped early, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Administrator who started the rollout.
+    pub created_by: IdentityId,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Rollout {
+    /// Number of targets in the given state.
+    #[must_use]
+    pub fn count(&self, state: RolloutTargetState) -> usize {
+        self.targets.iter().filter(|t| t.state == state).count()
+    }
+}
+
+/// Selects the agents a rollout applies to.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RolloutSelector {
+    /// Only agents currently on this runtime version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime_version: Option<String>,
+    /// Only agents carrying all of these labels.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+}
+
+/// Pacing and failure handling for a rollout.
+///
+/// Unset fields take their defaults when deserialized.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RolloutStrategy {
+    /// Agents updated at once.
+    pub max_unavailable: u32,
+    /// Pause between batches (seconds).
+    pub pause_seconds: u64,
+    /// Failed agents tolerated before the rollout halts.
+    pub max_failures: u32,
+    /// How long a restarted agent may take to become ready (seconds).
+    pub ready_timeout_seconds: u64,
+    /// Whether a halted rollout moves updated agents back to their previous version.
+    pub rollback_on_failure: bool,
+}
+
+impl Default for RolloutStrategy {
+    fn default() -> Self {
+        Self {
+            max_unavailable: 1,
+            pause_seconds: 60,
+            max_failures: 0,
+            ready_timeout_seconds: 300, // 5 minutes
+            rollback_on_failure: false,
+        }
+    }
+}
+
+/// Status of a rollout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloutStatus {
+    /// Batches are still being updated.
+    InProgress,
+    /// Every target was updated.
+    Completed,
+    /// Too many targets failed; remaining targets were left alone.
+    Halted,
+    /// Updated targets were moved back to their previous version.
+    RolledBack,
+    /// An administrator stopped the rollout.
+    Cancelled,
+}
+
+/// One agent targeted by a rollout.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RolloutTarget {
+    /// The targeted agent.
+    pub agent_id: AgentId,
+    /// Runtime version the agent was on before the rollout.
+    pub previous_version: String,
+    /// Progress of the update.
+    pub state: RolloutTargetState,
+    /// Whether the agent's pod was replaced to apply the update.
+    #[serde(default)]
+    pub restarted: bool,
+    /// When the agent was updated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    /// Why the update failed or was skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Progress of a single agent's update within a rollout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RolloutTargetState {
+    /// Not updated yet.
+    Pending,
+    /// Updated and restarted; waiting for the pod to become ready.
+    Updating,
+    /// Running the target version (or will on its next start).
+    Succeeded,
+    /// The restarted pod did not become ready.
+    Failed,
+    /// The agent was deleted before it was updated.
+    Skipped,
+    /// Moved back to its previous version.
+    RolledBack,
+}
+
+/// A tracked, resumable agent deletion.
+///
+/// Deleting an agent cleans up everything it owns one step at a time, in
+/// [`DeletionStep::ALL`] order. Finished steps are recorded, so a failed
+/// deletion resumes where it stopped. The agent record itself is removed by
+/// the last step; the deletion record is kept for a retention period
+/// afterwards.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentDeletion {
+    /// The agent being deleted.
+    pub agent_id: AgentId,
+    /// The agent's owner.
+    pub user_id: UserId,
+    /// The agent's namespace.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<NamespaceId>,
+    /// User who requested the deletion.
+    pub requested_by: UserId,
+    /// Current status.
+    pub status: DeletionStatus,
+    /// Steps that have finished, in order.
+    pub completed_steps: Vec<DeletionStep>,
+    /// The step that failed on the last attempt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failed_step: Option<DeletionStep>,
+    /// Why the last attempt failed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Number of attempts so far.
+    pub attempts: u32,
+    /// When the deletion is next picked up by the background worker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AgentDeletion {
+    /// The first step that has not finished yet, if any.
+    #[must_use]
+    pub fn next_step(&self) -> Option<DeletionStep> {
+        DeletionStep::ALL
+            .into_iter()
+            .find(|step| !self.completed_steps.contains(step))
+    }
+}
+
+/// Status of an agent deletion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletionStatus {
+    /// Steps are being run.
+    InProgress,
+    /// A step failed; the deletion is retried later.
+    Failed,
+    /// Every step finished and the agent record is gone.
+    Completed,
+}
+
+/// One step of an agent deletion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeletionStep {
+    /// Close the agent's active sessions and delete all its session records.
+    PurgeSessions,
+    /// Terminate the agent's pod.
+    TerminatePod,
+    /// Remove the agent's state volume, or its directory on the shared state
+    /// volume, and evict its cached endpoint.
+    DeleteState,
+    /// Delete the agent's Kubernetes Secret.
+    DeleteSecrets,
+    /// Delete the agent's Kubernetes `NetworkPolicy`.
+    DeleteNetworkPolicy,
+    /// Delete the agent's schedule and record, with its grants and stored secrets.
+    DeleteRecord,
+}
+
+impl DeletionStep {
+    /// Every step, in the order they run.
+    pub const ALL: [Self; 6] = [
+        Self::PurgeSessions,
+        Self::TerminatePod,
+        Self::DeleteState,
+        Self::DeleteSecrets,
+        Self::DeleteNetworkPolicy,
+        Self::DeleteRecord,
+    ];
+}
+
+/// A pending copy of an agent's state into its clone.
+///
+/// Cloning records the copy and returns at once; the control plane's clone
+/// worker copies the state and then schedules the clone's pod. The record is
+/// removed once the copy has succeeded or failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateCopy {
+    /// The clone the state is copied into.
+    pub agent_id: AgentId,
+    /// The agent whose state is copied.
+    pub source_id: AgentId,
+    /// Number of attempts so far.
+    pub attempts: u32,
+    /// When the copy is next picked up by the clone worker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// Creation timestamp.
+    pub created_at: DateTime<Utc>,
+}
@@ -100,6 +100,8 @@ load_secret() {
 ANTHROPIC_API_KEY=$(load_secret "ANTHROPIC_API_KEY")
 OPENAI_API_KEY=$(load_secret "OPENAI_API_KEY")
 ZERO_ID_SECRET=$(load_secret "ZERO_ID_SECRET")
+# Hex-encoded 32-byte key for per-agent secrets (optional; e.g. `openssl rand -hex 32`)
+AGENT_SECRETS_KEY=$(load_secret "AGENT_SECRETS_KEY")
 
 # Validate required secrets
 MISSING_SECRETS=()
@@ -145,6 +147,7 @@ cp "$SECRETS_YAML" "$SECRETS_YAML_TMP"
 sed -i "s|__ANTHROPIC_API_KEY__|${ANTHROPIC_API_KEY}|g" "$SECRETS_YAML_TMP"
 sed -i "s|__OPENAI_API_KEY__|${OPENAI_API_KEY:-placeholder-not-set}|g" "$SECRETS_YAML_TMP"
 sed -i "s|__ZERO_ID_SECRET__|${ZERO_ID_SECRET:-placeholder-not-set}|g" "$SECRETS_YAML_TMP"
+sed -i "s|__AGENT_SECRETS_KEY__|${AGENT_SECRETS_KEY}|g" "$SECRETS_YAML_TMP"
 sed -i "s|__DEFAULT_ISOLATION__|${DEFAULT_ISOLATION}|g" "$SECRETS_YAML_TMP"
 
 # Update deployments with ECR image URLs